@@ -14,8 +14,9 @@ use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncSeek};
 
 use self::{query::query, record::read_record};
 use crate::{
+    bai,
     reader::{bytes_with_nul_to_string, resolve_region},
-    Record, MAGIC_NUMBER,
+    Error, Record, MAGIC_NUMBER,
 };
 
 /// An async BAM reader.
@@ -313,6 +314,84 @@ where
         let chunks = index.query(reference_sequence_id, interval)?;
         Ok(query(self, chunks, reference_sequence_id, interval))
     }
+
+    /// Returns a stream of unmapped records after querying for the unmapped region.
+    ///
+    /// This is the same as querying the region `*` using samtools, i.e., only unplaced, unmapped
+    /// records are returned. See [`crate::Reader::query_unmapped`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>>{
+    /// use futures::TryStreamExt;
+    /// use noodles_bam::{self as bam, bai};
+    /// use tokio::fs::File;
+    ///
+    /// let mut reader = File::open("sample.bam").await.map(bam::AsyncReader::new)?;
+    /// let index = bai::r#async::read("sample.bam.bai").await?;
+    /// let mut query = reader.query_unmapped(&index).await?;
+    ///
+    /// while let Some(record) = query.try_next().await? {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_unmapped(
+        &mut self,
+        index: &bai::Index,
+    ) -> io::Result<impl Stream<Item = io::Result<Record>> + '_> {
+        let last_mapped_record_end_position = index
+            .reference_sequences()
+            .iter()
+            .rev()
+            .find_map(|reference_sequence| reference_sequence.metadata())
+            .map(|metadata| metadata.end_position());
+
+        if let Some(pos) = last_mapped_record_end_position
+            .or_else(|| index.first_record_in_last_linear_bin_start_position())
+        {
+            self.seek(pos).await?;
+        } else {
+            self.seek_to_first_record().await?;
+        }
+
+        let remaining = index.unplaced_unmapped_record_count();
+
+        Ok(Box::pin(stream::try_unfold(
+            (&mut self.inner, Record::default(), remaining),
+            |(mut reader, mut record, mut remaining)| async move {
+                if remaining == Some(0) {
+                    return Ok(None);
+                }
+
+                loop {
+                    if read_record(&mut reader, &mut record).await? == 0 {
+                        return Ok(None);
+                    }
+
+                    if record.reference_sequence_id().is_none() {
+                        if let Some(n) = remaining.as_mut() {
+                            *n -= 1;
+                        }
+
+                        return Ok(Some((record.clone(), (reader, record, remaining))));
+                    }
+                }
+            },
+        )))
+    }
+
+    // Seeks to the first record by setting the cursor to the beginning of the stream and
+    // (re)reading the header and binary reference sequences.
+    async fn seek_to_first_record(&mut self) -> io::Result<bgzf::VirtualPosition> {
+        self.seek(bgzf::VirtualPosition::default()).await?;
+        self.read_header().await?;
+        self.read_reference_sequences().await?;
+        Ok(self.virtual_position())
+    }
 }
 
 async fn read_magic<R>(reader: &mut R) -> io::Result<()>
@@ -325,10 +404,7 @@ where
     if magic == MAGIC_NUMBER {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "invalid BAM header",
-        ))
+        Err(Error::InvalidMagicNumber(magic).into())
     }
 }
 
@@ -345,9 +421,8 @@ where
 
     // § 4.2 The BAM format (2021-06-03): "Plain header text in SAM; not necessarily
     // NUL-terminated".
-    bytes_with_nul_to_string(&text).or_else(|_| {
-        String::from_utf8(text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-    })
+    bytes_with_nul_to_string(&text)
+        .or_else(|_| String::from_utf8(text).map_err(|e| Error::InvalidHeaderText(e).into()))
 }
 
 async fn read_reference_sequences<R>(reader: &mut R) -> io::Result<ReferenceSequences>
@@ -360,8 +435,13 @@ where
 
     let mut reference_sequences = ReferenceSequences::with_capacity(n_ref);
 
-    for _ in 0..n_ref {
-        let reference_sequence = read_reference_sequence(reader).await?;
+    for i in 0..n_ref {
+        let reference_sequence = read_reference_sequence(reader).await.map_err(|e| {
+            io::Error::from(Error::InvalidReferenceSequence {
+                index: i,
+                source: e,
+            })
+        })?;
         let name = reference_sequence.name().to_string();
         reference_sequences.insert(name, reference_sequence);
     }
@@ -461,4 +541,63 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_query_unmapped() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        use futures::TryStreamExt;
+        use sam::{
+            header::ReferenceSequence,
+            record::{Flags, Position},
+        };
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .build();
+
+        let sam_records = [
+            sam::Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_name("sq0".parse()?)
+                .set_position(Position::try_from(2)?)
+                .set_cigar("4M".parse()?)
+                .build()?,
+            // placed, unmapped
+            sam::Record::builder()
+                .set_read_name("r1".parse()?)
+                .set_flags(Flags::UNMAPPED)
+                .set_reference_sequence_name("sq0".parse()?)
+                .set_position(Position::try_from(2)?)
+                .build()?,
+            sam::Record::builder()
+                .set_read_name("r2".parse()?)
+                .build()?,
+        ];
+
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        for sam_record in &sam_records {
+            let record = Record::try_from_sam_record(header.reference_sequences(), sam_record)?;
+            writer.write_record(&record)?;
+        }
+
+        let data = writer.into_inner().finish()?;
+        let index = bai::index(&data[..])?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        let records: Vec<_> = reader.query_unmapped(&index).await?.try_collect().await?;
+
+        let read_names: Vec<_> = records
+            .iter()
+            .map(|record| record.read_name().map(|name| name.to_string_lossy()))
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(read_names, ["r2"]);
+
+        Ok(())
+    }
 }
@@ -31,6 +31,7 @@ where
     end: i32,
 
     state: State,
+    record: Record,
 }
 
 pub fn query<R, B>(
@@ -56,6 +57,7 @@ where
         end,
 
         state: State::Seek,
+        record: Record::default(),
     };
 
     Box::pin(stream::try_unfold(ctx, |mut ctx| async {
@@ -70,32 +72,22 @@ where
                         None => State::Done,
                     };
                 }
-                State::Read(chunk_end) => match next_record(ctx.reader).await? {
-                    Some(record) => {
+                State::Read(chunk_end) => match ctx.reader.read_record(&mut ctx.record).await? {
+                    0 => ctx.state = State::Seek,
+                    _ => {
                         if ctx.reader.virtual_position() >= chunk_end {
                             ctx.state = State::Seek;
                         }
 
-                        if intersects(&record, ctx.reference_sequence_id, ctx.start, ctx.end)? {
+                        // Only records that intersect the region are cloned.
+                        if intersects(&ctx.record, ctx.reference_sequence_id, ctx.start, ctx.end)? {
+                            let record = ctx.record.clone();
                             return Ok(Some((record, ctx)));
                         }
                     }
-                    None => ctx.state = State::Seek,
                 },
                 State::Done => return Ok(None),
             }
         }
     }))
 }
-
-async fn next_record<R>(reader: &mut Reader<R>) -> io::Result<Option<Record>>
-where
-    R: AsyncRead + AsyncSeek + Unpin,
-{
-    let mut record = Record::default();
-
-    reader.read_record(&mut record).await.map(|n| match n {
-        0 => None,
-        _ => Some(record),
-    })
-}
@@ -2,13 +2,12 @@
 
 pub mod data;
 
-use std::mem;
-
 use noodles_sam as sam;
 use tokio::io::{self, AsyncRead, AsyncReadExt};
 
 use crate::{
-    record::{Cigar, Data, QualityScores, ReferenceSequenceId, Sequence},
+    reader::record::data_len,
+    record::{long_cigar, Cigar, Data, QualityScores, ReferenceSequenceId, Sequence},
     Record,
 };
 
@@ -47,19 +46,15 @@ where
 
     *record.template_length_mut() = reader.read_i32_le().await?;
 
+    let data_len = data_len(block_size, l_read_name, n_cigar_op, l_seq)?;
+
     read_read_name(reader, &mut record.read_name, l_read_name).await?;
     read_cigar(reader, record.cigar_mut(), n_cigar_op).await?;
     read_seq(reader, record.sequence_mut(), l_seq).await?;
     read_qual(reader, record.quality_scores_mut(), l_seq).await?;
-    read_data(
-        reader,
-        record.data_mut(),
-        block_size,
-        l_read_name,
-        n_cigar_op,
-        l_seq,
-    )
-    .await?;
+    read_data(reader, record.data_mut(), data_len).await?;
+
+    long_cigar::resolve(record)?;
 
     Ok(block_size)
 }
@@ -157,22 +152,10 @@ where
     Ok(())
 }
 
-async fn read_data<R>(
-    reader: &mut R,
-    data: &mut Data,
-    block_size: usize,
-    l_read_name: usize,
-    n_cigar_op: usize,
-    l_seq: usize,
-) -> io::Result<()>
+async fn read_data<R>(reader: &mut R, data: &mut Data, data_len: usize) -> io::Result<()>
 where
     R: AsyncRead + Unpin,
 {
-    let cigar_len = mem::size_of::<u32>() * n_cigar_op;
-    let seq_len = (l_seq + 1) / 2;
-    let data_offset = 32 + l_read_name + cigar_len + seq_len + l_seq;
-    let data_len = block_size - data_offset;
-
     let buf = data.as_mut();
     buf.resize(data_len, Default::default());
     reader.read_exact(buf).await?;
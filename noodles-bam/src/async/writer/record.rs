@@ -1,3 +1,5 @@
+use std::mem;
+
 use noodles_sam as sam;
 use tokio::io::{self, AsyncWrite, AsyncWriteExt};
 
@@ -11,8 +13,23 @@ pub(super) async fn write_record<W>(writer: &mut W, record: &Record) -> io::Resu
 where
     W: AsyncWrite + Unpin,
 {
-    let block_size = u32::try_from(record.block_size())
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    use crate::record::long_cigar::encode_record_cigar;
+
+    // A CIGAR with too many operations is replaced by a placeholder and stored in the `CG` data
+    // field.
+    let long_cigar = encode_record_cigar(record)?;
+
+    let (cigar, cigar_data): (&Cigar, &[u8]) = match &long_cigar {
+        Some((placeholder, cigar_data)) => (placeholder, cigar_data),
+        None => (record.cigar(), &[]),
+    };
+
+    let block_size = record.block_size() - mem::size_of::<u32>() * record.cigar().len()
+        + mem::size_of::<u32>() * cigar.len()
+        + cigar_data.len();
+
+    let block_size =
+        u32::try_from(block_size).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
     writer.write_u32_le(block_size).await?;
 
     // ref_id
@@ -30,8 +47,8 @@ where
 
     writer.write_u16_le(record.bin()).await?;
 
-    let n_cigar_op = u16::try_from(record.cigar().len())
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let n_cigar_op =
+        u16::try_from(cigar.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
     writer.write_u16_le(n_cigar_op).await?;
 
     let flag = u16::from(record.flags());
@@ -51,7 +68,7 @@ where
 
     writer.write_all(&record.read_name).await?;
 
-    write_cigar(writer, record.cigar()).await?;
+    write_cigar(writer, cigar).await?;
 
     let sequence = record.sequence();
     let quality_scores = record.quality_scores();
@@ -76,6 +93,7 @@ where
     }
 
     writer.write_all(record.data().as_ref()).await?;
+    writer.write_all(cigar_data).await?;
 
     Ok(())
 }
@@ -128,6 +146,36 @@ where
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_write_record_with_long_cigar() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::record::cigar::op::Kind;
+
+        use crate::record::{cigar::Op, long_cigar::MAX_CIGAR_OP_COUNT, sequence::Base, Sequence};
+
+        let n = MAX_CIGAR_OP_COUNT + 1;
+
+        let record = Record::builder()
+            .set_cigar(Cigar::from(vec![Op::new(Kind::Match, 1)?; n]))
+            .set_sequence(Sequence::from(vec![Base::A; n]))
+            .build()?;
+
+        let mut actual = Vec::new();
+        write_record(&mut actual, &record).await?;
+
+        let mut expected = Vec::new();
+        crate::writer::record::write_record(&mut expected, &record)?;
+
+        assert_eq!(actual, expected);
+
+        let mut src = &actual[..];
+        let mut actual_record = Record::default();
+        crate::reader::record::read_record(&mut src, &mut Vec::new(), &mut actual_record)?;
+        assert_eq!(actual_record.cigar(), record.cigar());
+        assert!(actual_record.data().is_empty());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_write_record_with_default_fields() -> Result<(), Box<dyn std::error::Error>> {
         let mut buf = Vec::new();
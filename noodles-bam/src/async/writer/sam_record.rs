@@ -15,7 +15,10 @@ pub async fn write_sam_record<W>(
 where
     W: AsyncWrite + Unpin,
 {
-    use crate::writer::sam_record::calculate_data_len;
+    use crate::{
+        record::long_cigar::encode_sam_record_cigar,
+        writer::sam_record::{calculate_block_size, calculate_data_len},
+    };
 
     let name = record
         .read_name()
@@ -28,22 +31,28 @@ where
     let l_read_name = u8::try_from(read_name.len())
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
 
-    let n_cigar_op = u16::try_from(record.cigar().len())
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    // A CIGAR with too many operations is replaced by a placeholder and stored in the `CG` data
+    // field.
+    let long_cigar = encode_sam_record_cigar(record)?;
+
+    let (n_cigar_op, cigar_data) = match &long_cigar {
+        Some((placeholder, cigar_data)) => (placeholder.len(), &cigar_data[..]),
+        None => (record.cigar().len(), &[][..]),
+    };
+
+    let n_cigar_op =
+        u16::try_from(n_cigar_op).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
 
     let l_seq = u32::try_from(record.sequence().len())
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
 
-    let data_len = calculate_data_len(record.data()).and_then(|len| {
-        u32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
-    })?;
+    let data_len = calculate_data_len(record.data())
+        .map(|len| len + cigar_data.len())
+        .and_then(|len| {
+            u32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+        })?;
 
-    let block_size = 32
-        + u32::from(l_read_name)
-        + (4 * u32::from(n_cigar_op))
-        + ((l_seq + 1) / 2)
-        + l_seq
-        + data_len;
+    let block_size = calculate_block_size(l_read_name, n_cigar_op, l_seq, data_len)?;
     writer.write_u32_le(block_size).await?;
 
     // ref_id
@@ -93,7 +102,14 @@ where
     writer.write_all(read_name).await?;
 
     // cigar
-    write_cigar(writer, record.cigar()).await?;
+    match &long_cigar {
+        Some((placeholder, _)) => {
+            for &raw_op in placeholder.as_ref() {
+                writer.write_u32_le(raw_op).await?;
+            }
+        }
+        None => write_cigar(writer, record.cigar()).await?,
+    }
 
     let sequence = record.sequence();
 
@@ -121,6 +137,7 @@ where
     }
 
     write_data(writer, record.data()).await?;
+    writer.write_all(cigar_data).await?;
 
     Ok(())
 }
@@ -469,6 +486,44 @@ where
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_write_sam_record_with_long_cigar() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::{
+            header::ReferenceSequence,
+            record::{
+                cigar::{op::Kind, Op},
+                Cigar, Flags, Position,
+            },
+        };
+
+        use crate::record::long_cigar::MAX_CIGAR_OP_COUNT;
+
+        let n = MAX_CIGAR_OP_COUNT + 1;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 1 << 20)?)
+            .build();
+        let reference_sequences = header.reference_sequences();
+
+        let record = sam::Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::try_from(1)?)
+            .set_cigar(Cigar::from(vec![Op::new(Kind::Match, 1); n]))
+            .set_sequence("A".repeat(n).parse()?)
+            .build()?;
+
+        let mut actual = Vec::new();
+        write_sam_record(&mut actual, reference_sequences, &record).await?;
+
+        let mut expected = Vec::new();
+        crate::writer::sam_record::write_sam_record(&mut expected, reference_sequences, &record)?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_write_reference_sequence_id() -> Result<(), Box<dyn std::error::Error>> {
         use sam::header::{reference_sequence, ReferenceSequence};
@@ -22,6 +22,16 @@
 //! let index = bai::read("sample.bam.bai")?;
 //! # Ok::<(), io::Error>(())
 //! ```
+//!
+//! ## Building a BAM index
+//!
+//! ```no_run
+//! # use std::io;
+//! use noodles_bam::{self as bam, bai};
+//! let index = bam::index("sample.bam")?;
+//! bai::write("sample.bam.bai", &index)?;
+//! # Ok::<(), io::Error>(())
+//! ```
 
 #[cfg(feature = "async")]
 pub mod r#async;
@@ -35,9 +45,16 @@ pub use self::{index::Index, reader::Reader, writer::Writer};
 #[cfg(feature = "async")]
 pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
 
-use std::{fs::File, io, path::Path};
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+use noodles_csi::index::reference_sequence::bin::Chunk;
 
 use self::index::reference_sequence::Bin;
+use crate::Record;
 
 static MAGIC_NUMBER: &[u8] = b"BAI\x01";
 
@@ -63,6 +80,79 @@ where
     reader.read_index()
 }
 
+/// Builds a BAM index from a BAM stream.
+///
+/// The stream is expected to be at the start of a coordinate-sorted BAM. The header and reference
+/// sequences are read, and all records are added to the index. This returns an error if the
+/// records are not coordinate-sorted.
+///
+/// See [`crate::index`] to index a BAM file.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam::{self as bam, bai};
+/// use noodles_csi::BinningIndex;
+/// use noodles_sam as sam;
+///
+/// let header = sam::Header::default();
+/// let mut writer = bam::Writer::new(Vec::new());
+/// writer.write_header(&header)?;
+/// writer.write_reference_sequences(header.reference_sequences())?;
+/// writer.write_record(&bam::Record::default())?;
+/// let data = writer.into_inner().finish()?;
+///
+/// let index = bai::index(&data[..])?;
+/// assert!(index.reference_sequences().is_empty());
+/// assert_eq!(index.unplaced_unmapped_record_count(), Some(1));
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn index<R>(reader: R) -> io::Result<Index>
+where
+    R: Read,
+{
+    let mut reader = crate::Reader::new(reader);
+    reader.read_header()?;
+    let reference_sequences = reader.read_reference_sequences()?;
+
+    let mut builder = Index::builder();
+    let mut record = Record::default();
+    let mut start_position = reader.virtual_position();
+    let mut last_sort_key = (i32::MIN, i32::MIN);
+
+    while reader.read_record(&mut record)? != 0 {
+        let sort_key = coordinate_sort_key(&record);
+
+        if sort_key < last_sort_key {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the input BAM must be coordinate-sorted to be indexed",
+            ));
+        }
+
+        last_sort_key = sort_key;
+
+        let end_position = reader.virtual_position();
+        builder.add_record(&record, Chunk::new(start_position, end_position))?;
+        start_position = end_position;
+    }
+
+    Ok(builder.build(reference_sequences.len()))
+}
+
+// Returns the reference sequence ID and position of a record.
+//
+// Records with a reference sequence ID but no position sort first on their reference sequence.
+// Unplaced records sort last.
+fn coordinate_sort_key(record: &Record) -> (i32, i32) {
+    match (record.reference_sequence_id(), record.position()) {
+        (Some(id), Some(position)) => (i32::from(id), i32::from(position)),
+        (Some(id), None) => (i32::from(id), 0),
+        (None, _) => (i32::MAX, i32::MAX),
+    }
+}
+
 /// Writes a BAM index to a file.
 ///
 /// This is a convenience function and is equivalent to creating a file at the given path, writing
@@ -85,3 +175,95 @@ where
     writer.write_header()?;
     writer.write_index(index)
 }
+
+#[cfg(test)]
+mod tests {
+    use noodles_csi::BinningIndex;
+    use noodles_sam::{
+        self as sam,
+        header::ReferenceSequence,
+        record::{Flags, Position},
+    };
+
+    use super::*;
+
+    fn build_data(positions: &[i32]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 34)?)
+            .build();
+
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        for &position in positions {
+            let sam_record = sam::Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_name("sq0".parse()?)
+                .set_position(Position::try_from(position)?)
+                .set_cigar("4M".parse()?)
+                .build()?;
+            let record = Record::try_from_sam_record(header.reference_sequences(), &sam_record)?;
+            writer.write_record(&record)?;
+        }
+
+        writer.write_record(&Record::default())?;
+
+        Ok(writer.into_inner().finish()?)
+    }
+
+    #[test]
+    fn test_index() -> Result<(), Box<dyn std::error::Error>> {
+        let data = build_data(&[2, 8, 13])?;
+        let actual = index(&data[..])?;
+
+        assert_eq!(actual.reference_sequences().len(), 1);
+        assert_eq!(actual.unplaced_unmapped_record_count(), Some(1));
+
+        let data = build_data(&[8, 2])?;
+        assert!(matches!(
+            index(&data[..]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_with_placed_unmapped_record() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::record::cigar::op::Kind;
+
+        use crate::record::{cigar::Op, Cigar, ReferenceSequenceId};
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 8)?)
+            .build();
+
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        writer.write_record(
+            &Record::builder()
+                .set_reference_sequence_id(ReferenceSequenceId::try_from(0)?)
+                .build()?,
+        )?;
+
+        writer.write_record(
+            &Record::builder()
+                .set_reference_sequence_id(ReferenceSequenceId::try_from(1)?)
+                .set_position(Position::try_from(2)?)
+                .set_flags(Flags::empty())
+                .set_cigar(Cigar::from(vec![Op::new(Kind::Match, 4)?]))
+                .build()?,
+        )?;
+
+        let data = writer.into_inner().finish()?;
+        let actual = index(&data[..])?;
+
+        assert_eq!(actual.reference_sequences().len(), 2);
+
+        Ok(())
+    }
+}
@@ -82,10 +82,12 @@ impl Builder {
     /// let index = bai::Index::builder().build(1);
     /// ```
     pub fn build(mut self, reference_sequence_count: usize) -> Index {
-        let last_reference_sequence_id =
-            ReferenceSequenceId::try_from((reference_sequence_count - 1) as i32)
-                .expect("invalid reference sequence count");
-        self.add_reference_sequences_builders_until(last_reference_sequence_id);
+        if let Some(last_reference_sequence_id) = reference_sequence_count.checked_sub(1) {
+            let last_reference_sequence_id =
+                ReferenceSequenceId::try_from(last_reference_sequence_id as i32)
+                    .expect("invalid reference sequence count");
+            self.add_reference_sequences_builders_until(last_reference_sequence_id);
+        }
 
         let reference_sequences = self
             .reference_sequences_builders
@@ -155,6 +157,10 @@ mod tests {
         assert_eq!(index.reference_sequences().len(), 2);
         assert_eq!(index.unplaced_unmapped_record_count(), Some(1));
 
+        let index = Builder::default().build(0);
+        assert!(index.reference_sequences().is_empty());
+        assert_eq!(index.unplaced_unmapped_record_count(), Some(0));
+
         Ok(())
     }
 }
@@ -3,9 +3,9 @@ use std::{cmp, collections::HashMap, io};
 use noodles_bgzf as bgzf;
 use noodles_csi::index::reference_sequence::bin::Chunk;
 
-use crate::Record;
+use crate::{record::alignment_end, Record};
 
-use super::{bin, Bin, Metadata, ReferenceSequence, WINDOW_SIZE};
+use super::{bin, Bin, Metadata, ReferenceSequence, MAX_POSITION, WINDOW_SIZE};
 
 // § 5.2 The BAI index format for BAM files (2020-07-19)
 const MAX_INTERVAL_COUNT: usize = 131072;
@@ -22,8 +22,8 @@ pub struct Builder {
 
 impl Builder {
     pub fn add_record(&mut self, record: &Record, chunk: Chunk) -> io::Result<()> {
-        self.update_bins(record, chunk);
         self.update_linear_index(record, chunk)?;
+        self.update_bins(record, chunk);
         self.update_metadata(record, chunk);
         Ok(())
     }
@@ -69,8 +69,19 @@ impl Builder {
 
     fn update_linear_index(&mut self, record: &Record, chunk: Chunk) -> io::Result<()> {
         let start = record.position().map(i32::from).expect("missing position");
-        let reference_len = record.cigar().reference_len().map(|len| len as i32)?;
-        let end = start + reference_len - 1;
+        let end = alignment_end(record, start)?;
+
+        // Positions past the BAI limit cannot be binned. Long reference sequences require a CSI,
+        // which can be built with a larger depth.
+        if end > MAX_POSITION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "record end position ({}) exceeds the BAI maximum ({}); use a CSI instead",
+                    end, MAX_POSITION
+                ),
+            ));
+        }
 
         let linear_index_start_offset = ((start - 1) / WINDOW_SIZE) as usize;
         let linear_index_end_offset = ((end - 1) / WINDOW_SIZE) as usize;
@@ -192,6 +203,66 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_add_record_with_end_position_past_max_position(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use sam::header::reference_sequence;
+
+        let reference_sequences = [("sq0".parse()?, i32::MAX)]
+            .into_iter()
+            .map(|(name, len): (reference_sequence::Name, i32)| {
+                let sn = name.to_string();
+                sam::header::ReferenceSequence::new(name, len).map(|rs| (sn, rs))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let record = Record::try_from_sam_record(
+            &reference_sequences,
+            &sam::Record::builder()
+                .set_flags(Flags::empty())
+                .set_position(Position::try_from(MAX_POSITION)?)
+                .set_cigar("2M".parse()?)
+                .build()?,
+        )?;
+
+        let mut builder = Builder::default();
+        let chunk = Chunk::new(
+            bgzf::VirtualPosition::from(55),
+            bgzf::VirtualPosition::from(89),
+        );
+
+        assert!(matches!(
+            builder.add_record(&record, chunk),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+        assert_eq!(builder.build(), ReferenceSequence::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_record_with_end_overflow() {
+        use crate::record::Cigar;
+
+        let mut record = Record::default();
+        *record.flags_mut() = Flags::empty();
+        record.pos = i32::MAX - 2; // position = 2147483646
+        *record.cigar_mut() = Cigar::from(vec![0x40]); // 4M
+
+        let result = Builder::default().add_record(
+            &record,
+            Chunk::new(
+                bgzf::VirtualPosition::from(55),
+                bgzf::VirtualPosition::from(89),
+            ),
+        );
+
+        assert!(matches!(
+            result,
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
     #[test]
     fn test_build_with_no_bins() {
         let reference_sequence = Builder::default().build();
@@ -0,0 +1,216 @@
+//! BAM errors.
+
+use std::{error, fmt, io, string};
+
+/// An error returned when BAM data fails to be read.
+///
+/// Readers continue to return [`std::io::Error`]s, but for invalid data, the error is wrapped in
+/// an `io::Error` with the kind [`std::io::ErrorKind::InvalidData`]. The cause can be inspected by
+/// downcasting the inner error.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Write};
+/// use noodles_bam as bam;
+/// use noodles_bgzf as bgzf;
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"MThd")?;
+/// let data = writer.finish()?;
+///
+/// let mut reader = bam::Reader::new(&data[..]);
+///
+/// let e = reader.read_header().unwrap_err();
+///
+/// assert!(matches!(
+///     e.get_ref().and_then(|e| e.downcast_ref::<bam::Error>()),
+///     Some(bam::Error::InvalidMagicNumber(_))
+/// ));
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The magic number is invalid.
+    InvalidMagicNumber([u8; 4]),
+    /// The header text is invalid.
+    InvalidHeaderText(string::FromUtf8Error),
+    /// A reference sequence is invalid.
+    InvalidReferenceSequence {
+        /// The index of the reference sequence.
+        index: usize,
+        /// The cause.
+        source: io::Error,
+    },
+    /// A reference sequence in the header text does not match the binary reference sequence at
+    /// the same index.
+    ///
+    /// This includes a difference in the number of reference sequences, in which case the index
+    /// is that of the first unmatched reference sequence.
+    ReferenceSequenceMismatch {
+        /// The index of the reference sequence.
+        index: usize,
+    },
+    /// A record is invalid.
+    InvalidRecord {
+        /// The 0-based number of the record from where reading started, if known.
+        record_number: Option<u64>,
+        /// The byte offset within the record data where decoding failed.
+        offset: usize,
+        /// The cause.
+        source: io::Error,
+    },
+    /// A record is larger than the maximum record size.
+    ///
+    /// See [`crate::reader::Builder::set_max_record_size`].
+    RecordTooLarge {
+        /// The record size (`block_size`).
+        size: usize,
+        /// The maximum record size.
+        max: usize,
+    },
+    /// A record has more CIGAR operations than the maximum CIGAR operation count.
+    ///
+    /// See [`crate::reader::Builder::set_max_cigar_op_count`].
+    TooManyCigarOps {
+        /// The number of CIGAR operations.
+        count: usize,
+        /// The maximum number of CIGAR operations.
+        max: usize,
+    },
+    /// A record has more data fields than the maximum data field count.
+    ///
+    /// See [`crate::reader::Builder::set_max_data_field_count`].
+    TooManyDataFields {
+        /// The number of data fields.
+        count: usize,
+        /// The maximum number of data fields.
+        max: usize,
+    },
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidMagicNumber(_) => None,
+            Self::InvalidHeaderText(e) => Some(e),
+            Self::InvalidReferenceSequence { source, .. } => Some(source),
+            Self::ReferenceSequenceMismatch { .. } => None,
+            Self::InvalidRecord { source, .. } => Some(source),
+            Self::RecordTooLarge { .. } => None,
+            Self::TooManyCigarOps { .. } => None,
+            Self::TooManyDataFields { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagicNumber(actual) => {
+                write!(
+                    f,
+                    "invalid magic number: expected BAM\\x01, got {:?}",
+                    actual
+                )
+            }
+            Self::InvalidHeaderText(_) => f.write_str("invalid header text"),
+            Self::InvalidReferenceSequence { index, .. } => {
+                write!(f, "invalid reference sequence at index {}", index)
+            }
+            Self::ReferenceSequenceMismatch { index } => write!(
+                f,
+                "reference sequence mismatch at index {}: header text and binary reference sequences differ",
+                index
+            ),
+            Self::InvalidRecord {
+                record_number,
+                offset,
+                ..
+            } => {
+                f.write_str("invalid record")?;
+
+                if let Some(n) = record_number {
+                    write!(f, " {}", n)?;
+                }
+
+                write!(f, " at offset {}", offset)
+            }
+            Self::RecordTooLarge { size, max } => {
+                write!(f, "record too large: expected <= {}, got {}", max, size)
+            }
+            Self::TooManyCigarOps { count, max } => write!(
+                f,
+                "too many CIGAR operations: expected <= {}, got {}",
+                max, count
+            ),
+            Self::TooManyDataFields { count, max } => {
+                write!(f, "too many data fields: expected <= {}, got {}", max, count)
+            }
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        Self::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(
+            Error::InvalidMagicNumber(*b"MThd").to_string(),
+            "invalid magic number: expected BAM\\x01, got [77, 84, 104, 100]"
+        );
+
+        assert_eq!(
+            Error::ReferenceSequenceMismatch { index: 1 }.to_string(),
+            "reference sequence mismatch at index 1: header text and binary reference sequences differ"
+        );
+
+        let error = Error::InvalidRecord {
+            record_number: Some(8),
+            offset: 13,
+            source: io::Error::from(io::ErrorKind::UnexpectedEof),
+        };
+        assert_eq!(error.to_string(), "invalid record 8 at offset 13");
+
+        let error = Error::InvalidRecord {
+            record_number: None,
+            offset: 13,
+            source: io::Error::from(io::ErrorKind::UnexpectedEof),
+        };
+        assert_eq!(error.to_string(), "invalid record at offset 13");
+
+        assert_eq!(
+            Error::RecordTooLarge { size: 21, max: 8 }.to_string(),
+            "record too large: expected <= 8, got 21"
+        );
+
+        assert_eq!(
+            Error::TooManyCigarOps { count: 21, max: 8 }.to_string(),
+            "too many CIGAR operations: expected <= 8, got 21"
+        );
+
+        assert_eq!(
+            Error::TooManyDataFields { count: 21, max: 8 }.to_string(),
+            "too many data fields: expected <= 8, got 21"
+        );
+    }
+
+    #[test]
+    fn test_from_error_for_io_error() {
+        let e = io::Error::from(Error::InvalidMagicNumber(*b"MThd"));
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(
+            e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+            Some(Error::InvalidMagicNumber(_))
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+//! Indexed BAM reader.
+
+use std::{
+    io::{self, Read, Seek},
+    ops::RangeInclusive,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_sam::{self as sam, AlignmentReader, AlignmentRecord};
+
+use super::{bai, Reader};
+
+/// An indexed BAM reader.
+///
+/// This wraps a BAM reader and its associated index. As a [`sam::AlignmentReader`], queries use
+/// the index to only read the records that may intersect the region.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bam::{self as bam, bai};
+/// use noodles_sam::AlignmentReader;
+///
+/// let reader = File::open("sample.bam").map(bam::Reader::new)?;
+/// let index = bai::read("sample.bam.bai")?;
+///
+/// let mut reader: Box<dyn AlignmentReader> = Box::new(bam::IndexedReader::new(reader, index));
+/// let header = reader.read_alignment_header()?;
+///
+/// for result in reader.query_alignment_records(&header, "sq0", 17711..=28657)? {
+///     let record = result?;
+///     println!("{:?}", record.read_name());
+/// }
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct IndexedReader<R> {
+    inner: Reader<bgzf::Reader<R>>,
+    index: bai::Index,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read,
+{
+    /// Creates an indexed BAM reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, bai};
+    /// let reader = bam::Reader::new(&[][..]);
+    /// let reader = bam::IndexedReader::new(reader, bai::Index::default());
+    /// ```
+    pub fn new(inner: Reader<bgzf::Reader<R>>, index: bai::Index) -> Self {
+        Self { inner, index }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Reader<bgzf::Reader<R>> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut Reader<bgzf::Reader<R>> {
+        &mut self.inner
+    }
+
+    /// Returns the index.
+    pub fn index(&self) -> &bai::Index {
+        &self.index
+    }
+
+    /// Returns the underlying reader and index.
+    pub fn into_parts(self) -> (Reader<bgzf::Reader<R>>, bai::Index) {
+        (self.inner, self.index)
+    }
+}
+
+impl<R> AlignmentReader for IndexedReader<R>
+where
+    R: Read + Seek,
+{
+    fn read_alignment_header(&mut self) -> io::Result<sam::Header> {
+        self.inner.read_alignment_header()
+    }
+
+    fn alignment_records<'a>(&'a mut self, header: &'a sam::Header) -> sam::AlignmentRecords<'a> {
+        self.inner.alignment_records(header)
+    }
+
+    fn query_alignment_records<'a>(
+        &'a mut self,
+        header: &'a sam::Header,
+        reference_sequence_name: &'a str,
+        interval: RangeInclusive<i32>,
+    ) -> io::Result<sam::AlignmentRecords<'a>> {
+        let region = Region::mapped(reference_sequence_name, interval);
+        let query = self
+            .inner
+            .query(header.reference_sequences(), &self.index, &region)?;
+
+        Ok(Box::new(query.map(|result| {
+            result.map(|record| Box::new(record) as Box<dyn AlignmentRecord>)
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_csi::index::reference_sequence::bin::Chunk;
+    use sam::{
+        header::ReferenceSequence,
+        record::{Flags, Position},
+    };
+
+    use super::*;
+    use crate::Record;
+
+    #[test]
+    fn test_query_alignment_records() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 34)?)
+            .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 34)?)
+            .build();
+
+        let sam_records = [
+            ("r0", "sq0", 1),
+            ("r1", "sq0", 8),
+            ("r2", "sq1", 8),
+            ("r3", "sq1", 21),
+        ]
+        .into_iter()
+        .map(|(read_name, reference_sequence_name, position)| {
+            sam::Record::builder()
+                .set_read_name(read_name.parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_name(reference_sequence_name.parse()?)
+                .set_position(Position::try_from(position)?)
+                .set_cigar("4M".parse()?)
+                .build()
+                .map_err(Box::<dyn std::error::Error>::from)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        for sam_record in &sam_records {
+            let record = Record::try_from_sam_record(header.reference_sequences(), sam_record)?;
+            writer.write_record(&record)?;
+        }
+
+        let data = writer.into_inner().finish()?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut builder = bai::Index::builder();
+        let mut record = Record::default();
+        let mut start = reader.virtual_position();
+
+        while reader.read_record(&mut record)? != 0 {
+            let end = reader.virtual_position();
+            builder.add_record(&record, Chunk::new(start, end))?;
+            start = end;
+        }
+
+        let index = builder.build(header.reference_sequences().len());
+
+        let reader = Reader::new(Cursor::new(&data));
+        let mut reader: Box<dyn AlignmentReader> = Box::new(IndexedReader::new(reader, index));
+        let header = reader.read_alignment_header()?;
+
+        let records: Vec<_> = reader
+            .query_alignment_records(&header, "sq1", 5..=10)?
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].read_name(), Some(&b"r2"[..]));
+
+        assert!(reader
+            .query_alignment_records(&header, "sq2", 5..=10)
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,499 @@
+//! Lazily-evaluated BAM record.
+//!
+//! A [`lazy::Record`](Record) holds the encoded fields of a BAM record in a single shared buffer
+//! ([`Bytes`]) with the offsets of its variable-length fields. Fields are decoded on access.
+//!
+//! Reading a lazy record makes one allocation for the whole record rather than one per field,
+//! and cloning one only increments a reference count, making it cheap to send to other threads.
+//! Lazy records are immutable. To modify one, convert it to a [`bam::Record`](crate::Record),
+//! which copies its fields.
+//!
+//! A lazy record is an addition to, not a replacement for, [`bam::Record`](crate::Record), which
+//! still allocates its fields separately. Backing [`bam::Record`](crate::Record) itself with a
+//! shared buffer and copy-on-write mutation, and an equivalent record for BCF, are not
+//! implemented.
+
+use std::{
+    ffi::{self, CStr},
+    fmt, io, mem,
+    ops::Range,
+};
+
+use byteorder::{ByteOrder, LittleEndian};
+use bytes::Bytes;
+use noodles_sam as sam;
+
+use crate::record::{long_cigar, reference_sequence_id, ReferenceSequenceId, UNMAPPED_POSITION};
+
+const FIXED_FIELDS_LEN: usize = 32;
+
+// ref_id = -1, pos = -1, l_read_name = 2, mapq = 255, bin = 4680, n_cigar_op = 0, flag = 4,
+// l_seq = 0, next_ref_id = -1, next_pos = -1, tlen = 0, read_name = "*\0"
+static DEFAULT_BUF: &[u8] = &[
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02, 0xff, 0x48, 0x12, 0x00, 0x00, 0x04, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00,
+    b'*', 0x00,
+];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Bounds {
+    read_name_end: usize,
+    cigar_end: usize,
+    sequence_end: usize,
+    quality_scores_end: usize,
+    // The range of the raw CIGAR operations in the `CG` data field, if the CIGAR field holds a
+    // placeholder.
+    long_cigar: Option<Range<usize>>,
+}
+
+/// A lazily-evaluated BAM record.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam::{self as bam, lazy};
+///
+/// let record = bam::Record::default();
+/// let lazy_record = lazy::Record::try_from(&record)?;
+///
+/// let clone = lazy_record.clone();
+/// assert_eq!(clone.as_ref().as_ptr(), lazy_record.as_ref().as_ptr());
+///
+/// assert_eq!(bam::Record::try_from(lazy_record)?, record);
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Clone, Eq, PartialEq)]
+pub struct Record {
+    buf: Bytes,
+    bounds: Bounds,
+}
+
+impl Record {
+    /// Returns the reference sequence ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.reference_sequence_id()?.is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn reference_sequence_id(&self) -> io::Result<Option<ReferenceSequenceId>> {
+        decode_reference_sequence_id(&self.buf[0..4])
+    }
+
+    /// Returns the position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.position()?.is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn position(&self) -> io::Result<Option<sam::record::Position>> {
+        decode_position(&self.buf[4..8])
+    }
+
+    /// Returns the mapping quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.mapping_quality().is_none());
+    /// ```
+    pub fn mapping_quality(&self) -> Option<sam::record::MappingQuality> {
+        sam::record::MappingQuality::try_from(self.buf[9]).ok()
+    }
+
+    /// Returns the index bin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert_eq!(record.bin(), 4680);
+    /// ```
+    pub fn bin(&self) -> u16 {
+        LittleEndian::read_u16(&self.buf[10..12])
+    }
+
+    /// Returns the flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// use noodles_sam as sam;
+    /// let record = lazy::Record::default();
+    /// assert_eq!(record.flags(), sam::record::Flags::UNMAPPED);
+    /// ```
+    pub fn flags(&self) -> sam::record::Flags {
+        sam::record::Flags::from(LittleEndian::read_u16(&self.buf[14..16]))
+    }
+
+    /// Returns the mate reference sequence ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.mate_reference_sequence_id()?.is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn mate_reference_sequence_id(&self) -> io::Result<Option<ReferenceSequenceId>> {
+        decode_reference_sequence_id(&self.buf[20..24])
+    }
+
+    /// Returns the mate position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.mate_position()?.is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn mate_position(&self) -> io::Result<Option<sam::record::Position>> {
+        decode_position(&self.buf[24..28])
+    }
+
+    /// Returns the template length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert_eq!(record.template_length(), 0);
+    /// ```
+    pub fn template_length(&self) -> i32 {
+        LittleEndian::read_i32(&self.buf[28..32])
+    }
+
+    /// Returns the read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert_eq!(record.read_name()?.to_bytes(), b"*");
+    /// # Ok::<(), std::ffi::FromBytesWithNulError>(())
+    /// ```
+    pub fn read_name(&self) -> Result<&CStr, ffi::FromBytesWithNulError> {
+        CStr::from_bytes_with_nul(&self.buf[FIXED_FIELDS_LEN..self.bounds.read_name_end])
+    }
+
+    /// Returns the raw CIGAR operations.
+    ///
+    /// Each operation is a little-endian `u32`.
+    ///
+    /// If the CIGAR field holds a placeholder (`kSmN`) for a CIGAR with too many operations, this
+    /// returns the operations stored in the `CG` data field instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.cigar().is_empty());
+    /// ```
+    pub fn cigar(&self) -> &[u8] {
+        match &self.bounds.long_cigar {
+            Some(range) => &self.data()[range.clone()],
+            None => &self.buf[self.bounds.read_name_end..self.bounds.cigar_end],
+        }
+    }
+
+    /// Returns the raw sequence.
+    ///
+    /// Bases are packed as 4-bit values, two per byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.sequence().is_empty());
+    /// ```
+    pub fn sequence(&self) -> &[u8] {
+        &self.buf[self.bounds.cigar_end..self.bounds.sequence_end]
+    }
+
+    /// Returns the raw quality scores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.quality_scores().is_empty());
+    /// ```
+    pub fn quality_scores(&self) -> &[u8] {
+        &self.buf[self.bounds.sequence_end..self.bounds.quality_scores_end]
+    }
+
+    /// Returns the raw data.
+    ///
+    /// Unlike [`bam::Record`](crate::Record), this includes the `CG` data field of a record with
+    /// a long CIGAR.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.data().is_empty());
+    /// ```
+    pub fn data(&self) -> &[u8] {
+        &self.buf[self.bounds.quality_scores_end..]
+    }
+}
+
+impl AsRef<[u8]> for Record {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl fmt::Debug for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Record")
+            .field("reference_sequence_id", &self.reference_sequence_id())
+            .field("position", &self.position())
+            .field("mapping_quality", &self.mapping_quality())
+            .field("bin", &self.bin())
+            .field("flags", &self.flags())
+            .field(
+                "mate_reference_sequence_id",
+                &self.mate_reference_sequence_id(),
+            )
+            .field("mate_position", &self.mate_position())
+            .field("template_length", &self.template_length())
+            .field("read_name", &self.read_name())
+            .field("cigar", &self.cigar())
+            .field("sequence", &self.sequence())
+            .field("quality_scores", &self.quality_scores())
+            .field("data", &self.data())
+            .finish()
+    }
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        Self::try_from(Bytes::from_static(DEFAULT_BUF)).expect("invalid default record")
+    }
+}
+
+impl TryFrom<Bytes> for Record {
+    type Error = io::Error;
+
+    /// Creates a lazy record from an encoded BAM record, excluding its block size.
+    fn try_from(buf: Bytes) -> Result<Self, Self::Error> {
+        fn invalid_field_lengths() -> io::Error {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid record field lengths: fields exceed the block size",
+            )
+        }
+
+        if buf.len() < FIXED_FIELDS_LEN {
+            return Err(invalid_field_lengths());
+        }
+
+        let l_read_name = usize::from(buf[8]);
+        let n_cigar_op = usize::from(LittleEndian::read_u16(&buf[12..14]));
+        let l_seq = usize::try_from(LittleEndian::read_u32(&buf[16..20]))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let read_name_end = FIXED_FIELDS_LEN + l_read_name;
+        let cigar_end = read_name_end + mem::size_of::<u32>() * n_cigar_op;
+
+        let mut bounds = l_seq
+            .checked_add(1)
+            .map(|n| n / 2)
+            .and_then(|seq_len| cigar_end.checked_add(seq_len))
+            .and_then(|sequence_end| {
+                sequence_end
+                    .checked_add(l_seq)
+                    .map(|quality_scores_end| Bounds {
+                        read_name_end,
+                        cigar_end,
+                        sequence_end,
+                        quality_scores_end,
+                        long_cigar: None,
+                    })
+            })
+            .filter(|bounds| bounds.quality_scores_end <= buf.len())
+            .ok_or_else(invalid_field_lengths)?;
+
+        bounds.long_cigar = long_cigar::find_raw_cigar(
+            &buf[read_name_end..cigar_end],
+            l_seq,
+            &buf[bounds.quality_scores_end..],
+        )?;
+
+        Ok(Self { buf, bounds })
+    }
+}
+
+impl TryFrom<&crate::Record> for Record {
+    type Error = io::Error;
+
+    fn try_from(record: &crate::Record) -> Result<Self, Self::Error> {
+        let mut buf = Vec::with_capacity(mem::size_of::<u32>() + record.block_size());
+        crate::writer::record::write_record(&mut buf, record)?;
+        buf.drain(..mem::size_of::<u32>());
+        Self::try_from(Bytes::from(buf))
+    }
+}
+
+impl TryFrom<Record> for crate::Record {
+    type Error = io::Error;
+
+    fn try_from(lazy_record: Record) -> Result<Self, Self::Error> {
+        let mut record = Self::default();
+        let mut src = &lazy_record.buf[..];
+        crate::reader::record::decode_record(&mut src, &mut record, lazy_record.buf.len())?;
+        Ok(record)
+    }
+}
+
+fn decode_reference_sequence_id(buf: &[u8]) -> io::Result<Option<ReferenceSequenceId>> {
+    match LittleEndian::read_i32(buf) {
+        reference_sequence_id::UNMAPPED => Ok(None),
+        n => ReferenceSequenceId::try_from(n)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+    }
+}
+
+fn decode_position(buf: &[u8]) -> io::Result<Option<sam::record::Position>> {
+    match LittleEndian::read_i32(buf) {
+        UNMAPPED_POSITION => Ok(None),
+        n => n
+            .checked_add(1)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid position: {}", n),
+                )
+            })
+            .and_then(|m| {
+                sam::record::Position::try_from(m)
+                    .map(Some)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sam::record::{Flags, MappingQuality, Position};
+
+    use super::*;
+
+    fn build_record() -> Result<crate::Record, Box<dyn std::error::Error>> {
+        let reference_sequences = [("sq0", 8), ("sq1", 13)]
+            .into_iter()
+            .map(|(name, len)| {
+                sam::header::ReferenceSequence::new(name.parse()?, len)
+                    .map(|rs| (name.to_string(), rs))
+                    .map_err(|e| e.into())
+            })
+            .collect::<Result<_, Box<dyn std::error::Error>>>()?;
+
+        let sam_record =
+            "r0\t99\tsq1\t5\t13\t2M1I1M\tsq0\t8\t-3\tACGT\tNDLS\tNH:i:1".parse::<sam::Record>()?;
+
+        crate::Record::try_from_sam_record(&reference_sequences, &sam_record).map_err(|e| e.into())
+    }
+
+    #[test]
+    fn test_default() -> io::Result<()> {
+        let record = crate::Record::try_from(Record::default())?;
+        assert_eq!(record, crate::Record::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let record = build_record()?;
+        let lazy_record = Record::try_from(&record)?;
+
+        assert_eq!(
+            lazy_record.reference_sequence_id()?,
+            record.reference_sequence_id()
+        );
+        assert_eq!(lazy_record.position()?, Position::try_from(5).map(Some)?);
+        assert_eq!(
+            lazy_record.mapping_quality(),
+            MappingQuality::try_from(13).map(Some)?
+        );
+        assert_eq!(lazy_record.bin(), record.bin());
+        assert_eq!(lazy_record.flags(), Flags::from(99));
+        assert_eq!(
+            lazy_record.mate_reference_sequence_id()?,
+            record.mate_reference_sequence_id()
+        );
+        assert_eq!(
+            lazy_record.mate_position()?,
+            Position::try_from(8).map(Some)?
+        );
+        assert_eq!(lazy_record.template_length(), -3);
+        assert_eq!(lazy_record.read_name()?.to_bytes(), b"r0");
+        assert_eq!(lazy_record.cigar().len(), 3 * mem::size_of::<u32>());
+        assert_eq!(lazy_record.sequence(), record.sequence().as_ref());
+        assert_eq!(
+            lazy_record.quality_scores(),
+            record.quality_scores().as_ref()
+        );
+        assert_eq!(lazy_record.data(), record.data().as_ref());
+
+        assert_eq!(crate::Record::try_from(lazy_record)?, record);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_bytes_with_invalid_field_lengths() {
+        assert!(Record::try_from(Bytes::from_static(&[0; 8])).is_err());
+
+        let mut buf = DEFAULT_BUF.to_vec();
+        buf[16] = 8; // l_seq
+        assert!(Record::try_from(Bytes::from(buf)).is_err());
+    }
+
+    #[test]
+    fn test_position_with_invalid_position() -> io::Result<()> {
+        let mut buf = DEFAULT_BUF.to_vec();
+        buf[4..8].copy_from_slice(&i32::MAX.to_le_bytes()); // pos
+        buf[24..28].copy_from_slice(&(-2i32).to_le_bytes()); // next_pos
+        let record = Record::try_from(Bytes::from(buf))?;
+
+        assert!(matches!(
+            record.position(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            record.mate_position(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -53,13 +53,42 @@
 mod r#async;
 
 pub mod bai;
+mod error;
+mod indexed_reader;
+pub mod lazy;
 pub mod reader;
 pub mod record;
-mod writer;
+#[cfg(feature = "serde")]
+mod serde;
+pub mod writer;
 
-pub use self::{reader::Reader, record::Record, writer::Writer};
+pub use self::{
+    error::Error, indexed_reader::IndexedReader, reader::Reader, record::Record, writer::Writer,
+};
 
 #[cfg(feature = "async")]
 pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
 
+use std::{fs::File, io, path::Path};
+
 static MAGIC_NUMBER: &[u8] = b"BAM\x01";
+
+/// Indexes a BAM file.
+///
+/// The BAM must be coordinate-sorted. This is a convenience function and is equivalent to opening
+/// the file at the given path and building the index using [`bai::index`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bam as bam;
+/// let index = bam::index("sample.bam")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index<P>(src: P) -> io::Result<bai::Index>
+where
+    P: AsRef<Path>,
+{
+    File::open(src).and_then(bai::index)
+}
@@ -1,25 +1,38 @@
 //! BAM reader and iterators.
 
+mod builder;
+mod checkpoint;
+mod lazy_records;
 pub(crate) mod query;
 pub mod record;
 mod records;
 mod unmapped_records;
 
-pub use self::{query::Query, records::Records, unmapped_records::UnmappedRecords};
+pub use self::{
+    builder::Builder, checkpoint::Checkpoint, lazy_records::LazyRecords, query::Query,
+    records::Records, unmapped_records::UnmappedRecords,
+};
 
 use std::{
     ffi::CStr,
+    hash::Hasher,
     io::{self, Read, Seek},
+    sync::Arc,
 };
 
 use byteorder::{LittleEndian, ReadBytesExt};
+use bytes::BytesMut;
 use noodles_bgzf as bgzf;
-use noodles_core::{region::Interval, Region};
+use noodles_core::{region::Interval, Fingerprint, Region};
 use noodles_csi::{binning_index::ReferenceSequenceExt, BinningIndex};
-use noodles_sam::header::{ReferenceSequence, ReferenceSequences};
+use noodles_sam::{
+    self as sam,
+    header::{ReferenceSequence, ReferenceSequences},
+    AlignmentReader, AlignmentRecord,
+};
 
-use self::record::read_record;
-use super::{bai, Record, MAGIC_NUMBER};
+use self::record::{read_lazy_record_with_max_size, read_record_with_limits, Limits};
+use super::{bai, lazy, Error, Record, MAGIC_NUMBER};
 
 /// A BAM reader.
 ///
@@ -53,6 +66,10 @@ use super::{bai, Record, MAGIC_NUMBER};
 pub struct Reader<R> {
     inner: R,
     buf: Vec<u8>,
+    lazy_buf: BytesMut,
+    limits: Limits,
+    observer: Option<Arc<dyn bgzf::Observer>>,
+    header_fingerprint: Fingerprint,
 }
 
 impl<R> Reader<R>
@@ -121,7 +138,13 @@ where
     /// ```
     pub fn read_header(&mut self) -> io::Result<String> {
         read_magic(&mut self.inner)?;
-        read_header(&mut self.inner)
+
+        let header = read_header(&mut self.inner)?;
+
+        self.header_fingerprint = Fingerprint::default();
+        self.header_fingerprint.write(header.as_bytes());
+
+        Ok(header)
     }
 
     /// Reads the binary reference sequences after the SAM header.
@@ -146,7 +169,57 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn read_reference_sequences(&mut self) -> io::Result<ReferenceSequences> {
-        read_reference_sequences(&mut self.inner)
+        let reference_sequences = read_reference_sequences(&mut self.inner)?;
+
+        for (name, reference_sequence) in &reference_sequences {
+            self.header_fingerprint.write(name.as_bytes());
+            self.header_fingerprint
+                .write(&reference_sequence.len().to_le_bytes());
+        }
+
+        Ok(reference_sequences)
+    }
+
+    /// Reads the SAM header and binary reference sequences and checks that they agree.
+    ///
+    /// The position of the stream is expected to be at the start.
+    ///
+    /// The header text is parsed as a SAM header. If it has no reference sequences, the binary
+    /// reference sequences are used. Otherwise, the reference sequences in the header text must
+    /// have the same names and lengths, in the same order, as the binary reference sequences, or
+    /// this returns an [`Error::ReferenceSequenceMismatch`].
+    ///
+    /// [`sam::AlignmentReader::read_alignment_header`] does not check for mismatches and uses the
+    /// binary reference sequences instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam as bam;
+    /// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+    /// let header = reader.read_sam_header()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_sam_header(&mut self) -> io::Result<sam::Header> {
+        match self.read_reconciled_header()? {
+            (header, None) => Ok(header),
+            (_, Some(index)) => Err(Error::ReferenceSequenceMismatch { index }.into()),
+        }
+    }
+
+    // Reads the SAM header and binary reference sequences and returns the header with the index of
+    // the first mismatched reference sequence, if any.
+    fn read_reconciled_header(&mut self) -> io::Result<(sam::Header, Option<usize>)> {
+        let mut header: sam::Header = self
+            .read_header()?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let reference_sequences = self.read_reference_sequences()?;
+        let mismatch = reconcile_reference_sequences(&mut header, reference_sequences);
+
+        Ok((header, mismatch))
     }
 
     /// Reads a single record.
@@ -164,6 +237,11 @@ where
     /// If successful, the record block size is returned. If a block size of 0 is returned, the
     /// stream reached EOF.
     ///
+    /// If a maximum record size is set (see [`Builder::set_max_record_size`]), records with a
+    /// larger block size fail with [`Error::RecordTooLarge`]. Likewise, records that exceed the
+    /// maximum CIGAR operation count ([`Builder::set_max_cigar_op_count`]) or data field count
+    /// ([`Builder::set_max_data_field_count`]) fail with [`Error::InvalidRecord`].
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -180,7 +258,61 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn read_record(&mut self, record: &mut Record) -> io::Result<usize> {
-        read_record(&mut self.inner, &mut self.buf, record)
+        let block_size =
+            read_record_with_limits(&mut self.inner, &mut self.buf, record, &self.limits)?;
+
+        if block_size > 0 {
+            if let Some(observer) = self.observer.as_ref() {
+                observer.on_record();
+            }
+        }
+
+        Ok(block_size)
+    }
+
+    /// Reads a single record into a lazy record.
+    ///
+    /// Unlike [`Self::read_record`], the fields of the record are not decoded. The record is
+    /// replaced by one that holds the encoded fields in a shared buffer, so it can be cheaply
+    /// cloned or sent to another thread.
+    ///
+    /// Records are read into chunks of memory that are shared by consecutive records, which
+    /// avoids an allocation per record. Note that a chunk is only freed once all records in it are
+    /// dropped.
+    ///
+    /// If successful, the record block size is returned. If a block size of 0 is returned, the
+    /// stream reached EOF.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam::{self as bam, lazy};
+    ///
+    /// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+    /// reader.read_header()?;
+    /// reader.read_reference_sequences()?;
+    ///
+    /// let mut record = lazy::Record::default();
+    /// reader.read_lazy_record(&mut record)?;
+    ///
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_lazy_record(&mut self, record: &mut lazy::Record) -> io::Result<usize> {
+        let block_size = read_lazy_record_with_max_size(
+            &mut self.inner,
+            &mut self.lazy_buf,
+            record,
+            self.limits.max_record_size,
+        )?;
+
+        if block_size > 0 {
+            if let Some(observer) = self.observer.as_ref() {
+                observer.on_record();
+            }
+        }
+
+        Ok(block_size)
     }
 
     /// Returns an iterator over records starting from the current stream position.
@@ -207,6 +339,38 @@ where
     pub fn records(&mut self) -> Records<'_, R> {
         Records::new(self)
     }
+
+    /// Returns an iterator over lazy records starting from the current stream position.
+    ///
+    /// The stream is expected to be directly after the reference sequences or at the start of
+    /// another record. See [`Self::read_lazy_record`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam as bam;
+    ///
+    /// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+    /// reader.read_header()?;
+    /// reader.read_reference_sequences()?;
+    ///
+    /// let mut n = 0;
+    ///
+    /// for result in reader.lazy_records() {
+    ///     let record = result?;
+    ///
+    ///     if record.flags().is_unmapped() {
+    ///         n += 1;
+    ///     }
+    /// }
+    ///
+    /// println!("{}", n);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn lazy_records(&mut self) -> LazyRecords<'_, R> {
+        LazyRecords::new(self)
+    }
 }
 
 impl<R> Reader<bgzf::Reader<R>>
@@ -228,6 +392,22 @@ where
         Self::from(bgzf::Reader::new(reader))
     }
 
+    /// Creates a BAM reader builder.
+    ///
+    /// The given reader must be a raw BGZF stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let data = [];
+    /// let builder = bam::Reader::builder(&data[..]);
+    /// let reader = builder.build();
+    /// ```
+    pub fn builder(reader: R) -> Builder<R> {
+        Builder::new(reader)
+    }
+
     /// Returns the current virtual position of the underlying BGZF reader.
     ///
     /// # Examples
@@ -247,6 +427,36 @@ where
     pub fn virtual_position(&self) -> bgzf::VirtualPosition {
         self.inner.virtual_position()
     }
+
+    /// Returns a checkpoint at the current position.
+    ///
+    /// The checkpoint holds the current virtual position and a fingerprint of the SAM header and
+    /// reference sequences read by this reader. It should be taken after reading the header and
+    /// reference sequences and between records, e.g., after [`Self::read_record`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = bam::Writer::new(Vec::new());
+    /// writer.write_header(&sam::Header::default())?;
+    /// writer.write_reference_sequences(&Default::default())?;
+    /// let data = writer.into_inner().finish()?;
+    ///
+    /// let mut reader = bam::Reader::new(&data[..]);
+    /// reader.read_header()?;
+    /// reader.read_reference_sequences()?;
+    ///
+    /// let checkpoint = reader.checkpoint();
+    /// assert_eq!(checkpoint.virtual_position(), reader.virtual_position());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint::new(self.virtual_position(), self.header_fingerprint.finish())
+    }
 }
 
 impl<R> Reader<bgzf::Reader<R>>
@@ -272,6 +482,55 @@ where
         self.inner.seek(pos)
     }
 
+    /// Creates a BAM reader that resumes reading at the given checkpoint.
+    ///
+    /// The given reader must be a raw BGZF stream positioned at the start. The SAM header and
+    /// reference sequences are read and compared to the checkpoint's header fingerprint, and the
+    /// reader is then moved to the checkpoint's virtual position. This fails with
+    /// [`io::ErrorKind::InvalidData`] if the header does not match, i.e., the checkpoint was
+    /// taken from a different file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bam as bam;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = bam::Writer::new(Vec::new());
+    /// writer.write_header(&sam::Header::default())?;
+    /// writer.write_reference_sequences(&Default::default())?;
+    /// writer.write_record(&bam::Record::default())?;
+    /// let data = writer.into_inner().finish()?;
+    ///
+    /// let mut reader = bam::Reader::new(&data[..]);
+    /// reader.read_header()?;
+    /// reader.read_reference_sequences()?;
+    /// let checkpoint = reader.checkpoint();
+    ///
+    /// let mut reader = bam::Reader::resume_at(Cursor::new(data), &checkpoint)?;
+    /// let mut record = bam::Record::default();
+    /// assert!(reader.read_record(&mut record)? > 0);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn resume_at(reader: R, checkpoint: &Checkpoint) -> io::Result<Self> {
+        let mut reader = Self::new(reader);
+
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        if reader.header_fingerprint.finish() != checkpoint.header_fingerprint() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "checkpoint header fingerprint mismatch",
+            ));
+        }
+
+        reader.seek(checkpoint.virtual_position())?;
+
+        Ok(reader)
+    }
+
     // Seeks to the first record by setting the cursor to the beginning of the stream and
     // (re)reading the header and binary reference sequences.
     fn seek_to_first_record(&mut self) -> io::Result<bgzf::VirtualPosition> {
@@ -283,6 +542,9 @@ where
 
     /// Returns an iterator over records that intersect the given region.
     ///
+    /// Only the chunks of the bins that overlap the region are read. Chunks that end before the
+    /// minimum offset of the region start in the linear index are skipped.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -322,6 +584,12 @@ where
 
     /// Returns an iterator of unmapped records after querying for the unmapped region.
     ///
+    /// This is the same as querying the region `*` using samtools, i.e., only unplaced, unmapped
+    /// records are returned. The reader seeks to the end of the last reference sequence with
+    /// metadata (pseudo-bin 37450) in the index, or, if missing, the start of its last linear bin.
+    /// Reading stops after the number of unplaced, unmapped records in the index (`n_no_coor`) is
+    /// reached.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -339,13 +607,25 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn query_unmapped(&mut self, index: &bai::Index) -> io::Result<UnmappedRecords<'_, R>> {
-        if let Some(pos) = index.first_record_in_last_linear_bin_start_position() {
+        let last_mapped_record_end_position = index
+            .reference_sequences()
+            .iter()
+            .rev()
+            .find_map(|reference_sequence| reference_sequence.metadata())
+            .map(|metadata| metadata.end_position());
+
+        if let Some(pos) = last_mapped_record_end_position
+            .or_else(|| index.first_record_in_last_linear_bin_start_position())
+        {
             self.seek(pos)?;
         } else {
             self.seek_to_first_record()?;
         }
 
-        Ok(UnmappedRecords::new(self))
+        Ok(UnmappedRecords::new(
+            self,
+            index.unplaced_unmapped_record_count(),
+        ))
     }
 }
 
@@ -354,10 +634,69 @@ impl<R> From<R> for Reader<R> {
         Self {
             inner,
             buf: Vec::new(),
+            lazy_buf: BytesMut::new(),
+            limits: Limits::default(),
+            observer: None,
+            header_fingerprint: Fingerprint::default(),
         }
     }
 }
 
+impl<R> AlignmentReader for Reader<R>
+where
+    R: Read,
+{
+    fn read_alignment_header(&mut self) -> io::Result<sam::Header> {
+        self.read_reconciled_header().map(|(header, _)| header)
+    }
+
+    fn alignment_records<'a>(&'a mut self, _: &'a sam::Header) -> sam::AlignmentRecords<'a> {
+        Box::new(
+            self.records()
+                .map(|result| result.map(|record| Box::new(record) as Box<dyn AlignmentRecord>)),
+        )
+    }
+}
+
+// Uses the binary reference sequences if the header text has none or if the reference sequences
+// in the header text do not have the same names and lengths, in the same order. The latter returns
+// the index of the first mismatched reference sequence.
+//
+// Like samtools, the binary reference sequences take precedence, since records refer to them by
+// index.
+fn reconcile_reference_sequences(
+    header: &mut sam::Header,
+    reference_sequences: ReferenceSequences,
+) -> Option<usize> {
+    use std::cmp;
+
+    if header.reference_sequences().is_empty() {
+        *header.reference_sequences_mut() = reference_sequences;
+        return None;
+    }
+
+    let len = cmp::max(
+        header.reference_sequences().len(),
+        reference_sequences.len(),
+    );
+
+    let mismatch = (0..len).find(|&i| {
+        !matches!(
+            (
+                header.reference_sequences().get_index(i),
+                reference_sequences.get_index(i),
+            ),
+            (Some((_, a)), Some((_, b))) if a.name() == b.name() && a.len() == b.len()
+        )
+    });
+
+    if mismatch.is_some() {
+        *header.reference_sequences_mut() = reference_sequences;
+    }
+
+    mismatch
+}
+
 fn read_magic<R>(reader: &mut R) -> io::Result<()>
 where
     R: Read,
@@ -368,10 +707,7 @@ where
     if magic == MAGIC_NUMBER {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "invalid BAM header",
-        ))
+        Err(Error::InvalidMagicNumber(magic).into())
     }
 }
 
@@ -383,14 +719,13 @@ where
         usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
 
-    let mut text = vec![0; l_text];
-    reader.read_exact(&mut text)?;
+    let mut text = Vec::new();
+    read_exact_to_vec(reader, &mut text, l_text)?;
 
     // § 4.2 The BAM format (2021-06-03): "Plain header text in SAM; not necessarily
     // NUL-terminated".
-    bytes_with_nul_to_string(&text).or_else(|_| {
-        String::from_utf8(text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-    })
+    bytes_with_nul_to_string(&text)
+        .or_else(|_| String::from_utf8(text).map_err(|e| Error::InvalidHeaderText(e).into()))
 }
 
 fn read_reference_sequences<R>(reader: &mut R) -> io::Result<ReferenceSequences>
@@ -401,10 +736,15 @@ where
         usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
 
-    let mut reference_sequences = ReferenceSequences::with_capacity(n_ref);
+    let mut reference_sequences = ReferenceSequences::default();
 
-    for _ in 0..n_ref {
-        let reference_sequence = read_reference_sequence(reader)?;
+    for i in 0..n_ref {
+        let reference_sequence = read_reference_sequence(reader).map_err(|e| {
+            io::Error::from(Error::InvalidReferenceSequence {
+                index: i,
+                source: e,
+            })
+        })?;
         let name = reference_sequence.name().to_string();
         reference_sequences.insert(name, reference_sequence);
     }
@@ -420,8 +760,8 @@ where
         usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
 
-    let mut c_name = vec![0; l_name];
-    reader.read_exact(&mut c_name)?;
+    let mut c_name = Vec::new();
+    read_exact_to_vec(reader, &mut c_name, l_name)?;
 
     let name = bytes_with_nul_to_string(&c_name).and_then(|name| {
         name.parse()
@@ -435,6 +775,29 @@ where
     ReferenceSequence::new(name, l_ref).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
+// Reads exactly `len` bytes into the given buffer.
+//
+// Unlike resizing the buffer and using `Read::read_exact`, the buffer only grows as data is read,
+// which avoids large allocations when `len` is read from corrupt or malicious input.
+pub(crate) fn read_exact_to_vec<R>(reader: &mut R, buf: &mut Vec<u8>, len: usize) -> io::Result<()>
+where
+    R: Read,
+{
+    buf.clear();
+
+    let limit = u64::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    reader.take(limit).read_to_end(buf)?;
+
+    if buf.len() < len {
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "failed to fill whole buffer",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 pub(crate) fn bytes_with_nul_to_string(buf: &[u8]) -> io::Result<String> {
     CStr::from_bytes_with_nul(buf)
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
@@ -499,6 +862,154 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_lazy_record() -> io::Result<()> {
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_record(&Record::default())?;
+        writer.try_finish()?;
+
+        let data = writer.get_ref().get_ref();
+        let mut reader = Reader::new(&data[..]);
+        let mut record = lazy::Record::default();
+
+        assert_eq!(reader.read_lazy_record(&mut record)?, 34);
+        assert_eq!(Record::try_from(record)?, Record::default());
+
+        let mut record = lazy::Record::default();
+        assert_eq!(reader.read_lazy_record(&mut record)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resume_at() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        use sam::header::ReferenceSequence;
+
+        fn build_data(header: &sam::Header) -> io::Result<Vec<u8>> {
+            let mut writer = crate::Writer::new(Vec::new());
+            writer.write_header(header)?;
+            writer.write_reference_sequences(header.reference_sequences())?;
+
+            for read_name in ["r0", "r1"] {
+                let sam_record = sam::Record::builder()
+                    .set_read_name(read_name.parse().unwrap())
+                    .build()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+                let record =
+                    Record::try_from_sam_record(header.reference_sequences(), &sam_record)?;
+                writer.write_record(&record)?;
+            }
+
+            writer.into_inner().finish()
+        }
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .build();
+        let data = build_data(&header)?;
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut record = Record::default();
+        reader.read_record(&mut record)?;
+        let checkpoint = reader.checkpoint();
+
+        let mut reader = Reader::resume_at(Cursor::new(&data), &checkpoint)?;
+        reader.read_record(&mut record)?;
+        assert_eq!(record.read_name()?.to_str()?, "r1");
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 8)?)
+            .build();
+        let data = build_data(&header)?;
+
+        assert!(matches!(
+            Reader::resume_at(Cursor::new(data), &checkpoint),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_unmapped() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        use noodles_csi::index::reference_sequence::bin::Chunk;
+        use sam::{
+            header::ReferenceSequence,
+            record::{Flags, Position},
+        };
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .build();
+
+        let sam_records = [
+            sam::Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_name("sq0".parse()?)
+                .set_position(Position::try_from(2)?)
+                .set_cigar("4M".parse()?)
+                .build()?,
+            // placed, unmapped
+            sam::Record::builder()
+                .set_read_name("r1".parse()?)
+                .set_flags(Flags::UNMAPPED)
+                .set_reference_sequence_name("sq0".parse()?)
+                .set_position(Position::try_from(2)?)
+                .build()?,
+            sam::Record::builder()
+                .set_read_name("r2".parse()?)
+                .build()?,
+            sam::Record::builder()
+                .set_read_name("r3".parse()?)
+                .build()?,
+        ];
+
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        for sam_record in &sam_records {
+            let record = Record::try_from_sam_record(header.reference_sequences(), sam_record)?;
+            writer.write_record(&record)?;
+        }
+
+        let data = writer.into_inner().finish()?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut builder = bai::Index::builder();
+        let mut record = Record::default();
+        let mut start = reader.virtual_position();
+
+        while reader.read_record(&mut record)? != 0 {
+            let end = reader.virtual_position();
+            builder.add_record(&record, Chunk::new(start, end))?;
+            start = end;
+        }
+
+        let index = builder.build(header.reference_sequences().len());
+
+        let records: Vec<_> = reader.query_unmapped(&index)?.collect::<io::Result<_>>()?;
+        let read_names: Vec<_> = records
+            .iter()
+            .map(|record| record.read_name().map(|name| name.to_string_lossy()))
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(read_names, ["r2", "r3"]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_header() -> io::Result<()> {
         let expected = "@HD\tVN:1.6\n";
@@ -541,4 +1052,80 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_alignment_header() -> io::Result<()> {
+        let mut data = b"BAM\x01".to_vec();
+
+        let text = b"@HD\tVN:1.6\n";
+        data.extend((text.len() as u32).to_le_bytes());
+        data.extend(text);
+
+        data.extend([
+            0x01, 0x00, 0x00, 0x00, // n_ref = 1
+            0x04, 0x00, 0x00, 0x00, // ref[0].l_name = 4
+            0x73, 0x71, 0x30, 0x00, // ref[0].name = "sq0\x00"
+            0x08, 0x00, 0x00, 0x00, // ref[0].l_ref = 8
+        ]);
+
+        let mut reader = Reader::from(&data[..]);
+        let header = reader.read_alignment_header()?;
+
+        assert!(header.header().is_some());
+        assert_eq!(header.reference_sequences().len(), 1);
+        assert!(header.reference_sequences().contains_key("sq0"));
+
+        assert!(reader.alignment_records(&header).next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_sam_header_with_reference_sequence_mismatch() -> io::Result<()> {
+        fn build_data(text: &[u8]) -> Vec<u8> {
+            let mut data = b"BAM\x01".to_vec();
+            data.extend((text.len() as u32).to_le_bytes());
+            data.extend(text);
+            data.extend([
+                0x01, 0x00, 0x00, 0x00, // n_ref = 1
+                0x04, 0x00, 0x00, 0x00, // ref[0].l_name = 4
+                0x73, 0x71, 0x30, 0x00, // ref[0].name = "sq0\x00"
+                0x08, 0x00, 0x00, 0x00, // ref[0].l_ref = 8
+            ]);
+            data
+        }
+
+        let data =
+            build_data(b"@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:8\tM5:d7eba311421bbc9d3ada44709dd61534\n");
+        let mut reader = Reader::from(&data[..]);
+        let header = reader.read_sam_header()?;
+        assert_eq!(header.reference_sequences().len(), 1);
+        assert!(header.reference_sequences()[0].md5_checksum().is_some());
+
+        for text in [
+            &b"@SQ\tSN:sq0\tLN:13\n"[..],
+            b"@SQ\tSN:sq1\tLN:8\n",
+            b"@SQ\tSN:sq0\tLN:8\n@SQ\tSN:sq1\tLN:13\n",
+        ] {
+            let data = build_data(text);
+
+            let mut reader = Reader::from(&data[..]);
+            let e = reader.read_sam_header().unwrap_err();
+
+            assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+            assert!(matches!(
+                e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+                Some(Error::ReferenceSequenceMismatch { .. })
+            ));
+
+            let mut reader = Reader::from(&data[..]);
+            let header = reader.read_alignment_header()?;
+            let reference_sequences = header.reference_sequences();
+            assert_eq!(reference_sequences.len(), 1);
+            assert_eq!(reference_sequences[0].name().as_str(), "sq0");
+            assert_eq!(reference_sequences[0].len(), 8);
+        }
+
+        Ok(())
+    }
 }
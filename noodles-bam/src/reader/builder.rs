@@ -0,0 +1,225 @@
+use std::{io::Read, sync::Arc};
+
+use noodles_bgzf as bgzf;
+
+use super::{record::Limits, Reader};
+
+/// A BAM reader builder.
+pub struct Builder<R> {
+    inner: R,
+    worker_pool: Option<bgzf::WorkerPool>,
+    limits: Limits,
+    observer: Option<Arc<dyn bgzf::Observer>>,
+}
+
+impl<R> Builder<R>
+where
+    R: Read,
+{
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            worker_pool: None,
+            limits: Limits::default(),
+            observer: None,
+        }
+    }
+
+    /// Sets a worker pool.
+    ///
+    /// The worker pool is used to decompress BGZF blocks in parallel. See
+    /// [`bgzf::reader::Builder::set_worker_pool`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(4).unwrap());
+    ///
+    /// let data = [];
+    /// let builder = bam::Reader::builder(&data[..]).set_worker_pool(worker_pool);
+    /// ```
+    pub fn set_worker_pool(mut self, worker_pool: bgzf::WorkerPool) -> Self {
+        self.worker_pool = Some(worker_pool);
+        self
+    }
+
+    /// Sets the maximum record size.
+    ///
+    /// This is the maximum record block size (`block_size`), in bytes. Records that are larger
+    /// fail to read with [`crate::Error::RecordTooLarge`] before any of the record data is
+    /// buffered. This guards against large allocations when reading corrupt or untrusted input.
+    ///
+    /// By default, the record size is not limited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let data = [];
+    /// let builder = bam::Reader::builder(&data[..]).set_max_record_size(1 << 20);
+    /// ```
+    pub fn set_max_record_size(mut self, max_record_size: usize) -> Self {
+        self.limits.max_record_size = Some(max_record_size);
+        self
+    }
+
+    /// Sets the maximum number of CIGAR operations in a record.
+    ///
+    /// This includes CIGARs stored in the `CG` data field. Records with more operations fail to
+    /// read with [`crate::Error::TooManyCigarOps`] as the source of an
+    /// [`crate::Error::InvalidRecord`].
+    ///
+    /// By default, the number of CIGAR operations is not limited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let data = [];
+    /// let builder = bam::Reader::builder(&data[..]).set_max_cigar_op_count(1 << 16);
+    /// ```
+    pub fn set_max_cigar_op_count(mut self, max_cigar_op_count: usize) -> Self {
+        self.limits.max_cigar_op_count = Some(max_cigar_op_count);
+        self
+    }
+
+    /// Sets the maximum number of data fields in a record.
+    ///
+    /// Records with more data fields fail to read with [`crate::Error::TooManyDataFields`] as the
+    /// source of an [`crate::Error::InvalidRecord`].
+    ///
+    /// By default, the number of data fields is not limited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let data = [];
+    /// let builder = bam::Reader::builder(&data[..]).set_max_data_field_count(256);
+    /// ```
+    pub fn set_max_data_field_count(mut self, max_data_field_count: usize) -> Self {
+        self.limits.max_data_field_count = Some(max_data_field_count);
+        self
+    }
+
+    /// Sets a progress observer.
+    ///
+    /// The observer is notified after each BGZF block and record is read. See
+    /// [`bgzf::reader::Builder::set_observer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf::observer::Counter;
+    ///
+    /// let data = [];
+    /// let builder = bam::Reader::builder(&data[..]).set_observer(Arc::new(Counter::default()));
+    /// ```
+    pub fn set_observer(mut self, observer: Arc<dyn bgzf::Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Builds a BAM reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let data = [];
+    /// let reader = bam::Reader::builder(&data[..]).build();
+    /// ```
+    pub fn build(self) -> Reader<bgzf::Reader<R>> {
+        let builder = bgzf::Reader::builder(self.inner);
+        build(builder, self.worker_pool, self.limits, self.observer)
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl Builder<bgzf::mmap::MappedFile> {
+    /// Builds a BAM reader from a memory-mapped file.
+    ///
+    /// Unlike [`Self::build`], BGZF blocks are decompressed directly from the mapping. See
+    /// [`bgzf::mmap::builder`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf::mmap::MappedFile;
+    ///
+    /// // SAFETY: `sample.bam` is not modified while it is mapped.
+    /// let file = unsafe { MappedFile::open("sample.bam")? };
+    /// let mut reader = bam::Reader::builder(file).build_mapped();
+    /// reader.read_header()?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_mapped(self) -> Reader<bgzf::Reader<bgzf::mmap::MappedFile>> {
+        let builder = bgzf::mmap::builder(self.inner);
+        build(builder, self.worker_pool, self.limits, self.observer)
+    }
+}
+
+fn build<R>(
+    mut builder: bgzf::reader::Builder<R>,
+    worker_pool: Option<bgzf::WorkerPool>,
+    limits: Limits,
+    observer: Option<Arc<dyn bgzf::Observer>>,
+) -> Reader<bgzf::Reader<R>>
+where
+    R: Read,
+{
+    if let Some(worker_pool) = worker_pool {
+        builder = builder.set_worker_pool(worker_pool);
+    }
+
+    if let Some(observer) = observer.clone() {
+        builder = builder.set_observer(observer);
+    }
+
+    let mut reader = Reader::from(builder.build());
+    reader.limits = limits;
+    reader.observer = observer;
+    reader
+}
+
+#[cfg(all(test, feature = "mmap"))]
+mod tests {
+    use std::{fs, io};
+
+    use noodles_sam as sam;
+
+    use super::*;
+    use crate::{Record, Writer};
+
+    #[test]
+    fn test_build_mapped() -> io::Result<()> {
+        let header = sam::Header::default();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+        writer.write_sam_record(header.reference_sequences(), &sam::Record::default())?;
+        let data = writer.into_inner().finish()?;
+
+        let path = std::env::temp_dir().join("noodles-bam-reader-builder-test_build_mapped.bam");
+        fs::write(&path, data)?;
+
+        let file = unsafe { bgzf::mmap::MappedFile::open(&path)? };
+        let mut reader = Builder::new(file).build_mapped();
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut record = Record::default();
+        assert!(reader.read_record(&mut record)? > 0);
+        assert_eq!(reader.read_record(&mut record)?, 0);
+
+        fs::remove_file(path)
+    }
+}
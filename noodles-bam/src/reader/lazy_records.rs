@@ -0,0 +1,84 @@
+use std::io::{self, Read};
+
+use crate::lazy;
+
+use super::Reader;
+
+/// An iterator over lazy records of a BAM reader.
+///
+/// This is created by calling [`Reader::lazy_records`].
+pub struct LazyRecords<'a, R>
+where
+    R: Read,
+{
+    reader: &'a mut Reader<R>,
+    record: lazy::Record,
+}
+
+impl<'a, R> LazyRecords<'a, R>
+where
+    R: Read,
+{
+    pub(crate) fn new(reader: &'a mut Reader<R>) -> Self {
+        Self {
+            reader,
+            record: lazy::Record::default(),
+        }
+    }
+}
+
+impl<'a, R> Iterator for LazyRecords<'a, R>
+where
+    R: Read,
+{
+    type Item = io::Result<lazy::Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.reader.read_lazy_record(&mut self.record) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(self.record.clone())),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Record, Writer};
+
+    #[test]
+    fn test_next() -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = Writer::new(Vec::new());
+
+        let mut expected = Vec::new();
+
+        for read_name in [b"r0\x00", b"r1\x00", b"r2\x00"] {
+            let record = Record::builder()
+                .set_read_name(read_name.to_vec())
+                .build()?;
+            writer.write_record(&record)?;
+            expected.push(record);
+        }
+
+        writer.try_finish()?;
+
+        let data = writer.get_ref().get_ref();
+        let mut reader = Reader::new(&data[..]);
+
+        let records: Vec<_> = reader.lazy_records().collect::<io::Result<_>>()?;
+
+        // Consecutive records are read into the same chunk.
+        let (a, b) = (records[0].as_ref(), records[1].as_ref());
+        assert_eq!(a.as_ptr_range().end, b.as_ptr());
+
+        let actual = records
+            .into_iter()
+            .map(Record::try_from)
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -6,7 +6,7 @@ use std::{
 use noodles_bgzf::{self as bgzf, VirtualPosition};
 use noodles_csi::index::reference_sequence::bin::Chunk;
 
-use crate::Record;
+use crate::{record::alignment_end, Record};
 
 use super::Reader;
 
@@ -66,11 +66,9 @@ where
         }
     }
 
-    fn read_record(&mut self) -> io::Result<Option<Record>> {
-        self.reader.read_record(&mut self.record).map(|n| match n {
-            0 => None,
-            _ => Some(self.record.clone()),
-        })
+    // Reads the next record into the reused record buffer. This returns `false` at EOF.
+    fn read_record(&mut self) -> io::Result<bool> {
+        self.reader.read_record(&mut self.record).map(|n| n > 0)
     }
 }
 
@@ -96,19 +94,24 @@ where
                     }
                 }
                 State::Read(chunk_end) => match self.read_record() {
-                    Ok(Some(record)) => {
+                    Ok(true) => {
                         if self.reader.virtual_position() >= chunk_end {
                             self.state = State::Seek;
                         }
 
-                        match intersects(&record, self.reference_sequence_id, self.start, self.end)
-                        {
-                            Ok(true) => return Some(Ok(record)),
+                        // Only records that intersect the region are cloned.
+                        match intersects(
+                            &self.record,
+                            self.reference_sequence_id,
+                            self.start,
+                            self.end,
+                        ) {
+                            Ok(true) => return Some(Ok(self.record.clone())),
                             Ok(false) => {}
                             Err(e) => return Some(Err(e)),
                         }
                     }
-                    Ok(None) => self.state = State::Seek,
+                    Ok(false) => self.state = State::Seek,
                     Err(e) => return Some(Err(e)),
                 },
                 State::Done => return None,
@@ -121,12 +124,19 @@ pub(crate) fn resolve_interval<B>(interval: B) -> (i32, i32)
 where
     B: RangeBounds<i32>,
 {
-    match (interval.start_bound(), interval.end_bound()) {
-        (Bound::Included(s), Bound::Included(e)) => (*s, *e),
-        (Bound::Included(s), Bound::Unbounded) => (*s, i32::MAX),
-        (Bound::Unbounded, Bound::Unbounded) => (1, i32::MAX),
-        _ => todo!(),
-    }
+    let start = match interval.start_bound() {
+        Bound::Included(s) => *s,
+        Bound::Excluded(s) => s.saturating_add(1),
+        Bound::Unbounded => 1,
+    };
+
+    let end = match interval.end_bound() {
+        Bound::Included(e) => *e,
+        Bound::Excluded(e) => e.saturating_sub(1),
+        Bound::Unbounded => i32::MAX,
+    };
+
+    (start, end)
 }
 
 pub(crate) fn next_chunk(chunks: &[Chunk], i: &mut usize) -> Option<Chunk> {
@@ -147,8 +157,7 @@ pub(crate) fn intersects(
     };
 
     let start = record.position().map(i32::from).expect("missing position");
-    let len = record.cigar().reference_len().map(|len| len as i32)?;
-    let end = start + len - 1;
+    let end = alignment_end(record, start)?;
 
     Ok(id == reference_sequence_id && in_interval(start, end, interval_start, interval_end))
 }
@@ -156,3 +165,44 @@ pub(crate) fn intersects(
 pub fn in_interval(a_start: i32, a_end: i32, b_start: i32, b_end: i32) -> bool {
     a_start <= b_end && b_start <= a_end
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_interval() {
+        assert_eq!(resolve_interval(8..=13), (8, 13));
+        assert_eq!(resolve_interval(8..13), (8, 12));
+        assert_eq!(resolve_interval(8..), (8, i32::MAX));
+        assert_eq!(resolve_interval(..=13), (1, 13));
+        assert_eq!(resolve_interval(..13), (1, 12));
+        assert_eq!(resolve_interval(..), (1, i32::MAX));
+        assert_eq!(
+            resolve_interval((Bound::Excluded(8), Bound::Included(13))),
+            (9, 13)
+        );
+    }
+
+    #[test]
+    fn test_in_interval() {
+        assert!(in_interval(8, 13, 13, 21));
+        assert!(in_interval(8, 13, 1, 8));
+        assert!(!in_interval(8, 13, 14, 21));
+    }
+
+    #[test]
+    fn test_intersects_with_end_overflow() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::record::{Cigar, ReferenceSequenceId};
+
+        let mut record = Record::default();
+        *record.reference_sequence_id_mut() = Some(ReferenceSequenceId::try_from(0)?);
+        record.pos = i32::MAX - 2; // position = 2147483646
+        *record.cigar_mut() = Cigar::from(vec![0x40]); // 4M
+
+        let e = intersects(&record, 0, 1, i32::MAX).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+
+        Ok(())
+    }
+}
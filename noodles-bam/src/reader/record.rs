@@ -3,16 +3,19 @@
 pub mod data;
 
 use std::{
+    cmp,
     io::{self, Read},
     mem,
 };
 
 use byteorder::{LittleEndian, ReadBytesExt};
+use bytes::BytesMut;
 use noodles_sam as sam;
 
 use crate::{
-    record::{Cigar, Data, QualityScores, ReferenceSequenceId, Sequence},
-    Record,
+    lazy,
+    record::{long_cigar, Cigar, Data, QualityScores, ReferenceSequenceId, Sequence},
+    Error, Record,
 };
 
 pub(crate) fn read_record<R>(
@@ -23,18 +26,141 @@ pub(crate) fn read_record<R>(
 where
     R: Read,
 {
+    read_record_with_limits(reader, buf, record, &Limits::default())
+}
+
+/// Limits on the sizes of a record and its fields.
+///
+/// These are set by [`super::Builder`] and default to no limits.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Limits {
+    pub(crate) max_record_size: Option<usize>,
+    pub(crate) max_cigar_op_count: Option<usize>,
+    pub(crate) max_data_field_count: Option<usize>,
+}
+
+pub(crate) fn read_record_with_limits<R>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    record: &mut Record,
+    limits: &Limits,
+) -> io::Result<usize>
+where
+    R: Read,
+{
+    use super::read_exact_to_vec;
+
     let block_size = match reader.read_u32::<LittleEndian>() {
         Ok(bs) => usize::try_from(bs).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
         Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(0),
         Err(e) => return Err(e),
     };
 
-    buf.resize(block_size, Default::default());
-    reader.read_exact(buf)?;
+    if let Some(max) = limits.max_record_size {
+        if block_size > max {
+            return Err(Error::RecordTooLarge {
+                size: block_size,
+                max,
+            }
+            .into());
+        }
+    }
+
+    read_exact_to_vec(reader, buf, block_size)?;
+
+    let mut src = &buf[..];
+
+    decode_record_with_limits(&mut src, record, block_size, limits).map_err(|e| {
+        io::Error::from(Error::InvalidRecord {
+            record_number: None,
+            offset: block_size - src.len(),
+            source: e,
+        })
+    })?;
+
+    Ok(block_size)
+}
+
+pub(crate) fn read_lazy_record_with_max_size<R>(
+    reader: &mut R,
+    buf: &mut BytesMut,
+    record: &mut lazy::Record,
+    max_record_size: Option<usize>,
+) -> io::Result<usize>
+where
+    R: Read,
+{
+    let block_size = match reader.read_u32::<LittleEndian>() {
+        Ok(bs) => usize::try_from(bs).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+        Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(0),
+        Err(e) => return Err(e),
+    };
+
+    if let Some(max) = max_record_size {
+        if block_size > max {
+            return Err(Error::RecordTooLarge {
+                size: block_size,
+                max,
+            }
+            .into());
+        }
+    }
+
+    read_exact_to_bytes_mut(reader, buf, block_size)?;
+
+    // The record buffer is split off the front of the chunk, so consecutive records share a
+    // single allocation.
+    *record = lazy::Record::try_from(buf.split().freeze())?;
+
+    Ok(block_size)
+}
+
+// Reads `len` bytes into the given (empty) buffer.
+//
+// The buffer is only grown as data is read, so a large, invalid block size does not allocate
+// before failing. When the buffer has to be reserved, at least `LAZY_CHUNK_SIZE` bytes are
+// allocated, which following records are then read into.
+fn read_exact_to_bytes_mut<R>(reader: &mut R, buf: &mut BytesMut, len: usize) -> io::Result<()>
+where
+    R: Read,
+{
+    const LAZY_CHUNK_SIZE: usize = 1 << 16;
+
+    buf.clear();
+
+    while buf.len() < len {
+        let start = buf.len();
+        let n = cmp::min(len - start, LAZY_CHUNK_SIZE);
 
-    let mut reader = &buf[..];
-    let reader = &mut reader;
+        if buf.capacity() - start < n {
+            buf.reserve(cmp::max(n, LAZY_CHUNK_SIZE));
+        }
+
+        buf.resize(start + n, 0);
+
+        if let Err(e) = reader.read_exact(&mut buf[start..]) {
+            buf.clear();
+            return Err(e);
+        }
+    }
 
+    Ok(())
+}
+
+pub(crate) fn decode_record(
+    reader: &mut &[u8],
+    record: &mut Record,
+    block_size: usize,
+) -> io::Result<()> {
+    decode_record_with_limits(reader, record, block_size, &Limits::default())
+}
+
+fn decode_record_with_limits(
+    reader: &mut &[u8],
+    record: &mut Record,
+    block_size: usize,
+    limits: &Limits,
+) -> io::Result<()> {
     *record.reference_sequence_id_mut() = read_reference_sequence_id(reader)?;
     record.pos = reader.read_i32::<LittleEndian>()?;
 
@@ -49,6 +175,8 @@ where
         usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
 
+    check_cigar_op_count(n_cigar_op, limits.max_cigar_op_count)?;
+
     *record.flags_mut() = read_flag(reader)?;
 
     let l_seq = reader.read_u32::<LittleEndian>().and_then(|n| {
@@ -60,20 +188,35 @@ where
 
     *record.template_length_mut() = reader.read_i32::<LittleEndian>()?;
 
+    let data_len = data_len(block_size, l_read_name, n_cigar_op, l_seq)?;
+
     read_read_name(reader, &mut record.read_name, l_read_name)?;
     read_cigar(reader, record.cigar_mut(), n_cigar_op)?;
     read_seq(reader, record.sequence_mut(), l_seq)?;
     read_qual(reader, record.quality_scores_mut(), l_seq)?;
-    read_data(
-        reader,
-        record.data_mut(),
-        block_size,
-        l_read_name,
-        n_cigar_op,
-        l_seq,
-    )?;
+    read_data(reader, record.data_mut(), data_len)?;
 
-    Ok(block_size)
+    if let Some(max) = limits.max_data_field_count {
+        let count = record.data().len();
+
+        if count > max {
+            return Err(Error::TooManyDataFields { count, max }.into());
+        }
+    }
+
+    long_cigar::resolve(record)?;
+
+    // The CIGAR may have been replaced by the one stored in the `CG` data field.
+    check_cigar_op_count(record.cigar().len(), limits.max_cigar_op_count)?;
+
+    Ok(())
+}
+
+fn check_cigar_op_count(count: usize, max_cigar_op_count: Option<usize>) -> io::Result<()> {
+    match max_cigar_op_count {
+        Some(max) if count > max => Err(Error::TooManyCigarOps { count, max }.into()),
+        _ => Ok(()),
+    }
 }
 
 fn read_reference_sequence_id<R>(reader: &mut R) -> io::Result<Option<ReferenceSequenceId>>
@@ -156,22 +299,10 @@ where
     Ok(())
 }
 
-fn read_data<R>(
-    reader: &mut R,
-    data: &mut Data,
-    block_size: usize,
-    l_read_name: usize,
-    n_cigar_op: usize,
-    l_seq: usize,
-) -> io::Result<()>
+fn read_data<R>(reader: &mut R, data: &mut Data, data_len: usize) -> io::Result<()>
 where
     R: Read,
 {
-    let cigar_len = mem::size_of::<u32>() * n_cigar_op;
-    let seq_len = (l_seq + 1) / 2;
-    let data_offset = 32 + l_read_name + cigar_len + seq_len + l_seq;
-    let data_len = block_size - data_offset;
-
     let buf = data.as_mut();
     buf.resize(data_len, Default::default());
     reader.read_exact(buf)?;
@@ -181,6 +312,40 @@ where
     Ok(())
 }
 
+// Returns the length of the data fields, i.e., the remaining length of the record after the
+// variable-length fields.
+//
+// This fails if the variable-length fields do not fit in the record, which guards against
+// allocating buffers for lengths that are invalid.
+pub(crate) fn data_len(
+    block_size: usize,
+    l_read_name: usize,
+    n_cigar_op: usize,
+    l_seq: usize,
+) -> io::Result<usize> {
+    data_offset(l_read_name, n_cigar_op, l_seq)
+        .and_then(|offset| block_size.checked_sub(offset))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid record field lengths: fields exceed the block size",
+            )
+        })
+}
+
+fn data_offset(l_read_name: usize, n_cigar_op: usize, l_seq: usize) -> Option<usize> {
+    const FIXED_FIELDS_LEN: usize = 32;
+
+    let cigar_len = mem::size_of::<u32>().checked_mul(n_cigar_op)?;
+    let seq_len = l_seq.checked_add(1)? / 2;
+
+    FIXED_FIELDS_LEN
+        .checked_add(l_read_name)?
+        .checked_add(cigar_len)?
+        .checked_add(seq_len)?
+        .checked_add(l_seq)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,4 +377,172 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_record_with_invalid_reference_sequence_id() {
+        let data = [
+            0x04, 0x00, 0x00, 0x00, // block_size = 4
+            0xfe, 0xff, 0xff, 0xff, // ref_id = -2
+        ];
+
+        let mut reader = &data[..];
+        let mut record = Record::default();
+        let result = read_record(&mut reader, &mut Vec::new(), &mut record);
+
+        let e = result.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(
+            e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+            Some(Error::InvalidRecord {
+                record_number: None,
+                offset: 4,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_read_record_with_max_size() -> io::Result<()> {
+        let data = [
+            0x22, 0x00, 0x00, 0x00, // block_size = 34
+        ];
+
+        let mut reader = &data[..];
+        let mut record = Record::default();
+        let limits = Limits {
+            max_record_size: Some(8),
+            ..Default::default()
+        };
+        let result = read_record_with_limits(&mut reader, &mut Vec::new(), &mut record, &limits);
+
+        let e = result.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(
+            e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+            Some(Error::RecordTooLarge { size: 34, max: 8 })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_with_max_cigar_op_count() {
+        let data = [
+            0x2a, 0x00, 0x00, 0x00, // block_size = 42
+            0xff, 0xff, 0xff, 0xff, // ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // pos = -1
+            0x02, // l_read_name = 2
+            0xff, // mapq = 255
+            0x48, 0x12, // bin = 4680
+            0x02, 0x00, // n_cigar_op = 2
+            0x04, 0x00, // flag = 4
+            0x00, 0x00, 0x00, 0x00, // l_seq = 0
+            0xff, 0xff, 0xff, 0xff, // next_ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // next_pos = -1
+            0x00, 0x00, 0x00, 0x00, // tlen = 0
+            0x2a, 0x00, // read_name = "*\x00"
+            0x10, 0x00, 0x00, 0x00, // cigar[0] = 1M
+            0x14, 0x00, 0x00, 0x00, // cigar[1] = 1S
+        ];
+
+        let mut reader = &data[..];
+        let mut record = Record::default();
+        let limits = Limits {
+            max_cigar_op_count: Some(1),
+            ..Default::default()
+        };
+        let result = read_record_with_limits(&mut reader, &mut Vec::new(), &mut record, &limits);
+
+        let e = result.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+
+        let source = match e.get_ref().and_then(|e| e.downcast_ref::<Error>()) {
+            Some(Error::InvalidRecord { source, .. }) => source,
+            _ => panic!("expected InvalidRecord, got {:?}", e),
+        };
+
+        assert!(matches!(
+            source.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+            Some(Error::TooManyCigarOps { count: 2, max: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_read_record_with_max_data_field_count() {
+        let data = [
+            0x2a, 0x00, 0x00, 0x00, // block_size = 42
+            0xff, 0xff, 0xff, 0xff, // ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // pos = -1
+            0x02, // l_read_name = 2
+            0xff, // mapq = 255
+            0x48, 0x12, // bin = 4680
+            0x00, 0x00, // n_cigar_op = 0
+            0x04, 0x00, // flag = 4
+            0x00, 0x00, 0x00, 0x00, // l_seq = 0
+            0xff, 0xff, 0xff, 0xff, // next_ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // next_pos = -1
+            0x00, 0x00, 0x00, 0x00, // tlen = 0
+            0x2a, 0x00, // read_name = "*\x00"
+            b'N', b'H', b'C', 0x01, // data[0] = NH:C:1
+            b'H', b'I', b'C', 0x00, // data[1] = HI:C:0
+        ];
+
+        let mut reader = &data[..];
+        let mut record = Record::default();
+        let limits = Limits {
+            max_data_field_count: Some(1),
+            ..Default::default()
+        };
+        let result = read_record_with_limits(&mut reader, &mut Vec::new(), &mut record, &limits);
+
+        let e = result.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+
+        let source = match e.get_ref().and_then(|e| e.downcast_ref::<Error>()) {
+            Some(Error::InvalidRecord { source, .. }) => source,
+            _ => panic!("expected InvalidRecord, got {:?}", e),
+        };
+
+        assert!(matches!(
+            source.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+            Some(Error::TooManyDataFields { count: 2, max: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_read_record_with_invalid_field_lengths() {
+        let data = [
+            0x20, 0x00, 0x00, 0x00, // block_size = 32
+            0xff, 0xff, 0xff, 0xff, // ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // pos = -1
+            0x02, // l_read_name = 2
+            0xff, // mapq = 255
+            0x48, 0x12, // bin = 4680
+            0x00, 0x00, // n_cigar_op = 0
+            0x04, 0x00, // flag = 4
+            0xff, 0xff, 0xff, 0xff, // l_seq = 4294967295
+            0xff, 0xff, 0xff, 0xff, // next_ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // next_pos = -1
+            0x00, 0x00, 0x00, 0x00, // tlen = 0
+        ];
+
+        let mut reader = &data[..];
+        let mut record = Record::default();
+        let result = read_record(&mut reader, &mut Vec::new(), &mut record);
+
+        let e = result.unwrap_err();
+        assert!(matches!(
+            e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+            Some(Error::InvalidRecord { offset: 32, .. })
+        ));
+    }
+
+    #[test]
+    fn test_data_len() -> io::Result<()> {
+        assert_eq!(data_len(34, 2, 0, 0)?, 0);
+        assert_eq!(data_len(48, 2, 1, 4)?, 4);
+        assert!(data_len(32, 2, 0, 0).is_err());
+        assert!(data_len(32, 0, 0, usize::MAX).is_err());
+        Ok(())
+    }
 }
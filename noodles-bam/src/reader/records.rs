@@ -1,6 +1,6 @@
 use std::io::{self, Read};
 
-use crate::Record;
+use crate::{Error, Record};
 
 use super::Reader;
 
@@ -13,6 +13,7 @@ where
 {
     reader: &'a mut Reader<R>,
     record: Record,
+    record_number: u64,
 }
 
 impl<'a, R> Records<'a, R>
@@ -23,6 +24,7 @@ where
         Self {
             reader,
             record: Record::default(),
+            record_number: 0,
         }
     }
 }
@@ -36,8 +38,78 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         match self.reader.read_record(&mut self.record) {
             Ok(0) => None,
-            Ok(_) => Some(Ok(self.record.clone())),
-            Err(e) => Some(Err(e)),
+            Ok(_) => {
+                self.record_number += 1;
+                Some(Ok(self.record.clone()))
+            }
+            Err(mut e) => {
+                if let Some(Error::InvalidRecord { record_number, .. }) =
+                    e.get_mut().and_then(|e| e.downcast_mut::<Error>())
+                {
+                    *record_number = Some(self.record_number);
+                }
+
+                Some(Err(e))
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use noodles_bgzf as bgzf;
+
+    use super::*;
+
+    #[test]
+    fn test_next_with_invalid_record() -> io::Result<()> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+
+        writer.write_all(b"BAM\x01")?;
+        writer.write_all(&[0x00, 0x00, 0x00, 0x00])?; // l_text = 0
+        writer.write_all(&[0x00, 0x00, 0x00, 0x00])?; // n_ref = 0
+
+        writer.write_all(&[
+            0x22, 0x00, 0x00, 0x00, // block_size = 34
+            0xff, 0xff, 0xff, 0xff, // ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // pos = -1
+            0x02, // l_read_name = 2
+            0xff, // mapq = 255
+            0x48, 0x12, // bin = 4680
+            0x00, 0x00, // n_cigar_op = 0
+            0x04, 0x00, // flag = 4
+            0x00, 0x00, 0x00, 0x00, // l_seq = 0
+            0xff, 0xff, 0xff, 0xff, // next_ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // next_pos = -1
+            0x00, 0x00, 0x00, 0x00, // tlen = 0
+            0x2a, 0x00, // read_name = "*\x00"
+        ])?;
+
+        writer.write_all(&[0x04, 0x00, 0x00, 0x00])?; // block_size = 4
+        writer.write_all(&[0xfe, 0xff, 0xff, 0xff])?; // ref_id = -2
+
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut records = reader.records();
+
+        assert_eq!(records.next().transpose()?, Some(Record::default()));
+
+        let e = records.next().unwrap().unwrap_err();
+        assert!(matches!(
+            e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+            Some(Error::InvalidRecord {
+                record_number: Some(1),
+                offset: 4,
+                ..
+            })
+        ));
+
+        Ok(())
+    }
+}
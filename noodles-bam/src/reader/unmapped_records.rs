@@ -6,7 +6,10 @@ use crate::Record;
 
 use super::Reader;
 
-/// An iterator over unmapped records of a BAM reader.
+/// An iterator over unplaced, unmapped records of a BAM reader.
+///
+/// Unplaced records have neither a reference sequence ID nor position. Unmapped records that are
+/// placed, e.g., with the position of their mate, are skipped.
 ///
 /// This is created by calling [`Reader::query_unmapped`].
 pub struct UnmappedRecords<'a, R>
@@ -15,16 +18,22 @@ where
 {
     reader: &'a mut Reader<bgzf::Reader<R>>,
     record: Record,
+    // The number of unplaced, unmapped records that have yet to be read, if known.
+    remaining: Option<u64>,
 }
 
 impl<'a, R> UnmappedRecords<'a, R>
 where
     R: Read,
 {
-    pub(crate) fn new(reader: &'a mut Reader<bgzf::Reader<R>>) -> Self {
+    pub(crate) fn new(
+        reader: &'a mut Reader<bgzf::Reader<R>>,
+        unplaced_unmapped_record_count: Option<u64>,
+    ) -> Self {
         Self {
             reader,
             record: Record::default(),
+            remaining: unplaced_unmapped_record_count,
         }
     }
 }
@@ -36,11 +45,19 @@ where
     type Item = io::Result<Record>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+
         loop {
             match self.reader.read_record(&mut self.record) {
                 Ok(0) => return None,
                 Ok(_) => {
-                    if self.record.flags().is_unmapped() {
+                    if self.record.reference_sequence_id().is_none() {
+                        if let Some(n) = self.remaining.as_mut() {
+                            *n -= 1;
+                        }
+
                         return Some(Ok(self.record.clone()));
                     }
                 }
@@ -4,6 +4,7 @@ pub mod builder;
 pub mod cigar;
 mod convert;
 pub mod data;
+pub(crate) mod long_cigar;
 pub mod quality_scores;
 pub mod reference_sequence_id;
 pub mod sequence;
@@ -93,7 +94,9 @@ impl Record {
             + self.read_name.len()
             + (mem::size_of::<u32>() * self.cigar.len())
             + self.seq.as_ref().len()
-            + self.qual.len()
+            // Missing quality scores are written as a filled block with the same length as the
+            // sequence.
+            + self.seq.len()
             + self.data.as_ref().len()
     }
 
@@ -450,14 +453,50 @@ impl Record {
     }
 }
 
-impl sam::RecordExt for Record {
+impl sam::AlignmentRecord for Record {
+    /// Returns the read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = bam::Record::default();
+    /// assert!(AlignmentRecord::read_name(&record).is_none());
+    /// ```
+    fn read_name(&self) -> Option<&[u8]> {
+        const MISSING: &[u8] = b"*";
+
+        let read_name = self.read_name.strip_suffix(&[0]).unwrap_or(&self.read_name);
+
+        if read_name == MISSING {
+            None
+        } else {
+            Some(read_name)
+        }
+    }
+
+    /// Returns the flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// let record = bam::Record::default();
+    /// assert_eq!(AlignmentRecord::flags(&record), sam::record::Flags::UNMAPPED);
+    /// ```
+    fn flags(&self) -> sam::record::Flags {
+        self.flags()
+    }
+
     /// Returns the associated reference sequence.
     ///
     /// # Examples
     ///
     /// ```
     /// use noodles_bam as bam;
-    /// use noodles_sam::{header::ReferenceSequences, RecordExt};
+    /// use noodles_sam::{header::ReferenceSequences, AlignmentRecord};
     ///
     /// let record = bam::Record::default();
     /// let reference_sequences = ReferenceSequences::default();
@@ -477,7 +516,7 @@ impl sam::RecordExt for Record {
     ///
     /// ```
     /// use noodles_bam as bam;
-    /// use noodles_sam::RecordExt;
+    /// use noodles_sam::AlignmentRecord;
     /// let record = bam::Record::default();
     /// assert!(record.alignment_start().is_none());
     /// ```
@@ -492,7 +531,7 @@ impl sam::RecordExt for Record {
     /// ```
     /// # use std::io;
     /// use noodles_bam as bam;
-    /// use noodles_sam::RecordExt;
+    /// use noodles_sam::AlignmentRecord;
     /// let record = bam::Record::default();
     /// assert_eq!(record.alignment_span()?, 0);
     /// # Ok::<_, io::Error>(())
@@ -507,7 +546,7 @@ impl sam::RecordExt for Record {
     ///
     /// ```
     /// use noodles_bam as bam;
-    /// use noodles_sam::{header::ReferenceSequences, RecordExt};
+    /// use noodles_sam::{header::ReferenceSequences, AlignmentRecord};
     ///
     /// let record = bam::Record::default();
     /// let reference_sequences = ReferenceSequences::default();
@@ -520,6 +559,128 @@ impl sam::RecordExt for Record {
     ) -> Option<io::Result<&'rs ReferenceSequence>> {
         get_reference_sequence(reference_sequences, self.mate_reference_sequence_id())
     }
+
+    /// Returns the mapping quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = bam::Record::default();
+    /// assert!(AlignmentRecord::mapping_quality(&record).is_none());
+    /// ```
+    fn mapping_quality(&self) -> Option<sam::record::MappingQuality> {
+        self.mapping_quality()
+    }
+
+    /// Returns the start position of the mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = bam::Record::default();
+    /// assert!(record.mate_alignment_start().is_none());
+    /// ```
+    fn mate_alignment_start(&self) -> Option<sam::record::Position> {
+        self.mate_position()
+    }
+
+    /// Returns the template length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = bam::Record::default();
+    /// assert_eq!(AlignmentRecord::template_length(&record), 0);
+    /// ```
+    fn template_length(&self) -> i32 {
+        self.template_length()
+    }
+
+    /// Returns the CIGAR operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = bam::Record::default();
+    /// assert!(AlignmentRecord::cigar(&record)?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn cigar(&self) -> io::Result<sam::record::Cigar> {
+        sam::record::Cigar::try_from(self.cigar())
+    }
+
+    /// Returns the read bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = bam::Record::default();
+    /// assert!(AlignmentRecord::sequence(&record)?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn sequence(&self) -> io::Result<sam::record::Sequence> {
+        Ok(self.sequence().into())
+    }
+
+    /// Returns the quality scores.
+    ///
+    /// Quality scores that are all missing (`0xff`) are returned as empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = bam::Record::default();
+    /// assert!(AlignmentRecord::quality_scores(&record)?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn quality_scores(&self) -> io::Result<sam::record::QualityScores> {
+        use crate::writer::sam_record::NULL_QUALITY_SCORE;
+
+        let quality_scores = self.quality_scores();
+
+        if quality_scores
+            .as_ref()
+            .iter()
+            .all(|&score| score == NULL_QUALITY_SCORE)
+        {
+            return Ok(sam::record::QualityScores::default());
+        }
+
+        sam::record::QualityScores::try_from(quality_scores)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns the optional data fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = bam::Record::default();
+    /// assert!(AlignmentRecord::data(&record)?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn data(&self) -> io::Result<sam::record::Data> {
+        sam::record::Data::try_from(self.data())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
 }
 
 fn get_reference_sequence(
@@ -540,6 +701,27 @@ fn get_reference_sequence(
     })
 }
 
+// Returns the alignment span as a signed position offset.
+pub(crate) fn alignment_span(record: &Record) -> io::Result<i32> {
+    use sam::AlignmentRecord;
+
+    record.alignment_span().and_then(|len| {
+        i32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    })
+}
+
+// Returns the 1-based end position of the alignment, given its start position.
+pub(crate) fn alignment_end(record: &Record, start: i32) -> io::Result<i32> {
+    let len = alignment_span(record)?;
+
+    start.checked_add(len - 1).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid alignment end: position + span overflows",
+        )
+    })
+}
+
 impl Default for Record {
     fn default() -> Self {
         use sam::record::Flags;
@@ -621,8 +803,13 @@ mod tests {
 
     #[test]
     fn test_block_size() -> io::Result<()> {
-        let record = build_record()?;
+        let mut record = build_record()?;
         assert_eq!(record.block_size(), 57);
+
+        // Missing quality scores are written with the same length as the sequence.
+        record.quality_scores_mut().as_mut().clear();
+        assert_eq!(record.block_size(), 57);
+
         Ok(())
     }
 
@@ -82,7 +82,7 @@ impl Cigar {
     ///
     /// This sums the lengths of the CIGAR operations that consume the reference sequence, i.e.,
     /// alignment matches (`M`), deletions from the reference (`D`), skipped reference regions
-    /// (`S`), sequence matches (`=`), and sequence mismatches (`X`).
+    /// (`N`), sequence matches (`=`), and sequence mismatches (`X`).
     ///
     /// # Examples
     ///
@@ -105,7 +105,7 @@ impl Cigar {
 
             match op.kind() {
                 Kind::Match | Kind::Deletion | Kind::Skip | Kind::SeqMatch | Kind::SeqMismatch => {
-                    len += op.len();
+                    len = checked_add_len(len, op.len())?;
                 }
                 _ => {}
             }
@@ -145,7 +145,7 @@ impl Cigar {
                 | Kind::SoftClip
                 | Kind::SeqMatch
                 | Kind::SeqMismatch => {
-                    len += op.len();
+                    len = checked_add_len(len, op.len())?;
                 }
                 _ => {}
             }
@@ -234,10 +234,31 @@ impl TryFrom<&Cigar> for sam::record::Cigar {
     }
 }
 
+fn checked_add_len(len: u32, op_len: u32) -> io::Result<u32> {
+    len.checked_add(op_len)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "CIGAR length overflow"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_reference_len_and_read_len_with_overflow() {
+        // 17 × (2^28 - 1)M
+        let cigar = Cigar::from(vec![0xfffffff0; 17]);
+
+        assert!(matches!(
+            cigar.reference_len(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            cigar.read_len(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
     #[test]
     fn test_from_vec_u8_for_cigar() {
         let cigar = Cigar::from(vec![0x00000240, 0x00000084]); // 36M8S
@@ -1,6 +1,6 @@
 use std::io;
 
-use noodles_sam::{self as sam, RecordExt};
+use noodles_sam::{self as sam, AlignmentRecord};
 
 use super::{Record, ReferenceSequenceId};
 
@@ -112,12 +112,10 @@ impl Record {
             builder = builder.set_sequence(self.sequence().into());
         }
 
-        if !self.quality_scores().is_empty() {
-            let quality_scores = self
-                .quality_scores()
-                .try_into()
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        // Missing quality scores are converted to an empty list.
+        let quality_scores = AlignmentRecord::quality_scores(self)?;
 
+        if !quality_scores.is_empty() {
             builder = builder.set_quality_scores(quality_scores);
         }
 
@@ -242,4 +240,22 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_try_into_sam_record_with_missing_quality_scores(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequences = build_reference_sequences()?;
+
+        let expected = sam::Record::builder()
+            .set_read_name("r0".parse()?)
+            .set_sequence("ATGC".parse()?)
+            .build()?;
+
+        let record = Record::try_from_sam_record(&reference_sequences, &expected)?;
+        let actual = record.try_into_sam_record(&reference_sequences)?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
 }
@@ -95,11 +95,36 @@ impl Data {
     /// # Ok::<_, io::Error>(())
     /// ```
     pub fn get(&self, tag: Tag) -> Option<io::Result<Field>> {
+        match self.get_index_of(tag)? {
+            Ok(i) => self.get_index(i),
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    /// Returns the index of the field of the given tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::record::Data;
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// let data = Data::try_from(vec![
+    ///     b'N', b'H', b'i', 0x01, 0x00, 0x00, 0x00, // NH:i:1
+    ///     b'R', b'G', b'Z', b'r', b'g', b'0', 0x00, // RG:Z:rg0
+    /// ])?;
+    ///
+    /// assert_eq!(data.get_index_of(Tag::ReadGroup).transpose()?, Some(1));
+    /// assert!(data.get_index_of(Tag::AlignmentScore).is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn get_index_of(&self, tag: Tag) -> Option<io::Result<usize>> {
         for (i, result) in self.keys().enumerate() {
             match result {
                 Ok(t) => {
                     if t == tag {
-                        return self.get_index(i);
+                        return Some(Ok(i));
                     }
                 }
                 Err(e) => return Some(Err(e)),
@@ -183,6 +208,49 @@ impl Data {
         }
     }
 
+    /// Removes the field with the given tag.
+    ///
+    /// The removed field is returned. The order of the remaining fields is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::record::{data::{field::Value, Field}, Data};
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// let mut data = Data::try_from(vec![
+    ///     b'N', b'H', b'i', 0x01, 0x00, 0x00, 0x00, // NH:i:1
+    ///     b'R', b'G', b'Z', b'r', b'g', b'0', 0x00, // RG:Z:rg0
+    /// ])?;
+    ///
+    /// let nh = data.remove(Tag::AlignmentHitCount).transpose()?;
+    /// assert_eq!(nh, Some(Field::new(Tag::AlignmentHitCount, Value::Int32(1))));
+    /// assert!(data.remove(Tag::AlignmentHitCount).is_none());
+    ///
+    /// assert_eq!(data.as_ref(), [b'R', b'G', b'Z', b'r', b'g', b'0', 0x00]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn remove(&mut self, tag: Tag) -> Option<io::Result<Field>> {
+        let i = match self.get_index_of(tag)? {
+            Ok(i) => i,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let field = match self.get_index(i)? {
+            Ok(field) => field,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let range = self.bounds.get(i).expect("index out of bounds");
+        self.data.drain(range);
+
+        match self.index() {
+            Ok(()) => Some(Ok(field)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+
     fn splice(&mut self, i: usize, field: Field) -> io::Result<()> {
         let range = self.bounds.get(i).expect("index out of bounds");
         let buf = <Vec<u8>>::try_from(field)?;
@@ -434,6 +502,40 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_remove() -> io::Result<()> {
+        use field::Value;
+
+        let mut data = Data::try_from(vec![
+            b'N', b'H', b'i', 0x01, 0x00, 0x00, 0x00, // NH:i:1
+            b'R', b'G', b'Z', b'r', b'g', b'0', 0x00, // RG:Z:rg0
+            b'A', b'S', b'C', 0x0d, // AS:C:13
+        ])?;
+
+        let expected = Field::new(Tag::ReadGroup, Value::String(String::from("rg0")));
+        assert_eq!(data.remove(Tag::ReadGroup).transpose()?, Some(expected));
+        assert_eq!(
+            data.as_ref(),
+            [
+                b'N', b'H', b'i', 0x01, 0x00, 0x00, 0x00, // NH:i:1
+                b'A', b'S', b'C', 0x0d, // AS:C:13
+            ]
+        );
+        assert_eq!(data.len(), 2);
+        assert_eq!(
+            data.get(Tag::AlignmentScore).transpose()?,
+            Some(Field::new(Tag::AlignmentScore, Value::UInt8(13)))
+        );
+
+        assert!(data.remove(Tag::ReadGroup).is_none());
+
+        assert!(data.remove(Tag::AlignmentHitCount).is_some());
+        assert!(data.remove(Tag::AlignmentScore).is_some());
+        assert!(data.is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_try_from_vec_field_for_data() -> io::Result<()> {
         use field::Value;
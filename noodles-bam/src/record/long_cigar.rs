@@ -0,0 +1,312 @@
+//! Long CIGAR handling.
+//!
+//! § 4.2.2 "N_CIGAR_OP field" (2022-08-22): The number of CIGAR operations is stored as a `u16`.
+//! When a CIGAR has more operations, the record stores a placeholder CIGAR `kSmN`, where `k` is
+//! the read length and `m` is the reference length, and the real CIGAR is stored in the `CG` data
+//! field as a `B,I` array.
+
+use std::{io, mem, ops::Range};
+
+use byteorder::{ByteOrder, LittleEndian};
+use noodles_sam::{self as sam, record::cigar::op::Kind};
+
+use super::{
+    cigar::Op,
+    data::{
+        field::{value::Subtype, Value},
+        Bounds,
+    },
+    Cigar, Record,
+};
+
+/// The maximum number of CIGAR operations that can be stored in the CIGAR field.
+pub(crate) const MAX_CIGAR_OP_COUNT: usize = u16::MAX as usize;
+
+const TAG: sam::record::data::field::Tag = sam::record::data::field::Tag::Cigar;
+
+/// Encodes the CIGAR of a record if it has too many operations to be stored in the CIGAR field.
+///
+/// This returns the placeholder CIGAR and the encoded `CG` data field.
+pub(crate) fn encode_record_cigar(record: &Record) -> io::Result<Option<(Cigar, Vec<u8>)>> {
+    let cigar = record.cigar();
+
+    if cigar.len() <= MAX_CIGAR_OP_COUNT {
+        return Ok(None);
+    }
+
+    if record.data().get_index_of(TAG).transpose()?.is_some() {
+        return Err(duplicate_tag_error());
+    }
+
+    let reference_len = cigar.reference_len()?;
+    encode(cigar.as_ref(), record.sequence().len(), reference_len).map(Some)
+}
+
+/// Encodes the CIGAR of a SAM record if it has too many operations to be stored in the CIGAR
+/// field.
+///
+/// This returns the placeholder CIGAR and the encoded `CG` data field.
+pub(crate) fn encode_sam_record_cigar(
+    record: &sam::Record,
+) -> io::Result<Option<(Cigar, Vec<u8>)>> {
+    let cigar = record.cigar();
+
+    if cigar.len() <= MAX_CIGAR_OP_COUNT {
+        return Ok(None);
+    }
+
+    if record.data().get(TAG).is_some() {
+        return Err(duplicate_tag_error());
+    }
+
+    let raw_ops: Vec<_> = cigar
+        .iter()
+        .map(|op| op.len() << 4 | op.kind() as u32)
+        .collect();
+
+    encode(&raw_ops, record.sequence().len(), cigar.reference_len()).map(Some)
+}
+
+fn encode(raw_ops: &[u32], read_len: usize, reference_len: u32) -> io::Result<(Cigar, Vec<u8>)> {
+    let read_len =
+        u32::try_from(read_len).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let placeholder = Op::new(Kind::SoftClip, read_len)
+        .and_then(|s| Op::new(Kind::Skip, reference_len).map(|n| Cigar::from(vec![s, n])))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let n =
+        u32::try_from(raw_ops.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let mut buf = Vec::with_capacity(8 + 4 * raw_ops.len());
+    buf.extend_from_slice(TAG.as_ref());
+    buf.extend_from_slice(&[b'B', u8::from(Subtype::UInt32)]);
+    buf.extend_from_slice(&n.to_le_bytes());
+
+    for &raw_op in raw_ops {
+        buf.extend_from_slice(&raw_op.to_le_bytes());
+    }
+
+    Ok((placeholder, buf))
+}
+
+fn duplicate_tag_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "the CIGAR has too many operations to be stored without a CG data field, but one is already set",
+    )
+}
+
+/// Replaces a placeholder CIGAR with the real CIGAR stored in the `CG` data field.
+///
+/// The `CG` data field is removed. A record without a placeholder CIGAR is not changed.
+pub(crate) fn resolve(record: &mut Record) -> io::Result<()> {
+    if !has_placeholder_cigar(record) {
+        return Ok(());
+    }
+
+    let field = match record.data_mut().remove(TAG).transpose()? {
+        Some(field) => field,
+        None => return Ok(()),
+    };
+
+    match field.value() {
+        Value::UInt32Array(raw_ops) => {
+            *record.cigar_mut() = Cigar::from(raw_ops.clone());
+            Ok(())
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid CG data field type: expected B:I",
+        )),
+    }
+}
+
+fn has_placeholder_cigar(record: &Record) -> bool {
+    is_placeholder_cigar(record.cigar().as_ref(), record.sequence().len())
+}
+
+fn is_placeholder_cigar(raw_ops: &[u32], read_len: usize) -> bool {
+    if raw_ops.len() != 2 {
+        return false;
+    }
+
+    let (s, n) = match (Op::try_from(raw_ops[0]), Op::try_from(raw_ops[1])) {
+        (Ok(s), Ok(n)) => (s, n),
+        _ => return false,
+    };
+
+    s.kind() == Kind::SoftClip
+        && usize::try_from(s.len()).ok() == Some(read_len)
+        && n.kind() == Kind::Skip
+}
+
+/// Finds the raw CIGAR operations in the `CG` data field of an encoded record.
+///
+/// This returns the range of the `CG` array values in the raw data if the raw CIGAR is a
+/// placeholder and the `CG` data field is present.
+pub(crate) fn find_raw_cigar(
+    raw_cigar: &[u8],
+    read_len: usize,
+    data: &[u8],
+) -> io::Result<Option<Range<usize>>> {
+    // tag (2) + type (1) + subtype (1) + count (4)
+    const HEADER_LEN: usize = 8;
+
+    let raw_ops: Vec<_> = raw_cigar
+        .chunks_exact(mem::size_of::<u32>())
+        .map(LittleEndian::read_u32)
+        .collect();
+
+    if !is_placeholder_cigar(&raw_ops, read_len) {
+        return Ok(None);
+    }
+
+    let mut bounds = Bounds::default();
+    bounds.update(data)?;
+
+    for range in (0..bounds.len()).filter_map(|i| bounds.get(i)) {
+        let field = &data[range.clone()];
+
+        if &field[..2] != TAG.as_ref() {
+            continue;
+        }
+
+        if field.len() < HEADER_LEN || field[2] != b'B' || field[3] != u8::from(Subtype::UInt32) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid CG data field type: expected B:I",
+            ));
+        }
+
+        return Ok(Some(range.start + HEADER_LEN..range.end));
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::{data::Field, sequence::Base, Data, Sequence};
+
+    fn build_record(n: usize) -> Result<Record, Box<dyn std::error::Error>> {
+        let cigar = Cigar::from(vec![Op::new(Kind::Match, 1)?; n]);
+        let sequence = Sequence::from(vec![Base::A; n]);
+
+        Record::builder()
+            .set_cigar(cigar)
+            .set_sequence(sequence)
+            .build()
+            .map_err(|e| e.into())
+    }
+
+    #[test]
+    fn test_encode_record_cigar() -> Result<(), Box<dyn std::error::Error>> {
+        let record = build_record(MAX_CIGAR_OP_COUNT)?;
+        assert!(encode_record_cigar(&record)?.is_none());
+
+        let record = build_record(MAX_CIGAR_OP_COUNT + 1)?;
+        let (placeholder, cigar_data) = encode_record_cigar(&record)?.unwrap();
+
+        let n = (MAX_CIGAR_OP_COUNT + 1) as u32;
+        assert_eq!(
+            placeholder,
+            Cigar::from(vec![Op::new(Kind::SoftClip, n)?, Op::new(Kind::Skip, n)?])
+        );
+
+        assert_eq!(&cigar_data[..4], b"CGBI");
+        assert_eq!(&cigar_data[4..8], &n.to_le_bytes());
+        assert_eq!(cigar_data.len(), 8 + 4 * (MAX_CIGAR_OP_COUNT + 1));
+
+        let mut record = build_record(MAX_CIGAR_OP_COUNT + 1)?;
+        record
+            .data_mut()
+            .insert(Field::new(TAG, Value::UInt32Array(Vec::new())))
+            .transpose()?;
+        assert!(matches!(
+            encode_record_cigar(&record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve() -> Result<(), Box<dyn std::error::Error>> {
+        let expected = build_record(MAX_CIGAR_OP_COUNT + 1)?;
+        let (placeholder, cigar_data) = encode_record_cigar(&expected)?.unwrap();
+
+        let mut record = expected.clone();
+        *record.cigar_mut() = placeholder.clone();
+        *record.data_mut() = Data::try_from(cigar_data)?;
+        resolve(&mut record)?;
+        assert_eq!(record, expected);
+
+        // A placeholder-like CIGAR without a CG data field is not changed.
+        let mut record = expected.clone();
+        *record.cigar_mut() = placeholder.clone();
+        resolve(&mut record)?;
+        assert_eq!(record.cigar(), &placeholder);
+
+        // A CG data field without a placeholder CIGAR is not changed.
+        let mut record = build_record(2)?;
+        let field = Field::new(TAG, Value::UInt32Array(vec![0x10]));
+        record.data_mut().insert(field.clone()).transpose()?;
+        resolve(&mut record)?;
+        assert_eq!(record.cigar().len(), 2);
+        assert_eq!(record.data().get(TAG).transpose()?, Some(field));
+
+        let mut record = expected;
+        *record.cigar_mut() = placeholder;
+        record
+            .data_mut()
+            .insert(Field::new(TAG, Value::String(String::from("1M"))))
+            .transpose()?;
+        assert!(matches!(
+            resolve(&mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_raw_cigar() -> Result<(), Box<dyn std::error::Error>> {
+        let record = build_record(MAX_CIGAR_OP_COUNT + 1)?;
+        let (placeholder, cigar_data) = encode_record_cigar(&record)?.unwrap();
+
+        let raw_placeholder: Vec<_> = placeholder
+            .as_ref()
+            .iter()
+            .flat_map(|raw_op| raw_op.to_le_bytes())
+            .collect();
+
+        let read_len = record.sequence().len();
+
+        let mut data = vec![b'N', b'M', b'C', 0x00]; // NM:i:0
+        data.extend(&cigar_data);
+
+        assert_eq!(
+            find_raw_cigar(&raw_placeholder, read_len, &data)?,
+            Some(12..data.len())
+        );
+
+        assert_eq!(
+            find_raw_cigar(&raw_placeholder, read_len, &data[..4])?,
+            None
+        );
+        assert_eq!(
+            find_raw_cigar(&raw_placeholder[..4], read_len, &data)?,
+            None
+        );
+
+        let data = [b'C', b'G', b'Z', b'1', b'M', 0x00]; // CG:Z:1M
+        assert!(matches!(
+            find_raw_cigar(&raw_placeholder, read_len, &data),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
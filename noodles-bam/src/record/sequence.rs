@@ -156,6 +156,22 @@ impl Sequence {
         Bases::new(self)
     }
 
+    /// Returns the reverse complement of this sequence.
+    ///
+    /// See [`Base::complement`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Sequence;
+    /// let sequence = Sequence::new(vec![0x11, 0x24], 3); // AAC
+    /// assert_eq!(sequence.reverse_complement().to_string(), "GTT");
+    /// ```
+    pub fn reverse_complement(&self) -> Self {
+        let bases: Vec<_> = self.bases().rev().map(Base::complement).collect();
+        Self::from(bases)
+    }
+
     /// Appends a base to the end of the sequence.
     ///
     /// # Examples
@@ -264,6 +280,36 @@ mod tests {
         assert_eq!(bases.next(), None);
     }
 
+    #[test]
+    fn test_bases_rev() {
+        let sequence = Sequence::new(vec![0x18, 0x40], 3); // ATG
+        let actual: Vec<_> = sequence.bases().rev().collect();
+        assert_eq!(actual, [Base::G, Base::T, Base::A]);
+
+        let sequence = Sequence::new(vec![0x10], 1); // A
+        let actual: Vec<_> = sequence.bases().rev().collect();
+        assert_eq!(actual, [Base::A]);
+
+        let sequence = Sequence::new(vec![0x18, 0x40], 3); // ATG
+        let mut bases = sequence.bases();
+        assert_eq!(bases.next(), Some(Base::A));
+        assert_eq!(bases.next_back(), Some(Base::G));
+        assert_eq!(bases.len(), 1);
+        assert_eq!(bases.next_back(), Some(Base::T));
+        assert!(bases.next().is_none());
+        assert!(bases.next_back().is_none());
+    }
+
+    #[test]
+    fn test_reverse_complement() {
+        let sequence = Sequence::new(vec![0x18, 0x4f, 0x00], 5); // ATGN=
+        let actual = sequence.reverse_complement();
+        assert_eq!(actual.to_string(), "=NCAT");
+        assert_eq!(actual.len(), 5);
+
+        assert!(Sequence::default().reverse_complement().is_empty());
+    }
+
     #[test]
     fn test_bases_with_empty_sequence() {
         let sequence = Sequence::new(Vec::new(), 0);
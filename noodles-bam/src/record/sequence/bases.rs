@@ -6,22 +6,14 @@ use super::{Base, Sequence};
 pub struct Bases<'a> {
     sequence: &'a Sequence,
     head: usize,
-    tail: usize,
     remaining: usize,
 }
 
 impl<'a> Bases<'a> {
     pub(crate) fn new(sequence: &'a Sequence) -> Self {
-        let tail = if sequence.is_empty() {
-            0
-        } else {
-            sequence.len() - 1
-        };
-
         Self {
             sequence,
             head: 0,
-            tail,
             remaining: sequence.len(),
         }
     }
@@ -48,17 +40,19 @@ impl<'a> Iterator for Bases<'a> {
     }
 }
 
+impl<'a> ExactSizeIterator for Bases<'a> {}
+
 impl<'a> DoubleEndedIterator for Bases<'a> {
     fn next_back(&mut self) -> Option<Self::Item> {
         if self.remaining == 0 {
             return None;
         }
 
-        let symbol = self.sequence.get(self.tail).copied();
-
-        self.tail -= 1;
         self.remaining -= 1;
 
+        let tail = self.head + self.remaining;
+        let symbol = self.sequence.get(tail).copied();
+
         symbol
     }
 }
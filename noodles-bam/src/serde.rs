@@ -0,0 +1,253 @@
+//! Serde implementations for BAM records.
+//!
+//! A record is serialized as a struct with one field per SAM column, using the column names of
+//! the SAM specification (e.g., `qname`, `flag`, `pos`). Unlike SAM records, reference sequences
+//! are referred to by their raw IDs (`ref_id` and `next_ref_id`) rather than their names, as
+//! resolving names requires a header. Missing values are represented as `null`.
+//!
+//! BAM headers are SAM headers; see the `serde` feature of `noodles-sam`.
+
+use std::io;
+
+use noodles_sam::{self as sam, header::ReferenceSequences};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{record::ReferenceSequenceId, Record};
+
+const NULL_FIELD: &str = "*";
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename = "Record")]
+struct RawRecord {
+    qname: Option<String>,
+    flag: u16,
+    ref_id: Option<i32>,
+    pos: Option<i32>,
+    mapq: Option<u8>,
+    cigar: String,
+    next_ref_id: Option<i32>,
+    pnext: Option<i32>,
+    tlen: i32,
+    seq: String,
+    qual: String,
+    data: Vec<String>,
+}
+
+impl TryFrom<&Record> for RawRecord {
+    type Error = io::Error;
+
+    fn try_from(record: &Record) -> Result<Self, Self::Error> {
+        // Reference sequence IDs are kept as is, so the conversion to a SAM record does not
+        // require a header.
+        let mut unplaced_record = record.clone();
+        *unplaced_record.reference_sequence_id_mut() = None;
+        *unplaced_record.mate_reference_sequence_id_mut() = None;
+
+        let sam_record = unplaced_record.try_into_sam_record(&ReferenceSequences::default())?;
+
+        let data = if sam_record.data().is_empty() {
+            Vec::new()
+        } else {
+            sam_record
+                .data()
+                .to_string()
+                .split('\t')
+                .map(String::from)
+                .collect()
+        };
+
+        Ok(Self {
+            qname: sam_record.read_name().map(|name| name.to_string()),
+            flag: u16::from(sam_record.flags()),
+            ref_id: record.reference_sequence_id().map(i32::from),
+            pos: sam_record.position().map(i32::from),
+            mapq: sam_record.mapping_quality().map(u8::from),
+            cigar: sam_record.cigar().to_string(),
+            next_ref_id: record.mate_reference_sequence_id().map(i32::from),
+            pnext: sam_record.mate_position().map(i32::from),
+            tlen: sam_record.template_length(),
+            seq: sam_record.sequence().to_string(),
+            qual: sam_record.quality_scores().to_string(),
+            data,
+        })
+    }
+}
+
+impl TryFrom<RawRecord> for Record {
+    type Error = io::Error;
+
+    fn try_from(raw_record: RawRecord) -> Result<Self, Self::Error> {
+        use sam::record::mapping_quality;
+
+        let mut fields = vec![
+            raw_record.qname.unwrap_or_else(|| NULL_FIELD.into()),
+            raw_record.flag.to_string(),
+            NULL_FIELD.into(),
+            raw_record.pos.unwrap_or(0).to_string(),
+            raw_record
+                .mapq
+                .unwrap_or(mapping_quality::MISSING)
+                .to_string(),
+            raw_record.cigar,
+            NULL_FIELD.into(),
+            raw_record.pnext.unwrap_or(0).to_string(),
+            raw_record.tlen.to_string(),
+            raw_record.seq,
+            raw_record.qual,
+        ];
+
+        fields.extend(raw_record.data);
+
+        let sam_record: sam::Record = fields
+            .join("\t")
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut record = Record::try_from_sam_record(&ReferenceSequences::default(), &sam_record)?;
+
+        *record.reference_sequence_id_mut() = raw_record
+            .ref_id
+            .map(ReferenceSequenceId::try_from)
+            .transpose()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        *record.mate_reference_sequence_id_mut() = raw_record
+            .next_ref_id
+            .map(ReferenceSequenceId::try_from)
+            .transpose()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(record)
+    }
+}
+
+impl Serialize for Record {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser;
+
+        RawRecord::try_from(self)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Record {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw_record = RawRecord::deserialize(deserializer)?;
+        Record::try_from(raw_record).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_tokens, Token};
+
+    use super::*;
+
+    #[test]
+    fn test_record() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::header::{reference_sequence, ReferenceSequence};
+
+        let reference_sequences: ReferenceSequences = [("sq0".parse()?, 8), ("sq1".parse()?, 13)]
+            .into_iter()
+            .map(|(name, len): (reference_sequence::Name, i32)| {
+                let sn = name.to_string();
+                ReferenceSequence::new(name, len).map(|rs| (sn, rs))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let sam_record: sam::Record =
+            "r0\t99\tsq1\t1\t60\t4M\t=\t5\t8\tACGT\tNDLS\tNH:i:1".parse()?;
+        let record = Record::try_from_sam_record(&reference_sequences, &sam_record)?;
+
+        assert_tokens(
+            &record,
+            &[
+                Token::Struct {
+                    name: "Record",
+                    len: 12,
+                },
+                Token::Str("qname"),
+                Token::Some,
+                Token::Str("r0"),
+                Token::Str("flag"),
+                Token::U16(99),
+                Token::Str("ref_id"),
+                Token::Some,
+                Token::I32(1),
+                Token::Str("pos"),
+                Token::Some,
+                Token::I32(1),
+                Token::Str("mapq"),
+                Token::Some,
+                Token::U8(60),
+                Token::Str("cigar"),
+                Token::Str("4M"),
+                Token::Str("next_ref_id"),
+                Token::Some,
+                Token::I32(1),
+                Token::Str("pnext"),
+                Token::Some,
+                Token::I32(5),
+                Token::Str("tlen"),
+                Token::I32(8),
+                Token::Str("seq"),
+                Token::Str("ACGT"),
+                Token::Str("qual"),
+                Token::Str("NDLS"),
+                Token::Str("data"),
+                Token::Seq { len: Some(1) },
+                Token::Str("NH:i:1"),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_with_missing_fields() {
+        assert_tokens(
+            &Record::default(),
+            &[
+                Token::Struct {
+                    name: "Record",
+                    len: 12,
+                },
+                Token::Str("qname"),
+                Token::None,
+                Token::Str("flag"),
+                Token::U16(4),
+                Token::Str("ref_id"),
+                Token::None,
+                Token::Str("pos"),
+                Token::None,
+                Token::Str("mapq"),
+                Token::None,
+                Token::Str("cigar"),
+                Token::Str("*"),
+                Token::Str("next_ref_id"),
+                Token::None,
+                Token::Str("pnext"),
+                Token::None,
+                Token::Str("tlen"),
+                Token::I32(0),
+                Token::Str("seq"),
+                Token::Str("*"),
+                Token::Str("qual"),
+                Token::Str("*"),
+                Token::Str("data"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -1,9 +1,15 @@
-mod record;
+//! BAM writer.
+
+mod builder;
+pub(crate) mod record;
 pub(crate) mod sam_record;
 
+pub use self::builder::Builder;
+
 use std::{
     ffi::CString,
     io::{self, Write},
+    sync::Arc,
 };
 
 use byteorder::{LittleEndian, WriteBytesExt};
@@ -11,9 +17,10 @@ use noodles_bgzf as bgzf;
 use noodles_sam::{
     self as sam,
     header::{ReferenceSequence, ReferenceSequences},
+    AlignmentWriter,
 };
 
-use super::Record;
+use super::{lazy, Record};
 
 /// A BAM writer.
 ///
@@ -39,6 +46,7 @@ use super::Record;
 /// ```
 pub struct Writer<W> {
     inner: W,
+    observer: Option<Arc<dyn bgzf::Observer>>,
 }
 
 impl<W> Writer<W>
@@ -145,11 +153,41 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
-        record::write_record(&mut self.inner, record)
+        record::write_record(&mut self.inner, record)?;
+        self.notify_record();
+        Ok(())
+    }
+
+    /// Writes a lazy BAM record.
+    ///
+    /// The encoded fields of the record are written as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, lazy};
+    /// let mut writer = bam::Writer::new(Vec::new());
+    /// let record = lazy::Record::default();
+    /// writer.write_lazy_record(&record)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_lazy_record(&mut self, record: &lazy::Record) -> io::Result<()> {
+        let buf = record.as_ref();
+        let block_size =
+            u32::try_from(buf.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        self.inner.write_u32::<LittleEndian>(block_size)?;
+        self.inner.write_all(buf)?;
+        self.notify_record();
+        Ok(())
     }
 
     /// Writes a SAM record.
     ///
+    /// The record is converted to a BAM record as it is written. This is equivalent to writing the
+    /// record returned by [`Record::try_from_sam_record`] but does not allocate an intermediate
+    /// record.
+    ///
     /// # Examples
     ///
     /// ```
@@ -169,7 +207,15 @@ where
         reference_sequences: &ReferenceSequences,
         record: &sam::Record,
     ) -> io::Result<()> {
-        sam_record::write_sam_record(&mut self.inner, reference_sequences, record)
+        sam_record::write_sam_record(&mut self.inner, reference_sequences, record)?;
+        self.notify_record();
+        Ok(())
+    }
+
+    fn notify_record(&self) {
+        if let Some(observer) = self.observer.as_ref() {
+            observer.on_record();
+        }
     }
 }
 
@@ -191,6 +237,19 @@ where
         Self::from(bgzf::Writer::new(writer))
     }
 
+    /// Creates a BAM writer builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let builder = bam::Writer::builder(Vec::new());
+    /// let writer = builder.build();
+    /// ```
+    pub fn builder(writer: W) -> Builder<W> {
+        Builder::new(writer)
+    }
+
     /// Attempts to finish the output stream.
     ///
     /// This is typically only manually called if the underlying stream is needed before the writer
@@ -210,9 +269,36 @@ where
     }
 }
 
+impl<W> AlignmentWriter for Writer<bgzf::Writer<W>>
+where
+    W: Write,
+{
+    fn write_alignment_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.write_header(header)?;
+        self.write_reference_sequences(header.reference_sequences())
+    }
+
+    fn write_alignment_record(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn sam::AlignmentRecord,
+    ) -> io::Result<()> {
+        let reference_sequences = header.reference_sequences();
+        let record = sam::Record::try_from_alignment_record(reference_sequences, record)?;
+        self.write_sam_record(reference_sequences, &record)
+    }
+
+    fn finish(&mut self, _: &sam::Header) -> io::Result<()> {
+        self.try_finish()
+    }
+}
+
 impl<W> From<W> for Writer<W> {
     fn from(inner: W) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            observer: None,
+        }
     }
 }
 
@@ -361,6 +447,214 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_write_sam_record_matches_converted_record() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::{
+            header::ReferenceSequence,
+            record::{
+                data::{field::Tag, Field},
+                Flags, Position,
+            },
+        };
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .build();
+        let reference_sequences = header.reference_sequences();
+
+        let sam_record = sam::Record::builder()
+            .set_read_name("r0".parse()?)
+            .set_flags(Flags::empty())
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::try_from(2)?)
+            .set_cigar("2M1I1M".parse()?)
+            .set_sequence("ACGT".parse()?)
+            .set_quality_scores("NDLS".parse()?)
+            .set_data(sam::record::Data::try_from(vec![Field::new(
+                Tag::AlignmentHitCount,
+                sam::record::data::field::Value::Int(1),
+            )])?)
+            .build()?;
+
+        let mut expected = Writer::from(Vec::new());
+        let record = Record::try_from_sam_record(reference_sequences, &sam_record)?;
+        expected.write_record(&record)?;
+
+        let mut actual = Writer::from(Vec::new());
+        actual.write_sam_record(reference_sequences, &sam_record)?;
+
+        assert_eq!(actual.get_ref(), expected.get_ref());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_alignment_record() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::{header::ReferenceSequence, AlignmentWriter};
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .build();
+
+        let sam_record: sam::Record =
+            "r0\t99\tsq0\t2\t60\t2M1I1M\t=\t5\t8\tACGT\tNDLS\tNH:i:1".parse()?;
+        let record = Record::try_from_sam_record(header.reference_sequences(), &sam_record)?;
+
+        let mut expected = Writer::new(Vec::new());
+        expected.write_alignment_record(&header, &sam_record)?;
+        expected.try_finish()?;
+
+        let mut actual = Writer::new(Vec::new());
+        actual.write_alignment_record(&header, &record)?;
+        actual.try_finish()?;
+
+        assert_eq!(actual.get_ref().get_ref(), expected.get_ref().get_ref());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_sam_record_with_long_cigar() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::{
+            header::ReferenceSequence,
+            record::{
+                cigar::{op::Kind, Op},
+                Cigar, Flags, Position,
+            },
+        };
+
+        use crate::{lazy, record::long_cigar::MAX_CIGAR_OP_COUNT};
+
+        let n = MAX_CIGAR_OP_COUNT + 1;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 1 << 20)?)
+            .build();
+        let reference_sequences = header.reference_sequences();
+
+        let sam_record = sam::Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::try_from(1)?)
+            .set_cigar(Cigar::from(vec![Op::new(Kind::Match, 1); n]))
+            .set_sequence("A".repeat(n).parse()?)
+            .build()?;
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_sam_record(reference_sequences, &sam_record)?;
+
+        let record = Record::try_from_sam_record(reference_sequences, &sam_record)?;
+        assert_eq!(record.cigar().len(), n);
+        assert!(record.data().is_empty());
+        writer.write_record(&record)?;
+
+        let data = writer.into_inner().finish()?;
+        let mut reader = Reader::new(&data[..]);
+
+        // The CIGAR is stored as a placeholder (`kSmN`) with the real CIGAR in the CG data field.
+        let mut lazy_record = lazy::Record::default();
+        reader.read_lazy_record(&mut lazy_record)?;
+        let raw_cigar: Vec<_> = record
+            .cigar()
+            .as_ref()
+            .iter()
+            .flat_map(|raw_op| raw_op.to_le_bytes())
+            .collect();
+        assert_eq!(lazy_record.cigar(), &raw_cigar[..]);
+        assert_eq!(&lazy_record.data()[..4], b"CGBI");
+
+        let mut actual = Record::default();
+        reader.read_record(&mut actual)?;
+        assert_eq!(actual, record);
+        assert_eq!(actual.try_into_sam_record(reference_sequences)?, sam_record);
+
+        assert_eq!(Record::try_from(lazy_record)?, record);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_worker_pool() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(2).unwrap());
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_worker_pool(worker_pool.clone())
+            .build();
+
+        let mut unpooled_writer = Writer::new(Vec::new());
+
+        let mut expected = Vec::new();
+
+        for i in 0..4096 {
+            let sam_record = sam::Record::builder()
+                .set_read_name(format!("r{}", i).parse()?)
+                .build()?;
+
+            let record = Record::try_from_sam_record(&Default::default(), &sam_record)?;
+            writer.write_record(&record)?;
+            unpooled_writer.write_record(&record)?;
+            expected.push(record);
+        }
+
+        writer.try_finish()?;
+        unpooled_writer.try_finish()?;
+
+        let data = writer.get_ref().get_ref();
+        assert_eq!(data, unpooled_writer.get_ref().get_ref());
+
+        let mut reader = Reader::builder(&data[..])
+            .set_worker_pool(worker_pool)
+            .build();
+
+        let actual: Vec<_> = reader.records().collect::<io::Result<_>>()?;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_observer() -> Result<(), Box<dyn std::error::Error>> {
+        use bgzf::observer::Counter;
+
+        let counter = Arc::new(Counter::default());
+        let mut writer = Writer::builder(Vec::new())
+            .set_observer(counter.clone())
+            .build();
+
+        let header = sam::Header::default();
+        writer.write_alignment_header(&header)?;
+
+        for _ in 0..3 {
+            writer.write_record(&Record::default())?;
+        }
+
+        writer.try_finish()?;
+
+        assert_eq!(counter.record_count(), 3);
+        assert_eq!(counter.block_count(), 2);
+
+        let data = writer.get_ref().get_ref();
+        assert_eq!(counter.compressed_bytes(), data.len() as u64);
+
+        let counter = Arc::new(Counter::default());
+        let mut reader = Reader::builder(&data[..])
+            .set_observer(counter.clone())
+            .build();
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        for result in reader.records() {
+            result?;
+        }
+
+        assert_eq!(counter.record_count(), 3);
+        assert_eq!(counter.compressed_bytes(), data.len() as u64);
+
+        Ok(())
+    }
+
     #[test]
     fn test_write_sam_record_with_sequence_length_less_than_quality_scores_length(
     ) -> Result<(), Box<dyn std::error::Error>> {
@@ -0,0 +1,196 @@
+use std::{io::Write, num::NonZeroUsize, sync::Arc};
+
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+
+use super::Writer;
+
+/// A BAM writer builder.
+pub struct Builder<W> {
+    inner: W,
+    compression_level: Option<CompressionLevel>,
+    worker_pool: Option<bgzf::WorkerPool>,
+    worker_count: Option<NonZeroUsize>,
+    observer: Option<Arc<dyn bgzf::Observer>>,
+}
+
+impl<W> Builder<W>
+where
+    W: Write,
+{
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            compression_level: None,
+            worker_pool: None,
+            worker_count: None,
+            observer: None,
+        }
+    }
+
+    /// Sets a compression level.
+    ///
+    /// A compression level of 0 ([`CompressionLevel::none`]) writes an uncompressed BAM, e.g., for
+    /// piping to another tool. By default, the compression level is set to level 6. See
+    /// [`bgzf::writer::Builder::set_compression_level`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf::writer::CompressionLevel;
+    ///
+    /// let builder = bam::Writer::builder(Vec::new())
+    ///     .set_compression_level(CompressionLevel::none());
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = Some(compression_level);
+        self
+    }
+
+    /// Sets a worker pool.
+    ///
+    /// The worker pool is used to compress BGZF blocks in parallel and replaces any worker count
+    /// previously set. See [`bgzf::writer::Builder::set_worker_pool`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(4).unwrap());
+    /// let builder = bam::Writer::builder(Vec::new()).set_worker_pool(worker_pool);
+    /// ```
+    pub fn set_worker_pool(mut self, worker_pool: bgzf::WorkerPool) -> Self {
+        self.worker_pool = Some(worker_pool);
+        self.worker_count = None;
+        self
+    }
+
+    /// Sets a worker count.
+    ///
+    /// This compresses BGZF blocks in parallel on a worker pool owned by the writer and replaces
+    /// any worker pool previously set. See [`bgzf::writer::Builder::set_worker_count`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bam as bam;
+    ///
+    /// let worker_count = NonZeroUsize::new(4).unwrap();
+    /// let builder = bam::Writer::builder(Vec::new()).set_worker_count(worker_count);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = Some(worker_count);
+        self.worker_pool = None;
+        self
+    }
+
+    /// Sets a progress observer.
+    ///
+    /// The observer is notified after each BGZF block and record is written. See
+    /// [`bgzf::writer::Builder::set_observer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf::observer::Counter;
+    ///
+    /// let builder = bam::Writer::builder(Vec::new()).set_observer(Arc::new(Counter::default()));
+    /// ```
+    pub fn set_observer(mut self, observer: Arc<dyn bgzf::Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Builds a BAM writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::Writer::builder(Vec::new()).build();
+    /// ```
+    pub fn build(self) -> Writer<bgzf::Writer<W>> {
+        let mut builder = bgzf::Writer::builder(self.inner);
+
+        if let Some(compression_level) = self.compression_level {
+            builder = builder.set_compression_level(compression_level);
+        }
+
+        if let Some(worker_pool) = self.worker_pool {
+            builder = builder.set_worker_pool(worker_pool);
+        }
+
+        if let Some(worker_count) = self.worker_count {
+            builder = builder.set_worker_count(worker_count);
+        }
+
+        if let Some(observer) = self.observer.clone() {
+            builder = builder.set_observer(observer);
+        }
+
+        let mut writer = Writer::from(builder.build());
+        writer.observer = self.observer;
+        writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+    use crate::{Reader, Record};
+
+    #[test]
+    fn test_build_with_compression_level() -> io::Result<()> {
+        fn write(compression_level: CompressionLevel) -> io::Result<Vec<u8>> {
+            let mut writer = Builder::new(Vec::new())
+                .set_compression_level(compression_level)
+                .build();
+
+            for _ in 0..64 {
+                writer.write_record(&Record::default())?;
+            }
+
+            writer.into_inner().finish()
+        }
+
+        let uncompressed_data = write(CompressionLevel::none())?;
+        let compressed_data = write(CompressionLevel::best())?;
+        assert!(uncompressed_data.len() > compressed_data.len());
+
+        for data in [uncompressed_data, compressed_data] {
+            let mut reader = Reader::new(&data[..]);
+            let records: Vec<_> = reader.records().collect::<io::Result<_>>()?;
+            assert_eq!(records.len(), 64);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_worker_count() -> io::Result<()> {
+        let worker_count = NonZeroUsize::new(2).unwrap();
+        let mut writer = Builder::new(Vec::new())
+            .set_worker_count(worker_count)
+            .build();
+
+        for _ in 0..64 {
+            writer.write_record(&Record::default())?;
+        }
+
+        let data = writer.into_inner().finish()?;
+
+        let mut reader = Reader::new(&data[..]);
+        let records: Vec<_> = reader.records().collect::<io::Result<_>>()?;
+        assert_eq!(records.len(), 64);
+
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
-use std::io::{self, Write};
+use std::{
+    io::{self, Write},
+    mem,
+};
 
 use byteorder::{LittleEndian, WriteBytesExt};
 use noodles_sam as sam;
@@ -9,12 +12,27 @@ use crate::{
     Record,
 };
 
-pub(super) fn write_record<W>(writer: &mut W, record: &Record) -> io::Result<()>
+pub(crate) fn write_record<W>(writer: &mut W, record: &Record) -> io::Result<()>
 where
     W: Write,
 {
-    let block_size = u32::try_from(record.block_size())
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    use crate::record::long_cigar::encode_record_cigar;
+
+    // A CIGAR with too many operations is replaced by a placeholder and stored in the `CG` data
+    // field.
+    let long_cigar = encode_record_cigar(record)?;
+
+    let (cigar, cigar_data): (&Cigar, &[u8]) = match &long_cigar {
+        Some((placeholder, cigar_data)) => (placeholder, cigar_data),
+        None => (record.cigar(), &[]),
+    };
+
+    let block_size = record.block_size() - mem::size_of::<u32>() * record.cigar().len()
+        + mem::size_of::<u32>() * cigar.len()
+        + cigar_data.len();
+
+    let block_size =
+        u32::try_from(block_size).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
     writer.write_u32::<LittleEndian>(block_size)?;
 
     // ref_id
@@ -32,8 +50,8 @@ where
 
     writer.write_u16::<LittleEndian>(record.bin())?;
 
-    let n_cigar_op = u16::try_from(record.cigar().len())
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let n_cigar_op =
+        u16::try_from(cigar.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
     writer.write_u16::<LittleEndian>(n_cigar_op)?;
 
     let flag = u16::from(record.flags());
@@ -53,7 +71,7 @@ where
 
     writer.write_all(&record.read_name)?;
 
-    write_cigar(writer, record.cigar())?;
+    write_cigar(writer, cigar)?;
 
     let sequence = record.sequence();
     let quality_scores = record.quality_scores();
@@ -78,6 +96,7 @@ where
     }
 
     writer.write_all(record.data().as_ref())?;
+    writer.write_all(cigar_data)?;
 
     Ok(())
 }
@@ -227,4 +246,41 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_record_with_modified_data() -> io::Result<()> {
+        use sam::record::data::field::Tag;
+
+        use crate::record::data::{field::Value, Field};
+
+        let mut record = Record::default();
+
+        let data = record.data_mut();
+        data.insert(Field::new(Tag::AlignmentHitCount, Value::UInt8(1)))
+            .transpose()?;
+        data.insert(Field::new(
+            Tag::ReadGroup,
+            Value::String(String::from("rg0")),
+        ));
+        data.insert(Field::new(Tag::AlignmentScore, Value::UInt8(13)))
+            .transpose()?;
+        data.remove(Tag::AlignmentHitCount).transpose()?;
+        data.insert(Field::new(Tag::AlignmentScore, Value::UInt8(8)))
+            .transpose()?;
+
+        let mut buf = Vec::new();
+        write_record(&mut buf, &record)?;
+
+        let (block_size, rest) = buf.split_at(4);
+        assert_eq!(block_size, [0x2d, 0x00, 0x00, 0x00]); // block_size = 45
+        assert_eq!(
+            &rest[34..],
+            [
+                b'R', b'G', b'Z', b'r', b'g', b'0', 0x00, // data[0] = RG:Z:rg0
+                b'A', b'S', b'C', 0x08, // data[1] = AS:C:8
+            ]
+        );
+
+        Ok(())
+    }
 }
@@ -11,7 +11,7 @@ use noodles_sam::{
     record::{Cigar, Data, QualityScores, Sequence},
 };
 
-use crate::record::sequence::Base;
+use crate::record::{long_cigar::encode_sam_record_cigar, sequence::Base};
 
 // § 4.2 The BAM format (2021-06-03)
 //
@@ -22,6 +22,24 @@ const BLOCK_HEADER_SIZE: u32 = 32;
 // § 4.2.3 SEQ and QUAL encoding (2021-06-03)
 pub(crate) const NULL_QUALITY_SCORE: u8 = 255;
 
+pub(crate) fn calculate_block_size(
+    l_read_name: u8,
+    n_cigar_op: u16,
+    l_seq: u32,
+    data_len: u32,
+) -> io::Result<u32> {
+    // The sequence is 4-bit encoded, and the quality scores are always written.
+    let seq_len = u64::from(l_seq).div_ceil(2) + u64::from(l_seq);
+
+    let block_size = u64::from(BLOCK_HEADER_SIZE)
+        + u64::from(l_read_name)
+        + 4 * u64::from(n_cigar_op)
+        + seq_len
+        + u64::from(data_len);
+
+    u32::try_from(block_size).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
 pub fn write_sam_record<W>(
     writer: &mut W,
     reference_sequences: &ReferenceSequences,
@@ -38,22 +56,28 @@ where
     let l_read_name = u8::try_from(read_name.len())
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
 
-    let n_cigar_op = u16::try_from(record.cigar().len())
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    // A CIGAR with too many operations is replaced by a placeholder and stored in the `CG` data
+    // field.
+    let long_cigar = encode_sam_record_cigar(record)?;
+
+    let (n_cigar_op, cigar_data) = match &long_cigar {
+        Some((placeholder, cigar_data)) => (placeholder.len(), &cigar_data[..]),
+        None => (record.cigar().len(), &[][..]),
+    };
+
+    let n_cigar_op =
+        u16::try_from(n_cigar_op).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
 
     let l_seq = u32::try_from(record.sequence().len())
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
 
-    let data_len = calculate_data_len(record.data()).and_then(|len| {
-        u32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
-    })?;
+    let data_len = calculate_data_len(record.data())
+        .map(|len| len + cigar_data.len())
+        .and_then(|len| {
+            u32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+        })?;
 
-    let block_size = BLOCK_HEADER_SIZE
-        + u32::from(l_read_name)
-        + (4 * u32::from(n_cigar_op))
-        + ((l_seq + 1) / 2)
-        + l_seq
-        + data_len;
+    let block_size = calculate_block_size(l_read_name, n_cigar_op, l_seq, data_len)?;
     writer.write_u32::<LittleEndian>(block_size)?;
 
     // ref_id
@@ -96,7 +120,14 @@ where
 
     writer.write_all(read_name)?;
 
-    write_cigar(writer, record.cigar())?;
+    match &long_cigar {
+        Some((placeholder, _)) => {
+            for &raw_op in placeholder.as_ref() {
+                writer.write_u32::<LittleEndian>(raw_op)?;
+            }
+        }
+        None => write_cigar(writer, record.cigar())?,
+    }
 
     // § 4.2.3 SEQ and QUAL encoding (2021-06-03)
     let sequence = record.sequence();
@@ -122,6 +153,7 @@ where
     }
 
     write_data(writer, record.data())?;
+    writer.write_all(cigar_data)?;
 
     Ok(())
 }
@@ -813,6 +845,14 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_calculate_block_size() -> io::Result<()> {
+        assert_eq!(calculate_block_size(2, 0, 0, 0)?, 34);
+        assert_eq!(calculate_block_size(3, 1, 4, 8)?, 53);
+        assert!(calculate_block_size(2, 0, u32::MAX, 0).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_region_to_bin() {
         // § 5.3 C source code for computing bin number and overlapping bins (2021-01-07)
@@ -23,7 +23,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     writer.write_header(&header)?;
 
-    let string_map = StringMap::from(&header);
+    let string_map = StringMap::try_from(&header)?;
 
     let record = vcf::Record::builder()
         .set_chromosome("sq0".parse()?)
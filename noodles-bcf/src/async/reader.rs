@@ -1,12 +1,17 @@
 mod builder;
+mod query;
 
 pub use self::builder::Builder;
 
 use futures::{stream, Stream};
 use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi::{binning_index::ReferenceSequenceExt, BinningIndex};
+use noodles_vcf::header::Contigs;
 use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncSeek};
 
-use crate::Record;
+use self::query::query;
+use crate::{error::c_str_to_string, reader::resolve_region, Error, Record};
 
 /// An async BCF reader.
 ///
@@ -251,6 +256,50 @@ where
     pub async fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
         self.inner.seek(pos).await
     }
+
+    /// Returns a stream over records that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::TryStreamExt;
+    /// use noodles_bcf as bcf;
+    /// use noodles_core::Region;
+    /// use noodles_csi as csi;
+    /// use noodles_vcf as vcf;
+    /// use tokio::fs::File;
+    ///
+    /// let mut reader = File::open("sample.bcf").await.map(bcf::AsyncReader::new)?;
+    /// reader.read_file_format().await?;
+    ///
+    /// let header: vcf::Header = reader.read_header().await?.parse()?;
+    ///
+    /// let index = csi::r#async::read("sample.bcf.csi").await?;
+    /// let region = Region::mapped("sq0", 8..=13);
+    /// let mut query = reader.query(header.contigs(), &index, &region)?;
+    ///
+    /// while let Some(record) = query.try_next().await? {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn query<I, RS>(
+        &mut self,
+        contigs: &Contigs,
+        index: &I,
+        region: &Region,
+    ) -> io::Result<impl Stream<Item = io::Result<Record>> + '_>
+    where
+        I: BinningIndex<RS>,
+        RS: ReferenceSequenceExt,
+    {
+        let (chromosome_id, interval) = resolve_region(contigs, region)?;
+        let chunks = index.query(chromosome_id, interval)?;
+        Ok(query(self, chunks, chromosome_id, interval))
+    }
 }
 
 async fn read_magic<R>(reader: &mut R) -> io::Result<()>
@@ -265,10 +314,7 @@ where
     if magic == MAGIC_NUMBER {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "invalid BCF header",
-        ))
+        Err(Error::InvalidMagicNumber(magic).into())
     }
 }
 
@@ -314,8 +360,14 @@ where
 
     buf.resize(l_shared, Default::default());
     reader.read_exact(buf).await?;
-    let mut reader = &buf[..];
-    let (n_fmt, n_sample) = read_site(&mut reader, record)?;
+    let mut src = &buf[..];
+    let (n_fmt, n_sample) = read_site(&mut src, record).map_err(|e| {
+        io::Error::from(Error::InvalidRecord {
+            record_number: None,
+            offset: l_shared - src.len(),
+            source: e,
+        })
+    })?;
 
     let genotypes = record.genotypes_mut().as_mut();
     genotypes.resize(l_indiv, Default::default());
@@ -326,19 +378,6 @@ where
     Ok(l_shared + l_indiv)
 }
 
-fn c_str_to_string(buf: &[u8]) -> io::Result<String> {
-    use std::ffi::CStr;
-
-    CStr::from_bytes_with_nul(buf)
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-        .and_then(|c_header| {
-            c_header
-                .to_str()
-                .map(|s| s.into())
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-        })
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -384,4 +423,80 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        use futures::TryStreamExt;
+        use noodles_csi::{
+            self as csi,
+            index::{
+                reference_sequence::bin::{Bin, Chunk},
+                ReferenceSequence,
+            },
+        };
+        use noodles_vcf::{self as vcf, header::Contig, record::Position};
+
+        use crate::{header::StringMap, AsyncWriter};
+
+        let header = vcf::Header::builder()
+            .add_contig(Contig::new("sq0"))
+            .add_contig(Contig::new("sq1"))
+            .build();
+
+        let string_map = StringMap::try_from(&header)?;
+
+        let mut data = Vec::new();
+        let mut writer = AsyncWriter::new(&mut data);
+        writer.write_file_format().await?;
+        writer.write_header(&header).await?;
+
+        for (chromosome, position) in [("sq0", 5), ("sq0", 8), ("sq0", 13), ("sq0", 21), ("sq1", 8)]
+        {
+            let record = vcf::Record::builder()
+                .set_chromosome(chromosome.parse()?)
+                .set_position(Position::try_from(position)?)
+                .set_reference_bases("A".parse()?)
+                .build()?;
+
+            writer
+                .write_vcf_record(&header, &string_map, &record)
+                .await?;
+        }
+
+        writer.shutdown().await?;
+        drop(writer);
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        reader.read_file_format().await?;
+        reader.read_header().await?;
+
+        let start = reader.virtual_position();
+        let _: Vec<_> = reader.records().try_collect().await?;
+        let end = reader.virtual_position();
+
+        // Both reference sequences point to a single chunk of all records in the root bin.
+        let reference_sequence =
+            ReferenceSequence::new(vec![Bin::new(0, start, vec![Chunk::new(start, end)])], None);
+
+        let index = csi::Index::builder()
+            .set_reference_sequences(vec![reference_sequence.clone(), reference_sequence])
+            .build();
+
+        let region = "sq0:8-13".parse()?;
+        let records: Vec<_> = reader
+            .query(header.contigs(), &index, &region)?
+            .try_collect()
+            .await?;
+
+        let positions: Vec<_> = records
+            .iter()
+            .map(|record| (record.chromosome_id(), i32::from(record.position())))
+            .collect();
+
+        assert_eq!(positions, [(0, 8), (0, 13)]);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,215 @@
+use noodles_bgzf as bgzf;
+use noodles_vcf as vcf;
+use tokio::io::{self, AsyncWrite, AsyncWriteExt};
+
+use crate::{header::StringMap, writer, Record};
+
+/// An async BCF writer.
+pub struct Writer<W>
+where
+    W: AsyncWrite,
+{
+    inner: bgzf::AsyncWriter<W>,
+}
+
+impl<W> Writer<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Creates an async BCF writer with a default compression level.
+    ///
+    /// The given stream is wrapped in a BGZF encoder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let writer = bcf::AsyncWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: bgzf::AsyncWriter::new(inner),
+        }
+    }
+
+    /// Shuts down the output stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bcf as bcf;
+    /// let mut writer = bcf::AsyncWriter::new(Vec::new());
+    /// writer.shutdown().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(&mut self) -> io::Result<()> {
+        self.inner.shutdown().await
+    }
+
+    /// Writes a BCF file format.
+    ///
+    /// All BCF files start with the magic number "BCF" and the file format version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bcf as bcf;
+    /// let mut writer = bcf::AsyncWriter::new(Vec::new());
+    /// writer.write_file_format().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_file_format(&mut self) -> io::Result<()> {
+        let mut buf = Vec::new();
+        writer::write_file_format(&mut buf)?;
+        self.inner.write_all(&buf).await
+    }
+
+    /// Writes a VCF header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bcf as bcf;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut writer = bcf::AsyncWriter::new(Vec::new());
+    ///
+    /// let header = vcf::Header::default();
+    /// writer.write_header(&header).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_header(&mut self, header: &vcf::Header) -> io::Result<()> {
+        let mut buf = Vec::new();
+        writer::write_header(&mut buf, header)?;
+        self.inner.write_all(&buf).await
+    }
+
+    /// Writes a record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bcf as bcf;
+    /// let mut writer = bcf::AsyncWriter::new(Vec::new());
+    /// let record = bcf::Record::default();
+    /// writer.write_record(&record).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        let mut buf = Vec::new();
+        writer::record::write_record(&mut buf, record)?;
+        self.inner.write_all(&buf).await
+    }
+
+    /// Writes a VCF record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use noodles_bcf::{self as bcf, header::StringMap};
+    /// use noodles_vcf::{self as vcf, header::Contig, record::Position};
+    ///
+    /// let mut writer = bcf::AsyncWriter::new(Vec::new());
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_contig(Contig::new("sq0"))
+    ///     .build();
+    ///
+    /// writer.write_header(&header).await?;
+    ///
+    /// let string_map = StringMap::try_from(&header)?;
+    ///
+    /// let record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse()?)
+    ///     .set_position(Position::try_from(8)?)
+    ///     .set_reference_bases("A".parse()?)
+    ///     .build()?;
+    ///
+    /// writer.write_vcf_record(&header, &string_map, &record).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_vcf_record(
+        &mut self,
+        header: &vcf::Header,
+        string_map: &StringMap,
+        record: &vcf::Record,
+    ) -> io::Result<()> {
+        let mut buf = Vec::new();
+        writer::vcf_record::write_vcf_record(&mut buf, header, string_map, record)?;
+        self.inner.write_all(&buf).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AsyncReader;
+
+    #[tokio::test]
+    async fn test_write_vcf_record() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_vcf::{header::Contig, record::Position};
+
+        let header = vcf::Header::builder()
+            .add_contig(Contig::new("sq0"))
+            .add_contig(Contig::new("sq1"))
+            .build();
+
+        let string_map = StringMap::try_from(&header)?;
+
+        let mut data = Vec::new();
+        let mut writer = Writer::new(&mut data);
+        writer.write_file_format().await?;
+        writer.write_header(&header).await?;
+
+        let record = vcf::Record::builder()
+            .set_chromosome("sq1".parse()?)
+            .set_position(Position::try_from(8)?)
+            .set_reference_bases("A".parse()?)
+            .build()?;
+
+        writer
+            .write_vcf_record(&header, &string_map, &record)
+            .await?;
+
+        writer.shutdown().await?;
+        drop(writer);
+
+        let mut reader = AsyncReader::new(&data[..]);
+        assert_eq!(reader.read_file_format().await?, (2, 2));
+
+        let actual_header: vcf::Header = reader.read_header().await?.parse()?;
+        assert_eq!(actual_header, header);
+
+        let mut record = Record::default();
+        assert!(reader.read_record(&mut record).await? > 0);
+        assert_eq!(record.chromosome_id(), 1);
+        assert_eq!(i32::from(record.position()), 8);
+
+        assert_eq!(reader.read_record(&mut record).await?, 0);
+
+        Ok(())
+    }
+}
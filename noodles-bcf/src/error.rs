@@ -0,0 +1,147 @@
+//! BCF errors.
+
+use std::{error, ffi, fmt, io, str};
+
+/// An error returned when BCF data fails to be read.
+///
+/// Readers continue to return [`std::io::Error`]s, but for invalid data, the error is wrapped in
+/// an `io::Error` with the kind [`std::io::ErrorKind::InvalidData`]. The cause can be inspected by
+/// downcasting the inner error.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bcf as bcf;
+///
+/// let data = b"BAM\x01";
+/// let mut reader = bcf::Reader::from(&data[..]);
+///
+/// let e = reader.read_file_format().unwrap_err();
+///
+/// assert!(matches!(
+///     e.get_ref().and_then(|e| e.downcast_ref::<bcf::Error>()),
+///     Some(bcf::Error::InvalidMagicNumber(_))
+/// ));
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The magic number is invalid.
+    InvalidMagicNumber([u8; 3]),
+    /// The header text is not NUL-terminated or has an interior NUL.
+    InvalidHeaderTextNul(ffi::FromBytesWithNulError),
+    /// The header text is not valid UTF-8.
+    InvalidHeaderText(str::Utf8Error),
+    /// A record is invalid.
+    InvalidRecord {
+        /// The 0-based number of the record from where reading started, if known.
+        record_number: Option<u64>,
+        /// The byte offset within the shared record data where decoding failed.
+        offset: usize,
+        /// The cause.
+        source: io::Error,
+    },
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidMagicNumber(_) => None,
+            Self::InvalidHeaderTextNul(e) => Some(e),
+            Self::InvalidHeaderText(e) => Some(e),
+            Self::InvalidRecord { source, .. } => Some(source),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagicNumber(actual) => {
+                write!(f, "invalid magic number: expected BCF, got {:?}", actual)
+            }
+            Self::InvalidHeaderTextNul(_) => f.write_str("invalid header text: invalid NUL"),
+            Self::InvalidHeaderText(_) => f.write_str("invalid header text"),
+            Self::InvalidRecord {
+                record_number,
+                offset,
+                ..
+            } => {
+                f.write_str("invalid record")?;
+
+                if let Some(n) = record_number {
+                    write!(f, " {}", n)?;
+                }
+
+                write!(f, " at offset {}", offset)
+            }
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        Self::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+// Converts a NUL-terminated header text to a string.
+pub(crate) fn c_str_to_string(buf: &[u8]) -> io::Result<String> {
+    let c_str = ffi::CStr::from_bytes_with_nul(buf).map_err(Error::InvalidHeaderTextNul)?;
+
+    c_str
+        .to_str()
+        .map(|s| s.into())
+        .map_err(|e| Error::InvalidHeaderText(e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(
+            Error::InvalidMagicNumber(*b"BAM").to_string(),
+            "invalid magic number: expected BCF, got [66, 65, 77]"
+        );
+
+        let error = Error::InvalidRecord {
+            record_number: Some(8),
+            offset: 13,
+            source: io::Error::from(io::ErrorKind::UnexpectedEof),
+        };
+        assert_eq!(error.to_string(), "invalid record 8 at offset 13");
+
+        let error = Error::InvalidRecord {
+            record_number: None,
+            offset: 13,
+            source: io::Error::from(io::ErrorKind::UnexpectedEof),
+        };
+        assert_eq!(error.to_string(), "invalid record at offset 13");
+    }
+
+    #[test]
+    fn test_c_str_to_string() -> io::Result<()> {
+        assert_eq!(c_str_to_string(b"noodles\x00")?, "noodles");
+
+        assert!(matches!(
+            c_str_to_string(b"noodles"),
+            Err(e) if matches!(
+                e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+                Some(Error::InvalidHeaderTextNul(_))
+            )
+        ));
+
+        assert!(matches!(
+            c_str_to_string(b"\xf0\x00"),
+            Err(e) if matches!(
+                e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+                Some(Error::InvalidHeaderText(_))
+            )
+        ));
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 //! BCF header.
 
-mod string_map;
+pub mod string_map;
+pub mod string_maps;
 
-pub use self::string_map::StringMap;
+pub use self::{string_map::StringMap, string_maps::StringMaps};
@@ -1,25 +1,159 @@
+//! BCF header string map.
+
 use std::{
-    ops::Deref,
+    collections::HashMap,
+    error, fmt,
     str::{FromStr, Lines},
 };
 
-use indexmap::IndexSet;
 use noodles_vcf::{
     self as vcf,
-    header::{Filter, Format, Info, ParseError, Record},
+    header::{Filter, Format, Info, Record},
 };
 
+// The smallest limit of an explicit index (`IDX`).
+const MIN_IDX_LIMIT: usize = 1 << 16;
+
+// The number of positions per header entry that explicit indices (`IDX`) are allowed to span.
+const IDX_LIMIT_FACTOR: usize = 4;
+
 /// An indexed map of VCF strings.
 ///
 /// This is also called a dictionary of strings.
 ///
 /// See § 6.2.1 Dictionary of strings (2021-05-13).
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct StringMap(IndexSet<String>);
+pub struct StringMap {
+    indices: HashMap<String, usize>,
+    entries: Vec<Option<String>>,
+}
 
 impl StringMap {
+    /// Returns an entry by index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::header::StringMap;
+    /// let string_map = StringMap::default();
+    /// assert_eq!(string_map.get_index(0), Some("PASS"));
+    /// assert!(string_map.get_index(1).is_none());
+    /// ```
+    pub fn get_index(&self, i: usize) -> Option<&str> {
+        self.entries.get(i).and_then(|entry| entry.as_deref())
+    }
+
+    /// Returns the index of the entry of the given value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::header::StringMap;
+    /// let string_map = StringMap::default();
+    /// assert_eq!(string_map.get_index_of("PASS"), Some(0));
+    /// assert!(string_map.get_index_of("DP").is_none());
+    /// ```
+    pub fn get_index_of(&self, value: &str) -> Option<usize> {
+        self.indices.get(value).copied()
+    }
+
     fn insert(&mut self, value: String) {
-        self.0.insert(value);
+        if !self.indices.contains_key(&value) {
+            let i = self.entries.len();
+            self.insert_at(i, value);
+        }
+    }
+
+    fn insert_at(&mut self, i: usize, value: String) {
+        if i >= self.entries.len() {
+            self.entries.resize(i + 1, None);
+        }
+
+        self.entries[i] = Some(value.clone());
+        self.indices.insert(value, i);
+    }
+
+    // Creates a string map with no entries, i.e., without the implicit `PASS` entry.
+    pub(crate) fn empty() -> Self {
+        Self {
+            indices: HashMap::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    // Inserts an entry, validating that it does not conflict with existing entries.
+    //
+    // An entry with an explicit index (`IDX`) must be less than `idx_limit` (see [`idx_limit`]),
+    // must not collide with a different entry at the same position, and the same entry must not
+    // be given different positions.
+    pub(crate) fn try_insert_with_idx(
+        &mut self,
+        idx: Option<usize>,
+        value: String,
+        idx_limit: usize,
+    ) -> Result<(), InsertError> {
+        match (idx, self.get_index_of(&value)) {
+            (Some(i), Some(j)) if i != j => Err(InsertError::PositionMismatch(value, j, i)),
+            (Some(_), Some(_)) | (None, Some(_)) => Ok(()),
+            (Some(i), None) if i >= idx_limit => Err(InsertError::PositionOutOfRange(i)),
+            (Some(i), None) => match self.get_index(i) {
+                Some(entry) => Err(InsertError::Collision(i, entry.into(), value)),
+                None => {
+                    self.insert_at(i, value);
+                    Ok(())
+                }
+            },
+            (None, None) => {
+                self.insert(value);
+                Ok(())
+            }
+        }
+    }
+}
+
+// Returns the limit of explicit indices (`IDX`) in a dictionary built from the given number of
+// header entries.
+//
+// Indices can be sparse, e.g., after header records are removed, but an index far beyond the
+// number of entries is invalid and would otherwise allocate an arbitrarily large dictionary.
+pub(crate) fn idx_limit(entry_count: usize) -> usize {
+    entry_count
+        .saturating_mul(IDX_LIMIT_FACTOR)
+        .max(MIN_IDX_LIMIT)
+}
+
+/// An error returned when an entry fails to be inserted into a string map.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InsertError {
+    /// The position of the entry is already occupied by a different entry.
+    ///
+    /// This includes the position and the existing and new entries.
+    Collision(usize, String, String),
+    /// The entry already exists at a different position.
+    ///
+    /// This includes the entry and the existing and new positions.
+    PositionMismatch(String, usize, usize),
+    /// The position of the entry is out of range.
+    PositionOutOfRange(usize),
+}
+
+impl error::Error for InsertError {}
+
+impl fmt::Display for InsertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Collision(i, actual, expected) => write!(
+                f,
+                "string map collision at position {}: expected {}, got {}",
+                i, expected, actual
+            ),
+            Self::PositionMismatch(value, actual, expected) => write!(
+                f,
+                "string map position mismatch for {}: expected {}, got {}",
+                value, expected, actual
+            ),
+            Self::PositionOutOfRange(i) => write!(f, "string map position out of range: {}", i),
+        }
     }
 }
 
@@ -27,15 +161,32 @@ impl Default for StringMap {
     fn default() -> Self {
         // § 6.2.1 Dictionary of strings (2021-01-13): "Note that 'PASS' is always implicitly
         // encoded as the first entry in the header dictionary."
-        Self([Filter::pass().id().into()].into_iter().collect())
+        let pass = Filter::pass().id().to_string();
+
+        Self {
+            indices: [(pass.clone(), 0)].into_iter().collect(),
+            entries: vec![Some(pass)],
+        }
     }
 }
 
-impl Deref for StringMap {
-    type Target = IndexSet<String>;
+/// An error returned when a raw BCF string map fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The VCF header is invalid.
+    InvalidHeader(vcf::header::ParseError),
+    /// The string map is invalid.
+    InvalidStringMap(InsertError),
+}
+
+impl error::Error for ParseError {}
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader(e) => write!(f, "invalid header: {}", e),
+            Self::InvalidStringMap(e) => write!(f, "invalid string map: {}", e),
+        }
     }
 }
 
@@ -43,36 +194,23 @@ impl FromStr for StringMap {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use vcf::header::record::Key;
-
         let mut string_map = StringMap::default();
+        let idx_limit = idx_limit(s.lines().count());
 
         let mut lines = s.lines();
-        let file_format = parse_file_format(&mut lines)?;
+        let file_format = parse_file_format(&mut lines).map_err(ParseError::InvalidHeader)?;
 
         for line in &mut lines {
             if line.starts_with("#CHROM") {
                 break;
             }
 
-            let record: Record = line.parse().map_err(ParseError::InvalidRecord)?;
-
-            match record.key() {
-                Key::Filter => {
-                    let filter = Filter::try_from(record).map_err(ParseError::InvalidFilter)?;
-                    string_map.insert(filter.id().into());
-                }
-                Key::Format => {
-                    let format = Format::try_from_record_file_format(record, file_format)
-                        .map_err(ParseError::InvalidFormat)?;
-                    string_map.insert(format.id().as_ref().into());
-                }
-                Key::Info => {
-                    let info = Info::try_from_record_file_format(record, file_format)
-                        .map_err(ParseError::InvalidInfo)?;
-                    string_map.insert(info.id().as_ref().into());
-                }
-                _ => {}
+            if let Some((idx, id)) =
+                parse_entry(line, file_format).map_err(ParseError::InvalidHeader)?
+            {
+                string_map
+                    .try_insert_with_idx(idx, id, idx_limit)
+                    .map_err(ParseError::InvalidStringMap)?;
             }
         }
 
@@ -80,8 +218,40 @@ impl FromStr for StringMap {
     }
 }
 
-fn parse_file_format(lines: &mut Lines<'_>) -> Result<vcf::header::FileFormat, ParseError> {
-    use vcf::header::record::{Key, Value};
+fn parse_entry(
+    line: &str,
+    file_format: vcf::header::FileFormat,
+) -> Result<Option<(Option<usize>, String)>, vcf::header::ParseError> {
+    use vcf::header::{record::Key, ParseError};
+
+    let record: Record = line.parse().map_err(ParseError::InvalidRecord)?;
+
+    match record.key() {
+        Key::Filter => {
+            let filter = Filter::try_from(record).map_err(ParseError::InvalidFilter)?;
+            Ok(Some((filter.idx(), filter.id().into())))
+        }
+        Key::Format => {
+            let format = Format::try_from_record_file_format(record, file_format)
+                .map_err(ParseError::InvalidFormat)?;
+            Ok(Some((format.idx(), format.id().as_ref().into())))
+        }
+        Key::Info => {
+            let info = Info::try_from_record_file_format(record, file_format)
+                .map_err(ParseError::InvalidInfo)?;
+            Ok(Some((info.idx(), info.id().as_ref().into())))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn parse_file_format(
+    lines: &mut Lines<'_>,
+) -> Result<vcf::header::FileFormat, vcf::header::ParseError> {
+    use vcf::header::{
+        record::{Key, Value},
+        ParseError,
+    };
 
     let record: Record = lines
         .next()
@@ -98,23 +268,29 @@ fn parse_file_format(lines: &mut Lines<'_>) -> Result<vcf::header::FileFormat, P
     }
 }
 
-impl From<&vcf::Header> for StringMap {
-    fn from(header: &vcf::Header) -> Self {
+impl TryFrom<&vcf::Header> for StringMap {
+    type Error = InsertError;
+
+    fn try_from(header: &vcf::Header) -> Result<Self, Self::Error> {
         let mut string_map = StringMap::default();
 
+        // The implicit `PASS` entry is also counted.
+        let idx_limit =
+            idx_limit(1 + header.infos().len() + header.filters().len() + header.formats().len());
+
         for info in header.infos().values() {
-            string_map.insert(info.id().as_ref().into());
+            string_map.try_insert_with_idx(info.idx(), info.id().as_ref().into(), idx_limit)?;
         }
 
         for filter in header.filters().values() {
-            string_map.insert(filter.id().into());
+            string_map.try_insert_with_idx(filter.idx(), filter.id().into(), idx_limit)?;
         }
 
         for format in header.formats().values() {
-            string_map.insert(format.id().as_ref().into());
+            string_map.try_insert_with_idx(format.idx(), format.id().as_ref().into(), idx_limit)?;
         }
 
-        string_map
+        Ok(string_map)
     }
 }
 
@@ -122,12 +298,21 @@ impl From<&vcf::Header> for StringMap {
 mod tests {
     use super::*;
 
+    fn build_string_map(entries: &[Option<&str>]) -> StringMap {
+        let entries: Vec<_> = entries.iter().map(|e| e.map(String::from)).collect();
+
+        let indices = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| entry.clone().map(|e| (e, i)))
+            .collect();
+
+        StringMap { indices, entries }
+    }
+
     #[test]
     fn test_default() {
-        assert_eq!(
-            StringMap::default(),
-            StringMap([String::from("PASS")].into_iter().collect())
-        );
+        assert_eq!(StringMap::default(), build_string_map(&[Some("PASS")]));
     }
 
     #[test]
@@ -149,16 +334,65 @@ mod tests {
 
         assert_eq!(
             s.parse(),
-            Ok(StringMap(
-                [
-                    String::from("PASS"),
-                    String::from("DP"),
-                    String::from("NS"),
-                    String::from("q10"),
-                    String::from("GT"),
-                ]
-                .into_iter()
-                .collect()
+            Ok(build_string_map(&[
+                Some("PASS"),
+                Some("NS"),
+                Some("DP"),
+                Some("q10"),
+                Some("GT"),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_from_str_with_sparse_idx() {
+        let s = r#"##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth across samples",IDX=3>
+##FILTER=<ID=PASS,Description="All filters passed",IDX=0>
+##FILTER=<ID=q10,Description="Quality below 10",IDX=1>
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#;
+
+        assert_eq!(
+            s.parse(),
+            Ok(build_string_map(&[
+                Some("PASS"),
+                Some("q10"),
+                None,
+                Some("DP"),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_from_str_with_invalid_idx() {
+        let s = r#"##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth across samples",IDX=1>
+##FILTER=<ID=q10,Description="Quality below 10",IDX=1>
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#;
+
+        assert_eq!(
+            s.parse::<StringMap>(),
+            Err(ParseError::InvalidStringMap(InsertError::Collision(
+                1,
+                "DP".into(),
+                "q10".into()
+            )))
+        );
+
+        let s = format!(
+            r#"##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth across samples",IDX={}>
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#,
+            usize::MAX
+        );
+
+        assert_eq!(
+            s.parse::<StringMap>(),
+            Err(ParseError::InvalidStringMap(
+                InsertError::PositionOutOfRange(usize::MAX)
             ))
         );
     }
@@ -182,7 +416,7 @@ mod tests {
             .add_contig(Contig::new("sq1"))
             .add_contig(Contig::new("sq2"))
             .add_info(Info::from(InfoKey::SamplesWithDataCount))
-            .add_info(Info::from(vcf::record::info::field::Key::TotalDepth))
+            .add_info(Info::from(InfoKey::TotalDepth))
             .add_filter(Filter::pass())
             .add_filter(Filter::new("q10", "Quality below 10"))
             .add_format(Format::from(GenotypeKey::Genotype))
@@ -194,24 +428,90 @@ mod tests {
             .build();
 
         assert_eq!(
-            StringMap::from(&header),
-            StringMap(
-                [
-                    String::from("PASS"),
-                    String::from("NS"),
-                    String::from("DP"),
-                    String::from("q10"),
-                    String::from("GT"),
-                ]
-                .into_iter()
-                .collect()
-            )
+            StringMap::try_from(&header),
+            Ok(build_string_map(&[
+                Some("PASS"),
+                Some("NS"),
+                Some("DP"),
+                Some("q10"),
+                Some("GT"),
+            ]))
+        );
+
+        let mut info = Info::from(InfoKey::TotalDepth);
+        *info.idx_mut() = Some(1);
+
+        let mut filter = Filter::new("q10", "Quality below 10");
+        *filter.idx_mut() = Some(1);
+
+        let header = vcf::Header::builder()
+            .add_info(info)
+            .add_filter(filter)
+            .build();
+
+        assert_eq!(
+            StringMap::try_from(&header),
+            Err(InsertError::Collision(1, "DP".into(), "q10".into()))
         );
     }
 
+    #[test]
+    fn test_try_insert_with_idx() {
+        const IDX_LIMIT: usize = 8;
+
+        let mut string_map = StringMap::default();
+
+        assert_eq!(
+            string_map.try_insert_with_idx(Some(2), "DP".into(), IDX_LIMIT),
+            Ok(())
+        );
+        assert_eq!(
+            string_map.try_insert_with_idx(None, "DP".into(), IDX_LIMIT),
+            Ok(())
+        );
+        assert_eq!(
+            string_map.try_insert_with_idx(Some(2), "DP".into(), IDX_LIMIT),
+            Ok(())
+        );
+        assert_eq!(
+            string_map.try_insert_with_idx(None, "GT".into(), IDX_LIMIT),
+            Ok(())
+        );
+        assert_eq!(
+            string_map,
+            build_string_map(&[Some("PASS"), None, Some("DP"), Some("GT")])
+        );
+
+        assert_eq!(
+            string_map.try_insert_with_idx(Some(2), "AD".into(), IDX_LIMIT),
+            Err(InsertError::Collision(2, "DP".into(), "AD".into()))
+        );
+
+        assert_eq!(
+            string_map.try_insert_with_idx(Some(1), "DP".into(), IDX_LIMIT),
+            Err(InsertError::PositionMismatch("DP".into(), 2, 1))
+        );
+
+        assert_eq!(
+            string_map.try_insert_with_idx(Some(IDX_LIMIT), "AD".into(), IDX_LIMIT),
+            Err(InsertError::PositionOutOfRange(IDX_LIMIT))
+        );
+        assert_eq!(
+            string_map.try_insert_with_idx(Some(usize::MAX), "AD".into(), IDX_LIMIT),
+            Err(InsertError::PositionOutOfRange(usize::MAX))
+        );
+    }
+
+    #[test]
+    fn test_idx_limit() {
+        assert_eq!(idx_limit(0), MIN_IDX_LIMIT);
+        assert_eq!(idx_limit(MIN_IDX_LIMIT), MIN_IDX_LIMIT * IDX_LIMIT_FACTOR);
+        assert_eq!(idx_limit(usize::MAX), usize::MAX);
+    }
+
     #[test]
     fn test_parse_file_format() {
-        use vcf::header::FileFormat;
+        use vcf::header::{FileFormat, ParseError};
 
         let s = "##fileformat=VCFv4.3\n";
         let mut lines = s.lines();
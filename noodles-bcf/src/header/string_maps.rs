@@ -0,0 +1,196 @@
+//! BCF header string maps.
+
+use std::{error, fmt, str::FromStr};
+
+use noodles_vcf as vcf;
+
+use super::{
+    string_map::{self, InsertError},
+    StringMap,
+};
+
+/// BCF string maps.
+///
+/// These are the dictionaries built from a VCF header that are used to encode and decode BCF
+/// records: a dictionary of strings, which holds the IDs of filters, infos, and formats; and a
+/// dictionary of contigs, which holds the contig IDs, i.e., the chromosome IDs of records.
+///
+/// See § 6.2.1 Dictionary of strings and § 6.2.2 Dictionary of contigs (2021-05-13).
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bcf::header::StringMaps;
+/// use noodles_vcf::{self as vcf, header::{Contig, Filter}};
+///
+/// let header = vcf::Header::builder()
+///     .add_contig(Contig::new("sq0"))
+///     .add_contig(Contig::new("sq1"))
+///     .add_filter(Filter::new("q10", "Quality below 10"))
+///     .build();
+///
+/// let string_maps = StringMaps::try_from(&header)?;
+///
+/// assert_eq!(string_maps.strings().get_index_of("q10"), Some(1));
+/// assert_eq!(string_maps.contigs().get_index_of("sq1"), Some(1));
+/// assert_eq!(string_maps.contigs().get_index(0), Some("sq0"));
+/// # Ok::<_, noodles_bcf::header::string_map::InsertError>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StringMaps {
+    strings: StringMap,
+    contigs: StringMap,
+}
+
+impl StringMaps {
+    /// Returns the dictionary of strings.
+    ///
+    /// This maps the IDs of filters, infos, and formats to their offsets. The `PASS` filter is
+    /// always the first entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::header::StringMaps;
+    /// let string_maps = StringMaps::default();
+    /// assert_eq!(string_maps.strings().get_index(0), Some("PASS"));
+    /// ```
+    pub fn strings(&self) -> &StringMap {
+        &self.strings
+    }
+
+    /// Returns the dictionary of contigs.
+    ///
+    /// This maps the contig IDs to their offsets, i.e., reference sequence IDs (`rid`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::header::StringMaps;
+    /// let string_maps = StringMaps::default();
+    /// assert!(string_maps.contigs().get_index(0).is_none());
+    /// ```
+    pub fn contigs(&self) -> &StringMap {
+        &self.contigs
+    }
+}
+
+impl Default for StringMaps {
+    fn default() -> Self {
+        Self {
+            strings: StringMap::default(),
+            contigs: StringMap::empty(),
+        }
+    }
+}
+
+impl TryFrom<&vcf::Header> for StringMaps {
+    type Error = InsertError;
+
+    fn try_from(header: &vcf::Header) -> Result<Self, Self::Error> {
+        let strings = StringMap::try_from(header)?;
+
+        let mut contigs = StringMap::empty();
+        let idx_limit = string_map::idx_limit(header.contigs().len());
+
+        for contig in header.contigs().values() {
+            contigs.try_insert_with_idx(contig.idx(), contig.id().into(), idx_limit)?;
+        }
+
+        Ok(Self { strings, contigs })
+    }
+}
+
+/// An error returned when raw BCF string maps fail to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The VCF header is invalid.
+    InvalidHeader(vcf::header::ParseError),
+    /// A string map is invalid.
+    InvalidStringMap(InsertError),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader(e) => write!(f, "invalid header: {}", e),
+            Self::InvalidStringMap(e) => write!(f, "invalid string map: {}", e),
+        }
+    }
+}
+
+impl FromStr for StringMaps {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let header: vcf::Header = s.parse().map_err(ParseError::InvalidHeader)?;
+        Self::try_from(&header).map_err(ParseError::InvalidStringMap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() -> Result<(), ParseError> {
+        let s = r#"##fileformat=VCFv4.3
+##contig=<ID=sq0,length=8,IDX=1>
+##contig=<ID=sq1,length=13,IDX=0>
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth across samples",IDX=1>
+##FILTER=<ID=PASS,Description="All filters passed",IDX=0>
+##FORMAT=<ID=GT,Number=1,Type=String,Description="Genotype",IDX=2>
+##FORMAT=<ID=DP,Number=1,Type=Integer,Description="Read depth",IDX=1>
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#;
+
+        let string_maps: StringMaps = s.parse()?;
+
+        let strings = string_maps.strings();
+        assert_eq!(strings.get_index(0), Some("PASS"));
+        assert_eq!(strings.get_index(1), Some("DP"));
+        assert_eq!(strings.get_index(2), Some("GT"));
+
+        let contigs = string_maps.contigs();
+        assert_eq!(contigs.get_index(0), Some("sq1"));
+        assert_eq!(contigs.get_index(1), Some("sq0"));
+        assert!(contigs.get_index_of("PASS").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str_with_collision() {
+        let s = r#"##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth across samples",IDX=1>
+##FILTER=<ID=q10,Description="Quality below 10",IDX=1>
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#;
+
+        assert_eq!(
+            s.parse::<StringMaps>(),
+            Err(ParseError::InvalidStringMap(InsertError::Collision(
+                1,
+                String::from("DP"),
+                String::from("q10")
+            )))
+        );
+
+        let s = r#"##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth across samples",IDX=1>
+##FORMAT=<ID=DP,Number=1,Type=Integer,Description="Read depth",IDX=2>
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#;
+
+        assert_eq!(
+            s.parse::<StringMaps>(),
+            Err(ParseError::InvalidStringMap(InsertError::PositionMismatch(
+                String::from("DP"),
+                1,
+                2
+            )))
+        );
+    }
+}
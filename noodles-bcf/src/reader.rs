@@ -1,17 +1,15 @@
 //! BCF reader and iterators.
 
-mod query;
+pub(crate) mod query;
 pub(crate) mod record;
 mod records;
+mod site_records;
 pub(crate) mod string_map;
 pub(crate) mod value;
 
-pub use self::{query::Query, records::Records};
+pub use self::{query::Query, records::Records, site_records::SiteRecords};
 
-use std::{
-    ffi::CStr,
-    io::{self, Read, Seek},
-};
+use std::io::{self, Read, Seek};
 
 use byteorder::{LittleEndian, ReadBytesExt};
 use noodles_bgzf as bgzf;
@@ -19,7 +17,7 @@ use noodles_core::{region::Interval, Region};
 use noodles_csi::{binning_index::ReferenceSequenceExt, BinningIndex};
 use noodles_vcf::header::Contigs;
 
-use super::Record;
+use super::{error::c_str_to_string, Error, Record};
 
 /// A BCF reader.
 ///
@@ -146,6 +144,35 @@ where
         record::read_record(&mut self.inner, &mut self.buf, record)
     }
 
+    /// Reads the site fields of a single record, skipping its genotypes.
+    ///
+    /// This reads the fixed columns and INFO fields (`CHROM`, `POS`, `ID`, `REF`, `ALT`, `QUAL`,
+    /// `FILTER`, and `INFO`) of the next record but discards the per-sample genotype data without
+    /// buffering it. The genotypes of the output record are cleared. This is useful for site
+    /// scans that have no need for sample data.
+    ///
+    /// If successful, the total record size (including the skipped genotypes) is returned. If
+    /// 0, the stream reached EOF.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bcf as bcf;
+    ///
+    /// let mut reader = File::open("sample.bcf").map(bcf::Reader::new)?;
+    /// reader.read_file_format()?;
+    /// reader.read_header()?;
+    ///
+    /// let mut record = bcf::Record::default();
+    /// reader.read_site_record(&mut record)?;
+    /// assert!(record.genotypes().is_empty());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_site_record(&mut self, record: &mut Record) -> io::Result<usize> {
+        record::read_site_record(&mut self.inner, &mut self.buf, record)
+    }
+
     /// Returns an iterator over records starting from the current stream position.
     ///
     /// The stream is expected to be directly after the header or at the start of another record.
@@ -169,6 +196,30 @@ where
     pub fn records(&mut self) -> Records<'_, R> {
         Records::new(self)
     }
+
+    /// Returns an iterator over site-only records starting from the current stream position.
+    ///
+    /// Each record is read using [`Self::read_site_record`], i.e., genotypes are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bcf as bcf;
+    ///
+    /// let mut reader = File::open("sample.bcf").map(bcf::Reader::new)?;
+    /// reader.read_file_format()?;
+    /// reader.read_header()?;
+    ///
+    /// for result in reader.site_records() {
+    ///     let record = result?;
+    ///     println!("{:?}", record.position());
+    /// }
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn site_records(&mut self) -> SiteRecords<'_, R> {
+        SiteRecords::new(self)
+    }
 }
 
 impl<R> Reader<bgzf::Reader<R>>
@@ -297,10 +348,7 @@ where
     if buf == MAGIC_NUMBER {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "invalid BCF header",
-        ))
+        Err(Error::InvalidMagicNumber(buf).into())
     }
 }
 
@@ -325,17 +373,10 @@ where
     let mut buf = vec![0; l_text];
     reader.read_exact(&mut buf)?;
 
-    CStr::from_bytes_with_nul(&buf)
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-        .and_then(|c_header| {
-            c_header
-                .to_str()
-                .map(|s| s.into())
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-        })
+    c_str_to_string(&buf)
 }
 
-fn resolve_region(contigs: &Contigs, region: &Region) -> io::Result<(usize, Interval)> {
+pub(crate) fn resolve_region(contigs: &Contigs, region: &Region) -> io::Result<(usize, Interval)> {
     if let Some(r) = region.as_mapped() {
         let i = contigs.get_index_of(r.name()).ok_or_else(|| {
             io::Error::new(
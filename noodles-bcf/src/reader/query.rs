@@ -46,12 +46,7 @@ where
     where
         B: RangeBounds<i32>,
     {
-        let (start, end) = match (interval.start_bound(), interval.end_bound()) {
-            (Bound::Included(s), Bound::Included(e)) => (*s, *e),
-            (Bound::Included(s), Bound::Unbounded) => (*s, i32::MAX),
-            (Bound::Unbounded, Bound::Unbounded) => (1, i32::MAX),
-            _ => todo!(),
-        };
+        let (start, end) = resolve_interval(interval);
 
         Self {
             reader,
@@ -111,18 +106,11 @@ where
 
                         match result {
                             Ok(record) => {
-                                let chromosome_id = record.chromosome_id() as usize;
-                                let start = i32::from(record.position());
-
-                                let end = match record.end() {
-                                    Ok(pos) => i32::from(pos),
-                                    Err(e) => return Some(Err(e)),
-                                };
-
-                                if chromosome_id == self.chromosome_id
-                                    && in_interval(start, end, self.start, self.end)
+                                match intersects(&record, self.chromosome_id, self.start, self.end)
                                 {
-                                    return Some(Ok(record));
+                                    Ok(true) => return Some(Ok(record)),
+                                    Ok(false) => {}
+                                    Err(e) => return Some(Err(e)),
                                 }
                             }
                             Err(e) => return Some(Err(e)),
@@ -138,6 +126,78 @@ where
     }
 }
 
+pub(crate) fn resolve_interval<B>(interval: B) -> (i32, i32)
+where
+    B: RangeBounds<i32>,
+{
+    let start = match interval.start_bound() {
+        Bound::Included(s) => *s,
+        Bound::Excluded(s) => *s + 1,
+        Bound::Unbounded => 1,
+    };
+
+    let end = match interval.end_bound() {
+        Bound::Included(e) => *e,
+        Bound::Excluded(e) => *e - 1,
+        Bound::Unbounded => i32::MAX,
+    };
+
+    (start, end)
+}
+
+// Checks whether the record's chromosome ID (`rid`) matches and its span, i.e., [`pos`, `pos` +
+// `rlen`), overlaps the given interval.
+pub(crate) fn intersects(
+    record: &Record,
+    chromosome_id: usize,
+    interval_start: i32,
+    interval_end: i32,
+) -> io::Result<bool> {
+    let id = match usize::try_from(record.chromosome_id()) {
+        Ok(id) => id,
+        Err(_) => return Ok(false),
+    };
+
+    let start = i32::from(record.position());
+    let end = record.end().map(i32::from)?;
+
+    Ok(id == chromosome_id && in_interval(start, end, interval_start, interval_end))
+}
+
 fn in_interval(a_start: i32, a_end: i32, b_start: i32, b_end: i32) -> bool {
     a_start <= b_end && b_start <= a_end
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_interval() {
+        assert_eq!(resolve_interval(8..=13), (8, 13));
+        assert_eq!(resolve_interval(8..13), (8, 12));
+        assert_eq!(resolve_interval(8..), (8, i32::MAX));
+        assert_eq!(resolve_interval(..=13), (1, 13));
+        assert_eq!(resolve_interval(..), (1, i32::MAX));
+    }
+
+    #[test]
+    fn test_intersects() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_vcf::record::Position;
+
+        let mut record = Record::default();
+        *record.chromosome_id_mut() = 1;
+        *record.position_mut() = Position::try_from(8)?;
+        *record.rlen_mut() = 5;
+
+        assert!(intersects(&record, 1, 1, 8)?);
+        assert!(intersects(&record, 1, 12, 21)?);
+        assert!(!intersects(&record, 1, 13, 21)?);
+        assert!(!intersects(&record, 0, 8, 13)?);
+
+        *record.chromosome_id_mut() = -1;
+        assert!(!intersects(&record, 1, 8, 13)?);
+
+        Ok(())
+    }
+}
@@ -11,27 +11,23 @@ use noodles_vcf::record::{AlternateBases, Ids, Position, QualityScore, Reference
 use super::value::read_value;
 use crate::{
     record::{Filters, Value},
-    Record,
+    Error, Record,
 };
 
 pub fn read_record<R>(reader: &mut R, buf: &mut Vec<u8>, record: &mut Record) -> io::Result<usize>
 where
     R: Read,
 {
-    let l_shared = match reader.read_u32::<LittleEndian>() {
-        Ok(n) => usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-        Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(0),
-        Err(e) => return Err(e),
+    let (l_shared, l_indiv) = match read_lengths(reader)? {
+        Some(lengths) => lengths,
+        None => return Ok(0),
     };
 
-    let l_indiv = reader.read_u32::<LittleEndian>().and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-    })?;
-
     buf.resize(l_shared, Default::default());
     reader.read_exact(buf)?;
-    let mut buf_reader = &buf[..];
-    let (n_fmt, n_sample) = read_site(&mut buf_reader, record)?;
+    let mut src = &buf[..];
+    let (n_fmt, n_sample) =
+        read_site(&mut src, record).map_err(|e| invalid_record(l_shared, src, e))?;
 
     let genotypes = record.genotypes_mut().as_mut();
     genotypes.resize(l_indiv, Default::default());
@@ -42,6 +38,72 @@ where
     Ok(l_shared + l_indiv)
 }
 
+/// Reads the site fields of a record, skipping the genotypes.
+///
+/// The genotypes of the output record are cleared.
+pub fn read_site_record<R>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    record: &mut Record,
+) -> io::Result<usize>
+where
+    R: Read,
+{
+    let (l_shared, l_indiv) = match read_lengths(reader)? {
+        Some(lengths) => lengths,
+        None => return Ok(0),
+    };
+
+    buf.resize(l_shared, Default::default());
+    reader.read_exact(buf)?;
+    let mut src = &buf[..];
+    read_site(&mut src, record).map_err(|e| invalid_record(l_shared, src, e))?;
+
+    skip(reader, l_indiv)?;
+    record.genotypes_mut().clear();
+
+    Ok(l_shared + l_indiv)
+}
+
+fn invalid_record(l_shared: usize, src: &[u8], e: io::Error) -> io::Error {
+    io::Error::from(Error::InvalidRecord {
+        record_number: None,
+        offset: l_shared - src.len(),
+        source: e,
+    })
+}
+
+fn read_lengths<R>(reader: &mut R) -> io::Result<Option<(usize, usize)>>
+where
+    R: Read,
+{
+    let l_shared = match reader.read_u32::<LittleEndian>() {
+        Ok(n) => usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+        Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let l_indiv = reader.read_u32::<LittleEndian>().and_then(|n| {
+        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    })?;
+
+    Ok(Some((l_shared, l_indiv)))
+}
+
+fn skip<R>(reader: &mut R, len: usize) -> io::Result<()>
+where
+    R: Read,
+{
+    let len = u64::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let n = io::copy(&mut reader.take(len), &mut io::sink())?;
+
+    if n < len {
+        Err(io::Error::from(io::ErrorKind::UnexpectedEof))
+    } else {
+        Ok(())
+    }
+}
+
 pub(crate) fn read_site<R>(reader: &mut R, record: &mut Record) -> io::Result<(usize, usize)>
 where
     R: Read,
@@ -413,4 +475,70 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_site_record() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_vcf as vcf;
+
+        use crate::{header::StringMap, writer::vcf_record::write_vcf_record};
+
+        let raw_header = "##fileformat=VCFv4.3
+##contig=<ID=sq0>
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1
+";
+
+        let header: vcf::Header = raw_header.parse()?;
+        let string_map: StringMap = raw_header.parse()?;
+
+        let mut data = Vec::new();
+
+        for s in [
+            "sq0\t8\t.\tA\tC\t.\t.\t.\tGT\t0|1\t1/1",
+            "sq0\t13\t.\tG\t.\t.\t.\t.\tGT\t0/0\t0|0",
+        ] {
+            let record = vcf::Record::try_from_str(s, &header)?;
+            write_vcf_record(&mut data, &header, &string_map, &record)?;
+        }
+
+        let mut reader = &data[..];
+        let mut buf = Vec::new();
+        let mut record = Record::default();
+
+        assert!(read_site_record(&mut reader, &mut buf, &mut record)? > 0);
+        assert_eq!(record.position(), Position::try_from(8)?);
+        assert!(record.genotypes().is_empty());
+
+        assert!(read_site_record(&mut reader, &mut buf, &mut record)? > 0);
+        assert_eq!(record.position(), Position::try_from(13)?);
+        assert!(record.genotypes().is_empty());
+
+        assert_eq!(read_site_record(&mut reader, &mut buf, &mut record)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_with_truncated_site() {
+        let data = [
+            0x04, 0x00, 0x00, 0x00, // l_shared = 4
+            0x00, 0x00, 0x00, 0x00, // l_indiv = 0
+            0x00, 0x00, 0x00, 0x00, // chrom = 0
+        ];
+
+        let mut reader = &data[..];
+        let mut record = Record::default();
+        let result = read_record(&mut reader, &mut Vec::new(), &mut record);
+
+        let e = result.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(
+            e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+            Some(Error::InvalidRecord {
+                record_number: None,
+                offset: 4,
+                ..
+            })
+        ));
+    }
 }
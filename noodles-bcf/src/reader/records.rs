@@ -1,6 +1,6 @@
 use std::io::{self, Read};
 
-use crate::Record;
+use crate::{Error, Record};
 
 use super::Reader;
 
@@ -13,6 +13,7 @@ where
 {
     reader: &'a mut Reader<R>,
     record: Record,
+    record_number: u64,
 }
 
 impl<'a, R> Records<'a, R>
@@ -23,6 +24,7 @@ where
         Self {
             reader,
             record: Record::default(),
+            record_number: 0,
         }
     }
 }
@@ -36,8 +38,19 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         match self.reader.read_record(&mut self.record) {
             Ok(0) => None,
-            Ok(_) => Some(Ok(self.record.clone())),
-            Err(e) => Some(Err(e)),
+            Ok(_) => {
+                self.record_number += 1;
+                Some(Ok(self.record.clone()))
+            }
+            Err(mut e) => {
+                if let Some(Error::InvalidRecord { record_number, .. }) =
+                    e.get_mut().and_then(|e| e.downcast_mut::<Error>())
+                {
+                    *record_number = Some(self.record_number);
+                }
+
+                Some(Err(e))
+            }
         }
     }
 }
@@ -0,0 +1,43 @@
+use std::io::{self, Read};
+
+use crate::Record;
+
+use super::Reader;
+
+/// An iterator over site-only records of a BCF reader.
+///
+/// This is created by calling [`Reader::site_records`].
+pub struct SiteRecords<'a, R>
+where
+    R: Read,
+{
+    reader: &'a mut Reader<R>,
+    record: Record,
+}
+
+impl<'a, R> SiteRecords<'a, R>
+where
+    R: Read,
+{
+    pub(crate) fn new(reader: &'a mut Reader<R>) -> Self {
+        Self {
+            reader,
+            record: Record::default(),
+        }
+    }
+}
+
+impl<'a, R> Iterator for SiteRecords<'a, R>
+where
+    R: Read,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.reader.read_site_record(&mut self.record) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(self.record.clone())),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
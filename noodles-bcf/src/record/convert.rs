@@ -8,6 +8,53 @@ use crate::header::StringMap;
 impl Record {
     /// Converts a VCF record to a BCF record.
     ///
+    /// The string map is used to resolve the indices of filters, info field keys, and genotype
+    /// field keys, including any explicit dictionary indices (`IDX`) set in the header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::{self as bcf, header::StringMap};
+    /// use noodles_vcf::{self as vcf, header::Contig, record::Position};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_contig(Contig::new("sq0"))
+    ///     .build();
+    ///
+    /// let string_map = StringMap::try_from(&header)?;
+    ///
+    /// let record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse()?)
+    ///     .set_position(Position::try_from(8)?)
+    ///     .set_reference_bases("A".parse()?)
+    ///     .build()?;
+    ///
+    /// let actual = bcf::Record::try_from_vcf_record(&header, &string_map, &record)?;
+    ///
+    /// assert_eq!(actual.chromosome_id(), 0);
+    /// assert_eq!(i32::from(actual.position()), 8);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_vcf_record(
+        header: &vcf::Header,
+        string_map: &StringMap,
+        record: &vcf::Record,
+    ) -> io::Result<Self> {
+        use crate::{reader::record::read_record, writer::vcf_record::write_vcf_record};
+
+        let mut raw_record = Vec::new();
+        write_vcf_record(&mut raw_record, header, string_map, record)?;
+
+        let mut reader = &raw_record[..];
+        let mut buf = Vec::new();
+        let mut bcf_record = Self::default();
+        read_record(&mut reader, &mut buf, &mut bcf_record)?;
+
+        Ok(bcf_record)
+    }
+
+    /// Converts a BCF record to a VCF record.
+    ///
     /// # Examples
     ///
     /// ```
@@ -77,3 +124,35 @@ impl Record {
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_vcf_record_and_try_into_vcf_record() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let raw_header = r#"##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth across samples",IDX=2>
+##FILTER=<ID=PASS,Description="All filters passed",IDX=0>
+##FILTER=<ID=q10,Description="Quality below 10",IDX=1>
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#;
+
+        let header: vcf::Header = raw_header.parse()?;
+        let string_map: StringMap = raw_header.parse()?;
+
+        let expected: vcf::Record = "sq1\t8\tnd0\tA\tC\t13\tq10\tDP=5".parse()?;
+
+        let record = Record::try_from_vcf_record(&header, &string_map, &expected)?;
+        assert_eq!(record.chromosome_id(), 1);
+        assert_eq!(record.filters().len(), 1);
+
+        let actual = record.try_into_vcf_record(&header, &string_map)?;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -105,7 +105,6 @@ impl Info {
     /// # Examples
     ///
     /// ```
-    /// # use std::io;
     /// use noodles_bcf::{header::StringMap, record::Info};
     /// use noodles_vcf::{self as vcf, record::info::{field::{Key, Value}, Field}, Header};
     ///
@@ -114,7 +113,7 @@ impl Info {
     ///     .add_info(vcf::header::Info::from(Key::TotalDepth))
     ///     .build();
     ///
-    /// let string_map = StringMap::from(&header);
+    /// let string_map = StringMap::try_from(&header)?;
     ///
     /// let data = vec![
     ///     0x11, 0x01, 0x11, 0x05, // AC=5
@@ -129,7 +128,7 @@ impl Info {
     /// );
     ///
     /// assert!(info.get(&header, &string_map, &Key::AncestralAllele).is_none());
-    /// # Ok::<_, io::Error>(())
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
     /// ```
     pub fn get(
         &self,
@@ -156,7 +155,6 @@ impl Info {
     /// # Examples
     ///
     /// ```
-    /// # use std::io;
     /// use noodles_bcf::{header::StringMap, record::Info};
     /// use noodles_vcf::{self as vcf, record::info::{field::{Key, Value}, Field}, Header};
     ///
@@ -165,7 +163,7 @@ impl Info {
     ///     .add_info(vcf::header::Info::from(Key::TotalDepth))
     ///     .build();
     ///
-    /// let string_map = StringMap::from(&header);
+    /// let string_map = StringMap::try_from(&header)?;
     ///
     /// let data = vec![
     ///     0x11, 0x01, 0x11, 0x05, // AC=5
@@ -186,7 +184,7 @@ impl Info {
     /// );
     ///
     /// assert!(fields.next().is_none());
-    /// # Ok::<_, io::Error>(())
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
     /// ```
     pub fn values<'a>(
         &'a self,
@@ -1,7 +1,7 @@
-mod record;
+pub(crate) mod record;
 mod string_map;
 mod value;
-mod vcf_record;
+pub(crate) mod vcf_record;
 
 use std::{
     ffi::CString,
@@ -131,7 +131,7 @@ where
     ///
     /// writer.write_header(&header)?;
     ///
-    /// let string_map = StringMap::from(&header);
+    /// let string_map = StringMap::try_from(&header)?;
     ///
     /// let record = vcf::Record::builder()
     ///     .set_chromosome("sq0".parse()?)
@@ -195,7 +195,7 @@ impl<W> From<W> for Writer<W> {
     }
 }
 
-fn write_file_format<W>(writer: &mut W) -> io::Result<()>
+pub(crate) fn write_file_format<W>(writer: &mut W) -> io::Result<()>
 where
     W: Write,
 {
@@ -208,7 +208,7 @@ where
     Ok(())
 }
 
-fn write_header<W>(writer: &mut W, header: &vcf::Header) -> io::Result<()>
+pub(crate) fn write_header<W>(writer: &mut W, header: &vcf::Header) -> io::Result<()>
 where
     W: Write,
 {
@@ -4,7 +4,7 @@ use byteorder::{LittleEndian, WriteBytesExt};
 
 use crate::{record::Filters, Record};
 
-pub(super) fn write_record<W>(writer: &mut W, record: &Record) -> io::Result<()>
+pub(crate) fn write_record<W>(writer: &mut W, record: &Record) -> io::Result<()>
 where
     W: Write,
 {
@@ -89,7 +89,7 @@ mod tests {
         write_record(&mut buf, &record)?;
 
         let expected = [
-            0x1d, 0x00, 0x00, 0x00, // l_shared = 29
+            0x1c, 0x00, 0x00, 0x00, // l_shared = 28
             0x00, 0x00, 0x00, 0x00, // l_indiv = 0
             0x00, 0x00, 0x00, 0x00, // chrom = 0,
             0x00, 0x00, 0x00, 0x00, // pos = 0 (0-based)
@@ -101,7 +101,6 @@ mod tests {
             0x00, // n_fmt = 0
             0x07, // id = None
             0x17, b'A', // ref = [A]
-            0x07, // alt = []
             0x00, // filter = []
         ];
 
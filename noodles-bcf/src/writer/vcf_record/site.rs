@@ -98,7 +98,7 @@ pub(crate) fn write_rlen<W>(
 where
     W: Write,
 {
-    let rlen = i32::from(start) - i32::from(end) + 1;
+    let rlen = i32::from(end) - i32::from(start) + 1;
     writer.write_i32::<LittleEndian>(rlen)
 }
 
@@ -181,13 +181,9 @@ where
     let ref_value = Some(Value::String(Some(r#ref)));
     write_value(writer, ref_value)?;
 
-    if alternate_bases.is_empty() {
-        write_value(writer, Some(Value::String(None)))?;
-    } else {
-        for allele in alternate_bases.iter() {
-            let alt_value = Some(Value::String(Some(allele.to_string())));
-            write_value(writer, alt_value)?;
-        }
+    for allele in alternate_bases.iter() {
+        let alt_value = Some(Value::String(Some(allele.to_string())));
+        write_value(writer, alt_value)?;
     }
 
     Ok(())
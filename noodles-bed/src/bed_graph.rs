@@ -0,0 +1,225 @@
+//! bedGraph record and fields.
+//!
+//! A bedGraph record is a BED3 record with an additional column holding a data value, e.g., read
+//! coverage.
+
+use std::{error, fmt, num, str::FromStr};
+
+const DELIMITER: char = '\t';
+
+/// A bedGraph record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    reference_sequence_name: String,
+    start_position: u64,
+    end_position: u64,
+    value: f64,
+}
+
+impl Record {
+    /// Creates a bedGraph record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bed_graph;
+    /// let record = bed_graph::Record::new("sq0", 8, 13, 0.75);
+    /// ```
+    pub fn new<N>(
+        reference_sequence_name: N,
+        start_position: u64,
+        end_position: u64,
+        value: f64,
+    ) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            reference_sequence_name: reference_sequence_name.into(),
+            start_position,
+            end_position,
+            value,
+        }
+    }
+
+    /// Returns the reference sequence name (`chrom`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bed_graph;
+    /// let record = bed_graph::Record::new("sq0", 8, 13, 0.75);
+    /// assert_eq!(record.reference_sequence_name(), "sq0");
+    /// ```
+    pub fn reference_sequence_name(&self) -> &str {
+        &self.reference_sequence_name
+    }
+
+    /// Returns the feature start position (`chromStart`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bed_graph;
+    /// let record = bed_graph::Record::new("sq0", 8, 13, 0.75);
+    /// assert_eq!(record.start_position(), 8);
+    /// ```
+    pub fn start_position(&self) -> u64 {
+        self.start_position
+    }
+
+    /// Returns the feature end position (`chromEnd`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bed_graph;
+    /// let record = bed_graph::Record::new("sq0", 8, 13, 0.75);
+    /// assert_eq!(record.end_position(), 13);
+    /// ```
+    pub fn end_position(&self) -> u64 {
+        self.end_position
+    }
+
+    /// Returns the data value (`dataValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bed_graph;
+    /// let record = bed_graph::Record::new("sq0", 8, 13, 0.75);
+    /// assert_eq!(record.value(), 0.75);
+    /// ```
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}{}{}{}{}",
+            self.reference_sequence_name,
+            DELIMITER,
+            self.start_position,
+            DELIMITER,
+            self.end_position,
+            DELIMITER,
+            self.value
+        )
+    }
+}
+
+/// An error returned when a raw bedGraph record fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The reference sequence name is missing.
+    MissingReferenceSequenceName,
+    /// The start position is missing.
+    MissingStartPosition,
+    /// The start position is invalid.
+    InvalidStartPosition(num::ParseIntError),
+    /// The end position is missing.
+    MissingEndPosition,
+    /// The end position is invalid.
+    InvalidEndPosition(num::ParseIntError),
+    /// The value is missing.
+    MissingValue,
+    /// The value is invalid.
+    InvalidValue(num::ParseFloatError),
+    /// There are more fields than expected.
+    UnexpectedField,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingReferenceSequenceName => f.write_str("missing reference sequence name"),
+            Self::MissingStartPosition => f.write_str("missing start position"),
+            Self::InvalidStartPosition(e) => write!(f, "invalid start position: {}", e),
+            Self::MissingEndPosition => f.write_str("missing end position"),
+            Self::InvalidEndPosition(e) => write!(f, "invalid end position: {}", e),
+            Self::MissingValue => f.write_str("missing value"),
+            Self::InvalidValue(e) => write!(f, "invalid value: {}", e),
+            Self::UnexpectedField => f.write_str("unexpected field"),
+        }
+    }
+}
+
+impl FromStr for Record {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split(DELIMITER);
+
+        let reference_sequence_name = fields
+            .next()
+            .ok_or(ParseError::MissingReferenceSequenceName)?;
+
+        let start_position = fields
+            .next()
+            .ok_or(ParseError::MissingStartPosition)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidStartPosition))?;
+
+        let end_position = fields
+            .next()
+            .ok_or(ParseError::MissingEndPosition)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidEndPosition))?;
+
+        let value = fields
+            .next()
+            .ok_or(ParseError::MissingValue)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidValue))?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        Ok(Self::new(
+            reference_sequence_name,
+            start_position,
+            end_position,
+            value,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let record = Record::new("sq0", 8, 13, 0.75);
+        assert_eq!(record.to_string(), "sq0\t8\t13\t0.75");
+
+        let record = Record::new("sq0", 8, 13, 5.0);
+        assert_eq!(record.to_string(), "sq0\t8\t13\t5");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "sq0\t8\t13\t0.75".parse(),
+            Ok(Record::new("sq0", 8, 13, 0.75))
+        );
+        assert_eq!("sq0\t8\t13\t5".parse(), Ok(Record::new("sq0", 8, 13, 5.0)));
+
+        assert_eq!("".parse::<Record>(), Err(ParseError::MissingStartPosition));
+        assert_eq!(
+            "sq0\t8\t13".parse::<Record>(),
+            Err(ParseError::MissingValue)
+        );
+        assert!(matches!(
+            "sq0\t8\t13\tndls".parse::<Record>(),
+            Err(ParseError::InvalidValue(_))
+        ));
+        assert_eq!(
+            "sq0\t8\t13\t0.75\tndls".parse::<Record>(),
+            Err(ParseError::UnexpectedField)
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! BED detail record and fields.
+//!
+//! A BED detail record is a BED record with two additional columns at the end of the line: an ID
+//! and a description.
+
+use std::{error, fmt, str::FromStr};
+
+use super::{record, Record as BedRecord};
+
+const DELIMITER: char = '\t';
+
+/// A BED detail record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record<const N: u8> {
+    record: BedRecord<N>,
+    id: String,
+    description: String,
+}
+
+impl<const N: u8> Record<N> {
+    /// Creates a BED detail record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, detail};
+    /// let record: bed::Record<4> = "sq0\t8\t13\tndls1".parse()?;
+    /// let record = detail::Record::new(record, "NDLS1", "noodles 1");
+    /// # Ok::<_, bed::record::ParseError>(())
+    /// ```
+    pub fn new<I, D>(record: BedRecord<N>, id: I, description: D) -> Self
+    where
+        I: Into<String>,
+        D: Into<String>,
+    {
+        Self {
+            record,
+            id: id.into(),
+            description: description.into(),
+        }
+    }
+
+    /// Returns the BED record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let record: bed::detail::Record<4> = "sq0\t8\t13\tndls1\tNDLS1\tnoodles 1".parse()?;
+    /// assert_eq!(record.record().name(), Some("ndls1"));
+    /// # Ok::<_, bed::detail::ParseError>(())
+    /// ```
+    pub fn record(&self) -> &BedRecord<N> {
+        &self.record
+    }
+
+    /// Returns the ID (`id`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let record: bed::detail::Record<4> = "sq0\t8\t13\tndls1\tNDLS1\tnoodles 1".parse()?;
+    /// assert_eq!(record.id(), "NDLS1");
+    /// # Ok::<_, bed::detail::ParseError>(())
+    /// ```
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the description (`description`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let record: bed::detail::Record<4> = "sq0\t8\t13\tndls1\tNDLS1\tnoodles 1".parse()?;
+    /// assert_eq!(record.description(), "noodles 1");
+    /// # Ok::<_, bed::detail::ParseError>(())
+    /// ```
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl<const N: u8> fmt::Display for Record<N>
+where
+    BedRecord<N>: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}{}{}",
+            self.record, DELIMITER, self.id, DELIMITER, self.description
+        )
+    }
+}
+
+/// An error returned when a raw BED detail record fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The description is missing.
+    MissingDescription,
+    /// The ID is missing.
+    MissingId,
+    /// The BED record is invalid.
+    InvalidRecord(record::ParseError),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingDescription => f.write_str("missing description"),
+            Self::MissingId => f.write_str("missing ID"),
+            Self::InvalidRecord(e) => write!(f, "invalid record: {}", e),
+        }
+    }
+}
+
+impl<const N: u8> FromStr for Record<N>
+where
+    BedRecord<N>: FromStr<Err = record::ParseError>,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The ID and description are always the last two columns, regardless of the number of
+        // BED fields.
+        let mut fields = s.rsplitn(3, DELIMITER);
+
+        let description = fields.next().ok_or(ParseError::MissingDescription)?;
+        let id = fields.next().ok_or(ParseError::MissingId)?;
+        let record = fields
+            .next()
+            .ok_or(ParseError::MissingId)
+            .and_then(|t| t.parse().map_err(ParseError::InvalidRecord))?;
+
+        Ok(Self::new(record, id, description))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), record::ParseError> {
+        let record: BedRecord<4> = "sq0\t8\t13\tndls1".parse()?;
+        let record = Record::new(record, "NDLS1", "noodles 1");
+        assert_eq!(record.to_string(), "sq0\t8\t13\tndls1\tNDLS1\tnoodles 1");
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), record::ParseError> {
+        let actual = "sq0\t8\t13\tndls1\tNDLS1\tnoodles 1".parse::<Record<4>>();
+        let record: BedRecord<4> = "sq0\t8\t13\tndls1".parse()?;
+        let expected = Ok(Record::new(record, "NDLS1", "noodles 1"));
+        assert_eq!(actual, expected);
+
+        let actual = "sq0\t8\t13\t.\t0\t+\tNDLS1\t".parse::<Record<6>>();
+        let record: BedRecord<6> = "sq0\t8\t13\t.\t0\t+".parse()?;
+        let expected = Ok(Record::new(record, "NDLS1", ""));
+        assert_eq!(actual, expected);
+
+        assert_eq!(
+            "sq0\t8\t13\tndls1".parse::<Record<4>>(),
+            Err(ParseError::InvalidRecord(
+                record::ParseError::MissingEndPosition
+            ))
+        );
+        assert_eq!("".parse::<Record<4>>(), Err(ParseError::MissingId));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+//! BED flavor.
+
+use std::{error, fmt, str::FromStr};
+
+/// A BED flavor.
+///
+/// The flavor describes the layout of the columns in a BED-like file. It is declared using the
+/// `type` attribute of a track line, e.g., `track type=bedGraph`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Flavor {
+    /// Standard BED (`bed`).
+    Bed,
+    /// bedGraph (`bedGraph`), i.e., BED3 with a data value column.
+    BedGraph,
+    /// BED detail (`bedDetail`), i.e., BED with trailing ID and description columns.
+    BedDetail,
+}
+
+impl Flavor {
+    /// Sniffs the flavor from a track line.
+    ///
+    /// This returns `None` if the line is not a track line or if it does not declare a known
+    /// `type`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::Flavor;
+    ///
+    /// assert_eq!(
+    ///     Flavor::from_track_line("track type=bedGraph name=\"coverage\""),
+    ///     Some(Flavor::BedGraph)
+    /// );
+    /// assert_eq!(Flavor::from_track_line("track name=\"genes\""), None);
+    /// assert_eq!(Flavor::from_track_line("sq0\t8\t13"), None);
+    /// ```
+    pub fn from_track_line(s: &str) -> Option<Self> {
+        let mut tokens = s.split_whitespace();
+
+        if tokens.next() != Some(TRACK_PREFIX) {
+            return None;
+        }
+
+        tokens
+            .filter_map(|token| token.strip_prefix("type="))
+            .map(|value| value.trim_matches('"'))
+            .find_map(|value| value.parse().ok())
+    }
+}
+
+pub(crate) const TRACK_PREFIX: &str = "track";
+
+impl AsRef<str> for Flavor {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Bed => "bed",
+            Self::BedGraph => "bedGraph",
+            Self::BedDetail => "bedDetail",
+        }
+    }
+}
+
+impl fmt::Display for Flavor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+/// An error returned when a raw BED flavor fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid => f.write_str("invalid input"),
+        }
+    }
+}
+
+impl FromStr for Flavor {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(ParseError::Empty),
+            "bed" => Ok(Self::Bed),
+            "bedGraph" => Ok(Self::BedGraph),
+            "bedDetail" => Ok(Self::BedDetail),
+            _ => Err(ParseError::Invalid),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_track_line() {
+        assert_eq!(Flavor::from_track_line("track type=bed"), Some(Flavor::Bed));
+        assert_eq!(
+            Flavor::from_track_line("track type=bedGraph"),
+            Some(Flavor::BedGraph)
+        );
+        assert_eq!(
+            Flavor::from_track_line("track name=\"ndls\" type=\"bedDetail\""),
+            Some(Flavor::BedDetail)
+        );
+        assert_eq!(Flavor::from_track_line("track name=ndls"), None);
+        assert_eq!(Flavor::from_track_line("track type=wiggle_0"), None);
+        assert_eq!(Flavor::from_track_line("browser position sq0:8-13"), None);
+        assert_eq!(Flavor::from_track_line("tracks type=bedGraph"), None);
+    }
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Flavor::Bed.to_string(), "bed");
+        assert_eq!(Flavor::BedGraph.to_string(), "bedGraph");
+        assert_eq!(Flavor::BedDetail.to_string(), "bedDetail");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("bed".parse(), Ok(Flavor::Bed));
+        assert_eq!("bedGraph".parse(), Ok(Flavor::BedGraph));
+        assert_eq!("bedDetail".parse(), Ok(Flavor::BedDetail));
+
+        assert_eq!("".parse::<Flavor>(), Err(ParseError::Empty));
+        assert_eq!("bedgraph".parse::<Flavor>(), Err(ParseError::Invalid));
+    }
+}
@@ -1,9 +1,17 @@
 #![warn(missing_docs)]
 
 //! **noodles-bed** handles the reading of the BED (Browser Extensible Data) format.
+//!
+//! Besides standard BED records, the bedGraph ([`bed_graph::Record`]) and BED detail
+//! ([`detail::Record`]) flavors are supported. A flavor can be selected explicitly by parsing a
+//! raw record as the corresponding record type or sniffed from the track line of a file
+//! ([`Reader::read_flavor`]).
 
+pub mod bed_graph;
+pub mod detail;
+pub mod flavor;
 mod reader;
 pub mod record;
 mod writer;
 
-pub use self::{reader::Reader, record::Record, writer::Writer};
+pub use self::{flavor::Flavor, reader::Reader, record::Record, writer::Writer};
@@ -1,5 +1,10 @@
 use std::io::{self, BufRead};
 
+use super::{flavor::TRACK_PREFIX, Flavor};
+
+const BROWSER_PREFIX: &str = "browser";
+const COMMENT_PREFIX: char = '#';
+
 /// A BED reader.
 pub struct Reader<R> {
     inner: R,
@@ -14,12 +19,69 @@ where
         Self { inner }
     }
 
+    /// Reads the header lines and sniffs the BED flavor.
+    ///
+    /// The header consists of any leading browser (`browser`), track (`track`), and comment (`#`)
+    /// lines. They are consumed, and the flavor declared by the `type` attribute of a track line
+    /// is returned. If no track line declares a known type, this returns `None`.
+    ///
+    /// The reader must be at the start of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed::{self as bed, bed_graph, Flavor};
+    ///
+    /// let data = b"track type=bedGraph\nsq0\t8\t13\t0.75\n";
+    /// let mut reader = bed::Reader::new(&data[..]);
+    ///
+    /// assert_eq!(reader.read_flavor()?, Some(Flavor::BedGraph));
+    ///
+    /// let mut buf = String::new();
+    /// reader.read_record(&mut buf)?;
+    /// assert_eq!(buf.parse(), Ok(bed_graph::Record::new("sq0", 8, 13, 0.75)));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_flavor(&mut self) -> io::Result<Option<Flavor>> {
+        let mut flavor = None;
+        let mut buf = String::new();
+
+        while is_header_line(self.inner.fill_buf()?) {
+            buf.clear();
+            read_line(&mut self.inner, &mut buf)?;
+
+            if flavor.is_none() {
+                flavor = Flavor::from_track_line(&buf);
+            }
+        }
+
+        Ok(flavor)
+    }
+
     /// Reads a single raw BED record.
     pub fn read_record(&mut self, buf: &mut String) -> io::Result<usize> {
         read_line(&mut self.inner, buf)
     }
 }
 
+fn is_header_line(buf: &[u8]) -> bool {
+    starts_with_keyword(buf, BROWSER_PREFIX)
+        || starts_with_keyword(buf, TRACK_PREFIX)
+        || buf.first() == Some(&(COMMENT_PREFIX as u8))
+}
+
+// Returns whether the line starts with the given keyword followed by whitespace or the end of the
+// line, e.g., `track` but not `track1`.
+fn starts_with_keyword(buf: &[u8], keyword: &str) -> bool {
+    buf.strip_prefix(keyword.as_bytes())
+        .map(|rest| match rest.first() {
+            Some(b) => b.is_ascii_whitespace(),
+            None => true,
+        })
+        .unwrap_or(false)
+}
+
 fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
 where
     R: BufRead,
@@ -48,6 +110,66 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_read_flavor() -> io::Result<()> {
+        fn t(data: &[u8], expected: Option<Flavor>) -> io::Result<()> {
+            let mut reader = Reader::new(data);
+            assert_eq!(reader.read_flavor()?, expected);
+
+            let mut buf = String::new();
+            reader.read_record(&mut buf)?;
+            assert_eq!(buf, "sq0\t8\t13\tndls1\tNDLS1\tnoodles 1");
+
+            Ok(())
+        }
+
+        let record = b"sq0\t8\t13\tndls1\tNDLS1\tnoodles 1\n";
+
+        t(record, None)?;
+        t(
+            &[b"browser position sq0:8-13\n", &record[..]].concat(),
+            None,
+        )?;
+        t(
+            &[
+                b"# noodles\nbrowser position sq0:8-13\ntrack type=bedDetail\n",
+                &record[..],
+            ]
+            .concat(),
+            Some(Flavor::BedDetail),
+        )?;
+        t(&[b"track name=ndls\n", &record[..]].concat(), None)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_flavor_with_keyword_reference_sequence_names() -> io::Result<()> {
+        let data = b"track1\t8\t13\nbrowserX\t21\t34\n";
+        let mut reader = Reader::new(&data[..]);
+        assert!(reader.read_flavor()?.is_none());
+
+        let mut buf = String::new();
+        reader.read_record(&mut buf)?;
+        assert_eq!(buf, "track1\t8\t13");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_header_line() {
+        assert!(is_header_line(b"browser position sq0:8-13\n"));
+        assert!(is_header_line(b"track\tname=ndls\n"));
+        assert!(is_header_line(b"track\n"));
+        assert!(is_header_line(b"track"));
+        assert!(is_header_line(b"# noodles\n"));
+
+        assert!(!is_header_line(b"track1\t8\t13\n"));
+        assert!(!is_header_line(b"browserX\t8\t13\n"));
+        assert!(!is_header_line(b"sq0\t8\t13\n"));
+        assert!(!is_header_line(b""));
+    }
+
     #[test]
     fn test_read_line() -> io::Result<()> {
         fn t(buf: &mut String, mut reader: &[u8], expected: &str) -> io::Result<()> {
@@ -3,7 +3,7 @@ use std::{
     io::{self, Write},
 };
 
-use super::Record;
+use super::{bed_graph, detail, flavor::TRACK_PREFIX, Flavor, Record};
 
 /// A BED writer.
 pub struct Writer<W> {
@@ -19,6 +19,60 @@ where
         Self { inner }
     }
 
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let writer = bed::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Writes a track line that declares the given flavor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed::{self as bed, Flavor};
+    /// let mut writer = bed::Writer::new(Vec::new());
+    /// writer.write_track_line(Flavor::BedGraph)?;
+    /// assert_eq!(writer.get_ref(), b"track type=bedGraph\n");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_track_line(&mut self, flavor: Flavor) -> io::Result<()> {
+        writeln!(self.inner, "{} type={}", TRACK_PREFIX, flavor)
+    }
+
+    /// Writes a bedGraph record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed::{self as bed, bed_graph};
+    /// let mut writer = bed::Writer::new(Vec::new());
+    /// let record = bed_graph::Record::new("sq0", 8, 13, 0.75);
+    /// writer.write_bed_graph_record(&record)?;
+    /// assert_eq!(writer.get_ref(), b"sq0\t8\t13\t0.75\n");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_bed_graph_record(&mut self, record: &bed_graph::Record) -> io::Result<()> {
+        writeln!(self.inner, "{}", record)
+    }
+
+    /// Writes a BED detail record.
+    pub fn write_detail_record<const N: u8>(&mut self, record: &detail::Record<N>) -> io::Result<()>
+    where
+        Record<N>: fmt::Display,
+    {
+        writeln!(self.inner, "{}", record)
+    }
+
     /// Writes a BED record.
     pub fn write_record<const N: u8>(&mut self, record: &Record<N>) -> io::Result<()>
     where
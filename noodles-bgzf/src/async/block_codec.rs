@@ -4,7 +4,11 @@ use byteorder::{ByteOrder, LittleEndian};
 use bytes::{BufMut, BytesMut};
 use tokio_util::codec::{Decoder, Encoder};
 
-use crate::{gz, BGZF_HEADER_SIZE};
+use crate::{
+    gz,
+    reader::{header_size, validate_header},
+    Error, BGZF_HEADER_SIZE,
+};
 
 use super::writer::deflate::GzData;
 
@@ -22,10 +26,15 @@ impl Decoder for BlockCodec {
 
         let block_size = {
             let header = &src[..BGZF_HEADER_SIZE];
+            validate_header(header)?;
             let bsize = LittleEndian::read_u16(&header[16..]);
             usize::from(bsize) + 1
         };
 
+        if block_size < header_size(src) + gz::TRAILER_SIZE {
+            return Err(Error::InvalidBlockSize(block_size).into());
+        }
+
         if src.len() < block_size {
             src.reserve(block_size);
             return Ok(None);
@@ -62,6 +62,61 @@ where
         Self::builder(inner).build()
     }
 
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::AsyncReader::new(&data[..]);
+    /// assert!(reader.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        self.stream
+            .as_ref()
+            .expect("missing stream")
+            .get_ref()
+            .get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let mut reader = bgzf::AsyncReader::new(&data[..]);
+    /// assert!(reader.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut R {
+        self.stream
+            .as_mut()
+            .expect("missing stream")
+            .get_mut()
+            .get_mut()
+    }
+
+    /// Unwraps and returns the underlying reader.
+    ///
+    /// Blocks that were read ahead of the current block are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::AsyncReader::new(&data[..]);
+    /// assert!(reader.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.stream
+            .expect("missing stream")
+            .into_inner()
+            .into_inner()
+    }
+
     /// Returns the current virtual position of the stream.
     ///
     /// # Examples
@@ -85,6 +140,10 @@ where
 {
     /// Seeks the stream to the given virtual position.
     ///
+    /// This can be used with the chunks of an index (e.g., BAI, CSI, or tabix) to read records in
+    /// a region. Seeking to the end of the stream, i.e., past the last block, is allowed; the
+    /// virtual position is kept, and subsequent reads return no data.
+    ///
     /// # Examples
     ///
     /// ```
@@ -107,10 +166,10 @@ where
 
         let mut stream = blocks.try_buffered(self.worker_count);
 
+        let (cpos, upos) = pos.into();
+
         self.block = match stream.try_next().await? {
             Some(mut block) => {
-                let (cpos, upos) = pos.into();
-
                 self.position = cpos + block.clen();
 
                 block.set_cpos(cpos);
@@ -118,7 +177,13 @@ where
 
                 block
             }
-            None => Block::default(),
+            None => {
+                self.position = cpos;
+
+                let mut block = Block::default();
+                block.set_cpos(cpos);
+                block
+            }
         };
 
         self.stream.replace(stream);
@@ -152,10 +217,12 @@ where
     R: AsyncRead,
 {
     fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
-        let this = self.project();
+        let mut this = self.project();
 
-        if this.block.is_eof() {
-            let stream = this.stream.as_pin_mut().expect("missing stream");
+        // Empty blocks, e.g., the EOF markers of concatenated streams, are skipped so that an
+        // empty buffer is only returned at the end of the stream.
+        while this.block.is_eof() {
+            let stream = this.stream.as_mut().as_pin_mut().expect("missing stream");
 
             match ready!(stream.poll_next(cx)) {
                 Some(Ok(mut block)) => {
@@ -218,6 +285,121 @@ mod tests {
         assert_eq!(buf, b"dles");
         assert_eq!(reader.virtual_position(), eof);
 
+        let end = VirtualPosition::try_from((data.len() as u64, 0))?;
+        reader.seek(end).await?;
+        assert_eq!(reader.virtual_position(), end);
+
+        buf.clear();
+        reader.read_to_end(&mut buf).await?;
+        assert!(buf.is_empty());
+        assert_eq!(reader.virtual_position(), end);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_empty_blocks() -> io::Result<()> {
+        use std::io::Write;
+
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.write_empty_block()?;
+        writer.write_empty_block()?;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(&data[..]);
+
+        let mut buf = [0; 16];
+        assert_eq!(reader.read(&mut buf).await?, 7);
+        assert_eq!(reader.read(&mut buf).await?, 4);
+        assert_eq!(reader.read(&mut buf).await?, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_seek_to_next_block() -> io::Result<()> {
+        use std::io::Write;
+
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+
+        // block 1
+        let position = VirtualPosition::from(35 << 16 | 2);
+        reader.seek(position).await?;
+        assert_eq!(reader.virtual_position(), position);
+
+        let mut buf = [0; 2];
+        reader.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"zf");
+        assert_eq!(reader.virtual_position(), VirtualPosition::from(67 << 16));
+
+        // block 0
+        reader.seek(VirtualPosition::from(0)).await?;
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await?;
+        assert_eq!(buf, b"noodlesbgzf");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_extra_subfields() -> io::Result<()> {
+        use std::io::Write;
+
+        use crate::extra_subfield::ExtraSubfield;
+
+        let mut writer = crate::Writer::builder(Vec::new())
+            .set_extra_subfields(vec![ExtraSubfield::new(*b"NR", b"ndl".to_vec())?])
+            .build();
+        writer.write_all(b"noodles")?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(&data[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await?;
+        assert_eq!(buf, b"noodles");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_invalid_checksum() -> io::Result<()> {
+        use std::io::Write;
+
+        use crate::Error;
+
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        let mut data = writer.finish()?;
+
+        // CRC32
+        data[27] ^= 0xff;
+
+        let mut reader = Reader::new(&data[..]);
+        let mut buf = Vec::new();
+        assert!(matches!(
+            reader.read_to_end(&mut buf).await,
+            Err(e) if matches!(
+                e.get_ref().and_then(|e| e.downcast_ref()),
+                Some(Error::InvalidBlockChecksum { .. })
+            )
+        ));
+
+        let mut reader = Reader::builder(&data[..])
+            .set_validate_checksums(false)
+            .build();
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await?;
+        assert_eq!(buf, b"noodles");
+
         Ok(())
     }
 }
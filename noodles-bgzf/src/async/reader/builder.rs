@@ -8,6 +8,7 @@ use crate::Block;
 pub struct Builder<R> {
     inner: R,
     worker_count: Option<usize>,
+    validate_checksums: bool,
 }
 
 impl<R> Builder<R>
@@ -18,6 +19,7 @@ where
         Self {
             inner,
             worker_count: None,
+            validate_checksums: true,
         }
     }
 
@@ -37,6 +39,24 @@ where
         self
     }
 
+    /// Sets whether to validate the checksum (`CRC32`) of each block.
+    ///
+    /// By default, the uncompressed data of each block is checked against the checksum in the
+    /// block trailer, and a mismatch is an error. Disabling this is faster but can return
+    /// corrupted data. The length of the uncompressed data (`ISIZE`) is always validated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let builder = bgzf::AsyncReader::builder(&data[..]).set_validate_checksums(false);
+    /// ```
+    pub fn set_validate_checksums(mut self, validate_checksums: bool) -> Self {
+        self.validate_checksums = validate_checksums;
+        self
+    }
+
     /// Builds an async BGZF reader.
     ///
     /// # Examples
@@ -50,7 +70,9 @@ where
         let worker_count = self.worker_count.unwrap_or_else(num_cpus::get);
 
         Reader {
-            stream: Some(Inflater::new(self.inner).try_buffered(worker_count)),
+            stream: Some(
+                Inflater::new(self.inner, self.validate_checksums).try_buffered(worker_count),
+            ),
             block: Block::default(),
             position: 0,
             worker_count,
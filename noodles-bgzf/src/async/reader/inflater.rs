@@ -1,6 +1,6 @@
 use std::{
     future::Future,
-    io, mem,
+    io,
     pin::Pin,
     task::{Context, Poll},
 };
@@ -11,7 +11,7 @@ use pin_project_lite::pin_project;
 use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt, SeekFrom};
 use tokio_util::codec::FramedRead;
 
-use crate::{gz, Block, VirtualPosition, BGZF_HEADER_SIZE};
+use crate::{gz, Block, VirtualPosition};
 
 use crate::r#async::BlockCodec;
 
@@ -19,6 +19,7 @@ pin_project! {
     pub struct Inflater<R> {
         #[pin]
         inner: FramedRead<R, BlockCodec>,
+        validate_checksums: bool,
     }
 }
 
@@ -26,11 +27,24 @@ impl<R> Inflater<R>
 where
     R: AsyncRead,
 {
-    pub fn new(inner: R) -> Self {
+    pub fn new(inner: R, validate_checksums: bool) -> Self {
         Self {
             inner: FramedRead::new(inner, BlockCodec),
+            validate_checksums,
         }
     }
+
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
 }
 
 impl<R> Inflater<R>
@@ -54,29 +68,34 @@ where
     type Item = io::Result<Pin<Box<dyn Future<Output = io::Result<Block>> + Send>>>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        match ready!(self.project().inner.poll_next(cx)) {
-            Some(Ok(buf)) => Poll::Ready(Some(Ok(Box::pin(inflate(buf))))),
+        let this = self.project();
+        let validate_checksums = *this.validate_checksums;
+
+        match ready!(this.inner.poll_next(cx)) {
+            Some(Ok(buf)) => Poll::Ready(Some(Ok(Box::pin(inflate(buf, validate_checksums))))),
             Some(Err(e)) => Poll::Ready(Some(Err(e))),
             None => Poll::Ready(None),
         }
     }
 }
 
-async fn inflate(mut src: BytesMut) -> io::Result<Block> {
-    use crate::reader::inflate_data;
+async fn inflate(mut src: BytesMut, validate_checksums: bool) -> io::Result<Block> {
+    use crate::reader::{header_size, inflate_data, validate_checksum, validate_uncompressed_size};
 
     tokio::task::spawn_blocking(move || {
-        let mut header = src.split_to(BGZF_HEADER_SIZE);
+        let mut header = src.split_to(header_size(&src));
         header.advance(16); // [ID1, ..., SLEN]
         let bsize = u64::from(header.get_u16_le()) + 1;
 
         let cdata = src.split_to(src.len() - gz::TRAILER_SIZE);
 
         // trailer
-        src.advance(mem::size_of::<u32>()); // CRC32
+        let crc32 = src.get_u32_le();
         let r#isize = usize::try_from(src.get_u32_le())
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
+        validate_uncompressed_size(r#isize)?;
+
         let mut block = Block::default();
 
         block.set_clen(bsize);
@@ -85,6 +104,10 @@ async fn inflate(mut src: BytesMut) -> io::Result<Block> {
 
         inflate_data(&cdata, block.buffer_mut())?;
 
+        if validate_checksums {
+            validate_checksum(block.buffer(), crc32)?;
+        }
+
         Ok(block)
     })
     .await?
@@ -65,6 +65,32 @@ where
         Self::builder(inner).build()
     }
 
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::AsyncWriter::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.sink.get_ref().get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let mut writer = bgzf::AsyncWriter::new(Vec::new());
+    /// assert!(writer.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut W {
+        self.sink.get_mut().get_mut()
+    }
+
     /// Returns the underlying writer.
     ///
     /// # Examples
@@ -88,16 +114,13 @@ where
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<Result<usize, io::Error>> {
-        if self.buf.len() >= block::MAX_UNCOMPRESSED_DATA_LENGTH {
+        if self.buf.len() >= block::MAX_WRITE_DATA_LENGTH {
             if let Err(e) = ready!(self.as_mut().poll_flush(cx)) {
                 return Poll::Ready(Err(e));
             }
         }
 
-        let n = cmp::min(
-            block::MAX_UNCOMPRESSED_DATA_LENGTH - self.buf.len(),
-            buf.len(),
-        );
+        let n = cmp::min(block::MAX_WRITE_DATA_LENGTH - self.buf.len(), buf.len());
 
         self.as_mut().buf.extend_from_slice(&buf[..n]);
 
@@ -4,6 +4,11 @@ use super::{virtual_position, VirtualPosition};
 
 pub(crate) const MAX_UNCOMPRESSED_DATA_LENGTH: usize = 1 << 16; // bytes
 
+// The maximum uncompressed data length of a written block. This leaves room for the block header,
+// trailer, and DEFLATE overhead when the data does not compress, e.g., with compression disabled,
+// so that the block size still fits in `BSIZE`.
+pub(crate) const MAX_WRITE_DATA_LENGTH: usize = 0xff00; // bytes
+
 /// A BGZF block.
 ///
 /// A BGZF block is a gzip stream less than 64 KiB and contains an extra field describing the size
@@ -39,11 +44,15 @@ impl Block {
     }
 
     /// Returns the compressed data length.
-    #[cfg(feature = "async")]
     pub fn clen(&self) -> u64 {
         self.clen
     }
 
+    /// Returns the position of this block in the compressed stream.
+    pub fn cpos(&self) -> u64 {
+        self.cpos
+    }
+
     /// Sets the position of this block in the compressed stream.
     pub fn set_cpos(&mut self, cpos: u64) {
         self.cpos = cpos;
@@ -80,6 +89,13 @@ impl Block {
     pub fn upos(&self) -> usize {
         self.upos
     }
+
+    /// Returns the uncompressed data of this block.
+    pub fn into_data(self) -> Vec<u8> {
+        let mut data = self.data.into_vec();
+        data.truncate(self.ulen);
+        data
+    }
 }
 
 impl Default for Block {
@@ -0,0 +1,171 @@
+use std::io::{self, BufRead, Read, Write};
+
+use crate::{raw_block::RawBlocks, Reader, Writer};
+
+/// Copies BGZF blocks from a reader to a writer without decompressing them.
+///
+/// The reader is a raw BGZF stream, which is expected to be at the start of a block. Blocks are
+/// written as-is after any data buffered by the writer, which makes this a cheap way to
+/// concatenate BGZF streams. Empty blocks, e.g., EOF markers, are skipped; the writer appends a
+/// single EOF marker when it is finished.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Read, Write};
+/// use noodles_bgzf as bgzf;
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// let src = writer.finish()?;
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// bgzf::copy_blocks(&src[..], &mut writer)?;
+/// bgzf::copy_blocks(&src[..], &mut writer)?;
+/// let data = writer.finish()?;
+///
+/// let mut reader = bgzf::Reader::new(&data[..]);
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf)?;
+/// assert_eq!(buf, b"noodlesnoodles");
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn copy_blocks<R, W>(reader: R, writer: &mut Writer<W>) -> io::Result<()>
+where
+    R: Read,
+    W: Write,
+{
+    for result in RawBlocks::new(reader) {
+        let block = result?;
+
+        if block.uncompressed_len() > 0 {
+            writer.write_raw_block(block.data())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Copies BGZF blocks from a reader to a writer, recompressing them.
+///
+/// Each block is decompressed and compressed again using the writer's settings, e.g., its
+/// compression level. Block boundaries in the uncompressed data are kept, except a block with
+/// more uncompressed data than the writer's maximum block size is split. Empty blocks, e.g., EOF
+/// markers, are skipped.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Read, Write};
+/// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// let src = writer.finish()?;
+///
+/// let mut reader = bgzf::Reader::new(&src[..]);
+/// let mut writer = bgzf::Writer::builder(Vec::new())
+///     .set_compression_level(CompressionLevel::best())
+///     .build();
+/// bgzf::recompress_blocks(&mut reader, &mut writer)?;
+/// let data = writer.finish()?;
+///
+/// let mut reader = bgzf::Reader::new(&data[..]);
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf)?;
+/// assert_eq!(buf, b"noodles");
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn recompress_blocks<R, W>(reader: &mut Reader<R>, writer: &mut Writer<W>) -> io::Result<()>
+where
+    R: Read,
+    W: Write,
+{
+    writer.flush_block()?;
+
+    loop {
+        let buf = reader.fill_buf()?;
+
+        if buf.is_empty() {
+            break;
+        }
+
+        writer.write_all(buf)?;
+        writer.flush_block()?;
+
+        let len = buf.len();
+        reader.consume(len);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::BGZF_EOF;
+
+    fn build_data() -> io::Result<Vec<u8>> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        writer.finish()
+    }
+
+    fn uncompressed_lens(data: &[u8]) -> io::Result<Vec<usize>> {
+        RawBlocks::new(data)
+            .map(|result| result.map(|block| block.uncompressed_len()))
+            .collect()
+    }
+
+    #[test]
+    fn test_copy_blocks() -> io::Result<()> {
+        let src = build_data()?;
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"bam")?;
+        copy_blocks(&src[..], &mut writer)?;
+        copy_blocks(&src[..], &mut writer)?;
+        let data = writer.finish()?;
+
+        assert_eq!(uncompressed_lens(&data)?, [3, 7, 4, 7, 4, 0]);
+        assert!(data.ends_with(BGZF_EOF));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_blocks_after_try_finish() -> io::Result<()> {
+        let src = build_data()?;
+
+        let mut writer = Writer::new(Vec::new());
+        writer.try_finish()?;
+        copy_blocks(&src[..], &mut writer)?;
+        let data = writer.finish()?;
+
+        assert_eq!(uncompressed_lens(&data)?, [0, 7, 4, 0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recompress_blocks() -> io::Result<()> {
+        let src = build_data()?;
+
+        let mut reader = Reader::new(&src[..]);
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"bam")?;
+        recompress_blocks(&mut reader, &mut writer)?;
+        let data = writer.finish()?;
+
+        assert_eq!(uncompressed_lens(&data)?, [3, 7, 4, 0]);
+
+        let mut reader = Reader::new(&data[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"bamnoodlesbgzf");
+
+        Ok(())
+    }
+}
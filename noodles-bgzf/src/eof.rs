@@ -0,0 +1,83 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+use crate::writer::BGZF_EOF;
+
+/// Checks whether a BGZF stream ends with an EOF marker.
+///
+/// The EOF marker is an empty block that a writer appends when it finishes the stream. A stream
+/// without one is likely truncated, similar to the check done by `samtools quickcheck`.
+///
+/// The stream is sought back to its original position after the check.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Cursor, Write};
+/// use noodles_bgzf as bgzf;
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// let mut data = writer.finish()?;
+///
+/// assert!(bgzf::has_eof_marker(&mut Cursor::new(&data))?);
+///
+/// data.truncate(data.len() - 1);
+/// assert!(!bgzf::has_eof_marker(&mut Cursor::new(&data))?);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn has_eof_marker<R>(reader: &mut R) -> io::Result<bool>
+where
+    R: Read + Seek,
+{
+    let pos = reader.stream_position()?;
+    let result = read_eof_marker(reader);
+    reader.seek(SeekFrom::Start(pos))?;
+    result
+}
+
+fn read_eof_marker<R>(reader: &mut R) -> io::Result<bool>
+where
+    R: Read + Seek,
+{
+    let len = reader.seek(SeekFrom::End(0))?;
+    let eof_len = BGZF_EOF.len() as u64;
+
+    if len < eof_len {
+        return Ok(false);
+    }
+
+    reader.seek(SeekFrom::Start(len - eof_len))?;
+
+    let mut buf = vec![0; BGZF_EOF.len()];
+    reader.read_exact(&mut buf)?;
+
+    Ok(buf == BGZF_EOF)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_has_eof_marker() -> io::Result<()> {
+        let mut reader = Cursor::new(BGZF_EOF);
+        assert!(has_eof_marker(&mut reader)?);
+
+        let mut reader = Cursor::new(Vec::new());
+        assert!(!has_eof_marker(&mut reader)?);
+
+        let mut data = b"noodles".to_vec();
+        data.extend_from_slice(BGZF_EOF);
+        let mut reader = Cursor::new(data);
+        reader.set_position(3);
+        assert!(has_eof_marker(&mut reader)?);
+        assert_eq!(reader.position(), 3);
+
+        let mut reader = Cursor::new(&BGZF_EOF[1..]);
+        assert!(!has_eof_marker(&mut reader)?);
+
+        Ok(())
+    }
+}
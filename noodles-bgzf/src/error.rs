@@ -0,0 +1,127 @@
+//! BGZF errors.
+
+use std::{error, fmt, io};
+
+/// An error returned when a BGZF block fails to be read.
+///
+/// Readers continue to return [`std::io::Error`]s, but for invalid data, the error is wrapped in
+/// an `io::Error` with the kind [`std::io::ErrorKind::InvalidData`]. The cause can be inspected by
+/// downcasting the inner error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The block size (`BSIZE` + 1) is invalid.
+    ///
+    /// The block size must be at least the size of the block header and trailer.
+    InvalidBlockSize(usize),
+    /// The uncompressed data length (`ISIZE`) is invalid.
+    ///
+    /// The uncompressed data of a block must be at most 64 KiB.
+    InvalidBlockUncompressedSize(usize),
+    /// The compressed data of a block failed to decompress.
+    InvalidBlockData(io::Error),
+    /// The stream is not gzip-compressed, e.g., it is an uncompressed text file.
+    NotGzip,
+    /// The stream is gzip-compressed but is not BGZF.
+    ///
+    /// The gzip header is missing the BGZF extra field, e.g., the stream was compressed using
+    /// `gzip` rather than `bgzip`.
+    NotBgzf,
+    /// The checksum (`CRC32`) of the uncompressed data of a block does not match.
+    InvalidBlockChecksum {
+        /// The checksum in the block trailer.
+        expected: u32,
+        /// The checksum of the uncompressed data.
+        actual: u32,
+    },
+    /// The gzip extra field (`XLEN` bytes of subfields) is invalid.
+    ///
+    /// A subfield length (`SLEN`) overruns the extra field.
+    InvalidExtraField,
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidBlockSize(_) => None,
+            Self::InvalidBlockUncompressedSize(_) => None,
+            Self::InvalidBlockData(e) => Some(e),
+            Self::NotGzip => None,
+            Self::NotBgzf => None,
+            Self::InvalidBlockChecksum { .. } => None,
+            Self::InvalidExtraField => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBlockSize(actual) => write!(
+                f,
+                "invalid block size: expected >= {}, got {}",
+                crate::BGZF_HEADER_SIZE + crate::gz::TRAILER_SIZE,
+                actual
+            ),
+            Self::InvalidBlockUncompressedSize(actual) => write!(
+                f,
+                "invalid block uncompressed size: expected <= {}, got {}",
+                crate::block::MAX_UNCOMPRESSED_DATA_LENGTH,
+                actual
+            ),
+            Self::InvalidBlockData(_) => f.write_str("invalid block data"),
+            Self::NotGzip => f.write_str("invalid block header: not gzip"),
+            Self::NotBgzf => f.write_str("invalid block header: gzip but not BGZF"),
+            Self::InvalidBlockChecksum { expected, actual } => write!(
+                f,
+                "invalid block checksum: expected {:08x}, got {:08x}",
+                expected, actual
+            ),
+            Self::InvalidExtraField => f.write_str("invalid block header: invalid extra field"),
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        Self::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(
+            Error::InvalidBlockSize(1).to_string(),
+            "invalid block size: expected >= 26, got 1"
+        );
+
+        assert_eq!(
+            Error::InvalidBlockUncompressedSize(65537).to_string(),
+            "invalid block uncompressed size: expected <= 65536, got 65537"
+        );
+
+        assert_eq!(Error::NotGzip.to_string(), "invalid block header: not gzip");
+        assert_eq!(
+            Error::NotBgzf.to_string(),
+            "invalid block header: gzip but not BGZF"
+        );
+
+        assert_eq!(
+            Error::InvalidBlockChecksum {
+                expected: 0x6d6e5ff1,
+                actual: 0
+            }
+            .to_string(),
+            "invalid block checksum: expected 6d6e5ff1, got 00000000"
+        );
+
+        assert_eq!(
+            Error::InvalidExtraField.to_string(),
+            "invalid block header: invalid extra field"
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Gzip extra subfields.
+//!
+//! The gzip header of each BGZF block has an extra field (`FEXTRA`) with at least the BGZF
+//! subfield (`BC`), which holds the block size. The extra field may hold additional subfields,
+//! e.g., custom metadata, which are written after the BGZF subfield.
+//!
+//! Note that some implementations, e.g., htslib, only accept blocks with the BGZF subfield and no
+//! other subfields.
+
+use std::io;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::Error;
+
+// SI1 (1) + SI2 (1) + SLEN (2)
+const SUBFIELD_HEADER_SIZE: usize = 4;
+
+const BGZF_ID: [u8; 2] = [b'B', b'C'];
+
+/// A gzip extra subfield.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtraSubfield {
+    id: [u8; 2],
+    data: Vec<u8>,
+}
+
+impl ExtraSubfield {
+    /// Creates a gzip extra subfield.
+    ///
+    /// This returns an error if the ID is the BGZF subfield ID (`BC`), which is reserved, or if
+    /// the data is longer than 65535 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::extra_subfield::ExtraSubfield;
+    /// let subfield = ExtraSubfield::new(*b"NR", b"noodles".to_vec())?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn new(id: [u8; 2], data: Vec<u8>) -> io::Result<Self> {
+        if id == BGZF_ID {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the BGZF extra subfield ID (BC) is reserved",
+            ))
+        } else if u16::try_from(data.len()).is_err() {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "extra subfield data is too long",
+            ))
+        } else {
+            Ok(Self { id, data })
+        }
+    }
+
+    /// Returns the subfield ID (`SI1`, `SI2`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::extra_subfield::ExtraSubfield;
+    /// let subfield = ExtraSubfield::new(*b"NR", b"noodles".to_vec())?;
+    /// assert_eq!(subfield.id(), *b"NR");
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn id(&self) -> [u8; 2] {
+        self.id
+    }
+
+    /// Returns the subfield data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::extra_subfield::ExtraSubfield;
+    /// let subfield = ExtraSubfield::new(*b"NR", b"noodles".to_vec())?;
+    /// assert_eq!(subfield.data(), b"noodles");
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Parses the subfields in a gzip extra field.
+///
+/// The BGZF subfield is skipped.
+pub(crate) fn parse(mut src: &[u8]) -> io::Result<Vec<ExtraSubfield>> {
+    let mut subfields = Vec::new();
+
+    while !src.is_empty() {
+        if src.len() < SUBFIELD_HEADER_SIZE {
+            return Err(Error::InvalidExtraField.into());
+        }
+
+        let id = [src[0], src[1]];
+        let len = usize::from(LittleEndian::read_u16(&src[2..]));
+        src = &src[SUBFIELD_HEADER_SIZE..];
+
+        if src.len() < len {
+            return Err(Error::InvalidExtraField.into());
+        }
+
+        let (data, rest) = src.split_at(len);
+        src = rest;
+
+        if id != BGZF_ID {
+            subfields.push(ExtraSubfield {
+                id,
+                data: data.to_vec(),
+            });
+        }
+    }
+
+    Ok(subfields)
+}
+
+/// Encodes subfields as part of a gzip extra field.
+pub(crate) fn encode(subfields: &[ExtraSubfield]) -> Vec<u8> {
+    let mut dst = Vec::new();
+
+    for subfield in subfields {
+        dst.extend_from_slice(&subfield.id);
+        // The length is checked when the subfield is created.
+        let len = subfield.data.len() as u16;
+        dst.extend_from_slice(&len.to_le_bytes());
+        dst.extend_from_slice(&subfield.data);
+    }
+
+    dst
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(ExtraSubfield::new(*b"NR", Vec::new()).is_ok());
+        assert!(ExtraSubfield::new(BGZF_ID, Vec::new()).is_err());
+        assert!(ExtraSubfield::new(*b"NR", vec![0; 1 << 16]).is_err());
+    }
+
+    #[test]
+    fn test_parse() -> io::Result<()> {
+        let src = [
+            b'B', b'C', 0x02, 0x00, 0x1b, 0x00, // BC
+            b'N', b'R', 0x03, 0x00, b'n', b'd', b'l', // NR
+        ];
+
+        assert_eq!(parse(&src)?, [ExtraSubfield::new(*b"NR", b"ndl".to_vec())?]);
+
+        assert!(matches!(
+            parse(&src[..src.len() - 1]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            parse(&src[..8]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode() -> io::Result<()> {
+        let subfields = [
+            ExtraSubfield::new(*b"NR", b"ndl".to_vec())?,
+            ExtraSubfield::new(*b"NZ", Vec::new())?,
+        ];
+
+        assert_eq!(
+            encode(&subfields),
+            [b'N', b'R', 0x03, 0x00, b'n', b'd', b'l', b'N', b'Z', 0x00, 0x00]
+        );
+
+        Ok(())
+    }
+}
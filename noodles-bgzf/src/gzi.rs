@@ -0,0 +1,68 @@
+//! gzip index (GZI).
+//!
+//! A gzip index maps uncompressed positions to the compressed positions of the blocks that
+//! contain them. This is typically used to randomly access a bgzipped file by its uncompressed
+//! positions, e.g., using the offsets of a FASTA index (FAI).
+
+mod reader;
+
+pub use self::reader::Reader;
+
+use std::{fs::File, io, path::Path};
+
+/// A gzip index.
+///
+/// Each entry is a pair of a block's compressed position and its uncompressed position. The
+/// first block, i.e., (0, 0), is implicit and not included.
+pub type Index = Vec<(u64, u64)>;
+
+/// Reads the entire contents of a gzip index.
+///
+/// This is a convenience function and is equivalent to opening the file at the given path and
+/// reading the index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::gzi;
+/// let index = gzi::read("reference.fa.gz.gzi")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn read<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(Reader::new)?;
+    reader.read_index()
+}
+
+// Returns the compressed and uncompressed positions of the block that contains the given
+// uncompressed position.
+pub(crate) fn resolve_uncompressed_position(index: &[(u64, u64)], pos: u64) -> (u64, u64) {
+    let i = index.partition_point(|&(_, upos)| upos <= pos);
+
+    if i == 0 {
+        (0, 0)
+    } else {
+        index[i - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_uncompressed_position() {
+        let index = vec![(4668, 21294), (23810, 86529)];
+
+        assert_eq!(resolve_uncompressed_position(&index, 0), (0, 0));
+        assert_eq!(resolve_uncompressed_position(&index, 21293), (0, 0));
+        assert_eq!(resolve_uncompressed_position(&index, 21294), (4668, 21294));
+        assert_eq!(resolve_uncompressed_position(&index, 86528), (4668, 21294));
+        assert_eq!(resolve_uncompressed_position(&index, 90000), (23810, 86529));
+
+        assert_eq!(resolve_uncompressed_position(&[], 8), (0, 0));
+    }
+}
@@ -0,0 +1,102 @@
+use std::io::{self, Read};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use super::Index;
+
+/// A gzip index (GZI) reader.
+pub struct Reader<R> {
+    inner: R,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    /// Creates a gzip index reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let data = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+    /// let reader = gzi::Reader::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Reads a gzip index.
+    ///
+    /// The position of the stream is expected to be at the start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::gzi;
+    ///
+    /// let data = [
+    ///     0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // number_entries = 1
+    ///     0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 8
+    ///     0x0d, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 13
+    /// ];
+    ///
+    /// let mut reader = gzi::Reader::new(&data[..]);
+    /// let index = reader.read_index()?;
+    ///
+    /// assert_eq!(index, [(8, 13)]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_index(&mut self) -> io::Result<Index> {
+        let len = self.inner.read_u64::<LittleEndian>().and_then(|n| {
+            usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })?;
+
+        // The number of entries is untrusted, so the index grows as entries are read.
+        let mut index = Vec::new();
+
+        for _ in 0..len {
+            let compressed_offset = self.inner.read_u64::<LittleEndian>()?;
+            let uncompressed_offset = self.inner.read_u64::<LittleEndian>()?;
+            index.push((compressed_offset, uncompressed_offset));
+        }
+
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_index() -> io::Result<()> {
+        let data = [
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // number_entries = 2
+            0x3c, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 4668
+            0x2e, 0x53, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 21294
+            0x02, 0x5d, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 23810
+            0x01, 0x52, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 86529
+        ];
+
+        let mut reader = Reader::new(&data[..]);
+        assert_eq!(reader.read_index()?, [(4668, 21294), (23810, 86529)]);
+
+        let data = [0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let mut reader = Reader::new(&data[..]);
+        assert!(matches!(
+            reader.read_index(),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        let data = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x0f];
+        let mut reader = Reader::new(&data[..]);
+        assert!(matches!(
+            reader.read_index(),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+
+use super::{gzi, Reader};
+
+/// An indexed BGZF reader.
+///
+/// This pairs a BGZF reader with its associated gzip index (GZI), allowing the stream to be
+/// seeked by uncompressed positions using [`Seek`].
+///
+/// If there is no gzip index, the reader can be created with [`IndexedReader::without_index`].
+/// The index is then built by scanning blocks forward from the last known block as positions past
+/// it are sought.
+///
+/// This is equivalent to a [`Reader`] built with a gzip index (see
+/// [`crate::reader::Builder::set_gzi_index`]), which itself implements [`Seek`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io::{self, Read, Seek, SeekFrom}};
+/// use noodles_bgzf::{self as bgzf, gzi};
+///
+/// let index = gzi::read("reference.fa.gz.gzi")?;
+/// let mut reader = File::open("reference.fa.gz").map(|f| bgzf::IndexedReader::new(f, index))?;
+///
+/// reader.seek(SeekFrom::Start(21294))?;
+///
+/// let mut buf = [0; 8];
+/// reader.read_exact(&mut buf)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct IndexedReader<R> {
+    inner: Reader<R>,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read,
+{
+    /// Creates an indexed BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// ```
+    pub fn new(inner: R, index: gzi::Index) -> Self {
+        Self {
+            inner: Reader::builder(inner).set_gzi_index(index).build(),
+        }
+    }
+
+    /// Creates an indexed BGZF reader without a gzip index.
+    ///
+    /// The index is built as needed by scanning blocks when seeking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, Read, Seek, SeekFrom, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush()?;
+    /// writer.write_all(b"-bgzf")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = bgzf::IndexedReader::without_index(Cursor::new(data));
+    /// reader.seek(SeekFrom::Start(8))?;
+    ///
+    /// let mut buf = [0; 4];
+    /// reader.read_exact(&mut buf)?;
+    /// assert_eq!(&buf, b"bgzf");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn without_index(inner: R) -> Self {
+        Self {
+            inner: Reader::new(inner),
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// assert!(reader.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &Reader<R> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let mut reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// assert!(reader.get_mut().get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut Reader<R> {
+        &mut self.inner
+    }
+
+    /// Unwraps and returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// assert!(reader.into_inner().into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> Reader<R> {
+        self.inner
+    }
+
+    /// Returns the associated gzip index.
+    ///
+    /// If the reader was created without an index, this is the part of the index that has been
+    /// built so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// assert!(reader.index().is_empty());
+    /// ```
+    pub fn index(&self) -> &gzi::Index {
+        self.inner.gzi_index()
+    }
+}
+
+impl<R> Read for IndexedReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<R> BufRead for IndexedReader<R>
+where
+    R: Read,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+    }
+}
+
+impl<R> Seek for IndexedReader<R>
+where
+    R: Read + Seek,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        Seek::seek(&mut self.inner, pos)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        self.inner.stream_position()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use super::*;
+    use crate::Writer;
+
+    #[test]
+    fn test_seek() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        let cpos = writer.get_ref().len() as u64;
+        writer.write_all(b"-bgzf")?;
+        let data = writer.finish()?;
+
+        let index = vec![(cpos, 7)];
+        let mut reader = IndexedReader::new(Cursor::new(data), index);
+
+        let mut buf = [0; 4];
+
+        assert_eq!(reader.seek(SeekFrom::Start(8))?, 8);
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"bgzf");
+
+        assert_eq!(reader.seek(SeekFrom::Start(3))?, 3);
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"dles");
+
+        assert_eq!(reader.seek(SeekFrom::Current(-6))?, 1);
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"oodl");
+
+        let mut line = String::new();
+        reader.seek(SeekFrom::Start(4))?;
+        reader.read_line(&mut line)?;
+        assert_eq!(line, "les-bgzf");
+        assert_eq!(reader.seek(SeekFrom::Current(-4))?, 8);
+
+        assert_eq!(reader.seek(SeekFrom::End(-4))?, 8);
+        assert_eq!(reader.stream_position()?, 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek_without_index() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+
+        for s in ["noodles", "-", "bgzf"] {
+            writer.write_all(s.as_bytes())?;
+            writer.flush()?;
+        }
+
+        let data = writer.finish()?;
+
+        let mut reader = IndexedReader::without_index(Cursor::new(data));
+
+        let mut buf = [0; 4];
+
+        assert_eq!(reader.seek(SeekFrom::Start(3))?, 3);
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"dles");
+        assert!(reader.index().is_empty());
+
+        assert_eq!(reader.seek(SeekFrom::Start(8))?, 8);
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"bgzf");
+        assert_eq!(reader.index(), &[(35, 7), (64, 8)]);
+
+        assert_eq!(reader.seek(SeekFrom::Start(1))?, 1);
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"oodl");
+
+        assert_eq!(reader.seek(SeekFrom::Start(12))?, 12);
+        assert_eq!(reader.read(&mut buf)?, 0);
+        assert_eq!(reader.index(), &[(35, 7), (64, 8)]);
+
+        Ok(())
+    }
+}
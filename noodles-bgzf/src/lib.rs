@@ -33,17 +33,64 @@
 //! writer.write_all(b"noodles-bgzf")?;
 //! # Ok::<(), io::Error>(())
 //! ```
+//!
+//! # Feature flags
+//!
+//!   * `async`: Adds async readers and writers using Tokio.
+//!   * `io-uring`: Adds an io_uring-backed file for async readers on Linux (see `uring`).
+//!   * `libdeflate`: Uses [libdeflate] to compress and decompress blocks.
+//!
+//!     This applies to the synchronous and async readers and writers. Note that enabling
+//!     `libdeflater`, the name of the optional dependency, only adds the dependency and does not
+//!     change the backend.
+//!
+//!   * `mmap`: Adds reading from memory-mapped files (see [`mmap`]).
+//!
+//! By default, blocks are compressed and decompressed using the pure Rust backend of [flate2],
+//! which does not depend on a C toolchain.
+//!
+//! # WebAssembly
+//!
+//! The synchronous reader and writer can be built for `wasm32-unknown-unknown` using the default
+//! backend. Since the target cannot spawn threads, a [`WorkerPool`] runs its jobs on the calling
+//! thread. Convenience functions that open files (e.g., [`gzi::read`]) return errors at runtime.
+//!
+//! [libdeflate]: https://github.com/ebiggers/libdeflate
+//! [flate2]: https://crates.io/crates/flate2
 
 #[cfg(feature = "async")]
 mod r#async;
 
 mod block;
+mod copy;
+mod eof;
+mod error;
+pub mod extra_subfield;
 mod gz;
-mod reader;
+pub mod gzi;
+mod indexed_reader;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod observer;
+pub mod raw_block;
+pub mod reader;
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub mod uring;
 pub mod virtual_position;
+pub mod worker_pool;
 pub mod writer;
 
-pub use self::{reader::Reader, virtual_position::VirtualPosition, writer::Writer};
+pub use self::{
+    copy::{copy_blocks, recompress_blocks},
+    eof::has_eof_marker,
+    error::Error,
+    indexed_reader::IndexedReader,
+    observer::Observer,
+    reader::Reader,
+    virtual_position::VirtualPosition,
+    worker_pool::WorkerPool,
+    writer::Writer,
+};
 
 #[cfg(feature = "async")]
 pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
@@ -0,0 +1,305 @@
+//! Memory-mapped files.
+//!
+//! A [`MappedFile`] maps an entire file into memory and reads from the mapping rather than
+//! issuing a system call per read. This is most useful for random access-heavy workloads, e.g.,
+//! many small queries over an indexed file.
+//!
+//! A BGZF reader opened with [`open`] or [`builder`] decompresses blocks directly from the
+//! mapping without first copying the compressed data. Because a mapped file implements
+//! [`BufRead`] and [`Seek`], it can also be passed to readers of other formats, e.g., to parse
+//! headers and indexes from the mapping.
+//!
+//! # Safety
+//!
+//! The contents of a mapping can change if the underlying file is modified by this or another
+//! process. The behavior of reading from a file that is concurrently modified or truncated is
+//! undefined, which is why [`MappedFile::open`] and [`open`] are `unsafe`. Only map files that
+//! are not expected to change while they are being read.
+
+use std::{
+    cmp,
+    fs::File,
+    io::{self, BufRead, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use memmap2::Mmap;
+
+use crate::{reader, Reader};
+
+/// A read-only memory-mapped file.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io::{self, Read};
+/// use noodles_bgzf::mmap::MappedFile;
+///
+/// // SAFETY: `sample.bam.bai` is not modified while it is mapped.
+/// let mut file = unsafe { MappedFile::open("sample.bam.bai")? };
+///
+/// let mut magic = [0; 4];
+/// file.read_exact(&mut magic)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct MappedFile {
+    mmap: Mmap,
+    position: usize,
+}
+
+impl MappedFile {
+    /// Maps the file at the given path into memory.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this or any other process, for the lifetime
+    /// of the returned mapping. Reads from a mapping of a file that changes are undefined
+    /// behavior, e.g., reading past the end of a truncated file can raise `SIGBUS`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_bgzf::mmap::MappedFile;
+    /// // SAFETY: `sample.bam` is not modified while it is mapped.
+    /// let file = unsafe { MappedFile::open("sample.bam")? };
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub unsafe fn open<P>(src: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(src)?;
+
+        // SAFETY: The mapping is read-only, and the caller guarantees that the underlying file is
+        // not modified while it is mapped.
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        Ok(Self { mmap, position: 0 })
+    }
+
+    /// Returns the mapped data.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_bgzf::mmap::MappedFile;
+    /// let file = unsafe { MappedFile::open("sample.bam")? };
+    /// let data = file.as_bytes();
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    /// Returns the current position of the cursor.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_bgzf::mmap::MappedFile;
+    /// let file = unsafe { MappedFile::open("sample.bam")? };
+    /// assert_eq!(file.position(), 0);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position as u64
+    }
+
+    /// Advises the operating system that the mapping will be accessed randomly.
+    ///
+    /// This disables aggressive read-ahead of the mapping, which can reduce I/O when only a small
+    /// number of blocks are read per query, e.g., when querying an indexed file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_bgzf::mmap::MappedFile;
+    /// let file = unsafe { MappedFile::open("sample.bam")? };
+    /// file.advise_random_access()?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    #[cfg(unix)]
+    pub fn advise_random_access(&self) -> io::Result<()> {
+        self.mmap.advise(memmap2::Advice::Random)
+    }
+
+    fn remaining(&self) -> &[u8] {
+        let i = cmp::min(self.position, self.mmap.len());
+        &self.mmap[i..]
+    }
+}
+
+impl Read for MappedFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.remaining().read(buf)?;
+        self.consume(n);
+        Ok(n)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.remaining().read_exact(buf)?;
+        self.consume(buf.len());
+        Ok(())
+    }
+}
+
+impl BufRead for MappedFile {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.remaining())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.position = cmp::min(self.position + amt, self.mmap.len());
+    }
+}
+
+impl Seek for MappedFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => {
+                self.position = usize::try_from(n)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+                return Ok(n);
+            }
+            SeekFrom::End(n) => (self.mmap.len() as u64, n),
+            SeekFrom::Current(n) => (self.position as u64, n),
+        };
+
+        let position = if offset >= 0 {
+            base.checked_add(offset as u64)
+        } else {
+            base.checked_sub(offset.unsigned_abs())
+        }
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        self.position = usize::try_from(position)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        Ok(position)
+    }
+}
+
+/// Opens a memory-mapped BGZF file.
+///
+/// Blocks are decompressed directly from the mapping.
+///
+/// # Safety
+///
+/// The file must not be modified or truncated while it is mapped. See [`MappedFile::open`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io::{self, Read};
+/// use noodles_bgzf::{self as bgzf, mmap};
+///
+/// // SAFETY: `sample.bam` is not modified while it is mapped.
+/// let mut reader = unsafe { mmap::open("sample.bam")? };
+/// reader.seek(bgzf::VirtualPosition::from(102334155))?;
+///
+/// let mut buf = [0; 4];
+/// reader.read_exact(&mut buf)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub unsafe fn open<P>(src: P) -> io::Result<Reader<MappedFile>>
+where
+    P: AsRef<Path>,
+{
+    // SAFETY: The caller upholds the contract of `MappedFile::open`.
+    unsafe { MappedFile::open(src) }.map(|file| builder(file).build())
+}
+
+/// Creates a BGZF reader builder for a memory-mapped file.
+///
+/// Unlike [`Reader::builder`], the built reader decompresses blocks directly from the mapping.
+/// If a worker pool is set, the compressed data is still copied to be sent to the workers.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use noodles_bgzf::{mmap::{self, MappedFile}, observer::Counter};
+///
+/// let file = unsafe { MappedFile::open("sample.bam")? };
+/// let reader = mmap::builder(file)
+///     .set_observer(Arc::new(Counter::default()))
+///     .build();
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn builder(file: MappedFile) -> reader::Builder<MappedFile> {
+    reader::Builder::new(file).set_inflate_block(reader::inflate_buffered_block)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::{VirtualPosition, Writer};
+
+    fn write_tmp_file(name: &str, data: &[u8]) -> io::Result<std::path::PathBuf> {
+        let path = std::env::temp_dir().join(format!("noodles-bgzf-mmap-{}", name));
+        File::create(&path)?.write_all(data)?;
+        Ok(path)
+    }
+
+    #[test]
+    fn test_read_and_seek() -> io::Result<()> {
+        let path = write_tmp_file("test_read_and_seek", b"noodles")?;
+        let mut file = unsafe { MappedFile::open(&path)? };
+
+        let mut buf = [0; 3];
+        file.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"noo");
+        assert_eq!(file.fill_buf()?, b"dles");
+
+        assert_eq!(file.seek(SeekFrom::End(-2))?, 5);
+        assert_eq!(file.fill_buf()?, b"es");
+
+        assert_eq!(file.seek(SeekFrom::Current(-4))?, 1);
+        assert_eq!(file.fill_buf()?, b"oodles");
+
+        assert!(file.seek(SeekFrom::Current(-2)).is_err());
+
+        file.seek(SeekFrom::Start(13))?;
+        assert!(file.fill_buf()?.is_empty());
+        assert_eq!(file.read(&mut buf)?, 0);
+
+        std::fs::remove_file(path)
+    }
+
+    #[test]
+    fn test_open() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        let position = VirtualPosition::try_from((writer.get_ref().len() as u64, 0)).unwrap();
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let path = write_tmp_file("test_open", &data)?;
+        let mut reader = unsafe { open(&path)? };
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodlesbgzf");
+
+        reader.seek(position)?;
+        buf.clear();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"bgzf");
+
+        reader.seek(VirtualPosition::from(0))?;
+        let mut buf = vec![0; crate::block::MAX_UNCOMPRESSED_DATA_LENGTH];
+        assert_eq!(reader.read(&mut buf)?, 7);
+        assert_eq!(&buf[..7], b"noodles");
+
+        std::fs::remove_file(path)
+    }
+}
@@ -0,0 +1,225 @@
+//! Progress observers.
+//!
+//! An [`Observer`] is notified as blocks and records are read or written, e.g., to report progress
+//! or measure throughput. It can be set on BGZF readers and writers (see
+//! [`crate::reader::Builder::set_observer`] and [`crate::writer::Builder::set_observer`]) and is
+//! shared with the readers and writers of formats that use BGZF, which additionally report
+//! records.
+
+use std::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// A progress observer.
+///
+/// All methods have default implementations that do nothing.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use noodles_bgzf::Observer;
+///
+/// #[derive(Default)]
+/// struct Progress {
+///     compressed_bytes: AtomicU64,
+/// }
+///
+/// impl Observer for Progress {
+///     fn on_block(&self, compressed_len: u64, _: u64) {
+///         self.compressed_bytes.fetch_add(compressed_len, Ordering::Relaxed);
+///     }
+/// }
+/// ```
+pub trait Observer: Send + Sync {
+    /// Called after a block is read or written.
+    ///
+    /// The lengths are the size of the block in the compressed stream and the size of its
+    /// uncompressed data, respectively.
+    fn on_block(&self, compressed_len: u64, uncompressed_len: u64) {
+        let _ = (compressed_len, uncompressed_len);
+    }
+
+    /// Called after a block is read, before [`Self::on_block`].
+    ///
+    /// `position` is the start of the block in the compressed stream, i.e., the compressed
+    /// position of the virtual positions of the data in the block. With the uncompressed length,
+    /// this is enough to build a gzip index (GZI) or to map uncompressed offsets to virtual
+    /// positions while reading.
+    ///
+    /// This is only called by readers.
+    fn on_block_start(&self, position: u64, uncompressed_len: u64) {
+        let _ = (position, uncompressed_len);
+    }
+
+    /// Called after a record is read or written.
+    fn on_record(&self) {}
+}
+
+impl fmt::Debug for dyn Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Observer")
+    }
+}
+
+/// An observer that counts blocks, bytes, and records.
+///
+/// The counters can be read at any time, e.g., from another thread.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Read, Write};
+/// use std::sync::Arc;
+/// use noodles_bgzf::{self as bgzf, observer::Counter};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// let data = writer.finish()?;
+///
+/// let counter = Arc::new(Counter::default());
+/// let mut reader = bgzf::Reader::builder(&data[..])
+///     .set_observer(counter.clone())
+///     .build();
+///
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf)?;
+///
+/// assert_eq!(counter.uncompressed_bytes(), 7);
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct Counter {
+    block_count: AtomicU64,
+    compressed_bytes: AtomicU64,
+    uncompressed_bytes: AtomicU64,
+    record_count: AtomicU64,
+}
+
+impl Counter {
+    /// Returns the number of blocks read or written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::observer::Counter;
+    /// let counter = Counter::default();
+    /// assert_eq!(counter.block_count(), 0);
+    /// ```
+    pub fn block_count(&self) -> u64 {
+        self.block_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of compressed bytes read or written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::observer::Counter;
+    /// let counter = Counter::default();
+    /// assert_eq!(counter.compressed_bytes(), 0);
+    /// ```
+    pub fn compressed_bytes(&self) -> u64 {
+        self.compressed_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of uncompressed bytes read or written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::observer::Counter;
+    /// let counter = Counter::default();
+    /// assert_eq!(counter.uncompressed_bytes(), 0);
+    /// ```
+    pub fn uncompressed_bytes(&self) -> u64 {
+        self.uncompressed_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the compression ratio, i.e., the number of uncompressed bytes per compressed byte.
+    ///
+    /// This is `None` if no compressed bytes were read or written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use std::sync::Arc;
+    /// use noodles_bgzf::{self as bgzf, observer::Counter};
+    ///
+    /// let counter = Arc::new(Counter::default());
+    /// assert!(counter.compression_ratio().is_none());
+    ///
+    /// let mut writer = bgzf::Writer::builder(Vec::new())
+    ///     .set_observer(counter.clone())
+    ///     .build();
+    /// writer.write_all(&[0; 1024])?;
+    /// writer.finish()?;
+    ///
+    /// assert!(counter.compression_ratio() > Some(1.0));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn compression_ratio(&self) -> Option<f64> {
+        match self.compressed_bytes() {
+            0 => None,
+            n => Some(self.uncompressed_bytes() as f64 / n as f64),
+        }
+    }
+
+    /// Returns the number of records read or written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::observer::Counter;
+    /// let counter = Counter::default();
+    /// assert_eq!(counter.record_count(), 0);
+    /// ```
+    pub fn record_count(&self) -> u64 {
+        self.record_count.load(Ordering::Relaxed)
+    }
+}
+
+impl Observer for Counter {
+    fn on_block(&self, compressed_len: u64, uncompressed_len: u64) {
+        self.block_count.fetch_add(1, Ordering::Relaxed);
+        self.compressed_bytes
+            .fetch_add(compressed_len, Ordering::Relaxed);
+        self.uncompressed_bytes
+            .fetch_add(uncompressed_len, Ordering::Relaxed);
+    }
+
+    fn on_record(&self) {
+        self.record_count.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observer_for_counter() {
+        let counter = Counter::default();
+
+        counter.on_block(21, 8);
+        counter.on_block(13, 5);
+        counter.on_record();
+
+        assert_eq!(counter.block_count(), 2);
+        assert_eq!(counter.compressed_bytes(), 34);
+        assert_eq!(counter.uncompressed_bytes(), 13);
+        assert_eq!(counter.record_count(), 1);
+    }
+
+    #[test]
+    fn test_compression_ratio() {
+        let counter = Counter::default();
+        assert!(counter.compression_ratio().is_none());
+
+        counter.on_block(4, 8);
+        counter.on_block(6, 7);
+        assert_eq!(counter.compression_ratio(), Some(1.5));
+    }
+}
@@ -0,0 +1,315 @@
+//! Raw BGZF blocks.
+//!
+//! Raw blocks are read without being decompressed, which allows fast block-level operations,
+//! e.g., concatenating or splitting BGZF files or building a gzip index (GZI).
+
+use std::io::{self, Read};
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use super::{
+    extra_subfield::{self, ExtraSubfield},
+    gz,
+    reader::{header_size, validate_header, validate_uncompressed_size},
+    Error, BGZF_HEADER_SIZE, GZIP_XLEN_SIZE,
+};
+
+/// A raw BGZF block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RawBlock {
+    position: u64,
+    data: Vec<u8>,
+}
+
+impl RawBlock {
+    /// Returns the position of the block in the compressed stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::{self as bgzf, raw_block::RawBlocks};
+    ///
+    /// let data = bgzf::Writer::new(Vec::new()).finish()?;
+    /// let block = RawBlocks::new(&data[..]).next().transpose()?.unwrap();
+    /// assert_eq!(block.position(), 0);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the raw data of the block.
+    ///
+    /// This is the entire block, including the header and trailer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::{self as bgzf, raw_block::RawBlocks};
+    ///
+    /// let data = bgzf::Writer::new(Vec::new()).finish()?;
+    /// let block = RawBlocks::new(&data[..]).next().transpose()?.unwrap();
+    /// assert_eq!(block.data(), data);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the length of the uncompressed data of the block (`ISIZE`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, raw_block::RawBlocks};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let block = RawBlocks::new(&data[..]).next().transpose()?.unwrap();
+    /// assert_eq!(block.uncompressed_len(), 7);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn uncompressed_len(&self) -> usize {
+        // ISIZE is the last field of the block.
+        let r#isize = LittleEndian::read_u32(&self.data[self.data.len() - 4..]);
+        r#isize as usize
+    }
+
+    /// Returns the additional gzip extra subfields of the block.
+    ///
+    /// The BGZF subfield (`BC`) is not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, extra_subfield::ExtraSubfield, raw_block::RawBlocks};
+    ///
+    /// let subfield = ExtraSubfield::new(*b"NR", b"ndl".to_vec())?;
+    ///
+    /// let mut writer = bgzf::Writer::builder(Vec::new())
+    ///     .set_extra_subfields(vec![subfield.clone()])
+    ///     .build();
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let block = RawBlocks::new(&data[..]).next().transpose()?.unwrap();
+    /// assert_eq!(block.extra_subfields()?, [subfield]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn extra_subfields(&self) -> io::Result<Vec<ExtraSubfield>> {
+        const XLEN_END: usize = gz::HEADER_SIZE + GZIP_XLEN_SIZE;
+        extra_subfield::parse(&self.data[XLEN_END..header_size(&self.data)])
+    }
+
+    /// Returns the raw data of the block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::{self as bgzf, raw_block::RawBlocks};
+    ///
+    /// let data = bgzf::Writer::new(Vec::new()).finish()?;
+    /// let block = RawBlocks::new(&data[..]).next().transpose()?.unwrap();
+    /// assert_eq!(block.into_data(), data);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/// An iterator over raw BGZF blocks.
+///
+/// Blocks are read without being decompressed. Block positions are relative to the position of
+/// the stream when the iterator is created, which is expected to be at the start of a block.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Write};
+/// use noodles_bgzf::{self as bgzf, raw_block::RawBlocks};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// writer.flush()?;
+/// writer.write_all(b"bgzf")?;
+/// let data = writer.finish()?;
+///
+/// let blocks = RawBlocks::new(&data[..])
+///     .map(|result| result.map(|block| (block.position(), block.uncompressed_len())))
+///     .collect::<io::Result<Vec<_>>>()?;
+///
+/// assert_eq!(blocks, [(0, 7), (35, 4), (67, 0)]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct RawBlocks<R> {
+    inner: R,
+    position: u64,
+}
+
+impl<R> RawBlocks<R>
+where
+    R: Read,
+{
+    /// Creates an iterator over raw BGZF blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::raw_block::RawBlocks;
+    /// let data = [];
+    /// let mut blocks = RawBlocks::new(&data[..]);
+    /// assert!(blocks.next().is_none());
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner, position: 0 }
+    }
+
+    /// Unwraps and returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::raw_block::RawBlocks;
+    /// let data = [];
+    /// let blocks = RawBlocks::new(&data[..]);
+    /// assert!(blocks.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn read_raw_block(&mut self) -> io::Result<Option<RawBlock>> {
+        let mut data = vec![0; BGZF_HEADER_SIZE];
+
+        if !read_header(&mut self.inner, &mut data)? {
+            return Ok(None);
+        }
+
+        validate_header(&data)?;
+
+        let header_size = header_size(&data);
+
+        // Add 1 because BSIZE is "total Block SIZE minus 1".
+        let block_size = usize::from(LittleEndian::read_u16(&data[16..])) + 1;
+
+        if block_size < header_size + gz::TRAILER_SIZE {
+            return Err(Error::InvalidBlockSize(block_size).into());
+        }
+
+        data.resize(block_size, 0);
+        self.inner.read_exact(&mut data[BGZF_HEADER_SIZE..])?;
+
+        let block = RawBlock {
+            position: self.position,
+            data,
+        };
+
+        validate_uncompressed_size(block.uncompressed_len())?;
+
+        self.position += block_size as u64;
+
+        Ok(Some(block))
+    }
+}
+
+impl<R> Iterator for RawBlocks<R>
+where
+    R: Read,
+{
+    type Item = io::Result<RawBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_raw_block().transpose()
+    }
+}
+
+// Reads a block header into the given buffer.
+//
+// This returns `false` if the stream is at EOF. A partial header is an error.
+fn read_header<R>(reader: &mut R, buf: &mut [u8]) -> io::Result<bool>
+where
+    R: Read,
+{
+    let mut len = 0;
+
+    while len < buf.len() {
+        match reader.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    match len {
+        0 => Ok(false),
+        n if n == buf.len() => Ok(true),
+        _ => Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::{writer::BGZF_EOF, Writer};
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let blocks: Vec<_> = RawBlocks::new(&data[..]).collect::<io::Result<_>>()?;
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[2].position(), (data.len() - BGZF_EOF.len()) as u64);
+        assert_eq!(blocks[2].data(), BGZF_EOF);
+
+        let concatenated_data: Vec<_> = blocks.into_iter().flat_map(RawBlock::into_data).collect();
+        assert_eq!(concatenated_data, data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_truncated_block() {
+        let data = &BGZF_EOF[..BGZF_EOF.len() - 1];
+        let mut blocks = RawBlocks::new(data);
+        assert!(matches!(
+            blocks.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        let data = &BGZF_EOF[..4];
+        let mut blocks = RawBlocks::new(data);
+        assert!(matches!(
+            blocks.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+
+    #[test]
+    fn test_next_with_invalid_block_size() {
+        let mut data = BGZF_EOF.to_vec();
+        data[16] = 0x04;
+        data[17] = 0x00;
+
+        let mut blocks = RawBlocks::new(&data[..]);
+        assert!(matches!(
+            blocks.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
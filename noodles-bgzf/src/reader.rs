@@ -1,22 +1,55 @@
+//! BGZF reader.
+
+mod builder;
+mod decompressed_blocks;
+
+pub use self::{
+    builder::Builder,
+    decompressed_blocks::{DecompressedBlock, DecompressedBlocks},
+};
+
 use std::{
+    cell::RefCell,
     cmp,
+    fs::File,
     io::{self, BufRead, Read, Seek, SeekFrom},
+    mem,
+    path::Path,
+    sync::Arc,
 };
 
 use byteorder::{ByteOrder, LittleEndian};
 
-use super::{gz, Block, VirtualPosition, BGZF_HEADER_SIZE};
+use super::{
+    gz, gzi,
+    worker_pool::{TaskQueue, WorkerPool},
+    Block, Error, Observer, VirtualPosition, BGZF_HEADER_SIZE, BGZF_XLEN, GZIP_XLEN_SIZE,
+};
 
 /// A BGZF reader.
 ///
-/// Due to the static structure of a BGZF block, gzip headers are mostly discarded. CRC32
-/// validation is also disabled when decompressing data.
+/// Due to the static structure of a BGZF block, gzip headers are mostly discarded. The gzip
+/// trailer is used to validate the uncompressed data of each block: its length must match `ISIZE`
+/// and, unless disabled (see [`Builder::set_validate_checksums`]), its CRC32 must match the
+/// checksum.
 ///
 /// The reader implements both [`std::io::Read`] and [`std::io::BufRead`], consuming compressed
 /// data and emitting uncompressed data. It is internally buffered by a single block, and to
 /// correctly track (virtual) positions, the reader _cannot_ be double buffered (e.g., using
 /// [`std::io::BufReader`]).
 ///
+/// Buffers and decompressor state are reused across blocks, so reading does not allocate once the
+/// reader is warmed up. When [`Read::read`] is called at the start of a block with a
+/// caller-owned buffer of at least 64 KiB, the block is decompressed directly into that buffer,
+/// skipping the internal block buffer. [`Reader::read_block_into`] reads whole blocks this way
+/// into a reusable [`Vec`].
+///
+/// When the underlying stream is seekable, the reader also implements [`std::io::Seek`] using
+/// uncompressed positions. Blocks are found using a gzip index (GZI), if given (see
+/// [`Builder::set_gzi_index`]), and otherwise by scanning blocks forward, which extends the index
+/// as the stream is sought. Note that the inherent [`Reader::seek`] takes a virtual position;
+/// use [`Seek::seek`] to seek by an uncompressed position.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -32,6 +65,84 @@ pub struct Reader<R> {
     position: u64,
     cdata: Vec<u8>,
     block: Block,
+    read_ahead: Option<ReadAhead>,
+    observer: Option<Arc<dyn Observer>>,
+    inflate_block: InflateBlockFn<R>,
+    validate_checksums: bool,
+    gzi_index: gzi::Index,
+    // The compressed and uncompressed positions of the next block to scan to extend the gzip
+    // index.
+    scan_position: (u64, u64),
+    is_gzi_index_complete: bool,
+}
+
+// A block and the buffer of its compressed data.
+type BlockBuffers = (Block, Vec<u8>);
+
+// Blocks read ahead of the current block and decompressed using a worker pool.
+struct ReadAhead {
+    worker_pool: WorkerPool,
+    tasks: TaskQueue<io::Result<BlockBuffers>>,
+    // The position of the underlying stream.
+    position: u64,
+    // Consumed blocks and compressed data buffers that are reused for the next blocks.
+    free_buffers: Vec<BlockBuffers>,
+}
+
+impl ReadAhead {
+    fn new(worker_pool: WorkerPool) -> Self {
+        let tasks = TaskQueue::new(&worker_pool);
+
+        Self {
+            worker_pool,
+            tasks,
+            position: 0,
+            free_buffers: Vec::new(),
+        }
+    }
+
+    // Reads compressed blocks and submits them to be decompressed until either the queue is full
+    // or the underlying stream reaches EOF.
+    fn fill<R>(&mut self, reader: &mut R, validate_checksums: bool) -> io::Result<()>
+    where
+        R: Read,
+    {
+        while !self.tasks.is_full() {
+            let (mut block, mut cdata) = self.free_buffers.pop().unwrap_or_default();
+
+            let (clen, crc32, ulen) = match read_compressed_block(reader, &mut cdata)? {
+                (0, _, 0) => {
+                    self.free_buffers.push((block, cdata));
+                    break;
+                }
+                (clen, crc32, ulen) => (clen, crc32, ulen),
+            };
+
+            let cpos = self.position;
+            self.position += clen as u64;
+
+            self.tasks.push(self.worker_pool.spawn(move || {
+                block.set_cpos(cpos);
+                block.set_clen(clen as u64);
+                block.set_upos(0);
+                block.set_ulen(ulen);
+                inflate_data(&cdata, block.buffer_mut())?;
+
+                if validate_checksums {
+                    validate_checksum(block.buffer(), crc32)?;
+                }
+
+                Ok((block, cdata))
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn reset(&mut self, position: u64) {
+        self.tasks.clear();
+        self.position = position;
+    }
 }
 
 impl<R> Reader<R>
@@ -48,12 +159,21 @@ where
     /// let reader = bgzf::Reader::new(&data[..]);
     /// ```
     pub fn new(inner: R) -> Self {
-        Self {
-            inner,
-            position: 0,
-            cdata: Vec::new(),
-            block: Block::default(),
-        }
+        Self::builder(inner).build()
+    }
+
+    /// Creates a BGZF reader builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let builder = bgzf::Reader::builder(&data[..]);
+    /// let reader = builder.build();
+    /// ```
+    pub fn builder(inner: R) -> Builder<R> {
+        Builder::new(inner)
     }
 
     /// Returns a reference to the underlying reader.
@@ -114,23 +234,207 @@ where
 
     /// Returns the current virtual position of the stream.
     ///
+    /// The virtual position is valid immediately after any read, e.g., to record the end of a
+    /// record for an index. When the current block is fully consumed, this is the start of the
+    /// next block (with an uncompressed offset of 0) rather than the end of the current block.
+    ///
     /// # Examples
     ///
     /// ```
+    /// # use std::io::{self, Read, Write};
     /// use noodles_bgzf as bgzf;
+    ///
     /// let data = [];
     /// let reader = bgzf::Reader::new(&data[..]);
     /// assert_eq!(reader.virtual_position(), bgzf::VirtualPosition::from(0));
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush()?;
+    /// writer.write_all(b"bgzf")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = bgzf::Reader::new(&data[..]);
+    /// let mut buf = [0; 7];
+    /// reader.read_exact(&mut buf)?;
+    /// assert_eq!(reader.virtual_position(), bgzf::VirtualPosition::new(35, 0).unwrap());
+    /// # Ok::<_, io::Error>(())
     /// ```
     pub fn virtual_position(&self) -> VirtualPosition {
         self.block.virtual_position()
     }
+
+    /// Reads the uncompressed data of a block into a caller-owned buffer.
+    ///
+    /// This reads the remaining data of the current block or, if it is consumed, the next
+    /// nonempty block. The buffer is resized to the length of the data read, reusing its
+    /// allocation; once it has grown to the maximum block size (64 KiB), reading does not allocate.
+    /// Unless blocks are read ahead using a worker pool, the next block is decompressed directly
+    /// into the buffer.
+    ///
+    /// This returns the number of bytes read. A value of 0 means the stream reached EOF.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush()?;
+    /// writer.write_all(b"bgzf")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = bgzf::Reader::new(&data[..]);
+    /// let mut buf = Vec::new();
+    ///
+    /// assert_eq!(reader.read_block_into(&mut buf)?, 7);
+    /// assert_eq!(buf, b"noodles");
+    ///
+    /// assert_eq!(reader.read_block_into(&mut buf)?, 4);
+    /// assert_eq!(buf, b"bgzf");
+    ///
+    /// assert_eq!(reader.read_block_into(&mut buf)?, 0);
+    /// assert!(buf.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_block_into(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        use super::block::MAX_UNCOMPRESSED_DATA_LENGTH;
+
+        // A buffer of at least the maximum block size always receives the whole remaining data
+        // of a block in a single read, either copied or decompressed directly into it.
+        if buf.len() < MAX_UNCOMPRESSED_DATA_LENGTH {
+            buf.resize(MAX_UNCOMPRESSED_DATA_LENGTH, 0);
+        }
+
+        match self.read(buf) {
+            Ok(n) => {
+                buf.truncate(n);
+                Ok(n)
+            }
+            Err(e) => {
+                buf.clear();
+                Err(e)
+            }
+        }
+    }
+
+    // Reads the next block into the block buffer.
+    //
+    // This returns `false` if the stream is at EOF.
+    fn read_block(&mut self) -> io::Result<bool> {
+        if let Some(read_ahead) = self.read_ahead.as_mut() {
+            read_ahead.fill(&mut self.inner, self.validate_checksums)?;
+
+            match read_ahead.tasks.pop() {
+                Some(result) => {
+                    let (block, cdata) = result?;
+                    let prev_block = mem::replace(&mut self.block, block);
+                    read_ahead.free_buffers.push((prev_block, cdata));
+
+                    self.position = self.block.cpos() + self.block.clen();
+                    self.notify_block();
+                    Ok(true)
+                }
+                None => {
+                    self.reset_block_at_eof();
+                    Ok(false)
+                }
+            }
+        } else {
+            let block_size = (self.inflate_block)(
+                &mut self.inner,
+                &mut self.cdata,
+                &mut self.block,
+                None,
+                self.validate_checksums,
+            )?;
+
+            if block_size > 0 {
+                self.block.set_cpos(self.position);
+                self.position += block_size as u64;
+                self.notify_block();
+                Ok(true)
+            } else {
+                self.reset_block_at_eof();
+                Ok(false)
+            }
+        }
+    }
+
+    // Empties the current block at the end of the stream so that the virtual position is the
+    // end of the stream.
+    fn reset_block_at_eof(&mut self) {
+        self.block.set_cpos(self.position);
+        self.block.set_clen(0);
+        self.block.set_ulen(0);
+        self.block.set_upos(0);
+    }
+
+    pub(crate) fn gzi_index(&self) -> &gzi::Index {
+        &self.gzi_index
+    }
+
+    fn notify_block(&self) {
+        if let Some(observer) = self.observer.as_ref() {
+            observer.on_block_start(self.block.cpos(), self.block.ulen() as u64);
+            observer.on_block(self.block.clen(), self.block.ulen() as u64);
+        }
+    }
+}
+
+impl Reader<File> {
+    /// Opens a BGZF file.
+    ///
+    /// This is a convenience function and is equivalent to opening the file at the given path and
+    /// using [`Self::try_new`], i.e., the file is checked to be BGZF.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_bgzf as bgzf;
+    /// let reader = bgzf::Reader::from_path("data.gz")?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn from_path<P>(src: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        File::open(src).and_then(Self::try_new)
+    }
 }
 
 impl<R> Reader<R>
 where
     R: Read + Seek,
 {
+    /// Creates a BGZF reader after checking that the stream is BGZF.
+    ///
+    /// The header of the first block is read and validated, and the stream is then sought back
+    /// to its original position. If the stream is not compressed or is gzip-compressed but not
+    /// BGZF, this returns an error that wraps [`Error::NotGzip`] or [`Error::NotBgzf`],
+    /// respectively. An empty stream is valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let data = bgzf::Writer::new(Vec::new()).finish()?;
+    /// assert!(bgzf::Reader::try_new(Cursor::new(data)).is_ok());
+    ///
+    /// let data = b"noodles";
+    /// assert!(bgzf::Reader::try_new(Cursor::new(data)).is_err());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn try_new(mut inner: R) -> io::Result<Self> {
+        check_header(&mut inner)?;
+        Ok(Self::new(inner))
+    }
+
     /// Seeks the stream to the given virtual position.
     ///
     /// The underlying stream's cursor is first moved the the compressed position. A block is read,
@@ -151,14 +455,231 @@ where
 
         self.inner.seek(SeekFrom::Start(cpos))?;
 
-        let block_size = read_block(&mut self.inner, &mut self.cdata, &mut self.block)?;
+        let block_size = (self.inflate_block)(
+            &mut self.inner,
+            &mut self.cdata,
+            &mut self.block,
+            None,
+            self.validate_checksums,
+        )?;
+        self.block.set_cpos(cpos);
         self.position = cpos + (block_size as u64);
 
+        if block_size > 0 {
+            self.notify_block();
+        }
+
+        if let Some(read_ahead) = self.read_ahead.as_mut() {
+            read_ahead.reset(self.position);
+        }
+
+        self.block.set_upos(usize::from(upos));
+
+        Ok(pos)
+    }
+
+    /// Checks whether the underlying stream ends with a BGZF EOF marker.
+    ///
+    /// A stream without an EOF marker is likely truncated. The position of the reader is not
+    /// changed. See [`crate::has_eof_marker`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let data = bgzf::Writer::new(Vec::new()).finish()?;
+    /// let mut reader = bgzf::Reader::new(Cursor::new(data));
+    /// assert!(reader.has_eof_marker()?);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn has_eof_marker(&mut self) -> io::Result<bool> {
+        crate::has_eof_marker(&mut self.inner)
+    }
+
+    /// Seeks the stream to the given uncompressed position.
+    ///
+    /// The gzip index (GZI) is used to find the block that contains the uncompressed position.
+    /// The stream is then seeked to that block, and the block's cursor is moved to the offset of
+    /// the position within the block.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    ///
+    /// let index = gzi::read("reference.fa.gz.gzi")?;
+    /// let mut reader = File::open("reference.fa.gz").map(bgzf::Reader::new)?;
+    /// reader.seek_by_uncompressed_position(&index, 21294)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek_by_uncompressed_position(
+        &mut self,
+        index: &[(u64, u64)],
+        pos: u64,
+    ) -> io::Result<VirtualPosition> {
+        let (cpos, block_upos) = gzi::resolve_uncompressed_position(index, pos);
+
+        let upos = u16::try_from(pos - block_upos)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let virtual_position = VirtualPosition::try_from((cpos, upos))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        self.inner.seek(SeekFrom::Start(cpos))?;
+
+        let block_size = (self.inflate_block)(
+            &mut self.inner,
+            &mut self.cdata,
+            &mut self.block,
+            None,
+            self.validate_checksums,
+        )?;
         self.block.set_cpos(cpos);
+        self.position = cpos + (block_size as u64);
+
+        if block_size > 0 {
+            self.notify_block();
+        }
+
+        if let Some(read_ahead) = self.read_ahead.as_mut() {
+            read_ahead.reset(self.position);
+        }
+
+        if usize::from(upos) > self.block.ulen() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "uncompressed position is out of bounds of the block",
+            ));
+        }
+
         self.block.set_upos(usize::from(upos));
 
+        Ok(virtual_position)
+    }
+
+    // Extends the gzip index by scanning blocks from the last scanned block until the compressed
+    // position `cpos` or uncompressed position `upos` is passed or the stream reaches EOF.
+    //
+    // Empty blocks are not indexed. The position of the underlying stream is restored after
+    // scanning.
+    fn scan_gzi_index(&mut self, cpos: u64, upos: u64) -> io::Result<()> {
+        use super::raw_block::RawBlocks;
+
+        let (mut block_cpos, mut block_upos) = self.scan_position;
+
+        if self.is_gzi_index_complete || block_cpos > cpos || block_upos > upos {
+            return Ok(());
+        }
+
+        let pos = self.inner.stream_position()?;
+        self.inner.seek(SeekFrom::Start(block_cpos))?;
+
+        let mut is_eof = true;
+
+        for result in RawBlocks::new(&mut self.inner) {
+            let block = result?;
+            let ulen = block.uncompressed_len() as u64;
+
+            let is_indexed = self
+                .gzi_index
+                .last()
+                .map(|&(c, _)| c >= block_cpos)
+                .unwrap_or(false);
+
+            if block_cpos > 0 && ulen > 0 && !is_indexed {
+                self.gzi_index.push((block_cpos, block_upos));
+            }
+
+            block_cpos += block.data().len() as u64;
+            block_upos += ulen;
+
+            if block_cpos > cpos || block_upos > upos {
+                is_eof = false;
+                break;
+            }
+        }
+
+        self.scan_position = (block_cpos, block_upos);
+        self.is_gzi_index_complete = is_eof;
+
+        self.inner.seek(SeekFrom::Start(pos))?;
+
+        Ok(())
+    }
+
+    // Returns the uncompressed position of the stream.
+    fn uncompressed_position(&mut self) -> io::Result<u64> {
+        let cpos = self.block.cpos();
+
+        self.scan_gzi_index(cpos, u64::MAX)?;
+
+        let block_upos = if cpos == 0 {
+            0
+        } else {
+            let i = self.gzi_index.partition_point(|&(c, _)| c < cpos);
+
+            match self.gzi_index.get(i) {
+                Some(&(c, u)) if c == cpos => u,
+                // An empty block, e.g., an EOF marker, starts at the uncompressed position of the
+                // next nonempty block.
+                Some(&(_, u)) if self.block.ulen() == 0 => u,
+                None if self.block.ulen() == 0 && self.scan_position.0 >= cpos => {
+                    self.scan_position.1
+                }
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "block not found in gzip index",
+                    ))
+                }
+            }
+        };
+
+        Ok(block_upos + self.block.upos() as u64)
+    }
+}
+
+impl<R> Seek for Reader<R>
+where
+    R: Read + Seek,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        fn offset(base: u64, n: i64) -> io::Result<u64> {
+            i64::try_from(base)
+                .ok()
+                .and_then(|m| m.checked_add(n))
+                .and_then(|m| u64::try_from(m).ok())
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid seek position"))
+        }
+
+        let pos = match pos {
+            SeekFrom::Start(n) => n,
+            SeekFrom::End(n) => {
+                self.scan_gzi_index(u64::MAX, u64::MAX)?;
+                offset(self.scan_position.1, n)?
+            }
+            SeekFrom::Current(n) => {
+                let position = self.uncompressed_position()?;
+                offset(position, n)?
+            }
+        };
+
+        self.scan_gzi_index(u64::MAX, pos)?;
+
+        let index = mem::take(&mut self.gzi_index);
+        let result = self.seek_by_uncompressed_position(&index, pos);
+        self.gzi_index = index;
+        result?;
+
         Ok(pos)
     }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        self.uncompressed_position()
+    }
 }
 
 impl<R> Read for Reader<R>
@@ -170,13 +691,34 @@ where
 
         // If a new block is about to be read and the given buffer is guaranteed to be larger than
         // next block, reading to the block buffer can be skipped. The uncompressed data is read
-        // directly to the given buffer to avoid double copying.
-        if self.block.is_eof() && buf.len() >= block::MAX_UNCOMPRESSED_DATA_LENGTH {
-            let block_size =
-                read_block_into(&mut self.inner, &mut self.cdata, &mut self.block, buf)?;
-            self.block.set_cpos(self.position);
-            self.position += block_size as u64;
-            return Ok(self.block.ulen());
+        // directly to the given buffer to avoid double copying. Empty blocks are skipped so that 0
+        // is only returned at the end of the stream.
+        if self.read_ahead.is_none()
+            && self.block.is_eof()
+            && buf.len() >= block::MAX_UNCOMPRESSED_DATA_LENGTH
+        {
+            loop {
+                let block_size = (self.inflate_block)(
+                    &mut self.inner,
+                    &mut self.cdata,
+                    &mut self.block,
+                    Some(buf),
+                    self.validate_checksums,
+                )?;
+
+                if block_size == 0 {
+                    self.reset_block_at_eof();
+                    return Ok(0);
+                }
+
+                self.block.set_cpos(self.position);
+                self.position += block_size as u64;
+                self.notify_block();
+
+                if self.block.ulen() > 0 {
+                    return Ok(self.block.ulen());
+                }
+            }
         }
 
         let bytes_read = {
@@ -213,10 +755,12 @@ where
     }
 
     fn fill_buf(&mut self) -> io::Result<&[u8]> {
-        if self.block.is_eof() {
-            let block_size = read_block(&mut self.inner, &mut self.cdata, &mut self.block)?;
-            self.block.set_cpos(self.position);
-            self.position += block_size as u64;
+        // Empty blocks, e.g., the EOF markers of concatenated streams, are skipped so that an
+        // empty buffer is only returned at the end of the stream.
+        while self.block.is_eof() {
+            if !self.read_block()? {
+                break;
+            }
         }
 
         Ok(self.block.buffer())
@@ -227,9 +771,9 @@ where
 ///
 /// The position of the stream is expected to be at the start of a block.
 ///
-/// If successful, the block size (`BSIZE` + 1) is returned. If a block size of 0 is returned, the
-/// stream reached EOF.
-fn read_header<R>(reader: &mut R) -> io::Result<u32>
+/// If successful, the block size (`BSIZE` + 1) and header size are returned. If a block size of 0
+/// is returned, the stream reached EOF. Additional extra subfields are skipped.
+fn read_header<R>(reader: &mut R) -> io::Result<(u32, usize)>
 where
     R: Read,
 {
@@ -237,14 +781,86 @@ where
 
     match reader.read_exact(&mut header) {
         Ok(_) => {}
-        Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(0),
+        Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok((0, 0)),
         Err(e) => return Err(e),
     }
 
+    validate_header(&header)?;
+
+    let header_size = header_size(&header);
+    let extra_len = (header_size - BGZF_HEADER_SIZE) as u64;
+
+    if io::copy(&mut reader.take(extra_len), &mut io::sink())? < extra_len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+
     let bsize = LittleEndian::read_u16(&header[16..]);
 
     // Add 1 because BSIZE is "total Block SIZE minus 1".
-    Ok(u32::from(bsize) + 1)
+    Ok((u32::from(bsize) + 1, header_size))
+}
+
+// Reads and validates the header of the next block and seeks back to the original position.
+fn check_header<R>(reader: &mut R) -> io::Result<()>
+where
+    R: Read + Seek,
+{
+    let pos = reader.stream_position()?;
+
+    let mut header = Vec::with_capacity(BGZF_HEADER_SIZE);
+    let result = reader
+        .by_ref()
+        .take(BGZF_HEADER_SIZE as u64)
+        .read_to_end(&mut header);
+
+    reader.seek(SeekFrom::Start(pos))?;
+    result?;
+
+    match header.len() {
+        0 => Ok(()),
+        BGZF_HEADER_SIZE => validate_header(&header),
+        n => {
+            let len = cmp::min(n, gz::MAGIC_NUMBER.len());
+
+            if header[..len] == gz::MAGIC_NUMBER[..len] {
+                Err(io::Error::from(io::ErrorKind::UnexpectedEof))
+            } else {
+                Err(Error::NotGzip.into())
+            }
+        }
+    }
+}
+
+/// Validates that a block header is a gzip header with the BGZF extra field.
+///
+/// The header is expected to be at least [`BGZF_HEADER_SIZE`] bytes.
+pub(crate) fn validate_header(header: &[u8]) -> io::Result<()> {
+    const FEXTRA: u8 = 0x04;
+
+    if header[..2] != gz::MAGIC_NUMBER {
+        return Err(Error::NotGzip.into());
+    }
+
+    let is_bgzf = header[2] == gz::CompressionMethod::Deflate as u8
+        && header[3] & FEXTRA != 0
+        && usize::from(LittleEndian::read_u16(&header[10..])) >= BGZF_XLEN
+        // SI1, SI2, SLEN
+        && header[12..16] == [b'B', b'C', 0x02, 0x00];
+
+    if is_bgzf {
+        Ok(())
+    } else {
+        Err(Error::NotBgzf.into())
+    }
+}
+
+/// Returns the size of a block header.
+///
+/// This includes the extra field, which can hold subfields after the BGZF subfield. The header
+/// is expected to be validated.
+pub(crate) fn header_size(header: &[u8]) -> usize {
+    let xlen = usize::from(LittleEndian::read_u16(&header[10..]));
+    gz::HEADER_SIZE + GZIP_XLEN_SIZE + xlen
 }
 
 /// Reads a BGZF block trailer.
@@ -252,110 +868,250 @@ where
 /// The position of the stream is expected to be at the start of the block trailer, i.e., 8 bytes
 /// from the end of the block.
 ///
-/// This returns the length of the uncompressed data (`ISIZE`).
-fn read_trailer<R>(reader: &mut R) -> io::Result<u32>
+/// This returns the checksum (`CRC32`) and length (`ISIZE`) of the uncompressed data.
+fn read_trailer<R>(reader: &mut R) -> io::Result<(u32, u32)>
 where
     R: Read,
 {
     let mut trailer = [0; gz::TRAILER_SIZE];
     reader.read_exact(&mut trailer)?;
+    let crc32 = LittleEndian::read_u32(&trailer[..4]);
     let r#isize = LittleEndian::read_u32(&trailer[4..]);
-    Ok(r#isize)
+    Ok((crc32, r#isize))
 }
 
+// Decompressors are reused per thread to avoid allocating decoder state for every block.
 #[cfg(feature = "libdeflate")]
-pub(crate) fn inflate_data(reader: &[u8], writer: &mut [u8]) -> io::Result<()> {
-    use libdeflater::Decompressor;
+thread_local! {
+    static DECOMPRESSOR: RefCell<libdeflater::Decompressor> =
+        RefCell::new(libdeflater::Decompressor::new());
+}
 
-    let mut decoder = Decompressor::new();
+#[cfg(not(feature = "libdeflate"))]
+thread_local! {
+    static DECOMPRESSOR: RefCell<flate2::Decompress> = RefCell::new(flate2::Decompress::new(false));
+}
 
-    decoder
-        .deflate_decompress(reader, writer)
-        .map(|_| ())
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+#[cfg(feature = "libdeflate")]
+pub(crate) fn inflate_data(reader: &[u8], writer: &mut [u8]) -> io::Result<()> {
+    DECOMPRESSOR.with(|decoder| {
+        let n = decoder
+            .borrow_mut()
+            .deflate_decompress(reader, writer)
+            .map_err(|e| Error::InvalidBlockData(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+
+        if n == writer.len() {
+            Ok(())
+        } else {
+            Err(invalid_uncompressed_data_length().into())
+        }
+    })
 }
 
 #[cfg(not(feature = "libdeflate"))]
 pub(crate) fn inflate_data(reader: &[u8], writer: &mut [u8]) -> io::Result<()> {
-    use flate2::bufread::DeflateDecoder;
+    use flate2::{FlushDecompress, Status};
+
+    DECOMPRESSOR.with(|decoder| {
+        let mut decoder = decoder.borrow_mut();
+        decoder.reset(false);
+
+        let status = decoder
+            .decompress(reader, writer, FlushDecompress::Finish)
+            .map_err(|e| Error::InvalidBlockData(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+
+        let is_output_full = decoder.total_out() == writer.len() as u64;
+
+        match status {
+            Status::StreamEnd if is_output_full => Ok(()),
+            // The uncompressed data is shorter or longer than `ISIZE`.
+            Status::StreamEnd => Err(invalid_uncompressed_data_length().into()),
+            _ if is_output_full => Err(invalid_uncompressed_data_length().into()),
+            _ => Err(Error::InvalidBlockData(io::Error::from(io::ErrorKind::UnexpectedEof)).into()),
+        }
+    })
+}
 
-    let mut decoder = DeflateDecoder::new(reader);
-    decoder.read_exact(writer)
+fn invalid_uncompressed_data_length() -> Error {
+    Error::InvalidBlockData(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "uncompressed data length does not match ISIZE",
+    ))
 }
 
-fn read_compressed_block<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<(usize, usize)>
+pub(crate) fn validate_checksum(data: &[u8], expected: u32) -> io::Result<()> {
+    use flate2::Crc;
+
+    let mut crc = Crc::new();
+    crc.update(data);
+    let actual = crc.sum();
+
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(Error::InvalidBlockChecksum { expected, actual }.into())
+    }
+}
+
+/// Reads a block without decompressing its data.
+///
+/// This returns the block size, the checksum of the uncompressed data (`CRC32`), and the length of
+/// the uncompressed data (`ISIZE`).
+fn read_compressed_block<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<(usize, u32, usize)>
 where
     R: Read,
 {
-    let clen = match read_header(reader) {
-        Ok(0) => return Ok((0, 0)),
-        Ok(bs) => bs as usize,
+    let (clen, header_size) = match read_header(reader) {
+        Ok((0, _)) => return Ok((0, 0, 0)),
+        Ok((bs, header_size)) => (bs as usize, header_size),
         Err(e) => return Err(e),
     };
 
-    if clen < BGZF_HEADER_SIZE + gz::TRAILER_SIZE {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!(
-                "expected clen >= {}, got {}",
-                BGZF_HEADER_SIZE + gz::TRAILER_SIZE,
-                clen
-            ),
-        ));
+    if clen < header_size + gz::TRAILER_SIZE {
+        return Err(Error::InvalidBlockSize(clen).into());
     }
 
-    let cdata_len = clen - BGZF_HEADER_SIZE - gz::TRAILER_SIZE;
+    let cdata_len = clen - header_size - gz::TRAILER_SIZE;
     buf.resize(cdata_len, Default::default());
     reader.read_exact(buf)?;
 
-    let ulen = read_trailer(reader).and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
-    })?;
+    let (crc32, r#isize) = read_trailer(reader)?;
+    let ulen =
+        usize::try_from(r#isize).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    validate_uncompressed_size(ulen)?;
+
+    Ok((clen, crc32, ulen))
+}
+
+pub(crate) fn validate_uncompressed_size(ulen: usize) -> io::Result<()> {
+    use super::block::MAX_UNCOMPRESSED_DATA_LENGTH;
 
-    Ok((clen, ulen))
+    if ulen > MAX_UNCOMPRESSED_DATA_LENGTH {
+        Err(Error::InvalidBlockUncompressedSize(ulen).into())
+    } else {
+        Ok(())
+    }
 }
 
-fn read_block<R>(reader: &mut R, cdata: &mut Vec<u8>, block: &mut Block) -> io::Result<usize>
+/// A function that reads and decompresses the next block.
+///
+/// If a destination buffer is given, the uncompressed data is written to it, and the block is
+/// marked as consumed. Otherwise, it is written to the block's buffer. If checksums are validated,
+/// the uncompressed data must match the checksum (`CRC32`) in the block trailer.
+///
+/// This returns the size of the compressed block. A size of 0 means the stream reached EOF.
+type InflateBlockFn<R> =
+    fn(&mut R, &mut Vec<u8>, &mut Block, Option<&mut [u8]>, bool) -> io::Result<usize>;
+
+fn inflate_block<R>(
+    reader: &mut R,
+    cdata: &mut Vec<u8>,
+    block: &mut Block,
+    dst: Option<&mut [u8]>,
+    validate_checksums: bool,
+) -> io::Result<usize>
 where
     R: Read,
 {
-    let (clen, ulen) = match read_compressed_block(reader, cdata) {
-        Ok((0, 0)) => return Ok(0),
-        Ok((clen, ulen)) => (clen, ulen),
+    let (clen, crc32, ulen) = match read_compressed_block(reader, cdata) {
+        Ok((0, _, 0)) => return Ok(0),
+        Ok((clen, crc32, ulen)) => (clen, crc32, ulen),
         Err(e) => return Err(e),
     };
 
-    block.set_clen(clen as u64);
-    block.set_upos(0);
-    block.set_ulen(ulen);
-
-    inflate_data(cdata, block.buffer_mut())?;
+    let crc32 = if validate_checksums {
+        Some(crc32)
+    } else {
+        None
+    };
+    inflate_block_data(cdata, clen, crc32, ulen, block, dst)?;
 
     Ok(clen)
 }
 
-fn read_block_into<R>(
+/// Reads and decompresses the next block directly from the underlying buffer.
+///
+/// This avoids copying the compressed data when the entire block is available in the buffer,
+/// e.g., when reading from a memory-mapped file. Otherwise, this falls back to
+/// [`inflate_block`].
+#[cfg(feature = "mmap")]
+pub(crate) fn inflate_buffered_block<R>(
     reader: &mut R,
     cdata: &mut Vec<u8>,
     block: &mut Block,
-    buf: &mut [u8],
+    dst: Option<&mut [u8]>,
+    validate_checksums: bool,
 ) -> io::Result<usize>
 where
-    R: Read,
+    R: BufRead,
 {
-    let (clen, ulen) = match read_compressed_block(reader, cdata) {
-        Ok((0, 0)) => return Ok(0),
-        Ok((clen, ulen)) => (clen, ulen),
-        Err(e) => return Err(e),
+    let src = reader.fill_buf()?;
+
+    if src.len() < BGZF_HEADER_SIZE {
+        return inflate_block(reader, cdata, block, dst, validate_checksums);
+    }
+
+    validate_header(src)?;
+
+    let header_size = header_size(src);
+    let clen = usize::from(LittleEndian::read_u16(&src[16..])) + 1;
+
+    if clen < header_size + gz::TRAILER_SIZE {
+        return Err(Error::InvalidBlockSize(clen).into());
+    } else if src.len() < clen {
+        return inflate_block(reader, cdata, block, dst, validate_checksums);
+    }
+
+    let trailer = &src[clen - gz::TRAILER_SIZE..clen];
+    let crc32 = LittleEndian::read_u32(&trailer[..4]);
+    let ulen = usize::try_from(LittleEndian::read_u32(&trailer[4..]))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    validate_uncompressed_size(ulen)?;
+
+    let src_cdata = &src[header_size..clen - gz::TRAILER_SIZE];
+    let crc32 = if validate_checksums {
+        Some(crc32)
+    } else {
+        None
     };
+    inflate_block_data(src_cdata, clen, crc32, ulen, block, dst)?;
 
+    reader.consume(clen);
+
+    Ok(clen)
+}
+
+fn inflate_block_data(
+    cdata: &[u8],
+    clen: usize,
+    crc32: Option<u32>,
+    ulen: usize,
+    block: &mut Block,
+    dst: Option<&mut [u8]>,
+) -> io::Result<()> {
     block.set_clen(clen as u64);
-    block.set_upos(ulen);
     block.set_ulen(ulen);
 
-    inflate_data(cdata, &mut buf[..ulen])?;
+    let data = match dst {
+        Some(buf) => {
+            block.set_upos(ulen);
+            let data = &mut buf[..ulen];
+            inflate_data(cdata, data)?;
+            data
+        }
+        None => {
+            block.set_upos(0);
+            inflate_data(cdata, block.buffer_mut())?;
+            block.buffer()
+        }
+    };
 
-    Ok(clen)
+    match crc32 {
+        Some(expected) => validate_checksum(data, expected),
+        None => Ok(()),
+    }
 }
 
 /// This is effectively the same as `std::io::default_read_exact`.
@@ -423,18 +1179,462 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_seek_by_uncompressed_position_with_io_seek() -> io::Result<()> {
+        use std::{io::Write, num::NonZeroUsize};
+
+        use crate::Writer;
+
+        fn seek_and_read<R>(reader: &mut Reader<R>) -> io::Result<()>
+        where
+            R: Read + Seek,
+        {
+            let mut buf = [0; 4];
+
+            assert_eq!(reader.stream_position()?, 0);
+
+            assert_eq!(Seek::seek(reader, SeekFrom::Start(8))?, 8);
+            reader.read_exact(&mut buf)?;
+            assert_eq!(&buf, b"bgzf");
+            assert_eq!(reader.stream_position()?, 12);
+
+            assert_eq!(Seek::seek(reader, SeekFrom::Current(-11))?, 1);
+            reader.read_exact(&mut buf)?;
+            assert_eq!(&buf, b"oodl");
+
+            assert_eq!(Seek::seek(reader, SeekFrom::End(-5))?, 7);
+            reader.read_exact(&mut buf)?;
+            assert_eq!(&buf, b"-bgz");
+
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf)?;
+            assert_eq!(buf, b"f");
+            assert_eq!(reader.stream_position()?, 12);
+
+            assert_eq!(Seek::seek(reader, SeekFrom::End(0))?, 12);
+            assert!(Seek::seek(reader, SeekFrom::Current(-13)).is_err());
+
+            Ok(())
+        }
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_empty_block()?;
+        writer.write_all(b"-")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        seek_and_read(&mut reader)?;
+        // The empty block at 35 and the EOF marker are not indexed.
+        assert_eq!(reader.gzi_index(), &[(63, 7), (92, 8)]);
+
+        let mut reader = Reader::builder(Cursor::new(&data))
+            .set_gzi_index(vec![(63, 7)])
+            .build();
+        seek_and_read(&mut reader)?;
+        assert_eq!(reader.gzi_index(), &[(63, 7), (92, 8)]);
+
+        let mut reader = Reader::builder(Cursor::new(&data))
+            .set_worker_count(NonZeroUsize::new(2).unwrap())
+            .build();
+        seek_and_read(&mut reader)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_block_into() -> io::Result<()> {
+        use std::{io::Write, num::NonZeroUsize};
+
+        use crate::Writer;
+
+        fn read_blocks<R>(reader: &mut Reader<R>) -> io::Result<Vec<Vec<u8>>>
+        where
+            R: Read,
+        {
+            let mut buf = Vec::new();
+            let mut blocks = Vec::new();
+
+            reader.read_block_into(&mut buf)?;
+            let ptr = buf.as_ptr();
+
+            while !buf.is_empty() {
+                blocks.push(buf.clone());
+                reader.read_block_into(&mut buf)?;
+                assert_eq!(buf.as_ptr(), ptr);
+            }
+
+            Ok(blocks)
+        }
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_empty_block()?;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let expected = [b"noodles".to_vec(), b"bgzf".to_vec()];
+
+        let mut reader = Reader::new(&data[..]);
+        assert_eq!(read_blocks(&mut reader)?, expected);
+
+        let mut reader = Reader::builder(&data[..])
+            .set_worker_count(NonZeroUsize::new(2).unwrap())
+            .build();
+        assert_eq!(read_blocks(&mut reader)?, expected);
+
+        let mut reader = Reader::new(&data[..]);
+        let mut buf = [0; 3];
+        reader.read_exact(&mut buf)?;
+        let mut buf = Vec::new();
+        assert_eq!(reader.read_block_into(&mut buf)?, 4);
+        assert_eq!(buf, b"dles");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_worker_pool() -> io::Result<()> {
+        use std::{io::Write, num::NonZeroUsize};
+
+        use crate::Writer;
+
+        let mut writer = Writer::new(Vec::new());
+
+        for i in 0..4096 {
+            writeln!(writer, "noodles-{}", i)?;
+            writer.flush()?;
+        }
+
+        let data = writer.finish()?;
+
+        fn read_lines<R>(reader: &mut Reader<R>) -> io::Result<Vec<(String, VirtualPosition)>>
+        where
+            R: Read,
+        {
+            let mut lines = Vec::new();
+            let mut buf = String::new();
+
+            while reader.read_line(&mut buf)? != 0 {
+                lines.push((buf.clone(), reader.virtual_position()));
+                buf.clear();
+            }
+
+            Ok(lines)
+        }
+
+        let expected = read_lines(&mut Reader::new(&data[..]))?;
+
+        let worker_pool = WorkerPool::new(NonZeroUsize::new(2).unwrap());
+        let mut reader = Reader::builder(Cursor::new(&data))
+            .set_worker_pool(worker_pool)
+            .build();
+
+        assert_eq!(read_lines(&mut reader)?, expected);
+
+        let virtual_position = expected[2047].1;
+        reader.seek(virtual_position)?;
+
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        assert_eq!(line, "noodles-2048\n");
+
+        let mut reader = Reader::builder(&data[..])
+            .set_worker_count(NonZeroUsize::new(2).unwrap())
+            .build();
+
+        assert_eq!(read_lines(&mut reader)?, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_observer() -> io::Result<()> {
+        use std::io::Write;
+
+        use crate::{observer::Counter, writer::Writer};
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        let data = writer.finish()?;
+
+        let counter = Arc::new(Counter::default());
+        let mut reader = Reader::builder(&data[..])
+            .set_observer(counter.clone())
+            .build();
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+
+        assert_eq!(counter.block_count(), 2);
+        assert_eq!(counter.compressed_bytes(), data.len() as u64);
+        assert_eq!(counter.uncompressed_bytes(), 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_invalid_trailer() -> io::Result<()> {
+        use std::{io::Write, num::NonZeroUsize};
+
+        use crate::writer::Writer;
+
+        fn is_invalid_block_checksum(e: &io::Error) -> bool {
+            matches!(
+                e.get_ref().and_then(|e| e.downcast_ref()),
+                Some(Error::InvalidBlockChecksum { .. })
+            )
+        }
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        let data = writer.finish()?;
+
+        // CRC32
+        let mut invalid_data = data.clone();
+        invalid_data[27] ^= 0xff;
+
+        let mut reader = Reader::new(&invalid_data[..]);
+        let mut buf = Vec::new();
+        assert!(matches!(
+            reader.read_to_end(&mut buf),
+            Err(e) if is_invalid_block_checksum(&e)
+        ));
+
+        let mut reader = Reader::builder(&invalid_data[..])
+            .set_worker_count(NonZeroUsize::new(2).unwrap())
+            .build();
+        let mut buf = Vec::new();
+        assert!(matches!(
+            reader.read_to_end(&mut buf),
+            Err(e) if is_invalid_block_checksum(&e)
+        ));
+
+        let mut reader = Reader::builder(&invalid_data[..])
+            .set_validate_checksums(false)
+            .build();
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodles");
+
+        // ISIZE
+        let mut invalid_data = data;
+        invalid_data[31] = 0x06;
+
+        let mut reader = Reader::builder(&invalid_data[..])
+            .set_validate_checksums(false)
+            .build();
+        let mut buf = Vec::new();
+        assert!(matches!(
+            reader.read_to_end(&mut buf),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inflate_data() -> io::Result<()> {
+        // DEFLATE stream of "noodles"
+        let src = [0xcb, 0xcb, 0xcf, 0x4f, 0xc9, 0x49, 0x2d, 0x06, 0x00];
+
+        let mut dst = [0; 7];
+        inflate_data(&src, &mut dst)?;
+        assert_eq!(&dst, b"noodles");
+
+        let mut dst = [0; 7];
+        assert!(matches!(
+            inflate_data(&src[..4], &mut dst),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let mut dst = [0; 6];
+        assert!(matches!(
+            inflate_data(&src, &mut dst),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let mut dst = [0; 8];
+        assert!(matches!(
+            inflate_data(&src, &mut dst),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        // The decoder state is reset after an error.
+        let mut dst = [0; 7];
+        inflate_data(&src, &mut dst)?;
+        assert_eq!(&dst, b"noodles");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_non_bgzf_data() -> io::Result<()> {
+        use std::io::Write;
+
+        use flate2::{write::GzEncoder, Compression};
+
+        fn read_error(data: &[u8]) -> Option<Error> {
+            let mut reader = Reader::new(data);
+            let mut buf = Vec::new();
+
+            reader
+                .read_to_end(&mut buf)
+                .err()
+                .and_then(|e| e.into_inner())
+                .and_then(|e| e.downcast().ok())
+                .map(|e| *e)
+        }
+
+        let data = b"##fileformat=VCFv4.3\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n";
+        assert!(matches!(read_error(data), Some(Error::NotGzip)));
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data)?;
+        let gz_data = encoder.finish()?;
+        assert!(matches!(read_error(&gz_data), Some(Error::NotBgzf)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_new() -> io::Result<()> {
+        use std::io::Write;
+
+        use flate2::{write::GzEncoder, Compression};
+
+        use crate::Writer;
+
+        fn try_new_error(data: &[u8]) -> Option<Error> {
+            Reader::try_new(Cursor::new(data))
+                .err()
+                .and_then(|e| e.into_inner())
+                .and_then(|e| e.downcast().ok())
+                .map(|e| *e)
+        }
+
+        let data = Writer::new(Vec::new()).finish()?;
+        let reader = Reader::try_new(Cursor::new(&data))?;
+        assert_eq!(reader.get_ref().position(), 0);
+
+        assert!(Reader::try_new(Cursor::new(Vec::new())).is_ok());
+
+        assert!(matches!(try_new_error(b"noodles"), Some(Error::NotGzip)));
+        assert!(try_new_error(b"\x1f").is_none());
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"noodles")?;
+        let data = encoder.finish()?;
+        assert!(matches!(try_new_error(&data), Some(Error::NotBgzf)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_empty_blocks() -> io::Result<()> {
+        use std::num::NonZeroUsize;
+
+        use crate::{block::MAX_UNCOMPRESSED_DATA_LENGTH, writer::Writer};
+
+        fn build_stream() -> io::Result<Vec<u8>> {
+            use std::io::Write;
+
+            let mut data = Vec::new();
+
+            for s in [&b"noodles"[..], b"bgzf"] {
+                let mut writer = Writer::new(Vec::new());
+                writer.write_all(s)?;
+                data.extend(writer.finish()?);
+            }
+
+            Ok(data)
+        }
+
+        let data = build_stream()?;
+
+        let mut reader = Reader::new(&data[..]);
+        let mut buf = [0; 16];
+        assert_eq!(reader.read(&mut buf)?, 7);
+        assert_eq!(reader.read(&mut buf)?, 4);
+        assert_eq!(reader.read(&mut buf)?, 0);
+
+        let mut reader = Reader::new(&data[..]);
+        let mut buf = vec![0; MAX_UNCOMPRESSED_DATA_LENGTH];
+        assert_eq!(reader.read(&mut buf)?, 7);
+        assert_eq!(reader.read(&mut buf)?, 4);
+        assert_eq!(reader.read(&mut buf)?, 0);
+
+        let mut reader = Reader::builder(&data[..])
+            .set_worker_count(NonZeroUsize::new(2).unwrap())
+            .build();
+        let mut buf = [0; 16];
+        assert_eq!(reader.read(&mut buf)?, 7);
+        assert_eq!(reader.read(&mut buf)?, 4);
+        assert_eq!(reader.read(&mut buf)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_observer_block_starts() -> io::Result<()> {
+        use std::{io::Write, sync::Mutex};
+
+        use crate::{observer::Observer, writer::Writer};
+
+        #[derive(Default)]
+        struct BlockStarts(Mutex<Vec<(u64, u64)>>);
+
+        impl Observer for BlockStarts {
+            fn on_block_start(&self, position: u64, uncompressed_len: u64) {
+                self.0.lock().unwrap().push((position, uncompressed_len));
+            }
+        }
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let block_starts = Arc::new(BlockStarts::default());
+        let mut reader = Reader::builder(Cursor::new(&data))
+            .set_observer(block_starts.clone())
+            .build();
+
+        let mut buf = [0; 7];
+        reader.read_exact(&mut buf)?;
+        assert_eq!(reader.virtual_position(), VirtualPosition::from(35 << 16));
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+
+        assert_eq!(*block_starts.0.lock().unwrap(), [(0, 7), (35, 4), (67, 0)]);
+
+        block_starts.0.lock().unwrap().clear();
+        reader.seek(VirtualPosition::from(35 << 16))?;
+        assert_eq!(*block_starts.0.lock().unwrap(), [(35, 4)]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_header() -> io::Result<()> {
         let mut reader = BGZF_EOF;
-        let block_size = read_header(&mut reader)?;
+        let (block_size, header_size) = read_header(&mut reader)?;
         assert_eq!(block_size, BGZF_EOF.len() as u32);
+        assert_eq!(header_size, BGZF_HEADER_SIZE);
         Ok(())
     }
 
     #[test]
     fn test_read_trailer() -> io::Result<()> {
         let (_, mut reader) = BGZF_EOF.split_at(BGZF_EOF.len() - gz::TRAILER_SIZE);
-        let r#isize = read_trailer(&mut reader)?;
+        let (crc32, r#isize) = read_trailer(&mut reader)?;
+        assert_eq!(crc32, 0);
         assert_eq!(r#isize, 0);
         Ok(())
     }
@@ -452,7 +1652,7 @@ mod tests {
         let mut cdata = Vec::new();
         let mut block = Block::default();
 
-        let block_size = read_block(&mut reader, &mut cdata, &mut block)?;
+        let block_size = inflate_block(&mut reader, &mut cdata, &mut block, None, true)?;
         assert_eq!(block_size, BGZF_EOF.len());
 
         Ok(())
@@ -472,6 +1672,35 @@ mod tests {
         let mut cdata = Vec::new();
         let mut block = Block::default();
 
-        assert!(read_block(&mut reader, &mut cdata, &mut block).is_err());
+        assert!(matches!(
+            inflate_block(&mut reader, &mut cdata, &mut block, None, true),
+            Err(ref e) if matches!(
+                e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+                Some(Error::InvalidBlockSize(1))
+            )
+        ));
+    }
+
+    #[test]
+    fn test_read_block_with_invalid_uncompressed_size() {
+        let data = {
+            let mut eof = BGZF_EOF.to_vec();
+            // ISIZE = 65537
+            let i = eof.len() - 4;
+            eof[i..].copy_from_slice(&[0x01, 0x00, 0x01, 0x00]);
+            eof
+        };
+
+        let mut reader = &data[..];
+        let mut cdata = Vec::new();
+        let mut block = Block::default();
+
+        assert!(matches!(
+            inflate_block(&mut reader, &mut cdata, &mut block, None, true),
+            Err(ref e) if matches!(
+                e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+                Some(Error::InvalidBlockUncompressedSize(65537))
+            )
+        ));
     }
 }
@@ -0,0 +1,160 @@
+use std::{io::Read, num::NonZeroUsize, sync::Arc};
+
+use super::{inflate_block, InflateBlockFn, ReadAhead, Reader};
+use crate::{gzi, Block, Observer, WorkerPool};
+
+/// A BGZF reader builder.
+pub struct Builder<R> {
+    inner: R,
+    worker_pool: Option<WorkerPool>,
+    observer: Option<Arc<dyn Observer>>,
+    inflate_block: InflateBlockFn<R>,
+    validate_checksums: bool,
+    gzi_index: Option<gzi::Index>,
+}
+
+impl<R> Builder<R>
+where
+    R: Read,
+{
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            worker_pool: None,
+            observer: None,
+            inflate_block,
+            validate_checksums: true,
+            gzi_index: None,
+        }
+    }
+
+    #[cfg(feature = "mmap")]
+    pub(crate) fn set_inflate_block(mut self, inflate_block: InflateBlockFn<R>) -> Self {
+        self.inflate_block = inflate_block;
+        self
+    }
+
+    /// Sets a worker pool.
+    ///
+    /// When set, blocks are read ahead and decompressed in parallel using the worker pool. By
+    /// default, blocks are decompressed on the calling thread as they are needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(4).unwrap());
+    ///
+    /// let data = [];
+    /// let builder = bgzf::Reader::builder(&data[..]).set_worker_pool(worker_pool);
+    /// ```
+    pub fn set_worker_pool(mut self, worker_pool: WorkerPool) -> Self {
+        self.worker_pool = Some(worker_pool);
+        self
+    }
+
+    /// Sets a worker count.
+    ///
+    /// This creates a worker pool with the given number of threads that is used only by this
+    /// reader and reads blocks ahead of the current block to decompress them in parallel on it.
+    /// Use [`Self::set_worker_pool`] to share threads with other readers and writers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let worker_count = NonZeroUsize::new(4).unwrap();
+    ///
+    /// let data = [];
+    /// let builder = bgzf::Reader::builder(&data[..]).set_worker_count(worker_count);
+    /// ```
+    pub fn set_worker_count(self, worker_count: NonZeroUsize) -> Self {
+        self.set_worker_pool(WorkerPool::new(worker_count))
+    }
+
+    /// Sets a progress observer.
+    ///
+    /// The observer is notified after each block is read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use noodles_bgzf::{self as bgzf, observer::Counter};
+    ///
+    /// let data = [];
+    /// let builder = bgzf::Reader::builder(&data[..]).set_observer(Arc::new(Counter::default()));
+    /// ```
+    pub fn set_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Sets whether to validate the checksum (`CRC32`) of each block.
+    ///
+    /// By default, the uncompressed data of each block is checked against the checksum in the
+    /// block trailer, and a mismatch is an error. Disabling this is faster but can return
+    /// corrupted data. The length of the uncompressed data (`ISIZE`) is always validated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let builder = bgzf::Reader::builder(&data[..]).set_validate_checksums(false);
+    /// ```
+    pub fn set_validate_checksums(mut self, validate_checksums: bool) -> Self {
+        self.validate_checksums = validate_checksums;
+        self
+    }
+
+    /// Sets a gzip index (GZI).
+    ///
+    /// The index is used to seek by uncompressed positions (see [`std::io::Seek`]). By default,
+    /// the index is built by scanning blocks as the stream is sought. A given index is assumed to
+    /// be complete up to its last entry, and blocks after it are scanned as needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let builder = bgzf::Reader::builder(&data[..]).set_gzi_index(gzi::Index::default());
+    /// ```
+    pub fn set_gzi_index(mut self, gzi_index: gzi::Index) -> Self {
+        self.gzi_index = Some(gzi_index);
+        self
+    }
+
+    /// Builds a BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::Reader::builder(&data[..]).build();
+    /// ```
+    pub fn build(self) -> Reader<R> {
+        let gzi_index = self.gzi_index.unwrap_or_default();
+        let scan_position = gzi_index.last().copied().unwrap_or_default();
+
+        Reader {
+            inner: self.inner,
+            position: 0,
+            cdata: Vec::new(),
+            block: Block::default(),
+            read_ahead: self.worker_pool.map(ReadAhead::new),
+            observer: self.observer,
+            inflate_block: self.inflate_block,
+            validate_checksums: self.validate_checksums,
+            gzi_index,
+            scan_position,
+            is_gzi_index_complete: false,
+        }
+    }
+}
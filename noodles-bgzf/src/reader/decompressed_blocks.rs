@@ -0,0 +1,245 @@
+use std::io::{self, Read};
+
+use super::ReadAhead;
+use crate::WorkerPool;
+
+/// A decompressed BGZF block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecompressedBlock {
+    position: u64,
+    data: Vec<u8>,
+}
+
+impl DecompressedBlock {
+    /// Returns the position of the block in the compressed stream.
+    ///
+    /// This is the compressed position of the virtual positions in the block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::{io::{self, Write}, num::NonZeroUsize};
+    /// use noodles_bgzf::{self as bgzf, reader::DecompressedBlocks};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(2).unwrap());
+    /// let block = DecompressedBlocks::new(&data[..], worker_pool).next().transpose()?.unwrap();
+    /// assert_eq!(block.position(), 0);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the uncompressed data of the block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::{io::{self, Write}, num::NonZeroUsize};
+    /// use noodles_bgzf::{self as bgzf, reader::DecompressedBlocks};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(2).unwrap());
+    /// let block = DecompressedBlocks::new(&data[..], worker_pool).next().transpose()?.unwrap();
+    /// assert_eq!(block.data(), b"noodles");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the uncompressed data of the block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::{io::{self, Write}, num::NonZeroUsize};
+    /// use noodles_bgzf::{self as bgzf, reader::DecompressedBlocks};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(2).unwrap());
+    /// let block = DecompressedBlocks::new(&data[..], worker_pool).next().transpose()?.unwrap();
+    /// assert_eq!(block.into_data(), b"noodles");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/// An iterator over decompressed BGZF blocks.
+///
+/// This takes ownership of a raw BGZF stream, decompresses blocks in parallel using a worker pool,
+/// and yields the uncompressed data of each block in stream order. Empty blocks, e.g., EOF
+/// markers, are skipped. Block positions are relative to the position of the stream when the
+/// iterator is created, which is expected to be at the start of a block.
+///
+/// Unlike [`super::Reader`], this does not copy the uncompressed data into an internal buffer,
+/// which makes it a building block for decoding records in parallel.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{io::{self, Write}, num::NonZeroUsize};
+/// use noodles_bgzf::{self as bgzf, reader::DecompressedBlocks};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// writer.flush()?;
+/// writer.write_all(b"bgzf")?;
+/// let data = writer.finish()?;
+///
+/// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(2).unwrap());
+///
+/// let blocks = DecompressedBlocks::new(&data[..], worker_pool)
+///     .map(|result| result.map(|block| (block.position(), block.into_data())))
+///     .collect::<io::Result<Vec<_>>>()?;
+///
+/// assert_eq!(blocks, [(0, b"noodles".to_vec()), (35, b"bgzf".to_vec())]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct DecompressedBlocks<R> {
+    inner: R,
+    read_ahead: ReadAhead,
+}
+
+impl<R> DecompressedBlocks<R>
+where
+    R: Read,
+{
+    /// Creates an iterator over decompressed BGZF blocks.
+    ///
+    /// The number of blocks read ahead of the current block is bounded by the size of the worker
+    /// pool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf::{self as bgzf, reader::DecompressedBlocks};
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(2).unwrap());
+    ///
+    /// let data = [];
+    /// let mut blocks = DecompressedBlocks::new(&data[..], worker_pool);
+    /// assert!(blocks.next().is_none());
+    /// ```
+    pub fn new(inner: R, worker_pool: WorkerPool) -> Self {
+        Self {
+            inner,
+            read_ahead: ReadAhead::new(worker_pool),
+        }
+    }
+
+    /// Unwraps and returns the underlying reader.
+    ///
+    /// Blocks that were read ahead are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf::{self as bgzf, reader::DecompressedBlocks};
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(2).unwrap());
+    ///
+    /// let data = [];
+    /// let blocks = DecompressedBlocks::new(&data[..], worker_pool);
+    /// assert!(blocks.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn next_block(&mut self) -> io::Result<Option<DecompressedBlock>> {
+        loop {
+            self.read_ahead.fill(&mut self.inner, true)?;
+
+            let (block, _) = match self.read_ahead.tasks.pop() {
+                Some(result) => result?,
+                None => return Ok(None),
+            };
+
+            if block.ulen() > 0 {
+                return Ok(Some(DecompressedBlock {
+                    position: block.cpos(),
+                    data: block.into_data(),
+                }));
+            }
+        }
+    }
+}
+
+impl<R> Iterator for DecompressedBlocks<R>
+where
+    R: Read,
+{
+    type Item = io::Result<DecompressedBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_block().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, num::NonZeroUsize};
+
+    use super::*;
+    use crate::Writer;
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+
+        for i in 0..64 {
+            writer.write_all(&[i; 1024])?;
+            writer.flush()?;
+        }
+
+        let data = writer.finish()?;
+
+        let worker_pool = WorkerPool::new(NonZeroUsize::new(2).unwrap());
+        let blocks: Vec<_> =
+            DecompressedBlocks::new(&data[..], worker_pool).collect::<io::Result<_>>()?;
+
+        assert_eq!(blocks.len(), 64);
+
+        for (i, block) in (0..).zip(&blocks) {
+            assert_eq!(block.data(), [i; 1024]);
+        }
+
+        assert_eq!(blocks[0].position(), 0);
+        assert!(blocks.windows(2).all(|w| w[0].position() < w[1].position()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_invalid_block() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        let mut data = writer.finish()?;
+        data[27] ^= 0xff; // CRC32
+
+        let worker_pool = WorkerPool::new(NonZeroUsize::new(2).unwrap());
+        let mut blocks = DecompressedBlocks::new(&data[..], worker_pool);
+
+        assert!(matches!(
+            blocks.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
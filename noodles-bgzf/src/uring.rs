@@ -0,0 +1,288 @@
+//! io_uring-backed file reading.
+//!
+//! A [`File`] implements the async read traits ([`AsyncRead`], [`AsyncBufRead`], and
+//! [`AsyncSeek`]) using positioned reads submitted to an io_uring instance rather than blocking
+//! reads on a thread pool. It can be used as the underlying reader of an async BGZF reader and,
+//! in turn, the async readers of formats that use BGZF, e.g., to serve many concurrent indexed
+//! queries.
+//!
+//! This is only available on Linux. Files must be opened, read, and dropped within a
+//! [`tokio_uring`] runtime, e.g., in the future given to [`tokio_uring::start`].
+
+use std::{
+    cmp,
+    future::Future,
+    io,
+    path::Path,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use futures::ready;
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncSeek, ReadBuf, SeekFrom};
+
+// The maximum size of a BGZF block.
+const DEFAULT_BUFFER_SIZE: usize = 1 << 16;
+
+type ReadAt = Pin<Box<dyn Future<Output = (io::Result<usize>, Vec<u8>)>>>;
+
+enum State {
+    Idle,
+    Reading(ReadAt),
+}
+
+/// An io_uring-backed file.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::{self as bgzf, uring};
+///
+/// tokio_uring::start(async {
+///     let file = uring::File::open("sample.bam")?;
+///     let mut reader = bgzf::AsyncReader::new(file);
+///     reader.seek(bgzf::VirtualPosition::from(102334155)).await?;
+///     Ok::<_, io::Error>(())
+/// })?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct File {
+    inner: Rc<tokio_uring::fs::File>,
+    len: u64,
+    position: u64,
+    buf: Vec<u8>,
+    buf_position: usize,
+    capacity: usize,
+    state: State,
+}
+
+impl File {
+    /// Opens a file for reading.
+    ///
+    /// This must be called within a [`tokio_uring`] runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_bgzf::uring;
+    ///
+    /// tokio_uring::start(async {
+    ///     let file = uring::File::open("sample.bam")?;
+    ///     Ok::<_, std::io::Error>(())
+    /// })?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn open<P>(src: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Self::with_capacity(src, DEFAULT_BUFFER_SIZE)
+    }
+
+    /// Opens a file for reading with the given read buffer capacity.
+    ///
+    /// Each read submitted to the ring requests up to `capacity` bytes. The default is 64 KiB,
+    /// the maximum size of a BGZF block.
+    ///
+    /// This must be called within a [`tokio_uring`] runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_bgzf::uring;
+    ///
+    /// tokio_uring::start(async {
+    ///     let file = uring::File::with_capacity("sample.bam", 1 << 20)?;
+    ///     Ok::<_, std::io::Error>(())
+    /// })?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn with_capacity<P>(src: P, capacity: usize) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = std::fs::File::open(src)?;
+        let len = file.metadata()?.len();
+        let capacity = cmp::max(capacity, 1);
+
+        Ok(Self {
+            inner: Rc::new(tokio_uring::fs::File::from_std(file)),
+            len,
+            position: 0,
+            buf: Vec::with_capacity(capacity),
+            buf_position: 0,
+            capacity,
+            state: State::Idle,
+        })
+    }
+
+    /// Returns the length of the file when it was opened.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_bgzf::uring;
+    ///
+    /// tokio_uring::start(async {
+    ///     let file = uring::File::open("sample.bam")?;
+    ///     println!("{}", file.len());
+    ///     Ok::<_, std::io::Error>(())
+    /// })?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    fn reset_buffer(&mut self) {
+        self.state = State::Idle;
+        self.buf.clear();
+        self.buf_position = 0;
+    }
+}
+
+impl AsyncBufRead for File {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+
+        if this.buf_position >= this.buf.len() {
+            if let State::Idle = this.state {
+                let file = this.inner.clone();
+                let mut buf = std::mem::take(&mut this.buf);
+                buf.clear();
+                buf.reserve(this.capacity);
+                let position = this.position;
+
+                this.state =
+                    State::Reading(Box::pin(async move { file.read_at(buf, position).await }));
+            }
+
+            if let State::Reading(read_at) = &mut this.state {
+                let (result, buf) = ready!(read_at.as_mut().poll(cx));
+
+                this.state = State::Idle;
+                this.buf = buf;
+                this.buf_position = 0;
+
+                if let Err(e) = result {
+                    this.buf.clear();
+                    return Poll::Ready(Err(e));
+                }
+            }
+        }
+
+        Poll::Ready(Ok(&this.buf[this.buf_position..]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        let amt = cmp::min(amt, this.buf.len() - this.buf_position);
+        this.buf_position += amt;
+        this.position += amt as u64;
+    }
+}
+
+impl AsyncRead for File {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let src = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let amt = cmp::min(src.len(), buf.remaining());
+        buf.put_slice(&src[..amt]);
+        self.consume(amt);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncSeek for File {
+    fn start_seek(self: Pin<&mut Self>, pos: SeekFrom) -> io::Result<()> {
+        let this = self.get_mut();
+
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => (n, 0),
+            SeekFrom::End(n) => (this.len, n),
+            SeekFrom::Current(n) => (this.position, n),
+        };
+
+        let position = if offset >= 0 {
+            base.checked_add(offset as u64)
+        } else {
+            base.checked_sub(offset.unsigned_abs())
+        }
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        if position != this.position {
+            this.reset_buffer();
+            this.position = position;
+        }
+
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Poll::Ready(Ok(self.position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    use super::*;
+    use crate::{AsyncReader, VirtualPosition, Writer};
+
+    #[test]
+    fn test_read_and_seek() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        let position = VirtualPosition::try_from((writer.get_ref().len() as u64, 0)).unwrap();
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let path = std::env::temp_dir().join("noodles-bgzf-uring-test_read_and_seek.gz");
+        std::fs::write(&path, &data)?;
+
+        tokio_uring::start(async {
+            let mut file = File::with_capacity(&path, 8)?;
+            assert_eq!(file.len(), data.len() as u64);
+
+            let mut buf = Vec::new();
+            file.read_to_end(&mut buf).await?;
+            assert_eq!(buf, data);
+
+            assert_eq!(file.seek(SeekFrom::End(-4)).await?, data.len() as u64 - 4);
+            buf.clear();
+            file.read_to_end(&mut buf).await?;
+            assert_eq!(buf, &data[data.len() - 4..]);
+
+            file.seek(SeekFrom::Start(0)).await?;
+            let mut reader = AsyncReader::new(file);
+
+            buf.clear();
+            reader.read_to_end(&mut buf).await?;
+            assert_eq!(buf, b"noodlesbgzf");
+
+            reader.seek(position).await?;
+            buf.clear();
+            reader.read_to_end(&mut buf).await?;
+            assert_eq!(buf, b"bgzf");
+
+            Ok::<_, io::Error>(())
+        })?;
+
+        std::fs::remove_file(path)
+    }
+}
@@ -1,6 +1,6 @@
 //! BGZF virtual position.
 
-use std::{error, fmt};
+use std::{error, fmt, num, str::FromStr};
 
 pub(crate) const MAX_COMPRESSED_POSITION: u64 = (1 << 48) - 1;
 pub(crate) const MAX_UNCOMPRESSED_POSITION: u16 = u16::MAX;
@@ -32,11 +32,41 @@ const MAX_VIRTUAL_POSITION: u64 = u64::MAX;
 /// The compressed position is at 156453154188165 (`8e 4b 16 ad eb 85`), and the uncompressed
 /// position, 35047 (`88 e7`).
 ///
+/// Virtual positions are ordered by compressed position and then by uncompressed position, i.e.,
+/// in the order of the uncompressed data they point to, provided blocks are in stream order.
+///
+/// A virtual position is formatted and parsed as `<compressed position>:<uncompressed position>`,
+/// e.g., `57:6086`.
+///
 /// This is also called a virtual file offset; or, simply, a virtual offset.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct VirtualPosition(u64);
 
 impl VirtualPosition {
+    /// Creates a virtual position from a compressed and uncompressed position.
+    ///
+    /// This returns `None` if the compressed position is larger than 2^48 - 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let virtual_position = bgzf::VirtualPosition::new(57, 6086);
+    /// assert_eq!(virtual_position, Some(bgzf::VirtualPosition::from(3741638)));
+    ///
+    /// assert!(bgzf::VirtualPosition::new(1 << 48, 0).is_none());
+    /// ```
+    pub const fn new(compressed_pos: u64, uncompressed_pos: u16) -> Option<Self> {
+        if compressed_pos > MAX_COMPRESSED_POSITION {
+            None
+        } else {
+            Some(Self(
+                compressed_pos << COMPRESSED_POSITION_SHIFT | uncompressed_pos as u64,
+            ))
+        }
+    }
+
     /// Creates the largest value that can be represented as a virtual position.
     ///
     /// # Examples
@@ -89,6 +119,76 @@ impl From<u64> for VirtualPosition {
     }
 }
 
+impl fmt::Display for VirtualPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.compressed(), self.uncompressed())
+    }
+}
+
+/// An error returned when a raw virtual position fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid,
+    /// The compressed position is invalid.
+    InvalidCompressedPosition(num::ParseIntError),
+    /// The uncompressed position is invalid.
+    InvalidUncompressedPosition(num::ParseIntError),
+    /// The compressed position is larger than 2^48 - 1.
+    CompressedPositionOverflow,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid => f.write_str("invalid input"),
+            Self::InvalidCompressedPosition(e) => write!(f, "invalid compressed position: {}", e),
+            Self::InvalidUncompressedPosition(e) => {
+                write!(f, "invalid uncompressed position: {}", e)
+            }
+            Self::CompressedPositionOverflow => {
+                f.write_str("the compressed position is larger than 2^48 - 1")
+            }
+        }
+    }
+}
+
+impl FromStr for VirtualPosition {
+    type Err = ParseError;
+
+    /// Parses a virtual position formatted as `<compressed position>:<uncompressed position>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// assert_eq!("57:6086".parse(), Ok(bgzf::VirtualPosition::from(3741638)));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let (raw_compressed_pos, raw_uncompressed_pos) =
+            s.split_once(':').ok_or(ParseError::Invalid)?;
+
+        let compressed_pos = raw_compressed_pos
+            .parse()
+            .map_err(ParseError::InvalidCompressedPosition)?;
+
+        let uncompressed_pos = raw_uncompressed_pos
+            .parse()
+            .map_err(ParseError::InvalidUncompressedPosition)?;
+
+        Self::new(compressed_pos, uncompressed_pos).ok_or(ParseError::CompressedPositionOverflow)
+    }
+}
+
 /// An error returned when converting a (u64, u16) to a virtual position fails.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum TryFromU64U16TupleError {
@@ -122,14 +222,8 @@ impl TryFrom<(u64, u16)> for VirtualPosition {
     /// ```
     fn try_from(pos: (u64, u16)) -> Result<Self, Self::Error> {
         let (compressed_pos, uncompressed_pos) = pos;
-
-        if compressed_pos > MAX_COMPRESSED_POSITION {
-            return Err(TryFromU64U16TupleError::CompressedPositionOverflow);
-        }
-
-        Ok(Self(
-            compressed_pos << COMPRESSED_POSITION_SHIFT | u64::from(uncompressed_pos),
-        ))
+        Self::new(compressed_pos, uncompressed_pos)
+            .ok_or(TryFromU64U16TupleError::CompressedPositionOverflow)
     }
 }
 
@@ -164,6 +258,41 @@ mod tests {
         assert_eq!(pos.uncompressed(), 321);
     }
 
+    #[test]
+    fn test_cmp() {
+        assert!(VirtualPosition::from(3741638) < VirtualPosition::new(57, 6087).unwrap());
+        assert!(VirtualPosition::from(3741638) < VirtualPosition::new(58, 0).unwrap());
+        assert!(VirtualPosition::new(56, u16::MAX).unwrap() < VirtualPosition::from(3741638));
+    }
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(VirtualPosition::from(3741638).to_string(), "57:6086");
+        assert_eq!(VirtualPosition::default().to_string(), "0:0");
+        assert_eq!(VirtualPosition::max().to_string(), "281474976710655:65535");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("57:6086".parse(), Ok(VirtualPosition::from(3741638)));
+        assert_eq!("281474976710655:65535".parse(), Ok(VirtualPosition::max()));
+
+        assert_eq!("".parse::<VirtualPosition>(), Err(ParseError::Empty));
+        assert_eq!("57".parse::<VirtualPosition>(), Err(ParseError::Invalid));
+        assert!(matches!(
+            ":6086".parse::<VirtualPosition>(),
+            Err(ParseError::InvalidCompressedPosition(_))
+        ));
+        assert!(matches!(
+            "57:65536".parse::<VirtualPosition>(),
+            Err(ParseError::InvalidUncompressedPosition(_))
+        ));
+        assert_eq!(
+            "281474976710656:0".parse::<VirtualPosition>(),
+            Err(ParseError::CompressedPositionOverflow)
+        );
+    }
+
     #[test]
     fn test_try_from_u64_u16_tuple_for_virtual_position() {
         assert_eq!(
@@ -0,0 +1,261 @@
+//! Worker pool.
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+// Whether the target supports spawning threads.
+const HAS_THREADS: bool = !cfg!(target_arch = "wasm32");
+
+/// A worker pool.
+///
+/// A worker pool is a fixed set of threads that run compression and decompression jobs. The pool
+/// is a cheaply cloneable handle, and the same pool can be given to multiple readers and writers,
+/// e.g., across BGZF, BAM, and CRAM, to cap the total number of threads used by an application.
+///
+/// The worker threads are stopped when the last handle is dropped, after all submitted jobs are
+/// completed.
+///
+/// On targets without thread support (i.e., `wasm32`), no threads are started, and jobs are run
+/// on the calling thread when they are submitted.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroUsize;
+/// use noodles_bgzf as bgzf;
+///
+/// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(4).unwrap());
+/// assert_eq!(worker_pool.worker_count().get(), 4);
+///
+/// let task = worker_pool.spawn(|| 5 + 8);
+/// assert_eq!(task.wait(), 13);
+/// ```
+#[derive(Clone)]
+pub struct WorkerPool {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    sender: Mutex<Option<Sender<Job>>>,
+    workers: Vec<JoinHandle<()>>,
+    worker_count: NonZeroUsize,
+}
+
+impl WorkerPool {
+    /// Creates a worker pool with the given number of threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(4).unwrap());
+    /// ```
+    pub fn new(worker_count: NonZeroUsize) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = if HAS_THREADS {
+            (0..worker_count.get())
+                .map(|_| {
+                    let receiver = receiver.clone();
+                    thread::spawn(move || run(&receiver))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            inner: Arc::new(Inner {
+                sender: Mutex::new(Some(sender)),
+                workers,
+                worker_count,
+            }),
+        }
+    }
+
+    /// Returns the number of worker threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(4).unwrap());
+    /// assert_eq!(worker_pool.worker_count().get(), 4);
+    /// ```
+    pub fn worker_count(&self) -> NonZeroUsize {
+        self.inner.worker_count
+    }
+
+    /// Submits a job to the pool.
+    ///
+    /// This returns a task that can be used to wait for the result of the job.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(1).unwrap());
+    /// let task = worker_pool.spawn(|| 5 + 8);
+    /// assert_eq!(task.wait(), 13);
+    /// ```
+    pub fn spawn<F, T>(&self, f: F) -> Task<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+
+        let job: Job = Box::new(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(f));
+            let _ = sender.send(result);
+        });
+
+        if self.inner.workers.is_empty() {
+            job();
+            return Task { receiver };
+        }
+
+        let guard = self.inner.sender.lock().unwrap();
+
+        if let Some(sender) = guard.as_ref() {
+            // The receiver is only dropped when all workers stop, which requires the sender to
+            // be dropped first.
+            sender.send(job).unwrap();
+        }
+
+        Task { receiver }
+    }
+}
+
+impl fmt::Debug for WorkerPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerPool")
+            .field("worker_count", &self.inner.worker_count)
+            .finish()
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        if let Ok(mut sender) = self.sender.lock() {
+            sender.take();
+        }
+
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn run(receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        let job = match receiver.lock() {
+            Ok(receiver) => receiver.recv(),
+            Err(_) => return,
+        };
+
+        match job {
+            Ok(job) => job(),
+            Err(_) => return,
+        }
+    }
+}
+
+/// A handle to the result of a job submitted to a worker pool.
+pub struct Task<T> {
+    receiver: Receiver<thread::Result<T>>,
+}
+
+impl<T> Task<T> {
+    /// Waits for the job to complete and returns its result.
+    ///
+    /// If the job panicked, the panic is resumed on the calling thread.
+    pub fn wait(self) -> T {
+        match self.receiver.recv() {
+            Ok(Ok(value)) => value,
+            Ok(Err(payload)) => panic::resume_unwind(payload),
+            Err(_) => panic!("worker pool job was dropped"),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Task<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Task").finish()
+    }
+}
+
+// A queue of tasks, bounded by the number of workers, whose results are consumed in submission
+// order.
+#[derive(Debug)]
+pub(crate) struct TaskQueue<T> {
+    tasks: VecDeque<Task<T>>,
+    capacity: usize,
+}
+
+impl<T> TaskQueue<T> {
+    pub(crate) fn new(worker_pool: &WorkerPool) -> Self {
+        // Keep twice as many tasks in flight as there are workers so that workers are not idle
+        // while results are consumed.
+        let capacity = 2 * worker_pool.worker_count().get();
+
+        Self {
+            tasks: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.tasks.len() >= self.capacity
+    }
+
+    pub(crate) fn push(&mut self, task: Task<T>) {
+        self.tasks.push_back(task);
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        self.tasks.pop_front().map(Task::wait)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.tasks.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawn() {
+        let worker_pool = WorkerPool::new(NonZeroUsize::new(2).unwrap());
+
+        let tasks: Vec<_> = (0..8).map(|i| worker_pool.spawn(move || i * 2)).collect();
+        let results: Vec<_> = tasks.into_iter().map(Task::wait).collect();
+
+        assert_eq!(results, [0, 2, 4, 6, 8, 10, 12, 14]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_spawn_with_panicking_job() {
+        let worker_pool = WorkerPool::new(NonZeroUsize::new(1).unwrap());
+        let task = worker_pool.spawn(|| panic!("noodles"));
+        task.wait();
+    }
+}
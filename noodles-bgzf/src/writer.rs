@@ -1,18 +1,30 @@
 //! BGZF writer.
 
+mod builder;
 mod compression_level;
 
-pub use self::compression_level::CompressionLevel;
+pub use self::{builder::Builder, compression_level::CompressionLevel};
 
 use std::{
     cmp,
     io::{self, Write},
+    mem,
+    sync::Arc,
 };
 
-use byteorder::{LittleEndian, WriteBytesExt};
+use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
 use flate2::Crc;
 
-use super::{block, gz, BGZF_HEADER_SIZE};
+use super::{
+    gz,
+    worker_pool::{TaskQueue, WorkerPool},
+    Observer, VirtualPosition, BGZF_HEADER_SIZE,
+};
+
+#[cfg(feature = "libdeflate")]
+type CompressionLevelImpl = libdeflater::CompressionLvl;
+#[cfg(not(feature = "libdeflate"))]
+type CompressionLevelImpl = flate2::Compression;
 
 const BGZF_FLG: u8 = 0x04; // FEXTRA
 const BGZF_XFL: u8 = 0x00; // none
@@ -62,6 +74,16 @@ where
 {
     inner: Option<W>,
     buf: Vec<u8>,
+    deflater: Deflater,
+    max_block_size: usize,
+    worker_pool: Option<(WorkerPool, TaskQueue<io::Result<Vec<u8>>>)>,
+    observer: Option<Arc<dyn Observer>>,
+    // Encoded additional gzip extra subfields written in each block header.
+    extra_subfields: Arc<[u8]>,
+    // The number of compressed bytes written to the underlying writer.
+    position: u64,
+    // Whether the EOF block was written after the last data.
+    is_finished: bool,
 }
 
 impl<W> Writer<W>
@@ -77,10 +99,20 @@ where
     /// let writer = bgzf::Writer::new(Vec::new());
     /// ```
     pub fn new(inner: W) -> Self {
-        Self {
-            inner: Some(inner),
-            buf: Vec::with_capacity(block::MAX_UNCOMPRESSED_DATA_LENGTH),
-        }
+        Self::builder(inner).build()
+    }
+
+    /// Creates a BGZF writer builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::Writer::builder(Vec::new());
+    /// let writer = builder.build();
+    /// ```
+    pub fn builder(inner: W) -> Builder<W> {
+        Builder::new(inner)
     }
 
     /// Returns a reference to the underlying writer.
@@ -96,23 +128,176 @@ where
         self.inner.as_ref().unwrap()
     }
 
-    fn flush_block(&mut self) -> io::Result<()> {
-        let (cdata, crc32, r#isize) = deflate_data(&self.buf, Default::default())?;
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// assert!(writer.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.as_mut().unwrap()
+    }
+
+    /// Returns the virtual position of the next write.
+    ///
+    /// This includes uncompressed data that is buffered but not yet written as a block, e.g., the
+    /// virtual position before writing a record is its start for an index. When the current block
+    /// is full, this is the end of the current block, which is equivalent to the start of the
+    /// next block.
+    ///
+    /// If blocks are compressed using a worker pool, this waits for the pending blocks to be
+    /// written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// assert_eq!(writer.virtual_position()?, bgzf::VirtualPosition::from(0));
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// assert_eq!(writer.virtual_position()?, bgzf::VirtualPosition::from(7));
+    ///
+    /// writer.flush()?;
+    /// assert_eq!(writer.virtual_position()?, bgzf::VirtualPosition::from(35 << 16));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn virtual_position(&mut self) -> io::Result<VirtualPosition> {
+        self.flush_tasks()?;
+
+        // The uncompressed data length of a block is at most `block::MAX_WRITE_DATA_LENGTH`.
+        let upos = self.buf.len() as u16;
+
+        VirtualPosition::new(self.position, upos).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "compressed position is out of bounds of a virtual position",
+            )
+        })
+    }
+
+    fn write_buffered_block(&mut self) -> io::Result<()> {
+        let inner = self.inner.as_mut().unwrap();
+
+        match self.worker_pool.as_mut() {
+            Some((worker_pool, tasks)) => {
+                if tasks.is_full() {
+                    if let Some(block) = tasks.pop() {
+                        self.position += write_block(inner, &block?, self.observer.as_deref())?;
+                    }
+                }
+
+                let data = mem::replace(&mut self.buf, Vec::with_capacity(self.max_block_size));
+
+                let deflater = self.deflater;
+                let extra_subfields = self.extra_subfields.clone();
+
+                tasks.push(
+                    worker_pool.spawn(move || compress_block(&data, deflater, &extra_subfields)),
+                );
+            }
+            None => {
+                let block = compress_block(&self.buf, self.deflater, &self.extra_subfields)?;
+                self.position += write_block(inner, &block, self.observer.as_deref())?;
+                self.buf.clear();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ends the current block.
+    ///
+    /// Any buffered data is compressed as a block, so the next write starts a new block. This can
+    /// be used to force block boundaries at record boundaries, e.g., at the start of each
+    /// reference sequence, which aligns index chunks to blocks. If no data is buffered, this does
+    /// nothing, i.e., empty blocks are not written.
+    ///
+    /// Unlike [`Write::flush`], this does not wait for blocks being compressed by a worker pool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush_block()?;
+    /// assert_eq!(writer.virtual_position()?, bgzf::VirtualPosition::from(35 << 16));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn flush_block(&mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            Ok(())
+        } else {
+            self.write_buffered_block()
+        }
+    }
+
+    /// Writes an empty block.
+    ///
+    /// Any buffered data is first compressed as a block. An empty block has the same form as the
+    /// EOF marker, and readers skip it, but it can be used as a sync point in the middle of a
+    /// stream, e.g., to mark the end of a section. The EOF marker is still written when the
+    /// stream is finished.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.write_empty_block()?;
+    /// writer.write_all(b"bgzf")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = bgzf::Reader::new(&data[..]);
+    /// let mut buf = Vec::new();
+    /// reader.read_to_end(&mut buf)?;
+    /// assert_eq!(buf, b"noodlesbgzf");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_empty_block(&mut self) -> io::Result<()> {
+        self.write_raw_block(BGZF_EOF)
+    }
+
+    // Writes an already compressed block after any buffered data.
+    pub(crate) fn write_raw_block(&mut self, block: &[u8]) -> io::Result<()> {
+        self.flush()?;
 
         let inner = self.inner.as_mut().unwrap();
+        self.position += write_block(inner, block, self.observer.as_deref())?;
+        self.is_finished = false;
 
-        write_header(inner, cdata.len())?;
-        inner.write_all(&cdata[..])?;
-        write_trailer(inner, crc32, r#isize)?;
+        Ok(())
+    }
 
-        self.buf.clear();
+    // Writes all blocks that are being compressed by the worker pool.
+    fn flush_tasks(&mut self) -> io::Result<()> {
+        if let Some((_, tasks)) = self.worker_pool.as_mut() {
+            let inner = self.inner.as_mut().unwrap();
+
+            while let Some(block) = tasks.pop() {
+                self.position += write_block(inner, &block?, self.observer.as_deref())?;
+            }
+        }
 
         Ok(())
     }
 
     /// Attempts to finish the output stream by flushing any remaining buffers.
     ///
-    /// This then appends the final BGZF EOF block.
+    /// This then appends the final BGZF EOF block. The EOF block is only written once, i.e.,
+    /// calling this again or dropping the writer afterward does not write another EOF block
+    /// unless more data was written.
     ///
     /// # Examples
     ///
@@ -128,8 +313,14 @@ where
     /// ```
     pub fn try_finish(&mut self) -> io::Result<()> {
         self.flush()?;
-        let inner = self.inner.as_mut().unwrap();
-        inner.write_all(BGZF_EOF)
+
+        if !self.is_finished {
+            let inner = self.inner.as_mut().unwrap();
+            self.position += write_block(inner, BGZF_EOF, self.observer.as_deref())?;
+            self.is_finished = true;
+        }
+
+        Ok(())
     }
 
     /// Returns the underlying writer after finishing the output stream.
@@ -171,49 +362,106 @@ where
     W: Write,
 {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        if self.buf.len() >= block::MAX_UNCOMPRESSED_DATA_LENGTH {
-            self.flush()?;
-            return Err(io::Error::from(io::ErrorKind::Interrupted));
+        if self.buf.len() >= self.max_block_size {
+            self.write_buffered_block()?;
         }
 
-        let max_write_len = cmp::min(
-            block::MAX_UNCOMPRESSED_DATA_LENGTH - self.buf.len(),
-            buf.len(),
-        );
+        let max_write_len = cmp::min(self.max_block_size - self.buf.len(), buf.len());
 
         self.buf.extend_from_slice(&buf[..max_write_len]);
 
+        if max_write_len > 0 {
+            self.is_finished = false;
+        }
+
         Ok(max_write_len)
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        if self.buf.is_empty() {
-            Ok(())
-        } else {
-            self.flush_block()
-        }
+        self.flush_block()?;
+        self.flush_tasks()
     }
 }
 
-fn write_header<W>(writer: &mut W, cdata_len: usize) -> io::Result<()>
+// Writes a block and returns its size.
+fn write_block<W>(writer: &mut W, block: &[u8], observer: Option<&dyn Observer>) -> io::Result<u64>
 where
     W: Write,
 {
+    writer.write_all(block)?;
+
+    let block_size = block.len() as u64;
+
+    if let Some(observer) = observer {
+        // The uncompressed data length is the last field of the block (ISIZE).
+        let r#isize = LittleEndian::read_u32(&block[block.len() - mem::size_of::<u32>()..]);
+        observer.on_block(block_size, u64::from(r#isize));
+    }
+
+    Ok(block_size)
+}
+
+// The DEFLATE encoder used to compress blocks.
+#[derive(Clone, Copy, Debug)]
+enum Deflater {
+    // The enabled backend, i.e., flate2 or libdeflate.
+    Default(CompressionLevelImpl),
+    // miniz_oxide, regardless of the enabled backend (see `Builder::set_deterministic`).
+    Deterministic(u8),
+}
+
+// Compresses the given data as a BGZF block.
+fn compress_block(data: &[u8], deflater: Deflater, extra_subfields: &[u8]) -> io::Result<Vec<u8>> {
+    let (cdata, crc32, r#isize) = match deflater {
+        Deflater::Default(compression_level) => deflate_data(data, compression_level)?,
+        Deflater::Deterministic(compression_level) => {
+            deflate_data_deterministic(data, compression_level)
+        }
+    };
+
+    let header_size = BGZF_HEADER_SIZE + extra_subfields.len();
+    let mut block = Vec::with_capacity(header_size + cdata.len() + gz::TRAILER_SIZE);
+    write_header(&mut block, extra_subfields, cdata.len())?;
+    block.extend_from_slice(&cdata);
+    write_trailer(&mut block, crc32, r#isize)?;
+
+    Ok(block)
+}
+
+fn write_header<W>(writer: &mut W, extra_subfields: &[u8], cdata_len: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    let invalid_block_size = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "block size exceeds the maximum BSIZE",
+        )
+    };
+
+    let xlen = u16::try_from(extra_subfields.len())
+        .ok()
+        .and_then(|n| n.checked_add(BGZF_XLEN))
+        .ok_or_else(invalid_block_size)?;
+
+    let block_size = BGZF_HEADER_SIZE + extra_subfields.len() + cdata_len + gz::TRAILER_SIZE;
+    let bsize = u16::try_from(block_size - 1).map_err(|_| invalid_block_size())?;
+
     writer.write_all(&gz::MAGIC_NUMBER)?;
     writer.write_u8(gz::CompressionMethod::Deflate as u8)?;
     writer.write_u8(BGZF_FLG)?;
     writer.write_u32::<LittleEndian>(gz::MTIME_NONE)?;
     writer.write_u8(BGZF_XFL)?;
     writer.write_u8(gz::OperatingSystem::Unknown as u8)?;
-    writer.write_u16::<LittleEndian>(BGZF_XLEN)?;
+    writer.write_u16::<LittleEndian>(xlen)?;
 
     writer.write_u8(BGZF_SI1)?;
     writer.write_u8(BGZF_SI2)?;
     writer.write_u16::<LittleEndian>(BGZF_SLEN)?;
-
-    let bsize = (cdata_len + BGZF_HEADER_SIZE + gz::TRAILER_SIZE - 1) as u16;
     writer.write_u16::<LittleEndian>(bsize)?;
 
+    writer.write_all(extra_subfields)?;
+
     Ok(())
 }
 
@@ -226,6 +474,20 @@ where
     Ok(())
 }
 
+// Compresses data using miniz_oxide, which produces the same output for the same input and
+// compression level on all platforms and independent of how flate2 is configured.
+fn deflate_data_deterministic(data: &[u8], compression_level: u8) -> (Vec<u8>, u32, u32) {
+    const MAX_COMPRESSION_LEVEL: u8 = 9;
+
+    let compression_level = cmp::min(compression_level, MAX_COMPRESSION_LEVEL);
+    let compressed_data = miniz_oxide::deflate::compress_to_vec(data, compression_level);
+
+    let mut crc = Crc::new();
+    crc.update(data);
+
+    (compressed_data, crc.sum(), crc.amount())
+}
+
 #[cfg(feature = "libdeflate")]
 pub(crate) fn deflate_data(
     data: &[u8],
@@ -285,4 +547,273 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_try_finish() -> io::Result<()> {
+        let mut data = Vec::new();
+
+        {
+            let mut writer = Writer::new(&mut data);
+            writer.write_all(b"noodles")?;
+            writer.try_finish()?;
+            writer.try_finish()?;
+        }
+
+        let mut expected = Writer::new(Vec::new());
+        expected.write_all(b"noodles")?;
+        assert_eq!(data, expected.finish()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_virtual_position() -> io::Result<()> {
+        use std::{io::Read, num::NonZeroUsize};
+
+        use crate::Reader;
+
+        let records: Vec<_> = (0..1024u32)
+            .map(|i| i.to_string().repeat(64).into_bytes())
+            .collect();
+
+        for worker_count in [None, NonZeroUsize::new(2)] {
+            let mut builder = Writer::builder(Vec::new());
+
+            if let Some(n) = worker_count {
+                builder = builder.set_worker_count(n);
+            }
+
+            let mut writer = builder.build();
+            let mut positions = Vec::new();
+
+            for record in &records {
+                positions.push(writer.virtual_position()?);
+                writer.write_all(record)?;
+            }
+
+            let data = writer.finish()?;
+            let mut reader = Reader::new(io::Cursor::new(data));
+
+            for (record, position) in records.iter().zip(positions).rev() {
+                reader.seek(position)?;
+
+                let mut buf = vec![0; record.len()];
+                reader.read_exact(&mut buf)?;
+
+                assert_eq!(&buf, record);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write() -> io::Result<()> {
+        let mut writer = Writer::builder(Vec::new())
+            .set_max_block_size(std::num::NonZeroUsize::new(4).unwrap())
+            .build();
+
+        assert_eq!(writer.write(b"nood")?, 4);
+        assert_eq!(writer.write(b"les")?, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flush_block() -> io::Result<()> {
+        use std::num::NonZeroUsize;
+
+        use crate::raw_block::RawBlocks;
+
+        for worker_count in [None, NonZeroUsize::new(2)] {
+            let mut builder = Writer::builder(Vec::new());
+
+            if let Some(n) = worker_count {
+                builder = builder.set_worker_count(n);
+            }
+
+            let mut writer = builder.build();
+
+            writer.flush_block()?;
+            writer.write_all(b"noodles")?;
+            writer.flush_block()?;
+            writer.flush_block()?;
+            writer.write_all(b"bgzf")?;
+
+            let data = writer.finish()?;
+
+            let uncompressed_lens = RawBlocks::new(&data[..])
+                .map(|result| result.map(|block| block.uncompressed_len()))
+                .collect::<io::Result<Vec<_>>>()?;
+
+            assert_eq!(uncompressed_lens, [7, 4, 0]);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_max_block_size() -> io::Result<()> {
+        use std::num::NonZeroUsize;
+
+        use crate::raw_block::RawBlocks;
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_max_block_size(NonZeroUsize::new(4).unwrap())
+            .build();
+
+        writer.write_all(b"noodles")?;
+        let data = writer.finish()?;
+
+        let uncompressed_lens = RawBlocks::new(&data[..])
+            .map(|result| result.map(|block| block.uncompressed_len()))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(uncompressed_lens, [4, 3, 0]);
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "libdeflate"))]
+    #[test]
+    fn test_write_with_compression_level() -> io::Result<()> {
+        use std::io::Read;
+
+        use crate::Reader;
+
+        let data: Vec<_> = (0..=255).cycle().take(1 << 16).collect();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(&data)?;
+        let compressed_data = writer.finish()?;
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_compression_level(CompressionLevel::none())
+            .build();
+        writer.write_all(&data)?;
+        let stored_data = writer.finish()?;
+
+        assert!(stored_data.len() > data.len());
+        assert!(stored_data.len() > compressed_data.len());
+
+        let mut reader = Reader::new(&stored_data[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_worker_pool() -> io::Result<()> {
+        use std::num::NonZeroUsize;
+
+        let data: Vec<_> = (0..=255).cycle().take(1 << 20).collect();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(&data)?;
+        let expected = writer.finish()?;
+
+        let worker_pool = WorkerPool::new(NonZeroUsize::new(2).unwrap());
+        let mut writer = Writer::builder(Vec::new())
+            .set_worker_pool(worker_pool)
+            .build();
+        writer.write_all(&data)?;
+        let actual = writer.finish()?;
+
+        assert_eq!(actual, expected);
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_worker_count(NonZeroUsize::new(2).unwrap())
+            .build();
+        writer.write_all(&data)?;
+        let actual = writer.finish()?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_observer() -> io::Result<()> {
+        use crate::observer::Counter;
+
+        let counter = Arc::new(Counter::default());
+        let mut writer = Writer::builder(Vec::new())
+            .set_observer(counter.clone())
+            .build();
+        writer.write_all(b"noodles")?;
+        let data = writer.finish()?;
+
+        assert_eq!(counter.block_count(), 2);
+        assert_eq!(counter.compressed_bytes(), data.len() as u64);
+        assert_eq!(counter.uncompressed_bytes(), 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_deterministic() -> io::Result<()> {
+        use std::num::NonZeroUsize;
+
+        let mut writer = Writer::builder(Vec::new()).set_deterministic(true).build();
+        writer.write_all(b"noodles")?;
+        let actual = writer.finish()?;
+
+        let mut expected = vec![
+            0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43,
+            0x02, 0x00, 0x22, 0x00, 0xcb, 0xcb, 0xcf, 0x4f, 0xc9, 0x49, 0x2d, 0x06, 0x00, 0xa1,
+            0x58, 0x2a, 0x80, 0x07, 0x00, 0x00, 0x00,
+        ];
+        expected.extend_from_slice(BGZF_EOF);
+
+        assert_eq!(actual, expected);
+
+        let data: Vec<_> = (0..1 << 17).map(|i| (i % 251) as u8).collect();
+
+        let mut writer = Writer::builder(Vec::new()).set_deterministic(true).build();
+        writer.write_all(&data)?;
+        let expected = writer.finish()?;
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_deterministic(true)
+            .set_worker_count(NonZeroUsize::new(2).unwrap())
+            .build();
+        writer.write_all(&data)?;
+        let actual = writer.finish()?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_extra_subfields() -> io::Result<()> {
+        use std::io::Read;
+
+        use crate::{extra_subfield::ExtraSubfield, raw_block::RawBlocks, Reader};
+
+        let subfields = vec![
+            ExtraSubfield::new(*b"NR", b"ndl".to_vec())?,
+            ExtraSubfield::new(*b"NZ", Vec::new())?,
+        ];
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_extra_subfields(subfields.clone())
+            .build();
+        writer.write_all(b"noodles")?;
+        let data = writer.finish()?;
+
+        let blocks: Vec<_> = RawBlocks::new(&data[..]).collect::<io::Result<_>>()?;
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].extra_subfields()?, subfields);
+        assert!(blocks[1].extra_subfields()?.is_empty());
+
+        let mut reader = Reader::new(&data[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodles");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,256 @@
+use std::{cmp, io::Write, num::NonZeroUsize, sync::Arc};
+
+use super::{CompressionLevel, Deflater, Writer};
+use crate::{
+    block,
+    extra_subfield::{self, ExtraSubfield},
+    worker_pool::TaskQueue,
+    Observer, WorkerPool,
+};
+
+/// A BGZF writer builder.
+#[derive(Debug)]
+pub struct Builder<W> {
+    inner: W,
+    compression_level: Option<CompressionLevel>,
+    max_block_size: Option<NonZeroUsize>,
+    worker_pool: Option<WorkerPool>,
+    observer: Option<Arc<dyn Observer>>,
+    extra_subfields: Vec<ExtraSubfield>,
+    is_deterministic: bool,
+}
+
+impl<W> Builder<W>
+where
+    W: Write,
+{
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            compression_level: None,
+            max_block_size: None,
+            worker_pool: None,
+            observer: None,
+            extra_subfields: Vec::new(),
+            is_deterministic: false,
+        }
+    }
+
+    /// Sets a compression level.
+    ///
+    /// A compression level of 0 ([`CompressionLevel::none`]) writes blocks that are stored
+    /// without compression. By default, the compression level is set to level 6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    ///
+    /// let builder = bgzf::Writer::builder(Vec::new())
+    ///     .set_compression_level(CompressionLevel::best());
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = Some(compression_level);
+        self
+    }
+
+    /// Sets the maximum uncompressed data length of a block.
+    ///
+    /// Smaller blocks allow finer-grained random access at the cost of a lower compression ratio.
+    /// By default, and at most, blocks hold 65280 bytes of uncompressed data, which leaves room for
+    /// the block to fit in `BSIZE` when the data does not compress. Larger values are clamped to
+    /// this maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let max_block_size = NonZeroUsize::new(4096).unwrap();
+    /// let builder = bgzf::Writer::builder(Vec::new()).set_max_block_size(max_block_size);
+    /// ```
+    pub fn set_max_block_size(mut self, max_block_size: NonZeroUsize) -> Self {
+        self.max_block_size = Some(max_block_size);
+        self
+    }
+
+    /// Sets a worker pool.
+    ///
+    /// When set, blocks are compressed in parallel using the worker pool. By default, blocks are
+    /// compressed on the calling thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(4).unwrap());
+    /// let builder = bgzf::Writer::builder(Vec::new()).set_worker_pool(worker_pool);
+    /// ```
+    pub fn set_worker_pool(mut self, worker_pool: WorkerPool) -> Self {
+        self.worker_pool = Some(worker_pool);
+        self
+    }
+
+    /// Sets a worker count.
+    ///
+    /// This creates a worker pool with the given number of threads that is used only by this
+    /// writer and compresses blocks in parallel on it. Blocks are still written in order. Use
+    /// [`Self::set_worker_pool`] to share threads with other readers and writers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let worker_count = NonZeroUsize::new(4).unwrap();
+    /// let builder = bgzf::Writer::builder(Vec::new()).set_worker_count(worker_count);
+    /// ```
+    pub fn set_worker_count(self, worker_count: NonZeroUsize) -> Self {
+        self.set_worker_pool(WorkerPool::new(worker_count))
+    }
+
+    /// Sets a progress observer.
+    ///
+    /// The observer is notified after each block is written to the underlying stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use noodles_bgzf::{self as bgzf, observer::Counter};
+    /// let builder = bgzf::Writer::builder(Vec::new()).set_observer(Arc::new(Counter::default()));
+    /// ```
+    pub fn set_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Sets additional gzip extra subfields.
+    ///
+    /// The subfields are written in the header of every block after the BGZF subfield (`BC`) and
+    /// can be read back using [`crate::raw_block::RawBlock::extra_subfields`]. The maximum
+    /// uncompressed data length of a block is reduced by the size of the subfields to keep blocks
+    /// within `BSIZE`. By default, no additional subfields are written.
+    ///
+    /// Note that some readers, e.g., htslib, do not accept blocks with additional subfields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, extra_subfield::ExtraSubfield};
+    ///
+    /// let subfield = ExtraSubfield::new(*b"NR", b"noodles".to_vec())?;
+    /// let builder = bgzf::Writer::builder(Vec::new()).set_extra_subfields(vec![subfield]);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn set_extra_subfields(mut self, extra_subfields: Vec<ExtraSubfield>) -> Self {
+        self.extra_subfields = extra_subfields;
+        self
+    }
+
+    /// Sets whether the output is deterministic.
+    ///
+    /// When enabled, blocks are compressed using the same DEFLATE encoder (miniz_oxide)
+    /// regardless of the enabled backend (e.g., the `libdeflate` feature) or how flate2 is
+    /// configured by other crates. This guarantees bit-identical output across runs and platforms
+    /// for the same input, writes, compression level, and version of this crate, e.g., for
+    /// reproducible pipelines and test fixtures. Compression levels above 9 are clamped to 9.
+    ///
+    /// Block headers are always written with a fixed modification time (`MTIME` = 0) and operating
+    /// system (`OS` = 255), and blocks are written in order when compressed by a worker pool.
+    ///
+    /// By default, output is not guaranteed to be deterministic across backends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::Writer::builder(Vec::new()).set_deterministic(true);
+    /// ```
+    pub fn set_deterministic(mut self, is_deterministic: bool) -> Self {
+        self.is_deterministic = is_deterministic;
+        self
+    }
+
+    /// Builds a BGZF writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::Writer::builder(Vec::new()).build();
+    /// ```
+    pub fn build(self) -> Writer<W> {
+        let extra_subfields = extra_subfield::encode(&self.extra_subfields);
+
+        let max_write_data_length = cmp::max(
+            block::MAX_WRITE_DATA_LENGTH.saturating_sub(extra_subfields.len()),
+            1,
+        );
+
+        let max_block_size = self
+            .max_block_size
+            .map(|n| cmp::min(n.get(), max_write_data_length))
+            .unwrap_or(max_write_data_length);
+
+        let compression_level = self.compression_level.unwrap_or_default();
+
+        let deflater = if self.is_deterministic {
+            Deflater::Deterministic(u8::from(compression_level))
+        } else {
+            Deflater::Default(compression_level.into())
+        };
+
+        Writer {
+            inner: Some(self.inner),
+            buf: Vec::with_capacity(max_block_size),
+            deflater,
+            max_block_size,
+            worker_pool: self.worker_pool.map(|worker_pool| {
+                let tasks = TaskQueue::new(&worker_pool);
+                (worker_pool, tasks)
+            }),
+            observer: self.observer,
+            extra_subfields: extra_subfields.into(),
+            position: 0,
+            is_finished: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_with_max_block_size() {
+        let writer = Builder::new(Vec::new())
+            .set_max_block_size(NonZeroUsize::new(4096).unwrap())
+            .build();
+        assert_eq!(writer.max_block_size, 4096);
+
+        let writer = Builder::new(Vec::new())
+            .set_max_block_size(NonZeroUsize::new(1 << 16).unwrap())
+            .build();
+        assert_eq!(writer.max_block_size, block::MAX_WRITE_DATA_LENGTH);
+
+        let writer = Builder::new(Vec::new()).build();
+        assert_eq!(writer.max_block_size, block::MAX_WRITE_DATA_LENGTH);
+    }
+
+    #[test]
+    fn test_build_with_extra_subfields() -> std::io::Result<()> {
+        let writer = Builder::new(Vec::new())
+            .set_extra_subfields(vec![ExtraSubfield::new(*b"NR", vec![0; 12])?])
+            .build();
+
+        assert_eq!(&writer.extra_subfields[..4], [b'N', b'R', 0x0c, 0x00]);
+        assert_eq!(writer.max_block_size, block::MAX_WRITE_DATA_LENGTH - 16);
+
+        Ok(())
+    }
+}
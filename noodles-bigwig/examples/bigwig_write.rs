@@ -0,0 +1,71 @@
+//! Converts a bedGraph file to BigWig.
+//!
+//! The chromosome sizes file is a tab-delimited list of chromosome names and lengths, e.g., the
+//! first two columns of a FASTA index. Records in the bedGraph must be sorted in the order of the
+//! chromosome sizes.
+//!
+//! The result is similar to the output of `bedGraphToBigWig <src> <chrom.sizes> <dst>`.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader},
+};
+
+use noodles_bigwig as bigwig;
+
+fn invalid_data<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let chrom_sizes_src = args.next().expect("missing chrom.sizes");
+    let dst = args.next().expect("missing dst");
+
+    let mut chromosomes = Vec::new();
+
+    for result in File::open(chrom_sizes_src).map(BufReader::new)?.lines() {
+        let line = result?;
+        let mut fields = line.split('\t');
+
+        let name = fields.next().ok_or_else(|| invalid_data("missing name"))?;
+        let length = fields
+            .next()
+            .ok_or_else(|| invalid_data("missing length"))
+            .and_then(|s| s.parse().map_err(invalid_data))?;
+
+        chromosomes.push((name.to_string(), length));
+    }
+
+    let mut writer = File::create(dst).map(|f| bigwig::Writer::new(f, chromosomes))?;
+
+    for result in File::open(src).map(BufReader::new)?.lines() {
+        let line = result?;
+
+        if line.starts_with("track") || line.starts_with("browser") || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<_> = line.split('\t').collect();
+
+        if fields.len() != 4 {
+            return Err(invalid_data("invalid bedGraph record"));
+        }
+
+        let start = fields[1].parse().map_err(invalid_data)?;
+        let end = fields[2].parse().map_err(invalid_data)?;
+        let value = fields[3].parse().map_err(invalid_data)?;
+
+        writer.write_record(&bigwig::Record::new(fields[0], start, end, value))?;
+    }
+
+    writer.finish()?;
+
+    Ok(())
+}
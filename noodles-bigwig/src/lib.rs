@@ -0,0 +1,34 @@
+#![warn(missing_docs)]
+
+//! **noodles-bigwig** handles the writing of the BigWig format.
+//!
+//! BigWig is an indexed binary format for dense, continuous data, e.g., read depth or coverage,
+//! that genome browsers can display without loading the entire file. A BigWig file includes the
+//! data in compressed blocks; a chromosome B+ tree; an R-tree index over the blocks; and zoom
+//! levels, which are precomputed summaries of the data at increasingly coarser resolutions.
+//!
+//! # Examples
+//!
+//! ## Write coverage to a file
+//!
+//! ```no_run
+//! # use std::{fs::File, io};
+//! use noodles_bigwig as bigwig;
+//!
+//! let chromosomes = [("sq0", 8), ("sq1", 13)];
+//! let mut writer = File::create("out.bw").map(|f| bigwig::Writer::new(f, chromosomes))?;
+//!
+//! writer.write_record(&bigwig::Record::new("sq0", 0, 5, 2.0))?;
+//! writer.write_record(&bigwig::Record::new("sq0", 5, 8, 3.0))?;
+//! writer.write_record(&bigwig::Record::new("sq1", 2, 13, 1.0))?;
+//!
+//! writer.finish()?;
+//! # Ok::<(), io::Error>(())
+//! ```
+
+pub mod record;
+mod writer;
+
+pub use self::{record::Record, writer::Writer};
+
+pub(crate) const MAGIC_NUMBER: u32 = 0x888ffc26;
@@ -0,0 +1,87 @@
+//! BigWig record.
+
+/// A BigWig record.
+///
+/// A record is a value over an interval of a chromosome. Like a bedGraph record, the interval is
+/// 0-based and half-open.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    reference_sequence_name: String,
+    start: u32,
+    end: u32,
+    value: f32,
+}
+
+impl Record {
+    /// Creates a BigWig record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bigwig as bigwig;
+    /// let record = bigwig::Record::new("sq0", 8, 13, 0.75);
+    /// ```
+    pub fn new<N>(reference_sequence_name: N, start: u32, end: u32, value: f32) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            reference_sequence_name: reference_sequence_name.into(),
+            start,
+            end,
+            value,
+        }
+    }
+
+    /// Returns the reference sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bigwig as bigwig;
+    /// let record = bigwig::Record::new("sq0", 8, 13, 0.75);
+    /// assert_eq!(record.reference_sequence_name(), "sq0");
+    /// ```
+    pub fn reference_sequence_name(&self) -> &str {
+        &self.reference_sequence_name
+    }
+
+    /// Returns the 0-based start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bigwig as bigwig;
+    /// let record = bigwig::Record::new("sq0", 8, 13, 0.75);
+    /// assert_eq!(record.start(), 8);
+    /// ```
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// Returns the exclusive end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bigwig as bigwig;
+    /// let record = bigwig::Record::new("sq0", 8, 13, 0.75);
+    /// assert_eq!(record.end(), 13);
+    /// ```
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// Returns the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bigwig as bigwig;
+    /// let record = bigwig::Record::new("sq0", 8, 13, 0.75);
+    /// assert_eq!(record.value(), 0.75);
+    /// ```
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+}
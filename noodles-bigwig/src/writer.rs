@@ -0,0 +1,551 @@
+mod chromosome_tree;
+mod r_tree;
+mod zoom;
+
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use flate2::{write::ZlibEncoder, Compression};
+
+use self::{
+    chromosome_tree::write_chromosome_tree,
+    r_tree::{write_r_tree, Bounds, Entry},
+    zoom::{build_zoom_levels, summarize, ZoomRecord},
+};
+use super::{Record, MAGIC_NUMBER};
+
+const VERSION: u16 = 4;
+const HEADER_SIZE: u64 = 64;
+const ZOOM_HEADER_SIZE: u64 = 24;
+const TOTAL_SUMMARY_SIZE: u64 = 40;
+const ITEMS_PER_SLOT: usize = 1024;
+const ZOOM_RECORD_SIZE: usize = 32;
+
+// § "bigWig section": 1 = bedGraph, 2 = variableStep, 3 = fixedStep
+const SECTION_TYPE_BED_GRAPH: u8 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Item {
+    start: u32,
+    end: u32,
+    value: f32,
+}
+
+// A compressed data or zoom block and its bounds.
+struct Block {
+    bounds: Bounds,
+    data: Vec<u8>,
+    uncompressed_len: usize,
+}
+
+/// A BigWig writer.
+///
+/// Records are buffered in memory and must be written in order of the chromosome list, sorted by
+/// start position, and nonoverlapping. The file is written when the writer is finished (see
+/// [`Self::finish`]).
+pub struct Writer<W> {
+    inner: W,
+    chromosomes: Vec<(String, u32)>,
+    chromosome_ids: HashMap<String, usize>,
+    chromosome_items: Vec<Vec<Item>>,
+    last_position: Option<(usize, u32)>,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a BigWig writer.
+    ///
+    /// The chromosome list is a list of chromosome names and lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bigwig as bigwig;
+    /// let writer = bigwig::Writer::new(Vec::new(), [("sq0", 8), ("sq1", 13)]);
+    /// ```
+    pub fn new<I, N>(inner: W, chromosomes: I) -> Self
+    where
+        I: IntoIterator<Item = (N, u32)>,
+        N: Into<String>,
+    {
+        let chromosomes: Vec<(String, u32)> = chromosomes
+            .into_iter()
+            .map(|(name, length)| (name.into(), length))
+            .collect();
+
+        let chromosome_ids = chromosomes
+            .iter()
+            .enumerate()
+            .map(|(id, (name, _))| (name.clone(), id))
+            .collect();
+
+        let chromosome_items = vec![Vec::new(); chromosomes.len()];
+
+        Self {
+            inner,
+            chromosomes,
+            chromosome_ids,
+            chromosome_items,
+            last_position: None,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bigwig as bigwig;
+    /// let writer = bigwig::Writer::new(Vec::new(), [("sq0", 8)]);
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Writes a BigWig record.
+    ///
+    /// The record is buffered until the writer is finished.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the record's chromosome is not in the chromosome list; the
+    /// interval is empty or out of bounds; or the record is out of order or overlaps the
+    /// previous record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bigwig as bigwig;
+    /// let mut writer = bigwig::Writer::new(Vec::new(), [("sq0", 8)]);
+    /// writer.write_record(&bigwig::Record::new("sq0", 2, 5, 0.75))?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        let id = self
+            .chromosome_ids
+            .get(record.reference_sequence_name())
+            .copied()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "invalid reference sequence name: {}",
+                        record.reference_sequence_name()
+                    ),
+                )
+            })?;
+
+        let (_, length) = self.chromosomes[id];
+
+        if record.start() >= record.end() || record.end() > length {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid interval: {}:{}-{}",
+                    record.reference_sequence_name(),
+                    record.start(),
+                    record.end()
+                ),
+            ));
+        }
+
+        if let Some(last_position) = self.last_position {
+            if (id, record.start()) < last_position {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "records are not sorted or overlap",
+                ));
+            }
+        }
+
+        self.chromosome_items[id].push(Item {
+            start: record.start(),
+            end: record.end(),
+            value: record.value(),
+        });
+
+        self.last_position = Some((id, record.end()));
+
+        Ok(())
+    }
+
+    /// Writes the BigWig file and returns the underlying writer.
+    ///
+    /// This writes the header, total summary, chromosome B+ tree, data blocks and their R-tree
+    /// index, and zoom levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bigwig as bigwig;
+    ///
+    /// let mut writer = bigwig::Writer::new(Vec::new(), [("sq0", 8)]);
+    /// writer.write_record(&bigwig::Record::new("sq0", 2, 5, 0.75))?;
+    /// let data = writer.finish()?;
+    ///
+    /// assert_eq!(&data[..4], [0x26, 0xfc, 0x8f, 0x88]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(mut self) -> io::Result<W> {
+        let total_summary = summarize(&self.chromosome_items);
+
+        let data_blocks = build_data_blocks(&self.chromosome_items)?;
+
+        let max_chromosome_length = self
+            .chromosomes
+            .iter()
+            .map(|(_, length)| *length)
+            .max()
+            .unwrap_or(0);
+
+        let zoom_levels = build_zoom_levels(&self.chromosome_items, max_chromosome_length)
+            .into_iter()
+            .map(|(reduction, records)| {
+                build_zoom_blocks(&records).map(|blocks| (reduction, blocks))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let zoom_level_count = u16::try_from(zoom_levels.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let uncompress_buf_size = data_blocks
+            .iter()
+            .chain(zoom_levels.iter().flat_map(|(_, blocks)| blocks))
+            .map(|block| block.uncompressed_len)
+            .max()
+            .unwrap_or(0);
+
+        let uncompress_buf_size = u32::try_from(uncompress_buf_size)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        // Everything after the zoom headers is built first to resolve the offsets in the header.
+        let total_summary_offset = HEADER_SIZE + ZOOM_HEADER_SIZE * u64::from(zoom_level_count);
+        let chromosome_tree_offset = total_summary_offset + TOTAL_SUMMARY_SIZE;
+
+        let mut body = Vec::new();
+        write_chromosome_tree(&mut body, &self.chromosomes, chromosome_tree_offset)?;
+
+        let full_data_offset = chromosome_tree_offset + body.len() as u64;
+        body.write_u64::<LittleEndian>(data_blocks.len() as u64)?;
+        let full_index_offset =
+            write_blocks_and_index(&mut body, &data_blocks, chromosome_tree_offset)?;
+
+        let mut zoom_headers = Vec::with_capacity(zoom_levels.len());
+
+        for (reduction, blocks) in &zoom_levels {
+            let data_offset = chromosome_tree_offset + body.len() as u64;
+            let record_count: usize = blocks
+                .iter()
+                .map(|block| block.uncompressed_len / ZOOM_RECORD_SIZE)
+                .sum();
+            let record_count = u32::try_from(record_count)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            body.write_u32::<LittleEndian>(record_count)?;
+            let index_offset = write_blocks_and_index(&mut body, blocks, chromosome_tree_offset)?;
+            zoom_headers.push((*reduction, data_offset, index_offset));
+        }
+
+        let writer = &mut self.inner;
+
+        // header
+        writer.write_u32::<LittleEndian>(MAGIC_NUMBER)?;
+        writer.write_u16::<LittleEndian>(VERSION)?;
+        writer.write_u16::<LittleEndian>(zoom_level_count)?;
+        writer.write_u64::<LittleEndian>(chromosome_tree_offset)?;
+        writer.write_u64::<LittleEndian>(full_data_offset)?;
+        writer.write_u64::<LittleEndian>(full_index_offset)?;
+        writer.write_u16::<LittleEndian>(0)?; // fieldCount
+        writer.write_u16::<LittleEndian>(0)?; // definedFieldCount
+        writer.write_u64::<LittleEndian>(0)?; // autoSqlOffset
+        writer.write_u64::<LittleEndian>(total_summary_offset)?;
+        writer.write_u32::<LittleEndian>(uncompress_buf_size)?;
+        writer.write_u64::<LittleEndian>(0)?; // extensionOffset
+
+        for (reduction, data_offset, index_offset) in zoom_headers {
+            writer.write_u32::<LittleEndian>(reduction)?;
+            writer.write_u32::<LittleEndian>(0)?;
+            writer.write_u64::<LittleEndian>(data_offset)?;
+            writer.write_u64::<LittleEndian>(index_offset)?;
+        }
+
+        total_summary.write(writer)?;
+
+        writer.write_all(&body)?;
+        writer.write_u32::<LittleEndian>(MAGIC_NUMBER)?;
+
+        writer.flush()?;
+
+        Ok(self.inner)
+    }
+}
+
+// Writes the blocks followed by their R-tree index and returns the offset of the index.
+//
+// `base_offset` is the file offset of the start of `buf`.
+fn write_blocks_and_index(
+    buf: &mut Vec<u8>,
+    blocks: &[Block],
+    base_offset: u64,
+) -> io::Result<u64> {
+    let mut entries = Vec::with_capacity(blocks.len());
+
+    for block in blocks {
+        entries.push(Entry {
+            bounds: block.bounds,
+            offset: base_offset + buf.len() as u64,
+            size: block.data.len() as u64,
+        });
+
+        buf.extend(&block.data);
+    }
+
+    let index_offset = base_offset + buf.len() as u64;
+    write_r_tree(
+        buf,
+        &entries,
+        ITEMS_PER_SLOT as u32,
+        index_offset,
+        index_offset,
+    )?;
+
+    Ok(index_offset)
+}
+
+fn build_data_blocks(chromosome_items: &[Vec<Item>]) -> io::Result<Vec<Block>> {
+    let mut blocks = Vec::new();
+
+    for (id, items) in chromosome_items.iter().enumerate() {
+        let id = id as u32;
+
+        for chunk in items.chunks(ITEMS_PER_SLOT) {
+            let start = chunk[0].start;
+            let end = chunk.iter().map(|item| item.end).max().unwrap_or(start);
+
+            let mut buf = Vec::new();
+
+            buf.write_u32::<LittleEndian>(id)?;
+            buf.write_u32::<LittleEndian>(start)?;
+            buf.write_u32::<LittleEndian>(end)?;
+            buf.write_u32::<LittleEndian>(0)?; // itemStep
+            buf.write_u32::<LittleEndian>(0)?; // itemSpan
+            buf.write_u8(SECTION_TYPE_BED_GRAPH)?;
+            buf.write_u8(0)?; // reserved
+            buf.write_u16::<LittleEndian>(chunk.len() as u16)?;
+
+            for item in chunk {
+                buf.write_u32::<LittleEndian>(item.start)?;
+                buf.write_u32::<LittleEndian>(item.end)?;
+                buf.write_f32::<LittleEndian>(item.value)?;
+            }
+
+            blocks.push(build_block(id, start, end, &buf)?);
+        }
+    }
+
+    Ok(blocks)
+}
+
+fn build_zoom_blocks(chromosome_records: &[Vec<ZoomRecord>]) -> io::Result<Vec<Block>> {
+    let mut blocks = Vec::new();
+
+    for records in chromosome_records {
+        for chunk in records.chunks(ITEMS_PER_SLOT) {
+            let first = &chunk[0];
+            let end = chunk
+                .iter()
+                .map(|record| record.end)
+                .max()
+                .unwrap_or(first.start);
+
+            let mut buf = Vec::with_capacity(chunk.len() * ZOOM_RECORD_SIZE);
+
+            for record in chunk {
+                record.write(&mut buf)?;
+            }
+
+            blocks.push(build_block(first.chromosome_id, first.start, end, &buf)?);
+        }
+    }
+
+    Ok(blocks)
+}
+
+fn build_block(chromosome_id: u32, start: u32, end: u32, buf: &[u8]) -> io::Result<Block> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(buf)?;
+    let data = encoder.finish()?;
+
+    Ok(Block {
+        bounds: Bounds {
+            start_chromosome_id: chromosome_id,
+            start,
+            end_chromosome_id: chromosome_id,
+            end,
+        },
+        data,
+        uncompressed_len: buf.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::ZlibDecoder;
+
+    use super::*;
+
+    fn read_u16(buf: &[u8], i: usize) -> u16 {
+        u16::from_le_bytes(buf[i..i + 2].try_into().unwrap())
+    }
+
+    fn read_u32(buf: &[u8], i: usize) -> u32 {
+        u32::from_le_bytes(buf[i..i + 4].try_into().unwrap())
+    }
+
+    fn read_u64(buf: &[u8], i: usize) -> u64 {
+        u64::from_le_bytes(buf[i..i + 8].try_into().unwrap())
+    }
+
+    fn read_f32(buf: &[u8], i: usize) -> f32 {
+        f32::from_le_bytes(buf[i..i + 4].try_into().unwrap())
+    }
+
+    fn read_f64(buf: &[u8], i: usize) -> f64 {
+        f64::from_le_bytes(buf[i..i + 8].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_write_record() {
+        let mut writer = Writer::new(Vec::new(), [("sq0", 8), ("sq1", 13)]);
+
+        assert!(writer.write_record(&Record::new("sq2", 0, 5, 1.0)).is_err());
+        assert!(writer.write_record(&Record::new("sq0", 5, 5, 1.0)).is_err());
+        assert!(writer.write_record(&Record::new("sq0", 5, 9, 1.0)).is_err());
+
+        assert!(writer.write_record(&Record::new("sq0", 2, 5, 1.0)).is_ok());
+        assert!(writer.write_record(&Record::new("sq0", 4, 6, 1.0)).is_err());
+        assert!(writer.write_record(&Record::new("sq0", 5, 6, 1.0)).is_ok());
+        assert!(writer.write_record(&Record::new("sq1", 0, 1, 1.0)).is_ok());
+        assert!(writer.write_record(&Record::new("sq0", 6, 7, 1.0)).is_err());
+    }
+
+    #[test]
+    fn test_finish() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new(), [("sq0", 8), ("sq1", 13)]);
+        writer.write_record(&Record::new("sq0", 0, 5, 2.0))?;
+        writer.write_record(&Record::new("sq0", 5, 8, 3.0))?;
+        writer.write_record(&Record::new("sq1", 2, 13, 1.0))?;
+        let buf = writer.finish()?;
+
+        // header
+        assert_eq!(read_u32(&buf, 0), MAGIC_NUMBER);
+        assert_eq!(read_u16(&buf, 4), VERSION);
+
+        let zoom_level_count = u64::from(read_u16(&buf, 6));
+        let chromosome_tree_offset = read_u64(&buf, 8) as usize;
+        let full_data_offset = read_u64(&buf, 16) as usize;
+        let full_index_offset = read_u64(&buf, 24) as usize;
+        let total_summary_offset = read_u64(&buf, 44) as usize;
+        let uncompress_buf_size = read_u32(&buf, 52) as usize;
+
+        assert_eq!(
+            total_summary_offset as u64,
+            HEADER_SIZE + ZOOM_HEADER_SIZE * zoom_level_count
+        );
+        assert_eq!(uncompress_buf_size, 24 + 2 * 12);
+
+        // total summary
+        assert_eq!(read_u64(&buf, total_summary_offset), 19);
+        assert_eq!(read_f64(&buf, total_summary_offset + 8), 1.0);
+        assert_eq!(read_f64(&buf, total_summary_offset + 16), 3.0);
+        assert_eq!(read_f64(&buf, total_summary_offset + 24), 30.0);
+        assert_eq!(read_f64(&buf, total_summary_offset + 32), 58.0);
+
+        // chromosome tree
+        assert_eq!(read_u32(&buf, chromosome_tree_offset), 0x78ca8c91);
+
+        // data
+        assert_eq!(read_u64(&buf, full_data_offset), 2);
+
+        // index
+        let index = &buf[full_index_offset..];
+        assert_eq!(read_u32(index, 0), 0x2468ace0);
+        assert_eq!(read_u64(index, 8), 2);
+
+        let leaf = &index[48..];
+        assert_eq!(&leaf[..4], [1, 0, 2, 0]);
+        assert_eq!(read_u32(leaf, 4), 0); // startChromIx
+        assert_eq!(read_u32(leaf, 8), 0); // startBase
+        assert_eq!(read_u32(leaf, 16), 8); // endBase
+
+        let data_offset = read_u64(leaf, 20) as usize;
+        let data_size = read_u64(leaf, 28) as usize;
+        assert_eq!(data_offset, full_data_offset + 8);
+
+        let mut decoder = ZlibDecoder::new(&buf[data_offset..data_offset + data_size]);
+        let mut section = Vec::new();
+        decoder.read_to_end(&mut section)?;
+
+        assert_eq!(read_u32(&section, 0), 0); // chromId
+        assert_eq!(read_u32(&section, 4), 0); // chromStart
+        assert_eq!(read_u32(&section, 8), 8); // chromEnd
+        assert_eq!(section[20], SECTION_TYPE_BED_GRAPH);
+        assert_eq!(read_u16(&section, 22), 2); // itemCount
+        assert_eq!(read_u32(&section, 24), 0);
+        assert_eq!(read_u32(&section, 28), 5);
+        assert_eq!(read_f32(&section, 32), 2.0);
+        assert_eq!(read_u32(&section, 36), 5);
+        assert_eq!(read_u32(&section, 40), 8);
+        assert_eq!(read_f32(&section, 44), 3.0);
+
+        // trailer
+        assert_eq!(read_u32(&buf, buf.len() - 4), MAGIC_NUMBER);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_finish_with_zoom_levels() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new(), [("sq0", 4000)]);
+
+        for i in 0..4000 {
+            writer.write_record(&Record::new("sq0", i, i + 1, 1.0))?;
+        }
+
+        let buf = writer.finish()?;
+
+        let zoom_level_count = read_u16(&buf, 6) as usize;
+        assert_eq!(zoom_level_count, 5);
+
+        let mut previous_index_offset = 0;
+
+        let expected_record_counts = [400, 100, 25, 7, 2];
+
+        for (i, expected_record_count) in expected_record_counts.into_iter().enumerate() {
+            let zoom_header = &buf[64 + i * 24..];
+            let reduction = read_u32(zoom_header, 0);
+            let data_offset = read_u64(zoom_header, 8) as usize;
+            let index_offset = read_u64(zoom_header, 16) as usize;
+
+            assert_eq!(reduction, 10 * 4u32.pow(i as u32));
+            assert!(data_offset > previous_index_offset);
+            assert_eq!(read_u32(&buf, index_offset), 0x2468ace0);
+
+            let record_count = read_u32(&buf, data_offset);
+            assert_eq!(record_count, expected_record_count);
+
+            previous_index_offset = index_offset;
+        }
+
+        Ok(())
+    }
+}
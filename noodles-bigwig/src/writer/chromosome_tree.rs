@@ -0,0 +1,235 @@
+//! BigWig chromosome B+ tree.
+
+use std::{
+    io::{self, Write},
+    ops::Range,
+};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+const MAGIC_NUMBER: u32 = 0x78ca8c91;
+const HEADER_SIZE: u64 = 32;
+const NODE_HEADER_SIZE: u64 = 4;
+const VALUE_SIZE: u32 = 8;
+const MAX_BLOCK_SIZE: usize = 256;
+
+/// Writes a chromosome B+ tree.
+///
+/// Each chromosome is given as a name and length, and its ID is its position in the list. The
+/// tree is written as if it starts at `offset`, which is used to compute the child node offsets.
+pub(super) fn write_chromosome_tree<W>(
+    writer: &mut W,
+    chromosomes: &[(String, u32)],
+    offset: u64,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    // Keys are searched lexicographically, so the items are sorted by name. The chromosome IDs
+    // remain in input order.
+    let mut items: Vec<_> = chromosomes
+        .iter()
+        .enumerate()
+        .map(|(id, (name, length))| (name.as_bytes(), id, *length))
+        .collect();
+
+    items.sort_unstable_by(|a, b| a.0.cmp(b.0));
+
+    let key_size = items
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let block_size = items.len().clamp(1, MAX_BLOCK_SIZE);
+    let item_size = u64::try_from(key_size).map_err(invalid_input)? + u64::from(VALUE_SIZE);
+
+    writer.write_u32::<LittleEndian>(MAGIC_NUMBER)?;
+    write_u32(writer, block_size)?;
+    write_u32(writer, key_size)?;
+    writer.write_u32::<LittleEndian>(VALUE_SIZE)?;
+    writer.write_u64::<LittleEndian>(items.len() as u64)?;
+    writer.write_u64::<LittleEndian>(0)?;
+
+    // levels[0] are the leaf nodes, and the last level is the root. Each node is a range of
+    // indices into the level below.
+    let mut levels = vec![chunk_ranges(items.len(), block_size)];
+
+    while levels[levels.len() - 1].len() > 1 {
+        let n = levels[levels.len() - 1].len();
+        levels.push(chunk_ranges(n, block_size));
+    }
+
+    let mut level_offset = offset + HEADER_SIZE;
+
+    for (depth, level) in levels.iter().enumerate().rev() {
+        let level_size = level_size(level, item_size);
+        let next_level_offset = level_offset + level_size;
+
+        if depth == 0 {
+            for range in level {
+                write_node_header(writer, true, range.len())?;
+
+                for &(name, id, length) in &items[range.clone()] {
+                    write_key(writer, name, key_size)?;
+                    write_u32(writer, id)?;
+                    writer.write_u32::<LittleEndian>(length)?;
+                }
+            }
+        } else {
+            let children = &levels[depth - 1];
+            let mut child_offset = next_level_offset;
+            let mut child_offsets = Vec::with_capacity(children.len());
+
+            for child in children {
+                child_offsets.push(child_offset);
+                child_offset += NODE_HEADER_SIZE + child.len() as u64 * item_size;
+            }
+
+            for range in level {
+                write_node_header(writer, false, range.len())?;
+
+                for i in range.clone() {
+                    let first_item = first_leaf_item(&levels, depth - 1, i);
+                    write_key(writer, items[first_item].0, key_size)?;
+                    writer.write_u64::<LittleEndian>(child_offsets[i])?;
+                }
+            }
+        }
+
+        level_offset = next_level_offset;
+    }
+
+    Ok(())
+}
+
+fn chunk_ranges(n: usize, block_size: usize) -> Vec<Range<usize>> {
+    if n == 0 {
+        return std::iter::once(0..0).collect();
+    }
+
+    (0..n)
+        .step_by(block_size)
+        .map(|start| start..(start + block_size).min(n))
+        .collect()
+}
+
+fn level_size(level: &[Range<usize>], item_size: u64) -> u64 {
+    level
+        .iter()
+        .map(|range| NODE_HEADER_SIZE + range.len() as u64 * item_size)
+        .sum()
+}
+
+// Returns the index of the first leaf item under node `i` at the given depth.
+fn first_leaf_item(levels: &[Vec<Range<usize>>], depth: usize, i: usize) -> usize {
+    let mut j = levels[depth][i].start;
+
+    for level in levels[..depth].iter().rev() {
+        j = level[j].start;
+    }
+
+    j
+}
+
+fn write_node_header<W>(writer: &mut W, is_leaf: bool, count: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u8(u8::from(is_leaf))?;
+    writer.write_u8(0)?;
+    let count = u16::try_from(count).map_err(invalid_input)?;
+    writer.write_u16::<LittleEndian>(count)
+}
+
+fn write_key<W>(writer: &mut W, name: &[u8], key_size: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_all(name)?;
+
+    for _ in name.len()..key_size {
+        writer.write_u8(0)?;
+    }
+
+    Ok(())
+}
+
+fn write_u32<W>(writer: &mut W, n: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    let n = u32::try_from(n).map_err(invalid_input)?;
+    writer.write_u32::<LittleEndian>(n)
+}
+
+fn invalid_input<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidInput, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_chromosome_tree() -> io::Result<()> {
+        let chromosomes = [(String::from("sq1"), 13), (String::from("sq0"), 8)];
+
+        let mut buf = Vec::new();
+        write_chromosome_tree(&mut buf, &chromosomes, 0)?;
+
+        let mut expected = Vec::new();
+        // header
+        expected.extend(MAGIC_NUMBER.to_le_bytes());
+        expected.extend(2u32.to_le_bytes()); // blockSize
+        expected.extend(3u32.to_le_bytes()); // keySize
+        expected.extend(8u32.to_le_bytes()); // valSize
+        expected.extend(2u64.to_le_bytes()); // itemCount
+        expected.extend(0u64.to_le_bytes()); // reserved
+
+        // leaf node
+        expected.extend([1, 0]);
+        expected.extend(2u16.to_le_bytes());
+        expected.extend(b"sq0");
+        expected.extend(1u32.to_le_bytes());
+        expected.extend(8u32.to_le_bytes());
+        expected.extend(b"sq1");
+        expected.extend(0u32.to_le_bytes());
+        expected.extend(13u32.to_le_bytes());
+
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_chromosome_tree_with_multiple_levels() -> io::Result<()> {
+        let chromosomes: Vec<_> = (0..300).map(|i| (format!("sq{:03}", i), 8)).collect();
+
+        let mut buf = Vec::new();
+        write_chromosome_tree(&mut buf, &chromosomes, 0)?;
+
+        let item_size = 5 + 8;
+
+        // root node
+        let root = &buf[32..];
+        assert_eq!(&root[..4], [0, 0, 2, 0]);
+
+        let child_offset = |i: usize| {
+            let start = 4 + i * item_size + 5;
+            let data = root[start..start + 8].try_into().unwrap();
+            u64::from_le_bytes(data) as usize
+        };
+
+        assert_eq!(&root[4..9], b"sq000");
+        assert_eq!(&root[4 + item_size..4 + item_size + 5], b"sq256");
+
+        let leaf = &buf[child_offset(1)..];
+        assert_eq!(&leaf[..4], [1, 0, 44, 0]);
+        assert_eq!(&leaf[4..9], b"sq256");
+
+        Ok(())
+    }
+}
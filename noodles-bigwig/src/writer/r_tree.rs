@@ -0,0 +1,275 @@
+//! BigWig R-tree index.
+
+use std::{
+    io::{self, Write},
+    ops::Range,
+};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+const MAGIC_NUMBER: u32 = 0x2468ace0;
+const HEADER_SIZE: u64 = 48;
+const NODE_HEADER_SIZE: u64 = 4;
+const LEAF_ITEM_SIZE: u64 = 32;
+const NON_LEAF_ITEM_SIZE: u64 = 24;
+const BLOCK_SIZE: usize = 256;
+
+/// The bounds of an index entry.
+///
+/// This is the start chromosome ID, start position, end chromosome ID, and end position.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(super) struct Bounds {
+    pub start_chromosome_id: u32,
+    pub start: u32,
+    pub end_chromosome_id: u32,
+    pub end: u32,
+}
+
+impl Bounds {
+    fn union(self, other: Self) -> Self {
+        let (start_chromosome_id, start) =
+            (self.start_chromosome_id, self.start).min((other.start_chromosome_id, other.start));
+        let (end_chromosome_id, end) =
+            (self.end_chromosome_id, self.end).max((other.end_chromosome_id, other.end));
+
+        Self {
+            start_chromosome_id,
+            start,
+            end_chromosome_id,
+            end,
+        }
+    }
+}
+
+/// An R-tree index entry, i.e., the bounds and location of a data block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) struct Entry {
+    pub bounds: Bounds,
+    pub offset: u64,
+    pub size: u64,
+}
+
+/// Writes an R-tree index over the given entries.
+///
+/// `end_file_offset` is the position at the end of the indexed data. The tree is written as if
+/// it starts at `offset`, which is used to compute the child node offsets.
+pub(super) fn write_r_tree<W>(
+    writer: &mut W,
+    entries: &[Entry],
+    items_per_slot: u32,
+    end_file_offset: u64,
+    offset: u64,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let bounds = entries
+        .iter()
+        .map(|entry| entry.bounds)
+        .reduce(Bounds::union)
+        .unwrap_or_default();
+
+    writer.write_u32::<LittleEndian>(MAGIC_NUMBER)?;
+    writer.write_u32::<LittleEndian>(BLOCK_SIZE as u32)?;
+    writer.write_u64::<LittleEndian>(entries.len() as u64)?;
+    write_bounds(writer, bounds)?;
+    writer.write_u64::<LittleEndian>(end_file_offset)?;
+    writer.write_u32::<LittleEndian>(items_per_slot)?;
+    writer.write_u32::<LittleEndian>(0)?;
+
+    // levels[0] are the leaf nodes, and the last level is the root. Each node is a range of
+    // indices into the level below and its bounds.
+    let leaf_bounds: Vec<_> = entries.iter().map(|entry| entry.bounds).collect();
+    let mut levels = vec![build_level(&leaf_bounds)];
+
+    while levels[levels.len() - 1].len() > 1 {
+        let child_bounds: Vec<_> = levels[levels.len() - 1]
+            .iter()
+            .map(|(_, bounds)| *bounds)
+            .collect();
+
+        levels.push(build_level(&child_bounds));
+    }
+
+    let mut level_offset = offset + HEADER_SIZE;
+
+    for (depth, level) in levels.iter().enumerate().rev() {
+        let level_size = level_size(level, depth == 0);
+        let next_level_offset = level_offset + level_size;
+
+        if depth == 0 {
+            for (range, _) in level {
+                write_node_header(writer, true, range.len())?;
+
+                for entry in &entries[range.clone()] {
+                    write_bounds(writer, entry.bounds)?;
+                    writer.write_u64::<LittleEndian>(entry.offset)?;
+                    writer.write_u64::<LittleEndian>(entry.size)?;
+                }
+            }
+        } else {
+            let children = &levels[depth - 1];
+            let is_leaf = depth - 1 == 0;
+            let mut child_offset = next_level_offset;
+
+            for (range, _) in level {
+                write_node_header(writer, false, range.len())?;
+
+                for (child_range, child_bounds) in &children[range.clone()] {
+                    write_bounds(writer, *child_bounds)?;
+                    writer.write_u64::<LittleEndian>(child_offset)?;
+                    child_offset += node_size(child_range.len(), is_leaf);
+                }
+            }
+        }
+
+        level_offset = next_level_offset;
+    }
+
+    Ok(())
+}
+
+fn build_level(bounds: &[Bounds]) -> Vec<(Range<usize>, Bounds)> {
+    if bounds.is_empty() {
+        return vec![(0..0, Bounds::default())];
+    }
+
+    (0..bounds.len())
+        .step_by(BLOCK_SIZE)
+        .map(|start| {
+            let end = (start + BLOCK_SIZE).min(bounds.len());
+
+            let node_bounds = bounds[start..end]
+                .iter()
+                .copied()
+                .reduce(Bounds::union)
+                .unwrap_or_default();
+
+            (start..end, node_bounds)
+        })
+        .collect()
+}
+
+fn node_size(count: usize, is_leaf: bool) -> u64 {
+    let item_size = if is_leaf {
+        LEAF_ITEM_SIZE
+    } else {
+        NON_LEAF_ITEM_SIZE
+    };
+
+    NODE_HEADER_SIZE + count as u64 * item_size
+}
+
+fn level_size(level: &[(Range<usize>, Bounds)], is_leaf: bool) -> u64 {
+    level
+        .iter()
+        .map(|(range, _)| node_size(range.len(), is_leaf))
+        .sum()
+}
+
+fn write_node_header<W>(writer: &mut W, is_leaf: bool, count: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u8(u8::from(is_leaf))?;
+    writer.write_u8(0)?;
+    let count = u16::try_from(count).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    writer.write_u16::<LittleEndian>(count)
+}
+
+fn write_bounds<W>(writer: &mut W, bounds: Bounds) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u32::<LittleEndian>(bounds.start_chromosome_id)?;
+    writer.write_u32::<LittleEndian>(bounds.start)?;
+    writer.write_u32::<LittleEndian>(bounds.end_chromosome_id)?;
+    writer.write_u32::<LittleEndian>(bounds.end)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_entry(chromosome_id: u32, start: u32, end: u32, offset: u64) -> Entry {
+        Entry {
+            bounds: Bounds {
+                start_chromosome_id: chromosome_id,
+                start,
+                end_chromosome_id: chromosome_id,
+                end,
+            },
+            offset,
+            size: 8,
+        }
+    }
+
+    #[test]
+    fn test_write_r_tree() -> io::Result<()> {
+        let entries = [build_entry(0, 8, 13, 100), build_entry(1, 2, 5, 108)];
+
+        let mut buf = Vec::new();
+        write_r_tree(&mut buf, &entries, 1024, 116, 116)?;
+
+        let mut expected = Vec::new();
+        // header
+        expected.extend(MAGIC_NUMBER.to_le_bytes());
+        expected.extend(256u32.to_le_bytes()); // blockSize
+        expected.extend(2u64.to_le_bytes()); // itemCount
+        expected.extend(0u32.to_le_bytes()); // startChromIx
+        expected.extend(8u32.to_le_bytes()); // startBase
+        expected.extend(1u32.to_le_bytes()); // endChromIx
+        expected.extend(5u32.to_le_bytes()); // endBase
+        expected.extend(116u64.to_le_bytes()); // endFileOffset
+        expected.extend(1024u32.to_le_bytes()); // itemsPerSlot
+        expected.extend(0u32.to_le_bytes()); // reserved
+
+        // leaf node
+        expected.extend([1, 0]);
+        expected.extend(2u16.to_le_bytes());
+
+        for (chromosome_id, start, end, offset) in [(0u32, 8u32, 13u32, 100u64), (1, 2, 5, 108)] {
+            expected.extend(chromosome_id.to_le_bytes());
+            expected.extend(start.to_le_bytes());
+            expected.extend(chromosome_id.to_le_bytes());
+            expected.extend(end.to_le_bytes());
+            expected.extend(offset.to_le_bytes());
+            expected.extend(8u64.to_le_bytes());
+        }
+
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_r_tree_with_multiple_levels() -> io::Result<()> {
+        let entries: Vec<_> = (0..300)
+            .map(|i| build_entry(0, i * 10, i * 10 + 10, u64::from(i) * 8))
+            .collect();
+
+        let offset = 2400;
+        let mut buf = Vec::new();
+        write_r_tree(&mut buf, &entries, 1024, offset, offset)?;
+
+        let read_u32 = |buf: &[u8], i: usize| u32::from_le_bytes(buf[i..i + 4].try_into().unwrap());
+        let read_u64 = |buf: &[u8], i: usize| u64::from_le_bytes(buf[i..i + 8].try_into().unwrap());
+
+        // root node
+        let root = &buf[48..];
+        assert_eq!(&root[..4], [0, 0, 2, 0]);
+        assert_eq!(read_u32(root, 4 + 4), 0); // startBase
+        assert_eq!(read_u32(root, 4 + 12), 2560); // endBase
+
+        let child_offset = read_u64(root, 4 + NON_LEAF_ITEM_SIZE as usize + 16);
+        assert_eq!(read_u32(root, 4 + NON_LEAF_ITEM_SIZE as usize + 4), 2560);
+
+        let leaf = &buf[(child_offset - offset) as usize..];
+        assert_eq!(&leaf[..4], [1, 0, 44, 0]);
+        assert_eq!(read_u32(leaf, 4 + 4), 2560);
+        assert_eq!(read_u64(leaf, 4 + 16), 256 * 8);
+
+        Ok(())
+    }
+}
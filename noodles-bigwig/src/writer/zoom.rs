@@ -0,0 +1,262 @@
+//! BigWig summaries and zoom levels.
+
+use std::io::{self, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use super::Item;
+
+const MAX_ZOOM_LEVEL_COUNT: usize = 10;
+const INITIAL_REDUCTION_FACTOR: u64 = 10;
+const REDUCTION_INCREMENT: u64 = 4;
+
+/// A summary of values.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(super) struct Summary {
+    pub bases_covered: u64,
+    pub min: f64,
+    pub max: f64,
+    pub sum: f64,
+    pub sum_squares: f64,
+}
+
+impl Summary {
+    fn add(&mut self, len: u32, value: f32) {
+        let len = u64::from(len);
+        let value = f64::from(value);
+
+        if self.bases_covered == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+
+        self.bases_covered += len;
+        self.sum += value * len as f64;
+        self.sum_squares += value * value * len as f64;
+    }
+
+    /// Writes the total summary.
+    pub fn write<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writer.write_u64::<LittleEndian>(self.bases_covered)?;
+        writer.write_f64::<LittleEndian>(self.min)?;
+        writer.write_f64::<LittleEndian>(self.max)?;
+        writer.write_f64::<LittleEndian>(self.sum)?;
+        writer.write_f64::<LittleEndian>(self.sum_squares)?;
+        Ok(())
+    }
+}
+
+/// Summarizes all items.
+pub(super) fn summarize(chromosome_items: &[Vec<Item>]) -> Summary {
+    let mut summary = Summary::default();
+
+    for item in chromosome_items.iter().flatten() {
+        summary.add(item.end - item.start, item.value);
+    }
+
+    summary
+}
+
+/// A zoom record, i.e., a summary of the items in a bin of a chromosome.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct ZoomRecord {
+    pub chromosome_id: u32,
+    pub start: u32,
+    pub end: u32,
+    pub summary: Summary,
+}
+
+impl ZoomRecord {
+    pub fn write<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let valid_count = u32::try_from(self.summary.bases_covered)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        writer.write_u32::<LittleEndian>(self.chromosome_id)?;
+        writer.write_u32::<LittleEndian>(self.start)?;
+        writer.write_u32::<LittleEndian>(self.end)?;
+        writer.write_u32::<LittleEndian>(valid_count)?;
+        writer.write_f32::<LittleEndian>(self.summary.min as f32)?;
+        writer.write_f32::<LittleEndian>(self.summary.max as f32)?;
+        writer.write_f32::<LittleEndian>(self.summary.sum as f32)?;
+        writer.write_f32::<LittleEndian>(self.summary.sum_squares as f32)?;
+        Ok(())
+    }
+}
+
+/// Reduces the items of each chromosome into bins of the given size.
+///
+/// Items that span multiple bins are split, and each bin only summarizes the bases it covers.
+pub(super) fn reduce(chromosome_items: &[Vec<Item>], reduction: u32) -> Vec<Vec<ZoomRecord>> {
+    chromosome_items
+        .iter()
+        .enumerate()
+        .map(|(chromosome_id, items)| {
+            let mut records: Vec<ZoomRecord> = Vec::new();
+
+            for item in items {
+                let mut start = item.start;
+
+                while start < item.end {
+                    let bin = start / reduction;
+                    let bin_end =
+                        (u64::from(bin + 1) * u64::from(reduction)).min(u64::from(u32::MAX)) as u32;
+                    let end = item.end.min(bin_end);
+
+                    match records.last_mut() {
+                        Some(record) if record.start / reduction == bin => {
+                            record.end = end;
+                            record.summary.add(end - start, item.value);
+                        }
+                        _ => {
+                            let mut summary = Summary::default();
+                            summary.add(end - start, item.value);
+
+                            records.push(ZoomRecord {
+                                chromosome_id: chromosome_id as u32,
+                                start,
+                                end,
+                                summary,
+                            });
+                        }
+                    }
+
+                    start = end;
+                }
+            }
+
+            records
+        })
+        .collect()
+}
+
+/// Builds the zoom levels.
+///
+/// The first reduction is 10 times the average item span and increases by a factor of 4 for
+/// each subsequent level. Zoom levels stop being added when a level no longer halves the number
+/// of records of the previous level or the reduction exceeds the longest chromosome.
+pub(super) fn build_zoom_levels(
+    chromosome_items: &[Vec<Item>],
+    max_chromosome_length: u32,
+) -> Vec<(u32, Vec<Vec<ZoomRecord>>)> {
+    let summary = summarize(chromosome_items);
+    let item_count: usize = chromosome_items.iter().map(|items| items.len()).sum();
+
+    if item_count == 0 {
+        return Vec::new();
+    }
+
+    let average_span = (summary.bases_covered / item_count as u64).max(1);
+    let mut reduction = average_span * INITIAL_REDUCTION_FACTOR;
+    let mut previous_count = item_count;
+    let mut zoom_levels = Vec::new();
+
+    while zoom_levels.len() < MAX_ZOOM_LEVEL_COUNT && reduction <= u64::from(max_chromosome_length)
+    {
+        let r = reduction as u32;
+        let records = reduce(chromosome_items, r);
+        let count: usize = records.iter().map(|r| r.len()).sum();
+
+        if count * 2 > previous_count {
+            break;
+        }
+
+        zoom_levels.push((r, records));
+
+        previous_count = count;
+        reduction *= REDUCTION_INCREMENT;
+    }
+
+    zoom_levels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(start: u32, end: u32, value: f32) -> Item {
+        Item { start, end, value }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let chromosome_items = vec![
+            vec![item(0, 2, 1.0), item(2, 3, 4.0)],
+            vec![item(5, 8, -1.0)],
+        ];
+
+        assert_eq!(
+            summarize(&chromosome_items),
+            Summary {
+                bases_covered: 6,
+                min: -1.0,
+                max: 4.0,
+                sum: 3.0,
+                sum_squares: 21.0,
+            }
+        );
+
+        assert_eq!(summarize(&[]), Summary::default());
+    }
+
+    #[test]
+    fn test_reduce() {
+        let chromosome_items = vec![vec![item(2, 5, 1.0), item(5, 12, 2.0)], vec![]];
+        let actual = reduce(&chromosome_items, 4);
+
+        let summary = |bases_covered, min, max, sum, sum_squares| Summary {
+            bases_covered,
+            min,
+            max,
+            sum,
+            sum_squares,
+        };
+
+        let expected = vec![
+            vec![
+                ZoomRecord {
+                    chromosome_id: 0,
+                    start: 2,
+                    end: 4,
+                    summary: summary(2, 1.0, 1.0, 2.0, 2.0),
+                },
+                ZoomRecord {
+                    chromosome_id: 0,
+                    start: 4,
+                    end: 8,
+                    summary: summary(4, 1.0, 2.0, 7.0, 13.0),
+                },
+                ZoomRecord {
+                    chromosome_id: 0,
+                    start: 8,
+                    end: 12,
+                    summary: summary(4, 2.0, 2.0, 8.0, 16.0),
+                },
+            ],
+            vec![],
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_build_zoom_levels() {
+        let items: Vec<_> = (0..1000).map(|i| item(i, i + 1, 1.0)).collect();
+        let zoom_levels = build_zoom_levels(&[items], 1000);
+
+        let reductions: Vec<_> = zoom_levels.iter().map(|(r, _)| *r).collect();
+        assert_eq!(reductions, [10, 40, 160, 640]);
+
+        assert_eq!(zoom_levels[0].1[0].len(), 100);
+
+        assert!(build_zoom_levels(&[vec![]], 1000).is_empty());
+    }
+}
@@ -0,0 +1,66 @@
+//! Fingerprint.
+
+use std::hash::Hasher;
+
+const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const PRIME: u64 = 0x100000001b3;
+
+/// A running fingerprint (64-bit FNV-1a).
+///
+/// This is used to identify data across runs, e.g., the header of a file in a reader checkpoint.
+/// Unlike [`std::collections::hash_map::DefaultHasher`], its output is stable across runs and
+/// releases. It is not a cryptographic hash.
+///
+/// Only [`Hasher::write`] should be used to update the fingerprint. The other `write_*` methods
+/// use the native byte order of the platform.
+///
+/// # Examples
+///
+/// ```
+/// use std::hash::Hasher;
+/// use noodles_core::Fingerprint;
+///
+/// let mut fingerprint = Fingerprint::default();
+/// fingerprint.write(b"noodles");
+/// assert_eq!(fingerprint.finish(), 0xe357fa3e1796978f);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Fingerprint(u64);
+
+impl Default for Fingerprint {
+    fn default() -> Self {
+        Self(OFFSET_BASIS)
+    }
+}
+
+impl Hasher for Fingerprint {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finish() {
+        let mut fingerprint = Fingerprint::default();
+        assert_eq!(fingerprint.finish(), 0xcbf29ce484222325);
+
+        fingerprint.write(b"a");
+        assert_eq!(fingerprint.finish(), 0xaf63dc4c8601ec8c);
+
+        let mut fingerprint = Fingerprint::default();
+        fingerprint.write(b"foo");
+        fingerprint.write(b"bar");
+        assert_eq!(fingerprint.finish(), 0x85944171f73967e8);
+    }
+}
@@ -0,0 +1,487 @@
+//! Interval set.
+
+use std::{
+    cmp,
+    iter::FromIterator,
+    ops::{Bound, RangeBounds, RangeInclusive},
+};
+
+/// A set of positions on a single reference sequence.
+///
+/// The set is stored as a sorted list of disjoint, closed intervals (1-based). Overlapping and
+/// adjacent intervals are merged on insertion, so lookups are binary searches over the list.
+/// Since the intervals never overlap, this answers the same queries as an interval tree in
+/// logarithmic time without the tree.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::IntervalSet;
+///
+/// let set: IntervalSet = [1..=5, 4..=8, 13..=21].into_iter().collect();
+/// assert_eq!(set.iter().collect::<Vec<_>>(), [1..=8, 13..=21]);
+///
+/// assert!(set.contains(8));
+/// assert!(!set.contains(10));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalSet {
+    intervals: Vec<(i32, i32)>,
+}
+
+impl IntervalSet {
+    /// Creates an empty interval set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let set = IntervalSet::new();
+    /// assert!(set.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let set = IntervalSet::new();
+    /// assert!(set.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns the number of disjoint intervals in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let set: IntervalSet = [1..=5, 4..=8, 13..=21].into_iter().collect();
+    /// assert_eq!(set.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Returns the number of positions in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let set: IntervalSet = [1..=5, 4..=8, 13..=21].into_iter().collect();
+    /// assert_eq!(set.position_count(), 17);
+    /// ```
+    pub fn position_count(&self) -> u64 {
+        self.intervals
+            .iter()
+            .map(|&(start, end)| (i64::from(end) - i64::from(start) + 1) as u64)
+            .sum()
+    }
+
+    /// Adds an interval to the set.
+    ///
+    /// The interval is merged with any intervals it overlaps or is adjacent to. An unbounded
+    /// start is 1, and an unbounded end is [`i32::MAX`]. Empty intervals are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert(1..=5);
+    /// set.insert(6..=8);
+    /// set.insert(13..);
+    ///
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), [1..=8, 13..=i32::MAX]);
+    /// ```
+    pub fn insert<B>(&mut self, interval: B)
+    where
+        B: RangeBounds<i32>,
+    {
+        let (start, end) = match resolve_interval(interval) {
+            Some(interval) => interval,
+            None => return,
+        };
+
+        // Intervals in `i..j` overlap or are adjacent to the new interval.
+        let i = self
+            .intervals
+            .partition_point(|&(_, e)| i64::from(e) < i64::from(start) - 1);
+        let j = self
+            .intervals
+            .partition_point(|&(s, _)| i64::from(s) <= i64::from(end) + 1);
+
+        let (start, end) = if i < j {
+            (
+                cmp::min(start, self.intervals[i].0),
+                cmp::max(end, self.intervals[j - 1].1),
+            )
+        } else {
+            (start, end)
+        };
+
+        self.intervals.splice(i..j, [(start, end)]);
+    }
+
+    /// Returns an iterator over the disjoint intervals in the set, in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let set: IntervalSet = [13..=21, 1..=5].into_iter().collect();
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), [1..=5, 13..=21]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<i32>> + '_ {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// Returns whether the set contains the given position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let set: IntervalSet = [5..=8].into_iter().collect();
+    /// assert!(set.contains(5));
+    /// assert!(!set.contains(13));
+    /// ```
+    pub fn contains(&self, position: i32) -> bool {
+        self.overlapping(position..=position).next().is_some()
+    }
+
+    /// Returns whether every position in the given interval is in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let set: IntervalSet = [5..=13].into_iter().collect();
+    /// assert!(set.contains_interval(8..=13));
+    /// assert!(!set.contains_interval(8..=21));
+    /// ```
+    pub fn contains_interval<B>(&self, interval: B) -> bool
+    where
+        B: RangeBounds<i32>,
+    {
+        match resolve_interval(interval) {
+            Some((start, end)) => self
+                .overlapping(start..=end)
+                .next()
+                .map(|interval| *interval.start() <= start && end <= *interval.end())
+                .unwrap_or(false),
+            None => true,
+        }
+    }
+
+    /// Returns whether any position in the given interval is in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let set: IntervalSet = [5..=8].into_iter().collect();
+    /// assert!(set.intersects(8..=13));
+    /// assert!(!set.intersects(13..=21));
+    /// ```
+    pub fn intersects<B>(&self, interval: B) -> bool
+    where
+        B: RangeBounds<i32>,
+    {
+        self.overlapping(interval).next().is_some()
+    }
+
+    /// Returns an iterator over the intervals in the set that overlap the given interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let set: IntervalSet = [1..=5, 8..=13, 21..=34].into_iter().collect();
+    /// assert_eq!(set.overlapping(5..=21).collect::<Vec<_>>(), [1..=5, 8..=13, 21..=34]);
+    /// assert_eq!(set.overlapping(6..=7).count(), 0);
+    /// ```
+    pub fn overlapping<B>(&self, interval: B) -> impl Iterator<Item = RangeInclusive<i32>> + '_
+    where
+        B: RangeBounds<i32>,
+    {
+        let (i, end) = match resolve_interval(interval) {
+            Some((start, end)) => (self.intervals.partition_point(|&(_, e)| e < start), end),
+            None => (self.intervals.len(), 0),
+        };
+
+        self.intervals[i..]
+            .iter()
+            .take_while(move |&&(s, _)| s <= end)
+            .map(|&(start, end)| start..=end)
+    }
+
+    /// Returns the set of positions in either set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let a: IntervalSet = [1..=5, 13..=21].into_iter().collect();
+    /// let b: IntervalSet = [3..=8].into_iter().collect();
+    /// assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), [1..=8, 13..=21]);
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        let mut intervals = Vec::with_capacity(self.len() + other.len());
+        let (mut a, mut b) = (
+            self.intervals.iter().peekable(),
+            other.intervals.iter().peekable(),
+        );
+
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x.0 <= y.0 => a.next(),
+                (Some(_), Some(_)) => b.next(),
+                (Some(_), None) => a.next(),
+                (None, Some(_)) => b.next(),
+                (None, None) => break,
+            };
+
+            let &(start, end) = next.unwrap();
+            push_merged(&mut intervals, start, end);
+        }
+
+        Self { intervals }
+    }
+
+    /// Returns the set of positions in both sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let a: IntervalSet = [1..=5, 13..=21].into_iter().collect();
+    /// let b: IntervalSet = [3..=15].into_iter().collect();
+    /// assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), [3..=5, 13..=15]);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a_start, a_end) = self.intervals[i];
+            let (b_start, b_end) = other.intervals[j];
+
+            let start = cmp::max(a_start, b_start);
+            let end = cmp::min(a_end, b_end);
+
+            if start <= end {
+                intervals.push((start, end));
+            }
+
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Returns the set of positions in this set that are not in the other set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let a: IntervalSet = [1..=21].into_iter().collect();
+    /// let b: IntervalSet = [5..=8, 13..=34].into_iter().collect();
+    /// assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), [1..=4, 9..=12]);
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+
+        for &(a_start, a_end) in &self.intervals {
+            let mut start = i64::from(a_start);
+            let end = i64::from(a_end);
+
+            while j < other.intervals.len() && i64::from(other.intervals[j].1) < start {
+                j += 1;
+            }
+
+            let mut k = j;
+
+            while k < other.intervals.len() && i64::from(other.intervals[k].0) <= end {
+                let (b_start, b_end) = other.intervals[k];
+
+                if start < i64::from(b_start) {
+                    intervals.push((start as i32, b_start - 1));
+                }
+
+                start = i64::from(b_end) + 1;
+                k += 1;
+            }
+
+            if start <= end {
+                intervals.push((start as i32, a_end));
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Returns the set of positions in `[1, len]` that are not in this set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalSet;
+    /// let set: IntervalSet = [1..=5, 13..=21].into_iter().collect();
+    /// assert_eq!(set.complement(34).iter().collect::<Vec<_>>(), [6..=12, 22..=34]);
+    /// ```
+    pub fn complement(&self, len: i32) -> Self {
+        let mut all = Self::new();
+        all.insert(1..=len);
+        all.difference(self)
+    }
+}
+
+impl<B> Extend<B> for IntervalSet
+where
+    B: RangeBounds<i32>,
+{
+    fn extend<T: IntoIterator<Item = B>>(&mut self, iter: T) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<B> FromIterator<B> for IntervalSet
+where
+    B: RangeBounds<i32>,
+{
+    fn from_iter<T: IntoIterator<Item = B>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+pub(crate) fn resolve_interval<B>(interval: B) -> Option<(i32, i32)>
+where
+    B: RangeBounds<i32>,
+{
+    let start = match interval.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s.checked_add(1)?,
+        Bound::Unbounded => 1,
+    };
+
+    let end = match interval.end_bound() {
+        Bound::Included(&e) => e,
+        Bound::Excluded(&e) => e.checked_sub(1)?,
+        Bound::Unbounded => i32::MAX,
+    };
+
+    if start <= end {
+        Some((start, end))
+    } else {
+        None
+    }
+}
+
+fn push_merged(intervals: &mut Vec<(i32, i32)>, start: i32, end: i32) {
+    if let Some(last) = intervals.last_mut() {
+        if i64::from(start) <= i64::from(last.1) + 1 {
+            last.1 = cmp::max(last.1, end);
+            return;
+        }
+    }
+
+    intervals.push((start, end));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intervals(set: &IntervalSet) -> Vec<RangeInclusive<i32>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut set = IntervalSet::new();
+
+        set.insert(13..=21);
+        set.insert(1..=5);
+        assert_eq!(intervals(&set), [1..=5, 13..=21]);
+
+        set.insert(6..=8);
+        assert_eq!(intervals(&set), [1..=8, 13..=21]);
+
+        set.insert(8..=34);
+        assert_eq!(intervals(&set), [1..=34]);
+
+        set.insert((Bound::Included(21), Bound::Included(13)));
+        assert_eq!(intervals(&set), [1..=34]);
+
+        set.insert(55..);
+        assert_eq!(intervals(&set), [1..=34, 55..=i32::MAX]);
+
+        set.insert(..);
+        assert_eq!(intervals(&set), [1..=i32::MAX]);
+    }
+
+    #[test]
+    fn test_contains_interval() {
+        let set: IntervalSet = [1..=5, 13..=21].into_iter().collect();
+
+        assert!(set.contains_interval(1..=5));
+        assert!(set.contains_interval(14..=20));
+        assert!(!set.contains_interval(5..=13));
+        assert!(!set.contains_interval(8..=8));
+    }
+
+    #[test]
+    fn test_union() {
+        let a: IntervalSet = [1..=5, 21..=34].into_iter().collect();
+        let b: IntervalSet = [6..=8, 13..=21, 55..=89].into_iter().collect();
+        assert_eq!(intervals(&a.union(&b)), [1..=8, 13..=34, 55..=89]);
+        assert_eq!(a.union(&IntervalSet::new()), a);
+    }
+
+    #[test]
+    fn test_intersection() {
+        let a: IntervalSet = [1..=8, 13..=34].into_iter().collect();
+        let b: IntervalSet = [5..=21, 34..=55].into_iter().collect();
+        assert_eq!(intervals(&a.intersection(&b)), [5..=8, 13..=21, 34..=34]);
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_difference() {
+        let a: IntervalSet = [1..=8, 13..=34].into_iter().collect();
+        let b: IntervalSet = [1..=2, 5..=5, 21..=55].into_iter().collect();
+        assert_eq!(intervals(&a.difference(&b)), [3..=4, 6..=8, 13..=20]);
+        assert_eq!(a.difference(&IntervalSet::new()), a);
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_complement() {
+        let set: IntervalSet = [1..=5, 13..=21].into_iter().collect();
+        assert_eq!(intervals(&set.complement(21)), [6..=12]);
+        assert_eq!(intervals(&set.complement(8)), [6..=8]);
+        assert_eq!(intervals(&IntervalSet::new().complement(8)), [1..=8]);
+
+        let set: IntervalSet = [..].into_iter().collect::<IntervalSet>();
+        assert!(set.complement(i32::MAX).is_empty());
+    }
+}
@@ -2,6 +2,13 @@
 
 //! **noodles-core** contains shared structures and behavior among noodles libraries.
 
+pub mod fingerprint;
+pub mod interval_set;
+pub mod position;
 pub mod region;
+pub mod region_set;
 
-pub use self::region::Region;
+pub use self::{
+    fingerprint::Fingerprint, interval_set::IntervalSet, position::Position, region::Region,
+    region_set::RegionSet,
+};
@@ -0,0 +1,225 @@
+//! 1-based position.
+
+use std::{error, fmt, num, str::FromStr};
+
+/// A 1-based position.
+///
+/// Unlike the 32-bit positions of alignment records, this can represent positions of reference
+/// sequences longer than 2^31 - 1 bases, e.g., the chromosomes of some plant genomes.
+///
+/// SAM and BAM record positions and BAI indices are limited to 32-bit positions by their
+/// specifications and keep using [`noodles_sam::record::Position`]. Convert between the two at
+/// the boundary, e.g., to query a CSI index using an alignment record position.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Position(num::NonZeroU64);
+
+impl Position {
+    /// The minimum value of a position.
+    pub const MIN: Self = match num::NonZeroU64::new(1) {
+        Some(n) => Self(n),
+        None => unreachable!(),
+    };
+
+    /// The maximum value of a position.
+    pub const MAX: Self = match num::NonZeroU64::new(u64::MAX) {
+        Some(n) => Self(n),
+        None => unreachable!(),
+    };
+
+    /// Creates a position if the given value is not zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// assert!(Position::new(8).is_some());
+    /// assert!(Position::new(0).is_none());
+    /// ```
+    pub const fn new(n: u64) -> Option<Self> {
+        match num::NonZeroU64::new(n) {
+            Some(m) => Some(Self(m)),
+            None => None,
+        }
+    }
+
+    /// Returns the position as a primitive integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// let position = Position::new(3_000_000_000).unwrap();
+    /// assert_eq!(position.get(), 3_000_000_000);
+    /// ```
+    pub const fn get(&self) -> u64 {
+        self.0.get()
+    }
+
+    /// Adds an unsigned integer to the position.
+    ///
+    /// This returns `None` if the operation overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// let position = Position::new(8).unwrap();
+    /// assert_eq!(position.checked_add(5), Position::new(13));
+    /// assert!(Position::MAX.checked_add(1).is_none());
+    /// ```
+    pub fn checked_add(self, other: u64) -> Option<Self> {
+        self.0.get().checked_add(other).and_then(Self::new)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An error returned when a position fails to convert.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TryFromIntError(i64);
+
+impl error::Error for TryFromIntError {}
+
+impl fmt::Display for TryFromIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid position: expected >= 1, got {}", self.0)
+    }
+}
+
+impl TryFrom<u64> for Position {
+    type Error = TryFromIntError;
+
+    fn try_from(n: u64) -> Result<Self, Self::Error> {
+        Self::new(n).ok_or(TryFromIntError(0))
+    }
+}
+
+impl TryFrom<i32> for Position {
+    type Error = TryFromIntError;
+
+    fn try_from(n: i32) -> Result<Self, Self::Error> {
+        u64::try_from(n)
+            .ok()
+            .and_then(Self::new)
+            .ok_or_else(|| TryFromIntError(i64::from(n)))
+    }
+}
+
+impl TryFrom<i64> for Position {
+    type Error = TryFromIntError;
+
+    fn try_from(n: i64) -> Result<Self, Self::Error> {
+        u64::try_from(n)
+            .ok()
+            .and_then(Self::new)
+            .ok_or(TryFromIntError(n))
+    }
+}
+
+impl From<Position> for u64 {
+    fn from(position: Position) -> Self {
+        position.get()
+    }
+}
+
+impl From<noodles_sam::record::Position> for Position {
+    fn from(position: noodles_sam::record::Position) -> Self {
+        let n = i32::from(position);
+
+        match u64::try_from(n).ok().and_then(Self::new) {
+            Some(position) => position,
+            None => unreachable!("SAM record positions are >= 1"),
+        }
+    }
+}
+
+impl TryFrom<Position> for noodles_sam::record::Position {
+    type Error = num::TryFromIntError;
+
+    fn try_from(position: Position) -> Result<Self, Self::Error> {
+        let n = i32::try_from(position.get())?;
+
+        match Self::try_from(n) {
+            Ok(position) => Ok(position),
+            Err(_) => unreachable!("positions are >= 1"),
+        }
+    }
+}
+
+/// An error returned when a raw position fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is invalid.
+    Invalid(num::ParseIntError),
+    /// The input is zero.
+    Zero,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(e) => write!(f, "invalid input: {}", e),
+            Self::Zero => f.write_str("invalid input: expected >= 1, got 0"),
+        }
+    }
+}
+
+impl FromStr for Position {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let n = s.parse().map_err(ParseError::Invalid)?;
+        Self::new(n).ok_or(ParseError::Zero)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_i32_for_position() {
+        assert_eq!(Position::try_from(8i32), Ok(Position::new(8).unwrap()));
+        assert_eq!(Position::try_from(0i32), Err(TryFromIntError(0)));
+        assert_eq!(Position::try_from(-1i32), Err(TryFromIntError(-1)));
+    }
+
+    #[test]
+    fn test_from_sam_record_position_for_position(
+    ) -> Result<(), noodles_sam::record::position::TryFromIntError> {
+        let position = noodles_sam::record::Position::try_from(8)?;
+        assert_eq!(Position::from(position), Position::new(8).unwrap());
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_position_for_sam_record_position() {
+        assert_eq!(
+            noodles_sam::record::Position::try_from(Position::new(8).unwrap()),
+            Ok(noodles_sam::record::Position::try_from(8).unwrap())
+        );
+
+        assert!(
+            noodles_sam::record::Position::try_from(Position::new(3_000_000_000).unwrap()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "3000000000".parse(),
+            Ok(Position::new(3_000_000_000).unwrap())
+        );
+        assert_eq!("0".parse::<Position>(), Err(ParseError::Zero));
+        assert!(matches!(
+            "".parse::<Position>(),
+            Err(ParseError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,363 @@
+//! Region set.
+
+use std::{collections::BTreeMap, iter::FromIterator, ops::RangeBounds};
+
+use super::{region::Mapped, IntervalSet, Region};
+
+/// A set of positions across reference sequences.
+///
+/// This is an [`IntervalSet`] per reference sequence name. It can be used to normalize a list of
+/// possibly overlapping query regions or to restrict records to a set of targets.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::{Region, RegionSet};
+///
+/// let mut set = RegionSet::new();
+/// set.insert("sq0", 8..=13);
+/// set.insert("sq0", 13..=21);
+/// set.insert("sq1", 5..=8);
+///
+/// assert_eq!(
+///     set.regions().collect::<Vec<_>>(),
+///     [Region::mapped("sq0", 8..=21), Region::mapped("sq1", 5..=8)]
+/// );
+///
+/// assert!(set.contains("sq0", 21));
+/// assert!(!set.contains("sq2", 1));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RegionSet {
+    interval_sets: BTreeMap<String, IntervalSet>,
+}
+
+impl RegionSet {
+    /// Creates an empty region set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::RegionSet;
+    /// let set = RegionSet::new();
+    /// assert!(set.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::RegionSet;
+    /// let set = RegionSet::new();
+    /// assert!(set.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.interval_sets.values().all(|set| set.is_empty())
+    }
+
+    /// Adds an interval on the given reference sequence to the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::RegionSet;
+    /// let mut set = RegionSet::new();
+    /// set.insert("sq0", 8..=13);
+    /// assert!(set.contains("sq0", 8));
+    /// ```
+    pub fn insert<N, B>(&mut self, name: N, interval: B)
+    where
+        N: Into<String>,
+        B: RangeBounds<i32>,
+    {
+        let set = self.interval_sets.entry(name.into()).or_default();
+        set.insert(interval);
+    }
+
+    /// Returns the interval set of the given reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::RegionSet;
+    ///
+    /// let mut set = RegionSet::new();
+    /// set.insert("sq0", 8..=13);
+    ///
+    /// assert!(set.get("sq0").is_some());
+    /// assert!(set.get("sq1").is_none());
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&IntervalSet> {
+        self.interval_sets.get(name)
+    }
+
+    /// Returns whether the set contains the given position on the given reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::RegionSet;
+    ///
+    /// let mut set = RegionSet::new();
+    /// set.insert("sq0", 8..=13);
+    ///
+    /// assert!(set.contains("sq0", 13));
+    /// assert!(!set.contains("sq0", 21));
+    /// ```
+    pub fn contains(&self, name: &str, position: i32) -> bool {
+        self.get(name)
+            .map(|set| set.contains(position))
+            .unwrap_or(false)
+    }
+
+    /// Returns whether any position in the given region is in the set.
+    ///
+    /// An unmapped region never intersects the set, and the region of all records intersects any
+    /// nonempty set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Region, RegionSet};
+    ///
+    /// let mut set = RegionSet::new();
+    /// set.insert("sq0", 8..=13);
+    ///
+    /// assert!(set.intersects(&Region::mapped("sq0", 13..=21)));
+    /// assert!(!set.intersects(&Region::mapped("sq1", 13..=21)));
+    /// assert!(!set.intersects(&Region::Unmapped));
+    /// assert!(set.intersects(&Region::All));
+    /// ```
+    pub fn intersects(&self, region: &Region) -> bool {
+        match region {
+            Region::Mapped(mapped) => self
+                .get(mapped.name())
+                .map(|set| set.intersects(mapped.interval()))
+                .unwrap_or(false),
+            Region::Unmapped => false,
+            Region::All => !self.is_empty(),
+        }
+    }
+
+    /// Returns an iterator over the disjoint regions in the set.
+    ///
+    /// Regions are ordered by reference sequence name and then by start position. These can be
+    /// used as the regions of successive indexed queries without returning a record twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Region, RegionSet};
+    ///
+    /// let mut set = RegionSet::new();
+    /// set.insert("sq0", 8..=13);
+    /// set.insert("sq0", 5..=10);
+    ///
+    /// assert_eq!(set.regions().collect::<Vec<_>>(), [Region::mapped("sq0", 5..=13)]);
+    /// ```
+    pub fn regions(&self) -> impl Iterator<Item = Region> + '_ {
+        self.interval_sets.iter().flat_map(|(name, set)| {
+            set.iter()
+                .map(move |interval| Region::mapped(name.clone(), interval))
+        })
+    }
+
+    /// Returns the set of positions in either set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Region, RegionSet};
+    ///
+    /// let mut a = RegionSet::new();
+    /// a.insert("sq0", 5..=8);
+    ///
+    /// let mut b = RegionSet::new();
+    /// b.insert("sq0", 8..=13);
+    /// b.insert("sq1", 5..=8);
+    ///
+    /// assert_eq!(
+    ///     a.union(&b).regions().collect::<Vec<_>>(),
+    ///     [Region::mapped("sq0", 5..=13), Region::mapped("sq1", 5..=8)]
+    /// );
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        let mut interval_sets = self.interval_sets.clone();
+
+        for (name, b) in &other.interval_sets {
+            let set = interval_sets.entry(name.clone()).or_default();
+            *set = set.union(b);
+        }
+
+        Self::from_interval_sets(interval_sets)
+    }
+
+    /// Returns the set of positions in both sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Region, RegionSet};
+    ///
+    /// let mut a = RegionSet::new();
+    /// a.insert("sq0", 5..=13);
+    /// a.insert("sq1", 5..=8);
+    ///
+    /// let mut b = RegionSet::new();
+    /// b.insert("sq0", 8..=21);
+    ///
+    /// assert_eq!(
+    ///     a.intersection(&b).regions().collect::<Vec<_>>(),
+    ///     [Region::mapped("sq0", 8..=13)]
+    /// );
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        let interval_sets = self
+            .interval_sets
+            .iter()
+            .filter_map(|(name, a)| {
+                other
+                    .interval_sets
+                    .get(name)
+                    .map(|b| (name.clone(), a.intersection(b)))
+            })
+            .collect();
+
+        Self::from_interval_sets(interval_sets)
+    }
+
+    /// Returns the set of positions in this set that are not in the other set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Region, RegionSet};
+    ///
+    /// let mut a = RegionSet::new();
+    /// a.insert("sq0", 5..=13);
+    /// a.insert("sq1", 5..=8);
+    ///
+    /// let mut b = RegionSet::new();
+    /// b.insert("sq0", 8..=21);
+    ///
+    /// assert_eq!(
+    ///     a.difference(&b).regions().collect::<Vec<_>>(),
+    ///     [Region::mapped("sq0", 5..=7), Region::mapped("sq1", 5..=8)]
+    /// );
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self {
+        let interval_sets = self
+            .interval_sets
+            .iter()
+            .map(|(name, a)| match other.interval_sets.get(name) {
+                Some(b) => (name.clone(), a.difference(b)),
+                None => (name.clone(), a.clone()),
+            })
+            .collect();
+
+        Self::from_interval_sets(interval_sets)
+    }
+
+    /// Returns the positions of the given reference sequences that are not in this set.
+    ///
+    /// The reference sequences are given as pairs of names and lengths. Positions on reference
+    /// sequences that are not given are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Region, RegionSet};
+    ///
+    /// let mut set = RegionSet::new();
+    /// set.insert("sq0", 5..=13);
+    ///
+    /// assert_eq!(
+    ///     set.complement([("sq0", 21), ("sq1", 8)]).regions().collect::<Vec<_>>(),
+    ///     [
+    ///         Region::mapped("sq0", 1..=4),
+    ///         Region::mapped("sq0", 14..=21),
+    ///         Region::mapped("sq1", 1..=8),
+    ///     ]
+    /// );
+    /// ```
+    pub fn complement<I, N>(&self, reference_sequences: I) -> Self
+    where
+        I: IntoIterator<Item = (N, i32)>,
+        N: Into<String>,
+    {
+        let interval_sets = reference_sequences
+            .into_iter()
+            .map(|(name, len)| {
+                let name = name.into();
+
+                let set = match self.interval_sets.get(&name) {
+                    Some(set) => set.complement(len),
+                    None => IntervalSet::new().complement(len),
+                };
+
+                (name, set)
+            })
+            .collect();
+
+        Self::from_interval_sets(interval_sets)
+    }
+
+    fn from_interval_sets(mut interval_sets: BTreeMap<String, IntervalSet>) -> Self {
+        interval_sets.retain(|_, set| !set.is_empty());
+        Self { interval_sets }
+    }
+}
+
+impl Extend<Mapped> for RegionSet {
+    fn extend<T: IntoIterator<Item = Mapped>>(&mut self, iter: T) {
+        for region in iter {
+            self.insert(region.name(), region.interval());
+        }
+    }
+}
+
+impl FromIterator<Mapped> for RegionSet {
+    fn from_iter<T: IntoIterator<Item = Mapped>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_iter() {
+        let set: RegionSet = [
+            Mapped::new("sq1", 5..=8),
+            Mapped::new("sq0", 13..=21),
+            Mapped::new("sq0", 8..=13),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            set.regions().collect::<Vec<_>>(),
+            [Region::mapped("sq0", 8..=21), Region::mapped("sq1", 5..=8)]
+        );
+    }
+
+    #[test]
+    fn test_intersection_removes_empty_sets() {
+        let mut a = RegionSet::new();
+        a.insert("sq0", 5..=8);
+
+        let mut b = RegionSet::new();
+        b.insert("sq0", 13..=21);
+
+        let set = a.intersection(&b);
+        assert!(set.is_empty());
+        assert!(set.get("sq0").is_none());
+    }
+}
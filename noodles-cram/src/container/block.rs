@@ -75,26 +75,20 @@ impl Block {
         match self.compression_method {
             CompressionMethod::None => Ok(Cow::from(self.data())),
             CompressionMethod::Gzip => {
-                let mut reader = GzDecoder::new(self.data());
-                let mut buf = Vec::with_capacity(self.uncompressed_len);
-                reader.read_to_end(&mut buf)?;
-                Ok(Cow::from(buf))
+                let reader = GzDecoder::new(self.data());
+                read_uncompressed_data(reader, self.uncompressed_len).map(Cow::from)
             }
             CompressionMethod::Bzip2 => {
-                let mut reader = BzDecoder::new(self.data());
-                let mut buf = Vec::with_capacity(self.uncompressed_len);
-                reader.read_to_end(&mut buf)?;
-                Ok(Cow::from(buf))
+                let reader = BzDecoder::new(self.data());
+                read_uncompressed_data(reader, self.uncompressed_len).map(Cow::from)
             }
             CompressionMethod::Lzma => {
-                let mut reader = XzDecoder::new(self.data());
-                let mut buf = Vec::with_capacity(self.uncompressed_len);
-                reader.read_to_end(&mut buf)?;
-                Ok(Cow::from(buf))
+                let reader = XzDecoder::new(self.data());
+                read_uncompressed_data(reader, self.uncompressed_len).map(Cow::from)
             }
             CompressionMethod::Rans => {
                 let mut buf = self.data();
-                rans_decode(&mut buf).map(Cow::from)
+                rans_decode(&mut buf, self.uncompressed_len).map(Cow::from)
             }
         }
     }
@@ -113,6 +107,32 @@ impl Block {
     }
 }
 
+// Reads decompressed data, which must not be longer than the declared uncompressed length.
+//
+// The buffer only grows as data is read, so an invalid uncompressed length does not allocate
+// before failing.
+fn read_uncompressed_data<R>(reader: R, len: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let limit = u64::try_from(len)
+        .ok()
+        .and_then(|n| n.checked_add(1))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid uncompressed length"))?;
+
+    let mut buf = Vec::new();
+    reader.take(limit).read_to_end(&mut buf)?;
+
+    if buf.len() > len {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "block data is longer than its uncompressed length",
+        ))
+    } else {
+        Ok(buf)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +149,38 @@ mod tests {
 
         assert_eq!(block.len(), 16);
     }
+
+    #[test]
+    fn test_decompressed_data_with_invalid_uncompressed_len() -> io::Result<()> {
+        use std::io::Write;
+
+        use flate2::{write::GzEncoder, Compression};
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"noodles")?;
+        let data = encoder.finish()?;
+
+        let block = Block::builder()
+            .set_compression_method(CompressionMethod::Gzip)
+            .set_content_type(ContentType::ExternalData)
+            .set_uncompressed_len(7)
+            .set_data(data.clone())
+            .build();
+
+        assert_eq!(&block.decompressed_data()?[..], b"noodles");
+
+        let block = Block::builder()
+            .set_compression_method(CompressionMethod::Gzip)
+            .set_content_type(ContentType::ExternalData)
+            .set_uncompressed_len(4)
+            .set_data(data)
+            .build();
+
+        assert!(matches!(
+            block.decompressed_data(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
 }
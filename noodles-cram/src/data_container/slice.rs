@@ -5,10 +5,16 @@ pub use self::{builder::Builder, header::Header};
 
 use std::io::{self, Cursor};
 
+use md5::{Digest, Md5};
+use noodles_fasta as fasta;
 use noodles_sam as sam;
 
 use super::CompressionHeader;
-use crate::{container::Block, BitReader, Record};
+use crate::{
+    container::{Block, ReferenceSequenceId},
+    record::resolve,
+    BitReader, Error, Record,
+};
 
 /// A CRAM data container slice.
 ///
@@ -131,6 +137,167 @@ impl Slice {
     pub fn resolve_mates(&self, records: Vec<Record>) -> Vec<Record> {
         resolve_mates(records)
     }
+
+    /// Resolves the read bases of records using reference sequences from a repository.
+    ///
+    /// The bases of mapped records are typically stored as differences to the reference sequence.
+    /// Reference sequences are fetched from the repository by the names in the given SAM header
+    /// reference sequences. After each fetch, the reference sequence of a single-reference slice
+    /// is checked against the slice reference MD5 checksum.
+    ///
+    /// Records that do not need a reference sequence are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// use noodles_fasta::{self as fasta, repository::Repository};
+    /// use noodles_sam as sam;
+    ///
+    /// let data = [];
+    /// let mut reader = cram::Reader::new(&data[..]);
+    /// reader.read_file_definition()?;
+    /// let header: sam::Header = reader.read_file_header()?.parse().unwrap();
+    ///
+    /// let mut repository = Repository::new(Vec::<fasta::Record>::new());
+    ///
+    /// while let Some(container) = reader.read_data_container()? {
+    ///     for slice in container.slices() {
+    ///         let compression_header = container.compression_header();
+    ///         let mut records = slice.records(compression_header)?;
+    ///
+    ///         slice.resolve_bases(
+    ///             &mut repository,
+    ///             header.reference_sequences(),
+    ///             compression_header,
+    ///             &mut records,
+    ///         )?;
+    ///
+    ///         // ...
+    ///     }
+    /// }
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn resolve_bases(
+        &self,
+        reference_sequence_repository: &mut fasta::Repository,
+        reference_sequences: &sam::header::ReferenceSequences,
+        compression_header: &CompressionHeader,
+        records: &mut [Record],
+    ) -> io::Result<()> {
+        let mut fetched_reference_sequence_id = None;
+
+        for record in records {
+            if !needs_reference_sequence(record) {
+                continue;
+            }
+
+            let reference_sequence_id = record
+                .reference_sequence_id()
+                .map(|id| i32::from(id) as usize)
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "missing reference sequence ID")
+                })?;
+
+            let name = reference_sequences
+                .get_index(reference_sequence_id)
+                .map(|(name, _)| name.as_str())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid reference sequence ID: {}", reference_sequence_id),
+                    )
+                })?;
+
+            let reference_sequence_record = reference_sequence_repository
+                .get(name)
+                .transpose()?
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("missing reference sequence: {}", name),
+                    )
+                })?;
+
+            if fetched_reference_sequence_id != Some(reference_sequence_id) {
+                validate_reference_md5(&self.header, reference_sequence_record.sequence())?;
+                fetched_reference_sequence_id = Some(reference_sequence_id);
+            }
+
+            let alignment_start = record.alignment_start().map(i32::from).unwrap_or_default();
+
+            record.bases = resolve::resolve_bases(
+                reference_sequence_record,
+                compression_header,
+                record.features(),
+                alignment_start,
+                record.read_length(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn needs_reference_sequence(record: &Record) -> bool {
+    record.bases().is_empty()
+        && record.read_length() > 0
+        && !record.bam_flags().is_unmapped()
+        && !record.flags().decode_sequence_as_unknown()
+}
+
+// Checks the reference sequence of a single-reference slice against the slice reference MD5
+// checksum.
+//
+// The checksum is all zeros when it is not set, e.g., for multi-reference slices.
+fn validate_reference_md5(
+    header: &Header,
+    reference_sequence: &fasta::record::Sequence,
+) -> io::Result<()> {
+    const MISSING: [u8; 16] = [0; 16];
+
+    let expected_md5 = header.reference_md5();
+
+    if !matches!(header.reference_sequence_id(), ReferenceSequenceId::Some(_))
+        || expected_md5 == MISSING
+    {
+        return Ok(());
+    }
+
+    let start = header
+        .alignment_start()
+        .map(|position| (i32::from(position) - 1) as usize)
+        .unwrap_or_default();
+
+    let end = usize::try_from(header.alignment_span())
+        .ok()
+        .and_then(|span| start.checked_add(span))
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "invalid slice alignment span")
+        })?;
+
+    let bases = reference_sequence.as_ref().get(start..end).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "slice alignment is out of range of the reference sequence",
+        )
+    })?;
+
+    let actual_md5 = Md5::digest(bases);
+
+    if actual_md5.as_slice() == expected_md5 {
+        Ok(())
+    } else {
+        let mut expected = [0; 16];
+        expected.copy_from_slice(expected_md5);
+
+        Err(Error::ReferenceSequenceChecksumMismatch {
+            expected,
+            actual: actual_md5.into(),
+        }
+        .into())
+    }
 }
 
 fn resolve_mates(records: Vec<Record>) -> Vec<Record> {
@@ -0,0 +1,154 @@
+//! CRAM errors.
+
+use std::{error, fmt, io, str};
+
+use noodles_sam as sam;
+
+/// An error returned when CRAM data fails to be read.
+///
+/// Readers continue to return [`std::io::Error`]s, but for invalid data, the error is wrapped in an `io::Error` with the kind [`std::io::ErrorKind::InvalidData`]. The cause can be
+/// inspected by downcasting the inner error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The magic number is invalid.
+    InvalidMagicNumber([u8; 4]),
+    /// The file header container is missing the block with the SAM header.
+    MissingFileHeaderBlock,
+    /// The first block of the file header container is not a file header block.
+    InvalidFileHeaderBlockContentType,
+    /// The SAM header text is not valid UTF-8.
+    InvalidFileHeaderText(str::Utf8Error),
+    /// The SAM header is invalid.
+    InvalidFileHeader(sam::header::ParseError),
+    /// The reference sequence of a slice does not match the slice reference MD5 checksum.
+    ReferenceSequenceChecksumMismatch {
+        /// The slice reference MD5 checksum.
+        expected: [u8; 16],
+        /// The MD5 checksum of the reference sequence bases spanned by the slice.
+        actual: [u8; 16],
+    },
+    /// The container length is invalid.
+    ///
+    /// The length of a container must be non-negative.
+    InvalidContainerLength(i32),
+    /// A container is larger than the maximum container size.
+    ///
+    /// See [`crate::reader::Builder::set_max_container_size`].
+    ContainerTooLarge {
+        /// The container size.
+        size: usize,
+        /// The maximum container size.
+        max: usize,
+    },
+    /// The number of blocks in a slice is invalid.
+    ///
+    /// A slice must have at least a core data block.
+    InvalidSliceBlockCount(usize),
+    /// The uncompressed data of a block is larger than the maximum block size.
+    ///
+    /// See [`crate::reader::Builder::set_max_block_size`].
+    BlockTooLarge {
+        /// The uncompressed block size.
+        size: usize,
+        /// The maximum block size.
+        max: usize,
+    },
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidFileHeaderText(e) => Some(e),
+            Self::InvalidFileHeader(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagicNumber(actual) => {
+                write!(f, "invalid magic number: expected CRAM, got {:?}", actual)
+            }
+            Self::MissingFileHeaderBlock => {
+                f.write_str("invalid file header container: missing block for SAM header")
+            }
+            Self::InvalidFileHeaderBlockContentType => {
+                f.write_str("invalid file header block: invalid content type")
+            }
+            Self::InvalidFileHeaderText(_) => f.write_str("invalid file header text"),
+            Self::InvalidFileHeader(_) => f.write_str("invalid file header"),
+            Self::ReferenceSequenceChecksumMismatch { expected, actual } => write!(
+                f,
+                "reference sequence checksum mismatch: expected {:02x?}, got {:02x?}",
+                expected, actual
+            ),
+            Self::InvalidContainerLength(actual) => {
+                write!(f, "invalid container length: expected >= 0, got {}", actual)
+            }
+            Self::ContainerTooLarge { size, max } => {
+                write!(f, "container too large: expected <= {}, got {}", max, size)
+            }
+            Self::BlockTooLarge { size, max } => {
+                write!(f, "block too large: expected <= {}, got {}", max, size)
+            }
+            Self::InvalidSliceBlockCount(actual) => {
+                write!(
+                    f,
+                    "invalid slice block count: expected >= 1, got {}",
+                    actual
+                )
+            }
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        Self::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(
+            Error::InvalidMagicNumber(*b"BAM\x01").to_string(),
+            "invalid magic number: expected CRAM, got [66, 65, 77, 1]"
+        );
+
+        assert_eq!(
+            Error::ReferenceSequenceChecksumMismatch {
+                expected: [0; 16],
+                actual: [0xff; 16],
+            }
+            .to_string(),
+            "reference sequence checksum mismatch: expected [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00], got [ff, ff, ff, ff, ff, ff, ff, ff, ff, ff, ff, ff, ff, ff, ff, ff]"
+        );
+
+        assert_eq!(
+            Error::InvalidContainerLength(-1).to_string(),
+            "invalid container length: expected >= 0, got -1"
+        );
+
+        assert_eq!(
+            Error::ContainerTooLarge { size: 21, max: 8 }.to_string(),
+            "container too large: expected <= 8, got 21"
+        );
+
+        assert_eq!(
+            Error::BlockTooLarge { size: 21, max: 8 }.to_string(),
+            "block too large: expected <= 8, got 21"
+        );
+
+        assert_eq!(
+            Error::InvalidSliceBlockCount(0).to_string(),
+            "invalid slice block count: expected >= 1, got 0"
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Indexed CRAM reader.
+
+use std::{
+    io::{self, Read, Seek},
+    ops::RangeInclusive,
+};
+
+use noodles_core::Region;
+use noodles_sam::{self as sam, AlignmentReader, AlignmentRecord};
+
+use super::{crai, Reader};
+
+/// An indexed CRAM reader.
+///
+/// This wraps a CRAM reader and its associated index. As a [`sam::AlignmentReader`], queries use
+/// the index to only read the containers that may hold records that intersect the region.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_cram::{self as cram, crai};
+/// use noodles_sam::AlignmentReader;
+///
+/// let reader = File::open("sample.cram").map(cram::Reader::new)?;
+/// let index = crai::read("sample.cram.crai")?;
+///
+/// let mut reader: Box<dyn AlignmentReader> = Box::new(cram::IndexedReader::new(reader, index));
+/// let header = reader.read_alignment_header()?;
+///
+/// for result in reader.query_alignment_records(&header, "sq0", 17711..=28657)? {
+///     let record = result?;
+///     println!("{:?}", record.read_name());
+/// }
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct IndexedReader<R>
+where
+    R: Read,
+{
+    inner: Reader<R>,
+    index: crai::Index,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read,
+{
+    /// Creates an indexed CRAM reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let reader = cram::Reader::new(&[][..]);
+    /// let reader = cram::IndexedReader::new(reader, Vec::new());
+    /// ```
+    pub fn new(inner: Reader<R>, index: crai::Index) -> Self {
+        Self { inner, index }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Reader<R> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut Reader<R> {
+        &mut self.inner
+    }
+
+    /// Returns the index.
+    pub fn index(&self) -> &crai::Index {
+        &self.index
+    }
+
+    /// Returns the underlying reader and index.
+    pub fn into_parts(self) -> (Reader<R>, crai::Index) {
+        (self.inner, self.index)
+    }
+}
+
+impl<R> AlignmentReader for IndexedReader<R>
+where
+    R: Read + Seek,
+{
+    fn read_alignment_header(&mut self) -> io::Result<sam::Header> {
+        self.inner.read_alignment_header()
+    }
+
+    fn alignment_records<'a>(&'a mut self, header: &'a sam::Header) -> sam::AlignmentRecords<'a> {
+        self.inner.alignment_records(header)
+    }
+
+    fn query_alignment_records<'a>(
+        &'a mut self,
+        header: &'a sam::Header,
+        reference_sequence_name: &'a str,
+        interval: RangeInclusive<i32>,
+    ) -> io::Result<sam::AlignmentRecords<'a>> {
+        let region = Region::mapped(reference_sequence_name, interval);
+        let query = self
+            .inner
+            .query(header.reference_sequences(), &self.index, &region)?;
+
+        Ok(Box::new(query.map(|result| {
+            result.map(|record| Box::new(record) as Box<dyn AlignmentRecord>)
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_bam::record::ReferenceSequenceId;
+    use noodles_fasta as fasta;
+    use sam::AlignmentWriter;
+
+    use super::*;
+    use crate::Writer;
+
+    #[test]
+    fn test_query_alignment_records() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequences = vec![fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"ACGTACGTACGTACGT".to_vec()),
+        )];
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                sam::header::ReferenceSequence::builder()
+                    .set_name("sq0".parse()?)
+                    .set_length(16)
+                    .set_md5_checksum(
+                        [
+                            0x56, 0xb7, 0x4a, 0x65, 0x2b, 0x3e, 0xd2, 0xf6, 0x10, 0x26, 0x3b, 0x8b,
+                            0xb4, 0x23, 0x16, 0x7c,
+                        ]
+                        .into(),
+                    )
+                    .build()?,
+            )
+            .build();
+
+        let sam_record: sam::Record = "r0\t0\tsq0\t9\t37\t4M\t*\t0\t0\tACGT\tNDLS".parse()?;
+
+        let mut writer = Writer::new(Vec::new(), reference_sequences.clone());
+        writer.write_alignment_header(&header)?;
+        writer.write_alignment_record(&header, &sam_record)?;
+        writer.finish(&header)?;
+        let data = writer.get_ref().clone();
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+        let offset = reader.position()?;
+
+        let sq0 = ReferenceSequenceId::try_from(0).map(Some)?;
+        let index = vec![crai::Record::new(sq0, 9, 4, offset, 0, 0)];
+
+        let reader = Reader::builder(Cursor::new(&data))
+            .set_reference_sequence_repository(fasta::Repository::new(reference_sequences))
+            .build();
+        let mut reader: Box<dyn AlignmentReader> = Box::new(IndexedReader::new(reader, index));
+        let header = reader.read_alignment_header()?;
+
+        let records: Vec<_> = reader
+            .query_alignment_records(&header, "sq0", 10..=11)?
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].read_name(), Some(&b"r0"[..]));
+        assert_eq!(records[0].sequence()?, sam_record.sequence().clone());
+
+        let records: Vec<_> = reader
+            .query_alignment_records(&header, "sq0", 1..=8)?
+            .collect::<io::Result<_>>()?;
+
+        assert!(records.is_empty());
+
+        Ok(())
+    }
+}
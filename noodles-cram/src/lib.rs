@@ -11,17 +11,19 @@ mod bit_writer;
 pub(crate) mod container;
 pub mod crai;
 pub mod data_container;
+mod error;
 pub mod file_definition;
 mod huffman;
+mod indexed_reader;
 mod num;
 mod rans;
 pub mod reader;
 pub mod record;
-pub(crate) mod writer;
+pub mod writer;
 
 pub use self::{
-    data_container::DataContainer, file_definition::FileDefinition, reader::Reader, record::Record,
-    writer::Writer,
+    data_container::DataContainer, error::Error, file_definition::FileDefinition,
+    indexed_reader::IndexedReader, reader::Reader, record::Record, writer::Writer,
 };
 
 #[cfg(feature = "async")]
@@ -17,7 +17,7 @@ mod tests {
         let compressed_data = rans_encode(Order::Zero, data)?;
 
         let mut reader = &compressed_data[..];
-        let decompressed_data = rans_decode(&mut reader)?;
+        let decompressed_data = rans_decode(&mut reader, data.len())?;
 
         assert_eq!(decompressed_data, data);
 
@@ -31,7 +31,7 @@ mod tests {
         let compressed_data = rans_encode(Order::One, data)?;
 
         let mut reader = &compressed_data[..];
-        let decompressed_data = rans_decode(&mut reader)?;
+        let decompressed_data = rans_decode(&mut reader, data.len())?;
 
         assert_eq!(decompressed_data, data);
 
@@ -7,13 +7,30 @@ use byteorder::{LittleEndian, ReadBytesExt};
 
 use super::Order;
 
-pub fn rans_decode<R>(reader: &mut R) -> io::Result<Vec<u8>>
+/// Decodes rANS-encoded data.
+///
+/// The uncompressed length in the header is not trusted: it must be at most `max_len`, e.g., the
+/// uncompressed length of the block, before the output buffer is allocated.
+pub fn rans_decode<R>(reader: &mut R, max_len: usize) -> io::Result<Vec<u8>>
 where
     R: Read,
 {
     let (order, _, data_len) = read_header(reader)?;
 
-    let mut buf = vec![0; data_len as usize];
+    let data_len =
+        usize::try_from(data_len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    if data_len > max_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid rANS data length: expected <= {}, got {}",
+                max_len, data_len
+            ),
+        ));
+    }
+
+    let mut buf = vec![0; data_len];
 
     match order {
         Order::Zero => order_0::decode(reader, &mut buf)?,
@@ -86,7 +103,7 @@ mod tests {
         ];
 
         let mut reader = &data[..];
-        let actual = rans_decode(&mut reader)?;
+        let actual = rans_decode(&mut reader, expected.len())?;
 
         assert_eq!(actual, expected);
 
@@ -106,10 +123,26 @@ mod tests {
         ];
 
         let mut reader = &data[..];
-        let actual = rans_decode(&mut reader)?;
+        let actual = rans_decode(&mut reader, expected.len())?;
 
         assert_eq!(actual, expected);
 
         Ok(())
     }
+
+    #[test]
+    fn test_rans_decode_with_invalid_data_len() {
+        let data = [
+            0x00, // order = 0
+            0x00, 0x00, 0x00, 0x00, // compressed size = 0
+            0xff, 0xff, 0xff, 0xff, // data size = 4294967295
+        ];
+
+        let mut reader = &data[..];
+
+        assert!(matches!(
+            rans_decode(&mut reader, 8),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
 }
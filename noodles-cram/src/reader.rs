@@ -1,24 +1,34 @@
 //! CRAM reader and record iterator.
 
+mod builder;
+mod checkpoint;
 mod container;
 pub(crate) mod data_container;
 pub(crate) mod num;
+mod query;
 pub(crate) mod record;
 mod records;
 
 use crate::data_container::DataContainer;
 
-pub use self::records::Records;
+pub use self::{builder::Builder, checkpoint::Checkpoint, query::Query, records::Records};
 
 use std::{
+    hash::Hasher,
     io::{self, Read, Seek, SeekFrom},
     str,
 };
 
 use byteorder::{LittleEndian, ReadBytesExt};
+use noodles_bgzf as bgzf;
+use noodles_core::{region::Interval, Fingerprint, Region};
+use noodles_fasta as fasta;
+use noodles_sam::{self as sam, header::ReferenceSequences, AlignmentReader, AlignmentRecord};
 
 use self::container::read_container;
-use super::{container::Block, file_definition::Version, FileDefinition, MAGIC_NUMBER};
+use super::{
+    container::Block, crai, file_definition::Version, Error, FileDefinition, MAGIC_NUMBER,
+};
 
 /// A CRAM reader.
 ///
@@ -47,6 +57,11 @@ where
     R: Read,
 {
     inner: R,
+    worker_pool: Option<bgzf::WorkerPool>,
+    max_container_size: Option<usize>,
+    max_block_size: Option<usize>,
+    header_fingerprint: Fingerprint,
+    reference_sequence_repository: Option<fasta::Repository>,
 }
 
 impl<R> Reader<R>
@@ -64,7 +79,21 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn new(reader: R) -> Self {
-        Self { inner: reader }
+        Self::builder(reader).build()
+    }
+
+    /// Creates a CRAM reader builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let builder = cram::Reader::builder(&data[..]);
+    /// let reader = builder.build();
+    /// ```
+    pub fn builder(reader: R) -> Builder<R> {
+        Builder::new(reader)
     }
 
     /// Returns a reference to the underlying reader.
@@ -130,6 +159,11 @@ where
         let format = read_format(&mut self.inner)?;
         let file_id = read_file_id(&mut self.inner)?;
 
+        self.header_fingerprint = Fingerprint::default();
+        self.header_fingerprint
+            .write(&[format.major(), format.minor()]);
+        self.header_fingerprint.write(&file_id);
+
         Ok(FileDefinition::new(format, file_id))
     }
 
@@ -154,23 +188,32 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn read_file_header(&mut self) -> io::Result<String> {
-        let container = read_container(&mut self.inner)?;
-
-        if let Some(block) = container.blocks().first() {
-            read_file_header_block(block)
+        let container = read_container(
+            &mut self.inner,
+            self.max_container_size,
+            self.max_block_size,
+        )?;
+
+        let header = if let Some(block) = container.blocks().first() {
+            read_file_header_block(block)?
         } else {
-            Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "invalid header container: missing block for SAM header",
-            ))
-        }
+            return Err(Error::MissingFileHeaderBlock.into());
+        };
+
+        self.header_fingerprint.write(header.as_bytes());
+
+        Ok(header)
     }
 
     pub(crate) fn read_data_container_with_container_header(
         &mut self,
     ) -> io::Result<Option<(crate::container::Header, DataContainer)>> {
         use self::data_container::read_data_container_with_container_header;
-        read_data_container_with_container_header(&mut self.inner)
+        read_data_container_with_container_header(
+            &mut self.inner,
+            self.max_container_size,
+            self.max_block_size,
+        )
     }
 
     /// Reads a data container.
@@ -178,6 +221,11 @@ where
     /// This returns `None` if the container header is the EOF container header, which signals the
     /// end of the stream.
     ///
+    /// If a maximum container size is set (see [`Builder::set_max_container_size`]), containers
+    /// that are larger fail with [`crate::Error::ContainerTooLarge`]. Likewise, if a maximum block
+    /// size is set (see [`Builder::set_max_block_size`]), blocks that are larger fail with
+    /// [`crate::Error::BlockTooLarge`].
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -196,13 +244,21 @@ where
     pub fn read_data_container(&mut self) -> io::Result<Option<DataContainer>> {
         use self::data_container::read_data_container;
 
-        read_data_container(&mut self.inner)
+        read_data_container(
+            &mut self.inner,
+            self.max_container_size,
+            self.max_block_size,
+        )
     }
 
     /// Returns a iterator over records starting from the current stream position.
     ///
     /// The stream is expected to be at the start of a data container.
     ///
+    /// The read bases of mapped records are not resolved. Use
+    /// [`noodles_sam::AlignmentReader::alignment_records`] with a reference sequence repository
+    /// (see [`Builder::set_reference_sequence_repository`]) to resolve them.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -220,7 +276,7 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn records(&mut self) -> Records<'_, R> {
-        Records::new(self)
+        Records::new(self, None)
     }
 }
 
@@ -263,6 +319,155 @@ where
     pub fn position(&mut self) -> io::Result<u64> {
         self.inner.seek(SeekFrom::Current(0))
     }
+
+    /// Returns a checkpoint at the current position.
+    ///
+    /// The checkpoint holds the current position and a fingerprint of the file definition and SAM
+    /// header read by this reader. It should be taken after reading the file definition and header
+    /// and between data containers, e.g., after [`Self::read_data_container`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_cram as cram;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = cram::Writer::new(Vec::new(), Vec::new());
+    /// writer.write_file_definition()?;
+    /// writer.write_file_header(&sam::Header::default())?;
+    /// writer.try_finish()?;
+    /// let data = writer.get_ref().clone();
+    ///
+    /// let mut reader = cram::Reader::new(Cursor::new(data));
+    /// reader.read_file_definition()?;
+    /// reader.read_file_header()?;
+    ///
+    /// let checkpoint = reader.checkpoint()?;
+    /// assert_eq!(checkpoint.position(), reader.position()?);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn checkpoint(&mut self) -> io::Result<Checkpoint> {
+        let position = self.position()?;
+        Ok(Checkpoint::new(position, self.header_fingerprint.finish()))
+    }
+
+    /// Creates a CRAM reader that resumes reading at the given checkpoint.
+    ///
+    /// The given reader must be positioned at the start of the stream. The file definition and
+    /// SAM header are read and compared to the checkpoint's header fingerprint, and the reader is
+    /// then moved to the checkpoint's position. This fails with [`io::ErrorKind::InvalidData`] if
+    /// the header does not match, i.e., the checkpoint was taken from a different file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_cram as cram;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = cram::Writer::new(Vec::new(), Vec::new());
+    /// writer.write_file_definition()?;
+    /// writer.write_file_header(&sam::Header::default())?;
+    /// writer.try_finish()?;
+    /// let data = writer.get_ref().clone();
+    ///
+    /// let mut reader = cram::Reader::new(Cursor::new(&data));
+    /// reader.read_file_definition()?;
+    /// reader.read_file_header()?;
+    /// let checkpoint = reader.checkpoint()?;
+    ///
+    /// let mut reader = cram::Reader::resume_at(Cursor::new(&data), &checkpoint)?;
+    /// assert_eq!(reader.position()?, checkpoint.position());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn resume_at(reader: R, checkpoint: &Checkpoint) -> io::Result<Self> {
+        let mut reader = Self::new(reader);
+
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        if reader.header_fingerprint.finish() != checkpoint.header_fingerprint() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "checkpoint header fingerprint mismatch",
+            ));
+        }
+
+        reader.seek(SeekFrom::Start(checkpoint.position()))?;
+
+        Ok(reader)
+    }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// The containers that may hold records in the region are found using the CRAM index. Each
+    /// is read and its records are filtered by the region.
+    ///
+    /// If the reader has a reference sequence repository (see
+    /// [`Builder::set_reference_sequence_repository`]), the bases of the records are resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_core::Region;
+    /// use noodles_cram::{self as cram, crai};
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+    /// reader.read_file_definition()?;
+    /// let header: sam::Header = reader.read_file_header()?.parse()?;
+    ///
+    /// let reference_sequences = header.reference_sequences();
+    /// let index = crai::read("sample.cram.crai")?;
+    /// let region = Region::mapped("sq0", 17711..=28657);
+    /// let query = reader.query(reference_sequences, &index, &region)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<'a>(
+        &'a mut self,
+        reference_sequences: &'a ReferenceSequences,
+        index: &crai::Index,
+        region: &Region,
+    ) -> io::Result<Query<'a, R>> {
+        let (reference_sequence_id, interval) = resolve_region(reference_sequences, region)?;
+        let offsets = container_offsets(index, reference_sequence_id, interval);
+
+        Ok(Query::new(
+            self,
+            reference_sequences,
+            offsets,
+            reference_sequence_id,
+            interval,
+        ))
+    }
+}
+
+impl<R> AlignmentReader for Reader<R>
+where
+    R: Read,
+{
+    fn read_alignment_header(&mut self) -> io::Result<sam::Header> {
+        self.read_file_definition()?;
+
+        self.read_file_header().and_then(|s| {
+            s.parse()
+                .map_err(|e| io::Error::from(Error::InvalidFileHeader(e)))
+        })
+    }
+
+    fn alignment_records<'a>(&'a mut self, header: &'a sam::Header) -> sam::AlignmentRecords<'a> {
+        Box::new(
+            Records::new(self, Some(header.reference_sequences()))
+                .map(|result| result.map(|record| Box::new(record) as Box<dyn AlignmentRecord>)),
+        )
+    }
 }
 
 fn read_magic_number<R>(reader: &mut R) -> io::Result<()>
@@ -275,10 +480,7 @@ where
     if buf == MAGIC_NUMBER {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "invalid CRAM header",
-        ))
+        Err(Error::InvalidMagicNumber(buf).into())
     }
 }
 
@@ -304,14 +506,7 @@ pub(crate) fn read_file_header_block(block: &Block) -> io::Result<String> {
     use crate::container::block::ContentType;
 
     if block.content_type() != ContentType::FileHeader {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!(
-                "invalid block content type: expected {:?}, got {:?}",
-                ContentType::FileHeader,
-                block.content_type()
-            ),
-        ));
+        return Err(Error::InvalidFileHeaderBlockContentType.into());
     }
 
     let data = block.decompressed_data()?;
@@ -321,11 +516,66 @@ pub(crate) fn read_file_header_block(block: &Block) -> io::Result<String> {
 
     str::from_utf8(reader)
         .map(|s| s.into())
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        .map_err(|e| Error::InvalidFileHeaderText(e).into())
+}
+
+fn resolve_region(
+    reference_sequences: &ReferenceSequences,
+    region: &Region,
+) -> io::Result<(usize, Interval)> {
+    if let Some(r) = region.as_mapped() {
+        let i = reference_sequences.get_index_of(r.name()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "region reference sequence does not exist in reference sequences: {:?}",
+                    region
+                ),
+            )
+        })?;
+
+        Ok((i, r.interval()))
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "region is not mapped",
+        ))
+    }
+}
+
+fn container_offsets(
+    index: &[crai::Record],
+    reference_sequence_id: usize,
+    interval: Interval,
+) -> Vec<u64> {
+    let (start, end) = query::resolve_interval(interval);
+
+    let mut offsets: Vec<_> = index
+        .iter()
+        .filter(|record| {
+            let id = record
+                .reference_sequence_id()
+                .map(|id| i32::from(id) as usize);
+
+            let record_start = record.alignment_start();
+            let record_end = record_start + record.alignment_span() - 1;
+
+            id == Some(reference_sequence_id)
+                && query::in_interval(record_start, record_end, start, end)
+        })
+        .map(|record| record.offset())
+        .collect();
+
+    offsets.sort_unstable();
+    offsets.dedup();
+
+    offsets
 }
 
 #[cfg(test)]
 mod tests {
+    use std::ops::Bound;
+
     use crate::container::block::ContentType;
 
     use super::*;
@@ -350,6 +600,121 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_resume_at() -> io::Result<()> {
+        use std::io::Cursor;
+
+        use crate::Writer;
+
+        fn build_data(header: &sam::Header) -> io::Result<Vec<u8>> {
+            let mut writer = Writer::new(Vec::new(), Vec::new());
+            writer.write_file_definition()?;
+            writer.write_file_header(header)?;
+            writer.try_finish()?;
+            Ok(writer.get_ref().clone())
+        }
+
+        let data = build_data(&sam::Header::builder().add_comment("noodles").build())?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+        let checkpoint = reader.checkpoint()?;
+
+        let mut reader = Reader::resume_at(Cursor::new(&data), &checkpoint)?;
+        assert_eq!(reader.position()?, checkpoint.position());
+        assert!(reader.read_data_container()?.is_none());
+
+        let data = build_data(&sam::Header::builder().add_comment("cram").build())?;
+
+        assert!(matches!(
+            Reader::resume_at(Cursor::new(&data), &checkpoint),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_alignment_records_with_reference_sequence_repository(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use sam::AlignmentWriter;
+
+        use crate::Writer;
+
+        fn build_reference_sequences(sequence: &[u8]) -> Vec<fasta::Record> {
+            vec![fasta::Record::new(
+                fasta::record::Definition::new("sq0", None),
+                fasta::record::Sequence::from(sequence.to_vec()),
+            )]
+        }
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                sam::header::ReferenceSequence::builder()
+                    .set_name("sq0".parse()?)
+                    .set_length(8)
+                    .set_md5_checksum(
+                        [
+                            0xd7, 0xeb, 0xa3, 0x11, 0x42, 0x1b, 0xbc, 0x9d, 0x3a, 0xda, 0x44, 0x70,
+                            0x9d, 0xd6, 0x15, 0x34,
+                        ]
+                        .into(),
+                    )
+                    .build()?,
+            )
+            .build();
+
+        let sam_record: sam::Record =
+            "r0\t0\tsq0\t2\t37\t1S2M1I1D2M\t*\t0\t0\tTCTAAG\tNDLSAB".parse()?;
+
+        let mut writer = Writer::new(Vec::new(), build_reference_sequences(b"ACGTACGT"));
+        writer.write_alignment_header(&header)?;
+        writer.write_alignment_record(&header, &sam_record)?;
+        writer.finish(&header)?;
+        let data = writer.get_ref();
+
+        let repository = fasta::Repository::new(build_reference_sequences(b"ACGTACGT"));
+        let mut reader = Reader::builder(&data[..])
+            .set_reference_sequence_repository(repository)
+            .build();
+        let actual_header = reader.read_alignment_header()?;
+
+        let records = reader
+            .alignment_records(&actual_header)
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].sequence()?, sam_record.sequence().clone());
+
+        // The slice reference MD5 checksum does not match.
+        let repository = fasta::Repository::new(build_reference_sequences(b"ACCTACGT"));
+        let mut reader = Reader::builder(&data[..])
+            .set_reference_sequence_repository(repository)
+            .build();
+        let actual_header = reader.read_alignment_header()?;
+
+        assert!(matches!(
+            reader.alignment_records(&actual_header).next(),
+            Some(Err(e)) if matches!(
+                e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+                Some(Error::ReferenceSequenceChecksumMismatch { .. })
+            )
+        ));
+
+        // Without a repository, the bases are not resolved.
+        let mut reader = Reader::new(&data[..]);
+        let actual_header = reader.read_alignment_header()?;
+
+        let records = reader
+            .alignment_records(&actual_header)
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert!(records[0].sequence().is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_file_header_block() -> io::Result<()> {
         let expected = "noodles";
@@ -405,7 +770,39 @@ mod tests {
         let mut reader = &data[..];
         assert!(matches!(
             read_magic_number(&mut reader),
-            Err(ref e) if e.kind() == io::ErrorKind::InvalidData,
+            Err(ref e) if matches!(
+                e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+                Some(Error::InvalidMagicNumber(actual)) if actual == b"BAM\x01"
+            ),
         ));
     }
+
+    #[test]
+    fn test_container_offsets(
+    ) -> Result<(), noodles_bam::record::reference_sequence_id::TryFromIntError> {
+        use noodles_bam::record::ReferenceSequenceId;
+
+        let sq0 = ReferenceSequenceId::try_from(0).map(Some)?;
+        let sq1 = ReferenceSequenceId::try_from(1).map(Some)?;
+
+        let index = [
+            crai::Record::new(sq0, 1, 10, 233, 0, 0),
+            crai::Record::new(sq0, 11, 10, 377, 0, 0),
+            crai::Record::new(sq1, 1, 10, 377, 0, 0),
+            crai::Record::new(sq0, 21, 10, 610, 0, 0),
+            crai::Record::new(sq0, 21, 10, 610, 8, 0),
+            crai::Record::new(None, 0, 0, 987, 0, 0),
+        ];
+
+        let interval = (Bound::Included(15), Bound::Included(25));
+        assert_eq!(container_offsets(&index, 0, interval), [377, 610]);
+
+        let interval = (Bound::Included(15), Bound::Included(25));
+        assert_eq!(container_offsets(&index, 1, interval), []);
+
+        let interval = (Bound::Unbounded, Bound::Unbounded);
+        assert_eq!(container_offsets(&index, 1, interval), [377]);
+
+        Ok(())
+    }
 }
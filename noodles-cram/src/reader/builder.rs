@@ -0,0 +1,141 @@
+use std::io::Read;
+
+use noodles_bgzf as bgzf;
+use noodles_fasta as fasta;
+
+use super::Reader;
+
+/// A CRAM reader builder.
+pub struct Builder<R> {
+    inner: R,
+    worker_pool: Option<bgzf::WorkerPool>,
+    max_container_size: Option<usize>,
+    max_block_size: Option<usize>,
+    reference_sequence_repository: Option<fasta::Repository>,
+}
+
+impl<R> Builder<R>
+where
+    R: Read,
+{
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            worker_pool: None,
+            max_container_size: None,
+            max_block_size: None,
+            reference_sequence_repository: None,
+        }
+    }
+
+    /// Sets a worker pool.
+    ///
+    /// When set, the record iterator ([`Reader::records`]) reads data containers ahead and
+    /// decodes them in parallel on the worker pool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_cram as cram;
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(4).unwrap());
+    ///
+    /// let data = [];
+    /// let builder = cram::Reader::builder(&data[..]).set_worker_pool(worker_pool);
+    /// ```
+    pub fn set_worker_pool(mut self, worker_pool: bgzf::WorkerPool) -> Self {
+        self.worker_pool = Some(worker_pool);
+        self
+    }
+
+    /// Sets the maximum container size.
+    ///
+    /// This is the maximum length of the data of a container, in bytes. Containers that are
+    /// larger fail to read with [`crate::Error::ContainerTooLarge`] before any of their blocks are
+    /// read. This guards against large allocations when reading corrupt or untrusted input.
+    ///
+    /// By default, the container size is not limited. Regardless, blocks are never read past the
+    /// end of their container.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let builder = cram::Reader::builder(&data[..]).set_max_container_size(1 << 24);
+    /// ```
+    pub fn set_max_container_size(mut self, max_container_size: usize) -> Self {
+        self.max_container_size = Some(max_container_size);
+        self
+    }
+
+    /// Sets the maximum block size.
+    ///
+    /// This is the maximum length of the uncompressed data of a block, in bytes. Blocks that
+    /// declare a larger uncompressed size fail to read with [`crate::Error::BlockTooLarge`]
+    /// before their data is decompressed. This guards against large allocations when reading
+    /// corrupt or untrusted input.
+    ///
+    /// By default, the block size is not limited. Regardless, decompressing a block never
+    /// produces more data than its declared uncompressed size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let builder = cram::Reader::builder(&data[..]).set_max_block_size(1 << 24);
+    /// ```
+    pub fn set_max_block_size(mut self, max_block_size: usize) -> Self {
+        self.max_block_size = Some(max_block_size);
+        self
+    }
+
+    /// Sets a reference sequence repository.
+    ///
+    /// When set, the bases of records read by the alignment record iterator
+    /// ([`noodles_sam::AlignmentReader::alignment_records`]) and by queries ([`Reader::query`])
+    /// are resolved using reference sequences fetched from the repository. See
+    /// [`crate::data_container::Slice::resolve_bases`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_fasta::{self as fasta, repository::Repository};
+    ///
+    /// let repository = Repository::new(Vec::<fasta::Record>::new());
+    ///
+    /// let data = [];
+    /// let builder = cram::Reader::builder(&data[..]).set_reference_sequence_repository(repository);
+    /// ```
+    pub fn set_reference_sequence_repository(
+        mut self,
+        reference_sequence_repository: fasta::Repository,
+    ) -> Self {
+        self.reference_sequence_repository = Some(reference_sequence_repository);
+        self
+    }
+
+    /// Builds a CRAM reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let reader = cram::Reader::builder(&data[..]).build();
+    /// ```
+    pub fn build(self) -> Reader<R> {
+        Reader {
+            inner: self.inner,
+            worker_pool: self.worker_pool,
+            max_container_size: self.max_container_size,
+            max_block_size: self.max_block_size,
+            header_fingerprint: Default::default(),
+            reference_sequence_repository: self.reference_sequence_repository,
+        }
+    }
+}
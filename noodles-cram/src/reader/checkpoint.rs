@@ -0,0 +1,62 @@
+//! CRAM reader checkpoint.
+
+/// A CRAM reader checkpoint.
+///
+/// A checkpoint is the position of a reader at the start of a data container and a fingerprint of
+/// the file definition and header it read. It can be persisted (e.g., using the `serde` feature)
+/// and later passed to [`super::Reader::resume_at`] to continue reading from the same container,
+/// e.g., after a batch job is preempted.
+///
+/// The header fingerprint is used to check that the reader is resumed on the same file. It is
+/// stable across runs but is not a cryptographic hash.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Checkpoint {
+    position: u64,
+    header_fingerprint: u64,
+}
+
+impl Checkpoint {
+    /// Creates a CRAM reader checkpoint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::reader::Checkpoint;
+    /// let checkpoint = Checkpoint::new(8, 13);
+    /// ```
+    pub fn new(position: u64, header_fingerprint: u64) -> Self {
+        Self {
+            position,
+            header_fingerprint,
+        }
+    }
+
+    /// Returns the position of the reader.
+    ///
+    /// This is the byte offset of a data container from the start of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::reader::Checkpoint;
+    /// let checkpoint = Checkpoint::new(8, 13);
+    /// assert_eq!(checkpoint.position(), 8);
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the fingerprint of the file definition and header read by the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::reader::Checkpoint;
+    /// let checkpoint = Checkpoint::new(8, 13);
+    /// assert_eq!(checkpoint.header_fingerprint(), 13);
+    /// ```
+    pub fn header_fingerprint(&self) -> u64 {
+        self.header_fingerprint
+    }
+}
@@ -5,21 +5,76 @@ pub use self::{block::read_block, header::read_header};
 
 use std::io::{self, Read};
 
-use crate::Container;
+use crate::{container::Header, Container, Error};
 
-pub fn read_container<R>(reader: &mut R) -> io::Result<Container>
+pub fn read_container<R>(
+    reader: &mut R,
+    max_container_size: Option<usize>,
+    max_block_size: Option<usize>,
+) -> io::Result<Container>
 where
     R: Read,
 {
     let header = read_header(reader)?;
 
-    let blocks_len = header.block_count();
-    let mut blocks = Vec::with_capacity(blocks_len);
+    let len = container_len(&header, max_container_size)?;
+    let mut reader = reader.take(len);
 
-    for _ in 0..blocks_len {
-        let block = read_block(reader)?;
+    // The block count is not trusted to preallocate the list of blocks.
+    let mut blocks = Vec::new();
+
+    for _ in 0..header.block_count() {
+        let block = read_block(&mut reader, max_block_size)?;
         blocks.push(block);
     }
 
     Ok(Container::new(header, blocks))
 }
+
+/// Returns the length of the container data.
+///
+/// This fails if the container length is negative or larger than the given maximum container
+/// size.
+pub(crate) fn container_len(header: &Header, max_container_size: Option<usize>) -> io::Result<u64> {
+    let len = usize::try_from(header.len())
+        .map_err(|_| io::Error::from(Error::InvalidContainerLength(header.len())))?;
+
+    if let Some(max) = max_container_size {
+        if len > max {
+            return Err(Error::ContainerTooLarge { size: len, max }.into());
+        }
+    }
+
+    u64::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_container_len() -> io::Result<()> {
+        let header = Header::builder().set_length(13).build();
+        assert_eq!(container_len(&header, None)?, 13);
+        assert_eq!(container_len(&header, Some(13))?, 13);
+
+        assert!(matches!(
+            container_len(&header, Some(8)),
+            Err(ref e) if matches!(
+                e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+                Some(Error::ContainerTooLarge { size: 13, max: 8 })
+            )
+        ));
+
+        let header = Header::builder().set_length(-1).build();
+        assert!(matches!(
+            container_len(&header, None),
+            Err(ref e) if matches!(
+                e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+                Some(Error::InvalidContainerLength(-1))
+            )
+        ));
+
+        Ok(())
+    }
+}
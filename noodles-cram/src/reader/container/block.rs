@@ -8,9 +8,10 @@ use crate::{
         Block,
     },
     reader::num::read_itf8,
+    Error,
 };
 
-pub fn read_block<R>(reader: &mut R) -> io::Result<Block>
+pub fn read_block<R>(reader: &mut R, max_block_size: Option<usize>) -> io::Result<Block>
 where
     R: Read,
 {
@@ -32,8 +33,17 @@ where
         usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
 
-    let mut data = vec![0; size_in_bytes];
-    reader.read_exact(&mut data)?;
+    if let Some(max) = max_block_size {
+        if raw_size_in_bytes > max {
+            return Err(Error::BlockTooLarge {
+                size: raw_size_in_bytes,
+                max,
+            }
+            .into());
+        }
+    }
+
+    let data = read_data(reader, size_in_bytes)?;
 
     let crc32 = reader.read_u32::<LittleEndian>()?;
 
@@ -47,6 +57,26 @@ where
         .build())
 }
 
+// The buffer only grows as data is read, which avoids a large allocation when the size is invalid.
+fn read_data<R>(reader: &mut R, len: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let limit = u64::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let mut buf = Vec::new();
+    reader.take(limit).read_to_end(&mut buf)?;
+
+    if buf.len() < len {
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "failed to fill whole buffer",
+        ))
+    } else {
+        Ok(buf)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,7 +93,7 @@ mod tests {
             0xfd, 0x38, 0x27, 0xb5, // CRC32
         ];
         let mut reader = &data[..];
-        let actual = read_block(&mut reader)?;
+        let actual = read_block(&mut reader, None)?;
 
         let expected = Block::builder()
             .set_compression_method(CompressionMethod::None)
@@ -78,4 +108,25 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_block_with_max_block_size() {
+        let data = [
+            0x04, // compression method = rANS (4)
+            0x04, // content type = external data (4)
+            0x01, // block content ID = 1
+            0x00, // size in bytes = 0 bytes
+            0xf7, 0xff, 0xff, 0xff, 0x0f, // raw size in bytes = 2147483647 bytes
+        ];
+
+        let mut reader = &data[..];
+
+        assert!(matches!(
+            read_block(&mut reader, Some(1 << 16)),
+            Err(ref e) if matches!(
+                e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+                Some(Error::BlockTooLarge { size: 2147483647, max: 65536 })
+            )
+        ));
+    }
 }
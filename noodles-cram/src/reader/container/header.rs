@@ -67,7 +67,9 @@ where
     let len = read_itf8(reader).and_then(|n| {
         usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
-    let mut buf = Vec::with_capacity(len);
+
+    // The landmark count is not trusted to preallocate the list of landmarks.
+    let mut buf = Vec::new();
 
     for _ in 0..len {
         let pos = read_itf8(reader)?;
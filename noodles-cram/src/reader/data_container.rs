@@ -9,31 +9,22 @@ use std::io::{self, Read};
 use super::container;
 use crate::DataContainer;
 
-pub fn read_data_container<R>(reader: &mut R) -> io::Result<Option<DataContainer>>
+pub fn read_data_container<R>(
+    reader: &mut R,
+    max_container_size: Option<usize>,
+    max_block_size: Option<usize>,
+) -> io::Result<Option<DataContainer>>
 where
     R: Read,
 {
-    let header = container::read_header(reader)?;
-
-    if header.is_eof() {
-        return Ok(None);
-    }
-
-    let compression_header = read_compression_header_from_block(reader)?;
-
-    let slice_count = header.landmarks().len();
-    let mut slices = Vec::with_capacity(slice_count);
-
-    for _ in 0..slice_count {
-        let slice = read_slice(reader)?;
-        slices.push(slice);
-    }
-
-    Ok(Some(DataContainer::new(compression_header, slices)))
+    read_data_container_with_container_header(reader, max_container_size, max_block_size)
+        .map(|result| result.map(|(_, data_container)| data_container))
 }
 
 pub fn read_data_container_with_container_header<R>(
     reader: &mut R,
+    max_container_size: Option<usize>,
+    max_block_size: Option<usize>,
 ) -> io::Result<Option<(crate::container::Header, DataContainer)>>
 where
     R: Read,
@@ -44,13 +35,16 @@ where
         return Ok(None);
     }
 
-    let compression_header = read_compression_header_from_block(reader)?;
+    let len = container::container_len(&header, max_container_size)?;
+    let mut reader = reader.take(len);
+
+    let compression_header = read_compression_header_from_block(&mut reader, max_block_size)?;
 
-    let slice_count = header.landmarks().len();
-    let mut slices = Vec::with_capacity(slice_count);
+    // The slice count is not trusted to preallocate the list of slices.
+    let mut slices = Vec::new();
 
-    for _ in 0..slice_count {
-        let slice = read_slice(reader)?;
+    for _ in 0..header.landmarks().len() {
+        let slice = read_slice(&mut reader, max_block_size)?;
         slices.push(slice);
     }
 
@@ -59,13 +53,16 @@ where
     Ok(Some((header, data_container)))
 }
 
-fn read_compression_header_from_block<R>(reader: &mut R) -> io::Result<CompressionHeader>
+fn read_compression_header_from_block<R>(
+    reader: &mut R,
+    max_block_size: Option<usize>,
+) -> io::Result<CompressionHeader>
 where
     R: Read,
 {
     use super::container::read_block;
 
-    let block = read_block(reader)?;
+    let block = read_block(reader, max_block_size)?;
     let data = block.decompressed_data()?;
     let mut data_reader = &data[..];
     read_compression_header(&mut data_reader)
@@ -8,39 +8,52 @@ use crate::{
     container::Block,
     data_container::{slice, Slice},
     reader::container::read_block,
+    Error,
 };
 
-pub fn read_slice<R>(reader: &mut R) -> io::Result<Slice>
+pub fn read_slice<R>(reader: &mut R, max_block_size: Option<usize>) -> io::Result<Slice>
 where
     R: Read,
 {
-    let header = read_header_from_block(reader)?;
-    let core_data_block = read_block(reader)?;
+    let header = read_header_from_block(reader, max_block_size)?;
+    let core_data_block = read_block(reader, max_block_size)?;
 
-    let external_block_count = header.block_count() - 1;
-    let external_blocks = read_external_blocks(reader, external_block_count)?;
+    let external_block_count = header
+        .block_count()
+        .checked_sub(1)
+        .ok_or_else(|| io::Error::from(Error::InvalidSliceBlockCount(header.block_count())))?;
+
+    let external_blocks = read_external_blocks(reader, external_block_count, max_block_size)?;
 
     Ok(Slice::new(header, core_data_block, external_blocks))
 }
 
-fn read_header_from_block<R>(reader: &mut R) -> io::Result<slice::Header>
+fn read_header_from_block<R>(
+    reader: &mut R,
+    max_block_size: Option<usize>,
+) -> io::Result<slice::Header>
 where
     R: Read,
 {
-    let block = read_block(reader)?;
+    let block = read_block(reader, max_block_size)?;
     let data = block.decompressed_data()?;
     let mut data_reader = &data[..];
     read_header(&mut data_reader)
 }
 
-fn read_external_blocks<R>(reader: &mut R, len: usize) -> io::Result<Vec<Block>>
+fn read_external_blocks<R>(
+    reader: &mut R,
+    len: usize,
+    max_block_size: Option<usize>,
+) -> io::Result<Vec<Block>>
 where
     R: Read,
 {
-    let mut external_blocks = Vec::with_capacity(len);
+    // The block count is not trusted to preallocate the list of blocks.
+    let mut external_blocks = Vec::new();
 
     for _ in 0..len {
-        let block = read_block(reader)?;
+        let block = read_block(reader, max_block_size)?;
         external_blocks.push(block);
     }
 
@@ -0,0 +1,177 @@
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    ops::Bound,
+    vec,
+};
+
+use noodles_core::region::Interval;
+use noodles_sam::header::ReferenceSequences;
+
+use crate::Record;
+
+use super::{
+    records::{read_slice_records, resolve_records},
+    Reader,
+};
+
+/// An iterator over records of a CRAM reader that intersects a given region.
+///
+/// This is created by calling [`Reader::query`].
+pub struct Query<'a, R>
+where
+    R: Read + Seek,
+{
+    reader: &'a mut Reader<R>,
+    reference_sequences: &'a ReferenceSequences,
+
+    offsets: vec::IntoIter<u64>,
+    records: vec::IntoIter<Record>,
+
+    reference_sequence_id: usize,
+    start: i32,
+    end: i32,
+}
+
+impl<'a, R> Query<'a, R>
+where
+    R: Read + Seek,
+{
+    pub(super) fn new(
+        reader: &'a mut Reader<R>,
+        reference_sequences: &'a ReferenceSequences,
+        offsets: Vec<u64>,
+        reference_sequence_id: usize,
+        interval: Interval,
+    ) -> Self {
+        let (start, end) = resolve_interval(interval);
+
+        Self {
+            reader,
+            reference_sequences,
+
+            offsets: offsets.into_iter(),
+            records: Vec::new().into_iter(),
+
+            reference_sequence_id,
+            start,
+            end,
+        }
+    }
+
+    fn read_next_container(&mut self) -> io::Result<bool> {
+        let offset = match self.offsets.next() {
+            Some(offset) => offset,
+            None => return Ok(false),
+        };
+
+        self.reader.seek(SeekFrom::Start(offset))?;
+
+        if let Some(container) = self.reader.read_data_container()? {
+            let slice_records = read_slice_records(&container)?;
+
+            self.records = resolve_records(
+                self.reader.reference_sequence_repository.as_mut(),
+                Some(self.reference_sequences),
+                &container,
+                slice_records,
+            )?
+            .into_iter();
+        }
+
+        Ok(true)
+    }
+}
+
+impl<'a, R> Iterator for Query<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.records.next() {
+                Some(record) => {
+                    if intersects(&record, self.reference_sequence_id, self.start, self.end) {
+                        return Some(Ok(record));
+                    }
+                }
+                None => match self.read_next_container() {
+                    Ok(true) => {}
+                    Ok(false) => return None,
+                    Err(e) => return Some(Err(e)),
+                },
+            }
+        }
+    }
+}
+
+pub(super) fn resolve_interval(interval: Interval) -> (i32, i32) {
+    let start = match interval.0 {
+        Bound::Included(s) => s,
+        Bound::Excluded(s) => s.saturating_add(1),
+        Bound::Unbounded => 1,
+    };
+
+    let end = match interval.1 {
+        Bound::Included(e) => e,
+        Bound::Excluded(e) => e.saturating_sub(1),
+        Bound::Unbounded => i32::MAX,
+    };
+
+    (start, end)
+}
+
+fn intersects(
+    record: &Record,
+    reference_sequence_id: usize,
+    interval_start: i32,
+    interval_end: i32,
+) -> bool {
+    let id = match record.reference_sequence_id() {
+        Some(id) => i32::from(id) as usize,
+        None => return false,
+    };
+
+    let start = match record.alignment_start() {
+        Some(position) => i32::from(position),
+        None => return false,
+    };
+
+    let end = record.alignment_end();
+
+    id == reference_sequence_id && in_interval(start, end, interval_start, interval_end)
+}
+
+pub(super) fn in_interval(a_start: i32, a_end: i32, b_start: i32, b_end: i32) -> bool {
+    a_start <= b_end && b_start <= a_end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_interval() {
+        assert_eq!(
+            resolve_interval((Bound::Included(8), Bound::Included(13))),
+            (8, 13)
+        );
+        assert_eq!(
+            resolve_interval((Bound::Excluded(8), Bound::Excluded(13))),
+            (9, 12)
+        );
+        assert_eq!(
+            resolve_interval((Bound::Unbounded, Bound::Unbounded)),
+            (1, i32::MAX)
+        );
+    }
+
+    #[test]
+    fn test_in_interval() {
+        assert!(in_interval(8, 13, 13, 21));
+        assert!(in_interval(8, 13, 1, 8));
+        assert!(!in_interval(8, 13, 14, 21));
+        assert!(!in_interval(8, 13, 1, 7));
+    }
+}
@@ -1,12 +1,20 @@
 use std::{
+    collections::VecDeque,
     io::{self, Read},
     vec,
 };
 
-use crate::Record;
+use noodles_bgzf::worker_pool::Task;
+use noodles_fasta as fasta;
+use noodles_sam::header::ReferenceSequences;
+
+use crate::{DataContainer, Record};
 
 use super::Reader;
 
+// A data container and the records of each of its slices.
+type ContainerRecords = (DataContainer, Vec<Vec<Record>>);
+
 /// An iterator over records of a CRAM reader.
 ///
 /// This is created by calling [`Reader::records`].
@@ -15,41 +23,87 @@ where
     R: Read,
 {
     reader: &'a mut Reader<R>,
+    reference_sequences: Option<&'a ReferenceSequences>,
     records: vec::IntoIter<Record>,
+    tasks: VecDeque<Task<io::Result<ContainerRecords>>>,
+    is_eof: bool,
 }
 
 impl<'a, R> Records<'a, R>
 where
     R: Read,
 {
-    pub(crate) fn new(reader: &'a mut Reader<R>) -> Records<'_, R> {
+    // Creates a record iterator.
+    //
+    // The bases of records are resolved when `reference_sequences` is set and the reader has a
+    // reference sequence repository.
+    pub(crate) fn new(
+        reader: &'a mut Reader<R>,
+        reference_sequences: Option<&'a ReferenceSequences>,
+    ) -> Records<'a, R> {
         Self {
             reader,
+            reference_sequences,
             records: Vec::new().into_iter(),
+            tasks: VecDeque::new(),
+            is_eof: false,
         }
     }
 
     fn read_container_records(&mut self) -> io::Result<bool> {
-        let container = match self.reader.read_data_container()? {
-            Some(c) => c,
-            None => return Ok(true),
+        let worker_pool = match self.reader.worker_pool.clone() {
+            Some(worker_pool) => worker_pool,
+            None => {
+                let container = match self.reader.read_data_container()? {
+                    Some(c) => c,
+                    None => return Ok(true),
+                };
+
+                let slice_records = read_slice_records(&container)?;
+
+                self.records = resolve_records(
+                    self.reader.reference_sequence_repository.as_mut(),
+                    self.reference_sequences,
+                    &container,
+                    slice_records,
+                )?
+                .into_iter();
+
+                return Ok(false);
+            }
         };
 
-        self.records = container
-            .slices()
-            .iter()
-            .map(|slice| {
-                slice
-                    .records(container.compression_header())
-                    .map(|r| slice.resolve_mates(r))
-            })
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>()
-            .into_iter();
-
-        Ok(false)
+        // Keep twice as many containers in flight as there are workers so that workers are not
+        // idle while records are consumed.
+        while !self.is_eof && self.tasks.len() < 2 * worker_pool.worker_count().get() {
+            match self.reader.read_data_container()? {
+                Some(container) => {
+                    let task = worker_pool.spawn(move || {
+                        read_slice_records(&container).map(|records| (container, records))
+                    });
+                    self.tasks.push_back(task);
+                }
+                None => self.is_eof = true,
+            }
+        }
+
+        match self.tasks.pop_front() {
+            Some(task) => {
+                // Bases are resolved on this thread, as the repository is owned by the reader.
+                let (container, slice_records) = task.wait()?;
+
+                self.records = resolve_records(
+                    self.reader.reference_sequence_repository.as_mut(),
+                    self.reference_sequences,
+                    &container,
+                    slice_records,
+                )?
+                .into_iter();
+
+                Ok(false)
+            }
+            None => Ok(true),
+        }
     }
 }
 
@@ -72,3 +126,40 @@ where
         }
     }
 }
+
+// Reads the records of each slice in a container, resolving mates.
+pub(super) fn read_slice_records(container: &DataContainer) -> io::Result<Vec<Vec<Record>>> {
+    container
+        .slices()
+        .iter()
+        .map(|slice| {
+            slice
+                .records(container.compression_header())
+                .map(|r| slice.resolve_mates(r))
+        })
+        .collect()
+}
+
+// Resolves the bases of the records of each slice in a container, if given a repository and
+// reference sequences, and flattens the records.
+pub(super) fn resolve_records(
+    reference_sequence_repository: Option<&mut fasta::Repository>,
+    reference_sequences: Option<&ReferenceSequences>,
+    container: &DataContainer,
+    mut slice_records: Vec<Vec<Record>>,
+) -> io::Result<Vec<Record>> {
+    if let (Some(repository), Some(reference_sequences)) =
+        (reference_sequence_repository, reference_sequences)
+    {
+        for (slice, records) in container.slices().iter().zip(&mut slice_records) {
+            slice.resolve_bases(
+                repository,
+                reference_sequences,
+                container.compression_header(),
+                records,
+            )?;
+        }
+    }
+
+    Ok(slice_records.into_iter().flatten().collect())
+}
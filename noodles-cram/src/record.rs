@@ -14,10 +14,13 @@ pub use self::{
     read_group_id::ReadGroupId, tag::Tag,
 };
 
-use std::{fmt, str};
+use std::{fmt, io, str};
 
 use noodles_bam as bam;
-use noodles_sam as sam;
+use noodles_sam::{
+    self as sam,
+    header::{ReferenceSequence, ReferenceSequences},
+};
 
 /// A CRAM record.
 #[derive(Clone, PartialEq)]
@@ -175,6 +178,274 @@ impl Record {
     }
 }
 
+impl sam::AlignmentRecord for Record {
+    /// Returns the read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = cram::Record::default();
+    /// assert!(AlignmentRecord::read_name(&record).is_none());
+    /// ```
+    fn read_name(&self) -> Option<&[u8]> {
+        const MISSING: &[u8] = b"*";
+
+        if self.read_name.is_empty() || self.read_name == MISSING {
+            None
+        } else {
+            Some(&self.read_name)
+        }
+    }
+
+    /// Returns the flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// let record = cram::Record::default();
+    /// assert_eq!(AlignmentRecord::flags(&record), sam::record::Flags::UNMAPPED);
+    /// ```
+    fn flags(&self) -> sam::record::Flags {
+        self.bam_flags()
+    }
+
+    /// Returns the associated reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_sam::{header::ReferenceSequences, AlignmentRecord};
+    ///
+    /// let record = cram::Record::default();
+    /// let reference_sequences = ReferenceSequences::default();
+    ///
+    /// assert!(record.reference_sequence(&reference_sequences).is_none());
+    /// ```
+    fn reference_sequence<'rs>(
+        &self,
+        reference_sequences: &'rs ReferenceSequences,
+    ) -> Option<io::Result<&'rs ReferenceSequence>> {
+        get_reference_sequence(reference_sequences, self.reference_sequence_id())
+    }
+
+    /// Returns the start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = cram::Record::default();
+    /// assert!(AlignmentRecord::alignment_start(&record).is_none());
+    /// ```
+    fn alignment_start(&self) -> Option<sam::record::Position> {
+        self.alignment_start()
+    }
+
+    /// Calculates the alignment span over the reference sequence.
+    ///
+    /// This is 0 if the record is unmapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = cram::Record::default();
+    /// assert_eq!(record.alignment_span()?, 0);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn alignment_span(&self) -> io::Result<u32> {
+        if self.bam_flags().is_unmapped() {
+            return Ok(0);
+        }
+
+        let read_length = i32::try_from(self.read_length())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let alignment_span = calculate_alignment_span(read_length, self.features());
+        u32::try_from(alignment_span).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns the mapping quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = cram::Record::default();
+    /// assert!(AlignmentRecord::mapping_quality(&record).is_none());
+    /// ```
+    fn mapping_quality(&self) -> Option<sam::record::MappingQuality> {
+        self.mapping_quality()
+    }
+
+    /// Returns the associated reference sequence of the mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_sam::{header::ReferenceSequences, AlignmentRecord};
+    ///
+    /// let record = cram::Record::default();
+    /// let reference_sequences = ReferenceSequences::default();
+    ///
+    /// assert!(record.mate_reference_sequence(&reference_sequences).is_none());
+    /// ```
+    fn mate_reference_sequence<'rs>(
+        &self,
+        reference_sequences: &'rs ReferenceSequences,
+    ) -> Option<io::Result<&'rs ReferenceSequence>> {
+        get_reference_sequence(
+            reference_sequences,
+            self.next_fragment_reference_sequence_id(),
+        )
+    }
+
+    /// Returns the start position of the mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = cram::Record::default();
+    /// assert!(record.mate_alignment_start().is_none());
+    /// ```
+    fn mate_alignment_start(&self) -> Option<sam::record::Position> {
+        self.next_mate_alignment_start()
+    }
+
+    /// Returns the template length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = cram::Record::default();
+    /// assert_eq!(record.template_length(), 0);
+    /// ```
+    fn template_length(&self) -> i32 {
+        self.template_size()
+    }
+
+    /// Returns the CIGAR operations.
+    ///
+    /// The CIGAR is resolved from the read features. This is empty if the record is unmapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = cram::Record::default();
+    /// assert!(AlignmentRecord::cigar(&record)?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn cigar(&self) -> io::Result<sam::record::Cigar> {
+        if self.bam_flags().is_unmapped() {
+            return Ok(sam::record::Cigar::default());
+        }
+
+        let read_length = i32::try_from(self.read_length())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(resolve::resolve_features(self.features(), read_length))
+    }
+
+    /// Returns the read bases.
+    ///
+    /// The bases of mapped records are typically stored as differences to the reference sequence
+    /// and can only be resolved with the reference assembly. If they are not resolved, e.g., by a
+    /// reader with a reference sequence repository, this returns an error. Use
+    /// [`Self::try_into_sam_record`] to resolve the bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = cram::Record::default();
+    /// assert!(AlignmentRecord::sequence(&record)?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn sequence(&self) -> io::Result<sam::record::Sequence> {
+        if self.bases().is_empty()
+            && self.read_length() > 0
+            && !self.bam_flags().is_unmapped()
+            && !self.flags().decode_sequence_as_unknown()
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "sequence requires the reference assembly to be resolved",
+            ));
+        }
+
+        convert::bytes_to_sequence(self.bases())
+    }
+
+    /// Returns the quality scores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = cram::Record::default();
+    /// assert!(AlignmentRecord::quality_scores(&record)?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn quality_scores(&self) -> io::Result<sam::record::QualityScores> {
+        convert::bytes_to_quality_scores(self.quality_scores())
+    }
+
+    /// Returns the optional data fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// use noodles_sam::AlignmentRecord;
+    /// let record = cram::Record::default();
+    /// assert!(AlignmentRecord::data(&record)?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn data(&self) -> io::Result<sam::record::Data> {
+        convert::tags_to_data(self.tags())
+    }
+}
+
+fn get_reference_sequence(
+    reference_sequences: &ReferenceSequences,
+    reference_sequence_id: Option<bam::record::ReferenceSequenceId>,
+) -> Option<io::Result<&ReferenceSequence>> {
+    reference_sequence_id.map(|reference_sequence_id| {
+        usize::try_from(i32::from(reference_sequence_id))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            .and_then(|i| {
+                reference_sequences
+                    .get_index(i)
+                    .map(|(_, rs)| rs)
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidData, "invalid reference sequence ID")
+                    })
+            })
+    })
+}
+
 impl Default for Record {
     fn default() -> Self {
         Builder::default().build()
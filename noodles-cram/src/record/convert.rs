@@ -1,21 +1,164 @@
 use std::{io, str};
 
-use noodles_bam::record::ReferenceSequenceId;
+use noodles_bam::{self as bam, record::ReferenceSequenceId};
 use noodles_fasta as fasta;
 use noodles_sam::{
     self as sam,
-    record::{Data, QualityScores, Sequence},
+    record::{Cigar, Data, QualityScores, Sequence},
 };
 
 use crate::data_container::CompressionHeader;
 
 use super::{
     resolve::{resolve_bases, resolve_features},
-    Record, Tag,
+    tag, Feature, Flags, NextMateFlags, Record, Tag,
 };
 
 impl Record {
+    /// Converts a SAM record to a CRAM record.
+    ///
+    /// Read features are built from the CIGAR operations, where mismatches are compared against
+    /// the given reference sequences. Records are always marked as detached, i.e., mate
+    /// information is stored in the record itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// use noodles_sam as sam;
+    ///
+    /// let reference_sequences = sam::header::ReferenceSequences::default();
+    /// let sam_record = sam::Record::default();
+    ///
+    /// let record = cram::Record::try_from_sam_record(&[], &reference_sequences, &sam_record)?;
+    /// assert!(record.bam_flags().is_unmapped());
+    /// assert_eq!(record.read_name(), b"*");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn try_from_sam_record(
+        reference_assembly: &[fasta::Record],
+        reference_sequences: &sam::header::ReferenceSequences,
+        sam_record: &sam::Record,
+    ) -> io::Result<Self> {
+        let bam_record = bam::Record::try_from_sam_record(reference_sequences, sam_record)?;
+
+        let mut builder = Self::builder()
+            .set_bam_flags(sam_record.flags())
+            .set_read_name(
+                sam_record
+                    .read_name()
+                    .map(|name| name.as_bytes().to_vec())
+                    .unwrap_or_else(|| b"*".to_vec()),
+            )
+            .set_template_size(sam_record.template_length());
+
+        if let Some(reference_sequence_id) = bam_record.reference_sequence_id() {
+            builder = builder.set_reference_sequence_id(reference_sequence_id);
+        }
+
+        if let Some(alignment_start) = sam_record.position() {
+            builder = builder.set_alignment_start(alignment_start);
+        }
+
+        if let Some(mapping_quality) = sam_record.mapping_quality() {
+            builder = builder.set_mapping_quality(mapping_quality);
+        }
+
+        let mut next_mate_flags = NextMateFlags::default();
+
+        if sam_record.flags().is_mate_reverse_complemented() {
+            next_mate_flags |= NextMateFlags::ON_NEGATIVE_STRAND;
+        }
+
+        if sam_record.flags().is_mate_unmapped() {
+            next_mate_flags |= NextMateFlags::UNMAPPED;
+        }
+
+        builder = builder.set_next_mate_flags(next_mate_flags);
+
+        if let Some(mate_reference_sequence_id) = bam_record.mate_reference_sequence_id() {
+            builder = builder.set_next_fragment_reference_sequence_id(mate_reference_sequence_id);
+        }
+
+        if let Some(mate_position) = sam_record.mate_position() {
+            builder = builder.set_next_mate_alignment_start(mate_position);
+        }
+
+        let mut flags = Flags::DETACHED;
+
+        let bases: Vec<u8> = sam_record
+            .sequence()
+            .iter()
+            .map(|&base| char::from(base) as u8)
+            .collect();
+
+        let quality_scores: Vec<u8> = sam_record
+            .quality_scores()
+            .iter()
+            .map(|&score| u8::from(score))
+            .collect();
+
+        if !quality_scores.is_empty() {
+            flags |= Flags::QUALITY_SCORES_STORED_AS_ARRAY;
+        }
+
+        let is_mapped = !sam_record.flags().is_unmapped();
+
+        if is_mapped && bases.is_empty() {
+            flags |= Flags::DECODE_SEQUENCE_AS_UNKNOWN;
+            builder = builder.set_read_length(sam_record.cigar().read_len() as usize);
+        } else {
+            builder = builder.set_read_length(bases.len());
+        }
+
+        if is_mapped {
+            if let Some(reference_sequence_id) = bam_record.reference_sequence_id() {
+                let reference_sequence = usize::try_from(i32::from(reference_sequence_id))
+                    .ok()
+                    .and_then(|id| reference_assembly.get(id))
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidInput, "missing reference sequence")
+                    })?;
+
+                let alignment_start = sam_record.position().map(i32::from).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "missing alignment start")
+                })?;
+
+                let features = build_features(
+                    reference_sequence.sequence().as_ref(),
+                    alignment_start,
+                    sam_record.cigar(),
+                    &bases,
+                    &quality_scores,
+                )?;
+
+                builder = builder.set_features(features);
+            }
+        }
+
+        let mut tags = Vec::new();
+
+        for result in bam_record.data().values() {
+            let field = result?;
+            let value = field.value();
+            let key = tag::Key::new(*field.tag().as_ref(), value.ty());
+            tags.push(Tag::new(key, value.clone()));
+        }
+
+        Ok(builder
+            .set_flags(flags)
+            .set_tags(tags)
+            .set_bases(bases)
+            .set_quality_scores(quality_scores)
+            .build())
+    }
+
     /// Converts this CRAM record to a SAM record.
+    ///
+    /// If the read bases are not yet resolved (see [`crate::data_container::Slice::resolve_bases`]),
+    /// they are resolved using the reference sequence in `reference_assembly` at the record's
+    /// reference sequence ID.
     pub fn try_into_sam_record(
         &self,
         reference_assembly: &[fasta::Record],
@@ -68,7 +211,12 @@ impl Record {
         builder = builder.set_template_length(self.template_size());
 
         if self.read_length() > 0 {
-            let sequence = if let Some(reference_sequence_id) = self.reference_sequence_id() {
+            // The bases may already be resolved, e.g., by a reader with a reference sequence
+            // repository.
+            let sequence = if let Some(reference_sequence_id) = self
+                .reference_sequence_id()
+                .filter(|_| self.bases().is_empty())
+            {
                 let reference_sequence_record = usize::try_from(i32::from(reference_sequence_id))
                     .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
                     .and_then(|id| {
@@ -109,6 +257,121 @@ impl Record {
     }
 }
 
+fn build_features(
+    reference_sequence: &[u8],
+    alignment_start: i32,
+    cigar: &Cigar,
+    bases: &[u8],
+    quality_scores: &[u8],
+) -> io::Result<Vec<Feature>> {
+    use sam::record::cigar::op::Kind;
+
+    const MISSING_QUALITY_SCORE: u8 = 0xff;
+
+    fn missing_bases() -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidInput, "sequence length mismatch")
+    }
+
+    let mut features = Vec::new();
+
+    let mut ref_pos = usize::try_from(alignment_start - 1)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut read_pos = 0;
+
+    for op in cigar.iter() {
+        let len = op.len() as usize;
+
+        // Read feature positions are 1-based.
+        let position = (read_pos + 1) as i32;
+
+        match op.kind() {
+            Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                if !bases.is_empty() {
+                    let read_bases = bases
+                        .get(read_pos..read_pos + len)
+                        .ok_or_else(missing_bases)?;
+
+                    let reference_bases = reference_sequence
+                        .get(ref_pos..ref_pos + len)
+                        .ok_or_else(|| {
+                            io::Error::new(
+                                io::ErrorKind::InvalidInput,
+                                "alignment extends past the end of the reference sequence",
+                            )
+                        })?;
+
+                    for (i, (&read_base, &reference_base)) in
+                        read_bases.iter().zip(reference_bases).enumerate()
+                    {
+                        if !read_base.eq_ignore_ascii_case(&reference_base) {
+                            let quality_score = quality_scores
+                                .get(read_pos + i)
+                                .copied()
+                                .unwrap_or(MISSING_QUALITY_SCORE);
+
+                            features.push(Feature::ReadBase(
+                                position + i as i32,
+                                read_base,
+                                quality_score,
+                            ));
+                        }
+                    }
+                }
+
+                ref_pos += len;
+                read_pos += len;
+            }
+            Kind::Insertion => {
+                let read_bases = if bases.is_empty() {
+                    vec![b'N'; len]
+                } else {
+                    bases
+                        .get(read_pos..read_pos + len)
+                        .map(|b| b.to_vec())
+                        .ok_or_else(missing_bases)?
+                };
+
+                if let [base] = read_bases[..] {
+                    features.push(Feature::InsertBase(position, base));
+                } else {
+                    features.push(Feature::Insertion(position, read_bases));
+                }
+
+                read_pos += len;
+            }
+            Kind::Deletion => {
+                features.push(Feature::Deletion(position, len as i32));
+                ref_pos += len;
+            }
+            Kind::Skip => {
+                features.push(Feature::ReferenceSkip(position, len as i32));
+                ref_pos += len;
+            }
+            Kind::SoftClip => {
+                let read_bases = if bases.is_empty() {
+                    vec![b'N'; len]
+                } else {
+                    bases
+                        .get(read_pos..read_pos + len)
+                        .map(|b| b.to_vec())
+                        .ok_or_else(missing_bases)?
+                };
+
+                features.push(Feature::SoftClip(position, read_bases));
+                read_pos += len;
+            }
+            Kind::HardClip => {
+                features.push(Feature::HardClip(position, len as i32));
+            }
+            Kind::Pad => {
+                features.push(Feature::Padding(position, len as i32));
+            }
+        }
+    }
+
+    Ok(features)
+}
+
 fn get_reference_sequence_name(
     reference_sequences: &sam::header::ReferenceSequences,
     reference_sequence_id: Option<ReferenceSequenceId>,
@@ -126,7 +389,7 @@ fn get_reference_sequence_name(
         .transpose()
 }
 
-fn bytes_to_sequence(data: &[u8]) -> io::Result<Sequence> {
+pub(super) fn bytes_to_sequence(data: &[u8]) -> io::Result<Sequence> {
     use sam::record::sequence::Base;
 
     data.iter()
@@ -137,7 +400,7 @@ fn bytes_to_sequence(data: &[u8]) -> io::Result<Sequence> {
         .map(Sequence::from)
 }
 
-fn bytes_to_quality_scores(data: &[u8]) -> io::Result<QualityScores> {
+pub(super) fn bytes_to_quality_scores(data: &[u8]) -> io::Result<QualityScores> {
     use sam::record::quality_scores::Score;
 
     data.iter()
@@ -148,7 +411,7 @@ fn bytes_to_quality_scores(data: &[u8]) -> io::Result<QualityScores> {
         .map(QualityScores::from)
 }
 
-fn tags_to_data(tags: &[Tag]) -> io::Result<Data> {
+pub(super) fn tags_to_data(tags: &[Tag]) -> io::Result<Data> {
     use sam::record::data::Field;
 
     let mut fields = Vec::with_capacity(tags.len());
@@ -171,3 +434,78 @@ fn tags_to_data(tags: &[Tag]) -> io::Result<Data> {
 
     Data::try_from(fields).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_features() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequence = b"ACGTACGT";
+
+        let cigar = "1S2M1I1D2M".parse()?;
+        let features = build_features(reference_sequence, 2, &cigar, b"TCTAAG", &[])?;
+
+        assert_eq!(
+            features,
+            [
+                Feature::SoftClip(1, vec![b'T']),
+                Feature::ReadBase(3, b'T', 0xff),
+                Feature::InsertBase(4, b'A'),
+                Feature::Deletion(5, 1),
+                Feature::ReadBase(6, b'G', 0xff),
+            ]
+        );
+
+        let cigar = "4M".parse()?;
+        assert!(build_features(reference_sequence, 6, &cigar, b"ACGT", &[]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_sam_record() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::header::ReferenceSequence;
+
+        let reference_assembly = [fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"ACGTACGT".to_vec()),
+        )];
+
+        let reference_sequences = [ReferenceSequence::new("sq0".parse()?, 8)?]
+            .into_iter()
+            .map(|reference_sequence| (reference_sequence.name().to_string(), reference_sequence))
+            .collect();
+
+        let sam_record = sam::Record::builder()
+            .set_read_name("r0".parse()?)
+            .set_flags(sam::record::Flags::empty())
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(sam::record::Position::try_from(2)?)
+            .set_cigar("4M".parse()?)
+            .set_sequence("CGAT".parse()?)
+            .set_quality_scores("NDLS".parse()?)
+            .build()?;
+
+        let record =
+            Record::try_from_sam_record(&reference_assembly, &reference_sequences, &sam_record)?;
+
+        assert_eq!(record.read_name(), b"r0");
+        assert_eq!(
+            record.reference_sequence_id(),
+            Some(ReferenceSequenceId::try_from(0)?)
+        );
+        assert_eq!(record.read_length(), 4);
+        assert!(record.flags().is_detached());
+        assert!(record.flags().are_quality_scores_stored_as_array());
+        assert_eq!(
+            record.features(),
+            [
+                Feature::ReadBase(3, b'A', 43),
+                Feature::ReadBase(4, b'T', 50)
+            ]
+        );
+
+        Ok(())
+    }
+}
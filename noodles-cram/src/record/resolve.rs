@@ -57,6 +57,18 @@ mod internal {
             }
 
             match feature {
+                Feature::Bases(_, bases) => {
+                    for &base in bases {
+                        buf[read_pos] = base;
+                        ref_pos += 1;
+                        read_pos += 1;
+                    }
+                }
+                Feature::ReadBase(_, base, _) => {
+                    buf[read_pos] = *base;
+                    ref_pos += 1;
+                    read_pos += 1;
+                }
                 Feature::Substitution(_, code) => {
                     let base = raw_reference_sequence[ref_pos] as char;
                     let reference_base = Base::try_from(base).unwrap_or_default();
@@ -105,7 +117,7 @@ mod internal {
 
 /// Resolves the read features as CIGAR operations.
 pub fn resolve_features(features: &[Feature], read_len: i32) -> Cigar {
-    use noodles_sam::record::cigar::{op::Kind, Op};
+    use noodles_sam::record::cigar::op::Kind;
 
     let mut ops = Vec::new();
     let mut i = 1;
@@ -113,8 +125,7 @@ pub fn resolve_features(features: &[Feature], read_len: i32) -> Cigar {
     for feature in features {
         if feature.position() > i {
             let len = feature.position() - i;
-            let op = Op::new(Kind::Match, len as u32);
-            ops.push(op);
+            push_op(&mut ops, Kind::Match, len as u32);
 
             i = feature.position();
         }
@@ -131,8 +142,7 @@ pub fn resolve_features(features: &[Feature], read_len: i32) -> Cigar {
             _ => continue,
         };
 
-        let op = Op::new(kind, len as u32);
-        ops.push(op);
+        push_op(&mut ops, kind, len as u32);
 
         if matches!(
             kind,
@@ -144,13 +154,32 @@ pub fn resolve_features(features: &[Feature], read_len: i32) -> Cigar {
 
     if i <= read_len {
         let len = read_len - i + 1;
-        let op = Op::new(Kind::Match, len as u32);
-        ops.push(op);
+        push_op(&mut ops, Kind::Match, len as u32);
     }
 
     Cigar::from(ops)
 }
 
+// Adds an operation, merging it with the previous one if they are both alignment matches.
+fn push_op(
+    ops: &mut Vec<noodles_sam::record::cigar::Op>,
+    kind: noodles_sam::record::cigar::op::Kind,
+    len: u32,
+) {
+    use noodles_sam::record::cigar::{op::Kind, Op};
+
+    if kind == Kind::Match {
+        if let Some(last_op) = ops.last_mut() {
+            if last_op.kind() == Kind::Match {
+                *last_op = Op::new(Kind::Match, last_op.len() + len);
+                return;
+            }
+        }
+    }
+
+    ops.push(Op::new(kind, len));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,6 +196,8 @@ mod tests {
         };
 
         t(&[], b"ACGT");
+        t(&[Feature::Bases(2, b"TT".to_vec())], b"ATTT");
+        t(&[Feature::ReadBase(2, b'T', 45)], b"ATGT");
         t(&[Feature::Substitution(2, 1)], b"AGGT");
         t(&[Feature::Insertion(2, b"GG".to_vec())], b"AGGC");
         t(&[Feature::Deletion(2, 2)], b"ATAC");
@@ -204,29 +235,28 @@ mod tests {
             Cigar::from(vec![Op::new(Kind::HardClip, 2), Op::new(Kind::Match, 4)]),
         );
 
-        // FIXME
         let features = [
             Feature::SoftClip(1, b"A".to_vec()),
             Feature::Substitution(3, 0),
         ];
         assert_eq!(
             resolve_features(&features, 4),
-            Cigar::from(vec![
-                Op::new(Kind::SoftClip, 1),
-                Op::new(Kind::Match, 1),
-                Op::new(Kind::Match, 1),
-                Op::new(Kind::Match, 1),
-            ])
+            Cigar::from(vec![Op::new(Kind::SoftClip, 1), Op::new(Kind::Match, 3)])
         );
 
-        // FIXME
         let features = [Feature::Substitution(2, 0)];
+        assert_eq!(
+            resolve_features(&features, 4),
+            Cigar::from(vec![Op::new(Kind::Match, 4)])
+        );
+
+        let features = [Feature::ReadBase(2, b'T', 45), Feature::InsertBase(3, b'A')];
         assert_eq!(
             resolve_features(&features, 4),
             Cigar::from(vec![
-                Op::new(Kind::Match, 1),
-                Op::new(Kind::Match, 1),
-                Op::new(Kind::Match, 2)
+                Op::new(Kind::Match, 2),
+                Op::new(Kind::Insertion, 1),
+                Op::new(Kind::Match, 1)
             ])
         );
     }
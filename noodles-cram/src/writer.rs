@@ -1,13 +1,21 @@
+//! CRAM writer.
+
+mod builder;
 mod container;
 pub(crate) mod data_container;
 pub(crate) mod num;
 pub(crate) mod record;
 
+pub use self::builder::Builder;
+
 use std::{
+    collections::VecDeque,
     io::{self, Write},
     mem,
+    sync::Arc,
 };
 
+use noodles_bgzf as bgzf;
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
@@ -44,9 +52,11 @@ where
     W: Write,
 {
     inner: W,
-    reference_sequences: Vec<fasta::Record>,
+    reference_sequences: Arc<Vec<fasta::Record>>,
     data_container_builder: crate::data_container::Builder,
     record_counter: i64,
+    worker_pool: Option<bgzf::WorkerPool>,
+    tasks: VecDeque<bgzf::worker_pool::Task<io::Result<Vec<u8>>>>,
 }
 
 impl<W> Writer<W>
@@ -62,12 +72,20 @@ where
     /// let writer = cram::Writer::new(Vec::new(), Vec::new());
     /// ```
     pub fn new(inner: W, reference_sequences: Vec<fasta::Record>) -> Self {
-        Self {
-            inner,
-            reference_sequences,
-            data_container_builder: DataContainer::builder(RECORD_COUNTER_START),
-            record_counter: RECORD_COUNTER_START,
-        }
+        Self::builder(inner, reference_sequences).build()
+    }
+
+    /// Creates a CRAM writer builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::Writer::builder(Vec::new(), Vec::new());
+    /// let writer = builder.build();
+    /// ```
+    pub fn builder(inner: W, reference_sequences: Vec<fasta::Record>) -> Builder<W> {
+        Builder::new(inner, reference_sequences)
     }
 
     /// Returns a reference to the underlying writer.
@@ -100,6 +118,12 @@ where
     /// ```
     pub fn try_finish(&mut self) -> io::Result<()> {
         self.flush()?;
+
+        while let Some(task) = self.tasks.pop_front() {
+            let buf = task.wait()?;
+            self.inner.write_all(&buf)?;
+        }
+
         let eof_container = Container::eof();
         write_container(&mut self.inner, &eof_container)
     }
@@ -215,14 +239,63 @@ where
             DataContainer::builder(self.record_counter),
         );
 
-        let base_count = data_container_builder.base_count();
+        let worker_pool = match &self.worker_pool {
+            Some(worker_pool) => worker_pool,
+            None => {
+                return build_container(data_container_builder, &self.reference_sequences)
+                    .and_then(|container| write_container(&mut self.inner, &container));
+            }
+        };
 
-        data_container_builder
-            .build(&self.reference_sequences)
-            .and_then(|data_container| {
-                Container::try_from_data_container(&data_container, base_count)
-            })
-            .and_then(|container| write_container(&mut self.inner, &container))
+        if self.tasks.len() >= 2 * worker_pool.worker_count().get() {
+            if let Some(task) = self.tasks.pop_front() {
+                let buf = task.wait()?;
+                self.inner.write_all(&buf)?;
+            }
+        }
+
+        let reference_sequences = self.reference_sequences.clone();
+
+        let task = worker_pool.spawn(move || {
+            let container = build_container(data_container_builder, &reference_sequences)?;
+            let mut buf = Vec::new();
+            write_container(&mut buf, &container)?;
+            Ok(buf)
+        });
+
+        self.tasks.push_back(task);
+
+        Ok(())
+    }
+}
+
+impl<W> sam::AlignmentWriter for Writer<W>
+where
+    W: Write,
+{
+    fn write_alignment_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.write_file_definition()?;
+        self.write_file_header(header)
+    }
+
+    fn write_alignment_record(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn sam::AlignmentRecord,
+    ) -> io::Result<()> {
+        let reference_sequences = header.reference_sequences();
+        let sam_record = sam::Record::try_from_alignment_record(reference_sequences, record)?;
+        let record = Record::try_from_sam_record(
+            &self.reference_sequences,
+            reference_sequences,
+            &sam_record,
+        )?;
+
+        self.write_record(record)
+    }
+
+    fn finish(&mut self, _: &sam::Header) -> io::Result<()> {
+        self.try_finish()
     }
 }
 
@@ -235,6 +308,17 @@ where
     }
 }
 
+fn build_container(
+    data_container_builder: crate::data_container::Builder,
+    reference_sequences: &[fasta::Record],
+) -> io::Result<Container> {
+    let base_count = data_container_builder.base_count();
+
+    data_container_builder
+        .build(reference_sequences)
+        .and_then(|data_container| Container::try_from_data_container(&data_container, base_count))
+}
+
 fn add_record(
     data_container_builder: &mut crate::data_container::Builder,
     reference_sequences: &[fasta::Record],
@@ -257,3 +341,131 @@ where
     let format = [version.major(), version.minor()];
     writer.write_all(&format)
 }
+
+#[cfg(test)]
+mod tests {
+    use sam::AlignmentWriter;
+
+    use super::*;
+
+    #[test]
+    fn test_write_alignment_record() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::Reader;
+
+        let reference_sequences = vec![fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"ACGTACGT".to_vec()),
+        )];
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                sam::header::ReferenceSequence::builder()
+                    .set_name("sq0".parse()?)
+                    .set_length(8)
+                    .set_md5_checksum(
+                        [
+                            0xd7, 0xeb, 0xa3, 0x11, 0x42, 0x1b, 0xbc, 0x9d, 0x3a, 0xda, 0x44, 0x70,
+                            0x9d, 0xd6, 0x15, 0x34,
+                        ]
+                        .into(),
+                    )
+                    .build()?,
+            )
+            .build();
+
+        let sam_record: sam::Record =
+            "r0\t0\tsq0\t2\t37\t1S2M1I1D2M\t*\t0\t0\tTCTAAG\tNDLSAB".parse()?;
+
+        let mut writer = Writer::new(Vec::new(), reference_sequences.clone());
+        writer.write_alignment_header(&header)?;
+        writer.write_alignment_record(&header, &sam_record)?;
+        writer.finish(&header)?;
+
+        let mut reader = Reader::new(&writer.get_ref()[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let mut records = Vec::new();
+
+        while let Some(container) = reader.read_data_container()? {
+            for slice in container.slices() {
+                for record in slice.records(container.compression_header())? {
+                    records.push(record.try_into_sam_record(
+                        &reference_sequences,
+                        header.reference_sequences(),
+                        container.compression_header(),
+                    )?);
+                }
+            }
+        }
+
+        assert_eq!(records, [sam_record]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_worker_pool() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use crate::Reader;
+
+        fn write(records: &[Record], worker_pool: Option<bgzf::WorkerPool>) -> io::Result<Vec<u8>> {
+            let mut builder = Writer::builder(Vec::new(), Vec::new());
+
+            if let Some(worker_pool) = worker_pool {
+                builder = builder.set_worker_pool(worker_pool);
+            }
+
+            let mut writer = builder.build();
+            writer.write_file_definition()?;
+            writer.write_file_header(&sam::Header::default())?;
+
+            for record in records {
+                writer.write_record(record.clone())?;
+            }
+
+            writer.try_finish()?;
+
+            Ok(writer.get_ref().clone())
+        }
+
+        let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(2).unwrap());
+
+        let alignment_start = sam::record::Position::try_from(1)?;
+
+        // 3 data containers
+        let records: Vec<_> = (0..25000)
+            .map(|i| {
+                Record::builder()
+                    .set_read_name(format!("r{}", i).into_bytes())
+                    .set_alignment_start(alignment_start)
+                    .build()
+            })
+            .collect();
+
+        let data = write(&records, Some(worker_pool.clone()))?;
+
+        let mut reader = Reader::builder(&data[..])
+            .set_worker_pool(worker_pool)
+            .build();
+
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let actual: Vec<_> = reader.records().collect::<io::Result<_>>()?;
+
+        let data = write(&records, None)?;
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let expected: Vec<_> = reader.records().collect::<io::Result<_>>()?;
+
+        assert_eq!(actual.len(), records.len());
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
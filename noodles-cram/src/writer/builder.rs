@@ -0,0 +1,66 @@
+use std::{io::Write, sync::Arc};
+
+use noodles_bgzf as bgzf;
+use noodles_fasta as fasta;
+
+use super::{Writer, RECORD_COUNTER_START};
+use crate::DataContainer;
+
+/// A CRAM writer builder.
+pub struct Builder<W> {
+    inner: W,
+    reference_sequences: Vec<fasta::Record>,
+    worker_pool: Option<bgzf::WorkerPool>,
+}
+
+impl<W> Builder<W>
+where
+    W: Write,
+{
+    pub(crate) fn new(inner: W, reference_sequences: Vec<fasta::Record>) -> Self {
+        Self {
+            inner,
+            reference_sequences,
+            worker_pool: None,
+        }
+    }
+
+    /// Sets a worker pool.
+    ///
+    /// When set, data containers are built and compressed in parallel on the worker pool. They
+    /// are still written in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_cram as cram;
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(4).unwrap());
+    /// let builder = cram::Writer::builder(Vec::new(), Vec::new()).set_worker_pool(worker_pool);
+    /// ```
+    pub fn set_worker_pool(mut self, worker_pool: bgzf::WorkerPool) -> Self {
+        self.worker_pool = Some(worker_pool);
+        self
+    }
+
+    /// Builds a CRAM writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let writer = cram::Writer::builder(Vec::new(), Vec::new()).build();
+    /// ```
+    pub fn build(self) -> Writer<W> {
+        Writer {
+            inner: self.inner,
+            reference_sequences: Arc::new(self.reference_sequences),
+            data_container_builder: DataContainer::builder(RECORD_COUNTER_START),
+            record_counter: RECORD_COUNTER_START,
+            worker_pool: self.worker_pool,
+            tasks: Default::default(),
+        }
+    }
+}
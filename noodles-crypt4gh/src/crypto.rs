@@ -0,0 +1,123 @@
+//! Cryptographic primitives used by Crypt4GH.
+//!
+//! These wrap the algorithms required by the Crypt4GH specification: X25519 for key exchange,
+//! BLAKE2b for shared key derivation, and ChaCha20-Poly1305 (IETF) for authenticated encryption.
+
+use std::{error, fmt, io};
+
+use blake2::{Blake2b512, Digest};
+use chacha20poly1305::{aead::AeadInPlace, ChaCha20Poly1305, KeyInit};
+
+pub(crate) const KEY_LEN: usize = 32;
+pub(crate) const NONCE_LEN: usize = 12;
+pub(crate) const MAC_LEN: usize = 16;
+
+/// An error returned when a ciphertext fails authentication.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct AuthenticationError;
+
+impl error::Error for AuthenticationError {}
+
+impl fmt::Display for AuthenticationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("authentication failed")
+    }
+}
+
+/// Encrypts the plaintext in place and appends the authentication tag (ChaCha20-Poly1305).
+pub(crate) fn seal(key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], aad: &[u8], buf: &mut Vec<u8>) {
+    ChaCha20Poly1305::new(key.into())
+        .encrypt_in_place(nonce.into(), aad, buf)
+        // Encryption only fails when the buffer cannot grow or the plaintext exceeds the
+        // ChaCha20 block counter (~256 GiB), neither of which is possible for a Crypt4GH segment.
+        .expect("ChaCha20-Poly1305 encryption failed");
+}
+
+/// Authenticates and decrypts the ciphertext in place, removing the authentication tag.
+pub(crate) fn open(
+    key: &[u8; KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    buf: &mut Vec<u8>,
+) -> Result<(), AuthenticationError> {
+    ChaCha20Poly1305::new(key.into())
+        .decrypt_in_place(nonce.into(), aad, buf)
+        .map_err(|_| AuthenticationError)
+}
+
+/// Computes the X25519 function (RFC 7748).
+pub(crate) fn x25519(scalar: &[u8; KEY_LEN], u: &[u8; KEY_LEN]) -> [u8; KEY_LEN] {
+    x25519_dalek::x25519(*scalar, *u)
+}
+
+/// Computes the X25519 function with the standard base point, i.e., the public key of a secret
+/// key.
+pub(crate) fn x25519_base(scalar: &[u8; KEY_LEN]) -> [u8; KEY_LEN] {
+    x25519_dalek::x25519(*scalar, x25519_dalek::X25519_BASEPOINT_BYTES)
+}
+
+/// Computes the BLAKE2b-512 digest of the concatenated inputs.
+pub(crate) fn blake2b_512(inputs: &[&[u8]]) -> [u8; 64] {
+    let mut hasher = Blake2b512::new();
+
+    for input in inputs {
+        hasher.update(input);
+    }
+
+    hasher.finalize().into()
+}
+
+/// Fills the buffer with random bytes from the operating system.
+pub(crate) fn fill_random(buf: &mut [u8]) -> io::Result<()> {
+    getrandom::getrandom(buf).map_err(io::Error::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAINTEXT: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+    #[test]
+    fn test_seal_and_open() -> Result<(), AuthenticationError> {
+        // RFC 8439 § 2.8.2
+        let key: [u8; KEY_LEN] = std::array::from_fn(|i| 0x80 + i as u8);
+        let nonce = [
+            0x07, 0x00, 0x00, 0x00, 0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
+        ];
+        let aad = [
+            0x50, 0x51, 0x52, 0x53, 0xc0, 0xc1, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7,
+        ];
+
+        let mut buf = PLAINTEXT.to_vec();
+        seal(&key, &nonce, &aad, &mut buf);
+
+        assert_eq!(buf.len(), PLAINTEXT.len() + MAC_LEN);
+        assert_eq!(
+            &buf[..16],
+            [
+                0xd3, 0x1a, 0x8d, 0x34, 0x64, 0x8e, 0x60, 0xdb, 0x7b, 0x86, 0xaf, 0xbc, 0x53, 0xef,
+                0x7e, 0xc2
+            ]
+        );
+        assert_eq!(
+            &buf[PLAINTEXT.len()..],
+            [
+                0x1a, 0xe1, 0x0b, 0x59, 0x4f, 0x09, 0xe2, 0x6a, 0x7e, 0x90, 0x2e, 0xcb, 0xd0, 0x60,
+                0x06, 0x91
+            ]
+        );
+
+        let mut tampered_buf = buf.clone();
+        tampered_buf[0] ^= 0x01;
+        assert_eq!(
+            open(&key, &nonce, &aad, &mut tampered_buf),
+            Err(AuthenticationError)
+        );
+
+        open(&key, &nonce, &aad, &mut buf)?;
+        assert_eq!(buf, PLAINTEXT);
+
+        Ok(())
+    }
+}
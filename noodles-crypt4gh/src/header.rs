@@ -0,0 +1,396 @@
+//! Crypt4GH header.
+
+use std::io::{self, Read, Write};
+
+use super::{
+    crypto::{self, KEY_LEN, MAC_LEN, NONCE_LEN},
+    key::{PublicKey, SecretKey},
+};
+
+pub(crate) static MAGIC_NUMBER: &[u8] = b"crypt4gh";
+
+const VERSION: u32 = 1;
+
+// X25519_chacha20_ietf_poly1305
+const HEADER_ENCRYPTION_METHOD: u32 = 0;
+// chacha20_ietf_poly1305
+const DATA_ENCRYPTION_METHOD: u32 = 0;
+
+const PACKET_TYPE_DATA_ENCRYPTION_PARAMETERS: u32 = 0;
+const PACKET_TYPE_DATA_EDIT_LIST: u32 = 1;
+
+// packet length (u32) + encryption method (u32) + writer public key + nonce
+const PACKET_PREFIX_LEN: usize = 4 + 4 + KEY_LEN + NONCE_LEN;
+
+/// A Crypt4GH header.
+///
+/// The header holds the decrypted data encryption keys and, if present, the data edit list.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Header {
+    data_keys: Vec<[u8; KEY_LEN]>,
+    edit_list: Option<Vec<u64>>,
+}
+
+impl Header {
+    pub(crate) fn data_keys(&self) -> &[[u8; KEY_LEN]] {
+        &self.data_keys
+    }
+
+    /// Returns the data edit list.
+    ///
+    /// The lengths alternate between the number of bytes to skip and to keep, starting with a
+    /// skip. If there is an odd number of lengths, all data after the last skip is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_crypt4gh::Header;
+    /// let header = Header::default();
+    /// assert!(header.edit_list().is_none());
+    /// ```
+    pub fn edit_list(&self) -> Option<&[u64]> {
+        self.edit_list.as_deref()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_edit_list(&mut self, edit_list: Vec<u64>) {
+        self.edit_list = Some(edit_list);
+    }
+}
+
+/// Derives the shared key used to encrypt header packets.
+///
+/// This is the first half of BLAKE2b-512(DH(sk, pk) || reader public key || writer public key),
+/// i.e., the libsodium key exchange session key.
+fn derive_shared_key(
+    secret_key: &SecretKey,
+    peer_public_key: &PublicKey,
+    reader_public_key: &PublicKey,
+    writer_public_key: &PublicKey,
+) -> [u8; KEY_LEN] {
+    let dh = crypto::x25519(secret_key.as_bytes(), peer_public_key.as_bytes());
+
+    let digest = crypto::blake2b_512(&[
+        &dh,
+        reader_public_key.as_bytes(),
+        writer_public_key.as_bytes(),
+    ]);
+
+    let mut key = [0; KEY_LEN];
+    key.copy_from_slice(&digest[..KEY_LEN]);
+    key
+}
+
+fn read_u32_le<R>(reader: &mut R) -> io::Result<u32>
+where
+    R: Read,
+{
+    let mut buf = [0; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn split_u32_le(buf: &[u8]) -> io::Result<(u32, &[u8])> {
+    if buf.len() < 4 {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+
+    let (n, rest) = buf.split_at(4);
+    Ok((u32::from_le_bytes([n[0], n[1], n[2], n[3]]), rest))
+}
+
+pub(crate) fn read_header<R>(reader: &mut R, secret_key: &SecretKey) -> io::Result<Header>
+where
+    R: Read,
+{
+    let mut magic_number = [0; 8];
+    reader.read_exact(&mut magic_number)?;
+
+    if magic_number != MAGIC_NUMBER {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid crypt4gh header",
+        ));
+    }
+
+    let version = read_u32_le(reader)?;
+
+    if version != VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported crypt4gh version: {}", version),
+        ));
+    }
+
+    let packet_count = read_u32_le(reader)?;
+    let reader_public_key = secret_key.public_key();
+
+    let mut header = Header::default();
+
+    for _ in 0..packet_count {
+        let packet_len = read_u32_le(reader).and_then(|n| {
+            usize::try_from(n)
+                .ok()
+                .filter(|&n| n >= PACKET_PREFIX_LEN + MAC_LEN)
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid header packet length")
+                })
+        })?;
+
+        let buf = read_packet_data(reader, packet_len - 4)?;
+
+        if let Some(data) = decrypt_packet(&buf, secret_key, &reader_public_key)? {
+            parse_packet(&data, &mut header)?;
+        }
+    }
+
+    if header.data_keys.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "no data encryption keys found for the given secret key",
+        ));
+    }
+
+    Ok(header)
+}
+
+// Reads `len` bytes of packet data.
+//
+// The buffer is only grown as data is read, so a large, invalid packet length does not allocate
+// before failing.
+fn read_packet_data<R>(reader: &mut R, len: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let mut buf = Vec::new();
+    reader.take(len as u64).read_to_end(&mut buf)?;
+
+    if buf.len() < len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+
+    Ok(buf)
+}
+
+/// Decrypts a header packet.
+///
+/// This returns `None` if the packet is not encrypted for the given key, which is not an error,
+/// as a header may include packets for multiple recipients.
+fn decrypt_packet(
+    buf: &[u8],
+    secret_key: &SecretKey,
+    reader_public_key: &PublicKey,
+) -> io::Result<Option<Vec<u8>>> {
+    let (encryption_method, buf) = split_u32_le(buf)?;
+
+    if encryption_method != HEADER_ENCRYPTION_METHOD {
+        return Ok(None);
+    }
+
+    let (writer_public_key, buf) = buf.split_at(KEY_LEN);
+    let (nonce, ciphertext) = buf.split_at(NONCE_LEN);
+
+    let writer_public_key = PublicKey::from(<[u8; KEY_LEN]>::try_from(writer_public_key).unwrap());
+    let nonce = <[u8; NONCE_LEN]>::try_from(nonce).unwrap();
+
+    let shared_key = derive_shared_key(
+        secret_key,
+        &writer_public_key,
+        reader_public_key,
+        &writer_public_key,
+    );
+
+    let mut data = ciphertext.to_vec();
+
+    match crypto::open(&shared_key, &nonce, &[], &mut data) {
+        Ok(()) => Ok(Some(data)),
+        Err(_) => Ok(None),
+    }
+}
+
+fn parse_packet(buf: &[u8], header: &mut Header) -> io::Result<()> {
+    let (packet_type, buf) = split_u32_le(buf)?;
+
+    match packet_type {
+        PACKET_TYPE_DATA_ENCRYPTION_PARAMETERS => {
+            let (encryption_method, buf) = split_u32_le(buf)?;
+
+            if encryption_method != DATA_ENCRYPTION_METHOD {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unsupported data encryption method: {}", encryption_method),
+                ));
+            }
+
+            let data_key = <[u8; KEY_LEN]>::try_from(buf).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid data key length")
+            })?;
+
+            header.data_keys.push(data_key);
+        }
+        PACKET_TYPE_DATA_EDIT_LIST => {
+            if header.edit_list.is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "duplicate data edit list",
+                ));
+            }
+
+            let (len, buf) = split_u32_le(buf)?;
+
+            let len = usize::try_from(len)
+                .ok()
+                .filter(|&len| buf.len() == len * 8)
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid data edit list length")
+                })?;
+
+            let mut lengths = Vec::with_capacity(len);
+
+            for chunk in buf.chunks_exact(8) {
+                let mut n = [0; 8];
+                n.copy_from_slice(chunk);
+                lengths.push(u64::from_le_bytes(n));
+            }
+
+            header.edit_list = Some(lengths);
+        }
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid header packet type: {}", packet_type),
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn write_header<W>(
+    writer: &mut W,
+    writer_secret_key: &SecretKey,
+    recipients: &[PublicKey],
+    data_key: &[u8; KEY_LEN],
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let packet_count = u32::try_from(recipients.len())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    writer.write_all(MAGIC_NUMBER)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&packet_count.to_le_bytes())?;
+
+    let writer_public_key = writer_secret_key.public_key();
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&PACKET_TYPE_DATA_ENCRYPTION_PARAMETERS.to_le_bytes());
+    data.extend_from_slice(&DATA_ENCRYPTION_METHOD.to_le_bytes());
+    data.extend_from_slice(data_key);
+
+    for reader_public_key in recipients {
+        let shared_key = derive_shared_key(
+            writer_secret_key,
+            reader_public_key,
+            reader_public_key,
+            &writer_public_key,
+        );
+
+        let mut nonce = [0; NONCE_LEN];
+        crypto::fill_random(&mut nonce)?;
+
+        let mut buf = data.clone();
+        crypto::seal(&shared_key, &nonce, &[], &mut buf);
+
+        let packet_len = u32::try_from(PACKET_PREFIX_LEN + buf.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        writer.write_all(&packet_len.to_le_bytes())?;
+        writer.write_all(&HEADER_ENCRYPTION_METHOD.to_le_bytes())?;
+        writer.write_all(writer_public_key.as_bytes())?;
+        writer.write_all(&nonce)?;
+        writer.write_all(&buf)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_header_and_read_header() -> io::Result<()> {
+        let writer_secret_key = SecretKey::generate()?;
+        let alice_secret_key = SecretKey::generate()?;
+        let bob_secret_key = SecretKey::generate()?;
+        let eve_secret_key = SecretKey::generate()?;
+
+        let recipients = [alice_secret_key.public_key(), bob_secret_key.public_key()];
+        let data_key = [0x55; KEY_LEN];
+
+        let mut buf = Vec::new();
+        write_header(&mut buf, &writer_secret_key, &recipients, &data_key)?;
+
+        let expected = Header {
+            data_keys: vec![data_key],
+            edit_list: None,
+        };
+
+        assert_eq!(read_header(&mut &buf[..], &alice_secret_key)?, expected);
+        assert_eq!(read_header(&mut &buf[..], &bob_secret_key)?, expected);
+
+        assert!(matches!(
+            read_header(&mut &buf[..], &eve_secret_key),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_header_with_truncated_packet() -> io::Result<()> {
+        let secret_key = SecretKey::generate()?;
+
+        let mut buf = MAGIC_NUMBER.to_vec();
+        buf.extend_from_slice(&VERSION.to_le_bytes());
+        buf.extend_from_slice(&1u32.to_le_bytes()); // packet count = 1
+        buf.extend_from_slice(&u32::MAX.to_le_bytes()); // packet length = 4294967295
+        buf.extend_from_slice(&HEADER_ENCRYPTION_METHOD.to_le_bytes());
+
+        assert!(matches!(
+            read_header(&mut &buf[..], &secret_key),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_packet() -> io::Result<()> {
+        let mut header = Header::default();
+
+        let buf = [
+            0x01, 0x00, 0x00, 0x00, // packet type = 1 (data edit list)
+            0x02, 0x00, 0x00, 0x00, // number of lengths = 2
+            0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // lengths[0] = 8
+            0x0d, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // lengths[1] = 13
+        ];
+        parse_packet(&buf, &mut header)?;
+        assert_eq!(header.edit_list(), Some(&[8, 13][..]));
+
+        assert!(matches!(
+            parse_packet(&buf, &mut header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let buf = [0x02, 0x00, 0x00, 0x00];
+        assert!(matches!(
+            parse_packet(&buf, &mut header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
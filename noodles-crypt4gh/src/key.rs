@@ -0,0 +1,411 @@
+//! Crypt4GH keys.
+
+use std::{
+    error, fmt,
+    fs::File,
+    io::{self, Read},
+    path::Path,
+    str::FromStr,
+};
+
+use base64ct::{Base64, Encoding};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use super::crypto;
+
+/// The length of a key in bytes.
+pub const KEY_LEN: usize = crypto::KEY_LEN;
+
+const PUBLIC_KEY_LABEL: &str = "CRYPT4GH PUBLIC KEY";
+const PRIVATE_KEY_LABEL: &str = "CRYPT4GH PRIVATE KEY";
+const ENCRYPTED_PRIVATE_KEY_LABEL: &str = "CRYPT4GH ENCRYPTED PRIVATE KEY";
+
+const SECRET_KEY_MAGIC: &[u8] = b"c4gh-v1";
+const NONE: &[u8] = b"none";
+
+/// An error returned when a Crypt4GH key fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The pre- or post-encapsulation boundary is missing or invalid.
+    InvalidBoundary,
+    /// The base64 encoded data is invalid.
+    InvalidData,
+    /// The key length is invalid.
+    InvalidLength(usize),
+    /// The secret key magic number is invalid.
+    InvalidMagicNumber,
+    /// The secret key key derivation function is unsupported.
+    ///
+    /// Only unencrypted secret keys (key derivation function `none`) are supported.
+    UnsupportedKdf(String),
+    /// The secret key cipher is unsupported.
+    UnsupportedCipher(String),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBoundary => f.write_str("invalid boundary"),
+            Self::InvalidData => f.write_str("invalid data"),
+            Self::InvalidLength(len) => {
+                write!(f, "invalid length: expected {}, got {}", KEY_LEN, len)
+            }
+            Self::InvalidMagicNumber => f.write_str("invalid magic number"),
+            Self::UnsupportedKdf(kdf) => write!(f, "unsupported key derivation function: {}", kdf),
+            Self::UnsupportedCipher(cipher) => write!(f, "unsupported cipher: {}", cipher),
+        }
+    }
+}
+
+/// A Crypt4GH public key.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PublicKey([u8; KEY_LEN]);
+
+impl PublicKey {
+    /// Returns the raw bytes of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_crypt4gh::key::PublicKey;
+    /// let public_key = PublicKey::from([0; 32]);
+    /// assert_eq!(public_key.as_bytes(), &[0; 32]);
+    /// ```
+    pub fn as_bytes(&self) -> &[u8; KEY_LEN] {
+        &self.0
+    }
+}
+
+impl From<[u8; KEY_LEN]> for PublicKey {
+    fn from(buf: [u8; KEY_LEN]) -> Self {
+        Self(buf)
+    }
+}
+
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_pem(f, PUBLIC_KEY_LABEL, &self.0)
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = parse_pem(s, &[PUBLIC_KEY_LABEL])?;
+        parse_key(&data).map(Self)
+    }
+}
+
+/// A Crypt4GH secret key.
+///
+/// The key material is not shown in the debug representation and is zeroed when the key is
+/// dropped.
+#[derive(Clone, Eq, PartialEq)]
+pub struct SecretKey([u8; KEY_LEN]);
+
+impl SecretKey {
+    /// Generates a random secret key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_crypt4gh::key::SecretKey;
+    /// let secret_key = SecretKey::generate()?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn generate() -> io::Result<Self> {
+        let mut buf = [0; KEY_LEN];
+        crypto::fill_random(&mut buf)?;
+        Ok(Self(buf))
+    }
+
+    /// Returns the public key derived from this secret key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_crypt4gh::key::SecretKey;
+    /// let secret_key = SecretKey::generate()?;
+    /// let public_key = secret_key.public_key();
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(crypto::x25519_base(&self.0))
+    }
+
+    /// Returns the raw bytes of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_crypt4gh::key::SecretKey;
+    /// let secret_key = SecretKey::from([0; 32]);
+    /// assert_eq!(secret_key.as_bytes(), &[0; 32]);
+    /// ```
+    pub fn as_bytes(&self) -> &[u8; KEY_LEN] {
+        &self.0
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretKey {}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SecretKey").field(&"[REDACTED]").finish()
+    }
+}
+
+impl From<[u8; KEY_LEN]> for SecretKey {
+    fn from(buf: [u8; KEY_LEN]) -> Self {
+        Self(buf)
+    }
+}
+
+impl fmt::Display for SecretKey {
+    /// Formats the secret key as an unencrypted Crypt4GH private key.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut data = Zeroizing::new(SECRET_KEY_MAGIC.to_vec());
+
+        for s in [NONE, NONE, &self.0[..]] {
+            data.extend_from_slice(&(s.len() as u16).to_be_bytes());
+            data.extend_from_slice(s);
+        }
+
+        write_pem(f, PRIVATE_KEY_LABEL, &data)
+    }
+}
+
+impl FromStr for SecretKey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = parse_pem(s, &[PRIVATE_KEY_LABEL, ENCRYPTED_PRIVATE_KEY_LABEL])?;
+        parse_secret_key_data(&data).map(Self)
+    }
+}
+
+/// Reads a Crypt4GH public key from a file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_crypt4gh::key;
+/// let public_key = key::read_public_key("recipient.pub")?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn read_public_key<P>(src: P) -> io::Result<PublicKey>
+where
+    P: AsRef<Path>,
+{
+    read_to_string(src)?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Reads a Crypt4GH secret key from a file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_crypt4gh::key;
+/// let secret_key = key::read_secret_key("recipient.sec")?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn read_secret_key<P>(src: P) -> io::Result<SecretKey>
+where
+    P: AsRef<Path>,
+{
+    read_to_string(src)?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn read_to_string<P>(src: P) -> io::Result<String>
+where
+    P: AsRef<Path>,
+{
+    let mut s = String::new();
+    File::open(src)?.read_to_string(&mut s)?;
+    Ok(s)
+}
+
+fn write_pem(f: &mut fmt::Formatter<'_>, label: &str, data: &[u8]) -> fmt::Result {
+    writeln!(f, "-----BEGIN {}-----", label)?;
+    let encoded_data = Zeroizing::new(Base64::encode_string(data));
+    writeln!(f, "{}", encoded_data.as_str())?;
+    write!(f, "-----END {}-----", label)
+}
+
+// The decoded data and its encoding are zeroed when dropped, as they may hold a secret key.
+fn parse_pem(s: &str, labels: &[&str]) -> Result<Zeroizing<Vec<u8>>, ParseError> {
+    let mut lines = s
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty());
+
+    let label = lines
+        .next()
+        .and_then(|line| line.strip_prefix("-----BEGIN "))
+        .and_then(|line| line.strip_suffix("-----"))
+        .filter(|label| labels.contains(label))
+        .ok_or(ParseError::InvalidBoundary)?;
+
+    let mut encoded_data = Zeroizing::new(String::new());
+    let mut has_end_boundary = false;
+
+    for line in lines.by_ref() {
+        if let Some(end_label) = line
+            .strip_prefix("-----END ")
+            .and_then(|line| line.strip_suffix("-----"))
+        {
+            if end_label != label {
+                return Err(ParseError::InvalidBoundary);
+            }
+
+            has_end_boundary = true;
+            break;
+        }
+
+        encoded_data.push_str(line);
+    }
+
+    if !has_end_boundary || lines.next().is_some() {
+        return Err(ParseError::InvalidBoundary);
+    }
+
+    Base64::decode_vec(&encoded_data)
+        .map(Zeroizing::new)
+        .map_err(|_| ParseError::InvalidData)
+}
+
+fn parse_key(data: &[u8]) -> Result<[u8; KEY_LEN], ParseError> {
+    data.try_into()
+        .map_err(|_| ParseError::InvalidLength(data.len()))
+}
+
+fn parse_secret_key_data(mut data: &[u8]) -> Result<[u8; KEY_LEN], ParseError> {
+    fn read_string<'a>(src: &mut &'a [u8]) -> Result<&'a [u8], ParseError> {
+        if src.len() < 2 {
+            return Err(ParseError::InvalidData);
+        }
+
+        let (len_buf, rest) = src.split_at(2);
+        let len = usize::from(u16::from_be_bytes([len_buf[0], len_buf[1]]));
+
+        if rest.len() < len {
+            return Err(ParseError::InvalidData);
+        }
+
+        let (s, rest) = rest.split_at(len);
+        *src = rest;
+
+        Ok(s)
+    }
+
+    data = data
+        .strip_prefix(SECRET_KEY_MAGIC)
+        .ok_or(ParseError::InvalidMagicNumber)?;
+
+    let kdf = read_string(&mut data)?;
+
+    if kdf != NONE {
+        return Err(ParseError::UnsupportedKdf(
+            String::from_utf8_lossy(kdf).into(),
+        ));
+    }
+
+    let cipher = read_string(&mut data)?;
+
+    if cipher != NONE {
+        return Err(ParseError::UnsupportedCipher(
+            String::from_utf8_lossy(cipher).into(),
+        ));
+    }
+
+    let key = read_string(&mut data)?;
+
+    // The remaining data, if any, is an optional comment.
+    parse_key(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt_and_parse_public_key() {
+        let public_key = PublicKey::from([0x55; KEY_LEN]);
+
+        let s = public_key.to_string();
+        assert_eq!(
+            s,
+            "-----BEGIN CRYPT4GH PUBLIC KEY-----
+VVVVVVVVVVVVVVVVVVVVVVVVVVVVVVVVVVVVVVVVVVU=
+-----END CRYPT4GH PUBLIC KEY-----"
+        );
+
+        assert_eq!(s.parse(), Ok(public_key));
+    }
+
+    #[test]
+    fn test_parse_public_key_with_invalid_input() {
+        assert_eq!("".parse::<PublicKey>(), Err(ParseError::InvalidBoundary));
+
+        assert_eq!(
+            "-----BEGIN CRYPT4GH PUBLIC KEY-----\nVVVV\n".parse::<PublicKey>(),
+            Err(ParseError::InvalidBoundary)
+        );
+
+        assert_eq!(
+            "-----BEGIN CRYPT4GH PUBLIC KEY-----\nVVVV\n-----END CRYPT4GH PUBLIC KEY-----"
+                .parse::<PublicKey>(),
+            Err(ParseError::InvalidLength(3))
+        );
+
+        assert_eq!(
+            "-----BEGIN CRYPT4GH PUBLIC KEY-----\nVVV\n-----END CRYPT4GH PUBLIC KEY-----"
+                .parse::<PublicKey>(),
+            Err(ParseError::InvalidData)
+        );
+    }
+
+    #[test]
+    fn test_fmt_and_parse_secret_key() {
+        let secret_key = SecretKey::from([0x55; KEY_LEN]);
+        let s = secret_key.to_string();
+        assert!(s.starts_with("-----BEGIN CRYPT4GH PRIVATE KEY-----\n"));
+        assert_eq!(s.parse(), Ok(secret_key));
+    }
+
+    #[test]
+    fn test_parse_secret_key_with_unsupported_kdf() {
+        let mut data = SECRET_KEY_MAGIC.to_vec();
+        data.extend_from_slice(&[0x00, 0x06]);
+        data.extend_from_slice(b"bcrypt");
+
+        let s = format!(
+            "-----BEGIN CRYPT4GH ENCRYPTED PRIVATE KEY-----\n{}\n-----END CRYPT4GH ENCRYPTED PRIVATE KEY-----",
+            Base64::encode_string(&data)
+        );
+
+        assert_eq!(
+            s.parse::<SecretKey>(),
+            Err(ParseError::UnsupportedKdf(String::from("bcrypt")))
+        );
+    }
+
+    #[test]
+    fn test_debug_for_secret_key() {
+        let secret_key = SecretKey::from([0x55; KEY_LEN]);
+        assert_eq!(format!("{:?}", secret_key), r#"SecretKey("[REDACTED]")"#);
+    }
+}
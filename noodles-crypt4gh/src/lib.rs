@@ -0,0 +1,62 @@
+#![warn(missing_docs)]
+
+//! **noodles-crypt4gh** handles the reading and writing of the GA4GH Crypt4GH encrypted container
+//! format.
+//!
+//! A Crypt4GH stream has a header followed by the encrypted data. The header holds one or more
+//! packets, each encrypted for a recipient's public key, that carry the key used to encrypt the
+//! data. The data is split into 64 KiB segments, each independently encrypted using
+//! ChaCha20-Poly1305.
+//!
+//! [`Reader`] and [`Writer`] implement [`std::io::Read`] and [`std::io::Write`], respectively, so
+//! they can be used as the underlying stream of any other reader or writer, e.g., to read and
+//! write encrypted BAM, CRAM, or VCF files transparently. When the underlying stream is seekable,
+//! [`Reader::new_seekable`] creates a reader that also implements [`std::io::Seek`], which allows
+//! indexed queries.
+//!
+//! Only the X25519 header encryption method, the ChaCha20-Poly1305 data encryption method, and
+//! unencrypted secret keys are supported.
+//!
+//! # Examples
+//!
+//! ## Write and read an encrypted BAM
+//!
+//! ```
+//! # use std::io;
+//! use noodles_bam as bam;
+//! use noodles_crypt4gh::{self as crypt4gh, key::SecretKey};
+//! use noodles_sam as sam;
+//!
+//! let writer_secret_key = SecretKey::generate()?;
+//! let reader_secret_key = SecretKey::generate()?;
+//! let recipients = [reader_secret_key.public_key()];
+//!
+//! let header = sam::Header::default();
+//!
+//! let inner = crypt4gh::Writer::new(Vec::new(), &writer_secret_key, &recipients)?;
+//! let mut writer = bam::Writer::new(inner);
+//! writer.write_header(&header)?;
+//! writer.write_reference_sequences(header.reference_sequences())?;
+//! writer.write_record(&bam::Record::default())?;
+//! let data = writer.into_inner().finish()?.finish()?;
+//!
+//! let inner = crypt4gh::Reader::new(&data[..], &reader_secret_key)?;
+//! let mut reader = bam::Reader::new(inner);
+//! reader.read_header()?;
+//! reader.read_reference_sequences()?;
+//!
+//! let records: Vec<_> = reader.records().collect::<io::Result<_>>()?;
+//! assert_eq!(records.len(), 1);
+//! # Ok::<_, io::Error>(())
+//! ```
+
+mod crypto;
+pub mod header;
+pub mod key;
+pub mod reader;
+pub mod writer;
+
+pub use self::{header::Header, reader::Reader, writer::Writer};
+
+// The size of a plaintext data segment.
+const SEGMENT_SIZE: usize = 65536;
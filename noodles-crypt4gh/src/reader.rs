@@ -0,0 +1,534 @@
+//! Crypt4GH reader.
+
+use std::{
+    cmp,
+    io::{self, Read, Seek, SeekFrom},
+};
+
+use super::{
+    crypto::{self, MAC_LEN, NONCE_LEN},
+    header,
+    key::SecretKey,
+    Header, SEGMENT_SIZE,
+};
+
+const ENCRYPTED_SEGMENT_SIZE: usize = NONCE_LEN + SEGMENT_SIZE + MAC_LEN;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Edit {
+    Skip(u64),
+    Keep(u64),
+    KeepAll,
+    Done,
+}
+
+/// A Crypt4GH reader.
+///
+/// This implements [`std::io::Read`], consuming encrypted data segments and emitting plaintext.
+/// If the header has a data edit list, it is applied to the plaintext.
+///
+/// When the underlying reader implements [`std::io::Seek`] and the header has no data edit list,
+/// this also implements [`std::io::Seek`] over the plaintext.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Read, Write};
+/// use noodles_crypt4gh::{self as crypt4gh, key::SecretKey};
+///
+/// let secret_key = SecretKey::generate()?;
+///
+/// let mut writer = crypt4gh::Writer::new(Vec::new(), &secret_key, &[secret_key.public_key()])?;
+/// writer.write_all(b"noodles")?;
+/// let data = writer.finish()?;
+///
+/// let mut reader = crypt4gh::Reader::new(&data[..], &secret_key)?;
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf)?;
+/// assert_eq!(buf, b"noodles");
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Reader<R> {
+    inner: R,
+    header: Header,
+    data_start: Option<u64>,
+    buf: Vec<u8>,
+    buf_start: u64,
+    position: usize,
+    segment_index: u64,
+    edit_list: Vec<u64>,
+    edit_list_index: usize,
+    edit: Edit,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    /// Creates a Crypt4GH reader.
+    ///
+    /// This reads the header and decrypts the data encryption keys using the given secret key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_crypt4gh::{self as crypt4gh, key::SecretKey};
+    ///
+    /// let secret_key = SecretKey::generate()?;
+    /// let data = crypt4gh::Writer::new(Vec::new(), &secret_key, &[secret_key.public_key()])?
+    ///     .finish()?;
+    ///
+    /// let reader = crypt4gh::Reader::new(&data[..], &secret_key)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn new(mut inner: R, secret_key: &SecretKey) -> io::Result<Self> {
+        let header = header::read_header(&mut inner, secret_key)?;
+        Ok(Self::from_parts(inner, header, None))
+    }
+
+    fn from_parts(inner: R, header: Header, data_start: Option<u64>) -> Self {
+        let edit_list = header.edit_list().map(|lengths| lengths.to_vec());
+
+        let edit = match edit_list.as_deref() {
+            None => Edit::KeepAll,
+            Some([]) => Edit::Done,
+            Some([len, ..]) => Edit::Skip(*len),
+        };
+
+        Self {
+            inner,
+            header,
+            data_start,
+            buf: Vec::with_capacity(SEGMENT_SIZE + MAC_LEN),
+            buf_start: 0,
+            position: 0,
+            segment_index: 0,
+            edit_list: edit_list.unwrap_or_default(),
+            edit_list_index: 0,
+            edit,
+        }
+    }
+
+    /// Returns the Crypt4GH header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_crypt4gh::{self as crypt4gh, key::SecretKey};
+    ///
+    /// let secret_key = SecretKey::generate()?;
+    /// let data = crypt4gh::Writer::new(Vec::new(), &secret_key, &[secret_key.public_key()])?
+    ///     .finish()?;
+    ///
+    /// let reader = crypt4gh::Reader::new(&data[..], &secret_key)?;
+    /// assert!(reader.header().edit_list().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_crypt4gh::{self as crypt4gh, key::SecretKey};
+    ///
+    /// let secret_key = SecretKey::generate()?;
+    /// let data = crypt4gh::Writer::new(Vec::new(), &secret_key, &[secret_key.public_key()])?
+    ///     .finish()?;
+    ///
+    /// let reader = crypt4gh::Reader::new(&data[..], &secret_key)?;
+    /// assert!(reader.get_ref().is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_crypt4gh::{self as crypt4gh, key::SecretKey};
+    ///
+    /// let secret_key = SecretKey::generate()?;
+    /// let data = crypt4gh::Writer::new(Vec::new(), &secret_key, &[secret_key.public_key()])?
+    ///     .finish()?;
+    ///
+    /// let reader = crypt4gh::Reader::new(&data[..], &secret_key)?;
+    /// assert!(reader.into_inner().is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads and decrypts the next segment.
+    ///
+    /// The buffer is empty at the end of the stream.
+    fn read_segment(&mut self) -> io::Result<()> {
+        self.buf.resize(ENCRYPTED_SEGMENT_SIZE, 0);
+        let len = read_full(&mut self.inner, &mut self.buf)?;
+        self.buf.truncate(len);
+        self.buf_start = self.segment_index * SEGMENT_SIZE as u64;
+        self.position = 0;
+
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+
+        if self.buf.len() < NONCE_LEN + MAC_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid crypt4gh segment length",
+            ));
+        }
+
+        let mut nonce = [0; NONCE_LEN];
+        nonce.copy_from_slice(&self.buf[..NONCE_LEN]);
+        self.buf.drain(..NONCE_LEN);
+
+        let is_decrypted = self
+            .header
+            .data_keys()
+            .iter()
+            .any(|data_key| crypto::open(data_key, &nonce, &[], &mut self.buf).is_ok());
+
+        if !is_decrypted {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("failed to decrypt crypt4gh segment {}", self.segment_index),
+            ));
+        }
+
+        self.segment_index += 1;
+
+        Ok(())
+    }
+
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.position >= self.buf.len() {
+            self.read_segment()?;
+        }
+
+        Ok(&self.buf[self.position..])
+    }
+
+    fn advance_edit(&mut self, len: u64) {
+        let remaining = match self.edit {
+            Edit::Skip(n) | Edit::Keep(n) => n - len,
+            Edit::KeepAll | Edit::Done => return,
+        };
+
+        if remaining > 0 {
+            self.edit = match self.edit {
+                Edit::Skip(_) => Edit::Skip(remaining),
+                _ => Edit::Keep(remaining),
+            };
+
+            return;
+        }
+
+        let is_skip = matches!(self.edit, Edit::Skip(_));
+        self.edit_list_index += 1;
+
+        self.edit = match self.edit_list.get(self.edit_list_index) {
+            Some(&n) if is_skip => Edit::Keep(n),
+            Some(&n) => Edit::Skip(n),
+            // An odd number of lengths keeps all data after the last skip.
+            None if is_skip => Edit::KeepAll,
+            None => Edit::Done,
+        };
+    }
+}
+
+impl<R> Read for Reader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            let edit = self.edit;
+
+            match edit {
+                Edit::Skip(0) | Edit::Keep(0) => {
+                    self.advance_edit(0);
+                    continue;
+                }
+                Edit::Done => return Ok(0),
+                _ => {}
+            }
+
+            let src = self.fill_buf()?;
+
+            if src.is_empty() {
+                return Ok(0);
+            }
+
+            let src_len = src.len() as u64;
+
+            match edit {
+                Edit::Skip(n) => {
+                    let amt = cmp::min(n, src_len);
+                    self.position += amt as usize;
+                    self.advance_edit(amt);
+                }
+                Edit::Keep(n) => {
+                    let amt = cmp::min(cmp::min(n, src_len), buf.len() as u64) as usize;
+                    buf[..amt].copy_from_slice(&src[..amt]);
+                    self.position += amt;
+                    self.advance_edit(amt as u64);
+                    return Ok(amt);
+                }
+                Edit::KeepAll => {
+                    let amt = cmp::min(src.len(), buf.len());
+                    buf[..amt].copy_from_slice(&src[..amt]);
+                    self.position += amt;
+                    return Ok(amt);
+                }
+                Edit::Done => unreachable!(),
+            }
+        }
+    }
+}
+
+impl<R> Reader<R>
+where
+    R: Read + Seek,
+{
+    /// Creates a seekable Crypt4GH reader.
+    ///
+    /// This is the same as [`Self::new`] but records the start of the data segments in the
+    /// underlying stream, which is required to seek.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, Write};
+    /// use noodles_crypt4gh::{self as crypt4gh, key::SecretKey};
+    ///
+    /// let secret_key = SecretKey::generate()?;
+    /// let data = crypt4gh::Writer::new(Vec::new(), &secret_key, &[secret_key.public_key()])?
+    ///     .finish()?;
+    ///
+    /// let reader = crypt4gh::Reader::new_seekable(Cursor::new(data), &secret_key)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn new_seekable(mut inner: R, secret_key: &SecretKey) -> io::Result<Self> {
+        let header = header::read_header(&mut inner, secret_key)?;
+        let data_start = inner.stream_position()?;
+        Ok(Self::from_parts(inner, header, Some(data_start)))
+    }
+
+    fn plaintext_len(&mut self, data_start: u64) -> io::Result<u64> {
+        let end = self.inner.seek(SeekFrom::End(0))?;
+        let len = end.saturating_sub(data_start);
+
+        let segment_count = len / ENCRYPTED_SEGMENT_SIZE as u64;
+        let remainder = len % ENCRYPTED_SEGMENT_SIZE as u64;
+
+        Ok(segment_count * SEGMENT_SIZE as u64
+            + remainder.saturating_sub((NONCE_LEN + MAC_LEN) as u64))
+    }
+}
+
+impl<R> Seek for Reader<R>
+where
+    R: Read + Seek,
+{
+    /// Seeks to a plaintext position.
+    ///
+    /// This fails if the reader was not created using [`Reader::new_seekable`] or if the header
+    /// has a data edit list.
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let data_start = self.data_start.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                "crypt4gh reader was not created as seekable",
+            )
+        })?;
+
+        if self.header.edit_list().is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "cannot seek a crypt4gh stream with a data edit list",
+            ));
+        }
+
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => (n, 0),
+            SeekFrom::Current(n) => (self.buf_start + self.position as u64, n),
+            SeekFrom::End(n) => (self.plaintext_len(data_start)?, n),
+        };
+
+        let position = base
+            .checked_add_signed(offset)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid seek position"))?;
+
+        let segment_index = position / SEGMENT_SIZE as u64;
+        let segment_offset = (position % SEGMENT_SIZE as u64) as usize;
+
+        self.inner.seek(SeekFrom::Start(
+            data_start + segment_index * ENCRYPTED_SEGMENT_SIZE as u64,
+        ))?;
+
+        self.segment_index = segment_index;
+        self.buf.clear();
+        self.buf_start = segment_index * SEGMENT_SIZE as u64;
+        self.position = 0;
+
+        if segment_offset > 0 {
+            self.read_segment()?;
+            self.position = cmp::min(segment_offset, self.buf.len());
+        }
+
+        Ok(position)
+    }
+}
+
+fn read_full<R>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize>
+where
+    R: Read,
+{
+    let mut len = 0;
+
+    while len < buf.len() {
+        match reader.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use super::*;
+    use crate::Writer;
+
+    fn build_data(secret_key: &SecretKey, len: usize) -> io::Result<(Vec<u8>, Vec<u8>)> {
+        let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+
+        let mut writer = Writer::new(Vec::new(), secret_key, &[secret_key.public_key()])?;
+        writer.write_all(&plaintext)?;
+        let data = writer.finish()?;
+
+        Ok((plaintext, data))
+    }
+
+    #[test]
+    fn test_read() -> io::Result<()> {
+        let secret_key = SecretKey::generate()?;
+
+        for len in [0, 1, SEGMENT_SIZE, SEGMENT_SIZE + 1, 2 * SEGMENT_SIZE + 13] {
+            let (expected, data) = build_data(&secret_key, len)?;
+
+            let mut reader = Reader::new(&data[..], &secret_key)?;
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf)?;
+
+            assert_eq!(buf, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_tampered_segment() -> io::Result<()> {
+        let secret_key = SecretKey::generate()?;
+        let (_, mut data) = build_data(&secret_key, 8)?;
+
+        let last_index = data.len() - 1;
+        data[last_index] ^= 0x01;
+
+        let mut reader = Reader::new(&data[..], &secret_key)?;
+        let mut buf = Vec::new();
+
+        assert!(matches!(
+            reader.read_to_end(&mut buf),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_edit_list() -> io::Result<()> {
+        let secret_key = SecretKey::generate()?;
+        let (plaintext, data) = build_data(&secret_key, SEGMENT_SIZE + 8)?;
+
+        let read_with_edit_list = |edit_list: Vec<u64>| -> io::Result<Vec<u8>> {
+            let mut header = header::read_header(&mut &data[..], &secret_key)?;
+            header.set_edit_list(edit_list);
+
+            let header_len = data.len() - (SEGMENT_SIZE + 8) - 2 * (NONCE_LEN + MAC_LEN);
+            let mut reader = Reader::from_parts(&data[header_len..], header, None);
+
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf)?;
+            Ok(buf)
+        };
+
+        let start = SEGMENT_SIZE - 2;
+
+        assert_eq!(
+            read_with_edit_list(vec![start as u64, 5])?,
+            &plaintext[start..start + 5]
+        );
+
+        assert_eq!(
+            read_with_edit_list(vec![1, 2, start as u64])?,
+            [&plaintext[1..3], &plaintext[3 + start..]].concat()
+        );
+
+        assert!(read_with_edit_list(Vec::new())?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek() -> io::Result<()> {
+        let secret_key = SecretKey::generate()?;
+        let (plaintext, data) = build_data(&secret_key, 2 * SEGMENT_SIZE + 13)?;
+
+        let mut reader = Reader::new_seekable(Cursor::new(data), &secret_key)?;
+
+        let mut buf = [0; 8];
+
+        let position = reader.seek(SeekFrom::Start(SEGMENT_SIZE as u64 - 4))?;
+        assert_eq!(position, SEGMENT_SIZE as u64 - 4);
+        reader.read_exact(&mut buf)?;
+        assert_eq!(buf, plaintext[SEGMENT_SIZE - 4..SEGMENT_SIZE + 4]);
+
+        assert_eq!(reader.stream_position()?, SEGMENT_SIZE as u64 + 4);
+
+        let position = reader.seek(SeekFrom::End(-8))?;
+        assert_eq!(position, plaintext.len() as u64 - 8);
+        reader.read_exact(&mut buf)?;
+        assert_eq!(buf, plaintext[plaintext.len() - 8..]);
+
+        reader.seek(SeekFrom::Start(0))?;
+        let mut actual = Vec::new();
+        reader.read_to_end(&mut actual)?;
+        assert_eq!(actual, plaintext);
+
+        Ok(())
+    }
+}
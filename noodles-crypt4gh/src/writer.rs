@@ -0,0 +1,198 @@
+//! Crypt4GH writer.
+
+use std::io::{self, Write};
+
+use super::{
+    crypto::{self, KEY_LEN, MAC_LEN, NONCE_LEN},
+    header,
+    key::{PublicKey, SecretKey},
+    SEGMENT_SIZE,
+};
+
+/// A Crypt4GH writer.
+///
+/// This implements [`std::io::Write`], consuming plaintext and emitting a Crypt4GH header followed
+/// by encrypted data segments. The final partial segment is written when the stream is finished.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Write};
+/// use noodles_crypt4gh::{self as crypt4gh, key::SecretKey};
+///
+/// let writer_secret_key = SecretKey::generate()?;
+/// let reader_secret_key = SecretKey::generate()?;
+/// let recipients = [reader_secret_key.public_key()];
+///
+/// let mut writer = crypt4gh::Writer::new(Vec::new(), &writer_secret_key, &recipients)?;
+/// writer.write_all(b"noodles")?;
+/// let data = writer.finish()?;
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Writer<W>
+where
+    W: Write,
+{
+    inner: Option<W>,
+    data_key: [u8; KEY_LEN],
+    buf: Vec<u8>,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a Crypt4GH writer.
+    ///
+    /// This writes the header, which includes a random data key encrypted for each recipient.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_crypt4gh::{self as crypt4gh, key::SecretKey};
+    ///
+    /// let writer_secret_key = SecretKey::generate()?;
+    /// let reader_secret_key = SecretKey::generate()?;
+    /// let recipients = [reader_secret_key.public_key()];
+    ///
+    /// let writer = crypt4gh::Writer::new(Vec::new(), &writer_secret_key, &recipients)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn new(
+        mut inner: W,
+        writer_secret_key: &SecretKey,
+        recipients: &[PublicKey],
+    ) -> io::Result<Self> {
+        if recipients.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "missing recipients",
+            ));
+        }
+
+        let mut data_key = [0; KEY_LEN];
+        crypto::fill_random(&mut data_key)?;
+
+        header::write_header(&mut inner, writer_secret_key, recipients, &data_key)?;
+
+        Ok(Self {
+            inner: Some(inner),
+            data_key,
+            buf: Vec::with_capacity(SEGMENT_SIZE + MAC_LEN),
+        })
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_crypt4gh::{self as crypt4gh, key::SecretKey};
+    ///
+    /// let secret_key = SecretKey::generate()?;
+    /// let writer = crypt4gh::Writer::new(Vec::new(), &secret_key, &[secret_key.public_key()])?;
+    /// assert!(writer.get_ref().starts_with(b"crypt4gh"));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().unwrap()
+    }
+
+    /// Attempts to finish the output stream by encrypting and writing the final partial segment.
+    ///
+    /// This is typically only manually called if the underlying stream is needed before the
+    /// writer is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_crypt4gh::{self as crypt4gh, key::SecretKey};
+    ///
+    /// let secret_key = SecretKey::generate()?;
+    /// let mut writer = crypt4gh::Writer::new(Vec::new(), &secret_key, &[secret_key.public_key()])?;
+    /// writer.write_all(b"noodles")?;
+    /// writer.try_finish()?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn try_finish(&mut self) -> io::Result<()> {
+        if !self.buf.is_empty() {
+            self.write_segment()?;
+        }
+
+        self.inner.as_mut().unwrap().flush()
+    }
+
+    /// Returns the underlying writer after finishing the output stream.
+    ///
+    /// This method can only be called once. Any further usage of the writer may result in a panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_crypt4gh::{self as crypt4gh, key::SecretKey};
+    ///
+    /// let secret_key = SecretKey::generate()?;
+    /// let mut writer = crypt4gh::Writer::new(Vec::new(), &secret_key, &[secret_key.public_key()])?;
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(mut self) -> io::Result<W> {
+        self.try_finish()?;
+        let inner = self.inner.take().unwrap();
+        Ok(inner)
+    }
+
+    fn write_segment(&mut self) -> io::Result<()> {
+        let mut nonce = [0; NONCE_LEN];
+        crypto::fill_random(&mut nonce)?;
+
+        crypto::seal(&self.data_key, &nonce, &[], &mut self.buf);
+
+        let inner = self.inner.as_mut().unwrap();
+        inner.write_all(&nonce)?;
+        inner.write_all(&self.buf)?;
+
+        self.buf.clear();
+
+        Ok(())
+    }
+}
+
+impl<W> Drop for Writer<W>
+where
+    W: Write,
+{
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.try_finish();
+        }
+    }
+}
+
+impl<W> Write for Writer<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let amt = (SEGMENT_SIZE - self.buf.len()).min(buf.len());
+        self.buf.extend_from_slice(&buf[..amt]);
+
+        if self.buf.len() >= SEGMENT_SIZE {
+            self.write_segment()?;
+        }
+
+        Ok(amt)
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// Only full segments are written before the stream is finished, so buffered data in a
+    /// partial segment is not written.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.as_mut().unwrap().flush()
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
         reference_sequence::{bin::Chunk, Bin, Metadata},
         ReferenceSequence,
     },
-    Index,
+    Error, Index,
 };
 
 /// An async CSI reader.
@@ -92,10 +92,7 @@ where
     if magic == MAGIC_NUMBER {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "invalid CSI header",
-        ))
+        Err(Error::InvalidMagicNumber(magic).into())
     }
 }
 
@@ -114,7 +111,7 @@ where
     R: AsyncRead + Unpin,
 {
     let l_aux = reader.read_i32_le().await.and_then(|len| {
-        usize::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(len).map_err(|e| io::Error::from(Error::InvalidAuxLength(e)))
     })?;
 
     let mut aux = vec![0; l_aux];
@@ -131,7 +128,7 @@ where
     R: AsyncRead + Unpin,
 {
     let n_ref = reader.read_i32_le().await.and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidReferenceSequenceCount(e)))
     })?;
 
     let mut reference_sequences = Vec::with_capacity(n_ref);
@@ -156,9 +153,10 @@ async fn read_bins<R>(reader: &mut R, depth: i32) -> io::Result<(Vec<Bin>, Optio
 where
     R: AsyncRead + Unpin,
 {
-    let n_bin = reader.read_i32_le().await.and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-    })?;
+    let n_bin = reader
+        .read_i32_le()
+        .await
+        .and_then(|n| usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidBinCount(e))))?;
 
     let mut bins = Vec::with_capacity(n_bin);
 
@@ -190,7 +188,7 @@ where
     R: AsyncRead + Unpin,
 {
     let n_chunk = reader.read_i32_le().await.and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidChunkCount(e)))
     })?;
 
     let mut chunks = Vec::with_capacity(n_chunk);
@@ -229,13 +227,7 @@ where
     let n_chunk = reader.read_u32_le().await?;
 
     if n_chunk != METADATA_CHUNK_COUNT {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!(
-                "invalid metadata pseudo-bin chunk count: expected {}, got {}",
-                METADATA_CHUNK_COUNT, n_chunk
-            ),
-        ));
+        return Err(Error::InvalidMetadataChunkCount(n_chunk).into());
     }
 
     let ref_beg = reader
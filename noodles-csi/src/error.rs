@@ -0,0 +1,89 @@
+//! CSI errors.
+
+use std::{error, fmt, io, num};
+
+use super::index::reference_sequence::bin::METADATA_CHUNK_COUNT;
+
+/// An error returned when a CSI fails to be read.
+///
+/// Readers continue to return [`std::io::Error`]s, but for invalid data, the error is wrapped in
+/// an `io::Error` with the kind [`std::io::ErrorKind::InvalidData`]. The cause can be inspected by
+/// downcasting the inner error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The magic number is invalid.
+    InvalidMagicNumber([u8; 4]),
+    /// The auxiliary data length (`l_aux`) is invalid.
+    InvalidAuxLength(num::TryFromIntError),
+    /// The number of reference sequences (`n_ref`) is invalid.
+    InvalidReferenceSequenceCount(num::TryFromIntError),
+    /// The number of bins (`n_bin`) is invalid.
+    InvalidBinCount(num::TryFromIntError),
+    /// The number of chunks (`n_chunk`) is invalid.
+    InvalidChunkCount(num::TryFromIntError),
+    /// The number of chunks in the metadata pseudo-bin is invalid.
+    InvalidMetadataChunkCount(u32),
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidMagicNumber(_) => None,
+            Self::InvalidAuxLength(e) => Some(e),
+            Self::InvalidReferenceSequenceCount(e) => Some(e),
+            Self::InvalidBinCount(e) => Some(e),
+            Self::InvalidChunkCount(e) => Some(e),
+            Self::InvalidMetadataChunkCount(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagicNumber(actual) => {
+                write!(
+                    f,
+                    "invalid magic number: expected CSI\\x01, got {:?}",
+                    actual
+                )
+            }
+            Self::InvalidAuxLength(_) => f.write_str("invalid aux length"),
+            Self::InvalidReferenceSequenceCount(_) => {
+                f.write_str("invalid reference sequence count")
+            }
+            Self::InvalidBinCount(_) => f.write_str("invalid bin count"),
+            Self::InvalidChunkCount(_) => f.write_str("invalid chunk count"),
+            Self::InvalidMetadataChunkCount(actual) => write!(
+                f,
+                "invalid metadata pseudo-bin chunk count: expected {}, got {}",
+                METADATA_CHUNK_COUNT, actual
+            ),
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        Self::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(
+            Error::InvalidMagicNumber(*b"MThd").to_string(),
+            "invalid magic number: expected CSI\\x01, got [77, 84, 104, 100]"
+        );
+
+        assert_eq!(
+            Error::InvalidMetadataChunkCount(1).to_string(),
+            "invalid metadata pseudo-bin chunk count: expected 2, got 1"
+        );
+    }
+}
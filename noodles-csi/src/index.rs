@@ -10,6 +10,8 @@ use std::{
     ops::{Bound, RangeBounds},
 };
 
+use noodles_core::Position;
+
 use super::{index::reference_sequence::bin::Chunk, BinningIndex};
 
 /// A coordinate-sorted index (CSI).
@@ -90,6 +92,109 @@ impl Index {
     pub fn unmapped_read_count(&self) -> Option<u64> {
         self.n_no_coor
     }
+
+    /// Returns the maximum position that can be indexed.
+    ///
+    /// This is 2^(`min_shift` + 3 * `depth`) - 1. Reference sequences longer than the BAI limit
+    /// (2^29 - 1) must use an index with a larger depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::default();
+    /// assert_eq!(index.max_position().get(), (1 << 29) - 1);
+    /// ```
+    pub fn max_position(&self) -> Position {
+        let n = ReferenceSequence::max_position(self.min_shift, self.depth);
+        u64::try_from(n)
+            .ok()
+            .and_then(Position::new)
+            .unwrap_or(Position::MIN)
+    }
+
+    /// Returns the chunks that overlap with the given region.
+    ///
+    /// Unlike [`BinningIndex::query`], the interval uses 64-bit positions, so positions past
+    /// 2^31 - 1 can be queried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_core::Position;
+    /// use noodles_csi as csi;
+    ///
+    /// let index = csi::Index::builder()
+    ///     .set_depth(7)
+    ///     .set_reference_sequences(vec![csi::index::ReferenceSequence::new(
+    ///         Vec::new(),
+    ///         None,
+    ///     )])
+    ///     .build();
+    ///
+    /// let start = Position::new(3_000_000_000).unwrap();
+    /// let end = Position::new(3_000_000_100).unwrap();
+    /// let chunks = index.query_by_position(0, start..=end)?;
+    /// assert!(chunks.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn query_by_position<B>(
+        &self,
+        reference_sequence_id: usize,
+        interval: B,
+    ) -> io::Result<Vec<Chunk>>
+    where
+        B: RangeBounds<Position>,
+    {
+        fn cast_bound_position_to_bound_i64(bound: Bound<&Position>) -> io::Result<Bound<i64>> {
+            let cast = |position: &Position| {
+                i64::try_from(position.get())
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+            };
+
+            match bound {
+                Bound::Included(v) => cast(v).map(Bound::Included),
+                Bound::Excluded(v) => cast(v).map(Bound::Excluded),
+                Bound::Unbounded => Ok(Bound::Unbounded),
+            }
+        }
+
+        let query_interval = (
+            cast_bound_position_to_bound_i64(interval.start_bound())?,
+            cast_bound_position_to_bound_i64(interval.end_bound())?,
+        );
+
+        self.query_chunks(reference_sequence_id, query_interval)
+    }
+
+    fn query_chunks(
+        &self,
+        reference_sequence_id: usize,
+        query_interval: (Bound<i64>, Bound<i64>),
+    ) -> io::Result<Vec<Chunk>> {
+        let reference_sequence = self
+            .reference_sequences()
+            .get(reference_sequence_id)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid reference sequence ID: {}", reference_sequence_id),
+                )
+            })?;
+
+        let query_bins = reference_sequence
+            .query(self.min_shift(), self.depth(), query_interval)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let chunks: Vec<_> = query_bins
+            .iter()
+            .flat_map(|bin| bin.chunks())
+            .copied()
+            .collect();
+
+        Ok(chunks)
+    }
 }
 
 impl BinningIndex<ReferenceSequence> for Index {
@@ -131,32 +236,12 @@ impl BinningIndex<ReferenceSequence> for Index {
             }
         }
 
-        let reference_sequence = self
-            .reference_sequences()
-            .get(reference_sequence_id)
-            .ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    format!("invalid reference sequence ID: {}", reference_sequence_id),
-                )
-            })?;
-
         let query_interval = (
             cast_bound_i32_to_bound_i64(interval.start_bound()),
             cast_bound_i32_to_bound_i64(interval.end_bound()),
         );
 
-        let query_bins = reference_sequence
-            .query(self.min_shift(), self.depth(), query_interval)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
-
-        let chunks: Vec<_> = query_bins
-            .iter()
-            .flat_map(|bin| bin.chunks())
-            .copied()
-            .collect();
-
-        Ok(chunks)
+        self.query_chunks(reference_sequence_id, query_interval)
     }
 }
 
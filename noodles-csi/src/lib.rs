@@ -6,11 +6,14 @@
 pub mod r#async;
 
 pub mod binning_index;
+mod error;
 pub mod index;
 mod reader;
 mod writer;
 
-pub use self::{binning_index::BinningIndex, index::Index, reader::Reader, writer::Writer};
+pub use self::{
+    binning_index::BinningIndex, error::Error, index::Index, reader::Reader, writer::Writer,
+};
 
 #[deprecated(
     since = "0.4.0",
@@ -8,7 +8,7 @@ use super::{
         reference_sequence::{bin::Chunk, Bin, Metadata},
         ReferenceSequence,
     },
-    Index, MAGIC_NUMBER,
+    Error, Index, MAGIC_NUMBER,
 };
 
 /// A CSI reader.
@@ -82,10 +82,7 @@ where
     if magic == MAGIC_NUMBER {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "invalid CSI file format",
-        ))
+        Err(Error::InvalidMagicNumber(magic).into())
     }
 }
 
@@ -94,7 +91,7 @@ where
     R: Read,
 {
     let l_aux = reader.read_i32::<LittleEndian>().and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidAuxLength(e)))
     })?;
 
     let mut aux = vec![0; l_aux];
@@ -108,7 +105,7 @@ where
     R: Read,
 {
     let n_ref = reader.read_i32::<LittleEndian>().and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidReferenceSequenceCount(e)))
     })?;
 
     let mut reference_sequences = Vec::with_capacity(n_ref);
@@ -126,9 +123,9 @@ fn read_bins<R>(reader: &mut R, depth: i32) -> io::Result<(Vec<Bin>, Option<Meta
 where
     R: Read,
 {
-    let n_bin = reader.read_i32::<LittleEndian>().and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-    })?;
+    let n_bin = reader
+        .read_i32::<LittleEndian>()
+        .and_then(|n| usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidBinCount(e))))?;
 
     let mut bins = Vec::with_capacity(n_bin);
 
@@ -159,7 +156,7 @@ where
     R: Read,
 {
     let n_chunk = reader.read_i32::<LittleEndian>().and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidChunkCount(e)))
     })?;
 
     let mut chunks = Vec::with_capacity(n_chunk);
@@ -188,13 +185,7 @@ where
     let n_chunk = reader.read_u32::<LittleEndian>()?;
 
     if n_chunk != METADATA_CHUNK_COUNT {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!(
-                "invalid metadata pseudo-bin chunk count: expected {}, got {}",
-                METADATA_CHUNK_COUNT, n_chunk
-            ),
-        ));
+        return Err(Error::InvalidMetadataChunkCount(n_chunk).into());
     }
 
     let ref_beg = reader
@@ -253,7 +244,10 @@ mod tests {
         let mut reader = &data[..];
         assert!(matches!(
             read_magic(&mut reader),
-            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+            Err(ref e) if matches!(
+                e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+                Some(Error::InvalidMagicNumber(actual)) if actual == b"MThd"
+            )
         ));
     }
 
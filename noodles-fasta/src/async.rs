@@ -0,0 +1,3 @@
+mod indexed_reader;
+
+pub use self::indexed_reader::IndexedReader;
@@ -0,0 +1,119 @@
+use std::io::SeekFrom;
+
+use noodles_core::Region;
+use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+use crate::{
+    fai,
+    reader::{interval_to_slice_range, position_offset, resolve_region},
+    record::{Definition, Sequence},
+    Record,
+};
+
+/// An async indexed FASTA reader.
+///
+/// This pairs a FASTA reader with its associated index. Queries use the line geometry of the
+/// index to read only the bytes that span the requested region.
+pub struct IndexedReader<R> {
+    inner: R,
+    index: fai::Index,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Creates an async indexed FASTA reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// use noodles_fasta as fasta;
+    /// let data = [];
+    /// let reader = fasta::AsyncIndexedReader::new(Cursor::new(&data[..]), Vec::new());
+    /// ```
+    pub fn new(inner: R, index: fai::Index) -> Self {
+        Self { inner, index }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps and returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns the associated index.
+    pub fn index(&self) -> &[fai::Record] {
+        &self.index
+    }
+
+    /// Returns a record of the subsequence of the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_core::Region;
+    /// use noodles_fasta::{self as fasta, fai};
+    ///
+    /// let data = b">sq0\nNNNN\nACGT\n";
+    /// let index = vec![fai::Record::new(String::from("sq0"), 8, 5, 4, 5)];
+    /// let mut reader = fasta::AsyncIndexedReader::new(Cursor::new(&data[..]), index);
+    ///
+    /// let region = Region::mapped("sq0", 4..=6);
+    /// let record = reader.query(&region).await?;
+    ///
+    /// assert_eq!(record.name(), "sq0:4-6");
+    /// assert_eq!(record.sequence().as_ref(), b"NAC");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query(&mut self, region: &Region) -> io::Result<Record> {
+        let (i, interval) = resolve_region(&self.index, region)?;
+        let index_record = &self.index[i];
+
+        let len = usize::try_from(index_record.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let range = interval_to_slice_range(interval, len)?;
+
+        if range.end > len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "region end is out of bounds",
+            ));
+        }
+
+        let definition = Definition::new(region.to_string(), None);
+
+        let mut buf = Vec::new();
+
+        if range.start < range.end {
+            let start = position_offset(index_record, range.start + 1)?;
+            let end = position_offset(index_record, range.end)? + 1;
+
+            self.inner.seek(SeekFrom::Start(start)).await?;
+
+            let n = usize::try_from(end - start)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            buf.resize(n, 0);
+            self.inner.read_exact(&mut buf).await?;
+
+            buf.retain(|&b| b != b'\n' && b != b'\r');
+        }
+
+        Ok(Record::new(definition, Sequence::from(buf)))
+    }
+}
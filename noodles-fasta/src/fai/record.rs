@@ -60,6 +60,32 @@ impl Record {
     pub fn line_width(&self) -> u64 {
         self.line_width
     }
+
+    /// Returns the byte offset of the given 1-based position in the sequence.
+    ///
+    /// This uses the line geometry of the record to account for line terminators. If the
+    /// position is not in the sequence, this returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::fai;
+    /// let record = fai::Record::new(String::from("sq0"), 10, 5, 4, 5);
+    /// assert_eq!(record.position_offset(1), Some(5));
+    /// assert_eq!(record.position_offset(6), Some(11));
+    /// assert_eq!(record.position_offset(11), None);
+    /// ```
+    pub fn position_offset(&self, position: u64) -> Option<u64> {
+        if position == 0 || position > self.len || self.line_bases == 0 {
+            return None;
+        }
+
+        let i = position - 1;
+        let line = i / self.line_bases;
+        let column = i % self.line_bases;
+
+        Some(self.offset + line * self.line_width + column)
+    }
 }
 
 /// An error returned when a raw FASTA index record fails to parse.
@@ -135,6 +161,21 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_position_offset() {
+        let record = Record::new(String::from("sq0"), 10, 5, 4, 6);
+
+        assert_eq!(record.position_offset(0), None);
+        assert_eq!(record.position_offset(1), Some(5));
+        assert_eq!(record.position_offset(4), Some(8));
+        assert_eq!(record.position_offset(5), Some(11));
+        assert_eq!(record.position_offset(10), Some(18));
+        assert_eq!(record.position_offset(11), None);
+
+        let record = Record::new(String::from("sq0"), 10, 5, 0, 0);
+        assert_eq!(record.position_offset(1), None);
+    }
+
     #[test]
     fn test_from_str() {
         assert_eq!(
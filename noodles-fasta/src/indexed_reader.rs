@@ -0,0 +1,198 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+use noodles_core::Region;
+
+use crate::{
+    fai,
+    reader::{interval_to_slice_range, position_offset, resolve_region},
+    record::{Definition, Sequence},
+    Record,
+};
+
+/// An indexed FASTA reader.
+///
+/// This pairs a FASTA reader with its associated index. Queries use the line geometry of the
+/// index to read only the bytes that span the requested region.
+///
+/// The underlying reader can be either an uncompressed stream or a bgzipped stream wrapped in a
+/// [`noodles_bgzf::IndexedReader`], which uses the associated gzip index (GZI) to translate the
+/// uncompressed offsets in the FASTA index to positions in the compressed stream.
+///
+/// With the `mmap` feature enabled, an uncompressed FASTA file can also be read from a
+/// `noodles_bgzf::mmap::MappedFile`, which serves queries from the mapping rather than issuing a
+/// seek and read per query.
+///
+/// # Examples
+///
+/// ## Query an uncompressed FASTA file
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_core::Region;
+/// use noodles_fasta::{self as fasta, fai};
+///
+/// let index = fai::read("reference.fa.fai")?;
+/// let mut reader = File::open("reference.fa").map(|f| fasta::IndexedReader::new(f, index))?;
+///
+/// let region = Region::mapped("sq0", 8..=13);
+/// let record = reader.query(&region)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+///
+/// ## Query a bgzipped FASTA file
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bgzf::{self as bgzf, gzi};
+/// use noodles_core::Region;
+/// use noodles_fasta::{self as fasta, fai};
+///
+/// let index = fai::read("reference.fa.gz.fai")?;
+/// let gzi_index = gzi::read("reference.fa.gz.gzi")?;
+///
+/// let mut reader = File::open("reference.fa.gz")
+///     .map(|f| bgzf::IndexedReader::new(f, gzi_index))
+///     .map(|r| fasta::IndexedReader::new(r, index))?;
+///
+/// let region = Region::mapped("sq0", 8..=13);
+/// let record = reader.query(&region)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct IndexedReader<R> {
+    inner: R,
+    index: fai::Index,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read + Seek,
+{
+    /// Creates an indexed FASTA reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// use noodles_fasta as fasta;
+    /// let data = [];
+    /// let reader = fasta::IndexedReader::new(Cursor::new(&data[..]), Vec::new());
+    /// ```
+    pub fn new(inner: R, index: fai::Index) -> Self {
+        Self { inner, index }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps and returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns the associated index.
+    pub fn index(&self) -> &[fai::Record] {
+        &self.index
+    }
+
+    /// Returns a record of the subsequence of the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_core::Region;
+    /// use noodles_fasta::{self as fasta, fai};
+    ///
+    /// let data = b">sq0\nNNNN\nACGT\n";
+    /// let index = vec![fai::Record::new(String::from("sq0"), 8, 5, 4, 5)];
+    /// let mut reader = fasta::IndexedReader::new(Cursor::new(&data[..]), index);
+    ///
+    /// let region = Region::mapped("sq0", 4..=6);
+    /// let record = reader.query(&region)?;
+    ///
+    /// assert_eq!(record.name(), "sq0:4-6");
+    /// assert_eq!(record.sequence().as_ref(), b"NAC");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn query(&mut self, region: &Region) -> io::Result<Record> {
+        let (i, interval) = resolve_region(&self.index, region)?;
+        let index_record = &self.index[i];
+
+        let len = usize::try_from(index_record.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let range = interval_to_slice_range(interval, len)?;
+
+        if range.end > len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "region end is out of bounds",
+            ));
+        }
+
+        let definition = Definition::new(region.to_string(), None);
+
+        let mut buf = Vec::new();
+
+        if range.start < range.end {
+            let start = position_offset(index_record, range.start + 1)?;
+            let end = position_offset(index_record, range.end)? + 1;
+
+            self.inner.seek(SeekFrom::Start(start))?;
+
+            let n = usize::try_from(end - start)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            buf.resize(n, 0);
+            self.inner.read_exact(&mut buf)?;
+
+            buf.retain(|&b| b != b'\n' && b != b'\r');
+        }
+
+        Ok(Record::new(definition, Sequence::from(buf)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use noodles_bgzf as bgzf;
+
+    use super::*;
+
+    #[test]
+    fn test_query_with_bgzf() -> io::Result<()> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(b">sq0\nNNNN\n")?;
+        writer.flush()?;
+        let cpos = writer.get_ref().len() as u64;
+        writer.write_all(b"ACGT\n>sq1\nTTGG\n")?;
+        let data = writer.finish()?;
+
+        let gzi_index = vec![(cpos, 10)];
+        let index = vec![
+            fai::Record::new(String::from("sq0"), 8, 5, 4, 5),
+            fai::Record::new(String::from("sq1"), 4, 20, 4, 5),
+        ];
+
+        let inner = bgzf::IndexedReader::new(Cursor::new(data), gzi_index);
+        let mut reader = IndexedReader::new(inner, index);
+
+        let record = reader.query(&Region::mapped("sq0", 4..=6))?;
+        assert_eq!(record.sequence().as_ref(), b"NAC");
+
+        let record = reader.query(&Region::mapped("sq1", ..))?;
+        assert_eq!(record.sequence().as_ref(), b"TTGG");
+
+        let record = reader.query(&Region::mapped("sq0", 5..=8))?;
+        assert_eq!(record.sequence().as_ref(), b"ACGT");
+
+        Ok(())
+    }
+}
@@ -52,13 +52,24 @@
 //! # Ok::<(), io::Error>(())
 //! ```
 
+#[cfg(feature = "async")]
+mod r#async;
+
 pub mod fai;
+mod indexed_reader;
 mod indexer;
 pub mod reader;
 pub mod record;
+pub mod repository;
 mod writer;
 
-pub use self::{reader::Reader, record::Record, writer::Writer};
+pub use self::{
+    indexed_reader::IndexedReader, reader::Reader, record::Record, repository::Repository,
+    writer::Writer,
+};
+
+#[cfg(feature = "async")]
+pub use self::r#async::IndexedReader as AsyncIndexedReader;
 
 use std::{
     fs::File,
@@ -343,7 +343,10 @@ where
     Ok(bytes_read)
 }
 
-fn resolve_region(index: &[fai::Record], region: &Region) -> io::Result<(usize, Interval)> {
+pub(crate) fn resolve_region(
+    index: &[fai::Record],
+    region: &Region,
+) -> io::Result<(usize, Interval)> {
     if let Some(r) = region.as_mapped() {
         let i = index
             .iter()
@@ -364,8 +367,17 @@ fn resolve_region(index: &[fai::Record], region: &Region) -> io::Result<(usize,
     }
 }
 
+// Returns the offset of the given 1-based position in the stream using the line geometry of the
+// index record.
+pub(crate) fn position_offset(record: &fai::Record, position: usize) -> io::Result<u64> {
+    u64::try_from(position)
+        .ok()
+        .and_then(|p| record.position_offset(p))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid position"))
+}
+
 // Shifts a 1-based interval to a 0-based range for slicing.
-fn interval_to_slice_range(interval: Interval, len: usize) -> io::Result<Range<usize>> {
+pub(crate) fn interval_to_slice_range(interval: Interval, len: usize) -> io::Result<Range<usize>> {
     let start = match interval.start_bound() {
         Bound::Included(&s) => usize::try_from(s)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
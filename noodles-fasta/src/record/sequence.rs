@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 /// A FASTA record sequence.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Sequence(Vec<u8>);
@@ -28,6 +30,142 @@ impl Sequence {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Returns the complement of the sequence.
+    ///
+    /// IUPAC nucleotide codes are complemented, preserving case. Other characters, e.g., gaps
+    /// (`-`), are left as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::Sequence;
+    /// let sequence = Sequence::from(b"ACGTNacgtn".to_vec());
+    /// assert_eq!(sequence.complement().as_ref(), b"TGCANtgcan");
+    /// ```
+    pub fn complement(&self) -> Self {
+        Self(self.0.iter().copied().map(complement).collect())
+    }
+
+    /// Returns the reverse complement of the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::Sequence;
+    /// let sequence = Sequence::from(b"AACGTn".to_vec());
+    /// assert_eq!(sequence.reverse_complement().as_ref(), b"nACGTT");
+    /// ```
+    pub fn reverse_complement(&self) -> Self {
+        Self(self.0.iter().rev().copied().map(complement).collect())
+    }
+
+    /// Returns the sequence with all bases in uppercase.
+    ///
+    /// This removes soft-masking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::Sequence;
+    /// let sequence = Sequence::from(b"ACgtN".to_vec());
+    /// assert_eq!(sequence.to_uppercase().as_ref(), b"ACGTN");
+    /// ```
+    pub fn to_uppercase(&self) -> Self {
+        Self(self.0.to_ascii_uppercase())
+    }
+
+    /// Returns an iterator over the bases in uppercase, each paired with whether it is
+    /// soft-masked.
+    ///
+    /// A base is soft-masked if it is lowercase.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::Sequence;
+    ///
+    /// let sequence = Sequence::from(b"AcG".to_vec());
+    ///
+    /// assert_eq!(
+    ///     sequence.bases().collect::<Vec<_>>(),
+    ///     [(b'A', false), (b'C', true), (b'G', false)]
+    /// );
+    /// ```
+    pub fn bases(&self) -> impl Iterator<Item = (u8, bool)> + '_ {
+        self.0
+            .iter()
+            .map(|&b| (b.to_ascii_uppercase(), b.is_ascii_lowercase()))
+    }
+
+    /// Returns the 0-based, half-open ranges of soft-masked bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::Sequence;
+    /// let sequence = Sequence::from(b"acGTNnnA".to_vec());
+    /// assert_eq!(sequence.softmasked_ranges(), [0..2, 5..7]);
+    /// ```
+    pub fn softmasked_ranges(&self) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut start = None;
+
+        for (i, b) in self.0.iter().enumerate() {
+            match (b.is_ascii_lowercase(), start) {
+                (true, None) => start = Some(i),
+                (false, Some(s)) => {
+                    ranges.push(s..i);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(s) = start {
+            ranges.push(s..self.0.len());
+        }
+
+        ranges
+    }
+
+    /// Returns the GC content of the sequence.
+    ///
+    /// This is the fraction of unambiguous bases (A, C, G, T/U) that are G or C, ignoring case.
+    /// If the sequence has no unambiguous bases, this returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::Sequence;
+    ///
+    /// let sequence = Sequence::from(b"ACgtNN".to_vec());
+    /// assert_eq!(sequence.gc_content(), Some(0.5));
+    ///
+    /// let sequence = Sequence::from(b"NN".to_vec());
+    /// assert!(sequence.gc_content().is_none());
+    /// ```
+    pub fn gc_content(&self) -> Option<f64> {
+        let mut gc_count = 0;
+        let mut count = 0;
+
+        for b in &self.0 {
+            match b.to_ascii_uppercase() {
+                b'G' | b'C' => {
+                    gc_count += 1;
+                    count += 1;
+                }
+                b'A' | b'T' | b'U' => count += 1,
+                _ => {}
+            }
+        }
+
+        if count == 0 {
+            None
+        } else {
+            Some(f64::from(gc_count) / f64::from(count))
+        }
+    }
 }
 
 impl AsRef<[u8]> for Sequence {
@@ -41,3 +179,57 @@ impl From<Vec<u8>> for Sequence {
         Self(data)
     }
 }
+
+fn complement(b: u8) -> u8 {
+    let c = match b.to_ascii_uppercase() {
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' | b'U' => b'A',
+        b'R' => b'Y',
+        b'Y' => b'R',
+        b'K' => b'M',
+        b'M' => b'K',
+        b'B' => b'V',
+        b'V' => b'B',
+        b'D' => b'H',
+        b'H' => b'D',
+        _ => return b,
+    };
+
+    if b.is_ascii_lowercase() {
+        c.to_ascii_lowercase()
+    } else {
+        c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complement() {
+        let sequence = Sequence::from(b"ACGTURYKMBVDHSWN-.*".to_vec());
+        assert_eq!(sequence.complement().as_ref(), b"TGCAAYRMKVBHDSWN-.*");
+
+        let sequence = Sequence::from(b"acgturykmbvdhswn".to_vec());
+        assert_eq!(sequence.complement().as_ref(), b"tgcaayrmkvbhdswn");
+    }
+
+    #[test]
+    fn test_softmasked_ranges() {
+        assert!(Sequence::default().softmasked_ranges().is_empty());
+        assert!(Sequence::from(b"ACGT".to_vec())
+            .softmasked_ranges()
+            .is_empty());
+        assert_eq!(
+            Sequence::from(b"acgTn".to_vec()).softmasked_ranges(),
+            [0..3, 4..5]
+        );
+        assert_eq!(
+            Sequence::from(b"AcGtN".to_vec()).softmasked_ranges(),
+            [1..2, 3..4]
+        );
+    }
+}
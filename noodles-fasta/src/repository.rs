@@ -0,0 +1,149 @@
+//! FASTA repository.
+
+use std::{collections::HashMap, fmt, io};
+
+use super::Record;
+
+/// A repository adapter.
+///
+/// An adapter is a source of reference sequence records, e.g., a FASTA file or a remote service.
+pub trait Adapter {
+    /// Returns the record with the given name.
+    ///
+    /// This returns `None` if the adapter does not have a record with the given name.
+    fn get(&mut self, name: &str) -> Option<io::Result<Record>>;
+}
+
+impl Adapter for Vec<Record> {
+    fn get(&mut self, name: &str) -> Option<io::Result<Record>> {
+        self.iter()
+            .find(|record| record.name() == name)
+            .cloned()
+            .map(Ok)
+    }
+}
+
+/// A caching reference sequence repository.
+///
+/// Records are fetched from the adapter on first use and cached for subsequent lookups.
+pub struct Repository {
+    adapter: Box<dyn Adapter>,
+    cache: HashMap<String, Record>,
+}
+
+impl Repository {
+    /// Creates a repository.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::{self as fasta, repository::Repository};
+    /// let repository = Repository::new(Vec::<fasta::Record>::new());
+    /// ```
+    pub fn new<A>(adapter: A) -> Self
+    where
+        A: Adapter + 'static,
+    {
+        Self {
+            adapter: Box::new(adapter),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Returns the record with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fasta::{self as fasta, record::{Definition, Sequence}, repository::Repository};
+    ///
+    /// let records = vec![fasta::Record::new(
+    ///     Definition::new("sq0", None),
+    ///     Sequence::from(b"ACGT".to_vec()),
+    /// )];
+    ///
+    /// let mut repository = Repository::new(records);
+    ///
+    /// let record = repository.get("sq0").transpose()?;
+    /// assert_eq!(record.map(|r| r.name()), Some("sq0"));
+    ///
+    /// assert!(repository.get("sq1").is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn get(&mut self, name: &str) -> Option<io::Result<&Record>> {
+        if !self.cache.contains_key(name) {
+            match self.adapter.get(name)? {
+                Ok(record) => {
+                    self.cache.insert(name.into(), record);
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        self.cache.get(name).map(Ok)
+    }
+
+    /// Removes all cached records.
+    pub fn clear_cache(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl fmt::Debug for Repository {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Repository")
+            .field("cache", &self.cache)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+    use crate::record::{Definition, Sequence};
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        struct CountingAdapter(Rc<Cell<usize>>);
+
+        impl Adapter for CountingAdapter {
+            fn get(&mut self, name: &str) -> Option<io::Result<Record>> {
+                self.0.set(self.0.get() + 1);
+
+                match name {
+                    "sq0" => Some(Ok(Record::new(
+                        Definition::new("sq0", None),
+                        Sequence::from(b"ACGT".to_vec()),
+                    ))),
+                    "sq1" => Some(Err(io::Error::from(io::ErrorKind::InvalidData))),
+                    _ => None,
+                }
+            }
+        }
+
+        let count = Rc::new(Cell::new(0));
+        let mut repository = Repository::new(CountingAdapter(count.clone()));
+
+        assert_eq!(
+            repository
+                .get("sq0")
+                .transpose()?
+                .map(|r| r.sequence().as_ref()),
+            Some(&b"ACGT"[..])
+        );
+        assert!(repository.get("sq0").is_some());
+        assert_eq!(count.get(), 1);
+
+        assert!(matches!(repository.get("sq1"), Some(Err(_))));
+        assert!(repository.get("sq2").is_none());
+
+        repository.clear_cache();
+        assert!(repository.get("sq0").is_some());
+        assert_eq!(count.get(), 4);
+
+        Ok(())
+    }
+}
@@ -34,8 +34,8 @@ mod r#async;
 pub mod fai;
 mod indexer;
 mod reader;
-mod record;
-mod writer;
+pub mod record;
+pub mod writer;
 
 pub use self::{indexer::Indexer, reader::Reader, record::Record, writer::Writer};
 
@@ -1,4 +1,10 @@
-use std::fmt;
+//! FASTQ record and fields.
+
+pub mod quality_scores;
+
+use std::{error, fmt};
+
+use self::quality_scores::{DecodeError, Encoding};
 
 /// A FASTQ record.
 #[derive(Clone, Default, Debug, Eq, PartialEq)]
@@ -95,6 +101,77 @@ impl Record {
         &mut self.quality_scores
     }
 
+    /// Returns the quality scores decoded as Phred quality scores.
+    ///
+    /// The raw quality scores are assumed to be Phred+33 encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::Record;
+    /// let record = Record::new("r0", "AGCT", "NDLS");
+    /// assert_eq!(record.phred_quality_scores(), Ok(vec![45, 35, 43, 50]));
+    /// ```
+    pub fn phred_quality_scores(&self) -> Result<Vec<u8>, DecodeError> {
+        quality_scores::decode(self.quality_scores(), Encoding::Phred33)
+    }
+
+    /// Converts the raw quality scores from one encoding to another.
+    ///
+    /// The quality scores are unchanged if the conversion fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::{record::quality_scores::Encoding, Record};
+    ///
+    /// let mut record = Record::new("r0", "AGCT", "hJhJ");
+    /// record.convert_quality_scores(Encoding::Phred64, Encoding::Phred33)?;
+    ///
+    /// assert_eq!(record.quality_scores(), b"I+I+");
+    /// # Ok::<_, noodles_fastq::record::quality_scores::DecodeError>(())
+    /// ```
+    pub fn convert_quality_scores(
+        &mut self,
+        from: Encoding,
+        to: Encoding,
+    ) -> Result<(), DecodeError> {
+        self.quality_scores = quality_scores::convert(self.quality_scores(), from, to)?;
+        Ok(())
+    }
+
+    /// Validates the record.
+    ///
+    /// This checks that the sequence and quality scores have the same length and that the quality
+    /// scores are valid Phred+33 characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::{record::ValidationError, Record};
+    ///
+    /// let record = Record::new("r0", "AGCT", "NDLS");
+    /// assert!(record.validate().is_ok());
+    ///
+    /// let record = Record::new("r0", "AGCT", "NDL");
+    /// assert_eq!(
+    ///     record.validate(),
+    ///     Err(ValidationError::LengthMismatch(4, 3))
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.sequence.len() != self.quality_scores.len() {
+            return Err(ValidationError::LengthMismatch(
+                self.sequence.len(),
+                self.quality_scores.len(),
+            ));
+        }
+
+        self.phred_quality_scores()
+            .map(|_| ())
+            .map_err(ValidationError::InvalidQualityScores)
+    }
+
     // Truncates all field buffers to 0.
     pub(crate) fn clear(&mut self) {
         self.name.clear();
@@ -103,6 +180,32 @@ impl Record {
     }
 }
 
+/// An error returned when a FASTQ record fails validation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationError {
+    /// The sequence and quality scores lengths differ.
+    ///
+    /// This includes the sequence length and quality scores length, respectively.
+    LengthMismatch(usize, usize),
+    /// The quality scores are invalid.
+    InvalidQualityScores(DecodeError),
+}
+
+impl error::Error for ValidationError {}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch(sequence_len, quality_scores_len) => write!(
+                f,
+                "length mismatch: sequence length = {}, quality scores length = {}",
+                sequence_len, quality_scores_len
+            ),
+            Self::InvalidQualityScores(e) => write!(f, "invalid quality scores: {}", e),
+        }
+    }
+}
+
 impl fmt::Display for Record {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str("@")?;
@@ -141,6 +244,24 @@ mod tests {
         assert_eq!(record.to_string(), "@r0\nATCG\n+\nNDLS\n");
     }
 
+    #[test]
+    fn test_validate() {
+        assert!(Record::default().validate().is_ok());
+        assert!(Record::new("r0", "AGCT", "NDLS").validate().is_ok());
+
+        assert_eq!(
+            Record::new("r0", "AGC", "NDLS").validate(),
+            Err(ValidationError::LengthMismatch(3, 4))
+        );
+
+        assert_eq!(
+            Record::new("r0", "AGCT", "ND S").validate(),
+            Err(ValidationError::InvalidQualityScores(
+                DecodeError::InvalidScore(b' ')
+            ))
+        );
+    }
+
     #[test]
     fn test_clear() {
         let mut record = Record::new("r0", "AGCT", "NDLS");
@@ -0,0 +1,192 @@
+//! FASTQ record quality scores.
+
+use std::{error, fmt};
+
+const PHRED_33_OFFSET: u8 = b'!';
+const PHRED_64_OFFSET: u8 = b'@';
+
+const MAX_PRINTABLE: u8 = b'~';
+
+// Raw quality scores below this are only valid in Phred+33.
+const MIN_PHRED_64: u8 = b';';
+// Raw quality scores above this are not expected in Phred+33 (Illumina 1.8+).
+const MAX_PHRED_33: u8 = b'J';
+
+/// A FASTQ quality score encoding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    /// Phred quality scores offset by 33 (Sanger, Illumina 1.8+).
+    Phred33,
+    /// Phred quality scores offset by 64 (Illumina 1.3–1.7).
+    Phred64,
+}
+
+impl Encoding {
+    /// Returns the ASCII offset of the encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::record::quality_scores::Encoding;
+    /// assert_eq!(Encoding::Phred33.offset(), 33);
+    /// assert_eq!(Encoding::Phred64.offset(), 64);
+    /// ```
+    pub fn offset(self) -> u8 {
+        match self {
+            Self::Phred33 => PHRED_33_OFFSET,
+            Self::Phred64 => PHRED_64_OFFSET,
+        }
+    }
+
+    /// Guesses the encoding of the given raw quality scores.
+    ///
+    /// This returns `None` if the scores are empty or valid in both encodings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::record::quality_scores::Encoding;
+    /// assert_eq!(Encoding::detect(b"#5AJ"), Some(Encoding::Phred33));
+    /// assert_eq!(Encoding::detect(b"BXah"), Some(Encoding::Phred64));
+    /// assert_eq!(Encoding::detect(b"@@@@"), None);
+    /// ```
+    pub fn detect(raw_quality_scores: &[u8]) -> Option<Self> {
+        let min = raw_quality_scores.iter().min()?;
+        let max = raw_quality_scores.iter().max()?;
+
+        if *min < MIN_PHRED_64 {
+            Some(Self::Phred33)
+        } else if *max > MAX_PHRED_33 {
+            Some(Self::Phred64)
+        } else {
+            None
+        }
+    }
+}
+
+/// An error returned when raw quality scores fail to decode.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// A raw quality score is out of range for the encoding.
+    InvalidScore(u8),
+}
+
+impl error::Error for DecodeError {}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidScore(b) => write!(f, "invalid score: {:#04x}", b),
+        }
+    }
+}
+
+/// Decodes raw quality scores to Phred quality scores.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fastq::record::quality_scores::{decode, Encoding};
+/// assert_eq!(decode(b"NDLS", Encoding::Phred33), Ok(vec![45, 35, 43, 50]));
+/// assert_eq!(decode(b"hJ", Encoding::Phred64), Ok(vec![40, 10]));
+/// ```
+pub fn decode(raw_quality_scores: &[u8], encoding: Encoding) -> Result<Vec<u8>, DecodeError> {
+    let offset = encoding.offset();
+
+    raw_quality_scores
+        .iter()
+        .map(|&b| {
+            if (offset..=MAX_PRINTABLE).contains(&b) {
+                Ok(b - offset)
+            } else {
+                Err(DecodeError::InvalidScore(b))
+            }
+        })
+        .collect()
+}
+
+/// Encodes Phred quality scores to raw quality scores.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fastq::record::quality_scores::{encode, Encoding};
+/// assert_eq!(encode(&[45, 35, 43, 50], Encoding::Phred33), Ok(b"NDLS".to_vec()));
+/// assert_eq!(encode(&[40, 10], Encoding::Phred64), Ok(b"hJ".to_vec()));
+/// ```
+pub fn encode(scores: &[u8], encoding: Encoding) -> Result<Vec<u8>, DecodeError> {
+    let offset = encoding.offset();
+
+    scores
+        .iter()
+        .map(|&score| {
+            offset
+                .checked_add(score)
+                .filter(|b| *b <= MAX_PRINTABLE)
+                .ok_or(DecodeError::InvalidScore(score))
+        })
+        .collect()
+}
+
+/// Converts raw quality scores from one encoding to another.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fastq::record::quality_scores::{convert, Encoding};
+/// assert_eq!(convert(b"hJ", Encoding::Phred64, Encoding::Phred33), Ok(b"I+".to_vec()));
+/// ```
+pub fn convert(
+    raw_quality_scores: &[u8],
+    from: Encoding,
+    to: Encoding,
+) -> Result<Vec<u8>, DecodeError> {
+    decode(raw_quality_scores, from).and_then(|scores| encode(&scores, to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Encoding::detect(b""), None);
+        assert_eq!(Encoding::detect(b"!"), Some(Encoding::Phred33));
+        assert_eq!(Encoding::detect(b":"), Some(Encoding::Phred33));
+        assert_eq!(Encoding::detect(b";K"), Some(Encoding::Phred64));
+        assert_eq!(Encoding::detect(b";J"), None);
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(b"", Encoding::Phred33), Ok(Vec::new()));
+        assert_eq!(decode(b"!~", Encoding::Phred33), Ok(vec![0, 93]));
+        assert_eq!(decode(b"@~", Encoding::Phred64), Ok(vec![0, 62]));
+
+        assert_eq!(
+            decode(b" ", Encoding::Phred33),
+            Err(DecodeError::InvalidScore(b' '))
+        );
+        assert_eq!(
+            decode(b"?", Encoding::Phred64),
+            Err(DecodeError::InvalidScore(b'?'))
+        );
+        assert_eq!(
+            decode(&[0x7f], Encoding::Phred33),
+            Err(DecodeError::InvalidScore(0x7f))
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode(&[0, 93], Encoding::Phred33), Ok(b"!~".to_vec()));
+        assert_eq!(
+            encode(&[94], Encoding::Phred33),
+            Err(DecodeError::InvalidScore(94))
+        );
+        assert_eq!(
+            encode(&[63], Encoding::Phred64),
+            Err(DecodeError::InvalidScore(63))
+        );
+    }
+}
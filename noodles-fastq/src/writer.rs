@@ -1,16 +1,43 @@
+//! FASTQ writer.
+
+mod builder;
+mod name_format;
+
+pub use self::{
+    builder::Builder,
+    name_format::{NameFormat, ReadNumber},
+};
+
 use std::io::{self, Write};
 
+use self::name_format::format_name;
 use super::Record;
 
 /// A FASTQ writer.
 pub struct Writer<W> {
     inner: W,
+    name_format: NameFormat,
+    write_description: bool,
+    repeat_name: bool,
 }
 
 impl<W> Writer<W>
 where
     W: Write,
 {
+    /// Creates a FASTQ writer builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq as fastq;
+    /// let builder = fastq::Writer::builder(Vec::new());
+    /// let writer = builder.build();
+    /// ```
+    pub fn builder(inner: W) -> Builder<W> {
+        Builder::new(inner)
+    }
+
     /// Creates a FASTQ writer.
     ///
     /// # Examples
@@ -20,7 +47,7 @@ where
     /// let writer = fastq::Writer::new(Vec::new());
     /// ```
     pub fn new(inner: W) -> Self {
-        Self { inner }
+        Self::builder(inner).build()
     }
 
     /// Returns a reference to the underlying writer.
@@ -53,20 +80,45 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
-        write_record(&mut self.inner, record)
+        if self.name_format == NameFormat::Original && self.write_description {
+            write_record(&mut self.inner, record.name(), record, self.repeat_name)
+        } else {
+            let (id, description) =
+                format_name(record.name(), self.name_format, self.write_description);
+
+            let mut name = id;
+
+            if let Some(description) = description {
+                name.push(b' ');
+                name.extend(description);
+            }
+
+            write_record(&mut self.inner, &name, record, self.repeat_name)
+        }
     }
 }
 
-fn write_record<W>(writer: &mut W, record: &Record) -> io::Result<()>
+fn write_record<W>(
+    writer: &mut W,
+    name: &[u8],
+    record: &Record,
+    repeat_name: bool,
+) -> io::Result<()>
 where
     W: Write,
 {
     writer.write_all(b"@")?;
-    writer.write_all(record.name())?;
+    writer.write_all(name)?;
     writer.write_all(b"\n")?;
 
     writer.write_all(record.sequence())?;
-    writer.write_all(b"\n+\n")?;
+    writer.write_all(b"\n+")?;
+
+    if repeat_name {
+        writer.write_all(name)?;
+    }
+
+    writer.write_all(b"\n")?;
 
     writer.write_all(record.quality_scores())?;
     writer.write_all(b"\n")?;
@@ -83,11 +135,49 @@ mod tests {
         let record = Record::new("r0", "ACGT", "NDLS");
 
         let mut buf = Vec::new();
-        write_record(&mut buf, &record)?;
+        write_record(&mut buf, record.name(), &record, false)?;
 
         let expected = b"@r0\nACGT\n+\nNDLS\n";
         assert_eq!(buf, expected);
 
+        buf.clear();
+        write_record(&mut buf, record.name(), &record, true)?;
+
+        let expected = b"@r0\nACGT\n+r0\nNDLS\n";
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_options() -> io::Result<()> {
+        let record = Record::new("r0/1 1:Y:18:ATCACG", "ACGT", "NDLS");
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_name_format(NameFormat::Casava(ReadNumber::Two))
+            .set_repeat_name(true)
+            .build();
+
+        writer.write_record(&record)?;
+
+        let expected = b"@r0 2:Y:18:ATCACG\nACGT\n+r0 2:Y:18:ATCACG\nNDLS\n";
+        assert_eq!(writer.get_ref(), expected);
+
+        let record = Record::new("r0 LN:4", "ACGT", "NDLS");
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_name_format(NameFormat::Casava(ReadNumber::One))
+            .build();
+        writer.write_record(&record)?;
+        assert_eq!(writer.get_ref(), b"@r0 1:N:0: LN:4\nACGT\n+\nNDLS\n");
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_name_format(NameFormat::Casava(ReadNumber::One))
+            .set_write_description(false)
+            .build();
+        writer.write_record(&record)?;
+        assert_eq!(writer.get_ref(), b"@r0\nACGT\n+\nNDLS\n");
+
         Ok(())
     }
 }
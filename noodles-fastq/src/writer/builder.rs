@@ -0,0 +1,136 @@
+use std::io::Write;
+
+use super::{NameFormat, Writer};
+
+/// A FASTQ writer builder.
+#[derive(Debug)]
+pub struct Builder<W> {
+    inner: W,
+    name_format: Option<NameFormat>,
+    write_description: Option<bool>,
+    repeat_name: Option<bool>,
+}
+
+impl<W> Builder<W>
+where
+    W: Write,
+{
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            name_format: None,
+            write_description: None,
+            repeat_name: None,
+        }
+    }
+
+    /// Sets the read name format.
+    ///
+    /// This controls how the read number of a paired-end read is encoded in the definition line.
+    ///
+    /// By default, the read name is written as is ([`NameFormat::Original`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq::{self as fastq, writer::{NameFormat, ReadNumber}};
+    ///
+    /// let mut writer = fastq::Writer::builder(Vec::new())
+    ///     .set_name_format(NameFormat::Illumina(ReadNumber::Two))
+    ///     .build();
+    ///
+    /// let record = fastq::Record::new("r0", "ATCG", "NDLS");
+    /// writer.write_record(&record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"@r0/2\nATCG\n+\nNDLS\n");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_name_format(mut self, name_format: NameFormat) -> Self {
+        self.name_format = Some(name_format);
+        self
+    }
+
+    /// Sets whether to write the read name description.
+    ///
+    /// The description is the part of the definition line after the first whitespace. When
+    /// disabled, only the read name identifier is written.
+    ///
+    /// By default, the description is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq as fastq;
+    ///
+    /// let mut writer = fastq::Writer::builder(Vec::new())
+    ///     .set_write_description(false)
+    ///     .build();
+    ///
+    /// let record = fastq::Record::new("r0 LN:4", "ATCG", "NDLS");
+    /// writer.write_record(&record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"@r0\nATCG\n+\nNDLS\n");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_write_description(mut self, write_description: bool) -> Self {
+        self.write_description = Some(write_description);
+        self
+    }
+
+    /// Sets whether to repeat the definition line on the plus line.
+    ///
+    /// By default, the plus line is written without the read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq as fastq;
+    ///
+    /// let mut writer = fastq::Writer::builder(Vec::new())
+    ///     .set_repeat_name(true)
+    ///     .build();
+    ///
+    /// let record = fastq::Record::new("r0", "ATCG", "NDLS");
+    /// writer.write_record(&record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"@r0\nATCG\n+r0\nNDLS\n");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_repeat_name(mut self, repeat_name: bool) -> Self {
+        self.repeat_name = Some(repeat_name);
+        self
+    }
+
+    /// Builds a FASTQ writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq as fastq;
+    /// let writer = fastq::Writer::builder(Vec::new()).build();
+    /// ```
+    pub fn build(self) -> Writer<W> {
+        Writer {
+            inner: self.inner,
+            name_format: self.name_format.unwrap_or(NameFormat::Original),
+            write_description: self.write_description.unwrap_or(true),
+            repeat_name: self.repeat_name.unwrap_or(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let builder = Builder::new(Vec::new());
+        assert!(builder.name_format.is_none());
+        assert!(builder.write_description.is_none());
+        assert!(builder.repeat_name.is_none());
+    }
+}
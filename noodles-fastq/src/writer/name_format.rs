@@ -0,0 +1,241 @@
+//! FASTQ writer read name format.
+
+use std::fmt;
+
+/// The read number of a paired-end read.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReadNumber {
+    /// The first read of a pair (`1`).
+    One,
+    /// The second read of a pair (`2`).
+    Two,
+}
+
+impl ReadNumber {
+    fn as_byte(self) -> u8 {
+        match self {
+            Self::One => b'1',
+            Self::Two => b'2',
+        }
+    }
+}
+
+impl fmt::Display for ReadNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::One => f.write_str("1"),
+            Self::Two => f.write_str("2"),
+        }
+    }
+}
+
+/// A FASTQ read name format.
+///
+/// A FASTQ definition line consists of a read name and an optional description, separated by
+/// whitespace. The name format controls how the read number of a paired-end read is encoded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NameFormat {
+    /// The read name is written as is.
+    Original,
+    /// The read number is appended to the read name as a pair suffix, e.g., `r0/1`.
+    ///
+    /// This is the format used by Illumina pipelines prior to Casava 1.8. An existing pair
+    /// suffix is replaced.
+    Illumina(ReadNumber),
+    /// The read number is written as the first field of a Casava 1.8-style description, e.g.,
+    /// `r0 1:N:0:ATCACG`.
+    ///
+    /// If the description is already in this format, only its read number is replaced.
+    /// Otherwise, `<read number>:N:0:` is prepended to the description. An existing pair suffix on
+    /// the read name is removed.
+    ///
+    /// Like any other description, it is not written when descriptions are disabled
+    /// ([`super::Builder::set_write_description`]).
+    Casava(ReadNumber),
+}
+
+/// Formats a read name.
+///
+/// This returns the identifier and optional description.
+pub(super) fn format_name(
+    name: &[u8],
+    name_format: NameFormat,
+    write_description: bool,
+) -> (Vec<u8>, Option<Vec<u8>>) {
+    let (id, description) = split_name(name);
+
+    let description = if write_description { description } else { None };
+
+    match name_format {
+        NameFormat::Original => (id.to_vec(), description.map(|d| d.to_vec())),
+        NameFormat::Illumina(read_number) => {
+            let mut id = strip_pair_suffix(id).to_vec();
+            id.extend([b'/', read_number.as_byte()]);
+            (id, description.map(|d| d.to_vec()))
+        }
+        NameFormat::Casava(read_number) => {
+            let id = strip_pair_suffix(id).to_vec();
+
+            if !write_description {
+                return (id, None);
+            }
+
+            let description = match description {
+                Some(d) if is_casava_description(d) => {
+                    let mut d = d.to_vec();
+                    d[0] = read_number.as_byte();
+                    d
+                }
+                Some(d) => {
+                    let mut buf = casava_description(read_number);
+                    buf.push(b' ');
+                    buf.extend(d);
+                    buf
+                }
+                None => casava_description(read_number),
+            };
+
+            (id, Some(description))
+        }
+    }
+}
+
+fn casava_description(read_number: ReadNumber) -> Vec<u8> {
+    let mut buf = vec![read_number.as_byte()];
+    buf.extend(b":N:0:");
+    buf
+}
+
+fn split_name(name: &[u8]) -> (&[u8], Option<&[u8]>) {
+    match name.iter().position(|&b| b == b' ' || b == b'\t') {
+        Some(i) => (&name[..i], Some(&name[i + 1..])),
+        None => (name, None),
+    }
+}
+
+fn strip_pair_suffix(id: &[u8]) -> &[u8] {
+    match id {
+        [prefix @ .., b'/', b'1' | b'2'] => prefix,
+        _ => id,
+    }
+}
+
+// <read number>:<is filtered>:<control number>:<index>
+fn is_casava_description(description: &[u8]) -> bool {
+    let mut fields = description.splitn(4, |&b| b == b':');
+
+    matches!(fields.next(), Some(b"1" | b"2"))
+        && matches!(fields.next(), Some(b"Y" | b"N"))
+        && fields
+            .next()
+            .map(|s| !s.is_empty() && s.iter().all(u8::is_ascii_digit))
+            .unwrap_or(false)
+        && fields.next().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_name() {
+        fn t(
+            name: &[u8],
+            name_format: NameFormat,
+            write_description: bool,
+            expected: (&[u8], Option<&[u8]>),
+        ) {
+            let (id, description) = format_name(name, name_format, write_description);
+            assert_eq!((id.as_slice(), description.as_deref()), expected);
+        }
+
+        t(b"r0", NameFormat::Original, true, (b"r0", None));
+        t(
+            b"r0 LN:4",
+            NameFormat::Original,
+            true,
+            (b"r0", Some(b"LN:4")),
+        );
+        t(b"r0 LN:4", NameFormat::Original, false, (b"r0", None));
+        t(
+            b"r0\tLN:4",
+            NameFormat::Original,
+            true,
+            (b"r0", Some(b"LN:4")),
+        );
+
+        t(
+            b"r0",
+            NameFormat::Illumina(ReadNumber::One),
+            true,
+            (b"r0/1", None),
+        );
+        t(
+            b"r0/1",
+            NameFormat::Illumina(ReadNumber::Two),
+            true,
+            (b"r0/2", None),
+        );
+        t(
+            b"r0 LN:4",
+            NameFormat::Illumina(ReadNumber::Two),
+            true,
+            (b"r0/2", Some(b"LN:4")),
+        );
+
+        t(
+            b"r0",
+            NameFormat::Casava(ReadNumber::One),
+            true,
+            (b"r0", Some(b"1:N:0:")),
+        );
+        t(
+            b"r0/1",
+            NameFormat::Casava(ReadNumber::Two),
+            true,
+            (b"r0", Some(b"2:N:0:")),
+        );
+        t(
+            b"r0 LN:4",
+            NameFormat::Casava(ReadNumber::One),
+            true,
+            (b"r0", Some(b"1:N:0: LN:4")),
+        );
+        t(
+            b"r0 LN:4",
+            NameFormat::Casava(ReadNumber::One),
+            false,
+            (b"r0", None),
+        );
+        t(
+            b"r0/1",
+            NameFormat::Casava(ReadNumber::Two),
+            false,
+            (b"r0", None),
+        );
+        t(
+            b"r0 1:Y:18:ATCACG",
+            NameFormat::Casava(ReadNumber::Two),
+            true,
+            (b"r0", Some(b"2:Y:18:ATCACG")),
+        );
+        t(
+            b"r0 1:Y:18:ATCACG",
+            NameFormat::Casava(ReadNumber::Two),
+            false,
+            (b"r0", None),
+        );
+    }
+
+    #[test]
+    fn test_is_casava_description() {
+        assert!(is_casava_description(b"1:N:0:ATCACG"));
+        assert!(is_casava_description(b"2:Y:18:1"));
+        assert!(is_casava_description(b"1:N:0:"));
+        assert!(!is_casava_description(b"3:N:0:ATCACG"));
+        assert!(!is_casava_description(b"1:X:0:ATCACG"));
+        assert!(!is_casava_description(b"1:N::ATCACG"));
+        assert!(!is_casava_description(b"1:N:0"));
+        assert!(!is_casava_description(b"LN:4"));
+    }
+}
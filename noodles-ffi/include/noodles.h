@@ -0,0 +1,109 @@
+#ifndef NOODLES_H
+#define NOODLES_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Errors
+ *
+ * Functions that fail return NULL or -1. The message of the last error on the calling thread is
+ * returned by noodles_last_error_message and is valid until the next failing call on the same
+ * thread.
+ */
+
+const char *noodles_last_error_message(void);
+
+/*
+ * Alignment (BAM and CRAM) readers
+ *
+ * Records returned by noodles_alignment_reader_next are owned by the reader and are valid until
+ * the next call to noodles_alignment_reader_next or noodles_alignment_reader_free.
+ */
+
+typedef struct noodles_alignment_reader noodles_alignment_reader;
+typedef struct noodles_alignment_record noodles_alignment_record;
+
+noodles_alignment_reader *noodles_bam_reader_open(const char *src);
+
+/* reference_src is an optional path to a FASTA file. It may be NULL. */
+noodles_alignment_reader *noodles_cram_reader_open(const char *src, const char *reference_src);
+
+void noodles_alignment_reader_free(noodles_alignment_reader *reader);
+
+const char *noodles_alignment_reader_header(const noodles_alignment_reader *reader);
+
+/* Reads the records that intersect the region. Returns 0 on success and -1 on error. */
+int noodles_alignment_reader_query(noodles_alignment_reader *reader,
+                                   const char *index_src,
+                                   const char *region);
+
+/* Returns 1 if a record was read, 0 at the end of the stream or query, and -1 on error. */
+int noodles_alignment_reader_next(noodles_alignment_reader *reader,
+                                  const noodles_alignment_record **record);
+
+/* Returns NULL if the read name is missing. */
+const char *noodles_alignment_record_read_name(const noodles_alignment_record *record);
+
+uint16_t noodles_alignment_record_flags(const noodles_alignment_record *record);
+
+/* Returns NULL if the reference sequence name is missing. */
+const char *noodles_alignment_record_reference_sequence_name(
+    const noodles_alignment_record *record);
+
+/* Returns the 1-based position or 0 if it is missing. */
+int32_t noodles_alignment_record_position(const noodles_alignment_record *record);
+
+/* Returns 255 if the mapping quality is missing. */
+uint8_t noodles_alignment_record_mapping_quality(const noodles_alignment_record *record);
+
+const char *noodles_alignment_record_cigar(const noodles_alignment_record *record);
+
+/* Returns the record as a SAM record line. */
+const char *noodles_alignment_record_to_string(const noodles_alignment_record *record);
+
+/*
+ * Variant (VCF) readers
+ *
+ * Records returned by noodles_vcf_reader_next are owned by the reader and are valid until the
+ * next call to noodles_vcf_reader_next or noodles_vcf_reader_free.
+ */
+
+typedef struct noodles_vcf_reader noodles_vcf_reader;
+typedef struct noodles_vcf_record noodles_vcf_record;
+
+/* Files with a .gz or .bgz extension are read as bgzip-compressed VCFs. */
+noodles_vcf_reader *noodles_vcf_reader_open(const char *src);
+
+void noodles_vcf_reader_free(noodles_vcf_reader *reader);
+
+const char *noodles_vcf_reader_header(const noodles_vcf_reader *reader);
+
+/* Returns 0 on success and -1 on error. The reader must be bgzip-compressed. */
+int noodles_vcf_reader_query(noodles_vcf_reader *reader,
+                             const char *index_src,
+                             const char *region);
+
+/* Returns 1 if a record was read, 0 at the end of the stream or query, and -1 on error. */
+int noodles_vcf_reader_next(noodles_vcf_reader *reader, const noodles_vcf_record **record);
+
+const char *noodles_vcf_record_chromosome(const noodles_vcf_record *record);
+
+/* Returns the 1-based position. */
+int32_t noodles_vcf_record_position(const noodles_vcf_record *record);
+
+const char *noodles_vcf_record_reference_bases(const noodles_vcf_record *record);
+
+const char *noodles_vcf_record_alternate_bases(const noodles_vcf_record *record);
+
+/* Returns the record as a VCF record line. */
+const char *noodles_vcf_record_to_string(const noodles_vcf_record *record);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,613 @@
+//! Alignment (BAM and CRAM) readers and records.
+
+use std::{
+    collections::VecDeque,
+    ffi::CString,
+    fs::File,
+    io::{self, BufReader},
+    os::raw::{c_char, c_int},
+    ptr,
+    rc::Rc,
+};
+
+use noodles_bam::{self as bam, bai};
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_cram::{self as cram, crai};
+use noodles_fasta as fasta;
+use noodles_sam::{self as sam, header::ReferenceSequences};
+
+use crate::{
+    as_ptr, catch_panic,
+    error::{to_c_string, to_str},
+    into_raw, next_status, status,
+};
+
+/// An alignment reader.
+///
+/// This is created by calling [`noodles_bam_reader_open`] or [`noodles_cram_reader_open`].
+pub struct AlignmentReader {
+    inner: Inner,
+    header: sam::Header,
+    raw_header: CString,
+    query_records: Option<VecDeque<sam::Record>>,
+    record: Option<AlignmentRecord>,
+}
+
+enum Inner {
+    Bam {
+        reader: Box<bam::Reader<bgzf::Reader<File>>>,
+        reference_sequences: ReferenceSequences,
+        record: Box<bam::Record>,
+    },
+    Cram {
+        reader: cram::Reader<File>,
+        reference_sequences: Rc<Vec<fasta::Record>>,
+        records: VecDeque<sam::Record>,
+    },
+}
+
+// A reference sequence repository adapter that shares the reference sequences of a CRAM reader.
+struct SharedReferenceSequences(Rc<Vec<fasta::Record>>);
+
+impl fasta::repository::Adapter for SharedReferenceSequences {
+    fn get(&mut self, name: &str) -> Option<io::Result<fasta::Record>> {
+        self.0
+            .iter()
+            .find(|record| record.name() == name)
+            .cloned()
+            .map(Ok)
+    }
+}
+
+impl AlignmentReader {
+    fn open_bam(src: &str) -> io::Result<Self> {
+        let mut reader = File::open(src).map(bam::Reader::new)?;
+
+        let raw_header = reader.read_header()?;
+        let header = parse_header(&raw_header)?;
+        let reference_sequences = reader.read_reference_sequences()?;
+
+        Ok(Self {
+            inner: Inner::Bam {
+                reader: Box::new(reader),
+                reference_sequences,
+                record: Box::default(),
+            },
+            header,
+            raw_header: to_c_string(raw_header)?,
+            query_records: None,
+            record: None,
+        })
+    }
+
+    fn open_cram(src: &str, reference_src: Option<&str>) -> io::Result<Self> {
+        let reference_sequences: Rc<Vec<_>> = match reference_src {
+            Some(reference_src) => {
+                let mut reader = File::open(reference_src)
+                    .map(BufReader::new)
+                    .map(fasta::Reader::new)?;
+
+                Rc::new(reader.records().collect::<io::Result<_>>()?)
+            }
+            None => Rc::default(),
+        };
+
+        // The repository resolves the bases of records read by queries.
+        let repository =
+            fasta::Repository::new(SharedReferenceSequences(Rc::clone(&reference_sequences)));
+
+        let mut reader = File::open(src).map(|file| {
+            cram::Reader::builder(file)
+                .set_reference_sequence_repository(repository)
+                .build()
+        })?;
+
+        reader.read_file_definition()?;
+
+        let raw_header = reader.read_file_header()?;
+        let header = parse_header(&raw_header)?;
+
+        Ok(Self {
+            inner: Inner::Cram {
+                reader,
+                reference_sequences,
+                records: VecDeque::new(),
+            },
+            header,
+            raw_header: to_c_string(raw_header)?,
+            query_records: None,
+            record: None,
+        })
+    }
+
+    fn query(&mut self, index_src: &str, region: &Region) -> io::Result<()> {
+        let records = match &mut self.inner {
+            Inner::Bam {
+                reader,
+                reference_sequences,
+                ..
+            } => {
+                let index = bai::read(index_src)?;
+
+                reader
+                    .query(reference_sequences, &index, region)?
+                    .map(|result| {
+                        result.and_then(|record| record.try_into_sam_record(reference_sequences))
+                    })
+                    .collect::<io::Result<_>>()?
+            }
+            Inner::Cram { reader, .. } => {
+                let index = crai::read(index_src)?;
+                let reference_sequences = self.header.reference_sequences();
+
+                reader
+                    .query(reference_sequences, &index, region)?
+                    .map(|result| {
+                        result.and_then(|record| {
+                            sam::Record::try_from_alignment_record(reference_sequences, &record)
+                        })
+                    })
+                    .collect::<io::Result<_>>()?
+            }
+        };
+
+        self.query_records = Some(records);
+
+        Ok(())
+    }
+
+    fn next(&mut self) -> io::Result<bool> {
+        let record = match &mut self.query_records {
+            Some(records) => records.pop_front(),
+            None => self.read_record()?,
+        };
+
+        self.record = record.map(AlignmentRecord::try_from).transpose()?;
+
+        Ok(self.record.is_some())
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<sam::Record>> {
+        match &mut self.inner {
+            Inner::Bam {
+                reader,
+                reference_sequences,
+                record,
+            } => match reader.read_record(record)? {
+                0 => Ok(None),
+                _ => record.try_into_sam_record(reference_sequences).map(Some),
+            },
+            Inner::Cram {
+                reader,
+                reference_sequences,
+                records,
+            } => loop {
+                if let Some(record) = records.pop_front() {
+                    return Ok(Some(record));
+                }
+
+                let container = match reader.read_data_container()? {
+                    Some(container) => container,
+                    None => return Ok(None),
+                };
+
+                let compression_header = container.compression_header();
+
+                for slice in container.slices() {
+                    let slice_records = slice.records(compression_header)?;
+
+                    for record in slice.resolve_mates(slice_records) {
+                        let sam_record = record.try_into_sam_record(
+                            reference_sequences,
+                            self.header.reference_sequences(),
+                            compression_header,
+                        )?;
+
+                        records.push_back(sam_record);
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// An alignment record.
+///
+/// This is created by calling [`noodles_alignment_reader_next`].
+pub struct AlignmentRecord {
+    record: sam::Record,
+    read_name: Option<CString>,
+    reference_sequence_name: Option<CString>,
+    cigar: CString,
+    raw_record: CString,
+}
+
+impl TryFrom<sam::Record> for AlignmentRecord {
+    type Error = io::Error;
+
+    fn try_from(record: sam::Record) -> Result<Self, Self::Error> {
+        let read_name = record
+            .read_name()
+            .map(|name| to_c_string(name.to_string()))
+            .transpose()?;
+
+        let reference_sequence_name = record
+            .reference_sequence_name()
+            .map(|name| to_c_string(name.to_string()))
+            .transpose()?;
+
+        let cigar = to_c_string(record.cigar().to_string())?;
+        let raw_record = to_c_string(record.to_string())?;
+
+        Ok(Self {
+            record,
+            read_name,
+            reference_sequence_name,
+            cigar,
+            raw_record,
+        })
+    }
+}
+
+/// Opens a BAM file and reads its header.
+///
+/// This returns `NULL` on error. The reader must be freed with [`noodles_alignment_reader_free`].
+///
+/// # Safety
+///
+/// `src` must be a valid pointer to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_bam_reader_open(src: *const c_char) -> *mut AlignmentReader {
+    catch_panic(ptr::null_mut(), || {
+        into_raw(to_str(src).and_then(AlignmentReader::open_bam))
+    })
+}
+
+/// Opens a CRAM file and reads its header.
+///
+/// `reference_src` is an optional path to a FASTA file of the reference sequences used to
+/// decode records. It may be `NULL`.
+///
+/// This returns `NULL` on error. The reader must be freed with [`noodles_alignment_reader_free`].
+///
+/// # Safety
+///
+/// `src` must be a valid pointer to a NUL-terminated string. `reference_src` must be `NULL` or a
+/// valid pointer to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_cram_reader_open(
+    src: *const c_char,
+    reference_src: *const c_char,
+) -> *mut AlignmentReader {
+    catch_panic(ptr::null_mut(), || {
+        let result = to_str(src).and_then(|src| {
+            let reference_src = if reference_src.is_null() {
+                None
+            } else {
+                Some(to_str(reference_src)?)
+            };
+
+            AlignmentReader::open_cram(src, reference_src)
+        });
+
+        into_raw(result)
+    })
+}
+
+/// Frees an alignment reader.
+///
+/// # Safety
+///
+/// `reader` must be `NULL` or a pointer returned by an open function that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_alignment_reader_free(reader: *mut AlignmentReader) {
+    catch_panic((), || {
+        if !reader.is_null() {
+            drop(Box::from_raw(reader));
+        }
+    })
+}
+
+/// Returns the raw SAM header of an alignment reader.
+///
+/// The returned string is owned by the reader.
+///
+/// # Safety
+///
+/// `reader` must be a valid alignment reader.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_alignment_reader_header(
+    reader: *const AlignmentReader,
+) -> *const c_char {
+    (*reader).raw_header.as_ptr()
+}
+
+/// Restricts subsequent reads to records that intersect the given region.
+///
+/// `index_src` is the path to the associated index, i.e., a BAM index (BAI) for BAM files or a
+/// CRAM index (CRAI) for CRAM files. `region` is a region string, e.g., `sq0:8-13`.
+///
+/// The records that intersect the region are read when the query is set. Subsequent calls to
+/// [`noodles_alignment_reader_next`] return these records.
+///
+/// This returns 0 on success and -1 on error.
+///
+/// # Safety
+///
+/// `reader` must be a valid alignment reader. `index_src` and `region` must be valid pointers to
+/// NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_alignment_reader_query(
+    reader: *mut AlignmentReader,
+    index_src: *const c_char,
+    region: *const c_char,
+) -> c_int {
+    catch_panic(-1, || {
+        let result = to_str(index_src).and_then(|index_src| {
+            let region = parse_region(to_str(region)?)?;
+            (*reader).query(index_src, &region)
+        });
+
+        status(result)
+    })
+}
+
+/// Reads the next record.
+///
+/// On success, `record` is set to the read record, which is owned by the reader and valid until
+/// the next read.
+///
+/// This returns 1 if a record was read, 0 at the end of the stream or query, and -1 on error.
+///
+/// # Safety
+///
+/// `reader` must be a valid alignment reader, and `record` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_alignment_reader_next(
+    reader: *mut AlignmentReader,
+    record: *mut *const AlignmentRecord,
+) -> c_int {
+    catch_panic(-1, || {
+        let reader = &mut *reader;
+        let result = reader.next();
+
+        *record = reader
+            .record
+            .as_ref()
+            .map(|r| r as *const _)
+            .unwrap_or(ptr::null());
+
+        next_status(result)
+    })
+}
+
+/// Returns the read name of an alignment record or `NULL` if it is missing.
+///
+/// # Safety
+///
+/// `record` must be a valid alignment record.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_alignment_record_read_name(
+    record: *const AlignmentRecord,
+) -> *const c_char {
+    as_ptr((*record).read_name.as_ref())
+}
+
+/// Returns the SAM flags of an alignment record.
+///
+/// # Safety
+///
+/// `record` must be a valid alignment record.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_alignment_record_flags(record: *const AlignmentRecord) -> u16 {
+    u16::from((*record).record.flags())
+}
+
+/// Returns the reference sequence name of an alignment record or `NULL` if it is missing.
+///
+/// # Safety
+///
+/// `record` must be a valid alignment record.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_alignment_record_reference_sequence_name(
+    record: *const AlignmentRecord,
+) -> *const c_char {
+    as_ptr((*record).reference_sequence_name.as_ref())
+}
+
+/// Returns the 1-based position of an alignment record or 0 if it is missing.
+///
+/// # Safety
+///
+/// `record` must be a valid alignment record.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_alignment_record_position(record: *const AlignmentRecord) -> i32 {
+    (*record).record.position().map(i32::from).unwrap_or(0)
+}
+
+/// Returns the mapping quality of an alignment record or 255 if it is missing.
+///
+/// # Safety
+///
+/// `record` must be a valid alignment record.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_alignment_record_mapping_quality(
+    record: *const AlignmentRecord,
+) -> u8 {
+    (*record)
+        .record
+        .mapping_quality()
+        .map(u8::from)
+        .unwrap_or(sam::record::mapping_quality::MISSING)
+}
+
+/// Returns the CIGAR string of an alignment record.
+///
+/// # Safety
+///
+/// `record` must be a valid alignment record.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_alignment_record_cigar(
+    record: *const AlignmentRecord,
+) -> *const c_char {
+    (*record).cigar.as_ptr()
+}
+
+/// Returns an alignment record as a SAM record line.
+///
+/// # Safety
+///
+/// `record` must be a valid alignment record.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_alignment_record_to_string(
+    record: *const AlignmentRecord,
+) -> *const c_char {
+    (*record).raw_record.as_ptr()
+}
+
+fn parse_header(s: &str) -> io::Result<sam::Header> {
+    s.parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub(crate) fn parse_region(s: &str) -> io::Result<Region> {
+    s.parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+pub(crate) fn invalid_reference_sequence_name(name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+            "region reference sequence does not exist in reference sequences: {}",
+            name
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, ffi::CStr, fs, path::Path, process};
+
+    use noodles_csi::index::reference_sequence::bin::Chunk;
+
+    use super::*;
+
+    fn write_bam(dst: &Path, index_dst: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(sam::header::ReferenceSequence::new("sq0".parse()?, 8192)?)
+            .build();
+
+        let mut writer = File::create(dst).map(bam::Writer::new)?;
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        for i in 0..1024 {
+            let sam_record: sam::Record =
+                format!("r{}\t0\tsq0\t{}\t60\t4M\t*\t0\t0\t*\t*", i, i * 5 + 1).parse()?;
+            let record =
+                bam::Record::try_from_sam_record(header.reference_sequences(), &sam_record)?;
+            writer.write_record(&record)?;
+        }
+
+        writer.try_finish()?;
+        drop(writer);
+
+        let mut reader = File::open(dst).map(bam::Reader::new)?;
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut builder = bai::Index::builder();
+        let mut record = bam::Record::default();
+        let mut start_position = reader.virtual_position();
+
+        while reader.read_record(&mut record)? != 0 {
+            let end_position = reader.virtual_position();
+            builder.add_record(&record, Chunk::new(start_position, end_position))?;
+            start_position = end_position;
+        }
+
+        bai::write(index_dst, &builder.build(1))?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bam_reader() -> Result<(), Box<dyn std::error::Error>> {
+        let src = env::temp_dir().join(format!("noodles-ffi-{}.bam", process::id()));
+        let index_src = src.with_extension("bam.bai");
+        write_bam(&src, &index_src)?;
+
+        let raw_src = CString::new(src.to_str().unwrap())?;
+        let raw_index_src = CString::new(index_src.to_str().unwrap())?;
+        let raw_region = CString::new("sq0:501-600")?;
+
+        unsafe {
+            let reader = noodles_bam_reader_open(raw_src.as_ptr());
+            assert!(!reader.is_null());
+
+            let header = CStr::from_ptr(noodles_alignment_reader_header(reader));
+            assert!(header.to_str()?.contains("@SQ\tSN:sq0\tLN:8192"));
+
+            let mut record = ptr::null();
+            let mut n = 0;
+
+            while noodles_alignment_reader_next(reader, &mut record) == 1 {
+                n += 1;
+            }
+
+            assert_eq!(n, 1024);
+            assert!(record.is_null());
+
+            noodles_alignment_reader_free(reader);
+
+            let reader = noodles_bam_reader_open(raw_src.as_ptr());
+
+            let status =
+                noodles_alignment_reader_query(reader, raw_index_src.as_ptr(), raw_region.as_ptr());
+            assert_eq!(status, 0);
+
+            assert_eq!(noodles_alignment_reader_next(reader, &mut record), 1);
+
+            let read_name = CStr::from_ptr(noodles_alignment_record_read_name(record));
+            assert_eq!(read_name.to_str()?, "r100");
+            assert_eq!(noodles_alignment_record_flags(record), 0);
+            let reference_sequence_name =
+                CStr::from_ptr(noodles_alignment_record_reference_sequence_name(record));
+            assert_eq!(reference_sequence_name.to_str()?, "sq0");
+            assert_eq!(noodles_alignment_record_position(record), 501);
+            assert_eq!(noodles_alignment_record_mapping_quality(record), 60);
+            let cigar = CStr::from_ptr(noodles_alignment_record_cigar(record));
+            assert_eq!(cigar.to_str()?, "4M");
+
+            let mut n = 1;
+
+            while noodles_alignment_reader_next(reader, &mut record) == 1 {
+                n += 1;
+            }
+
+            assert_eq!(n, 20);
+
+            noodles_alignment_reader_free(reader);
+        }
+
+        fs::remove_file(src)?;
+        fs::remove_file(index_src)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_noodles_bam_reader_open_with_missing_file() -> Result<(), Box<dyn std::error::Error>> {
+        let src = CString::new("noodles-ffi-missing.bam")?;
+
+        unsafe {
+            let reader = noodles_bam_reader_open(src.as_ptr());
+            assert!(reader.is_null());
+            assert!(!crate::error::noodles_last_error_message().is_null());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+//! Error reporting.
+
+use std::{
+    cell::RefCell,
+    ffi::{CStr, CString},
+    fmt, io,
+    os::raw::c_char,
+    ptr,
+};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Returns the message of the last error that occurred on the calling thread.
+///
+/// This returns `NULL` if no error has occurred. The returned string is owned by the library and
+/// is valid until the next call that fails on the same thread.
+#[no_mangle]
+pub extern "C" fn noodles_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map(|message| message.as_ptr())
+            .unwrap_or(ptr::null())
+    })
+}
+
+pub(crate) fn set_last_error<E>(e: E)
+where
+    E: fmt::Display,
+{
+    // Interior NUL bytes cannot be represented in a C string and are dropped.
+    let message = e.to_string().replace('\0', "");
+    let message = CString::new(message).expect("message contains no NUL bytes");
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+}
+
+// Converts a C string to a string slice.
+//
+// # Safety
+//
+// `s` must be `NULL` or a valid pointer to a NUL-terminated string that outlives `'a`.
+pub(crate) unsafe fn to_str<'a>(s: *const c_char) -> io::Result<&'a str> {
+    if s.is_null() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "unexpected null pointer",
+        ));
+    }
+
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+pub(crate) fn to_c_string<S>(s: S) -> io::Result<CString>
+where
+    S: Into<Vec<u8>>,
+{
+    CString::new(s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_last_error() {
+        set_last_error("noodles");
+        let message = unsafe { CStr::from_ptr(noodles_last_error_message()) };
+        assert_eq!(message.to_str(), Ok("noodles"));
+    }
+}
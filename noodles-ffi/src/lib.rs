@@ -0,0 +1,185 @@
+#![warn(missing_docs)]
+
+//! **noodles-ffi** exposes a C ABI for reading BAM, CRAM, and VCF files using noodles.
+//!
+//! The library builds as both a shared (`cdylib`) and static (`staticlib`) library. Declarations
+//! are in `include/noodles.h`.
+//!
+//! Readers are opaque handles created by an open function (e.g., [`alignment::noodles_bam_reader_open`]) and
+//! must be freed with the matching free function. Records are owned by the reader that read them
+//! and are valid until the next read.
+//!
+//! Functions that can fail return `NULL` or a negative status. The cause can be retrieved using
+//! [`error::noodles_last_error_message`]. Panics do not unwind across the C ABI; they are caught
+//! and reported as errors.
+//!
+//! # Examples
+//!
+//! ```c
+//! #include <stdio.h>
+//! #include "noodles.h"
+//!
+//! int main(void) {
+//!     noodles_alignment_reader *reader = noodles_bam_reader_open("sample.bam");
+//!
+//!     if (reader == NULL) {
+//!         fprintf(stderr, "%s\n", noodles_last_error_message());
+//!         return 1;
+//!     }
+//!
+//!     const noodles_alignment_record *record;
+//!     int status;
+//!
+//!     while ((status = noodles_alignment_reader_next(reader, &record)) == 1) {
+//!         printf("%s\n", noodles_alignment_record_to_string(record));
+//!     }
+//!
+//!     noodles_alignment_reader_free(reader);
+//!
+//!     return status == 0 ? 0 : 1;
+//! }
+//! ```
+
+pub mod alignment;
+pub mod error;
+pub mod variant;
+
+use std::{
+    any::Any,
+    ffi::CString,
+    io,
+    ops::Bound,
+    os::raw::{c_char, c_int},
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+use noodles_core::region::Mapped;
+
+use self::error::set_last_error;
+
+// A closed interval of 1-based positions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Interval {
+    start: i32,
+    end: i32,
+}
+
+impl Interval {
+    fn intersects(&self, start: i32, end: i32) -> bool {
+        start <= self.end && self.start <= end
+    }
+}
+
+impl From<&Mapped> for Interval {
+    fn from(region: &Mapped) -> Self {
+        let start = match region.start() {
+            Bound::Included(s) => s,
+            Bound::Excluded(s) => s + 1,
+            Bound::Unbounded => 1,
+        };
+
+        let end = match region.end() {
+            Bound::Included(e) => e,
+            Bound::Excluded(e) => e - 1,
+            Bound::Unbounded => i32::MAX,
+        };
+
+        Self { start, end }
+    }
+}
+
+// Calls `f`, catching a panic so that it does not unwind across the C ABI.
+//
+// On a panic, the last error is set to the panic message, and `on_panic` is returned, e.g.,
+// `NULL` or -1.
+fn catch_panic<F, T>(on_panic: T, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => value,
+        Err(payload) => {
+            set_last_error(format!("panic: {}", panic_message(payload.as_ref())));
+            on_panic
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown panic"
+    }
+}
+
+fn into_raw<T>(result: io::Result<T>) -> *mut T {
+    match result {
+        Ok(value) => Box::into_raw(Box::new(value)),
+        Err(e) => {
+            set_last_error(e);
+            ptr::null_mut()
+        }
+    }
+}
+
+fn status(result: io::Result<()>) -> c_int {
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            set_last_error(e);
+            -1
+        }
+    }
+}
+
+fn next_status(result: io::Result<bool>) -> c_int {
+    match result {
+        Ok(true) => 1,
+        Ok(false) => 0,
+        Err(e) => {
+            set_last_error(e);
+            -1
+        }
+    }
+}
+
+fn as_ptr(s: Option<&CString>) -> *const c_char {
+    s.map(|s| s.as_ptr()).unwrap_or(ptr::null())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_panic() {
+        use std::ffi::CStr;
+
+        use self::error::noodles_last_error_message;
+
+        assert_eq!(catch_panic(-1, || 0), 0);
+
+        assert_eq!(catch_panic(-1, || panic!("noodles")), -1);
+        let message = unsafe { CStr::from_ptr(noodles_last_error_message()) };
+        assert_eq!(message.to_str(), Ok("panic: noodles"));
+
+        let n = 8;
+        let reader: *mut u8 = catch_panic(ptr::null_mut(), || panic!("n = {}", n));
+        assert!(reader.is_null());
+        let message = unsafe { CStr::from_ptr(noodles_last_error_message()) };
+        assert_eq!(message.to_str(), Ok("panic: n = 8"));
+    }
+
+    #[test]
+    fn test_interval_intersects() {
+        let interval = Interval::from(&Mapped::new("sq0", 8..=13));
+        assert!(interval.intersects(5, 8));
+        assert!(interval.intersects(13, 21));
+        assert!(!interval.intersects(1, 7));
+        assert!(!interval.intersects(14, 21));
+    }
+}
@@ -0,0 +1,458 @@
+//! Variant (VCF) readers and records.
+
+use std::{
+    collections::VecDeque,
+    ffi::CString,
+    fs::File,
+    io::{self, BufReader},
+    os::raw::{c_char, c_int},
+    ptr,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi::{index::reference_sequence::bin::Chunk, BinningIndex};
+use noodles_tabix as tabix;
+use noodles_vcf as vcf;
+
+use crate::{
+    alignment::{invalid_reference_sequence_name, parse_region},
+    catch_panic,
+    error::{to_c_string, to_str},
+    into_raw, next_status, status, Interval,
+};
+
+/// A VCF reader.
+///
+/// This is created by calling [`noodles_vcf_reader_open`].
+pub struct VcfReader {
+    inner: Inner,
+    header: vcf::Header,
+    raw_header: CString,
+    buf: String,
+    query: Option<Query>,
+    record: Option<VcfRecord>,
+}
+
+enum Inner {
+    Raw(vcf::Reader<BufReader<File>>),
+    Bgzf {
+        reader: Box<vcf::Reader<bgzf::Reader<File>>>,
+        chunks: VecDeque<Chunk>,
+        chunk_end: Option<bgzf::VirtualPosition>,
+    },
+}
+
+// The reference sequence and interval of a query.
+struct Query {
+    reference_sequence_name: String,
+    interval: Interval,
+}
+
+impl Query {
+    fn intersects(&self, record: &vcf::Record) -> io::Result<bool> {
+        if record.chromosome().to_string() != self.reference_sequence_name {
+            return Ok(false);
+        }
+
+        let start = i32::from(record.position());
+
+        let end = record
+            .end()
+            .map(i32::from)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(self.interval.intersects(start, end))
+    }
+}
+
+impl VcfReader {
+    fn open(src: &str) -> io::Result<Self> {
+        let file = File::open(src)?;
+
+        let (inner, raw_header) = if is_bgzf_compressed(src) {
+            let mut reader = vcf::Reader::new(bgzf::Reader::new(file));
+            let raw_header = reader.read_header()?;
+
+            let inner = Inner::Bgzf {
+                reader: Box::new(reader),
+                chunks: VecDeque::new(),
+                chunk_end: None,
+            };
+
+            (inner, raw_header)
+        } else {
+            let mut reader = vcf::Reader::new(BufReader::new(file));
+            let raw_header = reader.read_header()?;
+            (Inner::Raw(reader), raw_header)
+        };
+
+        let header = raw_header
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(Self {
+            inner,
+            header,
+            raw_header: to_c_string(raw_header)?,
+            buf: String::new(),
+            query: None,
+            record: None,
+        })
+    }
+
+    fn query(&mut self, index_src: &str, region: &Region) -> io::Result<()> {
+        let (chunks, chunk_end) = match &mut self.inner {
+            Inner::Raw(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "querying requires a bgzip-compressed VCF",
+                ))
+            }
+            Inner::Bgzf {
+                chunks, chunk_end, ..
+            } => (chunks, chunk_end),
+        };
+
+        let region = region
+            .as_mapped()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "region is not mapped"))?;
+
+        let index = tabix::read(index_src)?;
+
+        let reference_sequence_id = index
+            .reference_sequence_names()
+            .get_index_of(region.name())
+            .ok_or_else(|| invalid_reference_sequence_name(region.name()))?;
+
+        let query_chunks = index.query(reference_sequence_id, region.interval())?;
+
+        *chunks = query_chunks.into_iter().collect();
+        *chunk_end = None;
+
+        self.query = Some(Query {
+            reference_sequence_name: region.name().into(),
+            interval: Interval::from(region),
+        });
+
+        Ok(())
+    }
+
+    fn next(&mut self) -> io::Result<bool> {
+        loop {
+            if !self.read_record()? {
+                self.record = None;
+                return Ok(false);
+            }
+
+            let record = vcf::Record::try_from_str(&self.buf, &self.header)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            if let Some(query) = &self.query {
+                if !query.intersects(&record)? {
+                    continue;
+                }
+            }
+
+            self.record = Some(VcfRecord::try_from(record)?);
+
+            return Ok(true);
+        }
+    }
+
+    fn read_record(&mut self) -> io::Result<bool> {
+        let is_query = self.query.is_some();
+
+        self.buf.clear();
+
+        match &mut self.inner {
+            Inner::Raw(reader) => reader.read_record(&mut self.buf).map(|n| n > 0),
+            Inner::Bgzf {
+                reader,
+                chunks,
+                chunk_end,
+            } => loop {
+                if is_query {
+                    match chunk_end {
+                        Some(end) if reader.virtual_position() < *end => {}
+                        _ => match chunks.pop_front() {
+                            Some(chunk) => {
+                                reader.seek(chunk.start())?;
+                                *chunk_end = Some(chunk.end());
+                                continue;
+                            }
+                            None => return Ok(false),
+                        },
+                    }
+                }
+
+                match reader.read_record(&mut self.buf)? {
+                    0 if is_query => *chunk_end = None,
+                    0 => return Ok(false),
+                    _ => return Ok(true),
+                }
+            },
+        }
+    }
+}
+
+/// A VCF record.
+///
+/// This is created by calling [`noodles_vcf_reader_next`].
+pub struct VcfRecord {
+    chromosome: CString,
+    position: i32,
+    reference_bases: CString,
+    alternate_bases: CString,
+    raw_record: CString,
+}
+
+impl TryFrom<vcf::Record> for VcfRecord {
+    type Error = io::Error;
+
+    fn try_from(record: vcf::Record) -> Result<Self, Self::Error> {
+        Ok(Self {
+            chromosome: to_c_string(record.chromosome().to_string())?,
+            position: i32::from(record.position()),
+            reference_bases: to_c_string(record.reference_bases().to_string())?,
+            alternate_bases: to_c_string(record.alternate_bases().to_string())?,
+            raw_record: to_c_string(record.to_string())?,
+        })
+    }
+}
+
+/// Opens a VCF file and reads its header.
+///
+/// Files with a `.gz` or `.bgz` extension are read as bgzip-compressed VCFs, which can be queried
+/// using a tabix index.
+///
+/// This returns `NULL` on error. The reader must be freed with [`noodles_vcf_reader_free`].
+///
+/// # Safety
+///
+/// `src` must be a valid pointer to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_vcf_reader_open(src: *const c_char) -> *mut VcfReader {
+    catch_panic(ptr::null_mut(), || {
+        into_raw(to_str(src).and_then(VcfReader::open))
+    })
+}
+
+/// Frees a VCF reader.
+///
+/// # Safety
+///
+/// `reader` must be `NULL` or a pointer returned by [`noodles_vcf_reader_open`] that has not been
+/// freed.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_vcf_reader_free(reader: *mut VcfReader) {
+    catch_panic((), || {
+        if !reader.is_null() {
+            drop(Box::from_raw(reader));
+        }
+    })
+}
+
+/// Returns the raw header of a VCF reader.
+///
+/// The returned string is owned by the reader.
+///
+/// # Safety
+///
+/// `reader` must be a valid VCF reader.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_vcf_reader_header(reader: *const VcfReader) -> *const c_char {
+    (*reader).raw_header.as_ptr()
+}
+
+/// Restricts subsequent reads to records that intersect the given region.
+///
+/// `index_src` is the path to the associated tabix index. `region` is a region string, e.g.,
+/// `sq0:8-13`.
+///
+/// This returns 0 on success and -1 on error.
+///
+/// # Safety
+///
+/// `reader` must be a valid VCF reader. `index_src` and `region` must be valid pointers to
+/// NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_vcf_reader_query(
+    reader: *mut VcfReader,
+    index_src: *const c_char,
+    region: *const c_char,
+) -> c_int {
+    catch_panic(-1, || {
+        let result = to_str(index_src).and_then(|index_src| {
+            let region = parse_region(to_str(region)?)?;
+            (*reader).query(index_src, &region)
+        });
+
+        status(result)
+    })
+}
+
+/// Reads the next record.
+///
+/// On success, `record` is set to the read record, which is owned by the reader and valid until
+/// the next read.
+///
+/// This returns 1 if a record was read, 0 at the end of the stream or query, and -1 on error.
+///
+/// # Safety
+///
+/// `reader` must be a valid VCF reader, and `record` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_vcf_reader_next(
+    reader: *mut VcfReader,
+    record: *mut *const VcfRecord,
+) -> c_int {
+    catch_panic(-1, || {
+        let reader = &mut *reader;
+        let result = reader.next();
+
+        *record = reader
+            .record
+            .as_ref()
+            .map(|r| r as *const _)
+            .unwrap_or(ptr::null());
+
+        next_status(result)
+    })
+}
+
+/// Returns the chromosome of a VCF record.
+///
+/// # Safety
+///
+/// `record` must be a valid VCF record.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_vcf_record_chromosome(record: *const VcfRecord) -> *const c_char {
+    (*record).chromosome.as_ptr()
+}
+
+/// Returns the 1-based position of a VCF record.
+///
+/// # Safety
+///
+/// `record` must be a valid VCF record.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_vcf_record_position(record: *const VcfRecord) -> i32 {
+    (*record).position
+}
+
+/// Returns the reference bases of a VCF record.
+///
+/// # Safety
+///
+/// `record` must be a valid VCF record.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_vcf_record_reference_bases(
+    record: *const VcfRecord,
+) -> *const c_char {
+    (*record).reference_bases.as_ptr()
+}
+
+/// Returns the alternate bases of a VCF record.
+///
+/// # Safety
+///
+/// `record` must be a valid VCF record.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_vcf_record_alternate_bases(
+    record: *const VcfRecord,
+) -> *const c_char {
+    (*record).alternate_bases.as_ptr()
+}
+
+/// Returns a VCF record as a VCF record line.
+///
+/// # Safety
+///
+/// `record` must be a valid VCF record.
+#[no_mangle]
+pub unsafe extern "C" fn noodles_vcf_record_to_string(record: *const VcfRecord) -> *const c_char {
+    (*record).raw_record.as_ptr()
+}
+
+fn is_bgzf_compressed(src: &str) -> bool {
+    src.ends_with(".gz") || src.ends_with(".bgz")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, ffi::CStr, fs, io::Write, process};
+
+    use tabix::index::header;
+
+    use super::*;
+
+    #[test]
+    fn test_vcf_reader() -> Result<(), Box<dyn std::error::Error>> {
+        let src = env::temp_dir().join(format!("noodles-ffi-{}.vcf.gz", process::id()));
+        let index_src = src.with_extension("gz.tbi");
+
+        let mut writer = File::create(&src).map(bgzf::Writer::new)?;
+        writer.write_all(b"##fileformat=VCFv4.3\n##contig=<ID=sq0>\n")?;
+        writer.write_all(b"#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n")?;
+
+        for i in 1..=100 {
+            writeln!(writer, "sq0\t{}\t.\tA\tC\t.\tPASS\t.", i * 10)?;
+        }
+
+        writer.finish()?;
+
+        let layout = tabix::Layout::new(header::Builder::vcf().build());
+        let index = tabix::index(&src, &layout)?;
+        tabix::write(&index_src, &index)?;
+
+        let raw_src = CString::new(src.to_str().unwrap())?;
+        let raw_index_src = CString::new(index_src.to_str().unwrap())?;
+        let raw_region = CString::new("sq0:95-130")?;
+
+        unsafe {
+            let reader = noodles_vcf_reader_open(raw_src.as_ptr());
+            assert!(!reader.is_null());
+
+            let header = CStr::from_ptr(noodles_vcf_reader_header(reader));
+            assert!(header.to_str()?.starts_with("##fileformat=VCFv4.3\n"));
+
+            let mut record = ptr::null();
+            let mut n = 0;
+
+            while noodles_vcf_reader_next(reader, &mut record) == 1 {
+                n += 1;
+            }
+
+            assert_eq!(n, 100);
+
+            let status =
+                noodles_vcf_reader_query(reader, raw_index_src.as_ptr(), raw_region.as_ptr());
+            assert_eq!(status, 0);
+
+            let mut positions = Vec::new();
+
+            while noodles_vcf_reader_next(reader, &mut record) == 1 {
+                let chromosome = CStr::from_ptr(noodles_vcf_record_chromosome(record));
+                assert_eq!(chromosome.to_str()?, "sq0");
+
+                let reference_bases = CStr::from_ptr(noodles_vcf_record_reference_bases(record));
+                assert_eq!(reference_bases.to_str()?, "A");
+
+                let alternate_bases = CStr::from_ptr(noodles_vcf_record_alternate_bases(record));
+                assert_eq!(alternate_bases.to_str()?, "C");
+
+                positions.push(noodles_vcf_record_position(record));
+            }
+
+            assert_eq!(positions, [100, 110, 120, 130]);
+
+            noodles_vcf_reader_free(reader);
+        }
+
+        fs::remove_file(src)?;
+        fs::remove_file(index_src)?;
+
+        Ok(())
+    }
+}
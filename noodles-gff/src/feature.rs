@@ -0,0 +1,315 @@
+//! GFF feature hierarchy.
+
+use std::{collections::HashMap, error, fmt};
+
+use super::Record;
+
+const ID: &str = "ID";
+const PARENT: &str = "Parent";
+
+/// A GFF feature.
+///
+/// A feature is a group of one or more records that share the same `ID` attribute, along with the
+/// features that reference it as a parent (`Parent`).
+///
+/// Records that share an `ID` represent a single feature spanning multiple lines, e.g., a CDS
+/// that is split across exons.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Feature {
+    id: Option<String>,
+    records: Vec<Record>,
+    children: Vec<Feature>,
+}
+
+impl Feature {
+    /// Returns the ID of the feature.
+    ///
+    /// This is the value of the `ID` attribute. Records without an ID are each their own feature.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Returns the records that make up the feature.
+    ///
+    /// This is guaranteed to be nonempty.
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// Returns the child features.
+    pub fn children(&self) -> &[Feature] {
+        &self.children
+    }
+
+    /// Returns the start position of the feature.
+    ///
+    /// This is the minimum start position of all the records in the feature.
+    pub fn start(&self) -> i32 {
+        self.records
+            .iter()
+            .map(|r| r.start())
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// Returns the end position of the feature.
+    ///
+    /// This is the maximum end position of all the records in the feature.
+    pub fn end(&self) -> i32 {
+        self.records
+            .iter()
+            .map(|r| r.end())
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// An error returned when features fail to resolve.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ResolveError {
+    /// A parent ID does not reference a feature.
+    MissingParent(String),
+    /// The feature references form a cycle.
+    Cycle(String),
+}
+
+impl error::Error for ResolveError {}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingParent(id) => write!(f, "missing parent: {}", id),
+            Self::Cycle(id) => write!(f, "cycle detected at feature: {}", id),
+        }
+    }
+}
+
+struct Node {
+    id: Option<String>,
+    records: Vec<Record>,
+    parent_ids: Vec<String>,
+}
+
+/// Groups records into feature trees using the `ID` and `Parent` attributes.
+///
+/// Records that share an `ID` are merged into a single feature. A feature with multiple parents
+/// is included as a child of each parent. The returned list contains the top-level features,
+/// i.e., features with no parents, in the order they first appear.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_gff::{self as gff, feature};
+///
+/// let records = [
+///     "sq0\tNOODLES\tgene\t8\t21\t.\t+\t.\tID=gene0",
+///     "sq0\tNOODLES\tmRNA\t8\t21\t.\t+\t.\tID=tx0;Parent=gene0",
+///     "sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0;Parent=tx0",
+///     "sq0\tNOODLES\tCDS\t17\t21\t.\t+\t0\tID=cds0;Parent=tx0",
+/// ]
+/// .iter()
+/// .map(|s| s.parse())
+/// .collect::<Result<Vec<gff::Record>, _>>()?;
+///
+/// let features = feature::resolve(records)?;
+/// assert_eq!(features.len(), 1);
+///
+/// let gene = &features[0];
+/// assert_eq!(gene.id(), Some("gene0"));
+///
+/// let transcript = &gene.children()[0];
+/// assert_eq!(transcript.id(), Some("tx0"));
+///
+/// let cds = &transcript.children()[0];
+/// assert_eq!(cds.records().len(), 2);
+/// assert_eq!((cds.start(), cds.end()), (8, 21));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn resolve<I>(records: I) -> Result<Vec<Feature>, ResolveError>
+where
+    I: IntoIterator<Item = Record>,
+{
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut indices: HashMap<String, usize> = HashMap::new();
+
+    for record in records {
+        let id = record
+            .attributes()
+            .get(ID)
+            .map(|entry| entry.value().iter().collect::<Vec<_>>().join(","));
+
+        let parent_ids: Vec<String> = record
+            .attributes()
+            .get(PARENT)
+            .map(|entry| entry.value().iter().map(String::from).collect())
+            .unwrap_or_default();
+
+        let node = match id.as_ref().and_then(|id| indices.get(id)) {
+            Some(&i) => &mut nodes[i],
+            None => {
+                if let Some(id) = id.clone() {
+                    indices.insert(id, nodes.len());
+                }
+
+                nodes.push(Node {
+                    id,
+                    records: Vec::new(),
+                    parent_ids: Vec::new(),
+                });
+
+                nodes.last_mut().unwrap()
+            }
+        };
+
+        for parent_id in parent_ids {
+            if !node.parent_ids.contains(&parent_id) {
+                node.parent_ids.push(parent_id);
+            }
+        }
+
+        node.records.push(record);
+    }
+
+    let mut children = vec![Vec::new(); nodes.len()];
+    let mut roots = Vec::new();
+
+    for (i, node) in nodes.iter().enumerate() {
+        if node.parent_ids.is_empty() {
+            roots.push(i);
+        }
+
+        for parent_id in &node.parent_ids {
+            let j = indices
+                .get(parent_id)
+                .copied()
+                .ok_or_else(|| ResolveError::MissingParent(parent_id.clone()))?;
+
+            children[j].push(i);
+        }
+    }
+
+    let mut visited = vec![false; nodes.len()];
+    let mut is_on_path = vec![false; nodes.len()];
+
+    let features = roots
+        .into_iter()
+        .map(|i| build_feature(&nodes, &children, i, &mut visited, &mut is_on_path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Any nodes not reachable from a top-level feature must be part of a cycle.
+    if let Some(i) = visited.iter().position(|&v| !v) {
+        return Err(ResolveError::Cycle(node_name(&nodes[i])));
+    }
+
+    Ok(features)
+}
+
+fn build_feature(
+    nodes: &[Node],
+    children: &[Vec<usize>],
+    i: usize,
+    visited: &mut [bool],
+    is_on_path: &mut [bool],
+) -> Result<Feature, ResolveError> {
+    if is_on_path[i] {
+        return Err(ResolveError::Cycle(node_name(&nodes[i])));
+    }
+
+    visited[i] = true;
+    is_on_path[i] = true;
+
+    let child_features = children[i]
+        .iter()
+        .map(|&j| build_feature(nodes, children, j, visited, is_on_path))
+        .collect::<Result<_, _>>()?;
+
+    is_on_path[i] = false;
+
+    let node = &nodes[i];
+
+    Ok(Feature {
+        id: node.id.clone(),
+        records: node.records.clone(),
+        children: child_features,
+    })
+}
+
+fn node_name(node: &Node) -> String {
+    node.id.clone().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_records(lines: &[&str]) -> Vec<Record> {
+        lines
+            .iter()
+            .map(|s| s.parse())
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_resolve() -> Result<(), ResolveError> {
+        let records = parse_records(&[
+            "sq0\tNOODLES\tgene\t8\t34\t.\t+\t.\tID=gene0",
+            "sq0\tNOODLES\tmRNA\t8\t34\t.\t+\t.\tID=tx0;Parent=gene0",
+            "sq0\tNOODLES\tmRNA\t8\t21\t.\t+\t.\tID=tx1;Parent=gene0",
+            "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=tx0,tx1",
+            "sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0;Parent=tx0",
+            "sq0\tNOODLES\tCDS\t21\t34\t.\t+\t0\tID=cds0;Parent=tx0",
+            "sq1\tNOODLES\tgene\t1\t5\t.\t-\t.\tID=gene1",
+        ]);
+
+        let features = resolve(records)?;
+
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[0].id(), Some("gene0"));
+        assert_eq!(features[1].id(), Some("gene1"));
+
+        let transcripts = features[0].children();
+        assert_eq!(transcripts.len(), 2);
+
+        let tx0 = &transcripts[0];
+        assert_eq!(tx0.id(), Some("tx0"));
+        assert_eq!(tx0.children().len(), 2);
+        assert!(tx0.children()[0].id().is_none());
+
+        let cds0 = &tx0.children()[1];
+        assert_eq!(cds0.id(), Some("cds0"));
+        assert_eq!(cds0.records().len(), 2);
+        assert_eq!(cds0.start(), 8);
+        assert_eq!(cds0.end(), 34);
+
+        let tx1 = &transcripts[1];
+        assert_eq!(tx1.children().len(), 1);
+        assert_eq!(tx1.children()[0], tx0.children()[0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_with_missing_parent() {
+        let records = parse_records(&["sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx0;Parent=gene0"]);
+
+        assert_eq!(
+            resolve(records),
+            Err(ResolveError::MissingParent(String::from("gene0")))
+        );
+    }
+
+    #[test]
+    fn test_resolve_with_cycle() {
+        let records = parse_records(&[
+            "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0",
+            "sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx0;Parent=tx1",
+            "sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=tx1;Parent=tx0",
+        ]);
+
+        assert_eq!(
+            resolve(records),
+            Err(ResolveError::Cycle(String::from("tx0")))
+        );
+    }
+}
@@ -32,9 +32,13 @@
 //! ```
 
 pub mod directive;
+pub mod feature;
 pub mod line;
 pub mod reader;
 pub mod record;
 mod writer;
 
-pub use self::{directive::Directive, line::Line, reader::Reader, record::Record, writer::Writer};
+pub use self::{
+    directive::Directive, feature::Feature, line::Line, reader::Reader, record::Record,
+    writer::Writer,
+};
@@ -7,6 +7,8 @@ pub use self::{lines::Lines, records::Records};
 
 use std::io::{self, BufRead};
 
+use noodles_fasta as fasta;
+
 const LINE_FEED: char = '\n';
 const CARRIAGE_RETURN: char = '\r';
 
@@ -157,6 +159,44 @@ where
     pub fn records(&mut self) -> Records<'_, R> {
         Records::new(self.lines())
     }
+
+    /// Returns a FASTA reader over the embedded sequences.
+    ///
+    /// A GFF3 file can include reference sequences in a trailing FASTA section, which starts
+    /// after the `FASTA` directive. The stream must be positioned after this directive, e.g.,
+    /// after the records iterator (see [`Self::records`]) is exhausted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_gff as gff;
+    ///
+    /// let data = b"##gff-version 3
+    /// sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id=ndls0;gene_name=gene0
+    /// \x23#FASTA
+    /// >sq0
+    /// ACGT
+    /// ";
+    /// let mut reader = gff::Reader::new(&data[..]);
+    ///
+    /// for result in reader.records() {
+    ///     let _record = result?;
+    /// }
+    ///
+    /// let mut fasta_reader = reader.fasta_reader();
+    /// let mut records = fasta_reader.records();
+    ///
+    /// let record = records.next().transpose()?.expect("missing FASTA record");
+    /// assert_eq!(record.name(), "sq0");
+    /// assert_eq!(record.sequence().as_ref(), b"ACGT");
+    ///
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn fasta_reader(&mut self) -> fasta::Reader<&mut R> {
+        fasta::Reader::new(&mut self.inner)
+    }
 }
 
 fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
@@ -224,6 +264,12 @@ ACGT
 
         assert_eq!(n, 1);
 
+        let fasta_records: Vec<_> = reader.fasta_reader().records().collect::<io::Result<_>>()?;
+
+        assert_eq!(fasta_records.len(), 1);
+        assert_eq!(fasta_records[0].name(), "sq0");
+        assert_eq!(fasta_records[0].sequence().as_ref(), b"ACGT");
+
         Ok(())
     }
 
@@ -12,6 +12,24 @@ const DELIMITER: char = ';';
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Attributes(Vec<Entry>);
 
+impl Attributes {
+    /// Returns the first entry with the given key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::{attributes::Entry, Attributes};
+    ///
+    /// let attributes = Attributes::from(vec![Entry::new("gene_id", "ndls0")]);
+    ///
+    /// assert_eq!(attributes.get("gene_id"), Some(&Entry::new("gene_id", "ndls0")));
+    /// assert!(attributes.get("gene_name").is_none());
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&Entry> {
+        self.0.iter().find(|entry| entry.key() == key)
+    }
+}
+
 impl Deref for Attributes {
     type Target = [Entry];
 
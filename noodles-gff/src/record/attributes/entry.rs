@@ -1,5 +1,9 @@
 //! GFF record attribute entry.
 
+pub mod value;
+
+pub use self::value::Value;
+
 use std::{
     borrow::Cow,
     error, fmt,
@@ -24,7 +28,7 @@ const SEPARATOR: char = '=';
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Entry {
     key: String,
-    value: String,
+    value: Value,
 }
 
 impl Entry {
@@ -34,12 +38,14 @@ impl Entry {
     ///
     /// ```
     /// use noodles_gff::record::attributes::Entry;
+    ///
     /// let entry = Entry::new("gene_name", "gene0");
+    /// let entry = Entry::new("Parent", vec![String::from("tx0"), String::from("tx1")]);
     /// ```
     pub fn new<K, V>(key: K, value: V) -> Self
     where
         K: Into<String>,
-        V: Into<String>,
+        V: Into<Value>,
     {
         Self {
             key: key.into(),
@@ -65,11 +71,11 @@ impl Entry {
     /// # Examples
     ///
     /// ```
-    /// use noodles_gff::record::attributes::Entry;
+    /// use noodles_gff::record::attributes::{entry::Value, Entry};
     /// let entry = Entry::new("gene_name", "gene0");
-    /// assert_eq!(entry.value(), "gene0");
+    /// assert_eq!(entry.value(), &Value::from("gene0"));
     /// ```
-    pub fn value(&self) -> &str {
+    pub fn value(&self) -> &Value {
         &self.value
     }
 }
@@ -81,7 +87,7 @@ impl fmt::Display for Entry {
             "{}{}{}",
             percent_encode(self.key()),
             SEPARATOR,
-            percent_encode(self.value())
+            self.value()
         )
     }
 }
@@ -145,19 +151,18 @@ fn parse_key(s: &str) -> Result<Cow<'_, str>, ParseError> {
     }
 }
 
-fn parse_value(s: &str) -> Result<Cow<'_, str>, ParseError> {
-    if s.is_empty() {
-        Err(ParseError::MissingValue)
-    } else {
-        percent_decode(s).map_err(ParseError::InvalidValue)
-    }
+fn parse_value(s: &str) -> Result<Value, ParseError> {
+    s.parse().map_err(|e| match e {
+        value::ParseError::Empty => ParseError::MissingValue,
+        value::ParseError::Invalid(e) => ParseError::InvalidValue(e),
+    })
 }
 
-fn percent_decode(s: &str) -> Result<Cow<'_, str>, str::Utf8Error> {
+pub(crate) fn percent_decode(s: &str) -> Result<Cow<'_, str>, str::Utf8Error> {
     percent_decode_str(s).decode_utf8()
 }
 
-fn percent_encode(s: &str) -> Cow<'_, str> {
+pub(crate) fn percent_encode(s: &str) -> Cow<'_, str> {
     utf8_percent_encode(s, PERCENT_ENCODE_SET).into()
 }
 
@@ -172,6 +177,9 @@ mod tests {
 
         let entry = Entry::new("%s", "13,21");
         assert_eq!(entry.to_string(), "%25s=13%2C21");
+
+        let entry = Entry::new("Parent", vec![String::from("tx0"), String::from("tx1")]);
+        assert_eq!(entry.to_string(), "Parent=tx0,tx1");
     }
 
     #[test]
@@ -181,6 +189,13 @@ mod tests {
             Ok(Entry::new("gene_name", "gene0"))
         );
         assert_eq!("%25s=13%2C21".parse(), Ok(Entry::new("%s", "13,21")));
+        assert_eq!(
+            "Parent=tx0,tx1".parse(),
+            Ok(Entry::new(
+                "Parent",
+                vec![String::from("tx0"), String::from("tx1")]
+            ))
+        );
 
         assert_eq!("".parse::<Entry>(), Err(ParseError::Empty));
         assert_eq!("gene_name".parse::<Entry>(), Err(ParseError::Invalid));
@@ -0,0 +1,200 @@
+//! GFF record attribute entry value.
+
+use std::{
+    error, fmt,
+    str::{self, FromStr},
+};
+
+use super::{percent_decode, percent_encode};
+
+const DELIMITER: char = ',';
+
+/// A GFF record attribute entry value.
+///
+/// An attribute can have multiple values, which are delimited by a comma (`,`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Value {
+    /// A single value.
+    String(String),
+    /// A list of values.
+    Array(Vec<String>),
+}
+
+impl Value {
+    /// Returns the value as a single string, if it is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::attributes::entry::Value;
+    ///
+    /// let value = Value::from("gene0");
+    /// assert_eq!(value.as_string(), Some("gene0"));
+    ///
+    /// let value = Value::from(vec![String::from("gene0"), String::from("gene1")]);
+    /// assert!(value.as_string().is_none());
+    /// ```
+    pub fn as_string(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            Self::Array(_) => None,
+        }
+    }
+
+    /// Returns the value as a list of strings, if it is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::attributes::entry::Value;
+    ///
+    /// let value = Value::from(vec![String::from("gene0"), String::from("gene1")]);
+    /// assert_eq!(
+    ///     value.as_array(),
+    ///     Some(&[String::from("gene0"), String::from("gene1")][..])
+    /// );
+    ///
+    /// let value = Value::from("gene0");
+    /// assert!(value.as_array().is_none());
+    /// ```
+    pub fn as_array(&self) -> Option<&[String]> {
+        match self {
+            Self::String(_) => None,
+            Self::Array(values) => Some(values),
+        }
+    }
+
+    /// Returns an iterator over all values.
+    ///
+    /// A single value is treated as a list with one value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::record::attributes::entry::Value;
+    ///
+    /// let value = Value::from("gene0");
+    /// assert_eq!(value.iter().collect::<Vec<_>>(), ["gene0"]);
+    ///
+    /// let value = Value::from(vec![String::from("gene0"), String::from("gene1")]);
+    /// assert_eq!(value.iter().collect::<Vec<_>>(), ["gene0", "gene1"]);
+    /// ```
+    pub fn iter(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        match self {
+            Self::String(s) => Box::new(std::iter::once(s.as_str())),
+            Self::Array(values) => Box::new(values.iter().map(|s| s.as_str())),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(s) => write!(f, "{}", percent_encode(s)),
+            Self::Array(values) => {
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "{}", DELIMITER)?;
+                    }
+
+                    write!(f, "{}", percent_encode(value))?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Self::String(s.into())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl From<Vec<String>> for Value {
+    fn from(values: Vec<String>) -> Self {
+        Self::Array(values)
+    }
+}
+
+/// An error returned when a raw GFF record attribute entry value fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid(str::Utf8Error),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid(e) => write!(f, "invalid input: {}", e),
+        }
+    }
+}
+
+impl FromStr for Value {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        if s.contains(DELIMITER) {
+            s.split(DELIMITER)
+                .map(|t| percent_decode(t).map(|u| u.into_owned()))
+                .collect::<Result<_, _>>()
+                .map(Self::Array)
+                .map_err(ParseError::Invalid)
+        } else {
+            percent_decode(s)
+                .map(|t| Self::String(t.into_owned()))
+                .map_err(ParseError::Invalid)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Value::from("gene0").to_string(), "gene0");
+        assert_eq!(Value::from("13,21").to_string(), "13%2C21");
+
+        let value = Value::from(vec![String::from("gene0"), String::from("13,21")]);
+        assert_eq!(value.to_string(), "gene0,13%2C21");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("gene0".parse(), Ok(Value::from("gene0")));
+        assert_eq!("13%2C21".parse(), Ok(Value::from("13,21")));
+        assert_eq!(
+            "gene0,13%2C21".parse(),
+            Ok(Value::from(vec![
+                String::from("gene0"),
+                String::from("13,21")
+            ]))
+        );
+
+        assert_eq!("".parse::<Value>(), Err(ParseError::Empty));
+        assert!(matches!(
+            "%ff".parse::<Value>(),
+            Err(ParseError::Invalid(_))
+        ));
+    }
+}
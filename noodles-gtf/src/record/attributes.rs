@@ -13,10 +13,106 @@ use std::{
 
 const DELIMITER: char = ' ';
 
+const GENE_ID: &str = "gene_id";
+const TRANSCRIPT_ID: &str = "transcript_id";
+
 /// GTF record attributes.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Attributes(Vec<Entry>);
 
+impl Attributes {
+    /// Returns the value of the first entry with the given key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gtf::record::{attributes::Entry, Attributes};
+    ///
+    /// let attributes = Attributes::from(vec![Entry::new("gene_id", "g0")]);
+    ///
+    /// assert_eq!(attributes.get("gene_id"), Some("g0"));
+    /// assert!(attributes.get("gene_name").is_none());
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|entry| entry.key() == key)
+            .map(|entry| entry.value())
+    }
+
+    /// Returns the gene ID (`gene_id`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gtf::record::{attributes::Entry, Attributes};
+    /// let attributes = Attributes::from(vec![Entry::new("gene_id", "g0")]);
+    /// assert_eq!(attributes.gene_id(), Some("g0"));
+    /// ```
+    pub fn gene_id(&self) -> Option<&str> {
+        self.get(GENE_ID)
+    }
+
+    /// Returns the transcript ID (`transcript_id`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gtf::record::{attributes::Entry, Attributes};
+    ///
+    /// let attributes = Attributes::from(vec![
+    ///     Entry::new("gene_id", "g0"),
+    ///     Entry::new("transcript_id", "t0"),
+    /// ]);
+    ///
+    /// assert_eq!(attributes.transcript_id(), Some("t0"));
+    /// ```
+    pub fn transcript_id(&self) -> Option<&str> {
+        self.get(TRANSCRIPT_ID)
+    }
+
+    /// Validates the attributes against the GTF 2.2 requirements.
+    ///
+    /// GTF 2.2 requires every record to have a gene ID (`gene_id`) and a transcript ID
+    /// (`transcript_id`), listed as the first and second attributes, respectively.
+    ///
+    /// Parsing does not enforce this, as many annotation sources omit `transcript_id` on gene
+    /// records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gtf::record::{
+    ///     attributes::{Entry, ValidationError},
+    ///     Attributes,
+    /// };
+    ///
+    /// let attributes = Attributes::from(vec![
+    ///     Entry::new("gene_id", "g0"),
+    ///     Entry::new("transcript_id", "t0"),
+    /// ]);
+    /// assert!(attributes.validate().is_ok());
+    ///
+    /// let attributes = Attributes::from(vec![Entry::new("gene_id", "g0")]);
+    /// assert_eq!(attributes.validate(), Err(ValidationError::MissingTranscriptId));
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut keys = self.0.iter().map(|entry| entry.key());
+
+        match keys.next() {
+            Some(GENE_ID) => {}
+            Some(_) if self.gene_id().is_some() => return Err(ValidationError::InvalidOrder),
+            _ => return Err(ValidationError::MissingGeneId),
+        }
+
+        match keys.next() {
+            Some(TRANSCRIPT_ID) => Ok(()),
+            Some(_) if self.transcript_id().is_some() => Err(ValidationError::InvalidOrder),
+            _ => Err(ValidationError::MissingTranscriptId),
+        }
+    }
+}
+
 impl Deref for Attributes {
     type Target = [Entry];
 
@@ -68,6 +164,33 @@ impl fmt::Display for ParseError {
     }
 }
 
+/// An error returned when GTF record attributes fail validation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationError {
+    /// The gene ID (`gene_id`) is missing.
+    MissingGeneId,
+    /// The transcript ID (`transcript_id`) is missing.
+    MissingTranscriptId,
+    /// The gene ID and transcript ID are not the first two attributes.
+    InvalidOrder,
+}
+
+impl error::Error for ValidationError {}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingGeneId => write!(f, "missing {}", GENE_ID),
+            Self::MissingTranscriptId => write!(f, "missing {}", TRANSCRIPT_ID),
+            Self::InvalidOrder => write!(
+                f,
+                "{} and {} must be the first two attributes",
+                GENE_ID, TRANSCRIPT_ID
+            ),
+        }
+    }
+}
+
 impl FromStr for Attributes {
     type Err = ParseError;
 
@@ -162,4 +285,35 @@ mod tests {
             Err(ParseError::InvalidEntry(_))
         ));
     }
+
+    #[test]
+    fn test_validate() {
+        let attributes = Attributes::from(vec![
+            Entry::new("gene_id", "g0"),
+            Entry::new("transcript_id", "t0"),
+            Entry::new("gene_name", "n0"),
+        ]);
+        assert_eq!(attributes.validate(), Ok(()));
+
+        let attributes = Attributes::default();
+        assert_eq!(attributes.validate(), Err(ValidationError::MissingGeneId));
+
+        let attributes = Attributes::from(vec![Entry::new("transcript_id", "t0")]);
+        assert_eq!(attributes.validate(), Err(ValidationError::MissingGeneId));
+
+        let attributes = Attributes::from(vec![
+            Entry::new("gene_id", "g0"),
+            Entry::new("gene_name", "n0"),
+        ]);
+        assert_eq!(
+            attributes.validate(),
+            Err(ValidationError::MissingTranscriptId)
+        );
+
+        let attributes = Attributes::from(vec![
+            Entry::new("transcript_id", "t0"),
+            Entry::new("gene_id", "g0"),
+        ]);
+        assert_eq!(attributes.validate(), Err(ValidationError::InvalidOrder));
+    }
 }
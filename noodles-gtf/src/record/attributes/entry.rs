@@ -32,7 +32,7 @@ impl Entry {
         }
     }
 
-    /// Creates a GTF record attribute entry.
+    /// Returns the key of the entry.
     ///
     /// # Examples
     ///
@@ -45,7 +45,7 @@ impl Entry {
         &self.key
     }
 
-    /// Creates a GTF record attribute entry.
+    /// Returns the value of the entry.
     ///
     /// # Examples
     ///
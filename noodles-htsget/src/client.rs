@@ -0,0 +1,123 @@
+//! htsget client and request builder.
+
+mod request;
+
+pub use self::request::Request;
+
+use crate::{Reader, Ticket, Transport};
+
+/// An htsget client.
+pub struct Client<T> {
+    base_url: String,
+    transport: T,
+}
+
+impl<T> Client<T>
+where
+    T: Transport,
+{
+    /// Creates an htsget client.
+    ///
+    /// The base URL is the URL of the htsget service without the endpoint path, e.g.,
+    /// `https://localhost/htsget`. A trailing slash is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_htsget as htsget;
+    ///
+    /// # struct HttpTransport;
+    /// # impl htsget::Transport for HttpTransport {
+    /// #     fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+    /// #         unimplemented!()
+    /// #     }
+    /// # }
+    /// let client = htsget::Client::new("https://localhost/htsget/", HttpTransport);
+    /// assert_eq!(client.base_url(), "https://localhost/htsget");
+    /// ```
+    pub fn new<S>(base_url: S, transport: T) -> Self
+    where
+        S: Into<String>,
+    {
+        let mut base_url = base_url.into();
+
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+
+        Self {
+            base_url,
+            transport,
+        }
+    }
+
+    /// Returns the base URL of the htsget service.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the underlying transport.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Creates a request for alignment data (BAM or CRAM) of the given dataset ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_htsget as htsget;
+    ///
+    /// # struct HttpTransport;
+    /// # impl htsget::Transport for HttpTransport {
+    /// #     fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+    /// #         unimplemented!()
+    /// #     }
+    /// # }
+    /// let client = htsget::Client::new("https://localhost/htsget", HttpTransport);
+    /// let request = client.reads("NA12878");
+    /// assert_eq!(request.url(), "https://localhost/htsget/reads/NA12878");
+    /// ```
+    pub fn reads<I>(&self, id: I) -> Request<'_, T>
+    where
+        I: Into<String>,
+    {
+        Request::new(self, request::Endpoint::Reads, id.into())
+    }
+
+    /// Creates a request for variant data (VCF or BCF) of the given dataset ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_htsget as htsget;
+    ///
+    /// # struct HttpTransport;
+    /// # impl htsget::Transport for HttpTransport {
+    /// #     fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+    /// #         unimplemented!()
+    /// #     }
+    /// # }
+    /// let client = htsget::Client::new("https://localhost/htsget", HttpTransport);
+    /// let request = client.variants("NA12878");
+    /// assert_eq!(request.url(), "https://localhost/htsget/variants/NA12878");
+    /// ```
+    pub fn variants<I>(&self, id: I) -> Request<'_, T>
+    where
+        I: Into<String>,
+    {
+        Request::new(self, request::Endpoint::Variants, id.into())
+    }
+
+    /// Returns a reader over the reassembled data of the given ticket.
+    ///
+    /// Data blocks are fetched lazily, in order, as the reader is consumed. The resulting stream
+    /// is the complete file, e.g., a BGZF-compressed BAM, and can be wrapped in the format's
+    /// reader.
+    pub fn blocks<'a>(&'a self, ticket: &'a Ticket) -> Reader<'a, T> {
+        Reader::new(&self.transport, ticket.urls())
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    io,
+    ops::{Bound, RangeBounds},
+};
+
+use noodles_core::Region;
+
+use super::Client;
+use crate::{
+    ticket::{Class, Format},
+    Ticket, Transport,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Endpoint {
+    Reads,
+    Variants,
+}
+
+impl AsRef<str> for Endpoint {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Reads => "reads",
+            Self::Variants => "variants",
+        }
+    }
+}
+
+/// An htsget request.
+pub struct Request<'c, T> {
+    client: &'c Client<T>,
+    endpoint: Endpoint,
+    id: String,
+    format: Option<Format>,
+    class: Option<Class>,
+    region: Option<Region>,
+}
+
+impl<'c, T> Request<'c, T>
+where
+    T: Transport,
+{
+    pub(super) fn new(client: &'c Client<T>, endpoint: Endpoint, id: String) -> Self {
+        Self {
+            client,
+            endpoint,
+            id,
+            format: None,
+            class: None,
+            region: None,
+        }
+    }
+
+    /// Sets the requested data format.
+    ///
+    /// By default, the service uses BAM for reads and VCF for variants.
+    pub fn set_format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Sets the requested data class.
+    ///
+    /// Use [`Class::Header`] to only request the file header.
+    pub fn set_class(mut self, class: Class) -> Self {
+        self.class = Some(class);
+        self
+    }
+
+    /// Sets the requested genomic region.
+    ///
+    /// A mapped region is translated to the 0-based, half-open `start` and `end` parameters. An
+    /// unmapped region requests unplaced unmapped reads, and [`Region::All`] does not restrict the
+    /// request.
+    pub fn set_region(mut self, region: Region) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Returns the request URL, including the query parameters.
+    pub fn url(&self) -> String {
+        let mut url = format!(
+            "{}/{}/{}",
+            self.client.base_url(),
+            self.endpoint.as_ref(),
+            percent_encode(&self.id)
+        );
+
+        let mut params = Vec::new();
+
+        if let Some(format) = self.format {
+            params.push(("format", format.to_string()));
+        }
+
+        if let Some(class) = self.class {
+            params.push(("class", class.to_string()));
+        }
+
+        match &self.region {
+            Some(Region::Mapped(mapped)) => {
+                params.push(("referenceName", mapped.name().into()));
+
+                let (start, end) = resolve_interval(mapped.interval());
+
+                if let Some(start) = start {
+                    params.push(("start", start.to_string()));
+                }
+
+                if let Some(end) = end {
+                    params.push(("end", end.to_string()));
+                }
+            }
+            Some(Region::Unmapped) => params.push(("referenceName", "*".into())),
+            Some(Region::All) | None => {}
+        }
+
+        for (i, (key, value)) in params.iter().enumerate() {
+            url.push(if i == 0 { '?' } else { '&' });
+            url.push_str(key);
+            url.push('=');
+            url.push_str(&percent_encode(value));
+        }
+
+        url
+    }
+
+    /// Sends the request and returns the ticket.
+    pub fn send(self) -> io::Result<Ticket> {
+        let body = self.client.transport().get(&self.url(), &[])?;
+        let s =
+            String::from_utf8(body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        s.parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+// Converts a 1-based, closed interval to 0-based, half-open positions.
+fn resolve_interval<B>(interval: B) -> (Option<i64>, Option<i64>)
+where
+    B: RangeBounds<i32>,
+{
+    let start = match interval.start_bound() {
+        Bound::Included(s) => Some(i64::from(*s) - 1),
+        Bound::Excluded(s) => Some(i64::from(*s)),
+        Bound::Unbounded => None,
+    };
+
+    let end = match interval.end_bound() {
+        Bound::Included(e) => Some(i64::from(*e)),
+        Bound::Excluded(e) => Some(i64::from(*e) - 1),
+        Bound::Unbounded => None,
+    };
+
+    (start, end)
+}
+
+fn percent_encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(b));
+        } else {
+            encoded.push_str(&format!("%{:02X}", b));
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoopTransport;
+
+    impl Transport for NoopTransport {
+        fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+            Err(io::Error::from(io::ErrorKind::Unsupported))
+        }
+    }
+
+    #[test]
+    fn test_url() {
+        let client = Client::new("https://localhost/htsget", NoopTransport);
+
+        assert_eq!(
+            client.reads("NA12878").url(),
+            "https://localhost/htsget/reads/NA12878"
+        );
+
+        assert_eq!(
+            client
+                .reads("NA12878")
+                .set_format(Format::Cram)
+                .set_class(Class::Header)
+                .url(),
+            "https://localhost/htsget/reads/NA12878?format=CRAM&class=header"
+        );
+
+        assert_eq!(
+            client
+                .variants("sample 1")
+                .set_region(Region::mapped("chr1", 8..=13))
+                .url(),
+            "https://localhost/htsget/variants/sample%201?referenceName=chr1&start=7&end=13"
+        );
+
+        assert_eq!(
+            client
+                .reads("NA12878")
+                .set_region(Region::mapped("HLA-A*01:01", 5..))
+                .url(),
+            "https://localhost/htsget/reads/NA12878?referenceName=HLA-A%2A01%3A01&start=4"
+        );
+
+        assert_eq!(
+            client.reads("NA12878").set_region(Region::Unmapped).url(),
+            "https://localhost/htsget/reads/NA12878?referenceName=%2A"
+        );
+    }
+
+    #[test]
+    fn test_send() {
+        struct TicketTransport;
+
+        impl Transport for TicketTransport {
+            fn get(&self, url: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+                assert_eq!(url, "https://localhost/htsget/reads/NA12878");
+                Ok(br#"{"htsget":{"format":"BAM","urls":[]}}"#.to_vec())
+            }
+        }
+
+        let client = Client::new("https://localhost/htsget", TicketTransport);
+        let ticket = client.reads("NA12878").send().unwrap();
+        assert_eq!(ticket, Ticket::new(Format::Bam, Vec::new(), None));
+
+        let client = Client::new("https://localhost/htsget", NoopTransport);
+        assert!(client.reads("NA12878").send().is_err());
+    }
+}
@@ -0,0 +1,56 @@
+#![warn(missing_docs)]
+
+//! **noodles-htsget** is an [htsget] client.
+//!
+//! htsget is a protocol to retrieve alignment (BAM, CRAM) and variant (VCF, BCF) data over HTTP.
+//! A client first requests a ticket for a dataset and region from the reads or variants endpoint.
+//! The ticket lists the URLs of the data blocks, which are then fetched and concatenated to
+//! reassemble a valid file stream.
+//!
+//! HTTP requests are made using a user-provided [`Transport`]. Data blocks given as `data` URIs
+//! are decoded directly.
+//!
+//! With the `server` feature, the `server` module provides building blocks for an htsget server,
+//! i.e., computing the data blocks of a region of an indexed BAM or CRAM file and building the
+//! ticket.
+//!
+//! [htsget]: https://samtools.github.io/hts-specs/htsget.html
+//!
+//! # Examples
+//!
+//! ## Read the records of a BAM region
+//!
+//! ```no_run
+//! # use std::io;
+//! use noodles_core::Region;
+//! use noodles_htsget as htsget;
+//!
+//! # struct HttpTransport;
+//! # impl htsget::Transport for HttpTransport {
+//! #     fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+//! #         unimplemented!()
+//! #     }
+//! # }
+//! let client = htsget::Client::new("https://localhost/htsget", HttpTransport);
+//!
+//! let region = Region::mapped("sq0", 8..=13);
+//! let ticket = client.reads("NA12878").set_region(region).send()?;
+//!
+//! let mut reader = client.blocks(&ticket);
+//! // e.g., `bam::Reader::new(reader)`
+//! # Ok::<_, io::Error>(())
+//! ```
+
+pub mod client;
+#[cfg(feature = "range")]
+mod range_reader;
+mod reader;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod ticket;
+pub mod transport;
+
+pub use self::{client::Client, reader::Reader, ticket::Ticket, transport::Transport};
+
+#[cfg(feature = "range")]
+pub use self::range_reader::RangeReader;
@@ -0,0 +1,535 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+use crate::Transport;
+
+const DEFAULT_BLOCK_SIZE: usize = 1 << 20;
+
+const OK: u16 = 200;
+const PARTIAL_CONTENT: u16 = 206;
+const RANGE_NOT_SATISFIABLE: u16 = 416;
+
+/// A reader over a remote resource using HTTP range requests.
+///
+/// This implements [`Read`] and [`Seek`], allowing indexed queries, e.g., `bam::Reader::query`,
+/// to be made directly against remote files. Reads are buffered in blocks to limit the number of
+/// requests.
+///
+/// Presigned URLs, e.g., for S3-compatible object storage, are used as is. Additional request
+/// headers, e.g., for authorization, can be added with [`Self::add_header`].
+///
+/// The transport must implement [`Transport::get_response`]. A partial content (206) response
+/// must have a `Content-Range` that matches the requested range. A full (200) response is taken
+/// as the entire resource.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io::{self, Read, Seek, SeekFrom};
+/// use noodles_htsget::{self as htsget, RangeReader};
+///
+/// # struct HttpTransport;
+/// # impl htsget::Transport for HttpTransport {
+/// #     fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+/// #         unimplemented!()
+/// #     }
+/// #     fn get_response(&self, _: &str, _: &[(String, String)]) -> io::Result<htsget::transport::Response> {
+/// #         unimplemented!()
+/// #     }
+/// # }
+/// let mut reader = RangeReader::new(HttpTransport, "https://localhost/sample.bam")
+///     .add_header("Authorization", "Bearer noodles");
+///
+/// reader.seek(SeekFrom::Start(8))?;
+///
+/// let mut buf = [0; 4];
+/// reader.read_exact(&mut buf)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct RangeReader<T> {
+    transport: T,
+    url: String,
+    headers: Vec<(String, String)>,
+    len: Option<u64>,
+    block_size: usize,
+    position: u64,
+    buf: Vec<u8>,
+    buf_start: u64,
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<T> RangeReader<T>
+where
+    T: Transport,
+{
+    /// Creates a range reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_htsget::{self as htsget, RangeReader};
+    ///
+    /// # struct HttpTransport;
+    /// # impl htsget::Transport for HttpTransport {
+    /// #     fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+    /// #         unimplemented!()
+    /// #     }
+    /// # }
+    /// let reader = RangeReader::new(HttpTransport, "https://localhost/sample.bam");
+    /// assert_eq!(reader.url(), "https://localhost/sample.bam");
+    /// ```
+    pub fn new<S>(transport: T, url: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            transport,
+            url: url.into(),
+            headers: Vec::new(),
+            len: None,
+            block_size: DEFAULT_BLOCK_SIZE,
+            position: 0,
+            buf: Vec::new(),
+            buf_start: 0,
+        }
+    }
+
+    /// Adds a header to send with each request.
+    pub fn add_header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sets the length of the resource.
+    ///
+    /// The length is required to seek relative to the end of the resource. If the server does
+    /// not support range requests, it is set from the full response.
+    pub fn set_len(mut self, len: u64) -> Self {
+        self.len = Some(len);
+        self
+    }
+
+    /// Sets the number of bytes to request at a time.
+    ///
+    /// The default is 1 MiB.
+    pub fn set_block_size(mut self, block_size: usize) -> Self {
+        self.block_size = block_size.max(1);
+        self
+    }
+
+    /// Returns the URL of the resource.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the length of the resource, if known.
+    pub fn len(&self) -> Option<u64> {
+        self.len
+    }
+
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf_end = self.buf_start + self.buf.len() as u64;
+
+        if self.position < self.buf_start || self.position >= buf_end {
+            if let Some(len) = self.len {
+                if self.position >= len {
+                    return Ok(&[]);
+                }
+            }
+
+            let start = self.position;
+            let mut end = start + self.block_size as u64 - 1;
+
+            if let Some(len) = self.len {
+                end = end.min(len - 1);
+            }
+
+            let mut headers = self.headers.clone();
+            headers.push((String::from("Range"), format!("bytes={}-{}", start, end)));
+
+            let response = self.transport.get_response(&self.url, &headers)?;
+
+            match response.status() {
+                PARTIAL_CONTENT => {
+                    let content_range = response
+                        .header("Content-Range")
+                        .ok_or_else(|| {
+                            io::Error::new(io::ErrorKind::InvalidData, "missing Content-Range")
+                        })
+                        .and_then(parse_content_range)?;
+
+                    let body = response.into_body();
+
+                    if content_range.start != start
+                        || content_range.end > end
+                        || content_range.end - content_range.start + 1 != body.len() as u64
+                    {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "Content-Range does not match the requested range",
+                        ));
+                    }
+
+                    if let Some(len) = content_range.len {
+                        self.len = Some(len);
+                    }
+
+                    self.buf_start = start;
+                    self.buf = body;
+                }
+                OK => {
+                    // The server ignored the range and returned the entire resource.
+                    let body = response.into_body();
+                    self.len = Some(body.len() as u64);
+                    self.buf_start = 0;
+                    self.buf = body;
+                }
+                RANGE_NOT_SATISFIABLE => {
+                    if let Some(len) = response
+                        .header("Content-Range")
+                        .and_then(parse_unsatisfied_range)
+                    {
+                        self.len = Some(len);
+                    }
+
+                    return Ok(&[]);
+                }
+                status => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("unexpected response status: {}", status),
+                    ));
+                }
+            }
+        }
+
+        let buf_end = self.buf_start + self.buf.len() as u64;
+
+        if self.position >= buf_end {
+            return Ok(&[]);
+        }
+
+        let i = (self.position - self.buf_start) as usize;
+        Ok(&self.buf[i..])
+    }
+}
+
+impl<T> Read for RangeReader<T>
+where
+    T: Transport,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let src = self.fill_buf()?;
+        let n = src.len().min(buf.len());
+        buf[..n].copy_from_slice(&src[..n]);
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl<T> Seek for RangeReader<T>
+where
+    T: Transport,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(n) => offset(self.position, n),
+            SeekFrom::End(n) => {
+                let len = self.len.ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "cannot seek from end: unknown length",
+                    )
+                })?;
+
+                offset(len, n)
+            }
+        };
+
+        self.position = position
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid seek position"))?;
+
+        Ok(self.position)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct ContentRange {
+    start: u64,
+    end: u64,
+    len: Option<u64>,
+}
+
+// Parses a `Content-Range` value of the form `bytes <start>-<end>/<len>`, where `<len>` may be
+// `*`.
+fn parse_content_range(s: &str) -> io::Result<ContentRange> {
+    fn invalid_content_range() -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, "invalid Content-Range")
+    }
+
+    let (range, len) = s
+        .trim()
+        .strip_prefix("bytes ")
+        .and_then(|t| t.split_once('/'))
+        .ok_or_else(invalid_content_range)?;
+
+    let (start, end) = range.split_once('-').ok_or_else(invalid_content_range)?;
+    let start: u64 = start.parse().map_err(|_| invalid_content_range())?;
+    let end: u64 = end.parse().map_err(|_| invalid_content_range())?;
+
+    if end < start {
+        return Err(invalid_content_range());
+    }
+
+    let len = match len {
+        "*" => None,
+        t => {
+            let len: u64 = t.parse().map_err(|_| invalid_content_range())?;
+
+            if end >= len {
+                return Err(invalid_content_range());
+            }
+
+            Some(len)
+        }
+    };
+
+    Ok(ContentRange { start, end, len })
+}
+
+// Parses the length from a `Content-Range` value of the form `bytes */<len>`.
+fn parse_unsatisfied_range(s: &str) -> Option<u64> {
+    s.trim()
+        .strip_prefix("bytes */")
+        .and_then(|t| t.parse().ok())
+}
+
+fn offset(position: u64, n: i64) -> Option<u64> {
+    if n >= 0 {
+        position.checked_add(n as u64)
+    } else {
+        position.checked_sub(n.unsigned_abs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::transport::Response;
+
+    struct RangeTransport {
+        data: Vec<u8>,
+        requests: Cell<usize>,
+    }
+
+    impl RangeTransport {
+        fn new(data: &[u8]) -> Self {
+            Self {
+                data: data.to_vec(),
+                requests: Cell::new(0),
+            }
+        }
+    }
+
+    impl Transport for RangeTransport {
+        fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+            unimplemented!()
+        }
+
+        fn get_response(&self, _: &str, headers: &[(String, String)]) -> io::Result<Response> {
+            self.requests.set(self.requests.get() + 1);
+
+            let (_, value) = headers.iter().find(|(k, _)| k == "Range").unwrap();
+            let (start, end) = value
+                .strip_prefix("bytes=")
+                .unwrap()
+                .split_once('-')
+                .unwrap();
+            let start: usize = start.parse().unwrap();
+            let end: usize = end.parse().unwrap();
+
+            let len = self.data.len();
+
+            if start >= len {
+                let content_range = format!("bytes */{}", len);
+
+                return Ok(Response::new(
+                    RANGE_NOT_SATISFIABLE,
+                    vec![(String::from("Content-Range"), content_range)],
+                    Vec::new(),
+                ));
+            }
+
+            let end = end.min(len - 1);
+            let content_range = format!("bytes {}-{}/{}", start, end, len);
+
+            Ok(Response::new(
+                PARTIAL_CONTENT,
+                vec![(String::from("Content-Range"), content_range)],
+                self.data[start..=end].to_vec(),
+            ))
+        }
+    }
+
+    #[test]
+    fn test_read() -> io::Result<()> {
+        let transport = RangeTransport::new(b"noodles-htsget");
+        let mut reader = RangeReader::new(&transport, "https://localhost/data").set_block_size(4);
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+
+        assert_eq!(buf, b"noodles-htsget");
+        assert_eq!(transport.requests.get(), 4);
+        assert_eq!(reader.len(), Some(14));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek() -> io::Result<()> {
+        let transport = RangeTransport::new(b"noodles-htsget");
+        let mut reader = RangeReader::new(&transport, "https://localhost/data").set_block_size(4);
+
+        assert!(reader.seek(SeekFrom::End(-3)).is_err());
+
+        let mut buf = [0; 3];
+
+        reader.seek(SeekFrom::Start(8))?;
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"hts");
+
+        reader.seek(SeekFrom::Current(-11))?;
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"noo");
+
+        assert!(reader.seek(SeekFrom::Current(-8)).is_err());
+
+        // The length is set from the `Content-Range` of the first response.
+        assert_eq!(reader.len(), Some(14));
+        reader.seek(SeekFrom::End(-3))?;
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"get");
+
+        assert_eq!(reader.read(&mut buf)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_full_response() -> io::Result<()> {
+        struct FullTransport;
+
+        impl Transport for FullTransport {
+            fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+                unimplemented!()
+            }
+
+            fn get_response(&self, _: &str, _: &[(String, String)]) -> io::Result<Response> {
+                Ok(Response::new(OK, Vec::new(), b"noodles-htsget".to_vec()))
+            }
+        }
+
+        let mut reader =
+            RangeReader::new(FullTransport, "https://localhost/data").set_block_size(4);
+        reader.seek(SeekFrom::Start(8))?;
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+
+        assert_eq!(buf, b"htsget");
+        assert_eq!(reader.len(), Some(14));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_mismatched_content_range() {
+        struct ShiftedTransport;
+
+        impl Transport for ShiftedTransport {
+            fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+                unimplemented!()
+            }
+
+            fn get_response(&self, _: &str, _: &[(String, String)]) -> io::Result<Response> {
+                Ok(Response::new(
+                    PARTIAL_CONTENT,
+                    vec![(String::from("Content-Range"), String::from("bytes 0-3/14"))],
+                    b"nood".to_vec(),
+                ))
+            }
+        }
+
+        let mut reader =
+            RangeReader::new(ShiftedTransport, "https://localhost/data").set_block_size(4);
+        reader.seek(SeekFrom::Start(8)).unwrap();
+
+        let mut buf = [0; 4];
+        assert!(matches!(
+            reader.read(&mut buf),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_read_with_unsupported_transport() {
+        struct BodyTransport;
+
+        impl Transport for BodyTransport {
+            fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+                Ok(b"nood".to_vec())
+            }
+        }
+
+        let mut reader = RangeReader::new(BodyTransport, "https://localhost/data");
+
+        let mut buf = [0; 4];
+        assert!(matches!(
+            reader.read(&mut buf),
+            Err(e) if e.kind() == io::ErrorKind::Unsupported
+        ));
+    }
+
+    #[test]
+    fn test_parse_content_range() -> io::Result<()> {
+        assert_eq!(
+            parse_content_range("bytes 8-13/14")?,
+            ContentRange {
+                start: 8,
+                end: 13,
+                len: Some(14)
+            }
+        );
+
+        assert_eq!(
+            parse_content_range("bytes 8-13/*")?,
+            ContentRange {
+                start: 8,
+                end: 13,
+                len: None
+            }
+        );
+
+        assert!(parse_content_range("").is_err());
+        assert!(parse_content_range("bytes 8-13").is_err());
+        assert!(parse_content_range("bytes 13-8/14").is_err());
+        assert!(parse_content_range("bytes 8-14/14").is_err());
+        assert!(parse_content_range("items 8-13/14").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_unsatisfied_range() {
+        assert_eq!(parse_unsatisfied_range("bytes */14"), Some(14));
+        assert!(parse_unsatisfied_range("bytes 8-13/14").is_none());
+    }
+}
@@ -0,0 +1,126 @@
+pub(crate) mod data_uri;
+
+use std::{
+    io::{self, Read},
+    slice,
+};
+
+use crate::{ticket::Url, Transport};
+
+const DATA_SCHEME: &str = "data:";
+const HTTP_SCHEMES: [&str; 2] = ["https://", "http://"];
+
+/// An htsget data block reader.
+///
+/// This fetches the data blocks of a ticket in order and reads them as one contiguous stream.
+/// It is created using [`crate::Client::blocks`].
+pub struct Reader<'a, T> {
+    transport: &'a T,
+    urls: slice::Iter<'a, Url>,
+    buf: Vec<u8>,
+    position: usize,
+}
+
+impl<'a, T> Reader<'a, T>
+where
+    T: Transport,
+{
+    pub(crate) fn new(transport: &'a T, urls: &'a [Url]) -> Self {
+        Self {
+            transport,
+            urls: urls.iter(),
+            buf: Vec::new(),
+            position: 0,
+        }
+    }
+
+    fn fetch(&self, url: &Url) -> io::Result<Vec<u8>> {
+        let s = url.url();
+
+        if let Some(data) = s.strip_prefix(DATA_SCHEME) {
+            data_uri::decode(data)
+        } else if HTTP_SCHEMES.iter().any(|scheme| s.starts_with(scheme)) {
+            self.transport.get(s, url.headers())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported URL scheme: {}", s),
+            ))
+        }
+    }
+}
+
+impl<'a, T> Read for Reader<'a, T>
+where
+    T: Transport,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position >= self.buf.len() {
+            match self.urls.next() {
+                Some(url) => {
+                    self.buf = self.fetch(url)?;
+                    self.position = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+
+        let src = &self.buf[self.position..];
+        let n = src.len().min(buf.len());
+        buf[..n].copy_from_slice(&src[..n]);
+        self.position += n;
+
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct BlockTransport;
+
+    impl Transport for BlockTransport {
+        fn get(&self, url: &str, headers: &[(String, String)]) -> io::Result<Vec<u8>> {
+            match (url, headers) {
+                ("https://localhost/sample.bam", [(key, value)]) if key == "Range" => {
+                    Ok(value.as_bytes().to_vec())
+                }
+                _ => Err(io::Error::from(io::ErrorKind::NotFound)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_read() -> io::Result<()> {
+        let mut url = Url::new("https://localhost/sample.bam");
+        url.headers = vec![(String::from("Range"), String::from("bytes=4-7"))];
+
+        let urls = [
+            Url::new("data:application/vnd.ga4gh.bam;base64,bm9vZGxlcw=="),
+            Url::new("data:,"),
+            url,
+            Url::new("data:,%20htsget"),
+        ];
+
+        let mut reader = Reader::new(&BlockTransport, &urls);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+
+        assert_eq!(buf, b"noodlesbytes=4-7 htsget");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_unsupported_scheme() {
+        let urls = [Url::new("ftp://localhost/sample.bam")];
+        let mut reader = Reader::new(&BlockTransport, &urls);
+        let mut buf = Vec::new();
+
+        assert!(matches!(
+            reader.read_to_end(&mut buf),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
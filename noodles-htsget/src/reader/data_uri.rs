@@ -0,0 +1,115 @@
+//! `data` URI encoding and decoding (RFC 2397).
+
+use std::io;
+
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
+
+const BASE64_SUFFIX: &str = ";base64";
+
+// Data URIs are decoded leniently: padding is optional.
+const LENIENT_BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+// Decodes the data of a `data` URI, i.e., the URI without the `data:` scheme.
+pub(super) fn decode(s: &str) -> io::Result<Vec<u8>> {
+    let (media_type, data) = s
+        .split_once(',')
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid data URI"))?;
+
+    let data = percent_decode(data)?;
+
+    if media_type.ends_with(BASE64_SUFFIX) {
+        base64_decode(&data)
+    } else {
+        Ok(data)
+    }
+}
+
+// Encodes data as a base64 `data` URI with the given media type.
+#[cfg(feature = "server")]
+pub(crate) fn encode(media_type: &str, data: &[u8]) -> String {
+    let mut s = format!("data:{}{},", media_type, BASE64_SUFFIX);
+    base64::engine::general_purpose::STANDARD.encode_string(data, &mut s);
+    s
+}
+
+fn percent_decode(s: &str) -> io::Result<Vec<u8>> {
+    // `percent_encoding::percent_decode` passes through invalid escapes, which are rejected here.
+    let bytes = s.as_bytes();
+
+    for (i, _) in bytes.iter().enumerate().filter(|(_, &b)| b == b'%') {
+        let is_valid = bytes
+            .get(i + 1..i + 3)
+            .map(|digits| digits.iter().all(u8::is_ascii_hexdigit))
+            .unwrap_or(false);
+
+        if !is_valid {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid percent-encoding",
+            ));
+        }
+    }
+
+    Ok(percent_encoding::percent_decode(bytes).collect())
+}
+
+fn base64_decode(src: &[u8]) -> io::Result<Vec<u8>> {
+    let src: Vec<u8> = src
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+
+    LENIENT_BASE64
+        .decode(src)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() -> io::Result<()> {
+        assert_eq!(decode(",")?, b"");
+        assert_eq!(decode(",noodles%0A")?, b"noodles\n");
+        assert_eq!(decode("text/plain,noodles")?, b"noodles");
+        assert_eq!(decode(";base64,bm9vZGxlcw==")?, b"noodles");
+        assert_eq!(decode(";base64,bm9vZA")?, b"nood");
+        assert_eq!(
+            decode("application/vnd.ga4gh.bam;base64,H4sIBAAAAAAA/wYAQkMCABsAAwAAAAAAAAAAAA==")?,
+            [
+                0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43,
+                0x02, 0x00, 0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            ]
+        );
+
+        assert!(decode("noodles").is_err());
+        assert!(decode(",%0").is_err());
+        assert!(decode(";base64,bm9v!").is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn test_encode() -> io::Result<()> {
+        assert_eq!(encode("", b""), "data:;base64,");
+        assert_eq!(encode("", b"nood"), "data:;base64,bm9vZA==");
+        assert_eq!(encode("", b"noodles"), "data:;base64,bm9vZGxlcw==");
+        assert_eq!(encode("text/plain", b"noo"), "data:text/plain;base64,bm9v");
+
+        let data = [0x00, 0xff, 0x7f, 0x80, 0x1f];
+        let uri = encode("application/vnd.ga4gh.bam", &data);
+        assert_eq!(decode(uri.trim_start_matches("data:"))?, data);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+//! htsget server building blocks.
+//!
+//! An htsget server answers a reads or variants request with a ticket that lists the data blocks
+//! a client concatenates to reassemble a valid stream for the requested region. This module
+//! computes those data blocks using the associated index ([`bam`] with a BAI or CSI index,
+//! [`cram`] with a CRAM index) and builds the ticket ([`ticket`]).
+//!
+//! Data blocks are byte ranges of the file where possible. Because BAM records can span BGZF
+//! blocks, a BGZF block that is only partially part of the response is re-encoded and returned
+//! inline. The data blocks always include the header and EOF blocks of the file. Body blocks may
+//! include records outside the requested region, which the client is expected to filter.
+
+pub mod bam;
+mod byte_range;
+pub mod cram;
+
+pub use self::byte_range::ByteRange;
+
+use crate::{
+    reader::data_uri,
+    ticket::{Class, Format, Url},
+    Ticket,
+};
+
+/// A data block of an htsget response.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Block {
+    /// A byte range of the file.
+    Range(ByteRange),
+    /// Data that is returned inline, e.g., a re-encoded partial BGZF block.
+    Data(Class, Vec<u8>),
+}
+
+impl Block {
+    /// Returns the class of data in the block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{server::Block, ticket::Class};
+    /// let block = Block::Data(Class::Header, Vec::new());
+    /// assert_eq!(block.class(), Class::Header);
+    /// ```
+    pub fn class(&self) -> Class {
+        match self {
+            Self::Range(range) => range.class(),
+            Self::Data(class, _) => *class,
+        }
+    }
+}
+
+/// Builds an htsget ticket that lists the given data blocks of a file.
+///
+/// Byte ranges are returned as URLs to the given file location with a `Range` header. Inline
+/// data is returned as `data` URIs.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_htsget::{
+///     server::{self, Block, ByteRange},
+///     ticket::{Class, Format},
+/// };
+///
+/// let blocks = [
+///     Block::Range(ByteRange::new(Class::Header, 0, 8)),
+///     Block::Data(Class::Body, b"noodles".to_vec()),
+///     Block::Range(ByteRange::new(Class::Body, 13, 21)),
+/// ];
+///
+/// let ticket = server::ticket(Format::Bam, "https://localhost/sample.bam", &blocks);
+///
+/// assert_eq!(ticket.format(), Format::Bam);
+///
+/// let urls = ticket.urls();
+/// assert_eq!(urls.len(), 3);
+/// assert_eq!(
+///     urls[0].headers(),
+///     [(String::from("Range"), String::from("bytes=0-7"))]
+/// );
+/// assert_eq!(urls[1].url(), "data:application/vnd.ga4gh.bam;base64,bm9vZGxlcw==");
+/// assert_eq!(urls[2].class(), Some(Class::Body));
+/// ```
+pub fn ticket(format: Format, url: &str, blocks: &[Block]) -> Ticket {
+    let media_type = media_type(format);
+
+    let urls = blocks
+        .iter()
+        .map(|block| {
+            let mut ticket_url = match block {
+                Block::Range(range) => {
+                    let mut ticket_url = Url::new(url);
+
+                    ticket_url.headers.push((
+                        String::from("Range"),
+                        format!("bytes={}-{}", range.start(), range.end() - 1),
+                    ));
+
+                    ticket_url
+                }
+                Block::Data(_, data) => Url::new(data_uri::encode(media_type, data)),
+            };
+
+            ticket_url.class = Some(block.class());
+
+            ticket_url
+        })
+        .collect();
+
+    Ticket::new(format, urls, None)
+}
+
+fn media_type(format: Format) -> &'static str {
+    match format {
+        Format::Bam => "application/vnd.ga4gh.bam",
+        Format::Cram => "application/vnd.ga4gh.cram",
+        Format::Vcf => "application/vnd.ga4gh.vcf",
+        Format::Bcf => "application/vnd.ga4gh.bcf",
+    }
+}
+
+// Adds a block to a list of blocks.
+//
+// Empty blocks are skipped, and a byte range that directly follows another of the same class is
+// merged into it.
+fn push_block(blocks: &mut Vec<Block>, block: Block) {
+    match &block {
+        Block::Range(range) if range.start() >= range.end() => return,
+        Block::Data(_, data) if data.is_empty() => return,
+        _ => {}
+    }
+
+    if let (Some(Block::Range(last)), Block::Range(range)) = (blocks.last_mut(), &block) {
+        if last.class() == range.class() && last.end() == range.start() {
+            *last = ByteRange::new(last.class(), last.start(), range.end());
+            return;
+        }
+    }
+
+    blocks.push(block);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_block() {
+        let mut blocks = Vec::new();
+
+        push_block(
+            &mut blocks,
+            Block::Range(ByteRange::new(Class::Header, 0, 8)),
+        );
+        push_block(
+            &mut blocks,
+            Block::Range(ByteRange::new(Class::Body, 8, 13)),
+        );
+        push_block(
+            &mut blocks,
+            Block::Range(ByteRange::new(Class::Body, 13, 21)),
+        );
+        push_block(
+            &mut blocks,
+            Block::Range(ByteRange::new(Class::Body, 21, 21)),
+        );
+        push_block(&mut blocks, Block::Data(Class::Body, Vec::new()));
+        push_block(&mut blocks, Block::Data(Class::Body, vec![0x00]));
+        push_block(
+            &mut blocks,
+            Block::Range(ByteRange::new(Class::Body, 34, 55)),
+        );
+        push_block(
+            &mut blocks,
+            Block::Range(ByteRange::new(Class::Body, 89, 144)),
+        );
+
+        assert_eq!(
+            blocks,
+            [
+                Block::Range(ByteRange::new(Class::Header, 0, 8)),
+                Block::Range(ByteRange::new(Class::Body, 8, 21)),
+                Block::Data(Class::Body, vec![0x00]),
+                Block::Range(ByteRange::new(Class::Body, 34, 55)),
+                Block::Range(ByteRange::new(Class::Body, 89, 144)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! htsget server BAM data blocks.
+
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+
+use noodles_bam as bam;
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi::{
+    binning_index::ReferenceSequenceExt, index::reference_sequence::bin::Chunk, BinningIndex,
+};
+use noodles_sam::header::ReferenceSequences;
+
+use super::{push_block, Block, ByteRange};
+use crate::ticket::Class;
+
+// The size of the BGZF EOF block.
+const EOF_BLOCK_SIZE: u64 = 28;
+
+/// Returns the data blocks of the header of a BAM file.
+///
+/// This is the response to a request with the header class. The blocks include the EOF block.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_htsget::server;
+/// let mut reader = File::open("sample.bam")?;
+/// let blocks = server::bam::header(&mut reader)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn header<R>(reader: &mut R) -> io::Result<Vec<Block>>
+where
+    R: Read + Seek,
+{
+    let eof_start = read_eof_start(reader)?;
+    let (header_end, _) = read_header_end(reader)?;
+    build_blocks(reader, header_end, Vec::new(), eof_start)
+}
+
+/// Returns the data blocks of a BAM file that contain the records that intersect the given
+/// region.
+///
+/// The blocks include the header and EOF blocks. The chunks of mapped regions are queried using
+/// the given index (BAI or CSI).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bam::bai;
+/// use noodles_core::Region;
+/// use noodles_htsget::server;
+///
+/// let mut reader = File::open("sample.bam")?;
+/// let index = bai::read("sample.bam.bai")?;
+/// let region = Region::mapped("sq0", 8..=13);
+///
+/// let blocks = server::bam::query(&mut reader, &index, &region)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn query<R, I, RS>(reader: &mut R, index: &I, region: &Region) -> io::Result<Vec<Block>>
+where
+    R: Read + Seek,
+    I: BinningIndex<RS>,
+    RS: ReferenceSequenceExt,
+{
+    let eof_start = read_eof_start(reader)?;
+    let (header_end, reference_sequences) = read_header_end(reader)?;
+    let eof_position = virtual_position(eof_start, 0)?;
+
+    let chunks = match region {
+        Region::Mapped(mapped) => {
+            let reference_sequence_id = reference_sequences
+                .get_index_of(mapped.name())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("invalid reference sequence name: {}", mapped.name()),
+                    )
+                })?;
+
+            index.query(reference_sequence_id, mapped.interval())?
+        }
+        Region::Unmapped => {
+            let start = index
+                .first_record_in_last_linear_bin_start_position()
+                .unwrap_or(header_end);
+
+            vec![Chunk::new(start, eof_position)]
+        }
+        Region::All => vec![Chunk::new(header_end, eof_position)],
+    };
+
+    build_blocks(reader, header_end, chunks, eof_start)
+}
+
+fn read_eof_start<R>(reader: &mut R) -> io::Result<u64>
+where
+    R: Seek,
+{
+    let file_size = reader.seek(SeekFrom::End(0))?;
+
+    file_size
+        .checked_sub(EOF_BLOCK_SIZE)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing BGZF EOF block"))
+}
+
+// Reads the BAM header and returns the virtual position of its end.
+fn read_header_end<R>(reader: &mut R) -> io::Result<(bgzf::VirtualPosition, ReferenceSequences)>
+where
+    R: Read + Seek,
+{
+    reader.seek(SeekFrom::Start(0))?;
+
+    let mut bam_reader = bam::Reader::new(reader);
+    bam_reader.read_header()?;
+    let reference_sequences = bam_reader.read_reference_sequences()?;
+
+    Ok((bam_reader.virtual_position(), reference_sequences))
+}
+
+// Builds the data blocks of the header, the given chunks, and the EOF block.
+//
+// Chunks are clipped to start after the header and merged if they overlap. The whole BGZF blocks
+// of a chunk are returned as byte ranges, and the partial blocks at the start and end of a chunk
+// are re-encoded.
+fn build_blocks<R>(
+    reader: &mut R,
+    header_end: bgzf::VirtualPosition,
+    mut chunks: Vec<Chunk>,
+    eof_start: u64,
+) -> io::Result<Vec<Block>>
+where
+    R: Read + Seek,
+{
+    let file_size = reader.seek(SeekFrom::End(0))?;
+    let mut reader = bgzf::Reader::new(reader);
+
+    let mut blocks = Vec::new();
+
+    push_block(
+        &mut blocks,
+        Block::Range(ByteRange::new(Class::Header, 0, header_end.compressed())),
+    );
+
+    if header_end.uncompressed() > 0 {
+        let (data, _) = read_block(&mut reader, header_end.compressed())?;
+        let end = usize::from(header_end.uncompressed());
+        push_block(
+            &mut blocks,
+            Block::Data(Class::Header, deflate(&data[..end])?),
+        );
+    }
+
+    chunks.sort_unstable_by_key(|chunk| chunk.start());
+
+    let mut current: Option<(bgzf::VirtualPosition, bgzf::VirtualPosition)> = None;
+
+    for chunk in chunks {
+        let start = chunk.start().max(header_end);
+        let end = chunk.end();
+
+        if start >= end {
+            continue;
+        }
+
+        current = match current {
+            Some((s, e)) if start <= e => Some((s, e.max(end))),
+            Some((s, e)) => {
+                push_chunk_blocks(&mut reader, &mut blocks, s, e)?;
+                Some((start, end))
+            }
+            None => Some((start, end)),
+        };
+    }
+
+    if let Some((start, end)) = current {
+        push_chunk_blocks(&mut reader, &mut blocks, start, end)?;
+    }
+
+    push_block(
+        &mut blocks,
+        Block::Range(ByteRange::new(Class::Body, eof_start, file_size)),
+    );
+
+    Ok(blocks)
+}
+
+fn push_chunk_blocks<R>(
+    reader: &mut bgzf::Reader<R>,
+    blocks: &mut Vec<Block>,
+    start: bgzf::VirtualPosition,
+    end: bgzf::VirtualPosition,
+) -> io::Result<()>
+where
+    R: Read + Seek,
+{
+    let (start_cpos, start_upos) = (start.compressed(), usize::from(start.uncompressed()));
+    let (end_cpos, end_upos) = (end.compressed(), usize::from(end.uncompressed()));
+
+    if start_cpos == end_cpos {
+        let (data, _) = read_block(reader, start_cpos)?;
+        let data = &data[start_upos..end_upos];
+        push_block(blocks, Block::Data(Class::Body, deflate(data)?));
+        return Ok(());
+    }
+
+    let mut range_start = start_cpos;
+
+    if start_upos > 0 {
+        let (data, block_end) = read_block(reader, start_cpos)?;
+        push_block(
+            blocks,
+            Block::Data(Class::Body, deflate(&data[start_upos..])?),
+        );
+        range_start = block_end;
+    }
+
+    push_block(
+        blocks,
+        Block::Range(ByteRange::new(Class::Body, range_start, end_cpos)),
+    );
+
+    if end_upos > 0 {
+        let (data, _) = read_block(reader, end_cpos)?;
+        push_block(
+            blocks,
+            Block::Data(Class::Body, deflate(&data[..end_upos])?),
+        );
+    }
+
+    Ok(())
+}
+
+// Reads the BGZF block at the given compressed position.
+//
+// This returns the uncompressed data of the block and the compressed position of its end.
+fn read_block<R>(reader: &mut bgzf::Reader<R>, cpos: u64) -> io::Result<(Vec<u8>, u64)>
+where
+    R: Read + Seek,
+{
+    reader.seek(virtual_position(cpos, 0)?)?;
+    let data = reader.fill_buf()?.to_vec();
+    Ok((data, reader.position()))
+}
+
+// Compresses data as BGZF blocks without an EOF block.
+fn deflate(data: &[u8]) -> io::Result<Vec<u8>> {
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut writer = bgzf::Writer::new(Vec::new());
+    writer.write_all(data)?;
+    writer.flush()?;
+    Ok(writer.get_ref().clone())
+}
+
+fn virtual_position(cpos: u64, upos: u16) -> io::Result<bgzf::VirtualPosition> {
+    bgzf::VirtualPosition::try_from((cpos, upos))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_bam::bai;
+    use noodles_csi::index::reference_sequence::bin::Chunk;
+    use noodles_sam::{self as sam, AlignmentRecord};
+
+    use super::*;
+
+    fn build_bam() -> Result<(Vec<u8>, bai::Index), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(sam::header::ReferenceSequence::new("sq0".parse()?, 100000)?)
+            .build();
+
+        let mut writer = bam::Writer::new(Vec::new());
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        for i in 0..16384 {
+            let sam_record: sam::Record =
+                format!("r{}\t0\tsq0\t{}\t60\t4M\t*\t0\t0\t*\t*", i, i * 5 + 1).parse()?;
+            let record =
+                bam::Record::try_from_sam_record(header.reference_sequences(), &sam_record)?;
+            writer.write_record(&record)?;
+        }
+
+        writer.try_finish()?;
+        let data = writer.get_ref().get_ref().clone();
+
+        let mut reader = bam::Reader::new(&data[..]);
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut builder = bai::Index::builder();
+        let mut record = bam::Record::default();
+        let mut start_position = reader.virtual_position();
+
+        while reader.read_record(&mut record)? != 0 {
+            let end_position = reader.virtual_position();
+            builder.add_record(&record, Chunk::new(start_position, end_position))?;
+            start_position = end_position;
+        }
+
+        Ok((data, builder.build(1)))
+    }
+
+    fn read_positions(data: &[u8], blocks: &[Block]) -> io::Result<Vec<i32>> {
+        let mut buf = Vec::new();
+
+        for block in blocks {
+            match block {
+                Block::Range(range) => {
+                    buf.extend_from_slice(&data[range.start() as usize..range.end() as usize]);
+                }
+                Block::Data(_, data) => buf.extend_from_slice(data),
+            }
+        }
+
+        let mut reader = bam::Reader::new(&buf[..]);
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        reader
+            .records()
+            .map(|result| {
+                result.map(|record| {
+                    AlignmentRecord::alignment_start(&record)
+                        .map(i32::from)
+                        .unwrap_or_default()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        let (data, index) = build_bam()?;
+        let file_size = data.len() as u64;
+
+        let region = Region::mapped("sq0", 40001..=40100);
+        let blocks = query(&mut Cursor::new(&data), &index, &region)?;
+
+        assert_eq!(
+            blocks.first().map(|block| block.class()),
+            Some(Class::Header)
+        );
+        assert!(blocks[1..].iter().all(|block| block.class() == Class::Body));
+
+        let range_len: u64 = blocks
+            .iter()
+            .filter_map(|block| match block {
+                Block::Range(range) => Some(range.end() - range.start()),
+                Block::Data(..) => None,
+            })
+            .sum();
+
+        assert!(range_len < file_size / 2);
+
+        assert_eq!(
+            blocks.last(),
+            Some(&Block::Range(ByteRange::new(
+                Class::Body,
+                file_size - EOF_BLOCK_SIZE,
+                file_size
+            )))
+        );
+
+        let positions = read_positions(&data, &blocks)?;
+
+        for position in (40001..=40100).step_by(5) {
+            assert!(positions.contains(&position));
+        }
+
+        let blocks = query(&mut Cursor::new(&data), &index, &Region::All)?;
+        assert_eq!(read_positions(&data, &blocks)?.len(), 16384);
+
+        let blocks = header(&mut Cursor::new(&data))?;
+        assert!(read_positions(&data, &blocks)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_with_invalid_reference_sequence_name() -> Result<(), Box<dyn std::error::Error>> {
+        let (data, index) = build_bam()?;
+        let region = Region::mapped("sq1", 1..=8);
+
+        assert!(matches!(
+            query(&mut Cursor::new(&data), &index, &region),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use crate::ticket::Class;
+
+/// A byte range of a file.
+///
+/// The range is half-open, i.e., `[start, end)`, and describes the class of data it contains.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ByteRange {
+    class: Class,
+    start: u64,
+    end: u64,
+}
+
+impl ByteRange {
+    /// Creates a byte range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{server::ByteRange, ticket::Class};
+    /// let range = ByteRange::new(Class::Header, 0, 8);
+    /// ```
+    pub fn new(class: Class, start: u64, end: u64) -> Self {
+        Self { class, start, end }
+    }
+
+    /// Returns the class of data in the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{server::ByteRange, ticket::Class};
+    /// let range = ByteRange::new(Class::Header, 0, 8);
+    /// assert_eq!(range.class(), Class::Header);
+    /// ```
+    pub fn class(&self) -> Class {
+        self.class
+    }
+
+    /// Returns the start position of the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{server::ByteRange, ticket::Class};
+    /// let range = ByteRange::new(Class::Header, 0, 8);
+    /// assert_eq!(range.start(), 0);
+    /// ```
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Returns the end position of the range.
+    ///
+    /// This is exclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{server::ByteRange, ticket::Class};
+    /// let range = ByteRange::new(Class::Header, 0, 8);
+    /// assert_eq!(range.end(), 8);
+    /// ```
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+}
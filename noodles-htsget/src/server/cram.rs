@@ -0,0 +1,325 @@
+//! htsget server CRAM data blocks.
+
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    ops::Bound,
+};
+
+use noodles_core::Region;
+use noodles_cram::{self as cram, crai};
+use noodles_sam::{self as sam, header::ReferenceSequences};
+
+use super::{push_block, Block, ByteRange};
+use crate::ticket::Class;
+
+// The sizes of the EOF container in CRAM 2.1 and 3.x, respectively.
+const EOF_CONTAINER_SIZE_2_1: u64 = 30;
+const EOF_CONTAINER_SIZE_3: u64 = 38;
+
+/// Returns the data blocks of the header of a CRAM file.
+///
+/// This is the response to a request with the header class. The blocks include the file
+/// definition, the header container, and the EOF container.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_htsget::server;
+/// let mut reader = File::open("sample.cram")?;
+/// let blocks = server::cram::header(&mut reader)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn header<R>(reader: &mut R) -> io::Result<Vec<Block>>
+where
+    R: Read + Seek,
+{
+    let (header, eof, _) = read_header_and_eof_ranges(reader)?;
+    Ok(build_blocks(header, Vec::new(), eof))
+}
+
+/// Returns the data blocks of a CRAM file that contain the records that intersect the given
+/// region.
+///
+/// The blocks include the header and EOF containers. Data containers are selected using the given
+/// CRAM index. Because containers are self-contained, the blocks are always byte ranges of the
+/// file, and a container is included whole if any of its slices intersect the region.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_core::Region;
+/// use noodles_cram::crai;
+/// use noodles_htsget::server;
+///
+/// let mut reader = File::open("sample.cram")?;
+/// let index = crai::read("sample.cram.crai")?;
+/// let region = Region::mapped("sq0", 8..=13);
+///
+/// let blocks = server::cram::query(&mut reader, &index, &region)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn query<R>(reader: &mut R, index: &crai::Index, region: &Region) -> io::Result<Vec<Block>>
+where
+    R: Read + Seek,
+{
+    let (header, eof, reference_sequences) = read_header_and_eof_ranges(reader)?;
+
+    let mut container_positions: Vec<_> = index.iter().map(|record| record.offset()).collect();
+    container_positions.sort_unstable();
+    container_positions.dedup();
+
+    let container_range = |position: u64| {
+        let i = container_positions.partition_point(|&p| p <= position);
+        let end = container_positions.get(i).copied().unwrap_or(eof.start());
+        ByteRange::new(Class::Body, position, end)
+    };
+
+    let body = match region {
+        Region::Mapped(mapped) => {
+            let reference_sequence_id = reference_sequences
+                .get_index_of(mapped.name())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("invalid reference sequence name: {}", mapped.name()),
+                    )
+                })?;
+
+            let start = match mapped.start() {
+                Bound::Included(n) => n,
+                Bound::Excluded(n) => n + 1,
+                Bound::Unbounded => 1,
+            };
+
+            let end = match mapped.end() {
+                Bound::Included(n) => n,
+                Bound::Excluded(n) => n - 1,
+                Bound::Unbounded => i32::MAX,
+            };
+
+            index
+                .iter()
+                .filter(|record| {
+                    let record_reference_sequence_id = record
+                        .reference_sequence_id()
+                        .map(|id| i32::from(id) as usize);
+
+                    let record_start = record.alignment_start();
+                    let record_end = record_start + record.alignment_span() - 1;
+
+                    record_reference_sequence_id == Some(reference_sequence_id)
+                        && record_start <= end
+                        && start <= record_end
+                })
+                .map(|record| container_range(record.offset()))
+                .collect()
+        }
+        Region::Unmapped => index
+            .iter()
+            .filter(|record| record.reference_sequence_id().is_none())
+            .map(|record| container_range(record.offset()))
+            .collect(),
+        Region::All => vec![ByteRange::new(Class::Body, header.end(), eof.start())],
+    };
+
+    Ok(build_blocks(header, body, eof))
+}
+
+fn build_blocks(header: ByteRange, mut body: Vec<ByteRange>, eof: ByteRange) -> Vec<Block> {
+    body.sort_unstable_by_key(|range| range.start());
+    body.dedup();
+
+    let mut blocks = Vec::new();
+
+    push_block(&mut blocks, Block::Range(header));
+
+    for range in body {
+        push_block(&mut blocks, Block::Range(range));
+    }
+
+    push_block(&mut blocks, Block::Range(eof));
+
+    blocks
+}
+
+fn read_header_and_eof_ranges<R>(
+    reader: &mut R,
+) -> io::Result<(ByteRange, ByteRange, ReferenceSequences)>
+where
+    R: Read + Seek,
+{
+    reader.seek(SeekFrom::Start(0))?;
+
+    let mut cram_reader = cram::Reader::new(&mut *reader);
+
+    let file_definition = cram_reader.read_file_definition()?;
+
+    let header: sam::Header = cram_reader
+        .read_file_header()?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let header_end = cram_reader.position()?;
+
+    let eof_container_size = if file_definition.version().major() >= 3 {
+        EOF_CONTAINER_SIZE_3
+    } else {
+        EOF_CONTAINER_SIZE_2_1
+    };
+
+    let file_size = reader.seek(SeekFrom::End(0))?;
+
+    let eof_start = file_size
+        .checked_sub(eof_container_size)
+        .filter(|&start| start >= header_end)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing CRAM EOF container"))?;
+
+    Ok((
+        ByteRange::new(Class::Header, 0, header_end),
+        ByteRange::new(Class::Body, eof_start, file_size),
+        header.reference_sequences().clone(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, io::Cursor, process};
+
+    use noodles_bam::record::ReferenceSequenceId;
+
+    use super::*;
+
+    fn build_cram() -> Result<(Vec<u8>, crai::Index), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                sam::header::ReferenceSequence::builder()
+                    .set_name("sq0".parse()?)
+                    .set_length(8)
+                    .set_md5_checksum(
+                        [
+                            0xd7, 0xeb, 0xa3, 0x11, 0x42, 0x1b, 0xbc, 0x9d, 0x3a, 0xda, 0x44, 0x70,
+                            0x9d, 0xd6, 0x15, 0x34,
+                        ]
+                        .into(),
+                    )
+                    .build()?,
+            )
+            .build();
+
+        let mut writer = cram::Writer::new(Vec::new(), Vec::new());
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        let alignment_start = sam::record::Position::try_from(1)?;
+
+        // 3 data containers
+        for i in 0..30000 {
+            let record = cram::Record::builder()
+                .set_read_name(format!("r{}", i).into_bytes())
+                .set_alignment_start(alignment_start)
+                .build();
+
+            writer.write_record(record)?;
+        }
+
+        writer.try_finish()?;
+        let data = writer.get_ref().clone();
+
+        let path = env::temp_dir().join(format!("noodles-htsget-{}.cram", process::id()));
+        fs::write(&path, &data)?;
+        let index = cram::index(&path);
+        fs::remove_file(&path)?;
+
+        Ok((data, index?))
+    }
+
+    fn read_record_count(data: &[u8], blocks: &[Block]) -> io::Result<usize> {
+        let mut buf = Vec::new();
+
+        for block in blocks {
+            if let Block::Range(range) = block {
+                buf.extend_from_slice(&data[range.start() as usize..range.end() as usize]);
+            }
+        }
+
+        let mut reader = cram::Reader::new(&buf[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        reader
+            .records()
+            .try_fold(0, |n, result| result.map(|_| n + 1))
+    }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        let (data, index) = build_cram()?;
+        let file_size = data.len() as u64;
+
+        let mut container_positions: Vec<_> = index.iter().map(|record| record.offset()).collect();
+        container_positions.dedup();
+        assert_eq!(container_positions.len(), 3);
+
+        let header_end = container_positions[0];
+        let eof = Block::Range(ByteRange::new(
+            Class::Body,
+            file_size - EOF_CONTAINER_SIZE_3,
+            file_size,
+        ));
+
+        let blocks = header(&mut Cursor::new(&data))?;
+        assert_eq!(
+            blocks,
+            [
+                Block::Range(ByteRange::new(Class::Header, 0, header_end)),
+                eof.clone(),
+            ]
+        );
+        assert_eq!(read_record_count(&data, &blocks)?, 0);
+
+        let blocks = query(&mut Cursor::new(&data), &index, &Region::Unmapped)?;
+        assert_eq!(
+            blocks,
+            [
+                Block::Range(ByteRange::new(Class::Header, 0, header_end)),
+                Block::Range(ByteRange::new(Class::Body, header_end, file_size)),
+            ]
+        );
+        assert_eq!(read_record_count(&data, &blocks)?, 30000);
+
+        // Relabel the containers as mapped to sq0:1-100, sq0:101-200, and unmapped, respectively.
+        let sq0 = ReferenceSequenceId::try_from(0)?;
+
+        let index = vec![
+            crai::Record::new(Some(sq0), 1, 100, container_positions[0], 0, 0),
+            crai::Record::new(Some(sq0), 101, 100, container_positions[1], 0, 0),
+            crai::Record::new(None, 0, 0, container_positions[2], 0, 0),
+        ];
+
+        let region = Region::mapped("sq0", 150..=160);
+        let blocks = query(&mut Cursor::new(&data), &index, &region)?;
+
+        assert_eq!(
+            blocks,
+            [
+                Block::Range(ByteRange::new(Class::Header, 0, header_end)),
+                Block::Range(ByteRange::new(
+                    Class::Body,
+                    container_positions[1],
+                    container_positions[2]
+                )),
+                eof,
+            ]
+        );
+
+        let region = Region::mapped("sq1", 1..=8);
+        assert!(matches!(
+            query(&mut Cursor::new(&data), &index, &region),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
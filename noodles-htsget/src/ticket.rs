@@ -0,0 +1,284 @@
+//! htsget ticket and fields.
+
+mod class;
+mod format;
+mod raw;
+mod url;
+
+pub use self::{class::Class, format::Format, url::Url};
+
+use std::{borrow::Cow, error, fmt, str::FromStr};
+
+/// An htsget ticket.
+///
+/// A ticket is the response of a reads or variants request. It lists the URLs of the data blocks
+/// that, when concatenated in order, make up the requested data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ticket {
+    format: Format,
+    urls: Vec<Url>,
+    md5: Option<String>,
+}
+
+impl Ticket {
+    /// Creates an htsget ticket.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{ticket::Format, Ticket};
+    /// let ticket = Ticket::new(Format::Bam, Vec::new(), None);
+    /// ```
+    pub fn new(format: Format, urls: Vec<Url>, md5: Option<String>) -> Self {
+        Self { format, urls, md5 }
+    }
+
+    /// Returns the format of the data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{ticket::Format, Ticket};
+    /// let ticket = Ticket::new(Format::Bam, Vec::new(), None);
+    /// assert_eq!(ticket.format(), Format::Bam);
+    /// ```
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the URLs of the data blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{ticket::{Format, Url}, Ticket};
+    /// let ticket = Ticket::new(Format::Bam, vec![Url::new("data:,")], None);
+    /// assert_eq!(ticket.urls(), [Url::new("data:,")]);
+    /// ```
+    pub fn urls(&self) -> &[Url] {
+        &self.urls
+    }
+
+    /// Returns the MD5 checksum of the reassembled data, if set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{ticket::Format, Ticket};
+    /// let ticket = Ticket::new(Format::Bam, Vec::new(), None);
+    /// assert!(ticket.md5().is_none());
+    /// ```
+    pub fn md5(&self) -> Option<&str> {
+        self.md5.as_deref()
+    }
+}
+
+impl fmt::Display for Ticket {
+    /// Formats the ticket as a JSON htsget response.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let response = raw::Response {
+            htsget: raw::Ticket {
+                format: Cow::Borrowed(self.format.as_ref()),
+                urls: self
+                    .urls
+                    .iter()
+                    .map(|url| raw::Url {
+                        url: Cow::Borrowed(url.url()),
+                        headers: Cow::Borrowed(url.headers()),
+                        class: url
+                            .class
+                            .as_ref()
+                            .map(|class| Cow::Borrowed(class.as_ref())),
+                    })
+                    .collect(),
+                md5: self.md5().map(Cow::Borrowed),
+            },
+        };
+
+        let s = serde_json::to_string(&response).map_err(|_| fmt::Error)?;
+        f.write_str(&s)
+    }
+}
+
+/// An error returned when a raw htsget ticket fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is not a valid htsget response.
+    ///
+    /// This includes invalid JSON and missing fields or fields with unexpected types. The
+    /// message describes the cause.
+    InvalidJson(String),
+    /// The format is invalid.
+    InvalidFormat(format::ParseError),
+    /// A URL class is invalid.
+    InvalidClass(class::ParseError),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidJson(message) => write!(f, "invalid JSON: {}", message),
+            Self::InvalidFormat(e) => write!(f, "invalid format: {}", e),
+            Self::InvalidClass(e) => write!(f, "invalid class: {}", e),
+        }
+    }
+}
+
+impl FromStr for Ticket {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let response: raw::Response<'_> =
+            serde_json::from_str(s).map_err(|e| ParseError::InvalidJson(e.to_string()))?;
+
+        let raw_ticket = response.htsget;
+
+        let format = raw_ticket
+            .format
+            .parse()
+            .map_err(ParseError::InvalidFormat)?;
+
+        let urls = raw_ticket
+            .urls
+            .into_iter()
+            .map(parse_url)
+            .collect::<Result<_, _>>()?;
+
+        let md5 = raw_ticket.md5.map(Cow::into_owned);
+
+        Ok(Self { format, urls, md5 })
+    }
+}
+
+fn parse_url(raw_url: raw::Url<'_>) -> Result<Url, ParseError> {
+    let class = raw_url
+        .class
+        .map(|class| class.parse().map_err(ParseError::InvalidClass))
+        .transpose()?;
+
+    Ok(Url {
+        url: raw_url.url.into_owned(),
+        headers: raw_url.headers.into_owned(),
+        class,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        let s = r#"{
+  "htsget": {
+    "format": "BAM",
+    "urls": [
+      {
+        "url": "data:application/vnd.ga4gh.bam;base64,QkFNAQ==",
+        "class": "header"
+      },
+      {
+        "url": "https://localhost/sample.bam",
+        "headers": {
+          "Authorization": "Bearer noodles",
+          "Range": "bytes=65536-1003750"
+        },
+        "class": "body"
+      }
+    ],
+    "md5": "d41d8cd98f00b204e9800998ecf8427e"
+  }
+}"#;
+
+        let actual: Ticket = s.parse().unwrap();
+
+        let mut header_url = Url::new("data:application/vnd.ga4gh.bam;base64,QkFNAQ==");
+        header_url.class = Some(Class::Header);
+
+        let mut body_url = Url::new("https://localhost/sample.bam");
+        body_url.headers = vec![
+            (
+                String::from("Authorization"),
+                String::from("Bearer noodles"),
+            ),
+            (String::from("Range"), String::from("bytes=65536-1003750")),
+        ];
+        body_url.class = Some(Class::Body);
+
+        let expected = Ticket::new(
+            Format::Bam,
+            vec![header_url, body_url],
+            Some(String::from("d41d8cd98f00b204e9800998ecf8427e")),
+        );
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_fmt() {
+        let mut header_url = Url::new("data:application/vnd.ga4gh.bam;base64,QkFNAQ==");
+        header_url.class = Some(Class::Header);
+
+        let mut body_url = Url::new("https://localhost/sample.bam");
+        body_url.headers = vec![(String::from("Range"), String::from("bytes=65536-1003750"))];
+        body_url.class = Some(Class::Body);
+
+        let ticket = Ticket::new(
+            Format::Bam,
+            vec![header_url, body_url],
+            Some(String::from("d41d8cd98f00b204e9800998ecf8427e")),
+        );
+
+        let expected = concat!(
+            r#"{"htsget":{"format":"BAM","urls":["#,
+            r#"{"url":"data:application/vnd.ga4gh.bam;base64,QkFNAQ==","class":"header"},"#,
+            r#"{"url":"https://localhost/sample.bam","headers":{"Range":"bytes=65536-1003750"},"#,
+            r#""class":"body"}],"md5":"d41d8cd98f00b204e9800998ecf8427e"}}"#,
+        );
+
+        assert_eq!(ticket.to_string(), expected);
+        assert_eq!(expected.parse(), Ok(ticket));
+
+        let ticket = Ticket::new(Format::Vcf, vec![Url::new("data:,")], None);
+        assert_eq!(
+            ticket.to_string(),
+            r#"{"htsget":{"format":"VCF","urls":[{"url":"data:,"}]}}"#
+        );
+    }
+
+    #[test]
+    fn test_from_str_with_invalid_input() {
+        assert!(matches!(
+            "{".parse::<Ticket>(),
+            Err(ParseError::InvalidJson(_))
+        ));
+        assert!(matches!(
+            "{}".parse::<Ticket>(),
+            Err(ParseError::InvalidJson(message)) if message.starts_with("missing field `htsget`")
+        ));
+        assert!(matches!(
+            r#"{"htsget":{"format":"BAM"}}"#.parse::<Ticket>(),
+            Err(ParseError::InvalidJson(message)) if message.starts_with("missing field `urls`")
+        ));
+        assert!(matches!(
+            r#"{"htsget":{"format":"BAM","urls":{}}}"#.parse::<Ticket>(),
+            Err(ParseError::InvalidJson(message)) if message.starts_with("invalid type: map")
+        ));
+        assert!(matches!(
+            r#"{"htsget":{"format":"BAM","urls":[{"url":"data:,","headers":[]}]}}"#
+                .parse::<Ticket>(),
+            Err(ParseError::InvalidJson(_))
+        ));
+        assert!(matches!(
+            r#"{"htsget":{"format":"SAM","urls":[]}}"#.parse::<Ticket>(),
+            Err(ParseError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            r#"{"htsget":{"format":"BAM","urls":[{"url":"data:,","class":"all"}]}}"#
+                .parse::<Ticket>(),
+            Err(ParseError::InvalidClass(_))
+        ));
+    }
+}
@@ -0,0 +1,84 @@
+use std::{error, fmt, str::FromStr};
+
+/// An htsget data class.
+///
+/// A class is used to request only the header of a file or describe which part of the file a
+/// ticket URL returns.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Class {
+    /// The file header.
+    Header,
+    /// The file body.
+    Body,
+}
+
+impl AsRef<str> for Class {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Header => "header",
+            Self::Body => "body",
+        }
+    }
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+/// An error returned when a raw htsget class fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid(String),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid(s) => write!(f, "invalid input: {}", s),
+        }
+    }
+}
+
+impl FromStr for Class {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(ParseError::Empty),
+            "header" => Ok(Self::Header),
+            "body" => Ok(Self::Body),
+            _ => Err(ParseError::Invalid(s.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Class::Header.to_string(), "header");
+        assert_eq!(Class::Body.to_string(), "body");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("header".parse(), Ok(Class::Header));
+        assert_eq!("body".parse(), Ok(Class::Body));
+
+        assert_eq!("".parse::<Class>(), Err(ParseError::Empty));
+        assert_eq!(
+            "footer".parse::<Class>(),
+            Err(ParseError::Invalid(String::from("footer")))
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::{error, fmt, str::FromStr};
+
+/// An htsget data format.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// BAM.
+    Bam,
+    /// CRAM.
+    Cram,
+    /// VCF.
+    Vcf,
+    /// BCF.
+    Bcf,
+}
+
+impl AsRef<str> for Format {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Bam => "BAM",
+            Self::Cram => "CRAM",
+            Self::Vcf => "VCF",
+            Self::Bcf => "BCF",
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+/// An error returned when a raw htsget format fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid(String),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid(s) => write!(f, "invalid input: {}", s),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(ParseError::Empty),
+            "BAM" => Ok(Self::Bam),
+            "CRAM" => Ok(Self::Cram),
+            "VCF" => Ok(Self::Vcf),
+            "BCF" => Ok(Self::Bcf),
+            _ => Err(ParseError::Invalid(s.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Format::Bam.to_string(), "BAM");
+        assert_eq!(Format::Cram.to_string(), "CRAM");
+        assert_eq!(Format::Vcf.to_string(), "VCF");
+        assert_eq!(Format::Bcf.to_string(), "BCF");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("BAM".parse(), Ok(Format::Bam));
+        assert_eq!("CRAM".parse(), Ok(Format::Cram));
+        assert_eq!("VCF".parse(), Ok(Format::Vcf));
+        assert_eq!("BCF".parse(), Ok(Format::Bcf));
+
+        assert_eq!("".parse::<Format>(), Err(ParseError::Empty));
+        assert_eq!(
+            "bam".parse::<Format>(),
+            Err(ParseError::Invalid(String::from("bam")))
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! Serialized htsget ticket (JSON).
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(super) struct Response<'a> {
+    pub(super) htsget: Ticket<'a>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(super) struct Ticket<'a> {
+    pub(super) format: Cow<'a, str>,
+    pub(super) urls: Vec<Url<'a>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) md5: Option<Cow<'a, str>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(super) struct Url<'a> {
+    pub(super) url: Cow<'a, str>,
+    #[serde(default, skip_serializing_if = "headers::is_empty", with = "headers")]
+    pub(super) headers: Cow<'a, [(String, String)]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) class: Option<Cow<'a, str>>,
+}
+
+// Headers are a JSON object. They are kept as a list to preserve their order.
+mod headers {
+    use std::{borrow::Cow, fmt};
+
+    use serde::{
+        de::{MapAccess, Visitor},
+        Deserializer, Serializer,
+    };
+
+    pub(super) fn is_empty(headers: &[(String, String)]) -> bool {
+        headers.is_empty()
+    }
+
+    pub(super) fn serialize<S>(
+        headers: &[(String, String)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_map(headers.iter().map(|(key, value)| (key, value)))
+    }
+
+    pub(super) fn deserialize<'de, 'a, D>(
+        deserializer: D,
+    ) -> Result<Cow<'a, [(String, String)]>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct HeadersVisitor;
+
+        impl<'de> Visitor<'de> for HeadersVisitor {
+            type Value = Vec<(String, String)>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an object of header names and values")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut headers = Vec::new();
+
+                while let Some(entry) = map.next_entry()? {
+                    headers.push(entry);
+                }
+
+                Ok(headers)
+            }
+        }
+
+        deserializer.deserialize_map(HeadersVisitor).map(Cow::Owned)
+    }
+}
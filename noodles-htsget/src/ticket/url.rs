@@ -0,0 +1,73 @@
+use super::Class;
+
+/// An htsget ticket URL.
+///
+/// This describes the location of a data block and how to retrieve it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Url {
+    pub(crate) url: String,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) class: Option<Class>,
+}
+
+impl Url {
+    /// Creates an htsget ticket URL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::ticket::Url;
+    /// let url = Url::new("https://localhost/sample.bam");
+    /// ```
+    pub fn new<S>(url: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            url: url.into(),
+            headers: Vec::new(),
+            class: None,
+        }
+    }
+
+    /// Returns the URL.
+    ///
+    /// This is either an `https` URL or a `data` URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::ticket::Url;
+    /// let url = Url::new("https://localhost/sample.bam");
+    /// assert_eq!(url.url(), "https://localhost/sample.bam");
+    /// ```
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the headers to send with the request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::ticket::Url;
+    /// let url = Url::new("https://localhost/sample.bam");
+    /// assert!(url.headers().is_empty());
+    /// ```
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Returns the class of data the URL returns, if set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::ticket::Url;
+    /// let url = Url::new("https://localhost/sample.bam");
+    /// assert!(url.class().is_none());
+    /// ```
+    pub fn class(&self) -> Option<Class> {
+        self.class
+    }
+}
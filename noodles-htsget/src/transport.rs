@@ -0,0 +1,112 @@
+//! HTTP transport and response.
+
+use std::io;
+
+/// An HTTP transport.
+///
+/// This is used by a [`crate::Client`] to make HTTP GET requests. It is left to the application
+/// to implement using its HTTP library of choice.
+pub trait Transport {
+    /// Sends a GET request to the given URL with the given headers and returns the response body.
+    ///
+    /// Implementations must return an error if the response status is not successful.
+    fn get(&self, url: &str, headers: &[(String, String)]) -> io::Result<Vec<u8>>;
+
+    /// Sends a GET request to the given URL with the given headers and returns the response.
+    ///
+    /// Unlike [`Self::get`], this returns the response status and headers, and implementations
+    /// must not return an error for an unsuccessful status. This is required by the range reader
+    /// to validate partial content responses.
+    ///
+    /// The default implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    fn get_response(&self, url: &str, headers: &[(String, String)]) -> io::Result<Response> {
+        let _ = (url, headers);
+
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "transport does not return response status and headers",
+        ))
+    }
+}
+
+impl<T> Transport for &T
+where
+    T: Transport + ?Sized,
+{
+    fn get(&self, url: &str, headers: &[(String, String)]) -> io::Result<Vec<u8>> {
+        (**self).get(url, headers)
+    }
+
+    fn get_response(&self, url: &str, headers: &[(String, String)]) -> io::Result<Response> {
+        (**self).get_response(url, headers)
+    }
+}
+
+/// An HTTP response.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    /// Creates an HTTP response.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::transport::Response;
+    /// let response = Response::new(200, Vec::new(), b"noodles".to_vec());
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    pub fn new(status: u16, headers: Vec<(String, String)>, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    /// Returns the status code.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the headers.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Returns the value of the first header with the given name.
+    ///
+    /// Header names are compared case-insensitively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::transport::Response;
+    ///
+    /// let headers = vec![(String::from("Content-Range"), String::from("bytes 0-3/8"))];
+    /// let response = Response::new(206, headers, b"nood".to_vec());
+    ///
+    /// assert_eq!(response.header("content-range"), Some("bytes 0-3/8"));
+    /// assert!(response.header("Content-Type").is_none());
+    /// ```
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the body.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Returns the body, consuming the response.
+    pub fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
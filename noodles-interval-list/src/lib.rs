@@ -0,0 +1,37 @@
+#![warn(missing_docs)]
+
+//! **noodles-interval-list** handles the reading and writing of the Picard interval list format.
+//!
+//! An interval list has two sections: a SAM header and a list of intervals. The header holds
+//! the reference sequence dictionary the intervals are defined against. Each interval is a
+//! tab-delimited line with a reference sequence name, a 1-based inclusive start and end, a strand,
+//! and a name.
+//!
+//! # Examples
+//!
+//! ## Read all intervals from a file as regions
+//!
+//! ```no_run
+//! # use std::{fs::File, io::{self, BufReader}};
+//! use noodles_core::Region;
+//! use noodles_interval_list as interval_list;
+//!
+//! let mut reader = File::open("targets.interval_list")
+//!     .map(BufReader::new)
+//!     .map(interval_list::Reader::new)?;
+//!
+//! reader.read_header()?;
+//!
+//! for result in reader.records() {
+//!     let record = result?;
+//!     let region = Region::from(record);
+//!     println!("{}", region);
+//! }
+//! # Ok::<(), io::Error>(())
+//! ```
+
+mod reader;
+pub mod record;
+mod writer;
+
+pub use self::{reader::Reader, record::Record, writer::Writer};
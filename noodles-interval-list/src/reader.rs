@@ -0,0 +1,267 @@
+mod records;
+
+pub use self::records::Records;
+
+use std::io::{self, BufRead};
+
+const HEADER_PREFIX: u8 = b'@';
+const LINE_FEED: char = '\n';
+const CARRIAGE_RETURN: char = '\r';
+
+/// An interval list reader.
+///
+/// An interval list starts with a SAM header, where each header line is prefixed with an `@`
+/// (at sign). The intervals follow the header until EOF.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_interval_list as interval_list;
+///
+/// let data = b"@HD\tVN:1.6
+/// @SQ\tSN:sq0\tLN:21
+/// sq0\t8\t13\t+\t.
+/// ";
+///
+/// let mut reader = interval_list::Reader::new(&data[..]);
+/// reader.read_header()?;
+///
+/// for result in reader.records() {
+///     let record = result?;
+///     println!("{}", record);
+/// }
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct Reader<R> {
+    inner: R,
+}
+
+impl<R> Reader<R>
+where
+    R: BufRead,
+{
+    /// Creates an interval list reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_interval_list as interval_list;
+    /// let data = [];
+    /// let reader = interval_list::Reader::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_interval_list as interval_list;
+    /// let data = [];
+    /// let reader = interval_list::Reader::new(&data[..]);
+    /// assert!(reader.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Unwraps and returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_interval_list as interval_list;
+    /// let data = [];
+    /// let reader = interval_list::Reader::new(&data[..]);
+    /// assert!(reader.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads the raw SAM header.
+    ///
+    /// The position of the stream is expected to be at the start.
+    ///
+    /// This returns the raw SAM header as a [`String`]. It can subsequently be parsed as a
+    /// [`noodles_sam::Header`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_interval_list as interval_list;
+    ///
+    /// let data = b"@HD\tVN:1.6
+    /// @SQ\tSN:sq0\tLN:21
+    /// sq0\t8\t13\t+\t.
+    /// ";
+    ///
+    /// let mut reader = interval_list::Reader::new(&data[..]);
+    /// let header = reader.read_header()?;
+    ///
+    /// assert_eq!(header, "@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:21\n");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_header(&mut self) -> io::Result<String> {
+        read_header(&mut self.inner)
+    }
+
+    /// Reads a single raw interval list record.
+    ///
+    /// This reads from the underlying stream until a newline is reached and appends it to the
+    /// given buffer, sans the final newline character.
+    ///
+    /// The stream is expected to be directly after the header or at the start of another record.
+    ///
+    /// If successful, the number of bytes read is returned. If the number of bytes read is 0, the
+    /// stream reached EOF.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_interval_list as interval_list;
+    ///
+    /// let data = b"@HD\tVN:1.6
+    /// sq0\t8\t13\t+\t.
+    /// ";
+    ///
+    /// let mut reader = interval_list::Reader::new(&data[..]);
+    /// reader.read_header()?;
+    ///
+    /// let mut buf = String::new();
+    /// reader.read_record(&mut buf)?;
+    /// assert_eq!(buf, "sq0\t8\t13\t+\t.");
+    ///
+    /// assert_eq!(reader.read_record(&mut buf)?, 0);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_record(&mut self, buf: &mut String) -> io::Result<usize> {
+        read_line(&mut self.inner, buf)
+    }
+
+    /// Returns an iterator over records starting from the current stream position.
+    ///
+    /// The stream is expected to be directly after the header or at the start of another record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_core::Region;
+    /// use noodles_interval_list as interval_list;
+    ///
+    /// let data = b"@HD\tVN:1.6
+    /// sq0\t8\t13\t+\t.
+    /// sq0\t21\t34\t-\tt1
+    /// ";
+    ///
+    /// let mut reader = interval_list::Reader::new(&data[..]);
+    /// reader.read_header()?;
+    ///
+    /// let regions: Vec<_> = reader
+    ///     .records()
+    ///     .map(|result| result.map(Region::from))
+    ///     .collect::<io::Result<_>>()?;
+    ///
+    /// assert_eq!(
+    ///     regions,
+    ///     [Region::mapped("sq0", 8..=13), Region::mapped("sq0", 21..=34)]
+    /// );
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn records(&mut self) -> Records<'_, R> {
+        Records::new(self)
+    }
+}
+
+fn read_header<R>(reader: &mut R) -> io::Result<String>
+where
+    R: BufRead,
+{
+    let mut header_buf = String::new();
+    let mut buf = String::new();
+
+    loop {
+        let src = reader.fill_buf()?;
+
+        if src.first().map(|&b| b != HEADER_PREFIX).unwrap_or(true) {
+            break;
+        }
+
+        buf.clear();
+        reader.read_line(&mut buf)?;
+        header_buf.push_str(&buf);
+    }
+
+    Ok(header_buf)
+}
+
+fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
+where
+    R: BufRead,
+{
+    match reader.read_line(buf) {
+        Ok(0) => Ok(0),
+        Ok(n) => {
+            if buf.ends_with(LINE_FEED) {
+                buf.pop();
+
+                if buf.ends_with(CARRIAGE_RETURN) {
+                    buf.pop();
+                }
+            }
+
+            Ok(n)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_header() -> io::Result<()> {
+        let data = b"@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:21\nsq0\t8\t13\t+\t.\n";
+        let mut reader = &data[..];
+        assert_eq!(
+            read_header(&mut reader)?,
+            "@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:21\n"
+        );
+        assert_eq!(reader, b"sq0\t8\t13\t+\t.\n");
+
+        let data = b"sq0\t8\t13\t+\t.\n";
+        let mut reader = &data[..];
+        assert!(read_header(&mut reader)?.is_empty());
+
+        let data = b"";
+        let mut reader = &data[..];
+        assert!(read_header(&mut reader)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_line() -> io::Result<()> {
+        fn t(buf: &mut String, mut reader: &[u8], expected: &str) -> io::Result<()> {
+            buf.clear();
+            read_line(&mut reader, buf)?;
+            assert_eq!(buf, expected);
+            Ok(())
+        }
+
+        let mut buf = String::new();
+
+        t(&mut buf, b"noodles\n", "noodles")?;
+        t(&mut buf, b"noodles\r\n", "noodles")?;
+        t(&mut buf, b"noodles", "noodles")?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,436 @@
+//! Interval list record and fields.
+
+pub mod strand;
+
+pub use self::strand::Strand;
+
+use std::{error, fmt, num, ops::Bound, str::FromStr};
+
+use noodles_core::Region;
+
+const DELIMITER: char = '\t';
+const MISSING_FIELD: &str = ".";
+const FIELD_COUNT: usize = 5;
+
+/// An interval list record.
+///
+/// The start and end positions are 1-based and inclusive.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+    reference_sequence_name: String,
+    start: i32,
+    end: i32,
+    strand: Strand,
+    name: Option<String>,
+}
+
+impl Record {
+    /// Creates an interval list record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_interval_list::{self as interval_list, record::Strand};
+    /// let record = interval_list::Record::new("sq0", 8, 13, Strand::Forward, None);
+    /// ```
+    pub fn new<S>(
+        reference_sequence_name: S,
+        start: i32,
+        end: i32,
+        strand: Strand,
+        name: Option<String>,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            reference_sequence_name: reference_sequence_name.into(),
+            start,
+            end,
+            strand,
+            name,
+        }
+    }
+
+    /// Returns the reference sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_interval_list::{self as interval_list, record::Strand};
+    /// let record = interval_list::Record::new("sq0", 8, 13, Strand::Forward, None);
+    /// assert_eq!(record.reference_sequence_name(), "sq0");
+    /// ```
+    pub fn reference_sequence_name(&self) -> &str {
+        &self.reference_sequence_name
+    }
+
+    /// Returns the start position (1-based).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_interval_list::{self as interval_list, record::Strand};
+    /// let record = interval_list::Record::new("sq0", 8, 13, Strand::Forward, None);
+    /// assert_eq!(record.start(), 8);
+    /// ```
+    pub fn start(&self) -> i32 {
+        self.start
+    }
+
+    /// Returns the end position (1-based, inclusive).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_interval_list::{self as interval_list, record::Strand};
+    /// let record = interval_list::Record::new("sq0", 8, 13, Strand::Forward, None);
+    /// assert_eq!(record.end(), 13);
+    /// ```
+    pub fn end(&self) -> i32 {
+        self.end
+    }
+
+    /// Returns the strand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_interval_list::{self as interval_list, record::Strand};
+    /// let record = interval_list::Record::new("sq0", 8, 13, Strand::Reverse, None);
+    /// assert_eq!(record.strand(), Strand::Reverse);
+    /// ```
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Returns the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_interval_list::{self as interval_list, record::Strand};
+    ///
+    /// let record = interval_list::Record::new("sq0", 8, 13, Strand::Forward, None);
+    /// assert!(record.name().is_none());
+    ///
+    /// let record = interval_list::Record::new(
+    ///     "sq0",
+    ///     8,
+    ///     13,
+    ///     Strand::Forward,
+    ///     Some(String::from("target0")),
+    /// );
+    /// assert_eq!(record.name(), Some("target0"));
+    /// ```
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}{}{}{}{}{}",
+            self.reference_sequence_name,
+            DELIMITER,
+            self.start,
+            DELIMITER,
+            self.end,
+            DELIMITER,
+            self.strand,
+            DELIMITER
+        )?;
+
+        f.write_str(self.name().unwrap_or(MISSING_FIELD))
+    }
+}
+
+/// An error returned when a raw interval list record fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The number of fields is invalid.
+    InvalidFieldCount(usize),
+    /// The reference sequence name is invalid.
+    InvalidReferenceSequenceName,
+    /// The start position is invalid.
+    InvalidStart(num::ParseIntError),
+    /// The end position is invalid.
+    InvalidEnd(num::ParseIntError),
+    /// The interval is invalid.
+    ///
+    /// The start must be >= 1 and <= end + 1.
+    InvalidInterval(i32, i32),
+    /// The strand is invalid.
+    InvalidStrand(strand::ParseError),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::InvalidFieldCount(n) => {
+                write!(
+                    f,
+                    "invalid field count: expected {}, got {}",
+                    FIELD_COUNT, n
+                )
+            }
+            Self::InvalidReferenceSequenceName => f.write_str("invalid reference sequence name"),
+            Self::InvalidStart(e) => write!(f, "invalid start: {}", e),
+            Self::InvalidEnd(e) => write!(f, "invalid end: {}", e),
+            Self::InvalidInterval(start, end) => write!(f, "invalid interval: {}-{}", start, end),
+            Self::InvalidStrand(e) => write!(f, "invalid strand: {}", e),
+        }
+    }
+}
+
+impl FromStr for Record {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let fields: Vec<_> = s.split(DELIMITER).collect();
+
+        if fields.len() != FIELD_COUNT {
+            return Err(ParseError::InvalidFieldCount(fields.len()));
+        }
+
+        let reference_sequence_name = fields[0];
+
+        if reference_sequence_name.is_empty() {
+            return Err(ParseError::InvalidReferenceSequenceName);
+        }
+
+        let start = fields[1].parse().map_err(ParseError::InvalidStart)?;
+        let end = fields[2].parse().map_err(ParseError::InvalidEnd)?;
+
+        if !is_valid_interval(start, end) {
+            return Err(ParseError::InvalidInterval(start, end));
+        }
+
+        let strand = fields[3].parse().map_err(ParseError::InvalidStrand)?;
+
+        let name = match fields[4] {
+            MISSING_FIELD => None,
+            t => Some(t.into()),
+        };
+
+        Ok(Self::new(reference_sequence_name, start, end, strand, name))
+    }
+}
+
+/// Converts an interval list record to a region.
+///
+/// An empty interval, i.e., end = start - 1, is converted to an empty range (`start..start`).
+impl From<Record> for Region {
+    fn from(record: Record) -> Self {
+        if record.end < record.start {
+            Self::mapped(record.reference_sequence_name, record.start..record.start)
+        } else {
+            Self::mapped(record.reference_sequence_name, record.start..=record.end)
+        }
+    }
+}
+
+/// An error returned when a region fails to convert to an interval list record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TryFromRegionError {
+    /// The region is not mapped.
+    ///
+    /// Only mapped regions can be converted.
+    Unmapped,
+    /// The end position is unbounded.
+    ///
+    /// The end position must be known to be written as an interval.
+    UnboundedEnd,
+    /// The interval is invalid.
+    InvalidInterval(i32, i32),
+}
+
+impl error::Error for TryFromRegionError {}
+
+impl fmt::Display for TryFromRegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unmapped => f.write_str("region is not mapped"),
+            Self::UnboundedEnd => f.write_str("unbounded end"),
+            Self::InvalidInterval(start, end) => write!(f, "invalid interval: {}-{}", start, end),
+        }
+    }
+}
+
+/// Converts a region to an interval list record.
+///
+/// The record is given a forward strand and no name.
+impl TryFrom<Region> for Record {
+    type Error = TryFromRegionError;
+
+    fn try_from(region: Region) -> Result<Self, Self::Error> {
+        let mapped = region.as_mapped().ok_or(TryFromRegionError::Unmapped)?;
+
+        let end = match mapped.end() {
+            Bound::Included(n) => n,
+            Bound::Excluded(n) => n
+                .checked_sub(1)
+                .ok_or(TryFromRegionError::InvalidInterval(n, n))?,
+            Bound::Unbounded => return Err(TryFromRegionError::UnboundedEnd),
+        };
+
+        let start = match mapped.start() {
+            Bound::Included(n) => n,
+            Bound::Excluded(n) => n
+                .checked_add(1)
+                .ok_or(TryFromRegionError::InvalidInterval(n, end))?,
+            Bound::Unbounded => 1,
+        };
+
+        if !is_valid_interval(start, end) {
+            return Err(TryFromRegionError::InvalidInterval(start, end));
+        }
+
+        Ok(Self::new(mapped.name(), start, end, Strand::Forward, None))
+    }
+}
+
+// Returns whether the start is a valid position and the interval is not inverted.
+//
+// An empty interval, i.e., end = start - 1, is allowed.
+fn is_valid_interval(start: i32, end: i32) -> bool {
+    start >= 1 && start - 1 <= end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let record = Record::new("sq0", 8, 13, Strand::Forward, None);
+        assert_eq!(record.to_string(), "sq0\t8\t13\t+\t.");
+
+        let record = Record::new("sq1", 21, 34, Strand::Reverse, Some(String::from("t0")));
+        assert_eq!(record.to_string(), "sq1\t21\t34\t-\tt0");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "sq0\t8\t13\t+\t.".parse(),
+            Ok(Record::new("sq0", 8, 13, Strand::Forward, None))
+        );
+        assert_eq!(
+            "sq1\t21\t34\t-\tt0".parse(),
+            Ok(Record::new(
+                "sq1",
+                21,
+                34,
+                Strand::Reverse,
+                Some(String::from("t0"))
+            ))
+        );
+        assert_eq!(
+            "sq0\t8\t7\t+\t.".parse(),
+            Ok(Record::new("sq0", 8, 7, Strand::Forward, None))
+        );
+        assert_eq!(
+            "sq0\t8\t2147483647\t+\t.".parse(),
+            Ok(Record::new("sq0", 8, i32::MAX, Strand::Forward, None))
+        );
+
+        assert_eq!("".parse::<Record>(), Err(ParseError::Empty));
+        assert_eq!(
+            "sq0\t8\t13".parse::<Record>(),
+            Err(ParseError::InvalidFieldCount(3))
+        );
+        assert_eq!(
+            "\t8\t13\t+\t.".parse::<Record>(),
+            Err(ParseError::InvalidReferenceSequenceName)
+        );
+        assert!(matches!(
+            "sq0\t.\t13\t+\t.".parse::<Record>(),
+            Err(ParseError::InvalidStart(_))
+        ));
+        assert!(matches!(
+            "sq0\t8\t.\t+\t.".parse::<Record>(),
+            Err(ParseError::InvalidEnd(_))
+        ));
+        assert_eq!(
+            "sq0\t13\t8\t+\t.".parse::<Record>(),
+            Err(ParseError::InvalidInterval(13, 8))
+        );
+        assert_eq!(
+            "sq0\t0\t8\t+\t.".parse::<Record>(),
+            Err(ParseError::InvalidInterval(0, 8))
+        );
+        assert!(matches!(
+            "sq0\t8\t13\t.\t.".parse::<Record>(),
+            Err(ParseError::InvalidStrand(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_record_for_region() {
+        let record = Record::new("sq0", 8, 13, Strand::Reverse, Some(String::from("t0")));
+        assert_eq!(Region::from(record), Region::mapped("sq0", 8..=13));
+
+        let record = Record::new("sq0", 8, 7, Strand::Forward, None);
+        let region = Region::from(record.clone());
+        assert_eq!(region, Region::mapped("sq0", 8..8));
+        assert_eq!(Record::try_from(region), Ok(record));
+    }
+
+    #[test]
+    fn test_try_from_region_for_record() {
+        assert_eq!(
+            Record::try_from(Region::mapped("sq0", 8..=13)),
+            Ok(Record::new("sq0", 8, 13, Strand::Forward, None))
+        );
+        assert_eq!(
+            Record::try_from(Region::mapped("sq0", ..13)),
+            Ok(Record::new("sq0", 1, 12, Strand::Forward, None))
+        );
+        assert_eq!(
+            Record::try_from(Region::mapped("sq0", 8..)),
+            Err(TryFromRegionError::UnboundedEnd)
+        );
+        assert_eq!(
+            Record::try_from(Region::Unmapped),
+            Err(TryFromRegionError::Unmapped)
+        );
+        assert_eq!(
+            Record::try_from(Region::All),
+            Err(TryFromRegionError::Unmapped)
+        );
+        assert_eq!(
+            Record::try_from(Region::mapped("sq0", 8..8)),
+            Ok(Record::new("sq0", 8, 7, Strand::Forward, None))
+        );
+        assert_eq!(
+            Record::try_from(Region::mapped(
+                "sq0",
+                (Bound::Excluded(i32::MAX), Bound::Included(i32::MAX))
+            )),
+            Err(TryFromRegionError::InvalidInterval(i32::MAX, i32::MAX))
+        );
+        assert_eq!(
+            Record::try_from(Region::mapped("sq0", ..i32::MIN)),
+            Err(TryFromRegionError::InvalidInterval(i32::MIN, i32::MIN))
+        );
+        assert_eq!(
+            Record::try_from(Region::mapped(
+                "sq0",
+                (Bound::Included(13), Bound::Included(8))
+            )),
+            Err(TryFromRegionError::InvalidInterval(13, 8))
+        );
+    }
+}
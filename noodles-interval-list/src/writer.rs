@@ -0,0 +1,118 @@
+use std::io::{self, Write};
+
+use noodles_sam as sam;
+
+use super::Record;
+
+/// An interval list writer.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::Region;
+/// use noodles_interval_list as interval_list;
+/// use noodles_sam as sam;
+///
+/// let mut writer = interval_list::Writer::new(Vec::new());
+///
+/// let header = sam::Header::default();
+/// writer.write_header(&header)?;
+///
+/// let record = interval_list::Record::try_from(Region::mapped("sq0", 8..=13))
+///     .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+/// writer.write_record(&record)?;
+///
+/// assert_eq!(writer.get_ref(), b"sq0\t8\t13\t+\t.\n");
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates an interval list writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_interval_list as interval_list;
+    /// let writer = interval_list::Writer::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_interval_list as interval_list;
+    /// let writer = interval_list::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwraps and returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_interval_list as interval_list;
+    /// let writer = interval_list::Writer::new(Vec::new());
+    /// assert!(writer.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes a SAM header.
+    ///
+    /// The header should include the reference sequence dictionary the intervals are defined
+    /// against.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_interval_list as interval_list;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = interval_list::Writer::new(Vec::new());
+    ///
+    /// let header = sam::Header::builder().add_comment("noodles-interval-list").build();
+    /// writer.write_header(&header)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"@CO\tnoodles-interval-list\n");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        write!(self.inner, "{}", header)
+    }
+
+    /// Writes an interval list record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_interval_list::{self as interval_list, record::Strand};
+    ///
+    /// let mut writer = interval_list::Writer::new(Vec::new());
+    ///
+    /// let record = interval_list::Record::new("sq0", 8, 13, Strand::Reverse, None);
+    /// writer.write_record(&record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"sq0\t8\t13\t-\t.\n");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        writeln!(self.inner, "{}", record)
+    }
+}
@@ -0,0 +1,206 @@
+use std::{collections::HashMap, io};
+
+use md5::{Digest, Md5};
+use noodles_fasta::{
+    self as fasta,
+    record::{Definition, Sequence},
+};
+use noodles_htsget::Transport;
+use noodles_sam as sam;
+
+use super::Client;
+
+/// A refget FASTA repository adapter.
+///
+/// This resolves reference sequence names to checksums and fetches the sequences from a refget
+/// service. It allows, e.g., CRAM records to be decoded using only the `M5` fields of the SAM
+/// header.
+///
+/// When the checksum is an MD5 checksum, each fetched sequence is verified against it.
+pub struct Adapter<T> {
+    client: Client<T>,
+    checksums: HashMap<String, String>,
+}
+
+impl<T> Adapter<T>
+where
+    T: Transport,
+{
+    /// Creates a refget adapter with no known checksums.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_refget as refget;
+    ///
+    /// # struct HttpTransport;
+    /// # impl refget::Transport for HttpTransport {
+    /// #     fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+    /// #         unimplemented!()
+    /// #     }
+    /// # }
+    /// let client = refget::Client::new("https://localhost/refget", HttpTransport);
+    /// let adapter = refget::Adapter::new(client);
+    /// ```
+    pub fn new(client: Client<T>) -> Self {
+        Self {
+            client,
+            checksums: HashMap::new(),
+        }
+    }
+
+    /// Creates a refget adapter using the MD5 checksums of the reference sequences in a SAM
+    /// header.
+    ///
+    /// Reference sequences without an MD5 checksum (`M5`) are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_refget as refget;
+    /// use noodles_sam as sam;
+    ///
+    /// # struct HttpTransport;
+    /// # impl refget::Transport for HttpTransport {
+    /// #     fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+    /// #         unimplemented!()
+    /// #     }
+    /// # }
+    /// let header: sam::Header = "@SQ\tSN:sq0\tLN:8\tM5:d7eba311421bbc9d3ada44709dd61534"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let client = refget::Client::new("https://localhost/refget", HttpTransport);
+    /// let adapter = refget::Adapter::from_header(client, &header);
+    ///
+    /// assert_eq!(adapter.checksum("sq0"), Some("d7eba311421bbc9d3ada44709dd61534"));
+    /// ```
+    pub fn from_header(client: Client<T>, header: &sam::Header) -> Self {
+        let mut adapter = Self::new(client);
+
+        for (name, reference_sequence) in header.reference_sequences() {
+            if let Some(md5_checksum) = reference_sequence.md5_checksum() {
+                adapter.add_checksum(name.to_string(), md5_checksum.to_string());
+            }
+        }
+
+        adapter
+    }
+
+    /// Adds a checksum for the reference sequence with the given name.
+    pub fn add_checksum<N, C>(&mut self, name: N, checksum: C)
+    where
+        N: Into<String>,
+        C: Into<String>,
+    {
+        self.checksums.insert(name.into(), checksum.into());
+    }
+
+    /// Returns the checksum of the reference sequence with the given name.
+    pub fn checksum(&self, name: &str) -> Option<&str> {
+        self.checksums.get(name).map(|s| s.as_str())
+    }
+}
+
+impl<T> fasta::repository::Adapter for Adapter<T>
+where
+    T: Transport,
+{
+    fn get(&mut self, name: &str) -> Option<io::Result<fasta::Record>> {
+        let checksum = self.checksums.get(name)?;
+
+        let result = self
+            .client
+            .sequence(checksum.as_str())
+            .send()
+            .and_then(|sequence| {
+                validate_md5_checksum(checksum, &sequence)?;
+                Ok(fasta::Record::new(
+                    Definition::new(name, None),
+                    Sequence::from(sequence),
+                ))
+            });
+
+        Some(result)
+    }
+}
+
+// Validates the sequence against the checksum if it is an MD5 checksum, i.e., a 32-character
+// hexadecimal string.
+fn validate_md5_checksum(checksum: &str, sequence: &[u8]) -> io::Result<()> {
+    const MD5_CHECKSUM_LEN: usize = 32;
+
+    if checksum.len() != MD5_CHECKSUM_LEN || !checksum.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Ok(());
+    }
+
+    let digest = Md5::digest(sequence);
+    let actual: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+
+    if actual.eq_ignore_ascii_case(checksum) {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "reference sequence checksum mismatch: expected {}, got {}",
+                checksum, actual
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_fasta::repository::Adapter as _;
+
+    use super::*;
+
+    struct SequenceTransport;
+
+    impl Transport for SequenceTransport {
+        fn get(&self, url: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+            match url {
+                "https://localhost/refget/sequence/cc0af3a4fedb18378b4b57b98068e69f" => {
+                    Ok(b"ACGTACGT".to_vec())
+                }
+                "https://localhost/refget/sequence/d7eba311421bbc9d3ada44709dd61534" => {
+                    Ok(b"ACGTACGG".to_vec())
+                }
+                _ => Err(io::Error::from(io::ErrorKind::NotFound)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        let client = Client::new("https://localhost/refget", SequenceTransport);
+        let mut adapter = Adapter::new(client);
+        adapter.add_checksum("sq0", "cc0af3a4fedb18378b4b57b98068e69f");
+        adapter.add_checksum("sq1", "e1572c7e9d8a1e8a0b5e9d5ab9e2cd24");
+        adapter.add_checksum("sq2", "d7eba311421bbc9d3ada44709dd61534");
+
+        let record = adapter.get("sq0").transpose()?;
+
+        assert_eq!(
+            record,
+            Some(fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"ACGTACGT".to_vec())
+            ))
+        );
+
+        assert!(matches!(adapter.get("sq1"), Some(Err(_))));
+
+        assert!(matches!(
+            adapter.get("sq2"),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(adapter.get("sq3").is_none());
+
+        Ok(())
+    }
+}
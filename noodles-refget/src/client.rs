@@ -0,0 +1,95 @@
+//! refget client and request builder.
+
+mod request;
+
+pub use self::request::Request;
+
+use noodles_htsget::Transport;
+
+/// A refget client.
+pub struct Client<T> {
+    base_url: String,
+    transport: T,
+}
+
+impl<T> Client<T>
+where
+    T: Transport,
+{
+    /// Creates a refget client.
+    ///
+    /// The base URL is the URL of the refget service without the endpoint path, e.g.,
+    /// `https://localhost/refget`. A trailing slash is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_refget as refget;
+    ///
+    /// # struct HttpTransport;
+    /// # impl refget::Transport for HttpTransport {
+    /// #     fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+    /// #         unimplemented!()
+    /// #     }
+    /// # }
+    /// let client = refget::Client::new("https://localhost/refget/", HttpTransport);
+    /// assert_eq!(client.base_url(), "https://localhost/refget");
+    /// ```
+    pub fn new<S>(base_url: S, transport: T) -> Self
+    where
+        S: Into<String>,
+    {
+        let mut base_url = base_url.into();
+
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+
+        Self {
+            base_url,
+            transport,
+        }
+    }
+
+    /// Returns the base URL of the refget service.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the underlying transport.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Creates a request for the sequence with the given checksum.
+    ///
+    /// The checksum is either the hex-encoded MD5 or TRUNC512 digest of the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_refget as refget;
+    ///
+    /// # struct HttpTransport;
+    /// # impl refget::Transport for HttpTransport {
+    /// #     fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+    /// #         unimplemented!()
+    /// #     }
+    /// # }
+    /// let client = refget::Client::new("https://localhost/refget", HttpTransport);
+    /// let request = client.sequence("6aef897c3d6ff0c78aff06ac189178dd");
+    ///
+    /// assert_eq!(
+    ///     request.url(),
+    ///     "https://localhost/refget/sequence/6aef897c3d6ff0c78aff06ac189178dd"
+    /// );
+    /// ```
+    pub fn sequence<C>(&self, checksum: C) -> Request<'_, T>
+    where
+        C: Into<String>,
+    {
+        Request::new(self, checksum.into())
+    }
+}
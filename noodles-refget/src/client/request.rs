@@ -0,0 +1,129 @@
+use std::io;
+
+use noodles_htsget::Transport;
+
+use super::Client;
+
+const ACCEPT: &str = "text/vnd.ga4gh.refget.v1.0.0+plain";
+
+/// A refget sequence request.
+pub struct Request<'c, T> {
+    client: &'c Client<T>,
+    checksum: String,
+    start: Option<u64>,
+    end: Option<u64>,
+}
+
+impl<'c, T> Request<'c, T>
+where
+    T: Transport,
+{
+    pub(super) fn new(client: &'c Client<T>, checksum: String) -> Self {
+        Self {
+            client,
+            checksum,
+            start: None,
+            end: None,
+        }
+    }
+
+    /// Sets the 0-based start position of the subsequence (inclusive).
+    pub fn set_start(mut self, start: u64) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Sets the 0-based end position of the subsequence (exclusive).
+    pub fn set_end(mut self, end: u64) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Returns the request URL, including the query parameters.
+    pub fn url(&self) -> String {
+        let mut url = format!("{}/sequence/{}", self.client.base_url(), self.checksum);
+
+        let params = [("start", self.start), ("end", self.end)];
+
+        for (i, (key, value)) in params
+            .iter()
+            .filter_map(|(key, value)| value.map(|v| (key, v)))
+            .enumerate()
+        {
+            url.push(if i == 0 { '?' } else { '&' });
+            url.push_str(&format!("{}={}", key, value));
+        }
+
+        url
+    }
+
+    /// Sends the request and returns the sequence.
+    pub fn send(self) -> io::Result<Vec<u8>> {
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            if start > end {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "start position is greater than end position",
+                ));
+            }
+        }
+
+        let headers = [(String::from("Accept"), String::from(ACCEPT))];
+        self.client.transport().get(&self.url(), &headers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EchoTransport;
+
+    impl Transport for EchoTransport {
+        fn get(&self, url: &str, headers: &[(String, String)]) -> io::Result<Vec<u8>> {
+            assert_eq!(headers, [(String::from("Accept"), String::from(ACCEPT))]);
+            Ok(url.as_bytes().to_vec())
+        }
+    }
+
+    #[test]
+    fn test_url() {
+        let client = Client::new("https://localhost/refget", EchoTransport);
+
+        assert_eq!(
+            client.sequence("6aef897c").url(),
+            "https://localhost/refget/sequence/6aef897c"
+        );
+        assert_eq!(
+            client.sequence("6aef897c").set_start(7).url(),
+            "https://localhost/refget/sequence/6aef897c?start=7"
+        );
+        assert_eq!(
+            client.sequence("6aef897c").set_end(13).url(),
+            "https://localhost/refget/sequence/6aef897c?end=13"
+        );
+        assert_eq!(
+            client.sequence("6aef897c").set_start(7).set_end(13).url(),
+            "https://localhost/refget/sequence/6aef897c?start=7&end=13"
+        );
+    }
+
+    #[test]
+    fn test_send() -> io::Result<()> {
+        let client = Client::new("https://localhost/refget", EchoTransport);
+
+        assert_eq!(
+            client.sequence("6aef897c").send()?,
+            b"https://localhost/refget/sequence/6aef897c"
+        );
+
+        assert!(client
+            .sequence("6aef897c")
+            .set_start(13)
+            .set_end(7)
+            .send()
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+#![warn(missing_docs)]
+
+//! **noodles-refget** is a [refget] client.
+//!
+//! refget is a protocol to retrieve reference sequences by a checksum of the sequence, i.e., its
+//! MD5 or TRUNC512 digest, rather than its name.
+//!
+//! HTTP requests are made using a user-provided [`Transport`].
+//!
+//! [refget]: https://samtools.github.io/hts-specs/refget.html
+//!
+//! # Examples
+//!
+//! ## Fetch a subsequence
+//!
+//! ```no_run
+//! # use std::io;
+//! use noodles_refget as refget;
+//!
+//! # struct HttpTransport;
+//! # impl refget::Transport for HttpTransport {
+//! #     fn get(&self, _: &str, _: &[(String, String)]) -> io::Result<Vec<u8>> {
+//! #         unimplemented!()
+//! #     }
+//! # }
+//! let client = refget::Client::new("https://localhost/refget", HttpTransport);
+//!
+//! let sequence = client
+//!     .sequence("6aef897c3d6ff0c78aff06ac189178dd")
+//!     .set_start(7)
+//!     .set_end(13)
+//!     .send()?;
+//! # Ok::<_, io::Error>(())
+//! ```
+
+mod adapter;
+pub mod client;
+
+pub use self::{adapter::Adapter, client::Client};
+
+#[doc(no_inline)]
+pub use noodles_htsget::Transport;
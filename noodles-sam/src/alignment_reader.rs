@@ -0,0 +1,99 @@
+use std::{io, ops::RangeInclusive};
+
+use super::{AlignmentRecord, Header};
+
+/// An iterator over alignment records, as returned by an [`AlignmentReader`].
+pub type AlignmentRecords<'a> = Box<dyn Iterator<Item = io::Result<Box<dyn AlignmentRecord>>> + 'a>;
+
+/// An alignment reader.
+///
+/// This is implemented by the readers of alignment formats, e.g., SAM, BAM, and CRAM, and allows
+/// tools to be written once over any alignment format.
+pub trait AlignmentReader {
+    /// Reads and parses the alignment header.
+    ///
+    /// The stream is expected to be at the start of the file. After reading the header, the
+    /// stream is positioned at the start of the first record.
+    fn read_alignment_header(&mut self) -> io::Result<Header>;
+
+    /// Returns an iterator over alignment records starting from the current stream position.
+    fn alignment_records<'a>(&'a mut self, header: &'a Header) -> AlignmentRecords<'a>;
+
+    /// Returns an iterator over alignment records that intersect the given region.
+    ///
+    /// `interval` is 1-based and inclusive.
+    ///
+    /// The default implementation does not use an index. Records are read starting from the
+    /// current stream position, and records not on the given reference sequence or that do not
+    /// overlap the interval are skipped, i.e., the records of the entire stream are read. Readers
+    /// with an index, e.g., `bam::IndexedReader` and `cram::IndexedReader`, override this to only
+    /// read the records that may intersect the region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, AlignmentReader};
+    ///
+    /// let data = b"@HD\tVN:1.6
+    /// @SQ\tSN:sq0\tLN:34
+    /// r0\t0\tsq0\t1\t255\t4M\t*\t0\t0\tACGT\tNDLS
+    /// r1\t0\tsq0\t8\t255\t4M\t*\t0\t0\tACGT\tNDLS
+    /// ";
+    ///
+    /// let mut reader: Box<dyn AlignmentReader> = Box::new(sam::Reader::new(&data[..]));
+    /// let header = reader.read_alignment_header()?;
+    ///
+    /// let records: Vec<_> = reader
+    ///     .query_alignment_records(&header, "sq0", 5..=10)?
+    ///     .collect::<io::Result<_>>()?;
+    ///
+    /// assert_eq!(records.len(), 1);
+    /// assert_eq!(records[0].read_name(), Some(&b"r1"[..]));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn query_alignment_records<'a>(
+        &'a mut self,
+        header: &'a Header,
+        reference_sequence_name: &'a str,
+        interval: RangeInclusive<i32>,
+    ) -> io::Result<AlignmentRecords<'a>> {
+        let (start, end) = interval.into_inner();
+
+        Ok(Box::new(self.alignment_records(header).filter_map(
+            move |result| match result {
+                Ok(record) => {
+                    match intersects(header, record.as_ref(), reference_sequence_name, start, end) {
+                        Ok(true) => Some(Ok(record)),
+                        Ok(false) => None,
+                        Err(e) => Some(Err(e)),
+                    }
+                }
+                Err(e) => Some(Err(e)),
+            },
+        )))
+    }
+}
+
+fn intersects(
+    header: &Header,
+    record: &dyn AlignmentRecord,
+    reference_sequence_name: &str,
+    start: i32,
+    end: i32,
+) -> io::Result<bool> {
+    match record
+        .reference_sequence(header.reference_sequences())
+        .transpose()?
+    {
+        Some(reference_sequence) if **reference_sequence.name() == reference_sequence_name => {}
+        _ => return Ok(false),
+    }
+
+    let (record_start, record_end) = match (record.alignment_start(), record.alignment_end()) {
+        (Some(record_start), Some(record_end)) => (i32::from(record_start), i32::from(record_end?)),
+        _ => return Ok(false),
+    };
+
+    Ok(record_start <= end && start <= record_end)
+}
@@ -0,0 +1,138 @@
+use std::io;
+
+use super::{
+    header::{ReferenceSequence, ReferenceSequences},
+    record::{Cigar, Data, Flags, MappingQuality, Position, QualityScores, Sequence},
+};
+
+/// An alignment record.
+///
+/// This is implemented by the records of alignment formats, e.g., SAM, BAM, and CRAM, and allows
+/// tools to be written once over any alignment format.
+pub trait AlignmentRecord {
+    /// Returns the read name.
+    ///
+    /// This returns `None` if the read name is missing, i.e., `*`.
+    fn read_name(&self) -> Option<&[u8]>;
+
+    /// Returns the flags.
+    fn flags(&self) -> Flags;
+
+    /// Returns the associated reference sequence.
+    fn reference_sequence<'rs>(
+        &self,
+        reference_sequences: &'rs ReferenceSequences,
+    ) -> Option<io::Result<&'rs ReferenceSequence>>;
+
+    /// Returns the start position.
+    fn alignment_start(&self) -> Option<Position>;
+
+    /// Calculates the alignment span over the reference sequence.
+    fn alignment_span(&self) -> io::Result<u32>;
+
+    /// Calculates the end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     record::{cigar::{op::Kind, Op}, Cigar, Position},
+    ///     AlignmentRecord,
+    /// };
+    ///
+    /// let record = sam::Record::builder()
+    ///     .set_position(Position::try_from(8)?)
+    ///     .set_cigar(Cigar::from(vec![Op::new(Kind::Match, 5)]))
+    ///     .build()?;
+    ///
+    /// let actual = record.alignment_end().transpose()?;
+    /// let expected = Position::try_from(12).map(Some)?;
+    /// assert_eq!(actual, expected);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn alignment_end(&self) -> Option<io::Result<Position>> {
+        let start = self.alignment_start().map(i32::from)?;
+
+        let len = match self.alignment_span().and_then(|len| {
+            i32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }) {
+            Ok(len) => len,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let end = match start.checked_add(len - 1) {
+            Some(end) => end,
+            None => {
+                return Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid alignment end: position + span overflows",
+                )))
+            }
+        };
+
+        Some(Position::try_from(end).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+    }
+
+    /// Returns the mapping quality.
+    fn mapping_quality(&self) -> Option<MappingQuality>;
+
+    /// Returns the associated reference sequence of the mate.
+    fn mate_reference_sequence<'rs>(
+        &self,
+        reference_sequences: &'rs ReferenceSequences,
+    ) -> Option<io::Result<&'rs ReferenceSequence>>;
+
+    /// Returns the start position of the mate.
+    fn mate_alignment_start(&self) -> Option<Position>;
+
+    /// Returns the template length.
+    fn template_length(&self) -> i32;
+
+    /// Returns the CIGAR operations.
+    fn cigar(&self) -> io::Result<Cigar>;
+
+    /// Returns the read bases.
+    ///
+    /// This is empty if the sequence is missing, i.e., `*`.
+    fn sequence(&self) -> io::Result<Sequence>;
+
+    /// Returns the quality scores.
+    ///
+    /// This is empty if the quality scores are missing, i.e., `*`.
+    fn quality_scores(&self) -> io::Result<QualityScores>;
+
+    /// Returns the optional data fields.
+    fn data(&self) -> io::Result<Data>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::cigar::{op::Kind, Op};
+
+    #[test]
+    fn test_alignment_end() -> Result<(), Box<dyn std::error::Error>> {
+        let record = crate::Record::builder()
+            .set_position(Position::try_from(8)?)
+            .set_cigar(Cigar::from(vec![Op::new(Kind::Match, 5)]))
+            .build()?;
+
+        assert_eq!(
+            record.alignment_end().transpose()?,
+            Some(Position::try_from(12)?)
+        );
+
+        let record = crate::Record::builder()
+            .set_position(Position::try_from(i32::MAX - 1)?)
+            .set_cigar(Cigar::from(vec![Op::new(Kind::Match, 4)]))
+            .build()?;
+
+        assert!(matches!(
+            record.alignment_end(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
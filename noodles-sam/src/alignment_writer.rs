@@ -0,0 +1,27 @@
+use std::io;
+
+use super::{AlignmentRecord, Header};
+
+/// An alignment writer.
+///
+/// This is implemented by the writers of alignment formats, e.g., SAM, BAM, and CRAM, and allows
+/// tools to be written once over any alignment format. Records are given as alignment records of
+/// any format and converted to the output format by the writer.
+pub trait AlignmentWriter {
+    /// Writes an alignment header.
+    ///
+    /// This includes any format-specific preamble, e.g., a magic number or file definition.
+    fn write_alignment_header(&mut self, header: &Header) -> io::Result<()>;
+
+    /// Writes an alignment record.
+    fn write_alignment_record(
+        &mut self,
+        header: &Header,
+        record: &dyn AlignmentRecord,
+    ) -> io::Result<()>;
+
+    /// Finishes the output stream.
+    ///
+    /// This writes any pending data and format-specific trailers, e.g., an EOF marker.
+    fn finish(&mut self, header: &Header) -> io::Result<()>;
+}
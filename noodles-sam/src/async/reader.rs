@@ -1,7 +1,7 @@
 use futures::{stream, Stream};
 use tokio::io::{self, AsyncBufRead, AsyncBufReadExt};
 
-use crate::Record;
+use crate::{Error, Record};
 
 /// An async SAM reader.
 pub struct Reader<R> {
@@ -148,16 +148,21 @@ where
     /// ```
     pub fn records(&mut self) -> impl Stream<Item = io::Result<Record>> + '_ {
         Box::pin(stream::try_unfold(
-            (&mut self.inner, String::new()),
-            |(mut reader, mut buf)| async {
+            (&mut self.inner, String::new(), 0),
+            |(mut reader, mut buf, record_number)| async move {
                 buf.clear();
 
                 match read_line(&mut reader, &mut buf).await? {
                     0 => Ok(None),
                     _ => buf
                         .parse()
-                        .map(|record| Some((record, (reader, buf))))
-                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+                        .map(|record| Some((record, (reader, buf, record_number + 1))))
+                        .map_err(|e| {
+                            io::Error::from(Error::InvalidRecord {
+                                record_number: Some(record_number),
+                                source: e,
+                            })
+                        }),
                 }
             },
         ))
@@ -194,7 +199,7 @@ where
         reader.consume(len);
     }
 
-    String::from_utf8(header_buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    String::from_utf8(header_buf).map_err(|e| Error::InvalidHeaderText(e).into())
 }
 
 async fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
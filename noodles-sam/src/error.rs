@@ -0,0 +1,107 @@
+//! SAM errors.
+
+use std::{error, fmt, io, string};
+
+use super::{header, record};
+
+/// An error returned when SAM data fails to be read.
+///
+/// Readers continue to return [`std::io::Error`]s, but for invalid data, the error is wrapped in
+/// an `io::Error` with the kind [`std::io::ErrorKind::InvalidData`]. The cause can be inspected by
+/// downcasting the inner error.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_sam as sam;
+///
+/// let data = b"r0\t0\tsq0\t1\t255\t4M\t*\t0\t0\tACGT\tNDLS
+/// r1\t0\tsq0\t8\t256\t4M\t*\t0\t0\tACGT\tNDLS
+/// ";
+///
+/// let mut reader = sam::Reader::new(&data[..]);
+/// let mut records = reader.records();
+///
+/// assert!(records.next().transpose()?.is_some());
+///
+/// let e = records.next().transpose().unwrap_err();
+///
+/// assert!(matches!(
+///     e.get_ref().and_then(|e| e.downcast_ref::<sam::Error>()),
+///     Some(sam::Error::InvalidRecord { record_number: Some(1), .. })
+/// ));
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The header text is not valid UTF-8.
+    InvalidHeaderText(string::FromUtf8Error),
+    /// The header is invalid.
+    InvalidHeader(header::ParseError),
+    /// A record is invalid.
+    InvalidRecord {
+        /// The 0-based number of the record from where reading started, if known.
+        record_number: Option<u64>,
+        /// The cause.
+        source: record::ParseError,
+    },
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidHeaderText(e) => Some(e),
+            Self::InvalidHeader(e) => Some(e),
+            Self::InvalidRecord { source, .. } => Some(source),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeaderText(_) => f.write_str("invalid header text"),
+            Self::InvalidHeader(_) => f.write_str("invalid header"),
+            Self::InvalidRecord {
+                record_number,
+                source,
+            } => {
+                f.write_str("invalid record")?;
+
+                if let Some(n) = record_number {
+                    write!(f, " {}", n)?;
+                }
+
+                write!(f, ": {}", source)
+            }
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        Self::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let error = Error::InvalidRecord {
+            record_number: Some(8),
+            source: record::ParseError::MissingField(record::Field::Flags),
+        };
+        assert_eq!(error.to_string(), "invalid record 8: missing field: FLAG");
+
+        let error = Error::InvalidRecord {
+            record_number: None,
+            source: record::ParseError::MissingField(record::Field::Flags),
+        };
+        assert_eq!(error.to_string(), "invalid record: missing field: FLAG");
+    }
+}
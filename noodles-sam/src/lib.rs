@@ -34,14 +34,26 @@
 #[cfg(feature = "async")]
 mod r#async;
 
+mod alignment_reader;
+mod alignment_record;
+mod alignment_writer;
+mod error;
 pub mod header;
 pub mod reader;
 pub mod record;
-mod record_ext;
+#[cfg(feature = "serde")]
+mod serde;
 mod writer;
 
 pub use self::{
-    header::Header, reader::Reader, record::Record, record_ext::RecordExt, writer::Writer,
+    alignment_reader::{AlignmentReader, AlignmentRecords},
+    alignment_record::AlignmentRecord,
+    alignment_writer::AlignmentWriter,
+    error::Error,
+    header::Header,
+    reader::Reader,
+    record::Record,
+    writer::Writer,
 };
 
 #[cfg(feature = "async")]
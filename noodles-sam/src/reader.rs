@@ -8,6 +8,8 @@ use std::io::{self, BufRead, Read, Seek};
 
 use noodles_bgzf as bgzf;
 
+use super::{AlignmentReader, AlignmentRecord, AlignmentRecords, Error, Header};
+
 const LINE_FEED: char = '\n';
 const CARRIAGE_RETURN: char = '\r';
 
@@ -233,6 +235,23 @@ where
     }
 }
 
+impl<R> AlignmentReader for Reader<R>
+where
+    R: BufRead,
+{
+    fn read_alignment_header(&mut self) -> io::Result<Header> {
+        self.read_header()
+            .and_then(|s| s.parse().map_err(|e| Error::InvalidHeader(e).into()))
+    }
+
+    fn alignment_records<'a>(&'a mut self, _: &'a Header) -> AlignmentRecords<'a> {
+        Box::new(
+            self.records()
+                .map(|result| result.map(|record| Box::new(record) as Box<dyn AlignmentRecord>)),
+        )
+    }
+}
+
 fn read_header<R>(reader: &mut R) -> io::Result<String>
 where
     R: BufRead,
@@ -260,7 +279,7 @@ where
         reader.consume(len);
     }
 
-    String::from_utf8(header_buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    String::from_utf8(header_buf).map_err(|e| Error::InvalidHeaderText(e).into())
 }
 
 fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
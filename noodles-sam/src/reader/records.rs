@@ -1,6 +1,6 @@
 use std::io::{self, BufRead};
 
-use crate::Record;
+use crate::{Error, Record};
 
 use super::Reader;
 
@@ -10,6 +10,7 @@ use super::Reader;
 pub struct Records<'a, R> {
     inner: &'a mut Reader<R>,
     line_buf: String,
+    record_number: u64,
 }
 
 impl<'a, R> Records<'a, R>
@@ -20,6 +21,7 @@ where
         Self {
             inner,
             line_buf: String::new(),
+            record_number: 0,
         }
     }
 }
@@ -35,11 +37,17 @@ where
 
         match self.inner.read_record(&mut self.line_buf) {
             Ok(0) => None,
-            Ok(_) => Some(
-                self.line_buf
-                    .parse()
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
-            ),
+            Ok(_) => {
+                let record_number = self.record_number;
+                self.record_number += 1;
+
+                Some(self.line_buf.parse().map_err(|e| {
+                    io::Error::from(Error::InvalidRecord {
+                        record_number: Some(record_number),
+                        source: e,
+                    })
+                }))
+            }
             Err(e) => Some(Err(e)),
         }
     }
@@ -20,11 +20,14 @@ pub use self::{
     reference_sequence_name::ReferenceSequenceName, sequence::Sequence,
 };
 
-use std::{fmt, io, str::FromStr};
+use std::{
+    fmt, io,
+    str::{self, FromStr},
+};
 
 use super::{
     header::{ReferenceSequence, ReferenceSequences},
-    RecordExt,
+    AlignmentRecord,
 };
 
 pub(crate) const NULL_FIELD: &str = "*";
@@ -86,6 +89,75 @@ impl Record {
         Builder::default()
     }
 
+    /// Converts an alignment record to a SAM record.
+    ///
+    /// The reference sequence IDs of the record are resolved using the given reference
+    /// sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::ReferenceSequences};
+    ///
+    /// let record: sam::Record = "r0\t4\t*\t0\t255\t*\t*\t0\t0\tACGT\tNDLS".parse()?;
+    /// let reference_sequences = ReferenceSequences::default();
+    ///
+    /// let actual = sam::Record::try_from_alignment_record(&reference_sequences, &record)?;
+    /// assert_eq!(actual, record);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_alignment_record(
+        reference_sequences: &ReferenceSequences,
+        record: &dyn AlignmentRecord,
+    ) -> io::Result<Self> {
+        let mut builder = Self::builder()
+            .set_flags(record.flags())
+            .set_cigar(record.cigar()?)
+            .set_template_length(record.template_length())
+            .set_sequence(record.sequence()?)
+            .set_quality_scores(record.quality_scores()?)
+            .set_data(record.data()?);
+
+        if let Some(read_name) = record.read_name() {
+            let read_name = str::from_utf8(read_name)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            builder = builder.set_read_name(read_name);
+        }
+
+        if let Some(reference_sequence) =
+            record.reference_sequence(reference_sequences).transpose()?
+        {
+            builder = builder.set_reference_sequence_name(reference_sequence.name().clone());
+        }
+
+        if let Some(position) = record.alignment_start() {
+            builder = builder.set_position(position);
+        }
+
+        if let Some(mapping_quality) = record.mapping_quality() {
+            builder = builder.set_mapping_quality(mapping_quality);
+        }
+
+        if let Some(mate_reference_sequence) = record
+            .mate_reference_sequence(reference_sequences)
+            .transpose()?
+        {
+            builder =
+                builder.set_mate_reference_sequence_name(mate_reference_sequence.name().clone());
+        }
+
+        if let Some(mate_position) = record.mate_alignment_start() {
+            builder = builder.set_mate_position(mate_position);
+        }
+
+        builder
+            .build()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
     /// Returns the read name of this record.
     ///
     /// This is also called the query name.
@@ -604,13 +676,39 @@ impl Record {
     }
 }
 
-impl RecordExt for Record {
+impl AlignmentRecord for Record {
+    /// Returns the read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// let record = sam::Record::default();
+    /// assert!(AlignmentRecord::read_name(&record).is_none());
+    /// ```
+    fn read_name(&self) -> Option<&[u8]> {
+        self.read_name().map(|name| name.as_bytes())
+    }
+
+    /// Returns the flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::Flags, AlignmentRecord};
+    /// let record = sam::Record::default();
+    /// assert_eq!(AlignmentRecord::flags(&record), Flags::UNMAPPED);
+    /// ```
+    fn flags(&self) -> Flags {
+        self.flags()
+    }
+
     /// Returns the associated reference sequence.
     ///
     /// # Examples
     ///
     /// ```
-    /// use noodles_sam::{self as sam, header::ReferenceSequences, RecordExt};
+    /// use noodles_sam::{self as sam, header::ReferenceSequences, AlignmentRecord};
     /// let record = sam::Record::default();
     /// let reference_sequences = ReferenceSequences::default();
     /// assert!(record.reference_sequence(&reference_sequences).is_none());
@@ -627,7 +725,7 @@ impl RecordExt for Record {
     /// # Examples
     ///
     /// ```
-    /// use noodles_sam::{self as sam, RecordExt};
+    /// use noodles_sam::{self as sam, AlignmentRecord};
     /// let record = sam::Record::default();
     /// assert!(record.alignment_start().is_none());
     /// ```
@@ -641,7 +739,7 @@ impl RecordExt for Record {
     ///
     /// ```
     /// # use std::io;
-    /// use noodles_sam::{self as sam, RecordExt};
+    /// use noodles_sam::{self as sam, AlignmentRecord};
     /// let record = sam::Record::default();
     /// assert_eq!(record.alignment_span()?, 0);
     /// # Ok::<_, io::Error>(())
@@ -655,7 +753,7 @@ impl RecordExt for Record {
     /// # Examples
     ///
     /// ```
-    /// use noodles_sam::{self as sam, header::ReferenceSequences, RecordExt};
+    /// use noodles_sam::{self as sam, header::ReferenceSequences, AlignmentRecord};
     /// let record = sam::Record::default();
     /// let reference_sequences = ReferenceSequences::default();
     /// assert!(record.mate_reference_sequence(&reference_sequences).is_none());
@@ -666,6 +764,105 @@ impl RecordExt for Record {
     ) -> Option<io::Result<&'rs ReferenceSequence>> {
         get_reference_sequence(reference_sequences, self.mate_reference_sequence_name())
     }
+
+    /// Returns the mapping quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// let record = sam::Record::default();
+    /// assert!(AlignmentRecord::mapping_quality(&record).is_none());
+    /// ```
+    fn mapping_quality(&self) -> Option<MappingQuality> {
+        self.mapping_quality()
+    }
+
+    /// Returns the start position of the mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// let record = sam::Record::default();
+    /// assert!(record.mate_alignment_start().is_none());
+    /// ```
+    fn mate_alignment_start(&self) -> Option<Position> {
+        self.mate_position()
+    }
+
+    /// Returns the template length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// let record = sam::Record::default();
+    /// assert_eq!(AlignmentRecord::template_length(&record), 0);
+    /// ```
+    fn template_length(&self) -> i32 {
+        self.template_length()
+    }
+
+    /// Returns the CIGAR operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// let record = sam::Record::default();
+    /// assert!(AlignmentRecord::cigar(&record)?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn cigar(&self) -> io::Result<Cigar> {
+        Ok(self.cigar().clone())
+    }
+
+    /// Returns the read bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// let record = sam::Record::default();
+    /// assert!(AlignmentRecord::sequence(&record)?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn sequence(&self) -> io::Result<Sequence> {
+        Ok(self.sequence().clone())
+    }
+
+    /// Returns the quality scores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// let record = sam::Record::default();
+    /// assert!(AlignmentRecord::quality_scores(&record)?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn quality_scores(&self) -> io::Result<QualityScores> {
+        Ok(self.quality_scores().clone())
+    }
+
+    /// Returns the optional data fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// let record = sam::Record::default();
+    /// assert!(AlignmentRecord::data(&record)?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn data(&self) -> io::Result<Data> {
+        Ok(self.data().clone())
+    }
 }
 
 fn get_reference_sequence<'rs>(
@@ -251,13 +251,14 @@ impl Data {
         i.map(|j| {
             let removed_field = self.fields.swap_remove(j);
 
-            let swapped_field = &self.fields[j];
-            set_index(
-                &mut self.standard_field_indices,
-                &mut self.other_field_indices,
-                swapped_field.tag(),
-                j,
-            );
+            if let Some(swapped_field) = self.fields.get(j) {
+                set_index(
+                    &mut self.standard_field_indices,
+                    &mut self.other_field_indices,
+                    swapped_field.tag(),
+                    j,
+                );
+            }
 
             removed_field
         })
@@ -463,6 +464,19 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_remove() -> Result<(), ParseError> {
+        let rg = Field::new(Tag::ReadGroup, Value::String(String::from("rg0")));
+        let nh = Field::new(Tag::AlignmentHitCount, Value::Int(1));
+        let mut data = Data::try_from(vec![rg.clone(), nh.clone()])?;
+
+        assert_eq!(data.remove(Tag::AlignmentHitCount), Some(nh));
+        assert_eq!(data.remove(Tag::ReadGroup), Some(rg));
+        assert!(data.is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_from_str() -> Result<(), ParseError> {
         assert_eq!("".parse(), Ok(Data::default()));
@@ -0,0 +1,235 @@
+//! Serde implementations for the SAM header and records.
+//!
+//! A header is serialized as its raw SAM header text. A record is serialized as a struct with
+//! one field per SAM column, using the column names of the SAM specification (e.g., `qname`,
+//! `flag`, `rname`). Missing values are represented as `null`. Deserialization parses the
+//! fields as a SAM record, so the same validation as [`std::str::FromStr`] applies.
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Header, Record};
+
+const NULL_FIELD: &str = "*";
+
+impl Serialize for Header {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Header {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename = "Record")]
+struct RawRecord {
+    qname: Option<String>,
+    flag: u16,
+    rname: Option<String>,
+    pos: Option<i32>,
+    mapq: Option<u8>,
+    cigar: String,
+    rnext: Option<String>,
+    pnext: Option<i32>,
+    tlen: i32,
+    seq: String,
+    qual: String,
+    data: Vec<String>,
+}
+
+impl From<&Record> for RawRecord {
+    fn from(record: &Record) -> Self {
+        let data = if record.data().is_empty() {
+            Vec::new()
+        } else {
+            record
+                .data()
+                .to_string()
+                .split('\t')
+                .map(String::from)
+                .collect()
+        };
+
+        Self {
+            qname: record.read_name().map(|name| name.to_string()),
+            flag: u16::from(record.flags()),
+            rname: record
+                .reference_sequence_name()
+                .map(|name| name.to_string()),
+            pos: record.position().map(i32::from),
+            mapq: record.mapping_quality().map(u8::from),
+            cigar: record.cigar().to_string(),
+            rnext: record
+                .mate_reference_sequence_name()
+                .map(|name| name.to_string()),
+            pnext: record.mate_position().map(i32::from),
+            tlen: record.template_length(),
+            seq: record.sequence().to_string(),
+            qual: record.quality_scores().to_string(),
+            data,
+        }
+    }
+}
+
+impl RawRecord {
+    fn to_line(&self) -> String {
+        use crate::record::{mapping_quality, position};
+
+        let mut fields = vec![
+            self.qname.clone().unwrap_or_else(|| NULL_FIELD.into()),
+            self.flag.to_string(),
+            self.rname.clone().unwrap_or_else(|| NULL_FIELD.into()),
+            self.pos.unwrap_or(position::UNMAPPED).to_string(),
+            self.mapq.unwrap_or(mapping_quality::MISSING).to_string(),
+            self.cigar.clone(),
+            self.rnext.clone().unwrap_or_else(|| NULL_FIELD.into()),
+            self.pnext.unwrap_or(position::UNMAPPED).to_string(),
+            self.tlen.to_string(),
+            self.seq.clone(),
+            self.qual.clone(),
+        ];
+
+        fields.extend(self.data.iter().cloned());
+
+        fields.join("\t")
+    }
+}
+
+impl Serialize for Record {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        RawRecord::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Record {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw_record = RawRecord::deserialize(deserializer)?;
+        raw_record.to_line().parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_tokens, Token};
+
+    use super::*;
+
+    #[test]
+    fn test_header() -> Result<(), Box<dyn std::error::Error>> {
+        let header: Header = "@HD\tVN:1.6\tSO:coordinate\n@SQ\tSN:sq0\tLN:8\n".parse()?;
+
+        assert_tokens(
+            &header,
+            &[Token::Str(
+                "@HD\tVN:1.6\tSO:coordinate\n@SQ\tSN:sq0\tLN:8\n",
+            )],
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record = "r0\t99\tsq0\t1\t60\t4M\t=\t5\t8\tACGT\tNDLS\tNH:i:1".parse()?;
+
+        assert_tokens(
+            &record,
+            &[
+                Token::Struct {
+                    name: "Record",
+                    len: 12,
+                },
+                Token::Str("qname"),
+                Token::Some,
+                Token::Str("r0"),
+                Token::Str("flag"),
+                Token::U16(99),
+                Token::Str("rname"),
+                Token::Some,
+                Token::Str("sq0"),
+                Token::Str("pos"),
+                Token::Some,
+                Token::I32(1),
+                Token::Str("mapq"),
+                Token::Some,
+                Token::U8(60),
+                Token::Str("cigar"),
+                Token::Str("4M"),
+                Token::Str("rnext"),
+                Token::Some,
+                Token::Str("sq0"),
+                Token::Str("pnext"),
+                Token::Some,
+                Token::I32(5),
+                Token::Str("tlen"),
+                Token::I32(8),
+                Token::Str("seq"),
+                Token::Str("ACGT"),
+                Token::Str("qual"),
+                Token::Str("NDLS"),
+                Token::Str("data"),
+                Token::Seq { len: Some(1) },
+                Token::Str("NH:i:1"),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_with_missing_fields() {
+        assert_tokens(
+            &Record::default(),
+            &[
+                Token::Struct {
+                    name: "Record",
+                    len: 12,
+                },
+                Token::Str("qname"),
+                Token::None,
+                Token::Str("flag"),
+                Token::U16(4),
+                Token::Str("rname"),
+                Token::None,
+                Token::Str("pos"),
+                Token::None,
+                Token::Str("mapq"),
+                Token::None,
+                Token::Str("cigar"),
+                Token::Str("*"),
+                Token::Str("rnext"),
+                Token::None,
+                Token::Str("pnext"),
+                Token::None,
+                Token::Str("tlen"),
+                Token::I32(0),
+                Token::Str("seq"),
+                Token::Str("*"),
+                Token::Str("qual"),
+                Token::Str("*"),
+                Token::Str("data"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+}
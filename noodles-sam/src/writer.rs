@@ -1,6 +1,6 @@
 use std::io::{self, Write};
 
-use super::{Header, Record};
+use super::{AlignmentRecord, AlignmentWriter, Header, Record};
 
 /// A SAM writer.
 ///
@@ -132,6 +132,28 @@ where
     }
 }
 
+impl<W> AlignmentWriter for Writer<W>
+where
+    W: Write,
+{
+    fn write_alignment_header(&mut self, header: &Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_alignment_record(
+        &mut self,
+        header: &Header,
+        record: &dyn AlignmentRecord,
+    ) -> io::Result<()> {
+        let record = Record::try_from_alignment_record(header.reference_sequences(), record)?;
+        self.write_record(&record)
+    }
+
+    fn finish(&mut self, _: &Header) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::record::{
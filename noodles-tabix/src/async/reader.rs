@@ -4,7 +4,7 @@ use tokio::io::{self, AsyncRead, AsyncReadExt};
 
 use crate::{
     index::{reference_sequence::Bin, Header, ReferenceSequence, ReferenceSequenceNames},
-    Index,
+    Error, Index,
 };
 
 /// An async tabix reader.
@@ -68,7 +68,7 @@ where
     read_magic(reader).await?;
 
     let n_ref = reader.read_i32_le().await.and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidReferenceSequenceCount(e)))
     })?;
 
     let header = read_header(reader).await?;
@@ -102,10 +102,7 @@ where
     if magic == MAGIC_NUMBER {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "invalid tabix header",
-        ))
+        Err(Error::InvalidMagicNumber(magic).into())
     }
 }
 
@@ -115,16 +112,17 @@ where
 {
     use crate::index::header::Format;
 
-    let format = reader.read_i32_le().await.and_then(|n| {
-        Format::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-    })?;
+    let format = reader
+        .read_i32_le()
+        .await
+        .and_then(|n| Format::try_from(n).map_err(|e| io::Error::from(Error::InvalidFormat(e))))?;
 
     let col_seq = reader.read_i32_le().await.and_then(|i| {
-        usize::try_from(i).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(i).map_err(|e| io::Error::from(Error::InvalidColumnIndex(e)))
     })?;
 
     let col_beg = reader.read_i32_le().await.and_then(|i| {
-        usize::try_from(i).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(i).map_err(|e| io::Error::from(Error::InvalidColumnIndex(e)))
     })?;
 
     let col_end = reader.read_i32_le().await.and_then(|i| {
@@ -133,17 +131,16 @@ where
         } else {
             usize::try_from(i)
                 .map(Some)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                .map_err(|e| io::Error::from(Error::InvalidColumnIndex(e)))
         }
     })?;
 
-    let meta = reader
-        .read_i32_le()
-        .await
-        .and_then(|b| u8::try_from(b).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))?;
+    let meta = reader.read_i32_le().await.and_then(|b| {
+        u8::try_from(b).map_err(|e| io::Error::from(Error::InvalidLineCommentPrefix(e)))
+    })?;
 
     let skip = reader.read_i32_le().await.and_then(|b| {
-        u32::try_from(b).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        u32::try_from(b).map_err(|e| io::Error::from(Error::InvalidHeaderLineCount(e)))
     })?;
 
     Ok(Header::builder()
@@ -163,7 +160,8 @@ where
     use crate::reader::parse_names;
 
     let l_nm = reader.read_i32_le().await.and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(n)
+            .map_err(|e| io::Error::from(Error::InvalidReferenceSequenceNamesLength(e)))
     })?;
 
     let mut names = vec![0; l_nm];
@@ -204,9 +202,10 @@ where
 {
     use crate::index::reference_sequence::bin::METADATA_ID;
 
-    let n_bin = reader.read_i32_le().await.and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-    })?;
+    let n_bin = reader
+        .read_i32_le()
+        .await
+        .and_then(|n| usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidBinCount(e))))?;
 
     let mut bins = Vec::with_capacity(n_bin);
     let mut metadata = None;
@@ -231,7 +230,7 @@ where
     R: AsyncRead + Unpin,
 {
     let n_chunk = reader.read_i32_le().await.and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidChunkCount(e)))
     })?;
 
     let mut chunks = Vec::with_capacity(n_chunk);
@@ -266,7 +265,7 @@ where
     R: AsyncRead + Unpin,
 {
     let n_intv = reader.read_i32_le().await.and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidIntervalCount(e)))
     })?;
 
     let mut intervals = Vec::with_capacity(n_intv);
@@ -292,13 +291,7 @@ where
     let n_chunk = reader.read_u32_le().await?;
 
     if n_chunk != METADATA_CHUNK_COUNT {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!(
-                "invalid metadata pseudo-bin chunk count: expected {}, got {}",
-                METADATA_CHUNK_COUNT, n_chunk
-            ),
-        ));
+        return Err(Error::InvalidMetadataChunkCount(n_chunk).into());
     }
 
     let ref_beg = reader
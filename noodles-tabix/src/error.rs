@@ -0,0 +1,175 @@
+//! Tabix errors.
+
+use std::{error, fmt, io, num, str};
+
+use super::{
+    index::{header::format, reference_sequence::bin::METADATA_CHUNK_COUNT},
+    layout,
+};
+
+/// An error returned when a tabix index fails to be read or built.
+///
+/// Readers and indexers continue to return [`std::io::Error`]s, but for invalid data, the error
+/// is wrapped in an `io::Error` with the kind [`std::io::ErrorKind::InvalidData`]. The cause can
+/// be inspected by downcasting the inner error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The magic number is invalid.
+    InvalidMagicNumber([u8; 4]),
+    /// The number of reference sequences (`n_ref`) is invalid.
+    InvalidReferenceSequenceCount(num::TryFromIntError),
+    /// The format is invalid.
+    InvalidFormat(format::TryFromIntError),
+    /// A column index (`col_seq`, `col_beg`, or `col_end`) is invalid.
+    InvalidColumnIndex(num::TryFromIntError),
+    /// The line comment prefix (`meta`) is invalid.
+    InvalidLineCommentPrefix(num::TryFromIntError),
+    /// The number of header lines to skip (`skip`) is invalid.
+    InvalidHeaderLineCount(num::TryFromIntError),
+    /// The length of the reference sequence names (`l_nm`) is invalid.
+    InvalidReferenceSequenceNamesLength(num::TryFromIntError),
+    /// A reference sequence name is not valid UTF-8.
+    InvalidReferenceSequenceName(str::Utf8Error),
+    /// A reference sequence name is duplicated.
+    DuplicateReferenceSequenceName(String),
+    /// The number of bins (`n_bin`) is invalid.
+    InvalidBinCount(num::TryFromIntError),
+    /// The number of chunks (`n_chunk`) is invalid.
+    InvalidChunkCount(num::TryFromIntError),
+    /// The number of linear index intervals (`n_intv`) is invalid.
+    InvalidIntervalCount(num::TryFromIntError),
+    /// The number of chunks in the metadata pseudo-bin is invalid.
+    InvalidMetadataChunkCount(u32),
+    /// The interval of a line is invalid.
+    InvalidLine {
+        /// The 0-based line number, if known.
+        line_number: Option<usize>,
+        /// The cause.
+        source: layout::ParseError,
+    },
+    /// The lines of a reference sequence are not contiguous.
+    UngroupedReferenceSequenceName(String),
+    /// The lines of a reference sequence are not sorted by start position.
+    UnsortedStartPosition {
+        /// The reference sequence name.
+        reference_sequence_name: String,
+        /// The start position of the out-of-order line.
+        start: i32,
+    },
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidReferenceSequenceCount(e) => Some(e),
+            Self::InvalidFormat(e) => Some(e),
+            Self::InvalidColumnIndex(e) => Some(e),
+            Self::InvalidLineCommentPrefix(e) => Some(e),
+            Self::InvalidHeaderLineCount(e) => Some(e),
+            Self::InvalidReferenceSequenceNamesLength(e) => Some(e),
+            Self::InvalidReferenceSequenceName(e) => Some(e),
+            Self::InvalidBinCount(e) => Some(e),
+            Self::InvalidChunkCount(e) => Some(e),
+            Self::InvalidIntervalCount(e) => Some(e),
+            Self::InvalidLine { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagicNumber(actual) => {
+                write!(
+                    f,
+                    "invalid magic number: expected TBI\\x01, got {:?}",
+                    actual
+                )
+            }
+            Self::InvalidReferenceSequenceCount(_) => {
+                f.write_str("invalid reference sequence count")
+            }
+            Self::InvalidFormat(e) => write!(f, "invalid format: {}", e),
+            Self::InvalidColumnIndex(_) => f.write_str("invalid column index"),
+            Self::InvalidLineCommentPrefix(_) => f.write_str("invalid line comment prefix"),
+            Self::InvalidHeaderLineCount(_) => f.write_str("invalid header line count"),
+            Self::InvalidReferenceSequenceNamesLength(_) => {
+                f.write_str("invalid reference sequence names length")
+            }
+            Self::InvalidReferenceSequenceName(_) => f.write_str("invalid reference sequence name"),
+            Self::DuplicateReferenceSequenceName(name) => {
+                write!(f, "duplicate reference sequence name: {}", name)
+            }
+            Self::InvalidBinCount(_) => f.write_str("invalid bin count"),
+            Self::InvalidChunkCount(_) => f.write_str("invalid chunk count"),
+            Self::InvalidIntervalCount(_) => f.write_str("invalid interval count"),
+            Self::InvalidMetadataChunkCount(actual) => write!(
+                f,
+                "invalid metadata pseudo-bin chunk count: expected {}, got {}",
+                METADATA_CHUNK_COUNT, actual
+            ),
+            Self::InvalidLine {
+                line_number,
+                source,
+            } => {
+                f.write_str("invalid line")?;
+
+                if let Some(n) = line_number {
+                    write!(f, " {}", n)?;
+                }
+
+                write!(f, ": {}", source)
+            }
+            Self::UngroupedReferenceSequenceName(name) => {
+                write!(f, "reference sequence names are not grouped: {}", name)
+            }
+            Self::UnsortedStartPosition {
+                reference_sequence_name,
+                start,
+            } => write!(
+                f,
+                "lines are not sorted by start position: {}:{}",
+                reference_sequence_name, start
+            ),
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        Self::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(
+            Error::InvalidMagicNumber(*b"MThd").to_string(),
+            "invalid magic number: expected TBI\\x01, got [77, 84, 104, 100]"
+        );
+
+        assert_eq!(
+            Error::InvalidLine {
+                line_number: Some(8),
+                source: layout::ParseError::MissingStartPosition,
+            }
+            .to_string(),
+            "invalid line 8: missing start position"
+        );
+
+        assert_eq!(
+            Error::UnsortedStartPosition {
+                reference_sequence_name: String::from("sq0"),
+                start: 5,
+            }
+            .to_string(),
+            "lines are not sorted by start position: sq0:5"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! Indexed tab-delimited reader.
+
+mod query;
+
+pub use self::query::Query;
+
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_core::{region::Interval, Region};
+use noodles_csi::BinningIndex;
+
+use super::{Index, Layout};
+
+/// An indexed reader of a bgzipped, tab-delimited file.
+///
+/// The intervals of lines are parsed using a [`Layout`], which allows querying formats with
+/// arbitrary column layouts and coordinate conventions.
+pub struct IndexedReader<R> {
+    inner: bgzf::Reader<R>,
+    index: Index,
+    layout: Layout,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read,
+{
+    /// Creates an indexed reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let index = tabix::Index::default();
+    /// let layout = tabix::Layout::from(index.header().clone());
+    /// let reader = tabix::IndexedReader::new(bgzf::Reader::new(&[][..]), index, layout);
+    /// ```
+    pub fn new(inner: bgzf::Reader<R>, index: Index, layout: Layout) -> Self {
+        Self {
+            inner,
+            index,
+            layout,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let index = tabix::Index::default();
+    /// let layout = tabix::Layout::from(index.header().clone());
+    /// let reader = tabix::IndexedReader::new(bgzf::Reader::new(&[][..]), index, layout);
+    /// assert!(reader.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &bgzf::Reader<R> {
+        &self.inner
+    }
+
+    /// Returns the associated index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let index = tabix::Index::default();
+    /// let layout = tabix::Layout::from(index.header().clone());
+    /// let reader = tabix::IndexedReader::new(bgzf::Reader::new(&[][..]), index, layout);
+    /// assert!(reader.index().reference_sequence_names().is_empty());
+    /// ```
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Returns the layout used to parse intervals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let index = tabix::Index::default();
+    /// let layout = tabix::Layout::from(index.header().clone());
+    /// let reader = tabix::IndexedReader::new(bgzf::Reader::new(&[][..]), index, layout.clone());
+    /// assert_eq!(reader.layout(), &layout);
+    /// ```
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read + Seek,
+{
+    /// Returns an iterator over lines that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_core::Region;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let index = tabix::read("sample.tsv.gz.tbi")?;
+    /// let layout = tabix::Layout::from(index.header().clone());
+    /// let inner = File::open("sample.tsv.gz").map(bgzf::Reader::new)?;
+    /// let mut reader = tabix::IndexedReader::new(inner, index, layout);
+    ///
+    /// let region = Region::mapped("sq0", 8..=13);
+    ///
+    /// for result in reader.query(&region)? {
+    ///     let line = result?;
+    ///     println!("{}", line);
+    /// }
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn query(&mut self, region: &Region) -> io::Result<Query<'_, R>> {
+        let (reference_sequence_id, reference_sequence_name, interval) =
+            resolve_region(&self.index, region)?;
+
+        let chunks = self.index.query(reference_sequence_id, interval)?;
+
+        Ok(Query::new(
+            &mut self.inner,
+            &self.layout,
+            chunks,
+            reference_sequence_name,
+            interval,
+        ))
+    }
+}
+
+fn resolve_region(index: &Index, region: &Region) -> io::Result<(usize, String, Interval)> {
+    if let Some(r) = region.as_mapped() {
+        let i = index
+            .reference_sequence_names()
+            .get_index_of(r.name())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "region reference sequence does not exist in reference sequences: {:?}",
+                        region
+                    ),
+                )
+            })?;
+
+        Ok((i, r.name().into(), r.interval()))
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "region is not mapped",
+        ))
+    }
+}
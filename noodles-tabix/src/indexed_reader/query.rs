@@ -0,0 +1,163 @@
+use std::{
+    io::{self, BufRead, Read, Seek},
+    ops::{Bound, RangeBounds},
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::index::reference_sequence::bin::Chunk;
+
+use crate::{Error, Layout};
+
+enum State {
+    Seek,
+    Read(bgzf::VirtualPosition),
+    End,
+}
+
+/// An iterator over lines of an indexed reader that intersect a given region.
+///
+/// This is created by calling [`super::IndexedReader::query`].
+pub struct Query<'r, R>
+where
+    R: Read + Seek + 'r,
+{
+    reader: &'r mut bgzf::Reader<R>,
+    layout: &'r Layout,
+    chunks: Vec<Chunk>,
+    reference_sequence_name: String,
+    start: i32,
+    end: i32,
+    i: usize,
+    state: State,
+}
+
+impl<'r, R> Query<'r, R>
+where
+    R: Read + Seek,
+{
+    pub(super) fn new<B>(
+        reader: &'r mut bgzf::Reader<R>,
+        layout: &'r Layout,
+        chunks: Vec<Chunk>,
+        reference_sequence_name: String,
+        interval: B,
+    ) -> Self
+    where
+        B: RangeBounds<i32>,
+    {
+        let start = match interval.start_bound() {
+            Bound::Included(s) => *s,
+            Bound::Excluded(s) => *s + 1,
+            Bound::Unbounded => 1,
+        };
+
+        let end = match interval.end_bound() {
+            Bound::Included(e) => *e,
+            Bound::Excluded(e) => *e - 1,
+            Bound::Unbounded => i32::MAX,
+        };
+
+        Self {
+            reader,
+            layout,
+            chunks,
+            reference_sequence_name,
+            start,
+            end,
+            i: 0,
+            state: State::Seek,
+        }
+    }
+
+    fn next_chunk(&mut self) -> io::Result<Option<bgzf::VirtualPosition>> {
+        if self.i >= self.chunks.len() {
+            return Ok(None);
+        }
+
+        let chunk = self.chunks[self.i];
+        self.reader.seek(chunk.start())?;
+
+        self.i += 1;
+
+        Ok(Some(chunk.end()))
+    }
+
+    fn read_line(&mut self) -> Option<io::Result<String>> {
+        let mut buf = String::new();
+
+        match self.reader.read_line(&mut buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                if buf.ends_with('\n') {
+                    buf.pop();
+
+                    if buf.ends_with('\r') {
+                        buf.pop();
+                    }
+                }
+
+                Some(Ok(buf))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl<'r, R> Iterator for Query<'r, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.state {
+                State::Seek => {
+                    self.state = match self.next_chunk() {
+                        Ok(Some(chunk_end)) => State::Read(chunk_end),
+                        Ok(None) => State::End,
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                State::Read(chunk_end) => match self.read_line() {
+                    Some(result) => {
+                        if self.reader.virtual_position() >= chunk_end {
+                            self.state = State::Seek;
+                        }
+
+                        let line = match result {
+                            Ok(line) => line,
+                            Err(e) => return Some(Err(e)),
+                        };
+
+                        let (reference_sequence_name, start, end) =
+                            match self.layout.parse_interval(&line) {
+                                Ok(interval) => interval,
+                                Err(e) => {
+                                    return Some(Err(Error::InvalidLine {
+                                        line_number: None,
+                                        source: e,
+                                    }
+                                    .into()))
+                                }
+                            };
+
+                        if reference_sequence_name == self.reference_sequence_name
+                            && in_interval(start, end, self.start, self.end)
+                        {
+                            return Some(Ok(line));
+                        }
+                    }
+                    None => {
+                        self.state = State::Seek;
+                    }
+                },
+                State::End => return None,
+            }
+        }
+    }
+}
+
+fn in_interval(a_start: i32, a_end: i32, b_start: i32, b_end: i32) -> bool {
+    a_start <= b_end && b_start <= a_end
+}
@@ -0,0 +1,408 @@
+//! Tabix column layout and coordinate conventions.
+
+use std::{error, fmt, num};
+
+use super::index::{
+    header::{format::CoordinateSystem, Format},
+    Header,
+};
+
+const DELIMITER: char = '\t';
+
+/// The column layout and coordinate conventions of a tab-delimited format.
+///
+/// A layout describes how to read the genomic interval of a line: which columns hold the
+/// reference sequence name and positions (see [`Header`]); whether positions are 0-based or
+/// 1-based (see [`CoordinateSystem`]); and whether the end position is inclusive.
+///
+/// A tabix index header only records whether a format is 0-based with exclusive ends (BED) or
+/// 1-based with inclusive ends (GFF). Formats that use other conventions, e.g., 0-based starts
+/// with inclusive ends, can be described by overriding whether the end is inclusive. Intervals
+/// are normalized to 1-based, inclusive positions.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_tabix::{self as tabix, index::header::{format::CoordinateSystem, Format}};
+///
+/// // A 0-based, end-inclusive format with the interval in columns 2-4.
+/// let header = tabix::index::Header::builder()
+///     .set_format(Format::Generic(CoordinateSystem::Bed))
+///     .set_reference_sequence_name_index(2)
+///     .set_start_position_index(3)
+///     .set_end_position_index(Some(4))
+///     .build();
+///
+/// let layout = tabix::Layout::new(header).set_end_inclusive(true);
+///
+/// assert_eq!(layout.parse_interval("r0\tsq0\t7\t12"), Ok(("sq0", 8, 13)));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Layout {
+    header: Header,
+    is_end_inclusive: bool,
+}
+
+impl Layout {
+    /// Creates a layout from a tabix index header.
+    ///
+    /// Whether the end position is inclusive is derived from the coordinate system of the
+    /// header: exclusive for BED and inclusive for GFF.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::{self as tabix, index::header};
+    ///
+    /// let layout = tabix::Layout::new(header::Builder::bed().build());
+    /// assert!(!layout.is_end_inclusive());
+    ///
+    /// let layout = tabix::Layout::new(header::Builder::gff().build());
+    /// assert!(layout.is_end_inclusive());
+    /// ```
+    pub fn new(header: Header) -> Self {
+        let is_end_inclusive = header.format().coordinate_system() == CoordinateSystem::Gff;
+
+        Self {
+            header,
+            is_end_inclusive,
+        }
+    }
+
+    /// Sets whether the end position is inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::{self as tabix, index::header};
+    /// let layout = tabix::Layout::new(header::Builder::bed().build()).set_end_inclusive(true);
+    /// assert!(layout.is_end_inclusive());
+    /// ```
+    pub fn set_end_inclusive(mut self, is_end_inclusive: bool) -> Self {
+        self.is_end_inclusive = is_end_inclusive;
+        self
+    }
+
+    /// Returns the tabix index header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::{self as tabix, index::header};
+    /// let header = header::Builder::bed().build();
+    /// let layout = tabix::Layout::new(header.clone());
+    /// assert_eq!(layout.header(), &header);
+    /// ```
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns whether the end position is inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::{self as tabix, index::header};
+    /// let layout = tabix::Layout::new(header::Builder::vcf().build());
+    /// assert!(layout.is_end_inclusive());
+    /// ```
+    pub fn is_end_inclusive(&self) -> bool {
+        self.is_end_inclusive
+    }
+
+    /// Returns whether the line is a header line.
+    ///
+    /// A header line is either within the number of lines to skip or starts with the line
+    /// comment prefix. `line_number` is 0-based.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::{self as tabix, index::header};
+    /// let layout = tabix::Layout::new(header::Builder::vcf().build());
+    /// assert!(layout.is_header_line(0, "##fileformat=VCFv4.3"));
+    /// assert!(!layout.is_header_line(1, "sq0\t8\t.\tA\t.\t.\tPASS\t."));
+    /// ```
+    pub fn is_header_line(&self, line_number: usize, line: &str) -> bool {
+        line_number < self.header.line_skip_count() as usize
+            || line.as_bytes().first() == Some(&self.header.line_comment_prefix())
+    }
+
+    /// Parses the reference sequence name and interval of a line.
+    ///
+    /// The returned interval is 1-based and inclusive. If the layout does not have an end
+    /// position column, the end position is derived from the format: the length of the
+    /// reference bases for VCF, the alignment span of the CIGAR for SAM, and the start position
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::{self as tabix, index::header};
+    ///
+    /// let layout = tabix::Layout::new(header::Builder::bed().build());
+    /// assert_eq!(layout.parse_interval("sq0\t7\t13"), Ok(("sq0", 8, 13)));
+    ///
+    /// let layout = tabix::Layout::new(header::Builder::vcf().build());
+    /// assert_eq!(
+    ///     layout.parse_interval("sq0\t8\t.\tACGTA\t.\t.\tPASS\t."),
+    ///     Ok(("sq0", 8, 12))
+    /// );
+    /// ```
+    pub fn parse_interval<'a>(&self, line: &'a str) -> Result<(&'a str, i32, i32), ParseError> {
+        let fields: Vec<_> = line.split(DELIMITER).collect();
+
+        let reference_sequence_name =
+            get_field(&fields, self.header.reference_sequence_name_index())
+                .ok_or(ParseError::MissingReferenceSequenceName)?;
+
+        let raw_start: i32 = get_field(&fields, self.header.start_position_index())
+            .ok_or(ParseError::MissingStartPosition)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidStartPosition))?;
+
+        let is_zero_based = self.header.format().coordinate_system() == CoordinateSystem::Bed;
+
+        let start = if is_zero_based {
+            raw_start
+                .checked_add(1)
+                .ok_or(ParseError::PositionOutOfRange)?
+        } else {
+            raw_start
+        };
+
+        let end = match self.header.end_position_index() {
+            Some(i) => {
+                let raw_end: i32 = get_field(&fields, i)
+                    .ok_or(ParseError::MissingEndPosition)
+                    .and_then(|s| s.parse().map_err(ParseError::InvalidEndPosition))?;
+
+                match (is_zero_based, self.is_end_inclusive) {
+                    (true, true) => raw_end.checked_add(1),
+                    (true, false) | (false, true) => Some(raw_end),
+                    (false, false) => raw_end.checked_sub(1),
+                }
+                .ok_or(ParseError::PositionOutOfRange)?
+            }
+            None => match self.header.format() {
+                Format::Vcf => {
+                    const REFERENCE_BASES_INDEX: usize = 4;
+
+                    let reference_bases = get_field(&fields, REFERENCE_BASES_INDEX)
+                        .ok_or(ParseError::MissingEndPosition)?;
+
+                    i32::try_from(reference_bases.len())
+                        .ok()
+                        .and_then(|len| start.checked_add(len - 1))
+                        .ok_or(ParseError::PositionOutOfRange)?
+                }
+                Format::Sam => {
+                    const CIGAR_INDEX: usize = 6;
+
+                    let cigar =
+                        get_field(&fields, CIGAR_INDEX).ok_or(ParseError::MissingEndPosition)?;
+
+                    let span = alignment_span(cigar)?;
+
+                    start
+                        .checked_add(span - 1)
+                        .ok_or(ParseError::PositionOutOfRange)?
+                }
+                Format::Generic(_) => start,
+            },
+        };
+
+        // Empty intervals, e.g., BED insertion points, are treated as covering the start position.
+        Ok((reference_sequence_name, start, end.max(start)))
+    }
+}
+
+impl From<Header> for Layout {
+    fn from(header: Header) -> Self {
+        Self::new(header)
+    }
+}
+
+// `i` is 1-based.
+fn get_field<'a>(fields: &[&'a str], i: usize) -> Option<&'a str> {
+    i.checked_sub(1).and_then(|j| fields.get(j)).copied()
+}
+
+fn alignment_span(cigar: &str) -> Result<i32, ParseError> {
+    if cigar == "*" {
+        return Ok(1);
+    }
+
+    let mut span = 0i32;
+    let mut len = 0i32;
+
+    for b in cigar.bytes() {
+        match b {
+            b'0'..=b'9' => {
+                len = len
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(i32::from(b - b'0')))
+                    .ok_or(ParseError::InvalidCigar)?;
+            }
+            b'M' | b'D' | b'N' | b'=' | b'X' => {
+                span = span.checked_add(len).ok_or(ParseError::InvalidCigar)?;
+                len = 0;
+            }
+            b'I' | b'S' | b'H' | b'P' => len = 0,
+            _ => return Err(ParseError::InvalidCigar),
+        }
+    }
+
+    Ok(span.max(1))
+}
+
+/// An error returned when the interval of a line fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The reference sequence name is missing.
+    MissingReferenceSequenceName,
+    /// The start position is missing.
+    MissingStartPosition,
+    /// The start position is invalid.
+    InvalidStartPosition(num::ParseIntError),
+    /// The end position is missing.
+    MissingEndPosition,
+    /// The end position is invalid.
+    InvalidEndPosition(num::ParseIntError),
+    /// The CIGAR is invalid.
+    InvalidCigar,
+    /// A position is out of range.
+    ///
+    /// This is returned when normalizing a position or deriving an end position overflows.
+    PositionOutOfRange,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingReferenceSequenceName => f.write_str("missing reference sequence name"),
+            Self::MissingStartPosition => f.write_str("missing start position"),
+            Self::InvalidStartPosition(e) => write!(f, "invalid start position: {}", e),
+            Self::MissingEndPosition => f.write_str("missing end position"),
+            Self::InvalidEndPosition(e) => write!(f, "invalid end position: {}", e),
+            Self::InvalidCigar => f.write_str("invalid CIGAR"),
+            Self::PositionOutOfRange => f.write_str("position out of range"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::header::Builder;
+
+    #[test]
+    fn test_parse_interval() {
+        let layout = Layout::new(Builder::bed().build());
+        assert_eq!(layout.parse_interval("sq0\t7\t13"), Ok(("sq0", 8, 13)));
+        assert_eq!(layout.parse_interval("sq0\t7\t7"), Ok(("sq0", 8, 8)));
+        assert_eq!(
+            layout.parse_interval("sq0"),
+            Err(ParseError::MissingStartPosition)
+        );
+        assert!(matches!(
+            layout.parse_interval("sq0\tndls\t13"),
+            Err(ParseError::InvalidStartPosition(_))
+        ));
+        assert_eq!(
+            layout.parse_interval("sq0\t7"),
+            Err(ParseError::MissingEndPosition)
+        );
+
+        let layout = Layout::new(Builder::bed().build()).set_end_inclusive(true);
+        assert_eq!(layout.parse_interval("sq0\t7\t12"), Ok(("sq0", 8, 13)));
+
+        let layout = Layout::new(Builder::gff().build());
+        assert_eq!(
+            layout.parse_interval("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\t."),
+            Ok(("sq0", 8, 13))
+        );
+
+        let layout = Layout::new(Builder::gff().build()).set_end_inclusive(false);
+        assert_eq!(
+            layout.parse_interval("sq0\tNOODLES\tgene\t8\t14\t.\t+\t.\t."),
+            Ok(("sq0", 8, 13))
+        );
+
+        let layout = Layout::new(Builder::vcf().build());
+        assert_eq!(
+            layout.parse_interval("sq0\t8\t.\tA\t.\t.\tPASS\t."),
+            Ok(("sq0", 8, 8))
+        );
+
+        let layout = Layout::new(Builder::sam().build());
+        assert_eq!(
+            layout.parse_interval("r0\t0\tsq0\t8\t255\t2S4M1I2D\t*\t0\t0\tACGTACG\t*"),
+            Ok(("sq0", 8, 13))
+        );
+        assert_eq!(
+            layout.parse_interval("r0\t0\tsq0\t8\t255\t4Z\t*\t0\t0\tACGT\t*"),
+            Err(ParseError::InvalidCigar)
+        );
+
+        let header = crate::index::Header::builder()
+            .set_format(Format::Generic(CoordinateSystem::Gff))
+            .set_reference_sequence_name_index(1)
+            .set_start_position_index(2)
+            .set_end_position_index(None)
+            .build();
+        let layout = Layout::new(header);
+        assert_eq!(layout.parse_interval("sq0\t8\tndls"), Ok(("sq0", 8, 8)));
+    }
+
+    #[test]
+    fn test_parse_interval_with_out_of_range_positions() {
+        let layout = Layout::new(Builder::bed().build());
+        assert_eq!(
+            layout.parse_interval("sq0\t2147483647\t2147483647"),
+            Err(ParseError::PositionOutOfRange)
+        );
+
+        let layout = Layout::new(Builder::bed().build()).set_end_inclusive(true);
+        assert_eq!(
+            layout.parse_interval("sq0\t7\t2147483647"),
+            Err(ParseError::PositionOutOfRange)
+        );
+
+        let layout = Layout::new(Builder::gff().build()).set_end_inclusive(false);
+        assert_eq!(
+            layout.parse_interval("sq0\tNOODLES\tgene\t8\t-2147483648\t.\t+\t.\t."),
+            Err(ParseError::PositionOutOfRange)
+        );
+
+        let layout = Layout::new(Builder::vcf().build());
+        assert_eq!(
+            layout.parse_interval("sq0\t2147483647\t.\tACGT\t.\t.\tPASS\t."),
+            Err(ParseError::PositionOutOfRange)
+        );
+
+        let layout = Layout::new(Builder::sam().build());
+        assert_eq!(
+            layout.parse_interval("r0\t0\tsq0\t2147483647\t255\t4M\t*\t0\t0\tACGT\t*"),
+            Err(ParseError::PositionOutOfRange)
+        );
+        assert_eq!(
+            layout.parse_interval("r0\t0\tsq0\t8\t255\t2147483647M1M\t*\t0\t0\tA\t*"),
+            Err(ParseError::InvalidCigar)
+        );
+    }
+
+    #[test]
+    fn test_is_header_line() {
+        let header = crate::index::Header::builder()
+            .set_line_comment_prefix(b'#')
+            .set_line_skip_count(1)
+            .build();
+        let layout = Layout::new(header);
+
+        assert!(layout.is_header_line(0, "chrom\tstart\tend"));
+        assert!(layout.is_header_line(1, "#sq0\t7\t13"));
+        assert!(!layout.is_header_line(1, "sq0\t7\t13"));
+    }
+}
@@ -24,20 +24,52 @@
 //! let index = tabix::read("sample.vcf.gz.tbi")?;
 //! # Ok::<(), io::Error>(())
 //! ```
+//!
+//! ## Index a bgzipped, tab-delimited file with a custom layout
+//!
+//! ```no_run
+//! # use std::io;
+//! use noodles_tabix::{self as tabix, index::header::{format::CoordinateSystem, Format}};
+//!
+//! let header = tabix::index::Header::builder()
+//!     .set_format(Format::Generic(CoordinateSystem::Bed))
+//!     .set_reference_sequence_name_index(1)
+//!     .set_start_position_index(2)
+//!     .set_end_position_index(Some(3))
+//!     .build();
+//!
+//! let layout = tabix::Layout::new(header).set_end_inclusive(true);
+//! let index = tabix::index("sample.tsv.gz", &layout)?;
+//! # Ok::<(), io::Error>(())
+//! ```
 
 #[cfg(feature = "async")]
 pub mod r#async;
 
+mod error;
 pub mod index;
+pub mod indexed_reader;
+pub mod layout;
 mod reader;
 mod writer;
 
-pub use self::{index::Index, reader::Reader, writer::Writer};
+pub use self::{
+    error::Error, index::Index, indexed_reader::IndexedReader, layout::Layout, reader::Reader,
+    writer::Writer,
+};
 
 #[cfg(feature = "async")]
 pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
 
-use std::{fs::File, io, path::Path};
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, BufRead, Read},
+    path::Path,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::index::reference_sequence::bin::Chunk;
 
 static MAGIC_NUMBER: &[u8] = b"TBI\x01";
 
@@ -84,3 +116,176 @@ where
     writer.write_index(index)?;
     Ok(())
 }
+
+/// Indexes a bgzipped, tab-delimited file.
+///
+/// The intervals of lines are parsed using the given layout, and the layout header is used as
+/// the index header. Lines must be grouped by reference sequence name and sorted by start
+/// position.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_tabix::{self as tabix, index::header};
+/// let layout = tabix::Layout::new(header::Builder::bed().build());
+/// let index = tabix::index("sample.bed.gz", &layout)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index<P>(src: P, layout: &Layout) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(bgzf::Reader::new)?;
+    index_lines(&mut reader, layout)
+}
+
+fn index_lines<R>(reader: &mut bgzf::Reader<R>, layout: &Layout) -> io::Result<Index>
+where
+    R: Read,
+{
+    let mut indexer = Index::indexer();
+    indexer.set_header(layout.header().clone());
+
+    let mut buf = String::new();
+    let mut start_position = reader.virtual_position();
+
+    let mut reference_sequence_names = HashSet::new();
+    let mut current_reference_sequence_name = String::new();
+    let mut last_start = 0;
+
+    for line_number in 0.. {
+        buf.clear();
+
+        if reader.read_line(&mut buf)? == 0 {
+            break;
+        }
+
+        let end_position = reader.virtual_position();
+        let chunk = Chunk::new(start_position, end_position);
+        start_position = end_position;
+
+        let line = buf.trim_end_matches(['\n', '\r']);
+
+        if layout.is_header_line(line_number, line) {
+            continue;
+        }
+
+        let (reference_sequence_name, start, end) = layout.parse_interval(line).map_err(|e| {
+            io::Error::from(Error::InvalidLine {
+                line_number: Some(line_number),
+                source: e,
+            })
+        })?;
+
+        if reference_sequence_name != current_reference_sequence_name {
+            if !reference_sequence_names.insert(reference_sequence_name.to_string()) {
+                return Err(
+                    Error::UngroupedReferenceSequenceName(reference_sequence_name.into()).into(),
+                );
+            }
+
+            current_reference_sequence_name = reference_sequence_name.into();
+        } else if start < last_start {
+            return Err(Error::UnsortedStartPosition {
+                reference_sequence_name: reference_sequence_name.into(),
+                start,
+            }
+            .into());
+        }
+
+        last_start = start;
+
+        indexer.add_record(reference_sequence_name, start, end, chunk);
+    }
+
+    Ok(indexer.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use noodles_core::Region;
+
+    use super::*;
+
+    fn compress_data(data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(data)?;
+        writer.finish()
+    }
+
+    #[test]
+    fn test_index_lines_and_query() -> io::Result<()> {
+        use index::header::{format::CoordinateSystem, Format};
+
+        // 0-based start, inclusive end
+        let data = compress_data(
+            b"\
+#id\tchrom\tstart\tend
+r0\tsq0\t7\t12
+r1\tsq0\t20\t20
+r2\tsq1\t0\t99
+",
+        )?;
+
+        let header = index::Header::builder()
+            .set_format(Format::Generic(CoordinateSystem::Bed))
+            .set_reference_sequence_name_index(2)
+            .set_start_position_index(3)
+            .set_end_position_index(Some(4))
+            .set_line_comment_prefix(b'#')
+            .build();
+
+        let layout = Layout::new(header).set_end_inclusive(true);
+
+        let index = index_lines(&mut bgzf::Reader::new(&data[..]), &layout)?;
+
+        let reference_sequence_names: Vec<_> = index.reference_sequence_names().iter().collect();
+        assert_eq!(reference_sequence_names, ["sq0", "sq1"]);
+
+        let mut reader = IndexedReader::new(bgzf::Reader::new(Cursor::new(&data)), index, layout);
+
+        let lines: Vec<_> = reader
+            .query(&Region::mapped("sq0", 13..=21))?
+            .collect::<io::Result<_>>()?;
+        assert_eq!(lines, ["r0\tsq0\t7\t12", "r1\tsq0\t20\t20"]);
+
+        let lines: Vec<_> = reader
+            .query(&Region::mapped("sq0", 14..=20))?
+            .collect::<io::Result<_>>()?;
+        assert!(lines.is_empty());
+
+        let lines: Vec<_> = reader
+            .query(&Region::mapped("sq1", 100..=200))?
+            .collect::<io::Result<_>>()?;
+        assert_eq!(lines, ["r2\tsq1\t0\t99"]);
+
+        assert!(reader.query(&Region::mapped("sq2", 1..=2)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_lines_with_unsorted_lines() -> io::Result<()> {
+        let layout = Layout::new(index::header::Builder::bed().build());
+
+        let data = compress_data(b"sq0\t8\t13\nsq0\t5\t8\n")?;
+        assert!(matches!(
+            index_lines(&mut bgzf::Reader::new(&data[..]), &layout),
+            Err(e) if matches!(
+                e.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+                Some(Error::UnsortedStartPosition { start: 6, .. })
+            )
+        ));
+
+        let data = compress_data(b"sq0\t8\t13\nsq1\t5\t8\nsq0\t21\t34\n")?;
+        assert!(matches!(
+            index_lines(&mut bgzf::Reader::new(&data[..]), &layout),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
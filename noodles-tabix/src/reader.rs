@@ -14,7 +14,7 @@ use crate::index::{
     ReferenceSequence, ReferenceSequenceNames,
 };
 
-use super::{Index, MAGIC_NUMBER};
+use super::{Error, Index, MAGIC_NUMBER};
 
 const NUL: u8 = b'\x00';
 
@@ -62,7 +62,7 @@ where
         read_magic(&mut self.inner)?;
 
         let n_ref = self.inner.read_i32::<LittleEndian>().and_then(|n| {
-            usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidReferenceSequenceCount(e)))
         })?;
 
         let header = read_header(&mut self.inner)?;
@@ -94,10 +94,7 @@ where
     if magic == MAGIC_NUMBER {
         Ok(())
     } else {
-        Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "invalid tabix header",
-        ))
+        Err(Error::InvalidMagicNumber(magic).into())
     }
 }
 
@@ -105,16 +102,16 @@ fn read_header<R>(reader: &mut R) -> io::Result<index::Header>
 where
     R: Read,
 {
-    let format = reader.read_i32::<LittleEndian>().and_then(|n| {
-        Format::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-    })?;
+    let format = reader
+        .read_i32::<LittleEndian>()
+        .and_then(|n| Format::try_from(n).map_err(|e| io::Error::from(Error::InvalidFormat(e))))?;
 
     let col_seq = reader.read_i32::<LittleEndian>().and_then(|i| {
-        usize::try_from(i).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(i).map_err(|e| io::Error::from(Error::InvalidColumnIndex(e)))
     })?;
 
     let col_beg = reader.read_i32::<LittleEndian>().and_then(|i| {
-        usize::try_from(i).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(i).map_err(|e| io::Error::from(Error::InvalidColumnIndex(e)))
     })?;
 
     let col_end = reader.read_i32::<LittleEndian>().and_then(|i| {
@@ -123,16 +120,16 @@ where
         } else {
             usize::try_from(i)
                 .map(Some)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                .map_err(|e| io::Error::from(Error::InvalidColumnIndex(e)))
         }
     })?;
 
-    let meta = reader
-        .read_i32::<LittleEndian>()
-        .and_then(|b| u8::try_from(b).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))?;
+    let meta = reader.read_i32::<LittleEndian>().and_then(|b| {
+        u8::try_from(b).map_err(|e| io::Error::from(Error::InvalidLineCommentPrefix(e)))
+    })?;
 
     let skip = reader.read_i32::<LittleEndian>().and_then(|n| {
-        u32::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        u32::try_from(n).map_err(|e| io::Error::from(Error::InvalidHeaderLineCount(e)))
     })?;
 
     Ok(index::Header::builder()
@@ -150,7 +147,8 @@ where
     R: Read,
 {
     let l_nm = reader.read_i32::<LittleEndian>().and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(n)
+            .map_err(|e| io::Error::from(Error::InvalidReferenceSequenceNamesLength(e)))
     })?;
 
     let mut names = vec![0; l_nm];
@@ -170,13 +168,10 @@ pub(crate) fn parse_names(buf: &[u8]) -> io::Result<ReferenceSequenceNames> {
             Some(end) => {
                 let raw_name = &buf[..end];
                 let name = str::from_utf8(raw_name)
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                    .map_err(|e| io::Error::from(Error::InvalidReferenceSequenceName(e)))?;
 
                 if !names.insert(name.into()) {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        format!("duplicate reference sequence name: {}", name),
-                    ));
+                    return Err(Error::DuplicateReferenceSequenceName(name.into()).into());
                 }
 
                 start += end + 1;
@@ -209,9 +204,9 @@ where
 {
     use reference_sequence::bin::METADATA_ID;
 
-    let n_bin = reader.read_i32::<LittleEndian>().and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-    })?;
+    let n_bin = reader
+        .read_i32::<LittleEndian>()
+        .and_then(|n| usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidBinCount(e))))?;
 
     let mut bins = Vec::with_capacity(n_bin);
     let mut metadata = None;
@@ -236,7 +231,7 @@ where
     R: Read,
 {
     let n_chunk = reader.read_i32::<LittleEndian>().and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidChunkCount(e)))
     })?;
 
     let mut chunks = Vec::with_capacity(n_chunk);
@@ -261,7 +256,7 @@ where
     R: Read,
 {
     let n_intv = reader.read_i32::<LittleEndian>().and_then(|n| {
-        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        usize::try_from(n).map_err(|e| io::Error::from(Error::InvalidIntervalCount(e)))
     })?;
 
     let mut intervals = Vec::with_capacity(n_intv);
@@ -286,13 +281,7 @@ where
     let n_chunk = reader.read_u32::<LittleEndian>()?;
 
     if n_chunk != METADATA_CHUNK_COUNT {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!(
-                "invalid metadata pseudo-bin chunk count: expected {}, got {}",
-                METADATA_CHUNK_COUNT, n_chunk
-            ),
-        ));
+        return Err(Error::InvalidMetadataChunkCount(n_chunk).into());
     }
 
     let ref_beg = reader
@@ -0,0 +1,16 @@
+//! Alignment format utilities.
+
+pub mod collate;
+pub mod convert;
+pub mod depth;
+pub mod dict;
+pub mod merge;
+pub mod pileup;
+pub mod seqchksum;
+pub mod shard;
+pub mod slice;
+pub mod sort_order;
+pub mod transform;
+pub mod writer;
+
+pub use self::writer::Builder as WriterBuilder;
@@ -0,0 +1,191 @@
+//! Alignment record collation.
+//!
+//! Collation groups the records of an alignment stream by read name into templates, i.e., all
+//! the records of a read or read pair, similar to `samtools collate`. This is typically needed
+//! before processing templates as a whole, e.g., fixing mate information, converting to FASTQ,
+//! or marking duplicates.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::io;
+
+use noodles_sam::AlignmentRecord;
+
+use crate::sort::{self, Codec};
+
+// The sort key of a record, i.e., its read name.
+type Key = Option<Vec<u8>>;
+
+type KeyFn<R> = fn(&R) -> Key;
+
+/// An iterator over alignment templates.
+///
+/// A template is the list of all records with the same read name, in input order. Records with a
+/// missing read name are each returned as their own template. Templates are returned in read name
+/// order.
+///
+/// Records are collated using an external sort, so the input can be arbitrarily ordered and
+/// larger than memory. See [`Builder`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam as bam;
+/// use noodles_sam::{self as sam, AlignmentRecord};
+/// use noodles_util::{alignment::collate, sort::codec::BamCodec};
+///
+/// let reference_sequences = sam::header::ReferenceSequences::default();
+///
+/// let records = ["r0\t77", "r1\t77", "r0\t141"]
+///     .into_iter()
+///     .map(|s| {
+///         let record = format!("{}\t*\t0\t0\t*\t*\t0\t0\t*\t*", s).parse().unwrap();
+///         bam::Record::try_from_sam_record(&reference_sequences, &record)
+///     });
+///
+/// let templates = collate::Builder::new(BamCodec).build(records)?;
+///
+/// let read_names = templates
+///     .map(|result| {
+///         result.map(|template| {
+///             template
+///                 .iter()
+///                 .map(|record| AlignmentRecord::read_name(record).map(|name| name.to_vec()))
+///                 .collect::<Vec<_>>()
+///         })
+///     })
+///     .collect::<io::Result<Vec<_>>>()?;
+///
+/// assert_eq!(read_names, [
+///     vec![Some(b"r0".to_vec()), Some(b"r0".to_vec())],
+///     vec![Some(b"r1".to_vec())],
+/// ]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct Collate<C>
+where
+    C: Codec,
+{
+    records: sort::Records<C, Key, KeyFn<C::Record>>,
+    pending_record: Option<C::Record>,
+}
+
+impl<C> Collate<C>
+where
+    C: Codec,
+    C::Record: AlignmentRecord,
+{
+    fn next_template(&mut self) -> io::Result<Option<Vec<C::Record>>> {
+        let first_record = match self.pending_record.take() {
+            Some(record) => record,
+            None => match self.records.next().transpose()? {
+                Some(record) => record,
+                None => return Ok(None),
+            },
+        };
+
+        let mut template = vec![first_record];
+
+        let read_name = match template[0].read_name() {
+            Some(read_name) => read_name.to_vec(),
+            None => return Ok(Some(template)),
+        };
+
+        while let Some(record) = self.records.next().transpose()? {
+            if record.read_name() == Some(&read_name[..]) {
+                template.push(record);
+            } else {
+                self.pending_record = Some(record);
+                break;
+            }
+        }
+
+        Ok(Some(template))
+    }
+}
+
+impl<C> Iterator for Collate<C>
+where
+    C: Codec,
+    C::Record: AlignmentRecord,
+{
+    type Item = io::Result<Vec<C::Record>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_template().transpose()
+    }
+}
+
+fn read_name_key<R>(record: &R) -> Key
+where
+    R: AlignmentRecord,
+{
+    record.read_name().map(|read_name| read_name.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bam as bam;
+    use noodles_sam as sam;
+
+    use super::*;
+    use crate::sort::codec::BamCodec;
+
+    #[test]
+    fn test_next() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequences = sam::header::ReferenceSequences::default();
+
+        let records: Vec<_> = [
+            "r1\t77", "*\t4", "r0\t321", "r2\t4", "r0\t65", "*\t4", "r1\t141", "r0\t129",
+        ]
+        .iter()
+        .map(|s| {
+            let record: sam::Record = format!("{}\t*\t0\t0\t*\t*\t0\t0\t*\t*", s).parse()?;
+            Ok(bam::Record::try_from_sam_record(
+                &reference_sequences,
+                &record,
+            )?)
+        })
+        .collect::<Result<_, Box<dyn std::error::Error>>>()?;
+
+        // 2 records per shard
+        let templates: Vec<_> = Builder::new(BamCodec)
+            .set_memory_budget(160)
+            .build(records.into_iter().map(Ok))?
+            .collect::<io::Result<_>>()?;
+
+        let actual: Vec<Vec<_>> = templates
+            .iter()
+            .map(|template| {
+                template
+                    .iter()
+                    .map(|record| {
+                        (
+                            AlignmentRecord::read_name(record).map(|name| name.to_vec()),
+                            u16::from(AlignmentRecord::flags(record)),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let expected = [
+            vec![(None, 4)],
+            vec![(None, 4)],
+            vec![
+                (Some(b"r0".to_vec()), 321),
+                (Some(b"r0".to_vec()), 65),
+                (Some(b"r0".to_vec()), 129),
+            ],
+            vec![(Some(b"r1".to_vec()), 77), (Some(b"r1".to_vec()), 141)],
+            vec![(Some(b"r2".to_vec()), 4)],
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
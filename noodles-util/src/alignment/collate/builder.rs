@@ -0,0 +1,120 @@
+use std::{io, num::NonZeroUsize, path::PathBuf};
+
+use noodles_sam::AlignmentRecord;
+
+use super::{read_name_key, Collate, KeyFn};
+use crate::sort::{self, Codec};
+
+/// An alignment record collation builder.
+pub struct Builder<C>
+where
+    C: Codec,
+{
+    sort_builder: sort::Builder<C, KeyFn<C::Record>>,
+}
+
+impl<C> Builder<C>
+where
+    C: Codec,
+    C::Record: AlignmentRecord,
+{
+    /// Creates an alignment record collation builder.
+    ///
+    /// `codec` serializes records to temporary files when the memory budget is reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::{alignment::collate, sort::codec::BamCodec};
+    /// let builder = collate::Builder::new(BamCodec);
+    /// ```
+    pub fn new(codec: C) -> Self {
+        Self {
+            sort_builder: sort::Builder::new(codec, read_name_key::<C::Record>),
+        }
+    }
+
+    /// Sets the memory budget in bytes.
+    ///
+    /// See [`sort::Builder::set_memory_budget`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::{alignment::collate, sort::codec::BamCodec};
+    /// let builder = collate::Builder::new(BamCodec).set_memory_budget(1 << 20);
+    /// ```
+    pub fn set_memory_budget(mut self, memory_budget: usize) -> Self {
+        self.sort_builder = self.sort_builder.set_memory_budget(memory_budget);
+        self
+    }
+
+    /// Sets the directory to write temporary files to.
+    ///
+    /// See [`sort::Builder::set_temp_dir`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::{alignment::collate, sort::codec::BamCodec};
+    /// let builder = collate::Builder::new(BamCodec).set_temp_dir("/tmp");
+    /// ```
+    pub fn set_temp_dir<P>(mut self, temp_dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.sort_builder = self.sort_builder.set_temp_dir(temp_dir);
+        self
+    }
+
+    /// Sets the number of worker threads used to compress temporary files.
+    ///
+    /// See [`sort::Builder::set_worker_count`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_util::{alignment::collate, sort::codec::BamCodec};
+    /// let builder = collate::Builder::new(BamCodec)
+    ///     .set_worker_count(NonZeroUsize::new(4).unwrap());
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.sort_builder = self.sort_builder.set_worker_count(worker_count);
+        self
+    }
+
+    /// Builds an iterator over the templates of the given records.
+    ///
+    /// This reads all input records before returning.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_util::{alignment::collate, sort::codec::BamCodec};
+    ///
+    /// let records = vec![Ok(bam::Record::default())];
+    /// let mut templates = collate::Builder::new(BamCodec).build(records)?;
+    ///
+    /// assert_eq!(templates.next().transpose()?.map(|template| template.len()), Some(1));
+    /// assert!(templates.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build<I>(self, records: I) -> io::Result<Collate<C>>
+    where
+        I: IntoIterator<Item = io::Result<C::Record>>,
+    {
+        let mut sorter = self.sort_builder.build();
+
+        for result in records {
+            sorter.push(result?)?;
+        }
+
+        Ok(Collate {
+            records: sorter.finish()?,
+            pending_record: None,
+        })
+    }
+}
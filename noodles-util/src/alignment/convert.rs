@@ -0,0 +1,532 @@
+//! Alignment format conversion.
+//!
+//! This converts between SAM, BAM, and CRAM, i.e., the equivalent of `samtools view -o`. The
+//! header is copied as is, and records are converted through SAM records, which preserves all
+//! fields and data (tags).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use std::io;
+//! use noodles_util::alignment::convert;
+//! let record_count = convert::Builder::default().convert_path("sample.bam", "sample.sam")?;
+//! # Ok::<_, io::Error>(())
+//! ```
+
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufReader, Read, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use noodles_bam as bam;
+use noodles_bgzf::{self as bgzf, worker_pool::Task};
+use noodles_cram as cram;
+use noodles_fasta as fasta;
+use noodles_sam::{self as sam, AlignmentReader, AlignmentWriter};
+
+use super::{
+    transform::{Pipeline, Transform},
+    writer::detect_format_from_extension,
+    WriterBuilder,
+};
+use crate::Format;
+
+/// An alignment format converter builder.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_util::{alignment::convert, Format};
+///
+/// let src = b"@HD\tVN:1.6\n";
+///
+/// let record_count = convert::Builder::default()
+///     .set_input_format(Format::Sam)
+///     .set_output_format(Format::Bam)
+///     .convert(&src[..], io::sink())?;
+///
+/// assert_eq!(record_count, 0);
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct Builder {
+    input_format: Option<Format>,
+    output_format: Option<Format>,
+    reference_sequences: Vec<fasta::Record>,
+    worker_pool: Option<bgzf::WorkerPool>,
+    transform: Pipeline,
+}
+
+impl Builder {
+    /// Sets the input format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::{alignment::convert, Format};
+    /// let builder = convert::Builder::default().set_input_format(Format::Bam);
+    /// ```
+    pub fn set_input_format(mut self, format: Format) -> Self {
+        self.input_format = Some(format);
+        self
+    }
+
+    /// Sets the output format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::{alignment::convert, Format};
+    /// let builder = convert::Builder::default().set_output_format(Format::Cram);
+    /// ```
+    pub fn set_output_format(mut self, format: Format) -> Self {
+        self.output_format = Some(format);
+        self
+    }
+
+    /// Sets the reference sequences.
+    ///
+    /// These are required to decode CRAM records that are stored as differences to the reference
+    /// and to encode CRAM records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::convert;
+    /// let builder = convert::Builder::default().set_reference_sequences(Vec::new());
+    /// ```
+    pub fn set_reference_sequences(mut self, reference_sequences: Vec<fasta::Record>) -> Self {
+        self.reference_sequences = reference_sequences;
+        self
+    }
+
+    /// Sets a worker pool.
+    ///
+    /// The worker pool is shared by the reader and writer to decompress and compress BAM blocks
+    /// and decode and encode CRAM containers in parallel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_util::alignment::convert;
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(4).unwrap());
+    /// let builder = convert::Builder::default().set_worker_pool(worker_pool);
+    /// ```
+    pub fn set_worker_pool(mut self, worker_pool: bgzf::WorkerPool) -> Self {
+        self.worker_pool = Some(worker_pool);
+        self
+    }
+
+    /// Sets a transform pipeline.
+    ///
+    /// The transforms are applied to each record before it is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::{
+    ///     convert,
+    ///     transform::{Pipeline, QualityBinning},
+    /// };
+    ///
+    /// let builder = convert::Builder::default()
+    ///     .set_transform(Pipeline::default().push(QualityBinning::illumina()));
+    /// ```
+    pub fn set_transform(mut self, transform: Pipeline) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Converts the file at the source path and writes it to the destination path.
+    ///
+    /// Formats that are not set are detected from the file extensions, i.e., `.sam`, `.bam`, or
+    /// `.cram`.
+    ///
+    /// This returns the number of records converted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_util::alignment::convert;
+    /// convert::Builder::default().convert_path("sample.sam", "sample.bam")?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn convert_path<P, Q>(mut self, src: P, dst: Q) -> io::Result<u64>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        if self.input_format.is_none() {
+            self.input_format = detect_format_from_extension(&src);
+        }
+
+        if self.output_format.is_none() {
+            self.output_format = detect_format_from_extension(&dst);
+        }
+
+        let reader = File::open(src)?;
+        let writer = File::create(dst)?;
+
+        self.convert(reader, writer)
+    }
+
+    /// Converts the alignment data from the reader and writes it to the writer.
+    ///
+    /// The input and output formats must be set.
+    ///
+    /// This returns the number of records converted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::{alignment::convert, Format};
+    ///
+    /// let src = b"@HD\tVN:1.6\n";
+    ///
+    /// convert::Builder::default()
+    ///     .set_input_format(Format::Sam)
+    ///     .set_output_format(Format::Bam)
+    ///     .convert(&src[..], io::sink())?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn convert<R, W>(self, reader: R, writer: W) -> io::Result<u64>
+    where
+        R: Read,
+        W: Write + 'static,
+    {
+        let input_format = self.input_format.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "missing input alignment format",
+            )
+        })?;
+
+        let mut writer_builder =
+            WriterBuilder::default().set_reference_sequences(self.reference_sequences.clone());
+
+        if let Some(output_format) = self.output_format {
+            writer_builder = writer_builder.set_format(output_format);
+        }
+
+        if let Some(worker_pool) = self.worker_pool.clone() {
+            writer_builder = writer_builder.set_worker_pool(worker_pool);
+        }
+
+        let mut writer = writer_builder.build_from_writer(writer)?;
+        let mut transform = self.transform;
+
+        match input_format {
+            Format::Sam => convert_sam(reader, &mut transform, writer.as_mut()),
+            Format::Bam => convert_bam(reader, self.worker_pool, &mut transform, writer.as_mut()),
+            Format::Cram => convert_cram(
+                reader,
+                self.reference_sequences,
+                self.worker_pool,
+                &mut transform,
+                writer.as_mut(),
+            ),
+            format => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid alignment format: {}", format),
+            )),
+        }
+    }
+}
+
+fn convert_sam<R>(
+    reader: R,
+    transform: &mut dyn Transform,
+    writer: &mut dyn AlignmentWriter,
+) -> io::Result<u64>
+where
+    R: Read,
+{
+    let mut reader = sam::Reader::new(BufReader::new(reader));
+    let header = reader.read_alignment_header()?;
+
+    writer.write_alignment_header(&header)?;
+
+    let mut n = 0;
+
+    for result in reader.records() {
+        let mut record = result?;
+        transform.apply(&mut record)?;
+        writer.write_alignment_record(&header, &record)?;
+        n += 1;
+    }
+
+    writer.finish(&header)?;
+
+    Ok(n)
+}
+
+fn convert_bam<R>(
+    reader: R,
+    worker_pool: Option<bgzf::WorkerPool>,
+    transform: &mut dyn Transform,
+    writer: &mut dyn AlignmentWriter,
+) -> io::Result<u64>
+where
+    R: Read,
+{
+    let mut builder = bam::Reader::builder(reader);
+
+    if let Some(worker_pool) = worker_pool {
+        builder = builder.set_worker_pool(worker_pool);
+    }
+
+    let mut reader = builder.build();
+    let header = reader.read_alignment_header()?;
+
+    writer.write_alignment_header(&header)?;
+
+    let mut n = 0;
+
+    for result in reader.records() {
+        let record = result?;
+        let mut sam_record = record.try_into_sam_record(header.reference_sequences())?;
+        transform.apply(&mut sam_record)?;
+        writer.write_alignment_record(&header, &sam_record)?;
+        n += 1;
+    }
+
+    writer.finish(&header)?;
+
+    Ok(n)
+}
+
+fn convert_cram<R>(
+    reader: R,
+    reference_sequences: Vec<fasta::Record>,
+    worker_pool: Option<bgzf::WorkerPool>,
+    transform: &mut dyn Transform,
+    writer: &mut dyn AlignmentWriter,
+) -> io::Result<u64>
+where
+    R: Read,
+{
+    let mut reader = cram::Reader::new(reader);
+    let header = reader.read_alignment_header()?;
+
+    writer.write_alignment_header(&header)?;
+
+    let reference_sequences = Arc::new(reference_sequences);
+    let header_reference_sequences = Arc::new(header.reference_sequences().clone());
+
+    let decode = move |container: cram::DataContainer| {
+        decode_data_container(
+            &reference_sequences,
+            &header_reference_sequences,
+            &container,
+        )
+    };
+
+    let mut n = 0;
+
+    let mut write_records = |records: Vec<sam::Record>| -> io::Result<()> {
+        for mut record in records {
+            transform.apply(&mut record)?;
+            writer.write_alignment_record(&header, &record)?;
+            n += 1;
+        }
+
+        Ok(())
+    };
+
+    match worker_pool {
+        Some(worker_pool) => {
+            // Keep twice as many containers in flight as there are workers so that workers are not
+            // idle while records are written.
+            let max_task_count = 2 * worker_pool.worker_count().get();
+            let mut tasks: VecDeque<Task<io::Result<Vec<sam::Record>>>> = VecDeque::new();
+
+            while let Some(container) = reader.read_data_container()? {
+                let decode = decode.clone();
+                tasks.push_back(worker_pool.spawn(move || decode(container)));
+
+                if tasks.len() >= max_task_count {
+                    if let Some(task) = tasks.pop_front() {
+                        write_records(task.wait()?)?;
+                    }
+                }
+            }
+
+            while let Some(task) = tasks.pop_front() {
+                write_records(task.wait()?)?;
+            }
+        }
+        None => {
+            while let Some(container) = reader.read_data_container()? {
+                write_records(decode(container)?)?;
+            }
+        }
+    }
+
+    writer.finish(&header)?;
+
+    Ok(n)
+}
+
+pub(super) fn decode_data_container(
+    reference_sequences: &[fasta::Record],
+    header_reference_sequences: &sam::header::ReferenceSequences,
+    container: &cram::DataContainer,
+) -> io::Result<Vec<sam::Record>> {
+    let compression_header = container.compression_header();
+    let mut sam_records = Vec::new();
+
+    for slice in container.slices() {
+        let records = slice.records(compression_header)?;
+
+        for record in slice.resolve_mates(records) {
+            let sam_record = record.try_into_sam_record(
+                reference_sequences,
+                header_reference_sequences,
+                compression_header,
+            )?;
+
+            sam_records.push(sam_record);
+        }
+    }
+
+    Ok(sam_records)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_sam::header::ReferenceSequence;
+
+    use super::*;
+
+    fn build_reference_sequences() -> Vec<fasta::Record> {
+        vec![fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"ACGTACGT".to_vec()),
+        )]
+    }
+
+    fn build_sam() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name("sq0".parse()?)
+                    .set_length(8)
+                    .set_md5_checksum(
+                        [
+                            0xd7, 0xeb, 0xa3, 0x11, 0x42, 0x1b, 0xbc, 0x9d, 0x3a, 0xda, 0x44, 0x70,
+                            0x9d, 0xd6, 0x15, 0x34,
+                        ]
+                        .into(),
+                    )
+                    .build()?,
+            )
+            .build();
+
+        let mut writer = sam::Writer::new(Vec::new());
+        writer.write_alignment_header(&header)?;
+
+        for s in [
+            "r0\t0\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS\tNH:i:1",
+            "r1\t16\tsq0\t5\t37\t4M\t*\t0\t0\tACGA\tNDLS",
+        ] {
+            writer.write_alignment_record(&header, &s.parse::<sam::Record>()?)?;
+        }
+
+        Ok(writer.get_ref().clone())
+    }
+
+    #[test]
+    fn test_convert() -> Result<(), Box<dyn std::error::Error>> {
+        let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(2).unwrap());
+
+        for (format, worker_pool) in [
+            (Format::Bam, None),
+            (Format::Bam, Some(worker_pool.clone())),
+            (Format::Cram, None),
+            (Format::Cram, Some(worker_pool)),
+        ] {
+            let sam_src = build_sam()?;
+
+            let mut builder = Builder::default()
+                .set_input_format(Format::Sam)
+                .set_output_format(format)
+                .set_reference_sequences(build_reference_sequences());
+
+            if let Some(worker_pool) = worker_pool.clone() {
+                builder = builder.set_worker_pool(worker_pool);
+            }
+
+            let dst = SharedBuffer::default();
+            assert_eq!(builder.convert(&sam_src[..], dst.clone())?, 2);
+            let src = dst.into_inner();
+
+            let mut builder = Builder::default()
+                .set_input_format(format)
+                .set_output_format(Format::Sam)
+                .set_reference_sequences(build_reference_sequences());
+
+            if let Some(worker_pool) = worker_pool {
+                builder = builder.set_worker_pool(worker_pool);
+            }
+
+            let dst = SharedBuffer::default();
+            assert_eq!(builder.convert(&src[..], dst.clone())?, 2);
+
+            let mut reader = sam::Reader::new(&sam_src[..]);
+            reader.read_header()?;
+            let expected: Vec<sam::Record> = reader.records().collect::<Result<_, _>>()?;
+
+            let actual_src = dst.into_inner();
+            let mut reader = sam::Reader::new(&actual_src[..]);
+            reader.read_header()?;
+            let actual: Vec<sam::Record> = reader.records().collect::<Result<_, _>>()?;
+
+            assert_eq!(actual, expected, "{}", format);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_with_missing_input_format() {
+        assert!(matches!(
+            Builder::default()
+                .set_output_format(Format::Sam)
+                .convert(&[][..], io::sink()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    // The output writer is required to be `'static`, so a shared buffer is used to read the
+    // converted data.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl SharedBuffer {
+        fn into_inner(self) -> Vec<u8> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+}
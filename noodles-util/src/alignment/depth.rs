@@ -0,0 +1,321 @@
+//! Alignment coverage depth.
+//!
+//! The depth at a reference sequence position is the number of records with a base aligned to
+//! the position, similar to `samtools depth` and `mosdepth`. Results are given as bedGraph
+//! records, either as runs of positions with the same depth or as windows with the mean depth.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{io, num::NonZeroU32};
+
+use noodles_bed::bed_graph;
+use noodles_sam::{self as sam, AlignmentRecord};
+
+use super::pileup::{Column, Pileup};
+
+/// An alignment coverage depth calculator.
+///
+/// This is an iterator over bedGraph records built from a stream of coordinate-sorted alignment
+/// records. Positions not covered by any record have a depth of 0.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bed::bed_graph;
+/// use noodles_sam::{self as sam, AlignmentReader};
+/// use noodles_util::alignment::depth;
+///
+/// let data = b"@HD\tVN:1.6\tSO:coordinate
+/// @SQ\tSN:sq0\tLN:8
+/// r0\t0\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS
+/// r1\t16\tsq0\t3\t60\t4M\t*\t0\t0\tGTAC\tNDLS
+/// ";
+///
+/// let mut reader = sam::Reader::new(&data[..]);
+/// let header = reader.read_alignment_header()?;
+///
+/// let records: Vec<_> = depth::Builder::default()
+///     .build(&header, reader.alignment_records(&header))?
+///     .collect::<io::Result<_>>()?;
+///
+/// assert_eq!(records, [
+///     bed_graph::Record::new("sq0", 0, 2, 1.0),
+///     bed_graph::Record::new("sq0", 2, 4, 2.0),
+///     bed_graph::Record::new("sq0", 4, 6, 1.0),
+///     bed_graph::Record::new("sq0", 6, 8, 0.0),
+/// ]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct Depth<'a, I>
+where
+    I: Iterator<Item = io::Result<Box<dyn AlignmentRecord>>>,
+{
+    header: &'a sam::Header,
+    pileup: Pileup<'a, I>,
+    pending_column: Option<Column>,
+    targets: Vec<Target>,
+    target_index: usize,
+    position: i32,
+    window_size: Option<NonZeroU32>,
+    include_deletions: bool,
+}
+
+impl<'a, I> Depth<'a, I>
+where
+    I: Iterator<Item = io::Result<Box<dyn AlignmentRecord>>>,
+{
+    fn next_record(&mut self) -> io::Result<Option<bed_graph::Record>> {
+        let target = loop {
+            match self.targets.get(self.target_index) {
+                Some(target) if self.position <= target.end => break *target,
+                Some(_) => {
+                    self.target_index += 1;
+
+                    if let Some(target) = self.targets.get(self.target_index) {
+                        self.position = target.start;
+                    }
+                }
+                None => return Ok(None),
+            }
+        };
+
+        let id = target.reference_sequence_id;
+        let start = self.position;
+
+        let (end, value) = match self.window_size {
+            Some(window_size) => {
+                let len = i32::try_from(window_size.get())
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+                let end = start.saturating_add(len - 1).min(target.end);
+                let sum = self.sum_depths(id, start, end)?;
+                (end, sum as f64 / f64::from(end - start + 1))
+            }
+            None => {
+                let (end, depth) = self.next_run(id, start, target.end)?;
+                (end, depth as f64)
+            }
+        };
+
+        self.position = end + 1;
+
+        let (name, _) = self
+            .header
+            .reference_sequences()
+            .get_index(id)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid reference sequence ID")
+            })?;
+
+        Ok(Some(bed_graph::Record::new(
+            name.as_str(),
+            (start - 1) as u64,
+            end as u64,
+            value,
+        )))
+    }
+
+    // Returns the end of the run of positions with the same depth starting at `start` and the
+    // depth.
+    fn next_run(&mut self, id: usize, start: i32, max_end: i32) -> io::Result<(i32, usize)> {
+        let include_deletions = self.include_deletions;
+
+        let mut depth = None;
+        let mut end = start - 1;
+
+        while end < max_end {
+            let next = self.peek_column(id, end + 1)?.map(|column| {
+                (
+                    i32::from(column.position()),
+                    column_depth(column, include_deletions),
+                )
+            });
+
+            let (next_end, next_depth, is_covered) = match next {
+                Some((position, d)) if position == end + 1 => (position, d, true),
+                Some((position, _)) => ((position - 1).min(max_end), 0, false),
+                None => (max_end, 0, false),
+            };
+
+            if matches!(depth, Some(d) if d != next_depth) {
+                break;
+            }
+
+            if is_covered {
+                self.pending_column = None;
+            }
+
+            depth = Some(next_depth);
+            end = next_end;
+        }
+
+        Ok((end, depth.unwrap_or_default()))
+    }
+
+    fn sum_depths(&mut self, id: usize, start: i32, end: i32) -> io::Result<u64> {
+        let include_deletions = self.include_deletions;
+        let mut sum = 0;
+
+        while let Some(column) = self.peek_column(id, start)? {
+            if i32::from(column.position()) > end {
+                break;
+            }
+
+            sum += column_depth(column, include_deletions) as u64;
+            self.pending_column = None;
+        }
+
+        Ok(sum)
+    }
+
+    // Returns the next column on the given reference sequence at or after the given position.
+    //
+    // Columns before the given position are discarded.
+    fn peek_column(&mut self, id: usize, position: i32) -> io::Result<Option<&Column>> {
+        loop {
+            if self.pending_column.is_none() {
+                self.pending_column = self.pileup.next().transpose()?;
+            }
+
+            match self.pending_column.as_ref() {
+                Some(column) => {
+                    let key = (column.reference_sequence_id(), i32::from(column.position()));
+
+                    if key < (id, position) {
+                        self.pending_column = None;
+                    } else if column.reference_sequence_id() == id {
+                        break;
+                    } else {
+                        return Ok(None);
+                    }
+                }
+                None => return Ok(None),
+            }
+        }
+
+        Ok(self.pending_column.as_ref())
+    }
+}
+
+impl<'a, I> Iterator for Depth<'a, I>
+where
+    I: Iterator<Item = io::Result<Box<dyn AlignmentRecord>>>,
+{
+    type Item = io::Result<bed_graph::Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+fn column_depth(column: &Column, include_deletions: bool) -> usize {
+    if include_deletions {
+        column.depth()
+    } else {
+        column
+            .entries()
+            .iter()
+            .filter(|entry| !entry.is_deletion())
+            .count()
+    }
+}
+
+// A reference sequence interval to calculate the depth over. Positions are 1-based, inclusive.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Target {
+    reference_sequence_id: usize,
+    start: i32,
+    end: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Region;
+
+    use super::*;
+
+    fn calculate(builder: Builder<'_>, data: &[u8]) -> io::Result<Vec<bed_graph::Record>> {
+        use sam::AlignmentReader;
+
+        let mut reader = sam::Reader::new(data);
+        let header = reader.read_alignment_header()?;
+        let records = reader.alignment_records(&header);
+
+        let depth = builder.build(&header, records)?;
+        depth.collect()
+    }
+
+    static DATA: &[u8] = b"@SQ\tSN:sq0\tLN:8
+@SQ\tSN:sq1\tLN:4
+r0\t0\tsq0\t2\t60\t2M2D1M\t*\t0\t0\tACG\tNDL
+r1\t0\tsq0\t3\t10\t1M2N1M\t*\t0\t0\tAC\tN!
+r2\t0\tsq1\t2\t60\t2M\t*\t0\t0\tAC\tNN
+";
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        let actual = calculate(Builder::default(), DATA)?;
+
+        let expected = [
+            bed_graph::Record::new("sq0", 0, 1, 0.0),
+            bed_graph::Record::new("sq0", 1, 2, 1.0),
+            bed_graph::Record::new("sq0", 2, 3, 2.0),
+            bed_graph::Record::new("sq0", 3, 5, 0.0),
+            bed_graph::Record::new("sq0", 5, 6, 2.0),
+            bed_graph::Record::new("sq0", 6, 8, 0.0),
+            bed_graph::Record::new("sq1", 0, 1, 0.0),
+            bed_graph::Record::new("sq1", 1, 3, 1.0),
+            bed_graph::Record::new("sq1", 3, 4, 0.0),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_filters() -> io::Result<()> {
+        let builder = Builder::default()
+            .set_min_mapping_quality(20)
+            .set_include_deletions(true)
+            .set_regions(vec![Region::mapped("sq0", 2..=6)]);
+
+        let actual = calculate(builder, DATA)?;
+
+        let expected = [bed_graph::Record::new("sq0", 1, 6, 1.0)];
+        assert_eq!(actual, expected);
+
+        let builder = Builder::default()
+            .set_min_base_quality(13)
+            .set_regions(vec![Region::mapped("sq0", 6..=6)]);
+
+        let actual = calculate(builder, DATA)?;
+
+        let expected = [bed_graph::Record::new("sq0", 5, 6, 1.0)];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_window_size() -> io::Result<()> {
+        let window_size = NonZeroU32::new(3).unwrap();
+        let builder = Builder::default().set_window_size(window_size);
+
+        let actual = calculate(builder, DATA)?;
+
+        let expected = [
+            bed_graph::Record::new("sq0", 0, 3, 1.0),
+            bed_graph::Record::new("sq0", 3, 6, 2.0 / 3.0),
+            bed_graph::Record::new("sq0", 6, 8, 0.0),
+            bed_graph::Record::new("sq1", 0, 3, 2.0 / 3.0),
+            bed_graph::Record::new("sq1", 3, 4, 0.0),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+use std::{io, num::NonZeroU32};
+
+use noodles_core::{Region, RegionSet};
+use noodles_sam::{self as sam, record::Flags, AlignmentRecord};
+
+use super::{Depth, Target};
+use crate::alignment::pileup::{self, OverlapPolicy};
+
+/// An alignment coverage depth builder.
+#[derive(Default)]
+pub struct Builder<'a> {
+    pileup_builder: pileup::Builder<'a>,
+    regions: Vec<Region>,
+    window_size: Option<NonZeroU32>,
+    include_deletions: bool,
+}
+
+impl<'a> Builder<'a> {
+    /// Sets the minimum mapping quality.
+    ///
+    /// Records with a mapping quality less than this are not counted. By default, this is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::depth;
+    /// let builder = depth::Builder::default().set_min_mapping_quality(20);
+    /// ```
+    pub fn set_min_mapping_quality(mut self, min_mapping_quality: u8) -> Self {
+        self.pileup_builder = self
+            .pileup_builder
+            .set_min_mapping_quality(min_mapping_quality);
+        self
+    }
+
+    /// Sets the minimum base quality.
+    ///
+    /// Aligned bases with a quality score less than this are not counted. By default, this is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::depth;
+    /// let builder = depth::Builder::default().set_min_base_quality(13);
+    /// ```
+    pub fn set_min_base_quality(mut self, min_base_quality: u8) -> Self {
+        self.pileup_builder = self.pileup_builder.set_min_base_quality(min_base_quality);
+        self
+    }
+
+    /// Sets the flags of records to skip.
+    ///
+    /// By default, these are unmapped, secondary, QC fail, and duplicate records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Flags;
+    /// use noodles_util::alignment::depth;
+    /// let builder = depth::Builder::default().set_excluded_flags(Flags::UNMAPPED);
+    /// ```
+    pub fn set_excluded_flags(mut self, excluded_flags: Flags) -> Self {
+        self.pileup_builder = self.pileup_builder.set_excluded_flags(excluded_flags);
+        self
+    }
+
+    /// Sets the policy for handling overlapping mates.
+    ///
+    /// By default, overlapping mates are counted once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::{depth, pileup::OverlapPolicy};
+    /// let builder = depth::Builder::default().set_overlap_policy(OverlapPolicy::CountBoth);
+    /// ```
+    pub fn set_overlap_policy(mut self, overlap_policy: OverlapPolicy) -> Self {
+        self.pileup_builder = self.pileup_builder.set_overlap_policy(overlap_policy);
+        self
+    }
+
+    /// Sets the regions to calculate the depth over.
+    ///
+    /// Regions must be mapped or all records (`.`). Overlapping regions are merged. By default,
+    /// the depth is calculated over all reference sequences in the header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Region;
+    /// use noodles_util::alignment::depth;
+    /// let builder = depth::Builder::default().set_regions(vec![Region::mapped("sq0", 8..=13)]);
+    /// ```
+    pub fn set_regions(mut self, regions: Vec<Region>) -> Self {
+        self.regions = regions;
+        self
+    }
+
+    /// Sets the window size.
+    ///
+    /// When set, each output record is a window of this size (or shorter, at the end of a
+    /// region) with the mean depth over the window. Otherwise, each output record is a run of
+    /// positions with the same depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroU32;
+    /// use noodles_util::alignment::depth;
+    ///
+    /// let window_size = NonZeroU32::new(500).unwrap();
+    /// let builder = depth::Builder::default().set_window_size(window_size);
+    /// ```
+    pub fn set_window_size(mut self, window_size: NonZeroU32) -> Self {
+        self.window_size = Some(window_size);
+        self
+    }
+
+    /// Sets whether deletions are counted.
+    ///
+    /// By default, positions deleted in a read are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::depth;
+    /// let builder = depth::Builder::default().set_include_deletions(true);
+    /// ```
+    pub fn set_include_deletions(mut self, include_deletions: bool) -> Self {
+        self.include_deletions = include_deletions;
+        self
+    }
+
+    /// Builds a coverage depth calculator over the given records.
+    ///
+    /// The records must be coordinate-sorted. This returns an error if a region is unmapped or
+    /// its reference sequence is not in the header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{io, iter};
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// use noodles_util::alignment::depth;
+    ///
+    /// let header = sam::Header::default();
+    /// let records = iter::empty::<io::Result<Box<dyn AlignmentRecord>>>();
+    ///
+    /// let mut depth = depth::Builder::default().build(&header, records)?;
+    /// assert!(depth.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build<I>(self, header: &'a sam::Header, records: I) -> io::Result<Depth<'a, I>>
+    where
+        I: Iterator<Item = io::Result<Box<dyn AlignmentRecord>>>,
+    {
+        let targets = resolve_targets(header.reference_sequences(), &self.regions)?;
+        let position = targets
+            .first()
+            .map(|target| target.start)
+            .unwrap_or_default();
+
+        Ok(Depth {
+            header,
+            pileup: self.pileup_builder.build(header, records),
+            pending_column: None,
+            targets,
+            target_index: 0,
+            position,
+            window_size: self.window_size,
+            include_deletions: self.include_deletions,
+        })
+    }
+}
+
+fn resolve_targets(
+    reference_sequences: &sam::header::ReferenceSequences,
+    regions: &[Region],
+) -> io::Result<Vec<Target>> {
+    let mut region_set = RegionSet::new();
+
+    let insert_all = |region_set: &mut RegionSet| {
+        for (name, reference_sequence) in reference_sequences {
+            region_set.insert(name.as_str(), 1..=reference_sequence.len());
+        }
+    };
+
+    if regions.is_empty() {
+        insert_all(&mut region_set);
+    }
+
+    for region in regions {
+        match region {
+            Region::Mapped(mapped) => {
+                if !reference_sequences.contains_key(mapped.name()) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("invalid reference sequence name: {}", mapped.name()),
+                    ));
+                }
+
+                region_set.insert(mapped.name(), mapped.interval());
+            }
+            Region::All => insert_all(&mut region_set),
+            Region::Unmapped => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "unmapped region",
+                ))
+            }
+        }
+    }
+
+    let mut targets = Vec::new();
+
+    for (id, (name, reference_sequence)) in reference_sequences.iter().enumerate() {
+        let interval_set = match region_set.get(name) {
+            Some(interval_set) => interval_set,
+            None => continue,
+        };
+
+        let len = reference_sequence.len();
+
+        for interval in interval_set.overlapping(1..=len) {
+            targets.push(Target {
+                reference_sequence_id: id,
+                start: (*interval.start()).max(1),
+                end: (*interval.end()).min(len),
+            });
+        }
+    }
+
+    Ok(targets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_targets() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::header::ReferenceSequence;
+
+        let reference_sequences = [
+            ReferenceSequence::new("sq0".parse()?, 8)?,
+            ReferenceSequence::new("sq1".parse()?, 13)?,
+        ]
+        .into_iter()
+        .map(|reference_sequence| (reference_sequence.name().to_string(), reference_sequence))
+        .collect();
+
+        let target = |reference_sequence_id, start, end| Target {
+            reference_sequence_id,
+            start,
+            end,
+        };
+
+        assert_eq!(
+            resolve_targets(&reference_sequences, &[])?,
+            [target(0, 1, 8), target(1, 1, 13)]
+        );
+
+        let regions = [
+            Region::mapped("sq1", 5..=21),
+            Region::mapped("sq0", 2..=3),
+            Region::mapped("sq1", 2..=5),
+        ];
+        assert_eq!(
+            resolve_targets(&reference_sequences, &regions)?,
+            [target(0, 2, 3), target(1, 2, 13)]
+        );
+
+        assert!(resolve_targets(&reference_sequences, &[Region::mapped("sq2", ..)]).is_err());
+        assert!(resolve_targets(&reference_sequences, &[Region::Unmapped]).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,402 @@
+//! Sequence dictionaries.
+//!
+//! A sequence dictionary (`.dict`) is a SAM header that only lists the reference sequences of a
+//! reference genome, typically with their lengths, MD5 checksums, and the URI of the FASTA file,
+//! as created by Picard `CreateSequenceDictionary` or `samtools dict`.
+
+use std::{
+    error, fmt,
+    io::{self, BufRead, Write},
+};
+
+use md5::{Digest, Md5};
+use noodles_fasta as fasta;
+use noodles_sam::{
+    self as sam,
+    header::{reference_sequence::Md5Checksum, ReferenceSequence},
+};
+
+/// A sequence dictionary reader.
+pub struct Reader<R> {
+    inner: R,
+}
+
+impl<R> Reader<R>
+where
+    R: BufRead,
+{
+    /// Creates a sequence dictionary reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::dict;
+    /// let data = [];
+    /// let reader = dict::Reader::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Reads the sequence dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::alignment::dict;
+    ///
+    /// let data = b"@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:8\n";
+    /// let mut reader = dict::Reader::new(&data[..]);
+    /// let header = reader.read_header()?;
+    ///
+    /// assert_eq!(header.reference_sequences().len(), 1);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_header(&mut self) -> io::Result<sam::Header> {
+        let mut s = String::new();
+        self.inner.read_to_string(&mut s)?;
+        s.parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// A sequence dictionary writer.
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a sequence dictionary writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::dict;
+    /// let writer = dict::Writer::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::dict;
+    /// let writer = dict::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Writes a sequence dictionary.
+    ///
+    /// Only the header (`@HD`) and reference sequence (`@SQ`) records are written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, header::ReferenceSequence};
+    /// use noodles_util::alignment::dict;
+    ///
+    /// let header = sam::Header::builder()
+    ///     .set_header(Default::default())
+    ///     .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+    ///     .add_comment("noodles")
+    ///     .build();
+    ///
+    /// let mut writer = dict::Writer::new(Vec::new());
+    /// writer.write_header(&header)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:8\n");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        if let Some(hd) = header.header() {
+            writeln!(self.inner, "{}", hd)?;
+        }
+
+        for reference_sequence in header.reference_sequences().values() {
+            writeln!(self.inner, "{}", reference_sequence)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Builds a sequence dictionary from the records of a FASTA reader.
+///
+/// Each reference sequence has its length and MD5 checksum set. As described in the SAM
+/// specification, the MD5 checksum is calculated over the uppercase sequence without whitespace.
+/// If given, the URI of each reference sequence is set to `uri`.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_fasta as fasta;
+/// use noodles_util::alignment::dict;
+///
+/// let data = b">sq0\nacgt\n>sq1\nNNNN\nNNNN\n";
+/// let mut reader = fasta::Reader::new(&data[..]);
+/// let header = dict::build(&mut reader, Some("file:///tmp/ref.fa"))?;
+///
+/// let reference_sequences = header.reference_sequences();
+/// assert_eq!(reference_sequences.len(), 2);
+///
+/// let sq0 = &reference_sequences["sq0"];
+/// assert_eq!(sq0.len(), 4);
+/// assert_eq!(
+///     sq0.md5_checksum().map(|checksum| checksum.to_string()),
+///     Some(String::from("f1f8f4bf413b16ad135722aa4591043e"))
+/// );
+/// assert_eq!(sq0.uri(), Some("file:///tmp/ref.fa"));
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn build<R>(reader: &mut fasta::Reader<R>, uri: Option<&str>) -> io::Result<sam::Header>
+where
+    R: BufRead,
+{
+    let mut builder = sam::Header::builder().set_header(Default::default());
+
+    for result in reader.records() {
+        let record = result?;
+
+        let name = record
+            .name()
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let len = i32::try_from(record.sequence().len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut reference_sequence_builder = ReferenceSequence::builder()
+            .set_name(name)
+            .set_length(len)
+            .set_md5_checksum(calculate_md5_checksum(record.sequence().as_ref()));
+
+        if let Some(uri) = uri {
+            reference_sequence_builder = reference_sequence_builder.set_uri(uri);
+        }
+
+        let reference_sequence = reference_sequence_builder
+            .build()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        builder = builder.add_reference_sequence(reference_sequence);
+    }
+
+    Ok(builder.build())
+}
+
+/// Validates the reference sequences of an alignment header against a sequence dictionary.
+///
+/// Every reference sequence in the header must be in the dictionary with the same length and, if
+/// both have one, the same MD5 checksum. Reference sequences in the dictionary that are not in the
+/// header are allowed.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_sam::{self as sam, header::ReferenceSequence};
+/// use noodles_util::alignment::dict::{self, ValidationError};
+///
+/// let dictionary = sam::Header::builder()
+///     .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+///     .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 13)?)
+///     .build();
+///
+/// let header = sam::Header::builder()
+///     .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+///     .build();
+/// assert!(dict::validate(&header, &dictionary).is_ok());
+///
+/// let header = sam::Header::builder()
+///     .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 21)?)
+///     .build();
+/// assert_eq!(
+///     dict::validate(&header, &dictionary),
+///     Err(ValidationError::LengthMismatch {
+///         name: String::from("sq1"),
+///         actual: 21,
+///         expected: 13,
+///     })
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn validate(header: &sam::Header, dictionary: &sam::Header) -> Result<(), ValidationError> {
+    let expected_reference_sequences = dictionary.reference_sequences();
+
+    for (name, actual) in header.reference_sequences() {
+        let expected = expected_reference_sequences
+            .get(name)
+            .ok_or_else(|| ValidationError::MissingReferenceSequence(name.clone()))?;
+
+        if actual.len() != expected.len() {
+            return Err(ValidationError::LengthMismatch {
+                name: name.clone(),
+                actual: actual.len(),
+                expected: expected.len(),
+            });
+        }
+
+        if let (Some(actual), Some(expected)) = (actual.md5_checksum(), expected.md5_checksum()) {
+            if actual != expected {
+                return Err(ValidationError::Md5ChecksumMismatch {
+                    name: name.clone(),
+                    actual,
+                    expected,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// An error returned when an alignment header fails validation against a sequence dictionary.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationError {
+    /// A reference sequence is not in the dictionary.
+    MissingReferenceSequence(String),
+    /// A reference sequence length does not match the dictionary.
+    LengthMismatch {
+        /// The reference sequence name.
+        name: String,
+        /// The length in the header.
+        actual: i32,
+        /// The length in the dictionary.
+        expected: i32,
+    },
+    /// A reference sequence MD5 checksum does not match the dictionary.
+    Md5ChecksumMismatch {
+        /// The reference sequence name.
+        name: String,
+        /// The MD5 checksum in the header.
+        actual: Md5Checksum,
+        /// The MD5 checksum in the dictionary.
+        expected: Md5Checksum,
+    },
+}
+
+impl error::Error for ValidationError {}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingReferenceSequence(name) => {
+                write!(f, "missing reference sequence: {}", name)
+            }
+            Self::LengthMismatch {
+                name,
+                actual,
+                expected,
+            } => write!(
+                f,
+                "reference sequence length mismatch: {}: expected {}, got {}",
+                name, expected, actual
+            ),
+            Self::Md5ChecksumMismatch {
+                name,
+                actual,
+                expected,
+            } => write!(
+                f,
+                "reference sequence MD5 checksum mismatch: {}: expected {}, got {}",
+                name, expected, actual
+            ),
+        }
+    }
+}
+
+fn calculate_md5_checksum(sequence: &[u8]) -> Md5Checksum {
+    let mut hasher = Md5::new();
+
+    for chunk in sequence.split(|b| !b.is_ascii_graphic()) {
+        hasher.update(chunk.to_ascii_uppercase());
+    }
+
+    <[u8; 16]>::from(hasher.finalize()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_md5_checksum() {
+        assert_eq!(
+            calculate_md5_checksum(b"ACGT").to_string(),
+            "f1f8f4bf413b16ad135722aa4591043e"
+        );
+        assert_eq!(
+            calculate_md5_checksum(b"ac g\tt"),
+            calculate_md5_checksum(b"ACGT")
+        );
+    }
+
+    #[test]
+    fn test_read_header_and_write_header() -> Result<(), Box<dyn std::error::Error>> {
+        let data = b">sq0\nACGT\n>sq1\nNNNN\n";
+        let mut reader = fasta::Reader::new(&data[..]);
+        let header = build(&mut reader, None)?;
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header(&header)?;
+
+        let mut reader = Reader::new(&writer.get_ref()[..]);
+        assert_eq!(reader.read_header()?, header);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let data = b">sq0\nACGT\n";
+        let mut reader = fasta::Reader::new(&data[..]);
+        let dictionary = build(&mut reader, None)?;
+
+        assert!(validate(&dictionary, &dictionary).is_ok());
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 4)?)
+            .build();
+        assert_eq!(
+            validate(&header, &dictionary),
+            Err(ValidationError::MissingReferenceSequence(String::from(
+                "sq1"
+            )))
+        );
+
+        let expected = calculate_md5_checksum(b"ACGT");
+        let actual = calculate_md5_checksum(b"NNNN");
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name("sq0".parse()?)
+                    .set_length(4)
+                    .set_md5_checksum(actual)
+                    .build()?,
+            )
+            .build();
+        assert_eq!(
+            validate(&header, &dictionary),
+            Err(ValidationError::Md5ChecksumMismatch {
+                name: String::from("sq0"),
+                actual,
+                expected,
+            })
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,501 @@
+//! Alignment record merging.
+//!
+//! Coordinate-sorted alignment records from multiple inputs, which may be of different formats,
+//! can be merged into a single coordinate-sorted stream, similar to `samtools merge`.
+
+mod record;
+
+use std::{cmp::Reverse, collections::BinaryHeap, io};
+
+use noodles_sam::{
+    self as sam,
+    header::{header::SortOrder, ReferenceSequence},
+    AlignmentRecord,
+};
+
+use self::record::Record;
+
+// The sort key of a record: its reference sequence ID (with unmapped records last) and alignment
+// start.
+type Key = (usize, i32);
+
+/// Merges alignment headers.
+///
+/// The reference sequences of the merged header are the union of the reference sequences of the
+/// given headers, in the order they first appear. A reference sequence that appears in more than
+/// one header must have the same length in each. For read groups and programs with the same ID,
+/// the first is kept. Comments are concatenated.
+///
+/// The merged header is marked as coordinate-sorted and uses the format version of the first
+/// header that has one.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{self as sam, header::ReferenceSequence};
+/// use noodles_util::alignment::merge;
+///
+/// let header0 = sam::Header::builder()
+///     .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+///     .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 13)?)
+///     .build();
+///
+/// let header1 = sam::Header::builder()
+///     .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 13)?)
+///     .add_reference_sequence(ReferenceSequence::new("sq2".parse()?, 21)?)
+///     .build();
+///
+/// let header = merge::merge_headers(&[&header0, &header1])?;
+///
+/// let names: Vec<_> = header.reference_sequences().keys().collect();
+/// assert_eq!(names, ["sq0", "sq1", "sq2"]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn merge_headers(headers: &[&sam::Header]) -> io::Result<sam::Header> {
+    let version = headers
+        .iter()
+        .find_map(|header| header.header().map(|hd| hd.version()))
+        .unwrap_or_default();
+
+    let mut merged_header = sam::Header::builder()
+        .set_header(
+            sam::header::header::Header::builder()
+                .set_version(version)
+                .set_sort_order(SortOrder::Coordinate)
+                .build(),
+        )
+        .build();
+
+    for header in headers {
+        for (name, reference_sequence) in header.reference_sequences() {
+            merge_reference_sequence(&mut merged_header, name, reference_sequence)?;
+        }
+
+        for (id, read_group) in header.read_groups() {
+            merged_header
+                .read_groups_mut()
+                .entry(id.clone())
+                .or_insert_with(|| read_group.clone());
+        }
+
+        for (id, program) in header.programs() {
+            merged_header
+                .programs_mut()
+                .entry(id.clone())
+                .or_insert_with(|| program.clone());
+        }
+
+        merged_header
+            .comments_mut()
+            .extend(header.comments().iter().cloned());
+    }
+
+    Ok(merged_header)
+}
+
+fn merge_reference_sequence(
+    header: &mut sam::Header,
+    name: &str,
+    reference_sequence: &ReferenceSequence,
+) -> io::Result<()> {
+    match header.reference_sequences().get(name) {
+        Some(merged_reference_sequence) => {
+            if merged_reference_sequence.len() == reference_sequence.len() {
+                Ok(())
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("reference sequence length mismatch: {}", name),
+                ))
+            }
+        }
+        None => {
+            header
+                .reference_sequences_mut()
+                .insert(name.into(), reference_sequence.clone());
+
+            Ok(())
+        }
+    }
+}
+
+/// A merge of coordinate-sorted alignment records.
+///
+/// This is an iterator over the records of multiple inputs in coordinate order. Each input is a
+/// header and an iterator over its records, e.g., from [`sam::AlignmentReader`], so inputs of
+/// different formats (SAM, BAM, and CRAM) can be merged.
+///
+/// Returned records are associated with the merged header (see [`merge_headers`]), i.e., their
+/// reference sequence IDs are translated from the header of their input. Records with equal
+/// positions are returned in input order. Unmapped records without a reference sequence are
+/// returned last.
+///
+/// Each input must be coordinate-sorted with respect to the merged header; otherwise, an error is
+/// returned when an out-of-order record is read. In particular, inputs should list common
+/// reference sequences in the same order.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_sam::{self as sam, AlignmentReader};
+/// use noodles_util::alignment::merge::{self, Merge};
+///
+/// let data0 = b"@SQ\tSN:sq0\tLN:8
+/// r0\t0\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS
+/// r2\t0\tsq0\t5\t60\t4M\t*\t0\t0\tACGT\tNDLS
+/// ";
+/// let mut reader0 = sam::Reader::new(&data0[..]);
+/// let header0 = reader0.read_alignment_header()?;
+///
+/// let data1 = b"@SQ\tSN:sq0\tLN:8
+/// r1\t0\tsq0\t3\t60\t4M\t*\t0\t0\tGTAC\tNDLS
+/// ";
+/// let mut reader1 = sam::Reader::new(&data1[..]);
+/// let header1 = reader1.read_alignment_header()?;
+///
+/// let header = merge::merge_headers(&[&header0, &header1])?;
+///
+/// let records = Merge::new(
+///     &header,
+///     vec![
+///         (&header0, reader0.alignment_records(&header0)),
+///         (&header1, reader1.alignment_records(&header1)),
+///     ],
+/// )?;
+///
+/// let names = records
+///     .map(|result| result.map(|record| record.read_name().map(|name| name.to_vec())))
+///     .collect::<io::Result<Vec<_>>>()?;
+///
+/// assert_eq!(names, [
+///     Some(b"r0".to_vec()),
+///     Some(b"r1".to_vec()),
+///     Some(b"r2".to_vec()),
+/// ]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct Merge<'a> {
+    sources: Vec<Source<'a>>,
+    pending_records: Vec<Option<Record>>,
+    queue: BinaryHeap<Reverse<(Key, usize)>>,
+}
+
+impl<'a> Merge<'a> {
+    /// Creates a merge of coordinate-sorted alignment records.
+    ///
+    /// `header` is the merged header of the inputs, and `inputs` is a list of headers and their
+    /// record iterators. This returns an error if a reference sequence of an input header is
+    /// missing from the merged header.
+    pub fn new(
+        header: &sam::Header,
+        inputs: Vec<(&'a sam::Header, sam::AlignmentRecords<'a>)>,
+    ) -> io::Result<Self> {
+        let mut sources = Vec::with_capacity(inputs.len());
+
+        for (source_header, records) in inputs {
+            let reference_sequence_ids = source_header
+                .reference_sequences()
+                .keys()
+                .map(|name| {
+                    header
+                        .reference_sequences()
+                        .get_index_of(name)
+                        .ok_or_else(|| {
+                            io::Error::new(
+                                io::ErrorKind::InvalidInput,
+                                format!("missing reference sequence in merged header: {}", name),
+                            )
+                        })
+                })
+                .collect::<io::Result<_>>()?;
+
+            sources.push(Source {
+                header: source_header,
+                records,
+                reference_sequence_ids,
+                last_key: None,
+            });
+        }
+
+        let mut merge = Self {
+            pending_records: (0..sources.len()).map(|_| None).collect(),
+            sources,
+            queue: BinaryHeap::new(),
+        };
+
+        for i in 0..merge.sources.len() {
+            merge.fill(i)?;
+        }
+
+        Ok(merge)
+    }
+
+    // Reads the next record of the given source into the queue.
+    fn fill(&mut self, i: usize) -> io::Result<()> {
+        if let Some(record) = self.sources[i].next_record()? {
+            let key = sort_key(&record);
+            self.pending_records[i] = Some(record);
+            self.queue.push(Reverse((key, i)));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for Merge<'a> {
+    type Item = io::Result<Box<dyn AlignmentRecord>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((_, i)) = self.queue.pop()?;
+        let record = self.pending_records[i].take()?;
+
+        if let Err(e) = self.fill(i) {
+            return Some(Err(e));
+        }
+
+        Some(Ok(Box::new(record)))
+    }
+}
+
+struct Source<'a> {
+    header: &'a sam::Header,
+    records: sam::AlignmentRecords<'a>,
+    reference_sequence_ids: Vec<usize>,
+    last_key: Option<Key>,
+}
+
+impl<'a> Source<'a> {
+    fn next_record(&mut self) -> io::Result<Option<Record>> {
+        let record = match self.records.next().transpose()? {
+            Some(record) => record,
+            None => return Ok(None),
+        };
+
+        let reference_sequence_id = self.translate_reference_sequence(
+            record.reference_sequence(self.header.reference_sequences()),
+        )?;
+
+        let mate_reference_sequence_id = self.translate_reference_sequence(
+            record.mate_reference_sequence(self.header.reference_sequences()),
+        )?;
+
+        let record = Record::new(record, reference_sequence_id, mate_reference_sequence_id);
+        let key = sort_key(&record);
+
+        if matches!(self.last_key, Some(last_key) if key < last_key) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "input is not coordinate-sorted",
+            ));
+        }
+
+        self.last_key = Some(key);
+
+        Ok(Some(record))
+    }
+
+    fn translate_reference_sequence(
+        &self,
+        reference_sequence: Option<io::Result<&ReferenceSequence>>,
+    ) -> io::Result<Option<usize>> {
+        let reference_sequence = match reference_sequence.transpose()? {
+            Some(reference_sequence) => reference_sequence,
+            None => return Ok(None),
+        };
+
+        self.header
+            .reference_sequences()
+            .get_index_of(reference_sequence.name().as_str())
+            .map(|i| Some(self.reference_sequence_ids[i]))
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid reference sequence ID")
+            })
+    }
+}
+
+fn sort_key(record: &Record) -> Key {
+    let reference_sequence_id = record.reference_sequence_id().unwrap_or(usize::MAX);
+    let alignment_start = record.alignment_start().map(i32::from).unwrap_or_default();
+    (reference_sequence_id, alignment_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bam as bam;
+    use noodles_cram as cram;
+    use noodles_fasta as fasta;
+    use sam::{header::ReferenceSequence, AlignmentReader, AlignmentWriter};
+
+    use super::*;
+
+    #[test]
+    fn test_merge_headers() -> Result<(), Box<dyn std::error::Error>> {
+        let header0 = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .add_comment("noodles")
+            .build();
+
+        let header1 = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 13)?)
+            .build();
+
+        let actual = merge_headers(&[&header0, &header1])?;
+
+        let expected = sam::Header::builder()
+            .set_header(
+                sam::header::header::Header::builder()
+                    .set_sort_order(SortOrder::Coordinate)
+                    .build(),
+            )
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 13)?)
+            .add_comment("noodles")
+            .build();
+
+        assert_eq!(actual, expected);
+
+        let header2 = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 21)?)
+            .build();
+
+        assert!(matches!(
+            merge_headers(&[&header1, &header2]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next() -> Result<(), Box<dyn std::error::Error>> {
+        let bam_header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 8)?)
+            .build();
+
+        let mut bam_writer = bam::Writer::new(Vec::new());
+        bam_writer.write_alignment_header(&bam_header)?;
+
+        for s in [
+            "r0\t0\tsq0\t3\t60\t4M\t*\t0\t0\tACGT\tNDLS",
+            "r2\t0\tsq1\t2\t60\t4M\tsq1\t5\t0\tCGTA\tNDLS",
+            "r4\t4\t*\t0\t0\t*\t*\t0\t0\tACGT\tNDLS",
+        ] {
+            bam_writer.write_alignment_record(&bam_header, &s.parse::<sam::Record>()?)?;
+        }
+
+        bam_writer.finish(&bam_header)?;
+
+        let reference_sequences = vec![fasta::Record::new(
+            fasta::record::Definition::new("sq1", None),
+            fasta::record::Sequence::from(b"ACGTACGT".to_vec()),
+        )];
+
+        let cram_header = sam::Header::builder()
+            .add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name("sq1".parse()?)
+                    .set_length(8)
+                    .set_md5_checksum(
+                        [
+                            0xd7, 0xeb, 0xa3, 0x11, 0x42, 0x1b, 0xbc, 0x9d, 0x3a, 0xda, 0x44, 0x70,
+                            0x9d, 0xd6, 0x15, 0x34,
+                        ]
+                        .into(),
+                    )
+                    .build()?,
+            )
+            .build();
+
+        let mut cram_writer = cram::Writer::new(Vec::new(), reference_sequences);
+        cram_writer.write_alignment_header(&cram_header)?;
+
+        for s in [
+            "r1\t0\tsq1\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS",
+            "r3\t0\tsq1\t5\t60\t4M\t*\t0\t0\tACGT\tNDLS",
+        ] {
+            cram_writer.write_alignment_record(&cram_header, &s.parse::<sam::Record>()?)?;
+        }
+
+        cram_writer.finish(&cram_header)?;
+
+        let mut bam_reader = bam::Reader::new(&bam_writer.get_ref().get_ref()[..]);
+        let bam_header = bam_reader.read_alignment_header()?;
+
+        let mut cram_reader = cram::Reader::new(&cram_writer.get_ref()[..]);
+        let cram_header = cram_reader.read_alignment_header()?;
+
+        let header = merge_headers(&[&bam_header, &cram_header])?;
+
+        let merge = Merge::new(
+            &header,
+            vec![
+                (&bam_header, bam_reader.alignment_records(&bam_header)),
+                (&cram_header, cram_reader.alignment_records(&cram_header)),
+            ],
+        )?;
+
+        let mut actual = Vec::new();
+
+        for result in merge {
+            let record = result?;
+
+            let reference_sequence_name = record
+                .reference_sequence(header.reference_sequences())
+                .transpose()?
+                .map(|reference_sequence| reference_sequence.name().to_string());
+
+            let mate_reference_sequence_name = record
+                .mate_reference_sequence(header.reference_sequences())
+                .transpose()?
+                .map(|reference_sequence| reference_sequence.name().to_string());
+
+            actual.push((
+                record.read_name().map(|name| name.to_vec()),
+                reference_sequence_name,
+                mate_reference_sequence_name,
+            ));
+        }
+
+        let sq0 = Some(String::from("sq0"));
+        let sq1 = Some(String::from("sq1"));
+
+        let expected = [
+            (Some(b"r0".to_vec()), sq0, None),
+            (Some(b"r1".to_vec()), sq1.clone(), None),
+            (Some(b"r2".to_vec()), sq1.clone(), sq1.clone()),
+            (Some(b"r3".to_vec()), sq1, None),
+            (Some(b"r4".to_vec()), None, None),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_unsorted_input() -> io::Result<()> {
+        let data = b"@SQ\tSN:sq0\tLN:8
+r0\t0\tsq0\t5\t60\t4M\t*\t0\t0\tACGT\tNDLS
+r1\t0\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS
+";
+
+        let mut reader = sam::Reader::new(&data[..]);
+        let source_header = reader.read_alignment_header()?;
+        let header = merge_headers(&[&source_header])?;
+
+        let mut merge = Merge::new(
+            &header,
+            vec![(&source_header, reader.alignment_records(&source_header))],
+        )?;
+
+        assert!(matches!(
+            merge.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use std::io;
+
+use noodles_sam::{
+    header::{ReferenceSequence, ReferenceSequences},
+    record::{Cigar, Data, Flags, MappingQuality, Position, QualityScores, Sequence},
+    AlignmentRecord,
+};
+
+// An alignment record with reference sequence IDs translated to the merged header.
+pub(super) struct Record {
+    inner: Box<dyn AlignmentRecord>,
+    reference_sequence_id: Option<usize>,
+    mate_reference_sequence_id: Option<usize>,
+}
+
+impl Record {
+    pub(super) fn new(
+        inner: Box<dyn AlignmentRecord>,
+        reference_sequence_id: Option<usize>,
+        mate_reference_sequence_id: Option<usize>,
+    ) -> Self {
+        Self {
+            inner,
+            reference_sequence_id,
+            mate_reference_sequence_id,
+        }
+    }
+
+    pub(super) fn reference_sequence_id(&self) -> Option<usize> {
+        self.reference_sequence_id
+    }
+}
+
+impl AlignmentRecord for Record {
+    fn read_name(&self) -> Option<&[u8]> {
+        self.inner.read_name()
+    }
+
+    fn flags(&self) -> Flags {
+        self.inner.flags()
+    }
+
+    fn reference_sequence<'rs>(
+        &self,
+        reference_sequences: &'rs ReferenceSequences,
+    ) -> Option<io::Result<&'rs ReferenceSequence>> {
+        get_reference_sequence(reference_sequences, self.reference_sequence_id)
+    }
+
+    fn alignment_start(&self) -> Option<Position> {
+        self.inner.alignment_start()
+    }
+
+    fn alignment_span(&self) -> io::Result<u32> {
+        self.inner.alignment_span()
+    }
+
+    fn mapping_quality(&self) -> Option<MappingQuality> {
+        self.inner.mapping_quality()
+    }
+
+    fn mate_reference_sequence<'rs>(
+        &self,
+        reference_sequences: &'rs ReferenceSequences,
+    ) -> Option<io::Result<&'rs ReferenceSequence>> {
+        get_reference_sequence(reference_sequences, self.mate_reference_sequence_id)
+    }
+
+    fn mate_alignment_start(&self) -> Option<Position> {
+        self.inner.mate_alignment_start()
+    }
+
+    fn template_length(&self) -> i32 {
+        self.inner.template_length()
+    }
+
+    fn cigar(&self) -> io::Result<Cigar> {
+        self.inner.cigar()
+    }
+
+    fn sequence(&self) -> io::Result<Sequence> {
+        self.inner.sequence()
+    }
+
+    fn quality_scores(&self) -> io::Result<QualityScores> {
+        self.inner.quality_scores()
+    }
+
+    fn data(&self) -> io::Result<Data> {
+        self.inner.data()
+    }
+}
+
+fn get_reference_sequence(
+    reference_sequences: &ReferenceSequences,
+    reference_sequence_id: Option<usize>,
+) -> Option<io::Result<&ReferenceSequence>> {
+    reference_sequence_id.map(|id| {
+        reference_sequences
+            .get_index(id)
+            .map(|(_, reference_sequence)| reference_sequence)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid reference sequence ID")
+            })
+    })
+}
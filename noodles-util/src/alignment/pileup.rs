@@ -0,0 +1,560 @@
+//! Alignment pileup.
+//!
+//! A pileup is the list of columns over reference sequence positions, where each column holds the
+//! aligned bases (and deletions) of the records that overlap the position.
+
+mod baq;
+mod builder;
+pub mod column;
+mod overlap_policy;
+
+pub use self::{baq::Baq, builder::Builder, column::Column, overlap_policy::OverlapPolicy};
+
+use std::{collections::HashMap, io};
+
+use noodles_sam::{
+    self as sam,
+    record::{cigar::op::Kind, Flags, Position},
+    AlignmentRecord,
+};
+
+use self::column::{Entry, Indel};
+
+/// An alignment pileup.
+///
+/// This is an iterator over columns built from a stream of coordinate-sorted alignment records,
+/// e.g., from [`sam::AlignmentReader::alignment_records`]. Only positions covered by at least one
+/// entry are returned. Reference skips (`N`) are not included in columns.
+///
+/// Use [`Builder`] to configure record filters, overlapping mate handling, and base alignment
+/// quality (BAQ) calculation.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_sam::{self as sam, AlignmentReader};
+/// use noodles_util::alignment::pileup;
+///
+/// let data = b"@HD\tVN:1.6\tSO:coordinate
+/// @SQ\tSN:sq0\tLN:8
+/// r0\t0\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS
+/// r1\t16\tsq0\t3\t60\t4M\t*\t0\t0\tGTAC\tNDLS
+/// ";
+///
+/// let mut reader = sam::Reader::new(&data[..]);
+/// let header = reader.read_alignment_header()?;
+///
+/// let depths: Vec<_> = pileup::Builder::default()
+///     .build(&header, reader.alignment_records(&header))
+///     .map(|result| result.map(|column| column.depth()))
+///     .collect::<io::Result<_>>()?;
+///
+/// assert_eq!(depths, [1, 1, 2, 2, 1, 1]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct Pileup<'a, I>
+where
+    I: Iterator<Item = io::Result<Box<dyn AlignmentRecord>>>,
+{
+    header: &'a sam::Header,
+    records: I,
+    min_mapping_quality: u8,
+    min_base_quality: u8,
+    excluded_flags: Flags,
+    overlap_policy: OverlapPolicy,
+    baq: Option<Box<dyn Baq + 'a>>,
+    record_count: usize,
+    last_start: Option<(usize, i32)>,
+    pending_read: Option<Read>,
+    active_reads: Vec<Read>,
+    reference_sequence_id: usize,
+    position: i32,
+}
+
+impl<'a, I> Pileup<'a, I>
+where
+    I: Iterator<Item = io::Result<Box<dyn AlignmentRecord>>>,
+{
+    fn next_column(&mut self) -> io::Result<Option<Column>> {
+        loop {
+            if self.active_reads.is_empty() {
+                match self.next_read()? {
+                    Some(read) => {
+                        self.reference_sequence_id = read.reference_sequence_id;
+                        self.position = read.start;
+                        self.active_reads.push(read);
+                    }
+                    None => return Ok(None),
+                }
+            }
+
+            let (reference_sequence_id, position) = (self.reference_sequence_id, self.position);
+
+            while let Some(read) = self.peek_read()? {
+                if read.reference_sequence_id != reference_sequence_id || read.start > position {
+                    break;
+                }
+
+                if let Some(read) = self.pending_read.take() {
+                    self.active_reads.push(read);
+                }
+            }
+
+            self.active_reads.retain(|read| read.end >= position);
+
+            if self.active_reads.is_empty() {
+                continue;
+            }
+
+            let entries = self.build_entries();
+            self.position += 1;
+
+            if !entries.is_empty() {
+                let position = Position::try_from(position)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+                return Ok(Some(Column::new(
+                    self.reference_sequence_id,
+                    position,
+                    entries,
+                )));
+            }
+        }
+    }
+
+    fn build_entries(&self) -> Vec<Entry> {
+        let mut entries: Vec<Entry> = Vec::with_capacity(self.active_reads.len());
+        let mut mates: HashMap<&[u8], usize> = HashMap::new();
+
+        for read in &self.active_reads {
+            let i = (self.position - read.start) as usize;
+
+            let entry = match &read.entries[i] {
+                Some(entry) => entry,
+                None => continue,
+            };
+
+            if let Some(quality_score) = entry.quality_score {
+                if quality_score < self.min_base_quality {
+                    continue;
+                }
+            }
+
+            if self.overlap_policy == OverlapPolicy::CountOnce && entry.flags.is_segmented() {
+                if let Some(read_name) = read.read_name.as_deref() {
+                    if let Some(&j) = mates.get(read_name) {
+                        if entry_score(entry) > entry_score(&entries[j]) {
+                            entries[j] = entry.clone();
+                        }
+
+                        continue;
+                    }
+
+                    mates.insert(read_name, entries.len());
+                }
+            }
+
+            entries.push(entry.clone());
+        }
+
+        entries
+    }
+
+    fn peek_read(&mut self) -> io::Result<Option<&Read>> {
+        if self.pending_read.is_none() {
+            self.pending_read = self.read_next_read()?;
+        }
+
+        Ok(self.pending_read.as_ref())
+    }
+
+    fn next_read(&mut self) -> io::Result<Option<Read>> {
+        match self.pending_read.take() {
+            Some(read) => Ok(Some(read)),
+            None => self.read_next_read(),
+        }
+    }
+
+    fn read_next_read(&mut self) -> io::Result<Option<Read>> {
+        loop {
+            let record = match self.records.next() {
+                Some(result) => result?,
+                None => return Ok(None),
+            };
+
+            let record_index = self.record_count;
+            self.record_count += 1;
+
+            if let Some(read) = self.build_read(record_index, record.as_ref())? {
+                let start = (read.reference_sequence_id, read.start);
+
+                if let Some(last_start) = self.last_start {
+                    if start < last_start {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "records are not coordinate-sorted",
+                        ));
+                    }
+                }
+
+                self.last_start = Some(start);
+
+                return Ok(Some(read));
+            }
+        }
+    }
+
+    fn build_read(
+        &mut self,
+        record_index: usize,
+        record: &dyn AlignmentRecord,
+    ) -> io::Result<Option<Read>> {
+        let flags = record.flags();
+
+        if flags.is_unmapped() || flags.intersects(self.excluded_flags) {
+            return Ok(None);
+        }
+
+        let mapping_quality = record.mapping_quality();
+
+        if let Some(mapq) = mapping_quality.map(u8::from) {
+            if mapq < self.min_mapping_quality {
+                return Ok(None);
+            }
+        }
+
+        let reference_sequences = self.header.reference_sequences();
+
+        let reference_sequence_id = match record.reference_sequence(reference_sequences) {
+            Some(result) => {
+                let reference_sequence = result?;
+
+                reference_sequences
+                    .get_index_of(reference_sequence.name().as_str())
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "invalid reference sequence name",
+                        )
+                    })?
+            }
+            None => return Ok(None),
+        };
+
+        let start = match record.alignment_start() {
+            Some(position) => i32::from(position),
+            None => return Ok(None),
+        };
+
+        let cigar = record.cigar()?;
+        let sequence = record.sequence()?;
+
+        let mut quality_scores: Vec<_> = record
+            .quality_scores()?
+            .iter()
+            .map(|&score| u8::from(score))
+            .collect();
+
+        if let Some(baq) = self.baq.as_mut() {
+            baq.apply(self.header, record, &mut quality_scores)?;
+        }
+
+        let mut entries: Vec<Option<Entry>> = Vec::new();
+        let mut query_position = 0;
+
+        for op in cigar.iter() {
+            let len = op.len() as usize;
+
+            match op.kind() {
+                Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                    for i in query_position..query_position + len {
+                        entries.push(Some(Entry {
+                            record_index,
+                            flags,
+                            mapping_quality,
+                            query_position: Some(i),
+                            base: sequence.get(i).copied(),
+                            quality_score: quality_scores.get(i).copied(),
+                            indel: None,
+                            is_head: false,
+                            is_tail: false,
+                        }));
+                    }
+
+                    query_position += len;
+                }
+                Kind::Insertion => {
+                    let bases = sequence
+                        .get(query_position..query_position + len)
+                        .map(|bases| bases.to_vec())
+                        .unwrap_or_default();
+
+                    set_indel(&mut entries, Indel::Insertion(bases));
+
+                    query_position += len;
+                }
+                Kind::Deletion => {
+                    set_indel(&mut entries, Indel::Deletion(op.len()));
+
+                    for _ in 0..len {
+                        entries.push(Some(Entry {
+                            record_index,
+                            flags,
+                            mapping_quality,
+                            query_position: None,
+                            base: None,
+                            quality_score: None,
+                            indel: None,
+                            is_head: false,
+                            is_tail: false,
+                        }));
+                    }
+                }
+                Kind::Skip => entries.extend((0..len).map(|_| None)),
+                Kind::SoftClip => query_position += len,
+                Kind::HardClip | Kind::Pad => {}
+            }
+        }
+
+        if let Some(entry) = entries
+            .iter_mut()
+            .flatten()
+            .find(|entry| !entry.is_deletion())
+        {
+            entry.is_head = true;
+        }
+
+        if let Some(entry) = entries
+            .iter_mut()
+            .rev()
+            .flatten()
+            .find(|entry| !entry.is_deletion())
+        {
+            entry.is_tail = true;
+        }
+
+        if entries.is_empty() {
+            return Ok(None);
+        }
+
+        let end = start + entries.len() as i32 - 1;
+
+        Ok(Some(Read {
+            read_name: record.read_name().map(|name| name.to_vec()),
+            reference_sequence_id,
+            start,
+            end,
+            entries,
+        }))
+    }
+}
+
+impl<'a, I> Iterator for Pileup<'a, I>
+where
+    I: Iterator<Item = io::Result<Box<dyn AlignmentRecord>>>,
+{
+    type Item = io::Result<Column>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_column().transpose()
+    }
+}
+
+struct Read {
+    read_name: Option<Vec<u8>>,
+    reference_sequence_id: usize,
+    start: i32,
+    end: i32,
+    entries: Vec<Option<Entry>>,
+}
+
+// Attaches an indel to the last aligned base.
+fn set_indel(entries: &mut [Option<Entry>], indel: Indel) {
+    if let Some(Some(entry)) = entries.last_mut() {
+        if !entry.is_deletion() {
+            entry.indel = Some(indel);
+        }
+    }
+}
+
+fn entry_score(entry: &Entry) -> (bool, u8) {
+    (!entry.is_deletion(), entry.quality_score.unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_records(data: &[u8]) -> io::Result<(sam::Header, Vec<sam::Record>)> {
+        use sam::AlignmentReader;
+
+        let mut reader = sam::Reader::new(data);
+        let header = reader.read_alignment_header()?;
+        let records = reader.records().collect::<io::Result<_>>()?;
+
+        Ok((header, records))
+    }
+
+    fn build_pileup<'a>(
+        builder: Builder<'a>,
+        header: &'a sam::Header,
+        records: Vec<sam::Record>,
+    ) -> io::Result<Vec<Column>> {
+        let records = records
+            .into_iter()
+            .map(|record| Ok(Box::new(record) as Box<dyn AlignmentRecord>));
+
+        builder.build(header, records).collect()
+    }
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        use sam::record::sequence::Base;
+
+        let (header, records) = build_records(
+            b"@SQ\tSN:sq0\tLN:16
+@SQ\tSN:sq1\tLN:16
+r0\t0\tsq0\t2\t60\t2M1I1M2D1M\t*\t0\t0\tACGTA\tNDLSN
+r1\t16\tsq0\t4\t60\t1M2N1M\t*\t0\t0\tTC\tNN
+r2\t0\tsq1\t1\t60\t1M\t*\t0\t0\tG\tN
+",
+        )?;
+
+        let columns = build_pileup(Builder::default(), &header, records)?;
+
+        let actual: Vec<_> = columns
+            .iter()
+            .map(|column| {
+                (
+                    column.reference_sequence_id(),
+                    i32::from(column.position()),
+                    column.depth(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            actual,
+            [
+                (0, 2, 1),
+                (0, 3, 1),
+                (0, 4, 2),
+                (0, 5, 1),
+                (0, 6, 1),
+                (0, 7, 2),
+                (1, 1, 1)
+            ]
+        );
+
+        let entry = &columns[1].entries()[0];
+        assert_eq!(entry.base(), Some(Base::C));
+        assert_eq!(entry.indel(), Some(&Indel::Insertion(vec![Base::G])));
+
+        let entry = &columns[2].entries()[0];
+        assert_eq!(entry.query_position(), Some(3));
+        assert_eq!(entry.quality_score(), Some(50));
+        assert_eq!(entry.indel(), Some(&Indel::Deletion(2)));
+        assert!(!entry.is_reverse_complemented());
+
+        let entry = &columns[2].entries()[1];
+        assert!(entry.is_head());
+        assert!(entry.is_reverse_complemented());
+
+        assert!(columns[3].entries()[0].is_deletion());
+
+        let entry = &columns[5].entries()[0];
+        assert_eq!(entry.base(), Some(Base::A));
+        assert!(entry.is_tail());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_filters() -> io::Result<()> {
+        let (header, records) = build_records(
+            b"@SQ\tSN:sq0\tLN:8
+r0\t0\tsq0\t1\t60\t2M\t*\t0\t0\tAC\tN!
+r1\t0\tsq0\t1\t10\t2M\t*\t0\t0\tAC\tNN
+r2\t1024\tsq0\t1\t60\t2M\t*\t0\t0\tAC\tNN
+",
+        )?;
+
+        let builder = Builder::default()
+            .set_min_mapping_quality(20)
+            .set_min_base_quality(13);
+
+        let columns = build_pileup(builder, &header, records)?;
+
+        assert_eq!(columns.len(), 1);
+        assert_eq!(columns[0].depth(), 1);
+        assert_eq!(columns[0].entries()[0].record_index(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_overlapping_mates() -> io::Result<()> {
+        let data = b"@SQ\tSN:sq0\tLN:8
+r0\t99\tsq0\t1\t60\t3M\t=\t2\t4\tACG\tNNN
+r0\t147\tsq0\t2\t60\t3M\t=\t1\t-4\tCGT\t#SN
+";
+
+        let (header, records) = build_records(data)?;
+        let columns = build_pileup(Builder::default(), &header, records)?;
+        let depths: Vec<_> = columns.iter().map(|column| column.depth()).collect();
+        assert_eq!(depths, [1, 1, 1, 1]);
+        assert_eq!(columns[1].entries()[0].record_index(), 0);
+        assert_eq!(columns[2].entries()[0].record_index(), 1);
+
+        let (header, records) = build_records(data)?;
+        let builder = Builder::default().set_overlap_policy(OverlapPolicy::CountBoth);
+        let columns = build_pileup(builder, &header, records)?;
+        let depths: Vec<_> = columns.iter().map(|column| column.depth()).collect();
+        assert_eq!(depths, [1, 2, 2, 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_baq() -> io::Result<()> {
+        let (header, records) = build_records(
+            b"@SQ\tSN:sq0\tLN:8
+r0\t0\tsq0\t1\t60\t2M\t*\t0\t0\tAC\tNN
+",
+        )?;
+
+        let builder = Builder::default().set_baq(
+            |_: &sam::Header, _: &dyn AlignmentRecord, quality_scores: &mut [u8]| {
+                quality_scores[1] = 0;
+                Ok(())
+            },
+        );
+
+        let columns = build_pileup(builder, &header, records)?;
+        let quality_scores: Vec<_> = columns
+            .iter()
+            .map(|column| column.entries()[0].quality_score())
+            .collect();
+        assert_eq!(quality_scores, [Some(45), Some(0)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_unsorted_records() -> io::Result<()> {
+        let (header, records) = build_records(
+            b"@SQ\tSN:sq0\tLN:8
+r0\t0\tsq0\t5\t60\t1M\t*\t0\t0\tA\tN
+r1\t0\tsq0\t1\t60\t1M\t*\t0\t0\tA\tN
+",
+        )?;
+
+        assert!(matches!(
+            build_pileup(Builder::default(), &header, records),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use std::io;
+
+use noodles_sam::{self as sam, AlignmentRecord};
+
+/// A base alignment quality (BAQ) calculator.
+///
+/// BAQ is the probability of a read base being misaligned and is typically used to cap base
+/// quality scores near indels. This is applied to the quality scores of each record before the
+/// record is added to the pileup.
+///
+/// This is implemented for closures with a matching signature.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_sam::{self as sam, AlignmentRecord};
+/// use noodles_util::alignment::pileup;
+///
+/// let cap = |_: &sam::Header, _: &dyn AlignmentRecord, quality_scores: &mut [u8]| {
+///     for score in quality_scores {
+///         *score = (*score).min(30);
+///     }
+///
+///     Ok::<_, io::Error>(())
+/// };
+///
+/// let builder = pileup::Builder::default().set_baq(cap);
+/// ```
+pub trait Baq {
+    /// Adjusts the quality scores of a record.
+    ///
+    /// `quality_scores` are the raw (Phred-scaled) quality scores of the record. It is empty if
+    /// the record's quality scores are missing.
+    fn apply(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn AlignmentRecord,
+        quality_scores: &mut [u8],
+    ) -> io::Result<()>;
+}
+
+impl<F> Baq for F
+where
+    F: FnMut(&sam::Header, &dyn AlignmentRecord, &mut [u8]) -> io::Result<()>,
+{
+    fn apply(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn AlignmentRecord,
+        quality_scores: &mut [u8],
+    ) -> io::Result<()> {
+        self(header, record, quality_scores)
+    }
+}
@@ -0,0 +1,173 @@
+use std::io;
+
+use noodles_sam::{self as sam, record::Flags, AlignmentRecord};
+
+use super::{Baq, OverlapPolicy, Pileup};
+
+/// An alignment pileup builder.
+pub struct Builder<'a> {
+    min_mapping_quality: u8,
+    min_base_quality: u8,
+    excluded_flags: Flags,
+    overlap_policy: OverlapPolicy,
+    baq: Option<Box<dyn Baq + 'a>>,
+}
+
+impl<'a> Builder<'a> {
+    /// Sets the minimum mapping quality.
+    ///
+    /// Records with a mapping quality less than this are skipped. Records with a missing mapping
+    /// quality (255) are never skipped. By default, this is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::pileup;
+    /// let builder = pileup::Builder::default().set_min_mapping_quality(20);
+    /// ```
+    pub fn set_min_mapping_quality(mut self, min_mapping_quality: u8) -> Self {
+        self.min_mapping_quality = min_mapping_quality;
+        self
+    }
+
+    /// Sets the minimum base quality.
+    ///
+    /// Aligned bases with a quality score less than this are not included in columns. Deletions
+    /// and bases with missing quality scores are always included. By default, this is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::pileup;
+    /// let builder = pileup::Builder::default().set_min_base_quality(13);
+    /// ```
+    pub fn set_min_base_quality(mut self, min_base_quality: u8) -> Self {
+        self.min_base_quality = min_base_quality;
+        self
+    }
+
+    /// Sets the flags of records to skip.
+    ///
+    /// Records with any of these flags set are skipped. By default, these are unmapped,
+    /// secondary, QC fail, and duplicate records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Flags;
+    /// use noodles_util::alignment::pileup;
+    /// let builder = pileup::Builder::default().set_excluded_flags(Flags::UNMAPPED);
+    /// ```
+    pub fn set_excluded_flags(mut self, excluded_flags: Flags) -> Self {
+        self.excluded_flags = excluded_flags;
+        self
+    }
+
+    /// Sets the policy for handling overlapping mates.
+    ///
+    /// By default, overlapping mates are counted once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::pileup::{self, OverlapPolicy};
+    /// let builder = pileup::Builder::default().set_overlap_policy(OverlapPolicy::CountBoth);
+    /// ```
+    pub fn set_overlap_policy(mut self, overlap_policy: OverlapPolicy) -> Self {
+        self.overlap_policy = overlap_policy;
+        self
+    }
+
+    /// Sets a base alignment quality (BAQ) calculator.
+    ///
+    /// This is applied to the quality scores of each record before the minimum base quality
+    /// filter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// use noodles_util::alignment::pileup;
+    ///
+    /// let builder = pileup::Builder::default().set_baq(
+    ///     |_: &sam::Header, _: &dyn AlignmentRecord, _: &mut [u8]| Ok::<_, io::Error>(()),
+    /// );
+    /// ```
+    pub fn set_baq<B>(mut self, baq: B) -> Self
+    where
+        B: Baq + 'a,
+    {
+        self.baq = Some(Box::new(baq));
+        self
+    }
+
+    /// Builds an alignment pileup over the given records.
+    ///
+    /// The records must be coordinate-sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{io, iter};
+    /// use noodles_sam::{self as sam, AlignmentRecord};
+    /// use noodles_util::alignment::pileup;
+    ///
+    /// let header = sam::Header::default();
+    /// let records = iter::empty::<io::Result<Box<dyn AlignmentRecord>>>();
+    ///
+    /// let mut pileup = pileup::Builder::default().build(&header, records);
+    /// assert!(pileup.next().is_none());
+    /// ```
+    pub fn build<I>(self, header: &'a sam::Header, records: I) -> Pileup<'a, I>
+    where
+        I: Iterator<Item = io::Result<Box<dyn AlignmentRecord>>>,
+    {
+        Pileup {
+            header,
+            records,
+            min_mapping_quality: self.min_mapping_quality,
+            min_base_quality: self.min_base_quality,
+            excluded_flags: self.excluded_flags,
+            overlap_policy: self.overlap_policy,
+            baq: self.baq,
+            record_count: 0,
+            last_start: None,
+            pending_read: None,
+            active_reads: Vec::new(),
+            reference_sequence_id: 0,
+            position: 0,
+        }
+    }
+}
+
+impl<'a> Default for Builder<'a> {
+    fn default() -> Self {
+        Self {
+            min_mapping_quality: 0,
+            min_base_quality: 0,
+            excluded_flags: Flags::UNMAPPED | Flags::SECONDARY | Flags::QC_FAIL | Flags::DUPLICATE,
+            overlap_policy: OverlapPolicy::default(),
+            baq: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        let builder = Builder::default();
+
+        assert_eq!(builder.min_mapping_quality, 0);
+        assert_eq!(builder.min_base_quality, 0);
+        assert_eq!(
+            builder.excluded_flags,
+            Flags::UNMAPPED | Flags::SECONDARY | Flags::QC_FAIL | Flags::DUPLICATE
+        );
+        assert_eq!(builder.overlap_policy, OverlapPolicy::CountOnce);
+        assert!(builder.baq.is_none());
+    }
+}
@@ -0,0 +1,55 @@
+//! Alignment pileup column.
+
+pub mod entry;
+
+pub use self::entry::{Entry, Indel};
+
+use noodles_sam::record::Position;
+
+/// An alignment pileup column.
+///
+/// A column is the list of records aligned to a single reference sequence position.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Column {
+    reference_sequence_id: usize,
+    position: Position,
+    entries: Vec<Entry>,
+}
+
+impl Column {
+    pub(crate) fn new(
+        reference_sequence_id: usize,
+        position: Position,
+        entries: Vec<Entry>,
+    ) -> Self {
+        Self {
+            reference_sequence_id,
+            position,
+            entries,
+        }
+    }
+
+    /// Returns the reference sequence ID.
+    ///
+    /// This is the index of the reference sequence in the header reference sequence dictionary.
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    /// Returns the position.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the entries.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Returns the number of entries in the column.
+    ///
+    /// This includes deletions.
+    pub fn depth(&self) -> usize {
+        self.entries.len()
+    }
+}
@@ -0,0 +1,93 @@
+//! Alignment pileup column entry.
+
+use noodles_sam::record::{sequence::Base, Flags, MappingQuality};
+
+/// An indel that directly follows an aligned base.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Indel {
+    /// Bases inserted after the aligned base.
+    Insertion(Vec<Base>),
+    /// The number of reference bases deleted after the aligned base.
+    Deletion(u32),
+}
+
+/// An alignment pileup column entry.
+///
+/// An entry is a single record's contribution to a column: either an aligned base or a deletion.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub(crate) record_index: usize,
+    pub(crate) flags: Flags,
+    pub(crate) mapping_quality: Option<MappingQuality>,
+    pub(crate) query_position: Option<usize>,
+    pub(crate) base: Option<Base>,
+    pub(crate) quality_score: Option<u8>,
+    pub(crate) indel: Option<Indel>,
+    pub(crate) is_head: bool,
+    pub(crate) is_tail: bool,
+}
+
+impl Entry {
+    /// Returns the index of the record in the input stream.
+    ///
+    /// This is the 0-based count of records read before this record, including filtered records.
+    pub fn record_index(&self) -> usize {
+        self.record_index
+    }
+
+    /// Returns the flags of the record.
+    pub fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    /// Returns the mapping quality of the record.
+    pub fn mapping_quality(&self) -> Option<MappingQuality> {
+        self.mapping_quality
+    }
+
+    /// Returns whether the record is on the reverse strand.
+    pub fn is_reverse_complemented(&self) -> bool {
+        self.flags.is_reverse_complemented()
+    }
+
+    /// Returns the 0-based position of the base in the read.
+    ///
+    /// This is `None` if the entry is a deletion.
+    pub fn query_position(&self) -> Option<usize> {
+        self.query_position
+    }
+
+    /// Returns the read base.
+    ///
+    /// This is `None` if the entry is a deletion or the record sequence is missing.
+    pub fn base(&self) -> Option<Base> {
+        self.base
+    }
+
+    /// Returns the base quality score.
+    ///
+    /// This is `None` if the entry is a deletion or the record quality scores are missing.
+    pub fn quality_score(&self) -> Option<u8> {
+        self.quality_score
+    }
+
+    /// Returns whether the entry is a deletion, i.e., the reference base is deleted in the read.
+    pub fn is_deletion(&self) -> bool {
+        self.query_position.is_none()
+    }
+
+    /// Returns the indel that directly follows this entry, if any.
+    pub fn indel(&self) -> Option<&Indel> {
+        self.indel.as_ref()
+    }
+
+    /// Returns whether this is the first aligned base of the read.
+    pub fn is_head(&self) -> bool {
+        self.is_head
+    }
+
+    /// Returns whether this is the last aligned base of the read.
+    pub fn is_tail(&self) -> bool {
+        self.is_tail
+    }
+}
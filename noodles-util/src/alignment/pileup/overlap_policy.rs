@@ -0,0 +1,14 @@
+/// The policy for handling overlapping mates in a pileup column.
+///
+/// The mates of a read pair can overlap when the fragment is shorter than the sum of the read
+/// lengths. Counting both mates at these positions counts the same template twice.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OverlapPolicy {
+    /// Count both mates.
+    CountBoth,
+    /// Count only the mate with the higher base quality score.
+    ///
+    /// This is the default.
+    #[default]
+    CountOnce,
+}
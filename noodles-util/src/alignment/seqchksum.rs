@@ -0,0 +1,419 @@
+//! Alignment record checksums.
+//!
+//! These are order-independent digests of the read names, flags, sequences, quality scores, and
+//! selected data fields of alignment records, similar to `samtools seqchksum` and
+//! `bamseqchksum`. Two files with equal checksums have the same reads, regardless of sort order
+//! or format, which can be used to verify that a lossless conversion, e.g., from BAM to CRAM,
+//! preserved the original content.
+//!
+//! Only primary records are included, i.e., secondary and supplementary records are skipped.
+//! Fields that change with alignment are normalized: only the segmented, first segment, and last segment flags
+//! are used, and reverse complemented sequences and quality scores are returned to their
+//! original orientation.
+
+use std::{collections::BTreeMap, fmt, io};
+
+use flate2::Crc;
+use noodles_sam::{
+    record::{data::field::Tag, Flags},
+    AlignmentRecord,
+};
+
+// 2^31 - 1
+const MODULUS: u64 = 0x7fffffff;
+
+const DEFAULT_TAGS: [Tag; 5] = [
+    Tag::SampleBarcodeSequence,
+    Tag::SegmentIndex,
+    Tag::SampleBarcodeQualityScores,
+    Tag::ReservedRt,
+    Tag::SegmentCount,
+];
+
+/// An order-independent digest of a set of alignment records.
+///
+/// Each digest is the product of the CRC32 checksums of the included record fields modulo
+/// 2<sup>31</sup> - 1.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Digest {
+    count: u64,
+    b_seq: u64,
+    name_b_seq: u64,
+    b_seq_qual: u64,
+    b_seq_tags: u64,
+}
+
+impl Digest {
+    /// Returns the number of records in the digest.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the digest of the flags and sequences.
+    pub fn b_seq(&self) -> u64 {
+        self.b_seq
+    }
+
+    /// Returns the digest of the read names, flags, and sequences.
+    pub fn name_b_seq(&self) -> u64 {
+        self.name_b_seq
+    }
+
+    /// Returns the digest of the flags, sequences, and quality scores.
+    pub fn b_seq_qual(&self) -> u64 {
+        self.b_seq_qual
+    }
+
+    /// Returns the digest of the flags, sequences, and selected data fields.
+    pub fn b_seq_tags(&self) -> u64 {
+        self.b_seq_tags
+    }
+
+    fn add(&mut self, other: &Self) {
+        self.count += other.count;
+        self.b_seq = multiply(self.b_seq, other.b_seq);
+        self.name_b_seq = multiply(self.name_b_seq, other.name_b_seq);
+        self.b_seq_qual = multiply(self.b_seq_qual, other.b_seq_qual);
+        self.b_seq_tags = multiply(self.b_seq_tags, other.b_seq_tags);
+    }
+}
+
+impl Default for Digest {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            b_seq: 1,
+            name_b_seq: 1,
+            b_seq_qual: 1,
+            b_seq_tags: 1,
+        }
+    }
+}
+
+/// Alignment record checksums, in total and per read group.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_sam as sam;
+/// use noodles_util::alignment::seqchksum::SeqChksum;
+///
+/// let a: sam::Record = "r0\t0\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS".parse().unwrap();
+/// let b: sam::Record = "r1\t16\tsq0\t5\t60\t4M\t*\t0\t0\tACGT\tNDLS".parse().unwrap();
+///
+/// let mut x = SeqChksum::default();
+/// x.add(&a)?;
+/// x.add(&b)?;
+///
+/// let mut y = SeqChksum::default();
+/// y.add(&b)?;
+/// y.add(&a)?;
+///
+/// assert_eq!(x.total().count(), 2);
+/// assert_eq!(x, y);
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeqChksum {
+    tags: Vec<Tag>,
+    total: Digest,
+    read_groups: BTreeMap<String, Digest>,
+}
+
+impl SeqChksum {
+    /// Creates checksums that include the given data fields in the tags digest.
+    ///
+    /// The default tags are `BC`, `FI`, `QT`, `RT`, and `TC`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::Tag;
+    /// use noodles_util::alignment::seqchksum::SeqChksum;
+    /// let checksums = SeqChksum::with_tags(vec![Tag::ReadGroup]);
+    /// ```
+    pub fn with_tags(tags: Vec<Tag>) -> Self {
+        Self {
+            tags,
+            total: Digest::default(),
+            read_groups: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the digest of all records.
+    pub fn total(&self) -> &Digest {
+        &self.total
+    }
+
+    /// Returns the digests of records per read group.
+    ///
+    /// Records without a read group (`RG`) data field are grouped under an empty name.
+    pub fn read_groups(&self) -> &BTreeMap<String, Digest> {
+        &self.read_groups
+    }
+
+    /// Adds a record to the checksums.
+    ///
+    /// Secondary and supplementary records are ignored.
+    pub fn add(&mut self, record: &dyn AlignmentRecord) -> io::Result<()> {
+        let flags = record.flags();
+
+        if flags.is_secondary() || flags.is_supplementary() {
+            return Ok(());
+        }
+
+        let data = record.data()?;
+
+        let read_group = data
+            .get(Tag::ReadGroup)
+            .and_then(|field| field.value().as_str())
+            .unwrap_or_default();
+
+        let digest = digest_record(record, &self.tags)?;
+
+        self.total.add(&digest);
+
+        self.read_groups
+            .entry(read_group.into())
+            .or_default()
+            .add(&digest);
+
+        Ok(())
+    }
+}
+
+impl Default for SeqChksum {
+    fn default() -> Self {
+        Self::with_tags(DEFAULT_TAGS.to_vec())
+    }
+}
+
+impl fmt::Display for SeqChksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "###\tset\tcount\tb_seq\tname_b_seq\tb_seq_qual\tb_seq_tags("
+        )?;
+
+        for (i, tag) in self.tags.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+
+            write!(f, "{}", tag)?;
+        }
+
+        writeln!(f, ")")?;
+
+        write_digest(f, "all", &self.total)?;
+
+        for (name, digest) in &self.read_groups {
+            write_digest(f, name, digest)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Calculates the checksums of the given alignment records.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_sam::{self as sam, AlignmentReader};
+/// use noodles_util::alignment::seqchksum;
+///
+/// let data = b"@HD\tVN:1.6
+/// r0\t4\t*\t0\t0\t*\t*\t0\t0\tACGT\tNDLS\tRG:Z:rg0
+/// ";
+///
+/// let mut reader = sam::Reader::new(&data[..]);
+/// let header = reader.read_alignment_header()?;
+/// let checksums = seqchksum::checksum(reader.alignment_records(&header))?;
+///
+/// assert_eq!(checksums.total().count(), 1);
+/// assert_eq!(checksums.read_groups()["rg0"], *checksums.total());
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn checksum<I>(records: I) -> io::Result<SeqChksum>
+where
+    I: IntoIterator<Item = io::Result<Box<dyn AlignmentRecord>>>,
+{
+    let mut checksums = SeqChksum::default();
+
+    for result in records {
+        let record = result?;
+        checksums.add(record.as_ref())?;
+    }
+
+    Ok(checksums)
+}
+
+fn write_digest(f: &mut fmt::Formatter<'_>, name: &str, digest: &Digest) -> fmt::Result {
+    writeln!(
+        f,
+        "all\t{}\t{}\t{:08x}\t{:08x}\t{:08x}\t{:08x}",
+        name,
+        digest.count(),
+        digest.b_seq(),
+        digest.name_b_seq(),
+        digest.b_seq_qual(),
+        digest.b_seq_tags(),
+    )
+}
+
+fn digest_record(record: &dyn AlignmentRecord, tags: &[Tag]) -> io::Result<Digest> {
+    let flags = record.flags() & (Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::LAST_SEGMENT);
+    let is_reverse_complemented = record.flags().is_reverse_complemented();
+
+    let mut sequence: Vec<u8> = record
+        .sequence()?
+        .iter()
+        .map(|&base| char::from(base) as u8)
+        .collect();
+
+    let mut quality_scores: Vec<u8> = record
+        .quality_scores()?
+        .iter()
+        .map(|&score| u8::from(score))
+        .collect();
+
+    if is_reverse_complemented {
+        sequence.reverse();
+        sequence.iter_mut().for_each(|b| *b = complement(*b));
+        quality_scores.reverse();
+    }
+
+    let data = record.data()?;
+    let mut raw_tags = Vec::new();
+
+    for tag in tags {
+        if let Some(field) = data.get(*tag) {
+            raw_tags.extend(field.to_string().bytes());
+            raw_tags.push(b'\t');
+        }
+    }
+
+    let read_name = record.read_name().unwrap_or(b"*");
+    let b = flags.bits().to_le_bytes();
+
+    Ok(Digest {
+        count: 1,
+        b_seq: crc(&[&b, &sequence]),
+        name_b_seq: crc(&[read_name, &b, &sequence]),
+        b_seq_qual: crc(&[&b, &sequence, &quality_scores]),
+        b_seq_tags: crc(&[&b, &sequence, &raw_tags]),
+    })
+}
+
+fn crc(parts: &[&[u8]]) -> u64 {
+    let mut crc = Crc::new();
+
+    for part in parts {
+        crc.update(part);
+        crc.update(&[0]);
+    }
+
+    match u64::from(crc.sum()) & MODULUS {
+        0 => 1,
+        n if n == MODULUS => 1,
+        n => n,
+    }
+}
+
+fn multiply(a: u64, b: u64) -> u64 {
+    (a * b) % MODULUS
+}
+
+fn complement(b: u8) -> u8 {
+    match b {
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' => b'A',
+        b'R' => b'Y',
+        b'Y' => b'R',
+        b'K' => b'M',
+        b'M' => b'K',
+        b'B' => b'V',
+        b'V' => b'B',
+        b'D' => b'H',
+        b'H' => b'D',
+        _ => b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam as sam;
+
+    use super::*;
+
+    fn build_checksums(records: &[&str]) -> Result<SeqChksum, Box<dyn std::error::Error>> {
+        let mut checksums = SeqChksum::default();
+
+        for s in records {
+            let record: sam::Record = s.parse()?;
+            checksums.add(&record)?;
+        }
+
+        Ok(checksums)
+    }
+
+    #[test]
+    fn test_add() -> Result<(), Box<dyn std::error::Error>> {
+        let checksums = build_checksums(&[
+            "r0\t67\tsq0\t1\t60\t4M\t=\t5\t8\tACGT\tNDLS\tRG:Z:rg0",
+            "r0\t147\tsq0\t5\t60\t4M\t=\t1\t-8\tAACC\tABCD\tRG:Z:rg0",
+            "r1\t0\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS",
+            "r1\t2048\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS",
+            "r1\t256\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS",
+        ])?;
+
+        assert_eq!(checksums.total().count(), 3);
+        assert_eq!(checksums.read_groups().len(), 2);
+        assert_eq!(checksums.read_groups()["rg0"].count(), 2);
+        assert_eq!(checksums.read_groups()[""].count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_normalizes_alignment() -> Result<(), Box<dyn std::error::Error>> {
+        let unmapped = build_checksums(&["r0\t77\t*\t0\t0\t*\t*\t0\t0\tAACC\tABCD\tFI:i:1"])?;
+        let mapped = build_checksums(&["r0\t115\tsq0\t5\t60\t4M\t=\t1\t-8\tGGTT\tDCBA\tFI:i:1"])?;
+        assert_eq!(mapped, unmapped);
+
+        let other = build_checksums(&["r0\t77\t*\t0\t0\t*\t*\t0\t0\tAACC\tABCD\tFI:i:2"])?;
+        assert_ne!(other, unmapped);
+        assert_eq!(other.total().b_seq_qual(), unmapped.total().b_seq_qual());
+        assert_ne!(other.total().b_seq_tags(), unmapped.total().b_seq_tags());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let checksums = build_checksums(&["r0\t4\t*\t0\t0\t*\t*\t0\t0\tACGT\tNDLS\tRG:Z:rg0"])?;
+        let s = checksums.to_string();
+        let mut lines = s.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some("###\tset\tcount\tb_seq\tname_b_seq\tb_seq_qual\tb_seq_tags(BC,FI,QT,RT,TC)")
+        );
+        assert!(lines
+            .next()
+            .map(|l| l.starts_with("all\tall\t1\t"))
+            .unwrap_or(false));
+        assert!(lines
+            .next()
+            .map(|l| l.starts_with("all\trg0\t1\t"))
+            .unwrap_or(false));
+        assert!(lines.next().is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,569 @@
+//! Alignment record sharding.
+//!
+//! A sharding [`Writer`] routes records to one output file per reference sequence or per fixed
+//! width bin of a reference sequence, e.g., to scatter work over a cluster and gather the results
+//! afterward. Records that are not placed on a reference sequence are written to an unmapped
+//! shard.
+//!
+//! Each shard keeps the full header, including every reference sequence, so reference sequence
+//! IDs are the same across shards and the shards can be merged back together. A comment (`@CO`)
+//! naming the region of the shard is added. When the shards are finished, a BAM index (`.bai`)
+//! or CRAM index (`.crai`) is written for each shard.
+
+use std::{
+    collections::HashMap,
+    io,
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+};
+
+use noodles_bam::{self as bam, bai};
+use noodles_core::Region;
+use noodles_cram::{self as cram, crai};
+use noodles_fasta as fasta;
+use noodles_sam::{self as sam, AlignmentRecord, AlignmentWriter};
+
+use super::WriterBuilder;
+use crate::Format;
+
+const UNMAPPED_NAME: &str = "unmapped";
+
+/// A sharding alignment writer builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    format: Option<Format>,
+    reference_sequences: Vec<fasta::Record>,
+    bin_width: Option<NonZeroU32>,
+    skip_index: bool,
+}
+
+impl Builder {
+    /// Sets the output format of the shards.
+    ///
+    /// This must be BAM or CRAM. By default, shards are written as BAM.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::{alignment::shard, Format};
+    /// let builder = shard::Builder::default().set_format(Format::Cram);
+    /// ```
+    pub fn set_format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Sets the reference sequences used to write CRAM shards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::shard;
+    /// let builder = shard::Builder::default().set_reference_sequences(Vec::new());
+    /// ```
+    pub fn set_reference_sequences(mut self, reference_sequences: Vec<fasta::Record>) -> Self {
+        self.reference_sequences = reference_sequences;
+        self
+    }
+
+    /// Sets the bin width.
+    ///
+    /// By default, there is one shard per reference sequence. With a bin width, each reference
+    /// sequence is split into bins of the given width, and there is one shard per bin. Records
+    /// are placed in the bin of their alignment start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroU32;
+    /// use noodles_util::alignment::shard;
+    ///
+    /// let builder = shard::Builder::default()
+    ///     .set_bin_width(NonZeroU32::new(10_000_000).unwrap());
+    /// ```
+    pub fn set_bin_width(mut self, bin_width: NonZeroU32) -> Self {
+        self.bin_width = Some(bin_width);
+        self
+    }
+
+    /// Sets whether to skip writing an index for each shard.
+    ///
+    /// Indexes are only valid for coordinate-sorted records. By default, indexes are written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::shard;
+    /// let builder = shard::Builder::default().set_skip_index(true);
+    /// ```
+    pub fn set_skip_index(mut self, skip_index: bool) -> Self {
+        self.skip_index = skip_index;
+        self
+    }
+
+    /// Builds a sharding alignment writer.
+    ///
+    /// Shards are written to files named `<prefix>.<id>.<ext>`, where `<id>` is the 0-based
+    /// index of the reference sequence in the header (followed by `.<bin>` when binning) or
+    /// `unmapped`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::alignment::shard;
+    /// let writer = shard::Builder::default().build("out/sample")?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build<P>(self, prefix: P) -> io::Result<Writer>
+    where
+        P: Into<PathBuf>,
+    {
+        let format = self.format.unwrap_or(Format::Bam);
+
+        if !matches!(format, Format::Bam | Format::Cram) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid shard format: {}", format),
+            ));
+        }
+
+        Ok(Writer {
+            prefix: prefix.into(),
+            format,
+            reference_sequences: self.reference_sequences,
+            bin_width: self.bin_width,
+            skip_index: self.skip_index,
+            is_coordinate_sorted: false,
+            writers: HashMap::new(),
+            shards: Vec::new(),
+        })
+    }
+}
+
+/// A shard.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Shard {
+    path: PathBuf,
+    region: Region,
+    record_count: u64,
+}
+
+impl Shard {
+    /// Returns the path of the shard.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the region of the shard.
+    ///
+    /// This is [`Region::Unmapped`] for the unmapped shard.
+    pub fn region(&self) -> &Region {
+        &self.region
+    }
+
+    /// Returns the number of records written to the shard.
+    pub fn record_count(&self) -> u64 {
+        self.record_count
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Key {
+    Mapped(usize, u32),
+    Unmapped,
+}
+
+/// A sharding alignment writer.
+///
+/// This is created by [`Builder::build`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_sam::{self as sam, AlignmentReader, AlignmentWriter};
+/// use noodles_util::alignment::shard;
+///
+/// let mut reader = sam::Reader::new(io::stdin().lock());
+/// let header = reader.read_alignment_header()?;
+///
+/// let mut writer = shard::Builder::default().build("out/sample")?;
+/// writer.write_alignment_header(&header)?;
+///
+/// for result in reader.records() {
+///     let record = result?;
+///     writer.write_alignment_record(&header, &record)?;
+/// }
+///
+/// writer.finish(&header)?;
+///
+/// for shard in writer.shards() {
+///     println!("{}\t{:?}", shard.path().display(), shard.region());
+/// }
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct Writer {
+    prefix: PathBuf,
+    format: Format,
+    reference_sequences: Vec<fasta::Record>,
+    bin_width: Option<NonZeroU32>,
+    skip_index: bool,
+    is_coordinate_sorted: bool,
+    writers: HashMap<Key, (usize, Box<dyn AlignmentWriter>)>,
+    shards: Vec<Shard>,
+}
+
+impl Writer {
+    /// Returns the shards that were created, in order of creation.
+    pub fn shards(&self) -> &[Shard] {
+        &self.shards
+    }
+
+    fn key(&self, header: &sam::Header, record: &dyn AlignmentRecord) -> io::Result<Key> {
+        let reference_sequences = header.reference_sequences();
+
+        let id = match record.reference_sequence(reference_sequences).transpose()? {
+            Some(reference_sequence) => reference_sequences
+                .get_index_of(reference_sequence.name().as_str())
+                .expect("missing reference sequence"),
+            None => return Ok(Key::Unmapped),
+        };
+
+        let bin = match (self.bin_width, record.alignment_start()) {
+            (Some(bin_width), Some(position)) => {
+                let start = u32::try_from(i32::from(position))
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+                (start - 1) / bin_width.get()
+            }
+            _ => 0,
+        };
+
+        Ok(Key::Mapped(id, bin))
+    }
+
+    fn open(&mut self, header: &sam::Header, key: Key) -> io::Result<()> {
+        let ext = match self.format {
+            Format::Cram => "cram",
+            _ => "bam",
+        };
+
+        let (id, region) = match key {
+            Key::Mapped(id, bin) => {
+                let (name, reference_sequence) = header
+                    .reference_sequences()
+                    .get_index(id)
+                    .expect("invalid reference sequence ID");
+
+                let region = match self.bin_width {
+                    Some(bin_width) => {
+                        let start = bin * bin_width.get() + 1;
+                        let end = start.saturating_add(bin_width.get() - 1);
+                        let end = end.min(reference_sequence.len() as u32);
+                        Region::mapped(name.clone(), start as i32..=end as i32)
+                    }
+                    None => Region::mapped(name.clone(), ..),
+                };
+
+                let id = match self.bin_width {
+                    Some(_) => format!("{}.{}", id, bin),
+                    None => id.to_string(),
+                };
+
+                (id, region)
+            }
+            Key::Unmapped => (String::from(UNMAPPED_NAME), Region::Unmapped),
+        };
+
+        let mut path = self.prefix.clone().into_os_string();
+        path.push(format!(".{}.{}", id, ext));
+        let path = PathBuf::from(path);
+
+        let shard_header = build_shard_header(header, &region);
+
+        let mut writer = WriterBuilder::default()
+            .set_format(self.format)
+            .set_reference_sequences(self.reference_sequences.clone())
+            .build_from_path(&path)?;
+
+        writer.write_alignment_header(&shard_header)?;
+
+        self.writers.insert(key, (self.shards.len(), writer));
+
+        self.shards.push(Shard {
+            path,
+            region,
+            record_count: 0,
+        });
+
+        Ok(())
+    }
+
+    fn close(&mut self, header: &sam::Header, key: Key) -> io::Result<()> {
+        if let Some((i, mut writer)) = self.writers.remove(&key) {
+            writer.finish(header)?;
+            drop(writer);
+
+            if !self.skip_index {
+                index(self.format, &self.shards[i].path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl AlignmentWriter for Writer {
+    fn write_alignment_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        use sam::header::header::SortOrder;
+
+        self.is_coordinate_sorted = header
+            .header()
+            .and_then(|hd| hd.sort_order())
+            .map(|sort_order| sort_order == SortOrder::Coordinate)
+            .unwrap_or(false);
+
+        Ok(())
+    }
+
+    fn write_alignment_record(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn AlignmentRecord,
+    ) -> io::Result<()> {
+        let key = self.key(header, record)?;
+
+        if !self.writers.contains_key(&key) {
+            // Coordinate-sorted input never returns to a previous shard, so the open shards can
+            // be finished to limit the number of open files.
+            if self.is_coordinate_sorted {
+                let keys: Vec<_> = self.writers.keys().copied().collect();
+
+                for k in keys {
+                    self.close(header, k)?;
+                }
+            }
+
+            self.open(header, key)?;
+        }
+
+        let (i, writer) = self.writers.get_mut(&key).expect("missing shard writer");
+        writer.write_alignment_record(header, record)?;
+        self.shards[*i].record_count += 1;
+
+        Ok(())
+    }
+
+    fn finish(&mut self, header: &sam::Header) -> io::Result<()> {
+        let mut keys: Vec<_> = self.writers.iter().map(|(k, (i, _))| (*i, *k)).collect();
+        keys.sort_unstable_by_key(|(i, _)| *i);
+
+        for (_, key) in keys {
+            self.close(header, key)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn build_shard_header(header: &sam::Header, region: &Region) -> sam::Header {
+    let mut builder =
+        sam::Header::builder().set_reference_sequences(header.reference_sequences().clone());
+
+    if let Some(hd) = header.header() {
+        builder = builder.set_header(hd.clone());
+    }
+
+    for read_group in header.read_groups().values() {
+        builder = builder.add_read_group(read_group.clone());
+    }
+
+    for program in header.programs().values() {
+        builder = builder.add_program(program.clone());
+    }
+
+    for comment in header.comments() {
+        builder = builder.add_comment(comment.clone());
+    }
+
+    let description = match region {
+        Region::Mapped(_) => region.to_string(),
+        _ => String::from(UNMAPPED_NAME),
+    };
+
+    builder
+        .add_comment(format!("shard: {}", description))
+        .build()
+}
+
+fn index(format: Format, src: &Path) -> io::Result<()> {
+    let mut dst = src.as_os_str().to_owned();
+
+    match format {
+        Format::Cram => {
+            let index = cram::index(src)?;
+            dst.push(".crai");
+            crai::write(dst, &index)
+        }
+        _ => {
+            let index = bam::index(src)?;
+            dst.push(".bai");
+            bai::write(dst, &index)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+
+    use noodles_csi::BinningIndex;
+    use noodles_sam::{
+        header::{header::SortOrder, ReferenceSequence},
+        AlignmentReader,
+    };
+
+    use super::*;
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        Ok(sam::Header::builder()
+            .set_header(
+                sam::header::header::Header::builder()
+                    .set_sort_order(SortOrder::Coordinate)
+                    .build(),
+            )
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 13)?)
+            .build())
+    }
+
+    #[test]
+    fn test_write_alignment_record() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join("noodles-util-alignment-shard-test");
+        fs::create_dir_all(&dir)?;
+
+        let header = build_header()?;
+
+        let mut writer = Builder::default()
+            .set_bin_width(NonZeroU32::new(5).unwrap())
+            .build(dir.join("sample"))?;
+
+        writer.write_alignment_header(&header)?;
+
+        for s in [
+            "r0\t0\tsq0\t1\t60\t1M",
+            "r1\t0\tsq0\t6\t60\t1M",
+            "r2\t0\tsq0\t8\t60\t1M",
+            "r3\t0\tsq1\t13\t60\t1M",
+            "r4\t4\t*\t0\t0\t*",
+        ] {
+            let record: sam::Record = format!("{}\t*\t0\t0\tA\tN", s).parse()?;
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        writer.finish(&header)?;
+
+        let shards: Vec<_> = writer
+            .shards()
+            .iter()
+            .map(|shard| {
+                (
+                    shard.path().file_name().map(|s| s.to_owned()),
+                    shard.region().clone(),
+                    shard.record_count(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            shards,
+            [
+                (
+                    Some("sample.0.0.bam".into()),
+                    Region::mapped("sq0", 1..=5),
+                    1
+                ),
+                (
+                    Some("sample.0.1.bam".into()),
+                    Region::mapped("sq0", 6..=8),
+                    2
+                ),
+                (
+                    Some("sample.1.2.bam".into()),
+                    Region::mapped("sq1", 11..=13),
+                    1
+                ),
+                (Some("sample.unmapped.bam".into()), Region::Unmapped, 1),
+            ]
+        );
+
+        let shard = &writer.shards()[1];
+        let mut reader = File::open(shard.path()).map(bam::Reader::new)?;
+        let shard_header = reader.read_alignment_header()?;
+        assert_eq!(shard_header.reference_sequences().len(), 2);
+        assert_eq!(shard_header.comments(), ["shard: sq0:6-8"]);
+        assert_eq!(reader.records().count(), 2);
+
+        let mut index_src = shard.path().as_os_str().to_owned();
+        index_src.push(".bai");
+        let index = bai::read(index_src)?;
+        assert_eq!(index.reference_sequences().len(), 2);
+
+        fs::remove_dir_all(dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_alignment_record_with_cram_format() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join("noodles-util-alignment-shard-cram-test");
+        fs::create_dir_all(&dir)?;
+
+        let data = b">sq0\nACGTACGT\n>sq1\nACGTACGTACGTA\n";
+        let reference_sequences = fasta::Reader::new(&data[..])
+            .records()
+            .collect::<io::Result<Vec<_>>>()?;
+        let header = crate::alignment::dict::build(&mut fasta::Reader::new(&data[..]), None)?;
+
+        let mut writer = Builder::default()
+            .set_format(Format::Cram)
+            .set_reference_sequences(reference_sequences)
+            .build(dir.join("sample"))?;
+
+        writer.write_alignment_header(&header)?;
+
+        for s in ["r0\t0\tsq0\t1\t60\t1M", "r1\t0\tsq1\t5\t60\t1M"] {
+            let record: sam::Record = format!("{}\t*\t0\t0\tA\tN", s).parse()?;
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        writer.finish(&header)?;
+
+        let paths: Vec<_> = writer
+            .shards()
+            .iter()
+            .map(|shard| shard.path().file_name().map(|s| s.to_owned()))
+            .collect();
+        assert_eq!(
+            paths,
+            [Some("sample.0.cram".into()), Some("sample.1.cram".into())]
+        );
+
+        let mut index_src = writer.shards()[1].path().as_os_str().to_owned();
+        index_src.push(".crai");
+        let index = crai::read(index_src)?;
+        assert_eq!(index.len(), 1);
+
+        fs::remove_dir_all(dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_invalid_format() {
+        assert!(matches!(
+            Builder::default().set_format(Format::Sam).build("sample"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}
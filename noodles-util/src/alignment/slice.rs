@@ -0,0 +1,627 @@
+//! Alignment region extraction.
+//!
+//! This extracts the records that intersect a list of regions from an indexed BAM or CRAM file
+//! and writes them to a new file of the same format, similar to `samtools view <src> <region>...`.
+//! The output is self-contained: it has its own header, only the records in the regions, and an
+//! end-of-file marker.
+
+use std::{
+    fs::File,
+    io::{self, SeekFrom},
+    path::Path,
+};
+
+use noodles_bam::{self as bam, bai};
+use noodles_core::{Region, RegionSet};
+use noodles_cram::{self as cram, crai};
+use noodles_fasta as fasta;
+use noodles_sam::{self as sam, header::Program, AlignmentRecord};
+
+use super::{convert::decode_data_container, WriterBuilder};
+use crate::Format;
+
+const PROGRAM_ID: &str = "noodles-util.slice";
+
+/// An alignment index.
+///
+/// The index determines the format of the input and output.
+#[derive(Debug)]
+pub enum Index {
+    /// A BAM index.
+    Bai(bai::Index),
+    /// A CRAM index.
+    Crai(crai::Index),
+}
+
+impl From<bai::Index> for Index {
+    fn from(index: bai::Index) -> Self {
+        Self::Bai(index)
+    }
+}
+
+impl From<crai::Index> for Index {
+    fn from(index: crai::Index) -> Self {
+        Self::Crai(index)
+    }
+}
+
+/// An alignment region extractor builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    reference_sequences: Vec<fasta::Record>,
+}
+
+impl Builder {
+    /// Sets the reference sequences.
+    ///
+    /// These are required to decode and encode CRAM records that are stored as differences to the
+    /// reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::slice;
+    /// let builder = slice::Builder::default().set_reference_sequences(Vec::new());
+    /// ```
+    pub fn set_reference_sequences(mut self, reference_sequences: Vec<fasta::Record>) -> Self {
+        self.reference_sequences = reference_sequences;
+        self
+    }
+
+    /// Extracts the records in the given regions.
+    ///
+    /// See [`slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_core::Region;
+    /// use noodles_cram::crai;
+    /// use noodles_util::alignment::slice;
+    ///
+    /// let index = crai::read("sample.cram.crai").map(slice::Index::from)?;
+    /// let regions = [Region::mapped("sq0", 8..=13)];
+    ///
+    /// slice::Builder::default()
+    ///     .set_reference_sequences(Vec::new())
+    ///     .slice("sample.cram", &index, &regions, "out.cram")?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn slice<P, Q>(self, src: P, index: &Index, regions: &[Region], dst: Q) -> io::Result<u64>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        match index {
+            Index::Bai(index) => slice_bam(src.as_ref(), index, regions, dst.as_ref()),
+            Index::Crai(index) => slice_cram(
+                src.as_ref(),
+                index,
+                &self.reference_sequences,
+                regions,
+                dst.as_ref(),
+            ),
+        }
+    }
+}
+
+/// Extracts the records in the given regions from an indexed BAM or CRAM file.
+///
+/// The regions are sorted and overlapping regions are merged, so the output is coordinate-sorted
+/// and each record is written at most once. [`Region::Unmapped`] selects the records that are not
+/// placed on a reference sequence. [`Region::All`] is not supported.
+///
+/// The output header is the input header with a program (`@PG`) record that lists the regions.
+/// BAM records are copied as is, without being decoded. CRAM records are decoded and reencoded;
+/// use [`Builder::set_reference_sequences`] to set the reference sequences they need.
+///
+/// This returns the number of records written.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bam::bai;
+/// use noodles_core::Region;
+/// use noodles_util::alignment::slice;
+///
+/// let index = bai::read("sample.bam.bai").map(slice::Index::from)?;
+/// let regions = [Region::mapped("sq0", 8..=13), Region::Unmapped];
+/// let n = slice::slice("sample.bam", &index, &regions, "out.bam")?;
+/// eprintln!("{}", n);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn slice<P, Q>(src: P, index: &Index, regions: &[Region], dst: Q) -> io::Result<u64>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    Builder::default().slice(src, index, regions, dst)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Target {
+    Mapped {
+        id: usize,
+        name: String,
+        start: i32,
+        end: i32,
+    },
+    Unmapped,
+}
+
+impl Target {
+    fn intersects<R>(&self, record: &R, name: Option<&str>) -> io::Result<bool>
+    where
+        R: AlignmentRecord + ?Sized,
+    {
+        match self {
+            Self::Mapped {
+                name: target_name,
+                start,
+                end,
+                ..
+            } => {
+                if name != Some(target_name.as_str()) {
+                    return Ok(false);
+                }
+
+                let record_start = match record.alignment_start() {
+                    Some(position) => i32::from(position),
+                    None => return Ok(false),
+                };
+
+                let record_end = record
+                    .alignment_end()
+                    .transpose()?
+                    .map(i32::from)
+                    .unwrap_or(record_start);
+
+                Ok(record_start <= *end && *start <= record_end)
+            }
+            Self::Unmapped => Ok(name.is_none()),
+        }
+    }
+}
+
+fn resolve_targets(header: &sam::Header, regions: &[Region]) -> io::Result<Vec<Target>> {
+    let mut region_set = RegionSet::new();
+    let mut has_unmapped = false;
+
+    for region in regions {
+        match region {
+            Region::Mapped(mapped) => {
+                if !header.reference_sequences().contains_key(mapped.name()) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("missing reference sequence: {}", mapped.name()),
+                    ));
+                }
+
+                region_set.insert(mapped.name(), mapped.interval());
+            }
+            Region::Unmapped => has_unmapped = true,
+            Region::All => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid region: all",
+                ))
+            }
+        }
+    }
+
+    let mut targets = Vec::new();
+
+    for (id, name) in header.reference_sequences().keys().enumerate() {
+        if let Some(interval_set) = region_set.get(name) {
+            targets.extend(interval_set.iter().map(|interval| Target::Mapped {
+                id,
+                name: name.clone(),
+                start: *interval.start(),
+                end: *interval.end(),
+            }));
+        }
+    }
+
+    if has_unmapped {
+        targets.push(Target::Unmapped);
+    }
+
+    Ok(targets)
+}
+
+fn build_header(header: &sam::Header, targets: &[Target]) -> io::Result<sam::Header> {
+    let mut header = header.clone();
+
+    let mut id = String::from(PROGRAM_ID);
+    let mut i = 1;
+
+    while header.programs().contains_key(&id) {
+        id = format!("{}.{}", PROGRAM_ID, i);
+        i += 1;
+    }
+
+    let regions: Vec<_> = targets
+        .iter()
+        .map(|target| match target {
+            Target::Mapped {
+                name, start, end, ..
+            } => format!("{}:{}-{}", name, start, end),
+            Target::Unmapped => String::from("*"),
+        })
+        .collect();
+
+    let program = Program::builder()
+        .set_id(id.clone())
+        .set_name("noodles-util")
+        .set_command_line(format!("slice {}", regions.join(" ")))
+        .build()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    header.programs_mut().insert(id, program);
+
+    Ok(header)
+}
+
+fn slice_bam(src: &Path, index: &bai::Index, regions: &[Region], dst: &Path) -> io::Result<u64> {
+    let mut reader = File::open(src).map(bam::Reader::new)?;
+    let header: sam::Header = reader
+        .read_header()?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let reference_sequences = reader.read_reference_sequences()?;
+
+    let targets = resolve_targets(&header, regions)?;
+    let output_header = build_header(&header, &targets)?;
+
+    let mut writer = File::create(dst).map(bam::Writer::new)?;
+    writer.write_header(&output_header)?;
+    writer.write_reference_sequences(&reference_sequences)?;
+
+    let mut n = 0;
+    let mut prev_target: Option<&Target> = None;
+
+    for target in &targets {
+        let records: Box<dyn Iterator<Item = io::Result<bam::Record>>> = match target {
+            Target::Mapped {
+                name, start, end, ..
+            } => {
+                let region = Region::mapped(name.clone(), *start..=*end);
+                Box::new(reader.query(&reference_sequences, index, &region)?)
+            }
+            Target::Unmapped => Box::new(reader.query_unmapped(index)?),
+        };
+
+        for result in records {
+            let record = result?;
+
+            let name = record
+                .reference_sequence(&reference_sequences)
+                .transpose()?
+                .map(|reference_sequence| reference_sequence.name().as_str());
+
+            if let Some(prev_target) = prev_target {
+                if prev_target.intersects(&record, name)? {
+                    continue;
+                }
+            }
+
+            writer.write_record(&record)?;
+            n += 1;
+        }
+
+        prev_target = Some(target);
+    }
+
+    writer.try_finish()?;
+
+    Ok(n)
+}
+
+fn slice_cram(
+    src: &Path,
+    index: &crai::Index,
+    reference_sequences: &[fasta::Record],
+    regions: &[Region],
+    dst: &Path,
+) -> io::Result<u64> {
+    let mut reader = File::open(src).map(cram::Reader::new)?;
+    reader.read_file_definition()?;
+    let header: sam::Header = reader
+        .read_file_header()?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let targets = resolve_targets(&header, regions)?;
+    let output_header = build_header(&header, &targets)?;
+
+    let mut writer = WriterBuilder::default()
+        .set_format(Format::Cram)
+        .set_reference_sequences(reference_sequences.to_vec())
+        .build_from_path(dst)?;
+
+    writer.write_alignment_header(&output_header)?;
+
+    let mut n = 0;
+    let mut prev_target: Option<&Target> = None;
+
+    for target in &targets {
+        for offset in container_offsets(index, target)? {
+            reader.seek(SeekFrom::Start(offset))?;
+
+            let container = match reader.read_data_container()? {
+                Some(container) => container,
+                None => break,
+            };
+
+            let records = decode_data_container(
+                reference_sequences,
+                header.reference_sequences(),
+                &container,
+            )?;
+
+            for record in records {
+                let name = record.reference_sequence_name().map(|name| name.as_str());
+
+                if !target.intersects(&record, name)? {
+                    continue;
+                }
+
+                if let Some(prev_target) = prev_target {
+                    if prev_target.intersects(&record, name)? {
+                        continue;
+                    }
+                }
+
+                writer.write_alignment_record(&output_header, &record)?;
+                n += 1;
+            }
+        }
+
+        prev_target = Some(target);
+    }
+
+    writer.finish(&output_header)?;
+
+    Ok(n)
+}
+
+fn container_offsets(index: &crai::Index, target: &Target) -> io::Result<Vec<u64>> {
+    let mut offsets = Vec::new();
+
+    for record in index.iter() {
+        let reference_sequence_id = record
+            .reference_sequence_id()
+            .map(|id| i32::from(id) as usize);
+
+        let is_match = match target {
+            Target::Mapped { id, start, end, .. } => {
+                let record_start = record.alignment_start();
+                let record_end = record_start
+                    .checked_add(record.alignment_span())
+                    .and_then(|n| n.checked_sub(1))
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "invalid index record alignment end: start + span overflows",
+                        )
+                    })?;
+
+                reference_sequence_id == Some(*id) && record_start <= *end && *start <= record_end
+            }
+            Target::Unmapped => reference_sequence_id.is_none(),
+        };
+
+        if is_match {
+            offsets.push(record.offset());
+        }
+    }
+
+    offsets.dedup();
+
+    Ok(offsets)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use noodles_sam::{header::header::SortOrder, AlignmentReader};
+
+    use super::*;
+
+    fn build_input_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        let data = b">sq0\nACGTACGTACGTACGTACGT\n>sq1\nACGTACGT\n";
+        let mut header = crate::alignment::dict::build(&mut fasta::Reader::new(&data[..]), None)?;
+
+        *header.header_mut() = Some(
+            sam::header::header::Header::builder()
+                .set_sort_order(SortOrder::Coordinate)
+                .build(),
+        );
+
+        Ok(header)
+    }
+
+    fn build_records() -> Result<Vec<sam::Record>, Box<dyn std::error::Error>> {
+        [
+            "r0\t0\tsq0\t1\t60\t4M",
+            "r1\t0\tsq0\t5\t60\t4M",
+            "r2\t0\tsq0\t9\t60\t4M",
+            "r3\t0\tsq0\t13\t60\t4M",
+            "r4\t0\tsq1\t1\t60\t4M",
+        ]
+        .iter()
+        .map(|s| {
+            format!("{}\t*\t0\t0\tACGT\tNDLS", s)
+                .parse()
+                .map_err(|e: sam::record::ParseError| e.into())
+        })
+        .collect()
+    }
+
+    fn read_names<R>(reader: &mut R) -> io::Result<Vec<String>>
+    where
+        R: AlignmentReader,
+    {
+        let header = reader.read_alignment_header()?;
+
+        reader
+            .alignment_records(&header)
+            .map(|result| {
+                result.map(|record| {
+                    String::from_utf8_lossy(record.read_name().unwrap_or_default()).into()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_targets() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_input_header()?;
+
+        let regions = [
+            Region::Unmapped,
+            Region::mapped("sq1", ..),
+            Region::mapped("sq0", 8..=13),
+            Region::mapped("sq0", 1..=5),
+            Region::mapped("sq0", 5..=8),
+        ];
+
+        assert_eq!(
+            resolve_targets(&header, &regions)?,
+            [
+                Target::Mapped {
+                    id: 0,
+                    name: String::from("sq0"),
+                    start: 1,
+                    end: 13
+                },
+                Target::Mapped {
+                    id: 1,
+                    name: String::from("sq1"),
+                    start: 1,
+                    end: i32::MAX
+                },
+                Target::Unmapped,
+            ]
+        );
+
+        assert!(resolve_targets(&header, &[Region::mapped("sq2", ..)]).is_err());
+        assert!(resolve_targets(&header, &[Region::All]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_container_offsets() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_bam::record::ReferenceSequenceId;
+
+        let sq0 = ReferenceSequenceId::try_from(0).map(Some)?;
+
+        let target = Target::Mapped {
+            id: 0,
+            name: String::from("sq0"),
+            start: 5,
+            end: 8,
+        };
+
+        let index = vec![
+            crai::Record::new(sq0, 1, 4, 13, 0, 0),
+            crai::Record::new(sq0, 5, 4, 21, 0, 0),
+            crai::Record::new(None, 0, 0, 34, 0, 0),
+        ];
+
+        assert_eq!(container_offsets(&index, &target)?, [21]);
+        assert_eq!(container_offsets(&index, &Target::Unmapped)?, [34]);
+
+        let index = vec![crai::Record::new(sq0, i32::MAX - 1, 4, 13, 0, 0)];
+
+        assert!(matches!(
+            container_offsets(&index, &target),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_slice_with_bam() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join("noodles-util-alignment-slice-bam-test");
+        fs::create_dir_all(&dir)?;
+
+        let src = dir.join("sample.bam");
+        let dst = dir.join("out.bam");
+
+        let header = build_input_header()?;
+
+        let mut writer = File::create(&src).map(bam::Writer::new)?;
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        for record in build_records()? {
+            writer.write_sam_record(header.reference_sequences(), &record)?;
+        }
+
+        writer.try_finish()?;
+        drop(writer);
+
+        let index = bam::index(&src).map(Index::from)?;
+
+        let regions = [Region::mapped("sq0", 6..=10), Region::mapped("sq0", 7..=14)];
+        assert_eq!(slice(&src, &index, &regions, &dst)?, 3);
+
+        let mut reader = File::open(&dst).map(bam::Reader::new)?;
+        assert_eq!(read_names(&mut reader)?, ["r1", "r2", "r3"]);
+
+        fs::remove_dir_all(dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_slice_with_cram() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join("noodles-util-alignment-slice-cram-test");
+        fs::create_dir_all(&dir)?;
+
+        let src = dir.join("sample.cram");
+        let dst = dir.join("out.cram");
+
+        let data = b">sq0\nACGTACGTACGTACGTACGT\n>sq1\nACGTACGT\n";
+        let reference_sequences = fasta::Reader::new(&data[..])
+            .records()
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let header = build_input_header()?;
+
+        let mut writer = WriterBuilder::default()
+            .set_format(Format::Cram)
+            .set_reference_sequences(reference_sequences.clone())
+            .build_from_path(&src)?;
+
+        writer.write_alignment_header(&header)?;
+
+        for record in build_records()? {
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        writer.finish(&header)?;
+        drop(writer);
+
+        let index = cram::index(&src).map(Index::from)?;
+
+        let regions = [Region::mapped("sq1", ..), Region::mapped("sq0", 10..=12)];
+        let n = Builder::default()
+            .set_reference_sequences(reference_sequences)
+            .slice(&src, &index, &regions, &dst)?;
+        assert_eq!(n, 2);
+
+        let mut reader = File::open(&dst).map(cram::Reader::new)?;
+        assert_eq!(read_names(&mut reader)?, ["r2", "r4"]);
+
+        fs::remove_dir_all(dir)?;
+
+        Ok(())
+    }
+}
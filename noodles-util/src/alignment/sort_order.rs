@@ -0,0 +1,540 @@
+//! Alignment sort order enforcement.
+//!
+//! A [`Writer`] wraps an alignment writer and checks that records are written in the sort order
+//! declared by the header (`@HD SO`). Writing a record out of order fails instead of producing a
+//! file that claims to be sorted, which would otherwise only be noticed when, e.g., an index built
+//! from it returns wrong query results.
+
+use std::{cmp::Ordering, error, fmt, io};
+
+use noodles_sam::{
+    self as sam,
+    header::header::{SortOrder, SubsortOrder},
+    record::Position,
+    AlignmentRecord, AlignmentWriter,
+};
+
+/// An alignment writer that enforces a sort order.
+///
+/// Coordinate-sorted records are ordered by the position of their reference sequence in the
+/// header and then by alignment start. Unmapped records without a reference sequence are last.
+///
+/// Queryname-sorted records are ordered by their read names, either by their bytes
+/// (lexicographical) or with runs of digits compared as numbers (natural), e.g., `r9` before
+/// `r10`. The queryname order is read from the header subsort order (`@HD SS`), e.g.,
+/// `queryname:natural`, and defaults to lexicographical.
+///
+/// Records are not checked if the sort order is unknown, unsorted, or missing.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{
+///     self as sam,
+///     header::{header::SortOrder, ReferenceSequence},
+///     AlignmentWriter,
+/// };
+/// use noodles_util::alignment::sort_order;
+///
+/// let header = sam::Header::builder()
+///     .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+///     .build();
+///
+/// let mut writer = sort_order::Writer::with_sort_order(
+///     sam::Writer::new(Vec::new()),
+///     SortOrder::Coordinate,
+/// );
+///
+/// writer.write_alignment_header(&header)?;
+///
+/// let record: sam::Record = "r0\t0\tsq0\t5\t60\t1M\t*\t0\t0\tA\tN".parse()?;
+/// writer.write_alignment_record(&header, &record)?;
+///
+/// let record: sam::Record = "r1\t0\tsq0\t1\t60\t1M\t*\t0\t0\tA\tN".parse()?;
+/// assert!(writer.write_alignment_record(&header, &record).is_err());
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct Writer<W> {
+    inner: W,
+    sort_order: Option<SortOrder>,
+    query_name_order: Option<QueryNameOrder>,
+    prev: Option<Entry>,
+    record_count: u64,
+}
+
+/// The order of read names in a queryname-sorted output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum QueryNameOrder {
+    /// Read names are compared by their bytes (`lexicographical`).
+    #[default]
+    Lexicographical,
+    /// Read names are compared with runs of digits as numbers (`natural`).
+    Natural,
+}
+
+impl QueryNameOrder {
+    fn from_subsort_order(subsort_order: &SubsortOrder) -> Option<Self> {
+        match subsort_order {
+            SubsortOrder::QueryName(subsorts) => {
+                subsorts.iter().find_map(|subsort| match subsort.as_str() {
+                    "lexicographical" => Some(Self::Lexicographical),
+                    "natural" => Some(Self::Natural),
+                    _ => None,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    // Compares two read names. Records without read names are first.
+    fn cmp(self, a: Option<&[u8]>, b: Option<&[u8]>) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => match self {
+                Self::Lexicographical => a.cmp(b),
+                Self::Natural => natural_cmp(a, b),
+            },
+            _ => a.is_some().cmp(&b.is_some()),
+        }
+    }
+}
+
+impl<W> Writer<W>
+where
+    W: AlignmentWriter,
+{
+    /// Creates a sort order enforcing alignment writer.
+    ///
+    /// The sort order is read from the header when it is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    /// use noodles_util::alignment::sort_order;
+    /// let writer = sort_order::Writer::new(sam::Writer::new(Vec::new()));
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            sort_order: None,
+            query_name_order: None,
+            prev: None,
+            record_count: 0,
+        }
+    }
+
+    /// Creates a sort order enforcing alignment writer with an explicit sort order.
+    ///
+    /// This overrides the sort order declared in the header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::header::SortOrder};
+    /// use noodles_util::alignment::sort_order;
+    ///
+    /// let writer = sort_order::Writer::with_sort_order(
+    ///     sam::Writer::new(Vec::new()),
+    ///     SortOrder::QueryName,
+    /// );
+    /// ```
+    pub fn with_sort_order(inner: W, sort_order: SortOrder) -> Self {
+        Self {
+            sort_order: Some(sort_order),
+            ..Self::new(inner)
+        }
+    }
+
+    /// Sets the order of read names in a queryname-sorted output.
+    ///
+    /// This overrides the queryname order declared in the header subsort order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::header::SortOrder};
+    /// use noodles_util::alignment::sort_order::{self, QueryNameOrder};
+    ///
+    /// let mut writer = sort_order::Writer::with_sort_order(
+    ///     sam::Writer::new(Vec::new()),
+    ///     SortOrder::QueryName,
+    /// );
+    ///
+    /// writer.set_query_name_order(QueryNameOrder::Natural);
+    /// ```
+    pub fn set_query_name_order(&mut self, query_name_order: QueryNameOrder) {
+        self.query_name_order = Some(query_name_order);
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    /// use noodles_util::alignment::sort_order;
+    /// let writer = sort_order::Writer::new(sam::Writer::new(Vec::new()));
+    /// assert!(writer.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwraps and returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    /// use noodles_util::alignment::sort_order;
+    /// let writer = sort_order::Writer::new(sam::Writer::new(Vec::new()));
+    /// assert!(writer.into_inner().get_ref().is_empty());
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn check_order(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn AlignmentRecord,
+    ) -> io::Result<()> {
+        let sort_order = match self.sort_order {
+            Some(sort_order @ (SortOrder::Coordinate | SortOrder::QueryName)) => sort_order,
+            _ => return Ok(()),
+        };
+
+        let entry = Entry::new(header, record)?;
+
+        if let Some(prev) = &self.prev {
+            let ordering = if sort_order == SortOrder::QueryName {
+                self.query_name_order
+                    .unwrap_or_default()
+                    .cmp(entry.read_name.as_deref(), prev.read_name.as_deref())
+            } else {
+                entry.coordinate().cmp(&prev.coordinate())
+            };
+
+            if ordering == Ordering::Less {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    OutOfOrderError {
+                        record_index: self.record_count,
+                        record: entry.context(header),
+                        previous_record: prev.context(header),
+                    },
+                ));
+            }
+        }
+
+        self.prev = Some(entry);
+
+        Ok(())
+    }
+}
+
+impl<W> AlignmentWriter for Writer<W>
+where
+    W: AlignmentWriter,
+{
+    fn write_alignment_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        if self.sort_order.is_none() {
+            self.sort_order = header.header().and_then(|hd| hd.sort_order());
+        }
+
+        if self.query_name_order.is_none() {
+            self.query_name_order = header
+                .header()
+                .and_then(|hd| hd.subsort_order())
+                .and_then(QueryNameOrder::from_subsort_order);
+        }
+
+        self.inner.write_alignment_header(header)
+    }
+
+    fn write_alignment_record(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn AlignmentRecord,
+    ) -> io::Result<()> {
+        self.check_order(header, record)?;
+        self.inner.write_alignment_record(header, record)?;
+        self.record_count += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.inner.finish(header)
+    }
+}
+
+/// An error returned when a record is written out of order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutOfOrderError {
+    record_index: u64,
+    record: String,
+    previous_record: String,
+}
+
+impl OutOfOrderError {
+    /// Returns the 0-based index of the offending record in the output.
+    pub fn record_index(&self) -> u64 {
+        self.record_index
+    }
+
+    /// Returns a description of the offending record, i.e., its read name and position.
+    pub fn record(&self) -> &str {
+        &self.record
+    }
+
+    /// Returns a description of the record written before the offending record.
+    pub fn previous_record(&self) -> &str {
+        &self.previous_record
+    }
+}
+
+impl error::Error for OutOfOrderError {}
+
+impl fmt::Display for OutOfOrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "record {} ({}) is out of order: previous record was {}",
+            self.record_index, self.record, self.previous_record
+        )
+    }
+}
+
+// The fields of a written record used to check its order. The description of the record for
+// errors is only built when it is out of order.
+struct Entry {
+    read_name: Option<Vec<u8>>,
+    reference_sequence_id: Option<usize>,
+    alignment_start: Option<Position>,
+}
+
+impl Entry {
+    fn new(header: &sam::Header, record: &dyn AlignmentRecord) -> io::Result<Self> {
+        let reference_sequences = header.reference_sequences();
+
+        let reference_sequence_id = record
+            .reference_sequence(reference_sequences)
+            .transpose()?
+            .and_then(|reference_sequence| {
+                reference_sequences.get_index_of(reference_sequence.name().as_str())
+            });
+
+        Ok(Self {
+            read_name: record.read_name().map(|name| name.to_vec()),
+            reference_sequence_id,
+            alignment_start: record.alignment_start(),
+        })
+    }
+
+    fn coordinate(&self) -> (usize, i32) {
+        (
+            self.reference_sequence_id.unwrap_or(usize::MAX),
+            self.alignment_start.map(i32::from).unwrap_or_default(),
+        )
+    }
+
+    fn context(&self, header: &sam::Header) -> String {
+        let read_name = self
+            .read_name
+            .as_deref()
+            .map(String::from_utf8_lossy)
+            .unwrap_or_else(|| "*".into());
+
+        let reference_sequence_name = self
+            .reference_sequence_id
+            .and_then(|id| header.reference_sequences().get_index(id))
+            .map(|(name, _)| name);
+
+        match (reference_sequence_name, self.alignment_start) {
+            (Some(name), Some(position)) => {
+                format!("{} at {}:{}", read_name, name, i32::from(position))
+            }
+            _ => format!("{} (unmapped)", read_name),
+        }
+    }
+}
+
+// Compares read names with runs of ASCII digits compared by their numeric values.
+fn natural_cmp(a: &[u8], b: &[u8]) -> Ordering {
+    fn split_digits(s: &[u8]) -> (&[u8], &[u8]) {
+        let i = s
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .unwrap_or(s.len());
+
+        s.split_at(i)
+    }
+
+    fn trim_leading_zeros(s: &[u8]) -> &[u8] {
+        let i = s.iter().position(|&b| b != b'0').unwrap_or(s.len());
+        &s[i..]
+    }
+
+    let (mut a, mut b) = (a, b);
+
+    loop {
+        match (a.first(), b.first()) {
+            (Some(c), Some(d)) if c.is_ascii_digit() && d.is_ascii_digit() => {
+                let (a_digits, a_rest) = split_digits(a);
+                let (b_digits, b_rest) = split_digits(b);
+
+                let (a_digits, b_digits) =
+                    (trim_leading_zeros(a_digits), trim_leading_zeros(b_digits));
+
+                match a_digits
+                    .len()
+                    .cmp(&b_digits.len())
+                    .then_with(|| a_digits.cmp(b_digits))
+                {
+                    Ordering::Equal => {}
+                    ordering => return ordering,
+                }
+
+                a = a_rest;
+                b = b_rest;
+            }
+            (Some(c), Some(d)) => match c.cmp(d) {
+                Ordering::Equal => {
+                    a = &a[1..];
+                    b = &b[1..];
+                }
+                ordering => return ordering,
+            },
+            (c, d) => return c.is_some().cmp(&d.is_some()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::header::ReferenceSequence;
+
+    use super::*;
+
+    fn build_header(sort_order: SortOrder) -> Result<sam::Header, Box<dyn std::error::Error>> {
+        let hd = sam::header::header::Header::builder()
+            .set_sort_order(sort_order)
+            .build();
+
+        Ok(sam::Header::builder()
+            .set_header(hd)
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 13)?)
+            .build())
+    }
+
+    fn write(header: &sam::Header, records: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = Writer::new(sam::Writer::new(Vec::new()));
+        writer.write_alignment_header(header)?;
+
+        for s in records {
+            let record: sam::Record = format!("{}\t*\t0\t0\t*\t*", s).parse()?;
+            writer.write_alignment_record(header, &record)?;
+        }
+
+        writer.finish(header)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_alignment_record_with_coordinate_sort_order(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header(SortOrder::Coordinate)?;
+
+        write(
+            &header,
+            &[
+                "r0\t0\tsq0\t1\t60\t1M",
+                "r1\t0\tsq0\t1\t60\t1M",
+                "r2\t0\tsq1\t5\t60\t1M",
+                "r3\t4\t*\t0\t0\t*",
+            ],
+        )?;
+
+        let result = write(&header, &["r0\t0\tsq1\t1\t60\t1M", "r1\t0\tsq0\t5\t60\t1M"]);
+        let e = result.unwrap_err();
+        let e = e
+            .downcast_ref::<io::Error>()
+            .and_then(|e| e.get_ref())
+            .and_then(|e| e.downcast_ref::<OutOfOrderError>())
+            .expect("expected out of order error");
+        assert_eq!(e.record_index(), 1);
+        assert_eq!(e.record(), "r1 at sq0:5");
+        assert_eq!(e.previous_record(), "r0 at sq1:1");
+
+        assert!(write(&header, &["r0\t4\t*\t0\t0\t*", "r1\t0\tsq0\t5\t60\t1M"]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_alignment_record_with_queryname_sort_order(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header(SortOrder::QueryName)?;
+
+        write(
+            &header,
+            &[
+                "r0\t77\t*\t0\t0\t*",
+                "r0\t141\t*\t0\t0\t*",
+                "r1\t0\tsq0\t1\t60\t1M",
+            ],
+        )?;
+
+        assert!(write(&header, &["r1\t4\t*\t0\t0\t*", "r0\t4\t*\t0\t0\t*"]).is_err());
+        assert!(write(&header, &["r9\t4\t*\t0\t0\t*", "r10\t4\t*\t0\t0\t*"]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_alignment_record_with_natural_queryname_sort_order(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let hd = sam::header::header::Header::builder()
+            .set_sort_order(SortOrder::QueryName)
+            .set_subsort_order("queryname:natural".parse()?)
+            .build();
+
+        let header = sam::Header::builder().set_header(hd).build();
+
+        write(
+            &header,
+            &[
+                "r9\t4\t*\t0\t0\t*",
+                "r10\t4\t*\t0\t0\t*",
+                "r10a\t4\t*\t0\t0\t*",
+                "s2\t4\t*\t0\t0\t*",
+            ],
+        )?;
+
+        assert!(write(&header, &["r10\t4\t*\t0\t0\t*", "r9\t4\t*\t0\t0\t*"]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp(b"r9", b"r10"), Ordering::Less);
+        assert_eq!(natural_cmp(b"r10", b"r9"), Ordering::Greater);
+        assert_eq!(natural_cmp(b"r10", b"r10"), Ordering::Equal);
+        assert_eq!(natural_cmp(b"r010", b"r10"), Ordering::Equal);
+        assert_eq!(natural_cmp(b"r10:2", b"r10:10"), Ordering::Less);
+        assert_eq!(natural_cmp(b"r10", b"r10a"), Ordering::Less);
+        assert_eq!(natural_cmp(b"a10", b"b2"), Ordering::Less);
+        assert_eq!(natural_cmp(b"", b"r0"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_write_alignment_record_with_unsorted_sort_order(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header(SortOrder::Unsorted)?;
+        write(&header, &["r1\t0\tsq1\t5\t60\t1M", "r0\t0\tsq0\t1\t60\t1M"])?;
+        Ok(())
+    }
+}
@@ -0,0 +1,354 @@
+//! Lossy alignment record transforms.
+//!
+//! Transforms modify SAM records before they are written, typically to reduce the size of the
+//! output, e.g., by binning quality scores, removing data fields, or shortening read names. A
+//! [`Pipeline`] applies a list of transforms in order, so the same policy can be used for any
+//! output format.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io;
+//! use noodles_sam::{self as sam, record::data::field::Tag};
+//! use noodles_util::alignment::transform::{
+//!     Pipeline, QualityBinning, ReadNames, TagFilter, Transform,
+//! };
+//!
+//! let mut pipeline = Pipeline::default()
+//!     .push(QualityBinning::illumina())
+//!     .push(TagFilter::keep([Tag::ReadGroup]))
+//!     .push(ReadNames::Renumber(Default::default()));
+//!
+//! let mut record: sam::Record = "r0\t4\t*\t0\t0\t*\t*\t0\t0\tACGT\t#+5I\tRG:Z:rg0\tNM:i:0"
+//!     .parse()
+//!     .unwrap();
+//!
+//! pipeline.apply(&mut record)?;
+//!
+//! assert_eq!(record.to_string(), "1\t4\t*\t0\t0\t*\t*\t0\t0\tACGT\t'07I\tRG:Z:rg0");
+//! # Ok::<_, io::Error>(())
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, io,
+    ops::RangeInclusive,
+};
+
+use noodles_sam::{
+    self as sam,
+    record::{data::field::Tag, quality_scores::Score, QualityScores},
+};
+
+// § 1.4 The alignment section: mandatory fields (2020-07-19): `[!-~]`
+const MAX_SCORE: u8 = 93;
+
+/// A record transform.
+pub trait Transform {
+    /// Applies the transform to a record.
+    fn apply(&mut self, record: &mut sam::Record) -> io::Result<()>;
+}
+
+/// A list of transforms applied in order.
+#[derive(Default)]
+pub struct Pipeline {
+    transforms: Vec<Box<dyn Transform>>,
+}
+
+impl Pipeline {
+    /// Adds a transform to the end of the pipeline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::transform::{Pipeline, QualityBinning};
+    /// let pipeline = Pipeline::default().push(QualityBinning::illumina());
+    /// ```
+    pub fn push<T>(mut self, transform: T) -> Self
+    where
+        T: Transform + 'static,
+    {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Returns whether the pipeline has no transforms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::transform::Pipeline;
+    /// assert!(Pipeline::default().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.transforms.is_empty()
+    }
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("len", &self.transforms.len())
+            .finish()
+    }
+}
+
+impl Transform for Pipeline {
+    fn apply(&mut self, record: &mut sam::Record) -> io::Result<()> {
+        for transform in &mut self.transforms {
+            transform.apply(record)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A quality score binning transform.
+///
+/// Each quality score is replaced by the value of the bin it falls in. Scores that are not in any
+/// bin are unchanged.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QualityBinning {
+    table: Vec<u8>,
+}
+
+impl QualityBinning {
+    /// Creates a quality score binning transform from a list of bins.
+    ///
+    /// Each bin is a range of scores and the score that replaces them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::transform::QualityBinning;
+    /// let binning = QualityBinning::new(&[(0..=19, 10), (20..=93, 30)]);
+    /// ```
+    pub fn new(bins: &[(RangeInclusive<u8>, u8)]) -> Self {
+        let mut table: Vec<u8> = (0..=MAX_SCORE).collect();
+
+        for (range, value) in bins {
+            let value = (*value).min(MAX_SCORE);
+
+            for score in range.clone() {
+                if let Some(s) = table.get_mut(usize::from(score)) {
+                    *s = value;
+                }
+            }
+        }
+
+        Self { table }
+    }
+
+    /// Creates a quality score binning transform using the 8-level binning scheme of Illumina.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::transform::QualityBinning;
+    /// let binning = QualityBinning::illumina();
+    /// ```
+    pub fn illumina() -> Self {
+        Self::new(&[
+            (2..=9, 6),
+            (10..=19, 15),
+            (20..=24, 22),
+            (25..=29, 27),
+            (30..=34, 33),
+            (35..=39, 37),
+            (40..=MAX_SCORE, 40),
+        ])
+    }
+}
+
+impl Transform for QualityBinning {
+    fn apply(&mut self, record: &mut sam::Record) -> io::Result<()> {
+        let scores = record
+            .quality_scores()
+            .iter()
+            .map(|&score| {
+                let n = self.table[usize::from(u8::from(score))];
+                Score::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        *record.quality_scores_mut() = QualityScores::from(scores);
+
+        Ok(())
+    }
+}
+
+/// A data field filter transform.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TagFilter {
+    /// Keeps only the data fields with the given tags.
+    Keep(HashSet<Tag>),
+    /// Removes the data fields with the given tags.
+    Strip(HashSet<Tag>),
+}
+
+impl TagFilter {
+    /// Creates a filter that only keeps the data fields with the given tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::Tag;
+    /// use noodles_util::alignment::transform::TagFilter;
+    /// let filter = TagFilter::keep([Tag::ReadGroup]);
+    /// ```
+    pub fn keep<I>(tags: I) -> Self
+    where
+        I: IntoIterator<Item = Tag>,
+    {
+        Self::Keep(tags.into_iter().collect())
+    }
+
+    /// Creates a filter that removes the data fields with the given tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::Tag;
+    /// use noodles_util::alignment::transform::TagFilter;
+    /// let filter = TagFilter::strip([Tag::OriginalQualityScores]);
+    /// ```
+    pub fn strip<I>(tags: I) -> Self
+    where
+        I: IntoIterator<Item = Tag>,
+    {
+        Self::Strip(tags.into_iter().collect())
+    }
+}
+
+impl Transform for TagFilter {
+    fn apply(&mut self, record: &mut sam::Record) -> io::Result<()> {
+        let data = record.data_mut();
+
+        let tags: Vec<_> = match self {
+            Self::Keep(tags) => data.keys().filter(|tag| !tags.contains(tag)).collect(),
+            Self::Strip(tags) => data.keys().filter(|tag| tags.contains(tag)).collect(),
+        };
+
+        for tag in tags {
+            data.remove(tag);
+        }
+
+        Ok(())
+    }
+}
+
+/// A read name simplification transform.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReadNames {
+    /// Removes read names.
+    ///
+    /// This loses the association between the records of a template, unless the output format
+    /// keeps it otherwise, e.g., CRAM mate records.
+    Discard,
+    /// Replaces each distinct read name with a number, starting at 1.
+    ///
+    /// Records with the same read name are given the same number. This holds all read names seen
+    /// so far in memory.
+    Renumber(HashMap<String, u64>),
+}
+
+impl Transform for ReadNames {
+    fn apply(&mut self, record: &mut sam::Record) -> io::Result<()> {
+        match self {
+            Self::Discard => {
+                *record.read_name_mut() = None;
+            }
+            Self::Renumber(names) => {
+                if let Some(read_name) = record.read_name() {
+                    let next_id = names.len() as u64 + 1;
+                    let id = *names.entry(read_name.to_string()).or_insert(next_id);
+
+                    let read_name = id
+                        .to_string()
+                        .parse()
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+                    *record.read_name_mut() = Some(read_name);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quality_binning() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record: sam::Record = "r0\t4\t*\t0\t0\t*\t*\t0\t0\tACGTN\t!#+5~".parse()?;
+
+        QualityBinning::illumina().apply(&mut record)?;
+
+        let scores: Vec<_> = record
+            .quality_scores()
+            .iter()
+            .map(|&score| u8::from(score))
+            .collect();
+
+        assert_eq!(scores, [0, 6, 15, 22, 40]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tag_filter() -> Result<(), Box<dyn std::error::Error>> {
+        let record: sam::Record =
+            "r0\t4\t*\t0\t0\t*\t*\t0\t0\t*\t*\tRG:Z:rg0\tNM:i:0\tOQ:Z:NDLS".parse()?;
+
+        let mut actual = record.clone();
+        TagFilter::keep([Tag::ReadGroup]).apply(&mut actual)?;
+        assert_eq!(actual.data().keys().collect::<Vec<_>>(), [Tag::ReadGroup]);
+
+        let mut actual = record;
+        TagFilter::strip([Tag::OriginalQualityScores, Tag::ReadGroup]).apply(&mut actual)?;
+        assert_eq!(
+            actual.data().keys().collect::<Vec<_>>(),
+            [Tag::EditDistance]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_names() -> Result<(), Box<dyn std::error::Error>> {
+        let mut records: Vec<sam::Record> = ["r0\t77", "r1\t77", "r0\t141", "*\t4"]
+            .iter()
+            .map(|s| format!("{}\t*\t0\t0\t*\t*\t0\t0\t*\t*", s).parse())
+            .collect::<Result<_, _>>()?;
+
+        let mut transform = ReadNames::Renumber(HashMap::new());
+
+        for record in &mut records {
+            transform.apply(record)?;
+        }
+
+        let read_names: Vec<_> = records
+            .iter()
+            .map(|record| record.read_name().map(|name| name.to_string()))
+            .collect();
+
+        assert_eq!(
+            read_names,
+            [
+                Some(String::from("1")),
+                Some(String::from("2")),
+                Some(String::from("1")),
+                None
+            ]
+        );
+
+        let mut record = records[0].clone();
+        ReadNames::Discard.apply(&mut record)?;
+        assert!(record.read_name().is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,257 @@
+//! Alignment writer builder.
+
+use std::{
+    ffi::OsStr,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use noodles_bam as bam;
+use noodles_bgzf as bgzf;
+use noodles_cram as cram;
+use noodles_fasta as fasta;
+use noodles_sam::{self as sam, AlignmentWriter};
+
+use crate::Format;
+
+/// An alignment writer builder.
+///
+/// This builds a writer for SAM, BAM, or CRAM. The output format is either explicitly set or, when
+/// building from a path, detected from the file extension.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_sam as sam;
+/// use noodles_util::{alignment, Format};
+///
+/// let mut writer = alignment::WriterBuilder::default()
+///     .set_format(Format::Sam)
+///     .build_from_writer(io::sink())?;
+///
+/// let header = sam::Header::default();
+/// writer.write_alignment_header(&header)?;
+/// writer.write_alignment_record(&header, &sam::Record::default())?;
+/// writer.finish(&header)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct Builder {
+    format: Option<Format>,
+    reference_sequences: Vec<fasta::Record>,
+    worker_pool: Option<bgzf::WorkerPool>,
+}
+
+impl Builder {
+    /// Sets the output format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::{alignment, Format};
+    /// let builder = alignment::WriterBuilder::default().set_format(Format::Cram);
+    /// ```
+    pub fn set_format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Sets the reference sequences.
+    ///
+    /// This is only used when writing CRAM, where records are encoded against the reference
+    /// sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment;
+    /// let builder = alignment::WriterBuilder::default().set_reference_sequences(Vec::new());
+    /// ```
+    pub fn set_reference_sequences(mut self, reference_sequences: Vec<fasta::Record>) -> Self {
+        self.reference_sequences = reference_sequences;
+        self
+    }
+
+    /// Sets a worker pool.
+    ///
+    /// This is used to compress BAM blocks or encode CRAM containers in parallel. It is ignored
+    /// when writing SAM.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_util::alignment;
+    ///
+    /// let worker_pool = bgzf::WorkerPool::new(NonZeroUsize::new(4).unwrap());
+    /// let builder = alignment::WriterBuilder::default().set_worker_pool(worker_pool);
+    /// ```
+    pub fn set_worker_pool(mut self, worker_pool: bgzf::WorkerPool) -> Self {
+        self.worker_pool = Some(worker_pool);
+        self
+    }
+
+    /// Builds an alignment writer from a path.
+    ///
+    /// If the format is not set, it is detected from the file extension, i.e., `.sam`, `.bam`, or
+    /// `.cram`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_util::alignment;
+    /// let writer = alignment::WriterBuilder::default().build_from_path("out.bam")?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build_from_path<P>(mut self, dst: P) -> io::Result<Box<dyn AlignmentWriter>>
+    where
+        P: AsRef<Path>,
+    {
+        let dst = dst.as_ref();
+
+        if self.format.is_none() {
+            self.format = detect_format_from_extension(dst);
+        }
+
+        // An invalid format must not create or truncate the destination.
+        validate_format(self.format)?;
+
+        let file = File::create(dst)?;
+        self.build_from_writer(file)
+    }
+
+    /// Builds an alignment writer from a writer.
+    ///
+    /// The format must be set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::{alignment, Format};
+    ///
+    /// let writer = alignment::WriterBuilder::default()
+    ///     .set_format(Format::Bam)
+    ///     .build_from_writer(io::sink())?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build_from_writer<W>(self, writer: W) -> io::Result<Box<dyn AlignmentWriter>>
+    where
+        W: Write + 'static,
+    {
+        let writer: Box<dyn AlignmentWriter> = match validate_format(self.format)? {
+            Format::Sam => Box::new(sam::Writer::new(BufWriter::new(writer))),
+            Format::Bam => {
+                let mut builder = bam::Writer::builder(writer);
+
+                if let Some(worker_pool) = self.worker_pool {
+                    builder = builder.set_worker_pool(worker_pool);
+                }
+
+                Box::new(builder.build())
+            }
+            Format::Cram => {
+                let mut builder = cram::Writer::builder(writer, self.reference_sequences);
+
+                if let Some(worker_pool) = self.worker_pool {
+                    builder = builder.set_worker_pool(worker_pool);
+                }
+
+                Box::new(builder.build())
+            }
+            Format::Vcf | Format::Bcf => unreachable!("validated alignment format"),
+        };
+
+        Ok(writer)
+    }
+}
+
+fn validate_format(format: Option<Format>) -> io::Result<Format> {
+    match format {
+        Some(format @ (Format::Sam | Format::Bam | Format::Cram)) => Ok(format),
+        Some(format) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid alignment format: {}", format),
+        )),
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "missing alignment format",
+        )),
+    }
+}
+
+pub(super) fn detect_format_from_extension<P>(path: P) -> Option<Format>
+where
+    P: AsRef<Path>,
+{
+    match path.as_ref().extension().and_then(OsStr::to_str) {
+        Some("sam") => Some(Format::Sam),
+        Some("bam") => Some(Format::Bam),
+        Some("cram") => Some(Format::Cram),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format_from_extension() {
+        assert_eq!(detect_format_from_extension("out.sam"), Some(Format::Sam));
+        assert_eq!(detect_format_from_extension("out.bam"), Some(Format::Bam));
+        assert_eq!(detect_format_from_extension("out.cram"), Some(Format::Cram));
+        assert!(detect_format_from_extension("out.vcf").is_none());
+        assert!(detect_format_from_extension("out").is_none());
+    }
+
+    #[test]
+    fn test_build_from_path_with_an_invalid_format() -> io::Result<()> {
+        use std::fs;
+
+        let dir = std::env::temp_dir().join("noodles-util-alignment-writer-test");
+        fs::create_dir_all(&dir)?;
+
+        let dst = dir.join("out.txt");
+        let _ = fs::remove_file(&dst);
+        assert!(matches!(
+            Builder::default().build_from_path(&dst),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+        assert!(!dst.exists());
+
+        let dst = dir.join("out.vcf");
+        fs::write(&dst, b"noodles")?;
+        assert!(matches!(
+            Builder::default().set_format(Format::Vcf).build_from_path(&dst),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+        assert_eq!(fs::read(&dst)?, b"noodles");
+
+        fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_from_writer() {
+        assert!(Builder::default()
+            .set_format(Format::Cram)
+            .build_from_writer(io::sink())
+            .is_ok());
+
+        assert!(matches!(
+            Builder::default().build_from_writer(io::sink()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            Builder::default().set_format(Format::Vcf).build_from_writer(io::sink()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}
@@ -0,0 +1,57 @@
+//! Apache Arrow export.
+//!
+//! Records are converted to Arrow [`RecordBatch`]es with a fixed, typed schema, e.g., for analysis
+//! with dataframe libraries. Alignment records ([`alignment`], with the `alignment` feature) and
+//! VCF records ([`variant`], with the `variant` feature) are supported.
+//!
+//! Record batches can be written to Parquet using the Arrow writer of the `parquet` crate
+//! (`parquet::arrow::ArrowWriter`).
+//!
+//! [`RecordBatch`]: arrow_array::RecordBatch
+
+#[cfg(feature = "alignment")]
+pub mod alignment;
+#[cfg(feature = "variant")]
+pub mod variant;
+
+use std::{io, sync::Arc};
+
+use arrow_array::{
+    builder::{MapBuilder, MapFieldNames, StringBuilder},
+    ArrayRef, RecordBatch,
+};
+use arrow_schema::{DataType, Field, Schema};
+
+fn string_map_field_names() -> MapFieldNames {
+    MapFieldNames {
+        entry: String::from("entries"),
+        key: String::from("key"),
+        value: String::from("value"),
+    }
+}
+
+fn string_map_field(name: &str) -> Field {
+    let field_names = string_map_field_names();
+
+    Field::new_map(
+        name,
+        field_names.entry,
+        Field::new(field_names.key, DataType::Utf8, false),
+        Field::new(field_names.value, DataType::Utf8, true),
+        false,
+        false,
+    )
+}
+
+fn string_map_builder() -> MapBuilder<StringBuilder, StringBuilder> {
+    MapBuilder::new(
+        Some(string_map_field_names()),
+        StringBuilder::new(),
+        StringBuilder::new(),
+    )
+}
+
+fn build_record_batch(schema: Schema, columns: Vec<ArrayRef>) -> io::Result<RecordBatch> {
+    RecordBatch::try_new(Arc::new(schema), columns)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
@@ -0,0 +1,355 @@
+//! Alignment record Arrow export.
+
+use std::{io, str, sync::Arc};
+
+use arrow_array::{
+    builder::{Int32Builder, MapBuilder, StringBuilder, UInt16Builder, UInt8Builder},
+    ArrayRef, RecordBatch,
+};
+use arrow_schema::{DataType, Field, Schema};
+use noodles_sam::{self as sam, AlignmentRecord};
+
+/// Returns the Arrow schema of alignment record batches.
+///
+/// | name                           | type                | nullable |
+/// |--------------------------------|---------------------|----------|
+/// | `read_name`                    | `Utf8`              | yes      |
+/// | `flags`                        | `UInt16`            | no       |
+/// | `reference_sequence_name`      | `Utf8`              | yes      |
+/// | `position`                     | `Int32`             | yes      |
+/// | `mapping_quality`              | `UInt8`             | yes      |
+/// | `cigar`                        | `Utf8`              | yes      |
+/// | `mate_reference_sequence_name` | `Utf8`              | yes      |
+/// | `mate_position`                | `Int32`             | yes      |
+/// | `template_length`              | `Int32`             | no       |
+/// | `sequence`                     | `Utf8`              | yes      |
+/// | `quality_scores`               | `Utf8`              | yes      |
+/// | `data`                         | `Map<Utf8, Utf8>`   | no       |
+///
+/// Positions are 1-based. The CIGAR, sequence, and quality scores are formatted as in SAM. Data
+/// fields are mapped from their tags to their SAM-formatted types and values, e.g., `NH` =>
+/// `i:1`.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_util::arrow::alignment;
+/// let schema = alignment::schema();
+/// assert_eq!(schema.fields().len(), 12);
+/// ```
+pub fn schema() -> Schema {
+    Schema::new(vec![
+        Field::new("read_name", DataType::Utf8, true),
+        Field::new("flags", DataType::UInt16, false),
+        Field::new("reference_sequence_name", DataType::Utf8, true),
+        Field::new("position", DataType::Int32, true),
+        Field::new("mapping_quality", DataType::UInt8, true),
+        Field::new("cigar", DataType::Utf8, true),
+        Field::new("mate_reference_sequence_name", DataType::Utf8, true),
+        Field::new("mate_position", DataType::Int32, true),
+        Field::new("template_length", DataType::Int32, false),
+        Field::new("sequence", DataType::Utf8, true),
+        Field::new("quality_scores", DataType::Utf8, true),
+        super::string_map_field("data"),
+    ])
+}
+
+/// Converts alignment records to an Arrow record batch.
+///
+/// See [`schema`] for the schema of the record batch.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_sam::{self as sam, AlignmentReader};
+/// use noodles_util::arrow::alignment;
+///
+/// let data = b"@HD\tVN:1.6
+/// @SQ\tSN:sq0\tLN:8
+/// r0\t0\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS\tNH:i:1
+/// ";
+///
+/// let mut reader = sam::Reader::new(&data[..]);
+/// let header = reader.read_alignment_header()?;
+///
+/// let batch = alignment::to_record_batch(&header, reader.alignment_records(&header))?;
+/// assert_eq!(batch.num_rows(), 1);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn to_record_batch<I>(header: &sam::Header, records: I) -> io::Result<RecordBatch>
+where
+    I: IntoIterator<Item = io::Result<Box<dyn AlignmentRecord>>>,
+{
+    let mut builder = RecordBatchBuilder::new(header);
+
+    for result in records {
+        let record = result?;
+        builder.push(record.as_ref())?;
+    }
+
+    builder.finish()
+}
+
+/// An alignment record batch builder.
+///
+/// Records are appended using [`Self::push`], and [`Self::finish`] builds a record batch of the
+/// appended records. This can be used to convert a stream of records to batches of a bounded
+/// size.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_sam as sam;
+/// use noodles_util::arrow::alignment::RecordBatchBuilder;
+///
+/// let header = sam::Header::default();
+/// let mut builder = RecordBatchBuilder::new(&header);
+///
+/// builder.push(&sam::Record::default())?;
+/// assert_eq!(builder.len(), 1);
+///
+/// let batch = builder.finish()?;
+/// assert_eq!(batch.num_rows(), 1);
+/// assert!(builder.is_empty());
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct RecordBatchBuilder<'h> {
+    header: &'h sam::Header,
+    len: usize,
+    read_names: StringBuilder,
+    flags: UInt16Builder,
+    reference_sequence_names: StringBuilder,
+    positions: Int32Builder,
+    mapping_qualities: UInt8Builder,
+    cigars: StringBuilder,
+    mate_reference_sequence_names: StringBuilder,
+    mate_positions: Int32Builder,
+    template_lengths: Int32Builder,
+    sequences: StringBuilder,
+    quality_scores: StringBuilder,
+    data: MapBuilder<StringBuilder, StringBuilder>,
+}
+
+impl<'h> RecordBatchBuilder<'h> {
+    /// Creates an alignment record batch builder.
+    ///
+    /// The header is used to resolve reference sequence names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    /// use noodles_util::arrow::alignment::RecordBatchBuilder;
+    ///
+    /// let header = sam::Header::default();
+    /// let builder = RecordBatchBuilder::new(&header);
+    /// ```
+    pub fn new(header: &'h sam::Header) -> Self {
+        Self {
+            header,
+            len: 0,
+            read_names: StringBuilder::new(),
+            flags: UInt16Builder::new(),
+            reference_sequence_names: StringBuilder::new(),
+            positions: Int32Builder::new(),
+            mapping_qualities: UInt8Builder::new(),
+            cigars: StringBuilder::new(),
+            mate_reference_sequence_names: StringBuilder::new(),
+            mate_positions: Int32Builder::new(),
+            template_lengths: Int32Builder::new(),
+            sequences: StringBuilder::new(),
+            quality_scores: StringBuilder::new(),
+            data: super::string_map_builder(),
+        }
+    }
+
+    /// Returns the number of appended records.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether there are no appended records.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends an alignment record.
+    ///
+    /// If the record fails to convert, no columns are modified.
+    pub fn push<R>(&mut self, record: &R) -> io::Result<()>
+    where
+        R: AlignmentRecord + ?Sized,
+    {
+        let reference_sequences = self.header.reference_sequences();
+
+        let read_name = record
+            .read_name()
+            .map(|buf| {
+                str::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })
+            .transpose()?;
+
+        let reference_sequence_name = record
+            .reference_sequence(reference_sequences)
+            .transpose()?
+            .map(|reference_sequence| reference_sequence.name().to_string());
+
+        let mate_reference_sequence_name = record
+            .mate_reference_sequence(reference_sequences)
+            .transpose()?
+            .map(|reference_sequence| reference_sequence.name().to_string());
+
+        let cigar = record.cigar()?;
+        let sequence = record.sequence()?;
+        let quality_scores = record.quality_scores()?;
+        let data = record.data()?;
+
+        self.read_names.append_option(read_name);
+        self.flags.append_value(u16::from(record.flags()));
+        self.reference_sequence_names
+            .append_option(reference_sequence_name);
+        self.positions
+            .append_option(record.alignment_start().map(i32::from));
+        self.mapping_qualities
+            .append_option(record.mapping_quality().map(u8::from));
+        self.cigars
+            .append_option((!cigar.is_empty()).then(|| cigar.to_string()));
+        self.mate_reference_sequence_names
+            .append_option(mate_reference_sequence_name);
+        self.mate_positions
+            .append_option(record.mate_alignment_start().map(i32::from));
+        self.template_lengths.append_value(record.template_length());
+        self.sequences
+            .append_option((!sequence.is_empty()).then(|| sequence.to_string()));
+        self.quality_scores
+            .append_option((!quality_scores.is_empty()).then(|| quality_scores.to_string()));
+
+        for field in data.values() {
+            let value = field.value();
+            self.data.keys().append_value(field.tag().to_string());
+            self.data
+                .values()
+                .append_value(format!("{}:{}", value.ty(), value));
+        }
+
+        self.data
+            .append(true)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Builds a record batch of the appended records.
+    ///
+    /// The builder is reset and can be reused.
+    pub fn finish(&mut self) -> io::Result<RecordBatch> {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(self.read_names.finish()),
+            Arc::new(self.flags.finish()),
+            Arc::new(self.reference_sequence_names.finish()),
+            Arc::new(self.positions.finish()),
+            Arc::new(self.mapping_qualities.finish()),
+            Arc::new(self.cigars.finish()),
+            Arc::new(self.mate_reference_sequence_names.finish()),
+            Arc::new(self.mate_positions.finish()),
+            Arc::new(self.template_lengths.finish()),
+            Arc::new(self.sequences.finish()),
+            Arc::new(self.quality_scores.finish()),
+            Arc::new(self.data.finish()),
+        ];
+
+        self.len = 0;
+
+        super::build_record_batch(schema(), columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::{Array, Int32Array, MapArray, StringArray, UInt16Array, UInt8Array};
+
+    use super::*;
+
+    #[test]
+    fn test_to_record_batch() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::AlignmentReader;
+
+        let data = b"@HD\tVN:1.6
+@SQ\tSN:sq0\tLN:8
+r0\t99\tsq0\t1\t60\t4M\t=\t5\t8\tACGT\tNDLS\tNH:i:1\tRG:Z:rg0
+*\t4\t*\t0\t255\t*\t*\t0\t0\t*\t*
+";
+
+        let mut reader = sam::Reader::new(&data[..]);
+        let header = reader.read_alignment_header()?;
+        let batch = to_record_batch(&header, reader.alignment_records(&header))?;
+
+        assert_eq!(batch.schema().as_ref(), &schema());
+        assert_eq!(batch.num_rows(), 2);
+
+        let column = |name| batch.column_by_name(name).expect("missing column");
+
+        let read_names = column("read_name")
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(read_names.value(0), "r0");
+        assert!(read_names.is_null(1));
+
+        let flags = column("flags")
+            .as_any()
+            .downcast_ref::<UInt16Array>()
+            .unwrap();
+        assert_eq!(flags.values(), &[99, 4]);
+
+        let reference_sequence_names = column("mate_reference_sequence_name")
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(reference_sequence_names.value(0), "sq0");
+        assert!(reference_sequence_names.is_null(1));
+
+        let positions = column("position")
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(positions.value(0), 1);
+        assert!(positions.is_null(1));
+
+        let mapping_qualities = column("mapping_quality")
+            .as_any()
+            .downcast_ref::<UInt8Array>()
+            .unwrap();
+        assert_eq!(mapping_qualities.value(0), 60);
+        assert!(mapping_qualities.is_null(1));
+
+        let cigars = column("cigar")
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(cigars.value(0), "4M");
+        assert!(cigars.is_null(1));
+
+        let data = column("data").as_any().downcast_ref::<MapArray>().unwrap();
+        assert_eq!(data.value_length(0), 2);
+        assert_eq!(data.value_length(1), 0);
+
+        let keys = data.keys().as_any().downcast_ref::<StringArray>().unwrap();
+        let values = data
+            .values()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(keys.iter().collect::<Vec<_>>(), [Some("NH"), Some("RG")]);
+        assert_eq!(
+            values.iter().collect::<Vec<_>>(),
+            [Some("i:1"), Some("Z:rg0")]
+        );
+
+        Ok(())
+    }
+}
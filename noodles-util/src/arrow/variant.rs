@@ -0,0 +1,279 @@
+//! VCF record Arrow export.
+
+use std::{io, sync::Arc};
+
+use arrow_array::{
+    builder::{Float32Builder, Int32Builder, MapBuilder, StringBuilder},
+    ArrayRef, RecordBatch,
+};
+use arrow_schema::{DataType, Field, Schema};
+use noodles_vcf::{self as vcf, record::info::field::Value};
+
+/// Returns the Arrow schema of VCF record batches.
+///
+/// | name              | type              | nullable |
+/// |-------------------|-------------------|----------|
+/// | `chromosome`      | `Utf8`            | no       |
+/// | `position`        | `Int32`           | no       |
+/// | `ids`             | `Utf8`            | yes      |
+/// | `reference_bases` | `Utf8`            | no       |
+/// | `alternate_bases` | `Utf8`            | yes      |
+/// | `quality_score`   | `Float32`         | yes      |
+/// | `filters`         | `Utf8`            | yes      |
+/// | `info`            | `Map<Utf8, Utf8>` | no       |
+///
+/// Positions are 1-based. IDs, alternate bases, and filters are formatted as in VCF, e.g.,
+/// `rs1;rs2`. Info fields are mapped from their keys to their VCF-formatted values. Flags have
+/// null values.
+///
+/// Genotypes are not included.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_util::arrow::variant;
+/// let schema = variant::schema();
+/// assert_eq!(schema.fields().len(), 8);
+/// ```
+pub fn schema() -> Schema {
+    Schema::new(vec![
+        Field::new("chromosome", DataType::Utf8, false),
+        Field::new("position", DataType::Int32, false),
+        Field::new("ids", DataType::Utf8, true),
+        Field::new("reference_bases", DataType::Utf8, false),
+        Field::new("alternate_bases", DataType::Utf8, true),
+        Field::new("quality_score", DataType::Float32, true),
+        Field::new("filters", DataType::Utf8, true),
+        super::string_map_field("info"),
+    ])
+}
+
+/// Converts VCF records to an Arrow record batch.
+///
+/// See [`schema`] for the schema of the record batch.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_util::arrow::variant;
+/// use noodles_vcf as vcf;
+///
+/// let data = b"##fileformat=VCFv4.3
+/// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+/// sq0\t1\t.\tA\t.\t.\tPASS\t.
+/// ";
+///
+/// let mut reader = vcf::Reader::new(&data[..]);
+/// let header = reader.read_header()?.parse()?;
+///
+/// let batch = variant::to_record_batch(reader.records(&header))?;
+/// assert_eq!(batch.num_rows(), 1);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn to_record_batch<I>(records: I) -> io::Result<RecordBatch>
+where
+    I: IntoIterator<Item = io::Result<vcf::Record>>,
+{
+    let mut builder = RecordBatchBuilder::default();
+
+    for result in records {
+        let record = result?;
+        builder.push(&record)?;
+    }
+
+    builder.finish()
+}
+
+/// A VCF record batch builder.
+///
+/// Records are appended using [`Self::push`], and [`Self::finish`] builds a record batch of the
+/// appended records. This can be used to convert a stream of records to batches of a bounded
+/// size.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_util::arrow::variant::RecordBatchBuilder;
+/// use noodles_vcf as vcf;
+///
+/// let mut builder = RecordBatchBuilder::default();
+///
+/// let record: vcf::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.".parse()
+///     .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+/// builder.push(&record)?;
+/// assert_eq!(builder.len(), 1);
+///
+/// let batch = builder.finish()?;
+/// assert_eq!(batch.num_rows(), 1);
+/// assert!(builder.is_empty());
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct RecordBatchBuilder {
+    len: usize,
+    chromosomes: StringBuilder,
+    positions: Int32Builder,
+    ids: StringBuilder,
+    reference_bases: StringBuilder,
+    alternate_bases: StringBuilder,
+    quality_scores: Float32Builder,
+    filters: StringBuilder,
+    info: MapBuilder<StringBuilder, StringBuilder>,
+}
+
+impl RecordBatchBuilder {
+    /// Returns the number of appended records.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether there are no appended records.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends a VCF record.
+    pub fn push(&mut self, record: &vcf::Record) -> io::Result<()> {
+        let ids = record.ids();
+        let alternate_bases = record.alternate_bases();
+
+        self.chromosomes
+            .append_value(record.chromosome().to_string());
+        self.positions.append_value(i32::from(record.position()));
+        self.ids
+            .append_option((!ids.is_empty()).then(|| ids.to_string()));
+        self.reference_bases
+            .append_value(record.reference_bases().to_string());
+        self.alternate_bases
+            .append_option((!alternate_bases.is_empty()).then(|| alternate_bases.to_string()));
+        self.quality_scores
+            .append_option(record.quality_score().map(f32::from));
+        self.filters
+            .append_option(record.filters().map(|filters| filters.to_string()));
+
+        for field in record.info().values() {
+            self.info.keys().append_value(field.key());
+            let value = match field.value() {
+                None | Some(Value::Flag) => None,
+                Some(value) => Some(value.to_string()),
+            };
+
+            self.info.values().append_option(value);
+        }
+
+        self.info
+            .append(true)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Builds a record batch of the appended records.
+    ///
+    /// The builder is reset and can be reused.
+    pub fn finish(&mut self) -> io::Result<RecordBatch> {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(self.chromosomes.finish()),
+            Arc::new(self.positions.finish()),
+            Arc::new(self.ids.finish()),
+            Arc::new(self.reference_bases.finish()),
+            Arc::new(self.alternate_bases.finish()),
+            Arc::new(self.quality_scores.finish()),
+            Arc::new(self.filters.finish()),
+            Arc::new(self.info.finish()),
+        ];
+
+        self.len = 0;
+
+        super::build_record_batch(schema(), columns)
+    }
+}
+
+impl Default for RecordBatchBuilder {
+    fn default() -> Self {
+        Self {
+            len: 0,
+            chromosomes: StringBuilder::new(),
+            positions: Int32Builder::new(),
+            ids: StringBuilder::new(),
+            reference_bases: StringBuilder::new(),
+            alternate_bases: StringBuilder::new(),
+            quality_scores: Float32Builder::new(),
+            filters: StringBuilder::new(),
+            info: super::string_map_builder(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::{Array, Float32Array, Int32Array, MapArray, StringArray};
+
+    use super::*;
+
+    #[test]
+    fn test_to_record_batch() -> Result<(), Box<dyn std::error::Error>> {
+        let records: Vec<vcf::Record> = vec![
+            "sq0\t1\trs1\tA\tG,T\t13\tPASS\tNS=2;DB".parse()?,
+            "sq0\t8\t.\tC\t.\t.\t.\t.".parse()?,
+        ];
+
+        let batch = to_record_batch(records.into_iter().map(Ok))?;
+
+        assert_eq!(batch.schema().as_ref(), &schema());
+        assert_eq!(batch.num_rows(), 2);
+
+        let column = |name| batch.column_by_name(name).expect("missing column");
+
+        let positions = column("position")
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(positions.values(), &[1, 8]);
+
+        let ids = column("ids")
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(ids.value(0), "rs1");
+        assert!(ids.is_null(1));
+
+        let alternate_bases = column("alternate_bases")
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(alternate_bases.value(0), "G,T");
+        assert!(alternate_bases.is_null(1));
+
+        let quality_scores = column("quality_score")
+            .as_any()
+            .downcast_ref::<Float32Array>()
+            .unwrap();
+        assert_eq!(quality_scores.value(0), 13.0);
+        assert!(quality_scores.is_null(1));
+
+        let filters = column("filters")
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(filters.value(0), "PASS");
+        assert!(filters.is_null(1));
+
+        let info = column("info").as_any().downcast_ref::<MapArray>().unwrap();
+        assert_eq!(info.value_length(0), 2);
+        assert_eq!(info.value_length(1), 0);
+
+        let keys = info.keys().as_any().downcast_ref::<StringArray>().unwrap();
+        let values = info
+            .values()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(keys.iter().collect::<Vec<_>>(), [Some("NS"), Some("DB")]);
+        assert_eq!(values.iter().collect::<Vec<_>>(), [Some("2"), None]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// A compression method.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Compression {
+    /// BGZF (blocked gzip format).
+    Bgzf,
+}
+
+impl AsRef<str> for Compression {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Bgzf => "BGZF",
+        }
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
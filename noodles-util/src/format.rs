@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// A file format.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Format {
+    /// SAM (Sequence Alignment/Map).
+    Sam,
+    /// BAM (binary SAM).
+    Bam,
+    /// CRAM.
+    Cram,
+    /// VCF (Variant Call Format).
+    Vcf,
+    /// BCF (binary VCF).
+    Bcf,
+}
+
+impl AsRef<str> for Format {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Sam => "SAM",
+            Self::Bam => "BAM",
+            Self::Cram => "CRAM",
+            Self::Vcf => "VCF",
+            Self::Bcf => "BCF",
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Format::Sam.to_string(), "SAM");
+        assert_eq!(Format::Bam.to_string(), "BAM");
+        assert_eq!(Format::Cram.to_string(), "CRAM");
+        assert_eq!(Format::Vcf.to_string(), "VCF");
+        assert_eq!(Format::Bcf.to_string(), "BCF");
+    }
+}
@@ -0,0 +1,212 @@
+#![warn(missing_docs)]
+
+//! **noodles-util** are utilities for working with noodles.
+//!
+//! # Examples
+//!
+//! ## Detect the format of a file
+//!
+//! ```no_run
+//! # use std::{fs::File, io};
+//! use noodles_util as util;
+//!
+//! let mut file = File::open("sample.bam")?;
+//!
+//! match util::detect(&mut file)? {
+//!     Some((format, compression)) => println!("{} ({:?})", format, compression),
+//!     None => eprintln!("unknown format"),
+//! }
+//! # Ok::<(), io::Error>(())
+//! ```
+
+#[cfg(feature = "alignment")]
+pub mod alignment;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+mod compression;
+mod format;
+pub mod sort;
+#[cfg(feature = "variant")]
+pub mod variant;
+
+pub use self::{compression::Compression, format::Format};
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use noodles_bgzf as bgzf;
+
+// The maximum size of a BGZF block.
+const MAX_PEEK_LEN: u64 = 1 << 16;
+
+static BGZF_HEADER_PREFIX: &[u8] = &[0x1f, 0x8b, 0x08, 0x04];
+static BGZF_EXTRA_SUBFIELD_ID: &[u8] = b"BC";
+
+static BAM_MAGIC_NUMBER: &[u8] = b"BAM\x01";
+static BCF_MAGIC_NUMBER: &[u8] = b"BCF\x02";
+static CRAM_MAGIC_NUMBER: &[u8] = b"CRAM";
+static VCF_HEADER_PREFIX: &[u8] = b"##fileformat=VCF";
+
+const SAM_MIN_FIELD_COUNT: usize = 11;
+
+/// Detects the format and compression of a stream by inspecting its first bytes.
+///
+/// This recognizes SAM, BAM, CRAM, VCF, and BCF and whether the stream is BGZF-compressed. The
+/// stream is sought back to its original position after inspection.
+///
+/// If the format is not recognized, this returns `None`.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Cursor};
+/// use noodles_util::{self as util, Format};
+///
+/// let mut reader = Cursor::new(b"##fileformat=VCFv4.3\n");
+/// assert_eq!(util::detect(&mut reader)?, Some((Format::Vcf, None)));
+/// assert_eq!(reader.position(), 0);
+///
+/// let mut reader = Cursor::new(b"BCF\x02\x02");
+/// assert_eq!(util::detect(&mut reader)?, Some((Format::Bcf, None)));
+///
+/// let mut reader = Cursor::new(b"ndls");
+/// assert!(util::detect(&mut reader)?.is_none());
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn detect<R>(reader: &mut R) -> io::Result<Option<(Format, Option<Compression>)>>
+where
+    R: Read + Seek,
+{
+    let pos = reader.stream_position()?;
+    let result = detect_from_start(reader);
+    reader.seek(SeekFrom::Start(pos))?;
+    result
+}
+
+fn detect_from_start<R>(reader: &mut R) -> io::Result<Option<(Format, Option<Compression>)>>
+where
+    R: Read,
+{
+    let mut buf = Vec::new();
+    reader.take(MAX_PEEK_LEN).read_to_end(&mut buf)?;
+
+    if buf.starts_with(CRAM_MAGIC_NUMBER) {
+        return Ok(Some((Format::Cram, None)));
+    }
+
+    if is_bgzf(&buf) {
+        let mut decoder = bgzf::Reader::new((&buf[..]).chain(reader));
+
+        let mut uncompressed_buf = Vec::new();
+        (&mut decoder)
+            .take(MAX_PEEK_LEN)
+            .read_to_end(&mut uncompressed_buf)?;
+
+        Ok(detect_format(&uncompressed_buf).map(|format| (format, Some(Compression::Bgzf))))
+    } else {
+        Ok(detect_format(&buf).map(|format| (format, None)))
+    }
+}
+
+fn is_bgzf(buf: &[u8]) -> bool {
+    const EXTRA_SUBFIELD_ID_START: usize = 12;
+    const EXTRA_SUBFIELD_ID_END: usize = EXTRA_SUBFIELD_ID_START + 2;
+
+    buf.starts_with(BGZF_HEADER_PREFIX)
+        && buf.get(EXTRA_SUBFIELD_ID_START..EXTRA_SUBFIELD_ID_END) == Some(BGZF_EXTRA_SUBFIELD_ID)
+}
+
+fn detect_format(buf: &[u8]) -> Option<Format> {
+    if buf.starts_with(BAM_MAGIC_NUMBER) {
+        Some(Format::Bam)
+    } else if buf.starts_with(BCF_MAGIC_NUMBER) {
+        Some(Format::Bcf)
+    } else if buf.starts_with(VCF_HEADER_PREFIX) {
+        Some(Format::Vcf)
+    } else if is_sam(buf) {
+        Some(Format::Sam)
+    } else {
+        None
+    }
+}
+
+fn is_sam(buf: &[u8]) -> bool {
+    // A header line starts with `@` and a two-letter record type, e.g., `@HD`.
+    if let [b'@', a, b, b'\t', ..] = buf {
+        return a.is_ascii_alphabetic() && b.is_ascii_alphabetic();
+    }
+
+    // Otherwise, the first line must be a record with at least 11 fields and a numeric flag.
+    let line = buf.split(|&b| b == b'\n').next().unwrap_or_default();
+    let fields: Vec<_> = line.split(|&b| b == b'\t').collect();
+
+    fields.len() >= SAM_MIN_FIELD_COUNT
+        && std::str::from_utf8(fields[1])
+            .ok()
+            .and_then(|s| s.parse::<u16>().ok())
+            .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use super::*;
+
+    fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(data)?;
+        writer.finish()
+    }
+
+    #[test]
+    fn test_detect() -> io::Result<()> {
+        fn t(data: &[u8], expected: Option<(Format, Option<Compression>)>) -> io::Result<()> {
+            let mut reader = Cursor::new(data);
+            assert_eq!(detect(&mut reader)?, expected);
+            assert_eq!(reader.position(), 0);
+            Ok(())
+        }
+
+        const SAM_RECORD: &[u8] = b"*\t4\t*\t0\t255\t*\t*\t0\t0\t*\t*\n";
+
+        t(b"@HD\tVN:1.6\n", Some((Format::Sam, None)))?;
+        t(SAM_RECORD, Some((Format::Sam, None)))?;
+        t(b"BAM\x01", Some((Format::Bam, None)))?;
+        t(b"CRAM\x03\x00", Some((Format::Cram, None)))?;
+        t(b"##fileformat=VCFv4.3\n", Some((Format::Vcf, None)))?;
+        t(b"BCF\x02\x02", Some((Format::Bcf, None)))?;
+
+        t(
+            &compress(b"@SQ\tSN:sq0\tLN:8\n")?,
+            Some((Format::Sam, Some(Compression::Bgzf))),
+        )?;
+        t(
+            &compress(b"BAM\x01")?,
+            Some((Format::Bam, Some(Compression::Bgzf))),
+        )?;
+        t(
+            &compress(b"##fileformat=VCFv4.3\n")?,
+            Some((Format::Vcf, Some(Compression::Bgzf))),
+        )?;
+        t(
+            &compress(b"BCF\x02\x02")?,
+            Some((Format::Bcf, Some(Compression::Bgzf))),
+        )?;
+
+        t(b"", None)?;
+        t(b">sq0\nACGT\n", None)?;
+        t(b"sq0\t8\t13\n", None)?;
+        t(&compress(b"ndls")?, None)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_detect_from_current_position() -> io::Result<()> {
+        let mut reader = Cursor::new(b"ndlsBAM\x01");
+        reader.set_position(4);
+        assert_eq!(detect(&mut reader)?, Some((Format::Bam, None)));
+        assert_eq!(reader.position(), 4);
+        Ok(())
+    }
+}
@@ -0,0 +1,381 @@
+//! External sorting.
+//!
+//! An external sort orders more records than fit in memory. Records are buffered up to a memory
+//! budget, and each full buffer is sorted and written to a temporary BGZF-compressed shard. The
+//! shards are then merged to produce the sorted output.
+//!
+//! The sorter is generic over the record type, using a [`Codec`] to serialize records, and the
+//! sort key, using a key function. Codecs for BAM records ([`codec::BamCodec`], with the
+//! `alignment` feature) and VCF records ([`codec::VcfCodec`], with the `variant` feature) are
+//! included.
+
+mod builder;
+pub mod codec;
+mod shard;
+
+pub use self::{builder::Builder, codec::Codec};
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    fs::File,
+    io::{self, BufReader},
+    mem,
+    num::NonZeroUsize,
+    ops::Range,
+    panic,
+    path::PathBuf,
+    thread::{self, JoinHandle},
+    vec,
+};
+
+use noodles_bgzf as bgzf;
+
+use self::shard::Shard;
+
+/// An external sorter.
+///
+/// Records are added using [`Self::push`]. When all records have been added, [`Self::finish`]
+/// returns an iterator over the records in sorted order. The sort is stable, i.e., records with
+/// equal keys are returned in the order they were added.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alignment")] {
+/// # use std::io;
+/// use noodles_bam as bam;
+/// use noodles_sam::record::Flags;
+/// use noodles_util::sort::{self, codec::BamCodec};
+///
+/// let mut sorter = sort::Builder::new(BamCodec, |record: &bam::Record| record.flags().bits())
+///     .build();
+///
+/// for flags in [Flags::UNMAPPED, Flags::SEGMENTED, Flags::empty()] {
+///     let mut record = bam::Record::default();
+///     *record.flags_mut() = flags;
+///     sorter.push(record)?;
+/// }
+///
+/// let flags = sorter
+///     .finish()?
+///     .map(|result| result.map(|record| record.flags()))
+///     .collect::<io::Result<Vec<_>>>()?;
+///
+/// assert_eq!(flags, [Flags::empty(), Flags::SEGMENTED, Flags::UNMAPPED]);
+/// # }
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct Sorter<C, K, F> {
+    codec: C,
+    key: F,
+    memory_budget: usize,
+    temp_dir: PathBuf,
+    worker_count: NonZeroUsize,
+    buf: Vec<u8>,
+    entries: Vec<(K, Range<usize>)>,
+    shards: Vec<Shard>,
+    // Workers writing shards in the background and the memory used by their records.
+    workers: VecDeque<(JoinHandle<io::Result<()>>, usize)>,
+    in_flight_memory_usage: usize,
+}
+
+impl<C, K, F> Sorter<C, K, F>
+where
+    C: Codec,
+    F: Fn(&C::Record) -> K,
+    K: Ord,
+{
+    /// Adds a record to the sorter.
+    ///
+    /// If the memory budget is reached, the buffered records are sorted and written to a
+    /// temporary shard. With multiple workers, the budget is shared by the buffered records and
+    /// the records of shards still being written, and adding a record waits for a worker to
+    /// finish if the budget would be exceeded.
+    pub fn push(&mut self, record: C::Record) -> io::Result<()> {
+        let key = (self.key)(&record);
+
+        let start = self.buf.len();
+        self.codec.encode(&mut self.buf, &record)?;
+        let end = self.buf.len();
+
+        self.entries.push((key, start..end));
+
+        while self.memory_usage() >= self.memory_budget {
+            if !self.join_next_worker()? {
+                break;
+            }
+        }
+
+        if self.buffered_memory_usage() >= self.spill_threshold() {
+            self.spill()?;
+        }
+
+        Ok(())
+    }
+
+    /// Finishes adding records and returns an iterator over the sorted records.
+    ///
+    /// This waits for all temporary shards to be written.
+    pub fn finish(mut self) -> io::Result<Records<C, K, F>> {
+        while self.join_next_worker()? {}
+
+        let mut sources = Vec::with_capacity(self.shards.len() + 1);
+
+        for shard in &self.shards {
+            sources.push(Source::Shard(Box::new(shard.open()?)));
+        }
+
+        let ranges = sort_entries(mem::take(&mut self.entries));
+
+        sources.push(Source::Memory {
+            buf: mem::take(&mut self.buf),
+            ranges: ranges.into_iter(),
+        });
+
+        let mut records = Records {
+            codec: self.codec,
+            key: self.key,
+            pending_records: (0..sources.len()).map(|_| None).collect(),
+            sources,
+            queue: BinaryHeap::new(),
+            _shards: mem::take(&mut self.shards),
+        };
+
+        for i in 0..records.sources.len() {
+            records.fill(i)?;
+        }
+
+        Ok(records)
+    }
+
+    // Returns the memory used by buffered records and the records of shards being written.
+    fn memory_usage(&self) -> usize {
+        self.buffered_memory_usage() + self.in_flight_memory_usage
+    }
+
+    fn buffered_memory_usage(&self) -> usize {
+        self.buf.len() + self.entries.len() * mem::size_of::<(K, Range<usize>)>()
+    }
+
+    // Returns the buffered memory usage at which records are written to a shard.
+    //
+    // With multiple workers, the memory budget is split between the shards being written by each
+    // worker and the records being buffered.
+    fn spill_threshold(&self) -> usize {
+        match self.worker_count.get() {
+            1 => self.memory_budget,
+            n => self.memory_budget / (n + 1),
+        }
+    }
+
+    // Waits for the oldest worker to finish. This returns whether there was a worker.
+    fn join_next_worker(&mut self) -> io::Result<bool> {
+        match self.workers.pop_front() {
+            Some((worker, memory_usage)) => {
+                self.in_flight_memory_usage -= memory_usage;
+                join(worker)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        let memory_usage = self.buffered_memory_usage();
+        let ranges = sort_entries(mem::take(&mut self.entries));
+
+        let (shard, file) = Shard::create(&self.temp_dir)?;
+        self.shards.push(shard);
+
+        if self.worker_count.get() == 1 {
+            shard::write(file, &self.buf, &ranges)?;
+            self.buf.clear();
+            return Ok(());
+        }
+
+        while self.workers.len() >= self.worker_count.get() {
+            self.join_next_worker()?;
+        }
+
+        let buf = mem::take(&mut self.buf);
+        let worker = thread::spawn(move || shard::write(file, &buf, &ranges));
+        self.workers.push_back((worker, memory_usage));
+        self.in_flight_memory_usage += memory_usage;
+
+        Ok(())
+    }
+}
+
+/// An iterator over sorted records.
+///
+/// This is created by calling [`Sorter::finish`]. Temporary shards are removed when the iterator
+/// is dropped.
+pub struct Records<C, K, F>
+where
+    C: Codec,
+{
+    codec: C,
+    key: F,
+    sources: Vec<Source>,
+    pending_records: Vec<Option<C::Record>>,
+    queue: BinaryHeap<Reverse<(K, usize)>>,
+    _shards: Vec<Shard>,
+}
+
+impl<C, K, F> Records<C, K, F>
+where
+    C: Codec,
+    F: Fn(&C::Record) -> K,
+    K: Ord,
+{
+    // Reads the next record of the given source into the queue.
+    fn fill(&mut self, i: usize) -> io::Result<()> {
+        let record = match &mut self.sources[i] {
+            Source::Shard(reader) => self.codec.decode(reader)?,
+            Source::Memory { buf, ranges } => match ranges.next() {
+                Some(range) => self.codec.decode(&mut &buf[range])?,
+                None => None,
+            },
+        };
+
+        if let Some(record) = record {
+            let key = (self.key)(&record);
+            self.pending_records[i] = Some(record);
+            self.queue.push(Reverse((key, i)));
+        }
+
+        Ok(())
+    }
+}
+
+impl<C, K, F> Iterator for Records<C, K, F>
+where
+    C: Codec,
+    F: Fn(&C::Record) -> K,
+    K: Ord,
+{
+    type Item = io::Result<C::Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((_, i)) = self.queue.pop()?;
+        let record = self.pending_records[i].take()?;
+
+        if let Err(e) = self.fill(i) {
+            return Some(Err(e));
+        }
+
+        Some(Ok(record))
+    }
+}
+
+enum Source {
+    Shard(Box<bgzf::Reader<BufReader<File>>>),
+    Memory {
+        buf: Vec<u8>,
+        ranges: vec::IntoIter<Range<usize>>,
+    },
+}
+
+// Stably sorts the entries by key and returns the ranges of their encoded records.
+fn sort_entries<K>(mut entries: Vec<(K, Range<usize>)>) -> Vec<Range<usize>>
+where
+    K: Ord,
+{
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    entries.into_iter().map(|(_, range)| range).collect()
+}
+
+fn join(worker: JoinHandle<io::Result<()>>) -> io::Result<()> {
+    worker.join().unwrap_or_else(|e| panic::resume_unwind(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, Write};
+
+    use super::*;
+
+    // Encodes (key, value) pairs as two bytes.
+    struct PairCodec;
+
+    impl Codec for PairCodec {
+        type Record = (u8, u8);
+
+        fn encode<W>(&self, writer: &mut W, record: &Self::Record) -> io::Result<()>
+        where
+            W: Write,
+        {
+            writer.write_all(&[record.0, record.1])
+        }
+
+        fn decode<R>(&self, reader: &mut R) -> io::Result<Option<Self::Record>>
+        where
+            R: BufRead,
+        {
+            let mut buf = [0; 2];
+
+            match reader.read_exact(&mut buf) {
+                Ok(()) => Ok(Some((buf[0], buf[1]))),
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+                Err(e) => Err(e),
+            }
+        }
+    }
+
+    fn sort(
+        records: &[(u8, u8)],
+        memory_budget: usize,
+        worker_count: usize,
+    ) -> io::Result<(usize, Vec<(u8, u8)>)> {
+        let mut sorter = Builder::new(PairCodec, |record: &(u8, u8)| record.0)
+            .set_memory_budget(memory_budget)
+            .set_worker_count(NonZeroUsize::new(worker_count).unwrap())
+            .build();
+
+        for &record in records {
+            sorter.push(record)?;
+        }
+
+        let shard_count = sorter.shards.len();
+        let sorted_records = sorter.finish()?.collect::<io::Result<_>>()?;
+
+        Ok((shard_count, sorted_records))
+    }
+
+    #[test]
+    fn test_sort() -> io::Result<()> {
+        let records: Vec<_> = (0..32).map(|i| ((i * 7) % 5, i)).collect();
+
+        let mut expected = records.clone();
+        expected.sort_by_key(|record| record.0);
+
+        // 3 records per shard
+        let memory_budget = 3 * (2 + mem::size_of::<(u8, Range<usize>)>());
+
+        assert_eq!(sort(&records, usize::MAX, 1)?, (0, expected.clone()));
+        assert_eq!(sort(&records, memory_budget, 1)?, (10, expected.clone()));
+
+        // The budget is split between 4 workers and the buffered records.
+        assert_eq!(sort(&records, 5 * memory_budget, 4)?, (10, expected));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_with_workers_stays_within_memory_budget() -> io::Result<()> {
+        let memory_budget = 5 * 3 * (2 + mem::size_of::<(u8, Range<usize>)>());
+
+        let mut sorter = Builder::new(PairCodec, |record: &(u8, u8)| record.0)
+            .set_memory_budget(memory_budget)
+            .set_worker_count(NonZeroUsize::new(4).unwrap())
+            .build();
+
+        for i in 0..255 {
+            sorter.push((i % 7, i))?;
+            assert!(sorter.memory_usage() < memory_budget);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+use std::{env, num::NonZeroUsize, path::PathBuf};
+
+use super::{Codec, Sorter};
+
+// 256 MiB
+const DEFAULT_MEMORY_BUDGET: usize = 1 << 28;
+
+/// An external sorter builder.
+pub struct Builder<C, F> {
+    codec: C,
+    key: F,
+    memory_budget: usize,
+    temp_dir: Option<PathBuf>,
+    worker_count: NonZeroUsize,
+}
+
+impl<C, F> Builder<C, F>
+where
+    C: Codec,
+{
+    /// Creates an external sorter builder.
+    ///
+    /// `codec` serializes records to temporary shards, and `key` returns the sort key of a
+    /// record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alignment")] {
+    /// use noodles_bam as bam;
+    /// use noodles_util::sort::{self, codec::BamCodec};
+    ///
+    /// let builder = sort::Builder::new(BamCodec, |record: &bam::Record| record.flags().bits());
+    /// # }
+    /// ```
+    pub fn new(codec: C, key: F) -> Self {
+        Self {
+            codec,
+            key,
+            memory_budget: DEFAULT_MEMORY_BUDGET,
+            temp_dir: None,
+            worker_count: NonZeroUsize::new(1).unwrap(),
+        }
+    }
+
+    /// Sets the memory budget in bytes.
+    ///
+    /// When the encoded records held in memory reach this size, they are sorted and written to a
+    /// temporary shard. With multiple workers, this includes the records of shards that are still
+    /// being written, and the budget is split evenly between the workers and the records being
+    /// buffered. By default, this is 256 MiB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alignment")] {
+    /// use noodles_bam as bam;
+    /// use noodles_util::sort::{self, codec::BamCodec};
+    /// let builder = sort::Builder::new(BamCodec, |record: &bam::Record| record.flags().bits())
+    ///     .set_memory_budget(1 << 20);
+    /// # }
+    /// ```
+    pub fn set_memory_budget(mut self, memory_budget: usize) -> Self {
+        self.memory_budget = memory_budget;
+        self
+    }
+
+    /// Sets the directory to write temporary shards to.
+    ///
+    /// By default, this is the system temporary directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alignment")] {
+    /// use noodles_bam as bam;
+    /// use noodles_util::sort::{self, codec::BamCodec};
+    /// let builder = sort::Builder::new(BamCodec, |record: &bam::Record| record.flags().bits())
+    ///     .set_temp_dir("/tmp");
+    /// # }
+    /// ```
+    pub fn set_temp_dir<P>(mut self, temp_dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.temp_dir = Some(temp_dir.into());
+        self
+    }
+
+    /// Sets the number of worker threads used to compress temporary shards.
+    ///
+    /// With a single worker, shards are compressed on the calling thread. Otherwise, up to this
+    /// many shards are compressed in parallel in the background while records continue to be
+    /// added. Shards are smaller with more workers, as they share the memory budget (see
+    /// [`Self::set_memory_budget`]). By default, this is 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alignment")] {
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bam as bam;
+    /// use noodles_util::sort::{self, codec::BamCodec};
+    /// let builder = sort::Builder::new(BamCodec, |record: &bam::Record| record.flags().bits())
+    ///     .set_worker_count(NonZeroUsize::new(4).unwrap());
+    /// # }
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
+    /// Builds an external sorter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alignment")] {
+    /// use noodles_bam as bam;
+    /// use noodles_util::sort::{self, codec::BamCodec};
+    /// let sorter = sort::Builder::new(BamCodec, |record: &bam::Record| record.flags().bits())
+    ///     .build();
+    /// # }
+    /// ```
+    pub fn build<K>(self) -> Sorter<C, K, F>
+    where
+        F: Fn(&C::Record) -> K,
+        K: Ord,
+    {
+        Sorter {
+            codec: self.codec,
+            key: self.key,
+            memory_budget: self.memory_budget,
+            temp_dir: self.temp_dir.unwrap_or_else(env::temp_dir),
+            worker_count: self.worker_count,
+            buf: Vec::new(),
+            entries: Vec::new(),
+            shards: Vec::new(),
+            workers: Default::default(),
+            in_flight_memory_usage: 0,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Sort record codecs.
+
+#[cfg(feature = "alignment")]
+mod bam;
+#[cfg(feature = "variant")]
+mod vcf;
+
+#[cfg(feature = "alignment")]
+pub use self::bam::BamCodec;
+#[cfg(feature = "variant")]
+pub use self::vcf::VcfCodec;
+
+use std::io::{self, BufRead, Write};
+
+/// A record codec.
+///
+/// A codec serializes records to and deserializes records from the temporary shards of an
+/// external sort. Encoded records must be self-delimiting, i.e., `decode` reads exactly one
+/// record that was written by `encode`.
+pub trait Codec {
+    /// The record type.
+    type Record;
+
+    /// Encodes a record.
+    fn encode<W>(&self, writer: &mut W, record: &Self::Record) -> io::Result<()>
+    where
+        W: Write;
+
+    /// Decodes a record.
+    ///
+    /// This returns `None` if the reader is at EOF.
+    fn decode<R>(&self, reader: &mut R) -> io::Result<Option<Self::Record>>
+    where
+        R: BufRead;
+}
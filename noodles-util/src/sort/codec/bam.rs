@@ -0,0 +1,59 @@
+use std::io::{self, BufRead, Write};
+
+use noodles_bam as bam;
+
+use super::Codec;
+
+/// A BAM record codec.
+///
+/// Records are encoded as BAM records, i.e., the block size followed by the record fields.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BamCodec;
+
+impl Codec for BamCodec {
+    type Record = bam::Record;
+
+    fn encode<W>(&self, writer: &mut W, record: &Self::Record) -> io::Result<()>
+    where
+        W: Write,
+    {
+        bam::Writer::from(writer).write_record(record)
+    }
+
+    fn decode<R>(&self, reader: &mut R) -> io::Result<Option<Self::Record>>
+    where
+        R: BufRead,
+    {
+        let mut record = bam::Record::default();
+
+        match bam::Reader::from(reader).read_record(&mut record)? {
+            0 => Ok(None),
+            _ => Ok(Some(record)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::record::{Flags, MappingQuality};
+
+    use super::*;
+
+    #[test]
+    fn test_encode_and_decode() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record = bam::Record::default();
+        *record.flags_mut() = Flags::empty();
+        *record.mapping_quality_mut() = Some(MappingQuality::try_from(37)?);
+
+        let mut buf = Vec::new();
+        BamCodec.encode(&mut buf, &record)?;
+        BamCodec.encode(&mut buf, &record)?;
+
+        let mut reader = &buf[..];
+        assert_eq!(BamCodec.decode(&mut reader)?, Some(record.clone()));
+        assert_eq!(BamCodec.decode(&mut reader)?, Some(record));
+        assert!(BamCodec.decode(&mut reader)?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use std::io::{self, BufRead, Write};
+
+use noodles_vcf as vcf;
+
+use super::Codec;
+
+const LINE_FEED: char = '\n';
+
+/// A VCF record codec.
+///
+/// Records are encoded as VCF lines. The header is used to parse the lines when decoding.
+#[derive(Clone, Debug)]
+pub struct VcfCodec {
+    header: vcf::Header,
+}
+
+impl VcfCodec {
+    /// Creates a VCF record codec.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::sort::codec::VcfCodec;
+    /// use noodles_vcf as vcf;
+    /// let codec = VcfCodec::new(vcf::Header::default());
+    /// ```
+    pub fn new(header: vcf::Header) -> Self {
+        Self { header }
+    }
+}
+
+impl Codec for VcfCodec {
+    type Record = vcf::Record;
+
+    fn encode<W>(&self, writer: &mut W, record: &Self::Record) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writeln!(writer, "{}", record)
+    }
+
+    fn decode<R>(&self, reader: &mut R) -> io::Result<Option<Self::Record>>
+    where
+        R: BufRead,
+    {
+        let mut buf = String::new();
+
+        if reader.read_line(&mut buf)? == 0 {
+            return Ok(None);
+        }
+
+        if buf.ends_with(LINE_FEED) {
+            buf.pop();
+        }
+
+        vcf::Record::try_from_str(&buf, &self.header)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_and_decode() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::default();
+        let codec = VcfCodec::new(header.clone());
+
+        let record = vcf::Record::try_from_str("sq0\t8\t.\tA\t.\t.\tPASS\t.", &header)?;
+
+        let mut buf = Vec::new();
+        codec.encode(&mut buf, &record)?;
+        codec.encode(&mut buf, &record)?;
+
+        let mut reader = &buf[..];
+        assert_eq!(codec.decode(&mut reader)?, Some(record.clone()));
+        assert_eq!(codec.decode(&mut reader)?, Some(record));
+        assert!(codec.decode(&mut reader)?.is_none());
+
+        Ok(())
+    }
+}
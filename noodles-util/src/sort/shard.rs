@@ -0,0 +1,61 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use noodles_bgzf as bgzf;
+
+static SHARD_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+// A temporary file of sorted, BGZF-compressed encoded records.
+//
+// The file is removed when the shard is dropped.
+#[derive(Debug)]
+pub(super) struct Shard {
+    path: PathBuf,
+}
+
+impl Shard {
+    // Creates a new, empty shard file in the given directory.
+    pub(super) fn create(temp_dir: &Path) -> io::Result<(Self, File)> {
+        loop {
+            let i = SHARD_COUNT.fetch_add(1, Ordering::Relaxed);
+            let path = temp_dir.join(format!("noodles-sort-{}-{}.tmp", process::id(), i));
+
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((Self { path }, file)),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    pub(super) fn open(&self) -> io::Result<bgzf::Reader<BufReader<File>>> {
+        File::open(&self.path)
+            .map(BufReader::new)
+            .map(bgzf::Reader::new)
+    }
+}
+
+impl Drop for Shard {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// Writes the given encoded records, in order, to a shard file.
+pub(super) fn write(file: File, buf: &[u8], ranges: &[Range<usize>]) -> io::Result<()> {
+    let mut writer = bgzf::Writer::new(file);
+
+    for range in ranges {
+        writer.write_all(&buf[range.clone()])?;
+    }
+
+    writer.finish()?;
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+//! Variant format utilities.
+
+pub mod sort_order;
+pub mod zip;
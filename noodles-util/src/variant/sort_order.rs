@@ -0,0 +1,276 @@
+//! Variant sort order enforcement.
+//!
+//! A [`Writer`] wraps a VCF writer and checks that records are written in coordinate order, i.e.,
+//! grouped by chromosome and sorted by position. Writing a record out of order fails instead of
+//! producing a file that cannot be indexed.
+
+use std::{collections::HashSet, error, fmt, io};
+
+use noodles_vcf::{self as vcf, record::Chromosome};
+
+/// A VCF writer that enforces coordinate order.
+///
+/// Records of a chromosome must be contiguous and sorted by position. If the header lists the
+/// chromosome as a contig (`##contig`), chromosomes must also be in the order of the header.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_vcf as vcf;
+/// use noodles_util::variant::sort_order;
+///
+/// let header = vcf::Header::default();
+///
+/// let mut writer = sort_order::Writer::new(vcf::Writer::new(Vec::new()));
+/// writer.write_header(&header)?;
+///
+/// let record = vcf::Record::try_from_str("sq0\t8\t.\tA\t.\t.\tPASS\t.", &header)?;
+/// writer.write_record(&record)?;
+///
+/// let record = vcf::Record::try_from_str("sq0\t5\t.\tA\t.\t.\tPASS\t.", &header)?;
+/// assert!(writer.write_record(&record).is_err());
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct Writer<W> {
+    inner: vcf::Writer<W>,
+    contigs: Vec<String>,
+    finished_chromosomes: HashSet<String>,
+    prev: Option<(String, i32)>,
+    record_count: u64,
+}
+
+impl<W> Writer<W>
+where
+    W: io::Write,
+{
+    /// Creates a sort order enforcing VCF writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// use noodles_util::variant::sort_order;
+    /// let writer = sort_order::Writer::new(vcf::Writer::new(Vec::new()));
+    /// ```
+    pub fn new(inner: vcf::Writer<W>) -> Self {
+        Self {
+            inner,
+            contigs: Vec::new(),
+            finished_chromosomes: HashSet::new(),
+            prev: None,
+            record_count: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// use noodles_util::variant::sort_order;
+    /// let writer = sort_order::Writer::new(vcf::Writer::new(Vec::new()));
+    /// assert!(writer.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &vcf::Writer<W> {
+        &self.inner
+    }
+
+    /// Unwraps and returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// use noodles_util::variant::sort_order;
+    /// let writer = sort_order::Writer::new(vcf::Writer::new(Vec::new()));
+    /// assert!(writer.into_inner().get_ref().is_empty());
+    /// ```
+    pub fn into_inner(self) -> vcf::Writer<W> {
+        self.inner
+    }
+
+    /// Writes a VCF header.
+    ///
+    /// The contigs in the header define the chromosome order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf as vcf;
+    /// use noodles_util::variant::sort_order;
+    ///
+    /// let mut writer = sort_order::Writer::new(vcf::Writer::new(Vec::new()));
+    /// writer.write_header(&vcf::Header::default())?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_header(&mut self, header: &vcf::Header) -> io::Result<()> {
+        self.contigs = header.contigs().keys().cloned().collect();
+        self.inner.write_header(header)
+    }
+
+    /// Writes a VCF record.
+    ///
+    /// This fails with an [`OutOfOrderError`] if the record is not in coordinate order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf as vcf;
+    /// use noodles_util::variant::sort_order;
+    ///
+    /// let header = vcf::Header::default();
+    ///
+    /// let mut writer = sort_order::Writer::new(vcf::Writer::new(Vec::new()));
+    /// writer.write_header(&header)?;
+    ///
+    /// let record = vcf::Record::try_from_str("sq0\t1\t.\tA\t.\t.\tPASS\t.", &header)?;
+    /// writer.write_record(&record)?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_record(&mut self, record: &vcf::Record) -> io::Result<()> {
+        self.check_order(record)?;
+        self.inner.write_record(record)?;
+        self.record_count += 1;
+        Ok(())
+    }
+
+    fn check_order(&mut self, record: &vcf::Record) -> io::Result<()> {
+        let chromosome = match record.chromosome() {
+            Chromosome::Name(name) => name.clone(),
+            Chromosome::Symbol(symbol) => symbol.clone(),
+        };
+
+        let position = i32::from(record.position());
+
+        if let Some((prev_chromosome, prev_position)) = &self.prev {
+            let is_in_order = if chromosome == *prev_chromosome {
+                position >= *prev_position
+            } else {
+                !self.finished_chromosomes.contains(&chromosome)
+                    && self.is_in_contig_order(prev_chromosome, &chromosome)
+            };
+
+            if !is_in_order {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    OutOfOrderError {
+                        record_index: self.record_count,
+                        record: format!("{}:{}", chromosome, position),
+                        previous_record: format!("{}:{}", prev_chromosome, prev_position),
+                    },
+                ));
+            }
+
+            if chromosome != *prev_chromosome {
+                self.finished_chromosomes.insert(prev_chromosome.clone());
+            }
+        }
+
+        self.prev = Some((chromosome, position));
+
+        Ok(())
+    }
+
+    fn is_in_contig_order(&self, prev_chromosome: &str, chromosome: &str) -> bool {
+        let find = |name: &str| self.contigs.iter().position(|contig| contig == name);
+
+        match (find(prev_chromosome), find(chromosome)) {
+            (Some(i), Some(j)) => i < j,
+            _ => true,
+        }
+    }
+}
+
+/// An error returned when a record is written out of order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutOfOrderError {
+    record_index: u64,
+    record: String,
+    previous_record: String,
+}
+
+impl OutOfOrderError {
+    /// Returns the 0-based index of the offending record in the output.
+    pub fn record_index(&self) -> u64 {
+        self.record_index
+    }
+
+    /// Returns the position of the offending record, e.g., `sq0:8`.
+    pub fn record(&self) -> &str {
+        &self.record
+    }
+
+    /// Returns the position of the record written before the offending record.
+    pub fn previous_record(&self) -> &str {
+        &self.previous_record
+    }
+}
+
+impl error::Error for OutOfOrderError {}
+
+impl fmt::Display for OutOfOrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "record {} ({}) is out of order: previous record was at {}",
+            self.record_index, self.record, self.previous_record
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vcf::header::Contig;
+
+    use super::*;
+
+    fn write(header: &vcf::Header, records: &[&str]) -> io::Result<()> {
+        let mut writer = Writer::new(vcf::Writer::new(Vec::new()));
+        writer.write_header(header)?;
+
+        for s in records {
+            let record = vcf::Record::try_from_str(&format!("{}\t.\tA\t.\t.\tPASS\t.", s), header)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            writer.write_record(&record)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record() -> io::Result<()> {
+        let header = vcf::Header::default();
+
+        write(&header, &["sq0\t1", "sq0\t1", "sq0\t8", "sq1\t5", "sq2\t1"])?;
+        assert!(write(&header, &["sq0\t8", "sq0\t5"]).is_err());
+
+        let e = write(&header, &["sq0\t8", "sq1\t5", "sq0\t13"]).unwrap_err();
+        let e = e
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<OutOfOrderError>())
+            .expect("expected out of order error");
+        assert_eq!(e.record_index(), 2);
+        assert_eq!(e.record(), "sq0:13");
+        assert_eq!(e.previous_record(), "sq1:5");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_contigs() -> io::Result<()> {
+        let header = vcf::Header::builder()
+            .add_contig(Contig::new("sq1"))
+            .add_contig(Contig::new("sq0"))
+            .build();
+
+        write(&header, &["sq1\t8", "sq0\t5"])?;
+        assert!(write(&header, &["sq0\t5", "sq1\t8"]).is_err());
+
+        Ok(())
+    }
+}
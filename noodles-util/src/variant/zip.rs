@@ -0,0 +1,455 @@
+//! Synchronized iteration over multiple variant inputs.
+//!
+//! Coordinate-sorted variant records from multiple inputs can be iterated jointly by position,
+//! returning, for each site, the matching record of each input, if any. This is the basis of
+//! merging, intersecting, and concordance tools, similar to `bcftools isec`.
+
+mod allele_matching;
+mod builder;
+
+pub use self::{allele_matching::AlleleMatching, builder::Builder};
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+};
+
+use noodles_vcf::{self as vcf, record::Chromosome};
+
+type Records<'a> = Box<dyn Iterator<Item = io::Result<vcf::Record>> + 'a>;
+
+// The sort key of a record: its contig ID and position.
+type Key = (usize, i32);
+
+/// Reconciles the contig order of variant headers.
+///
+/// The reconciled contigs are the union of the contigs (`##contig`) of the given headers. Each
+/// contig is placed before the next contig of its header that is already known, or last, if
+/// there is none, e.g., contigs `[sq0, sq2]` and `[sq0, sq1, sq2]` are reconciled as `[sq0, sq1,
+/// sq2]`.
+///
+/// This returns an error if headers list common contigs in conflicting orders.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, header::Contig};
+/// use noodles_util::variant::zip;
+///
+/// let header0 = vcf::Header::builder()
+///     .add_contig(Contig::new("sq0"))
+///     .add_contig(Contig::new("sq2"))
+///     .build();
+///
+/// let header1 = vcf::Header::builder()
+///     .add_contig(Contig::new("sq1"))
+///     .add_contig(Contig::new("sq2"))
+///     .build();
+///
+/// let contigs = zip::reconcile_contigs(&[&header0, &header1])?;
+/// assert_eq!(contigs, ["sq0", "sq1", "sq2"]);
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn reconcile_contigs(headers: &[&vcf::Header]) -> io::Result<Vec<String>> {
+    let mut contigs: Vec<String> = Vec::new();
+
+    for header in headers {
+        let names: Vec<_> = header.contigs().keys().collect();
+
+        for (i, name) in names.iter().enumerate() {
+            if contigs.contains(name) {
+                continue;
+            }
+
+            let next_position = names[i + 1..]
+                .iter()
+                .find_map(|next_name| contigs.iter().position(|contig| contig == *next_name));
+
+            match next_position {
+                Some(j) => contigs.insert(j, (*name).clone()),
+                None => contigs.push((*name).clone()),
+            }
+        }
+    }
+
+    for header in headers {
+        let positions = header
+            .contigs()
+            .keys()
+            .filter_map(|name| contigs.iter().position(|contig| contig == name));
+
+        let mut last_position = None;
+
+        for position in positions {
+            if matches!(last_position, Some(last_position) if position < last_position) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "conflicting contig order",
+                ));
+            }
+
+            last_position = Some(position);
+        }
+    }
+
+    Ok(contigs)
+}
+
+/// A synchronized iteration over coordinate-sorted variant records.
+///
+/// This is an iterator over sites across multiple inputs. Each site is a list with an entry per
+/// input, in input order, that holds the input's record at the site, if any. Records at the same
+/// position are returned in the same site when they match by the [`AlleleMatching`] criteria;
+/// otherwise, they are returned in separate sites. Sites are returned in coordinate order.
+///
+/// Each input is a header and an iterator over its records, e.g., from [`vcf::Reader::records`].
+/// BCF records can be used by converting them to VCF records (`bcf::Record::try_into_vcf_record`).
+///
+/// Inputs must be coordinate-sorted with respect to the contig order, which, by default, is
+/// reconciled from the input headers (see [`reconcile_contigs`]). Records on contigs that are not
+/// in the contig order are ordered after all known contigs, in the order they are first read.
+/// An error is returned when an out-of-order record is read.
+///
+/// Use [`Builder`] to set the allele matching criteria or an explicit contig order.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_vcf as vcf;
+/// use noodles_util::variant::zip::Zip;
+///
+/// let data0 = b"##fileformat=VCFv4.3
+/// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+/// sq0\t1\t.\tA\tC\t.\tPASS\t.
+/// sq0\t5\t.\tG\tT\t.\tPASS\t.
+/// ";
+/// let mut reader0 = vcf::Reader::new(&data0[..]);
+/// let header0: vcf::Header = reader0.read_header()?.parse()?;
+///
+/// let data1 = b"##fileformat=VCFv4.3
+/// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+/// sq0\t5\t.\tG\tT\t.\tPASS\t.
+/// sq0\t8\t.\tC\tA\t.\tPASS\t.
+/// ";
+/// let mut reader1 = vcf::Reader::new(&data1[..]);
+/// let header1: vcf::Header = reader1.read_header()?.parse()?;
+///
+/// let zip = Zip::new(vec![
+///     (&header0, reader0.records(&header0)),
+///     (&header1, reader1.records(&header1)),
+/// ])?;
+///
+/// let presence = zip
+///     .map(|result| result.map(|site| site.iter().map(Option::is_some).collect::<Vec<_>>()))
+///     .collect::<io::Result<Vec<_>>>()?;
+///
+/// assert_eq!(presence, [[true, false], [true, true], [false, true]]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct Zip<'a> {
+    sources: Vec<Source<'a>>,
+    contig_ids: HashMap<String, usize>,
+    allele_matching: AlleleMatching,
+    pending_sites: VecDeque<Vec<Option<vcf::Record>>>,
+}
+
+impl<'a> Zip<'a> {
+    /// Creates a synchronized iteration over coordinate-sorted variant records.
+    ///
+    /// This uses the default [`Builder`] options. See [`Builder::build`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf as vcf;
+    /// use noodles_util::variant::zip::Zip;
+    ///
+    /// let header = vcf::Header::default();
+    /// let records = std::iter::empty::<io::Result<vcf::Record>>();
+    ///
+    /// let mut zip = Zip::new(vec![(&header, records)])?;
+    /// assert!(zip.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn new<'h, I>(inputs: Vec<(&'h vcf::Header, I)>) -> io::Result<Self>
+    where
+        I: Iterator<Item = io::Result<vcf::Record>> + 'a,
+    {
+        Builder::default().build(inputs)
+    }
+
+    // Reads the next record of the given source.
+    fn fill(&mut self, i: usize) -> io::Result<()> {
+        let source = &mut self.sources[i];
+
+        let record = match source.records.next().transpose()? {
+            Some(record) => record,
+            None => return Ok(()),
+        };
+
+        let name = match record.chromosome() {
+            Chromosome::Name(name) => name,
+            Chromosome::Symbol(symbol) => symbol,
+        };
+
+        let contig_id = match self.contig_ids.get(name) {
+            Some(id) => *id,
+            None => {
+                let id = self.contig_ids.len();
+                self.contig_ids.insert(name.clone(), id);
+                id
+            }
+        };
+
+        let key = (contig_id, i32::from(record.position()));
+
+        if matches!(source.last_key, Some(last_key) if key < last_key) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "input is not coordinate-sorted",
+            ));
+        }
+
+        source.last_key = Some(key);
+        source.pending_record = Some((key, record));
+
+        Ok(())
+    }
+
+    // Groups the records at the next position into sites.
+    fn read_sites(&mut self) -> io::Result<()> {
+        let key = match self
+            .sources
+            .iter()
+            .filter_map(|source| source.pending_record.as_ref().map(|(key, _)| *key))
+            .min()
+        {
+            Some(key) => key,
+            None => return Ok(()),
+        };
+
+        let allele_matching = self.allele_matching;
+        let source_count = self.sources.len();
+
+        for i in 0..source_count {
+            while let Some(record) = self.sources[i].take_record_at(key) {
+                self.fill(i)?;
+
+                let site = self.pending_sites.iter_mut().find(|site| {
+                    site[i].is_none()
+                        && site
+                            .iter()
+                            .flatten()
+                            .next()
+                            .map(|first_record| allele_matching.is_match(first_record, &record))
+                            .unwrap_or(true)
+                });
+
+                match site {
+                    Some(site) => site[i] = Some(record),
+                    None => {
+                        let mut site = vec![None; source_count];
+                        site[i] = Some(record);
+                        self.pending_sites.push_back(site);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for Zip<'a> {
+    type Item = io::Result<Vec<Option<vcf::Record>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending_sites.is_empty() {
+            if let Err(e) = self.read_sites() {
+                return Some(Err(e));
+            }
+        }
+
+        self.pending_sites.pop_front().map(Ok)
+    }
+}
+
+struct Source<'a> {
+    records: Records<'a>,
+    pending_record: Option<(Key, vcf::Record)>,
+    last_key: Option<Key>,
+}
+
+impl<'a> Source<'a> {
+    fn take_record_at(&mut self, key: Key) -> Option<vcf::Record> {
+        match self.pending_record.take() {
+            Some((k, record)) if k == key => Some(record),
+            pending_record => {
+                self.pending_record = pending_record;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vcf::header::Contig;
+
+    use super::*;
+
+    fn records(header: &vcf::Header, data: &[&str]) -> Records<'static> {
+        let records: Vec<_> = data
+            .iter()
+            .map(|s| {
+                vcf::Record::try_from_str(s, header)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })
+            .collect();
+
+        Box::new(records.into_iter())
+    }
+
+    fn positions(zip: Zip<'_>) -> io::Result<Vec<Vec<Option<String>>>> {
+        zip.map(|result| {
+            result.map(|site| {
+                site.iter()
+                    .map(|record| {
+                        record.as_ref().map(|r| {
+                            format!(
+                                "{}:{}:{}",
+                                r.chromosome(),
+                                i32::from(r.position()),
+                                r.alternate_bases()
+                            )
+                        })
+                    })
+                    .collect()
+            })
+        })
+        .collect()
+    }
+
+    fn s(v: &str) -> Option<String> {
+        Some(v.into())
+    }
+
+    #[test]
+    fn test_reconcile_contigs() -> io::Result<()> {
+        let header0 = vcf::Header::builder()
+            .add_contig(Contig::new("sq0"))
+            .add_contig(Contig::new("sq2"))
+            .build();
+
+        let header1 = vcf::Header::builder()
+            .add_contig(Contig::new("sq0"))
+            .add_contig(Contig::new("sq1"))
+            .add_contig(Contig::new("sq2"))
+            .add_contig(Contig::new("sq3"))
+            .build();
+
+        assert_eq!(
+            reconcile_contigs(&[&header0, &header1])?,
+            ["sq0", "sq1", "sq2", "sq3"]
+        );
+
+        let header2 = vcf::Header::builder()
+            .add_contig(Contig::new("sq2"))
+            .add_contig(Contig::new("sq0"))
+            .build();
+
+        assert!(matches!(
+            reconcile_contigs(&[&header0, &header2]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        let header0 = vcf::Header::builder()
+            .add_contig(Contig::new("sq0"))
+            .add_contig(Contig::new("sq1"))
+            .build();
+
+        let header1 = vcf::Header::builder()
+            .add_contig(Contig::new("sq1"))
+            .build();
+
+        let inputs = || {
+            vec![
+                (
+                    &header0,
+                    records(
+                        &header0,
+                        &[
+                            "sq0\t8\t.\tA\tC\t.\tPASS\t.",
+                            "sq1\t5\t.\tA\tC\t.\tPASS\t.",
+                            "sq1\t5\t.\tA\tG\t.\tPASS\t.",
+                        ],
+                    ),
+                ),
+                (
+                    &header1,
+                    records(
+                        &header1,
+                        &[
+                            "sq1\t5\t.\tA\tG\t.\tPASS\t.",
+                            "sq1\t13\t.\tA\tT\t.\tPASS\t.",
+                        ],
+                    ),
+                ),
+            ]
+        };
+
+        assert_eq!(
+            positions(Zip::new(inputs())?)?,
+            [
+                vec![s("sq0:8:C"), None],
+                vec![s("sq1:5:C"), None],
+                vec![s("sq1:5:G"), s("sq1:5:G")],
+                vec![None, s("sq1:13:T")],
+            ]
+        );
+
+        let zip = Builder::default()
+            .set_allele_matching(AlleleMatching::Position)
+            .build(inputs())?;
+
+        assert_eq!(
+            positions(zip)?,
+            [
+                vec![s("sq0:8:C"), None],
+                vec![s("sq1:5:C"), s("sq1:5:G")],
+                vec![s("sq1:5:G"), None],
+                vec![None, s("sq1:13:T")],
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_unsorted_input() -> io::Result<()> {
+        let header = vcf::Header::default();
+
+        let inputs = vec![(
+            &header,
+            records(
+                &header,
+                &["sq0\t8\t.\tA\tC\t.\tPASS\t.", "sq0\t5\t.\tA\tC\t.\tPASS\t."],
+            ),
+        )];
+
+        let mut zip = Zip::new(inputs)?;
+        assert!(matches!(
+            zip.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use noodles_vcf as vcf;
+
+/// The criteria for matching records at the same position across inputs.
+///
+/// Records are always first grouped by chromosome and position.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AlleleMatching {
+    /// Match records by position only.
+    Position,
+    /// Match records with the same reference bases.
+    Reference,
+    /// Match records with the same reference bases and at least one common alternate allele.
+    SharedAlternate,
+    /// Match records with the same reference bases and the same set of alternate alleles.
+    ///
+    /// This is the default.
+    #[default]
+    Exact,
+}
+
+impl AlleleMatching {
+    pub(super) fn is_match(self, a: &vcf::Record, b: &vcf::Record) -> bool {
+        if self == Self::Position {
+            return true;
+        }
+
+        if a.reference_bases() != b.reference_bases() {
+            return false;
+        }
+
+        let (a_alleles, b_alleles) = (a.alternate_bases(), b.alternate_bases());
+
+        match self {
+            Self::Position | Self::Reference => true,
+            Self::SharedAlternate => a_alleles.iter().any(|allele| b_alleles.contains(allele)),
+            Self::Exact => {
+                a_alleles.iter().all(|allele| b_alleles.contains(allele))
+                    && b_alleles.iter().all(|allele| a_alleles.contains(allele))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_match() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::default();
+        let parse = |s: &str| vcf::Record::try_from_str(s, &header);
+
+        let a = parse("sq0\t8\t.\tA\tC,G\t.\tPASS\t.")?;
+        let b = parse("sq0\t8\t.\tA\tG,C\t.\tPASS\t.")?;
+        let c = parse("sq0\t8\t.\tA\tG\t.\tPASS\t.")?;
+        let d = parse("sq0\t8\t.\tA\tT\t.\tPASS\t.")?;
+        let e = parse("sq0\t8\t.\tAC\tA\t.\tPASS\t.")?;
+
+        assert!(AlleleMatching::Exact.is_match(&a, &b));
+        assert!(!AlleleMatching::Exact.is_match(&a, &c));
+
+        assert!(AlleleMatching::SharedAlternate.is_match(&a, &c));
+        assert!(!AlleleMatching::SharedAlternate.is_match(&a, &d));
+
+        assert!(AlleleMatching::Reference.is_match(&a, &d));
+        assert!(!AlleleMatching::Reference.is_match(&a, &e));
+
+        assert!(AlleleMatching::Position.is_match(&a, &e));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use std::{collections::HashMap, io};
+
+use noodles_vcf as vcf;
+
+use super::{reconcile_contigs, AlleleMatching, Records, Source, Zip};
+
+/// A variant zip builder.
+#[derive(Default)]
+pub struct Builder {
+    allele_matching: AlleleMatching,
+    contigs: Option<Vec<String>>,
+}
+
+impl Builder {
+    /// Sets the allele matching criteria.
+    ///
+    /// By default, records must have the same reference bases and alternate alleles to be
+    /// returned in the same site.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::zip::{self, AlleleMatching};
+    /// let builder = zip::Builder::default().set_allele_matching(AlleleMatching::Position);
+    /// ```
+    pub fn set_allele_matching(mut self, allele_matching: AlleleMatching) -> Self {
+        self.allele_matching = allele_matching;
+        self
+    }
+
+    /// Sets the contig order.
+    ///
+    /// By default, the contig order is reconciled from the input headers (see
+    /// [`super::reconcile_contigs`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::zip;
+    /// let builder = zip::Builder::default().set_contigs(vec![String::from("sq0")]);
+    /// ```
+    pub fn set_contigs(mut self, contigs: Vec<String>) -> Self {
+        self.contigs = Some(contigs);
+        self
+    }
+
+    /// Builds a variant zip over the given inputs.
+    ///
+    /// Each input is a header and an iterator over its records. This returns an error if the
+    /// contig order cannot be reconciled from the headers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf as vcf;
+    /// use noodles_util::variant::zip;
+    ///
+    /// let header = vcf::Header::default();
+    /// let records = std::iter::empty::<io::Result<vcf::Record>>();
+    ///
+    /// let mut zip = zip::Builder::default().build(vec![(&header, records)])?;
+    /// assert!(zip.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build<'a, 'h, I>(self, inputs: Vec<(&'h vcf::Header, I)>) -> io::Result<Zip<'a>>
+    where
+        I: Iterator<Item = io::Result<vcf::Record>> + 'a,
+    {
+        let contigs = match self.contigs {
+            Some(contigs) => contigs,
+            None => {
+                let headers: Vec<_> = inputs.iter().map(|(header, _)| *header).collect();
+                reconcile_contigs(&headers)?
+            }
+        };
+
+        let contig_ids: HashMap<_, _> = contigs
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| (name, i))
+            .collect();
+
+        let sources = inputs
+            .into_iter()
+            .map(|(_, records)| Source {
+                records: Box::new(records) as Records<'a>,
+                pending_record: None,
+                last_key: None,
+            })
+            .collect();
+
+        let mut zip = Zip {
+            sources,
+            contig_ids,
+            allele_matching: self.allele_matching,
+            pending_sites: Default::default(),
+        };
+
+        for i in 0..zip.sources.len() {
+            zip.fill(i)?;
+        }
+
+        Ok(zip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        let builder = Builder::default();
+        assert_eq!(builder.allele_matching, AlleleMatching::Exact);
+        assert!(builder.contigs.is_none());
+    }
+}
@@ -9,7 +9,7 @@ use noodles_tabix as tabix;
 use tokio::io::{self, AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncSeek};
 
 use self::query::query;
-use crate::{reader::resolve_region, Header, Record};
+use crate::{reader::resolve_region, Error, Header, Record};
 
 const LINE_FEED: char = '\n';
 const CARRIAGE_RETURN: char = '\r';
@@ -184,15 +184,20 @@ where
         header: &'h Header,
     ) -> impl Stream<Item = io::Result<Record>> + 'r {
         Box::pin(stream::try_unfold(
-            (&mut self.inner, String::new()),
-            |(mut reader, mut buf)| async {
+            (&mut self.inner, String::new(), 0),
+            move |(mut reader, mut buf, record_number)| async move {
                 buf.clear();
 
                 match read_line(&mut reader, &mut buf).await? {
                     0 => Ok(None),
                     _ => Record::try_from_str(&buf, header)
-                        .map(|record| Some((record, (reader, buf))))
-                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+                        .map(|record| Some((record, (reader, buf, record_number + 1))))
+                        .map_err(|e| {
+                            io::Error::from(Error::InvalidRecord {
+                                record_number: Some(record_number),
+                                source: e,
+                            })
+                        }),
                 }
             },
         ))
@@ -336,7 +341,7 @@ where
         reader.consume(len);
     }
 
-    String::from_utf8(header_buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    String::from_utf8(header_buf).map_err(|e| Error::InvalidHeaderText(e).into())
 }
 
 async fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
@@ -6,7 +6,7 @@ use noodles_csi::index::reference_sequence::bin::Chunk;
 use tokio::io::{self, AsyncRead, AsyncSeek};
 
 use super::Reader;
-use crate::{Header, Record};
+use crate::{Error, Header, Record};
 
 enum State {
     Seek,
@@ -119,9 +119,12 @@ where
 
     match reader.read_record(&mut buf).await? {
         0 => Ok(None),
-        _ => Record::try_from_str(&buf, header)
-            .map(Some)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        _ => Record::try_from_str(&buf, header).map(Some).map_err(|e| {
+            io::Error::from(Error::InvalidRecord {
+                record_number: None,
+                source: e,
+            })
+        }),
     }
 }
 
@@ -142,7 +145,7 @@ fn intersects(
     let end = record
         .end()
         .map(i32::from)
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        .map_err(|e| io::Error::from(Error::InvalidRecordEnd(e)))?;
 
     Ok(name == reference_sequence_name && in_interval(start, end, interval_start, interval_end))
 }
@@ -0,0 +1,110 @@
+//! VCF errors.
+
+use std::{error, fmt, io, string};
+
+use super::record;
+
+/// An error returned when VCF data fails to be read.
+///
+/// Readers continue to return [`std::io::Error`]s, but for invalid data, the error is wrapped in
+/// an `io::Error` with the kind [`std::io::ErrorKind::InvalidData`]. The cause can be inspected by
+/// downcasting the inner error.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_vcf as vcf;
+///
+/// let data = b"##fileformat=VCFv4.3
+/// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+/// sq0\t1\t.\tA\t.\t.\tPASS\t.
+/// sq0\tnoodles\t.\tA\t.\t.\tPASS\t.
+/// ";
+///
+/// let mut reader = vcf::Reader::new(&data[..]);
+/// let header: vcf::Header = reader.read_header()?.parse().unwrap();
+/// let mut records = reader.records(&header);
+///
+/// assert!(records.next().transpose()?.is_some());
+///
+/// let e = records.next().transpose().unwrap_err();
+///
+/// assert!(matches!(
+///     e.get_ref().and_then(|e| e.downcast_ref::<vcf::Error>()),
+///     Some(vcf::Error::InvalidRecord { record_number: Some(1), .. })
+/// ));
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The header text is not valid UTF-8.
+    InvalidHeaderText(string::FromUtf8Error),
+    /// A record is invalid.
+    InvalidRecord {
+        /// The 0-based number of the record from where reading started, if known.
+        record_number: Option<u64>,
+        /// The cause.
+        source: record::ParseError,
+    },
+    /// The end position of a record is invalid.
+    InvalidRecordEnd(record::EndError),
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidHeaderText(e) => Some(e),
+            Self::InvalidRecord { source, .. } => Some(source),
+            Self::InvalidRecordEnd(e) => Some(e),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeaderText(_) => f.write_str("invalid header text"),
+            Self::InvalidRecord {
+                record_number,
+                source,
+            } => {
+                f.write_str("invalid record")?;
+
+                if let Some(n) = record_number {
+                    write!(f, " {}", n)?;
+                }
+
+                write!(f, ": {}", source)
+            }
+            Self::InvalidRecordEnd(e) => write!(f, "invalid record end: {}", e),
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        Self::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let error = Error::InvalidRecord {
+            record_number: Some(8),
+            source: record::ParseError::MissingField(record::Field::Position),
+        };
+        assert_eq!(error.to_string(), "invalid record 8: missing field: POS");
+
+        let error = Error::InvalidRecord {
+            record_number: None,
+            source: record::ParseError::MissingField(record::Field::Position),
+        };
+        assert_eq!(error.to_string(), "invalid record: missing field: POS");
+    }
+}
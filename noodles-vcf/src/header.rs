@@ -337,6 +337,22 @@ impl Header {
         &self.sample_names
     }
 
+    /// Returns a mutable reference to the sample names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut header = vcf::Header::builder().add_sample_name("sample0").build();
+    /// header.sample_names_mut().insert(String::from("sample1"));
+    ///
+    /// assert_eq!(header.sample_names().len(), 2);
+    /// ```
+    pub fn sample_names_mut(&mut self) -> &mut SampleNames {
+        &mut self.sample_names
+    }
+
     /// Returns a header record with the given key.
     ///
     /// This includes all records other than `fileformat`, `INFO`, `FILTER`, `FORMAT`, `ALT`,
@@ -562,6 +578,27 @@ mod tests {
         assert_eq!(header.to_string(), expected);
     }
 
+    #[test]
+    fn test_fmt_with_idx_and_extra_fields() -> Result<(), ParseError> {
+        let s = r#"##fileformat=VCFv4.3
+##INFO=<ID=NS,Number=1,Type=Integer,Description="Number of samples with data",Source="noodles",IDX=2>
+##FILTER=<ID=PASS,Description="All filters passed",IDX=0>
+##FILTER=<ID=q10,Description="Quality below 10",IDX=1>
+##FORMAT=<ID=GT,Number=1,Type=String,Description="Genotype",IDX=3>
+##ALT=<ID=DEL,Description="Deletion",Source="noodles">
+##contig=<ID=sq0,length=8,md5="d7eba311421bbc9d3ada44709dd61534",IDX=1>
+##contig=<ID=sq1,length=13,IDX=0>
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#;
+
+        let header: Header = s.parse()?;
+
+        assert_eq!(header.contigs().get("sq0").and_then(|c| c.idx()), Some(1));
+        assert_eq!(header.to_string(), s);
+
+        Ok(())
+    }
+
     #[test]
     fn test_insert_with_duplicate_keys() {
         let records = [
@@ -4,6 +4,8 @@ mod key;
 
 use std::{error, fmt};
 
+use indexmap::IndexMap;
+
 use crate::record::alternate_bases::allele::{symbol, Symbol};
 
 use super::{record, Record};
@@ -15,6 +17,7 @@ use self::key::Key;
 pub struct AlternativeAllele {
     id: Symbol,
     description: String,
+    fields: IndexMap<String, String>,
 }
 
 impl AlternativeAllele {
@@ -43,6 +46,7 @@ impl AlternativeAllele {
         Self {
             id,
             description: description.into(),
+            fields: IndexMap::new(),
         }
     }
 
@@ -96,6 +100,32 @@ impl AlternativeAllele {
     pub fn description(&self) -> &str {
         &self.description
     }
+
+    /// Returns the extra fields in the record.
+    ///
+    /// This includes fields other than `ID` and `Description`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{
+    ///     header::AlternativeAllele,
+    ///     record::alternate_bases::allele::{
+    ///         symbol::{structural_variant::Type, StructuralVariant},
+    ///         Symbol,
+    ///     },
+    /// };
+    ///
+    /// let alternative_allele = AlternativeAllele::new(
+    ///     Symbol::StructuralVariant(StructuralVariant::from(Type::Deletion)),
+    ///     "Deletion",
+    /// );
+    ///
+    /// assert!(alternative_allele.fields().is_empty());
+    /// ```
+    pub fn fields(&self) -> &IndexMap<String, String> {
+        &self.fields
+    }
 }
 
 impl fmt::Display for AlternativeAllele {
@@ -109,6 +139,11 @@ impl fmt::Display for AlternativeAllele {
         write!(f, ",{}=", Key::Description)?;
         super::fmt::write_escaped_string(f, self.description())?;
 
+        for (key, value) in &self.fields {
+            write!(f, ",{}=", key)?;
+            super::fmt::write_escaped_string(f, value)?;
+        }
+
         f.write_str(">")?;
 
         Ok(())
@@ -169,7 +204,13 @@ fn parse_struct(fields: Vec<(String, String)>) -> Result<AlternativeAllele, TryF
             _ => Err(TryFromRecordError::MissingField(Key::Description)),
         })?;
 
-    Ok(AlternativeAllele { id, description })
+    let fields = it.collect();
+
+    Ok(AlternativeAllele {
+        id,
+        description,
+        fields,
+    })
 }
 
 #[cfg(test)]
@@ -209,6 +250,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_try_from_record_for_filter_with_extra_fields() -> Result<(), TryFromRecordError> {
+        let record = Record::new(
+            record::Key::AlternativeAllele,
+            record::Value::Struct(vec![
+                (String::from("ID"), del().to_string()),
+                (String::from("Description"), String::from("Deletion")),
+                (String::from("Source"), String::from("noodles")),
+            ]),
+        );
+
+        let alternative_allele = AlternativeAllele::try_from(record)?;
+
+        assert_eq!(
+            alternative_allele.fields(),
+            &[(String::from("Source"), String::from("noodles"))]
+                .into_iter()
+                .collect::<IndexMap<_, _>>()
+        );
+
+        assert_eq!(
+            alternative_allele.to_string(),
+            r#"##ALT=<ID=DEL,Description="Deletion",Source="noodles">"#
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_try_from_record_for_filter_with_an_invalid_record_key() {
         let record = Record::new(
@@ -13,10 +13,13 @@ use super::{record, Record};
 use self::key::Key;
 
 /// A VCF header contig record (`contig`).
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Contig {
     id: String,
     len: Option<i32>,
+    idx: Option<usize>,
+    // The number of extra fields that precede `IDX` in the source record.
+    idx_position: Option<usize>,
     fields: IndexMap<String, String>,
 }
 
@@ -37,6 +40,8 @@ impl Contig {
         Self {
             id: id.into(),
             len: None,
+            idx: None,
+            idx_position: None,
             fields: IndexMap::new(),
         }
     }
@@ -67,6 +72,50 @@ impl Contig {
         self.len
     }
 
+    /// Returns the index of the ID in the dictionary of contigs.
+    ///
+    /// This is typically used in BCF.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::Contig;
+    /// let contig = Contig::new("sq0");
+    /// assert!(contig.idx().is_none());
+    /// ```
+    pub fn idx(&self) -> Option<usize> {
+        self.idx
+    }
+
+    /// Returns a mutable reference to the index of the ID in the dictionary of contigs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::Contig;
+    /// let mut contig = Contig::new("sq0");
+    /// *contig.idx_mut() = Some(2);
+    /// assert_eq!(contig.idx(), Some(2));
+    /// ```
+    pub fn idx_mut(&mut self) -> &mut Option<usize> {
+        &mut self.idx
+    }
+
+    /// Returns the extra fields in the record.
+    ///
+    /// This includes fields other than `ID`, `length`, and `IDX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::Contig;
+    /// let contig = Contig::new("sq0");
+    /// assert!(contig.fields().is_empty());
+    /// ```
+    pub fn fields(&self) -> &IndexMap<String, String> {
+        &self.fields
+    }
+
     /// Returns the value of the field with the given key.
     ///
     /// # Examples
@@ -93,6 +142,18 @@ impl Contig {
     }
 }
 
+// The position of `IDX` only affects formatting and is not compared.
+impl PartialEq for Contig {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.len == other.len
+            && self.idx == other.idx
+            && self.fields == other.fields
+    }
+}
+
+impl Eq for Contig {}
+
 impl fmt::Display for Contig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(record::PREFIX)?;
@@ -105,17 +166,40 @@ impl fmt::Display for Contig {
             write!(f, ",{}={}", Key::Length, len)?;
         }
 
-        for (key, value) in &self.fields {
+        // `IDX` is written at its position in the source record or, if it was set otherwise,
+        // last.
+        let idx_position = self
+            .idx_position
+            .filter(|&i| i <= self.fields.len())
+            .unwrap_or(self.fields.len());
+
+        for (i, (key, value)) in self.fields.iter().enumerate() {
+            if i == idx_position {
+                write_idx(f, self.idx)?;
+            }
+
             write!(f, ",{}=", key)?;
             super::fmt::write_escaped_string(f, value)?;
         }
 
+        if idx_position == self.fields.len() {
+            write_idx(f, self.idx)?;
+        }
+
         f.write_str(">")?;
 
         Ok(())
     }
 }
 
+fn write_idx(f: &mut fmt::Formatter<'_>, idx: Option<usize>) -> fmt::Result {
+    if let Some(idx) = idx {
+        write!(f, ",{}={}", Key::Idx, idx)?;
+    }
+
+    Ok(())
+}
+
 /// An error returned when a generic VCF header record fails to convert to a contig header record.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum TryFromRecordError {
@@ -127,6 +211,8 @@ pub enum TryFromRecordError {
     InvalidId,
     /// The length is invalid.
     InvalidLength(num::ParseIntError),
+    /// The index (`IDX`) is invalid.
+    InvalidIdx(num::ParseIntError),
     /// A required field is missing.
     MissingField(Key),
 }
@@ -141,6 +227,7 @@ impl fmt::Display for TryFromRecordError {
             Self::InvalidId => f.write_str("invalid ID"),
             Self::InvalidKey(e) => write!(f, "invalid key: {}", e),
             Self::InvalidLength(e) => write!(f, "invalid length: {}", e),
+            Self::InvalidIdx(e) => write!(f, "invalid index (`{}`): {}", Key::Idx, e),
         }
     }
 }
@@ -159,6 +246,8 @@ impl TryFrom<Record> for Contig {
 fn parse_struct(fields: Vec<(String, String)>) -> Result<Contig, TryFromRecordError> {
     let mut id = None;
     let mut len = None;
+    let mut idx = None;
+    let mut idx_position = None;
     let mut other_fields = IndexMap::new();
 
     for (raw_key, value) in fields {
@@ -178,6 +267,14 @@ fn parse_struct(fields: Vec<(String, String)>) -> Result<Contig, TryFromRecordEr
                     .map(Some)
                     .map_err(TryFromRecordError::InvalidLength)?;
             }
+            Key::Idx => {
+                idx = value
+                    .parse()
+                    .map(Some)
+                    .map_err(TryFromRecordError::InvalidIdx)?;
+
+                idx_position = Some(other_fields.len());
+            }
             Key::Other(k) => {
                 other_fields.insert(k, value);
             }
@@ -187,6 +284,8 @@ fn parse_struct(fields: Vec<(String, String)>) -> Result<Contig, TryFromRecordEr
     Ok(Contig {
         id: id.ok_or(TryFromRecordError::MissingField(Key::Id))?,
         len,
+        idx,
+        idx_position,
         fields: other_fields,
     })
 }
@@ -217,6 +316,14 @@ mod tests {
         let expected = r#"##contig=<ID=sq0,length=13,md5="d7eba311421bbc9d3ada44709dd61534">"#;
         assert_eq!(contig.to_string(), expected);
 
+        let mut contig = Contig::new("sq0");
+        contig
+            .fields
+            .insert(String::from("assembly"), String::from("GRCh38"));
+        *contig.idx_mut() = Some(0);
+        let expected = r#"##contig=<ID=sq0,assembly="GRCh38",IDX=0>"#;
+        assert_eq!(contig.to_string(), expected);
+
         Ok(())
     }
 
@@ -229,6 +336,8 @@ mod tests {
             Ok(Contig {
                 id: String::from("sq0"),
                 len: Some(13),
+                idx: None,
+                idx_position: None,
                 fields: [(
                     String::from("md5"),
                     String::from("d7eba311421bbc9d3ada44709dd61534")
@@ -239,6 +348,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_try_from_record_for_contig_with_idx() -> Result<(), TryFromRecordError> {
+        let record = Record::new(
+            record::Key::Contig,
+            record::Value::Struct(vec![
+                (String::from("ID"), String::from("sq0")),
+                (String::from("length"), String::from("13")),
+                (String::from("IDX"), String::from("2")),
+                (String::from("assembly"), String::from("GRCh38")),
+            ]),
+        );
+
+        let contig = Contig::try_from(record)?;
+
+        assert_eq!(contig.idx(), Some(2));
+        assert_eq!(contig.get("assembly"), Some("GRCh38"));
+        assert_eq!(
+            contig.to_string(),
+            r#"##contig=<ID=sq0,length=13,IDX=2,assembly="GRCh38">"#
+        );
+
+        let record = Record::new(
+            record::Key::Contig,
+            record::Value::Struct(vec![
+                (String::from("ID"), String::from("sq0")),
+                (String::from("assembly"), String::from("GRCh38")),
+                (String::from("IDX"), String::from("2")),
+            ]),
+        );
+
+        let contig = Contig::try_from(record)?;
+
+        assert_eq!(
+            contig.to_string(),
+            r#"##contig=<ID=sq0,assembly="GRCh38",IDX=2>"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_eq() -> Result<(), TryFromRecordError> {
+        let record = Record::new(
+            record::Key::Contig,
+            record::Value::Struct(vec![
+                (String::from("ID"), String::from("sq0")),
+                (String::from("IDX"), String::from("2")),
+                (String::from("assembly"), String::from("GRCh38")),
+            ]),
+        );
+
+        let actual = Contig::try_from(record)?;
+
+        let mut expected = Contig::new("sq0");
+        *expected.idx_mut() = Some(2);
+        expected
+            .fields
+            .insert(String::from("assembly"), String::from("GRCh38"));
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_record_for_contig_with_an_invalid_idx() {
+        let record = Record::new(
+            record::Key::Contig,
+            record::Value::Struct(vec![
+                (String::from("ID"), String::from("sq0")),
+                (String::from("IDX"), String::from("ndls")),
+            ]),
+        );
+
+        assert!(matches!(
+            Contig::try_from(record),
+            Err(TryFromRecordError::InvalidIdx(_))
+        ));
+    }
+
     #[test]
     fn test_try_from_record_for_contig_with_an_invalid_record_key() {
         let record = Record::new(
@@ -97,6 +97,20 @@ impl Filter {
         self.idx
     }
 
+    /// Returns a mutable reference to the index of the ID in the dictionary of strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::Filter;
+    /// let mut filter = Filter::new("q10", "Quality below 10");
+    /// *filter.idx_mut() = Some(1);
+    /// assert_eq!(filter.idx(), Some(1));
+    /// ```
+    pub fn idx_mut(&mut self) -> &mut Option<usize> {
+        &mut self.idx
+    }
+
     /// Returns the extra fields in the record.
     ///
     /// This includes fields other than `ID` and `Description`.
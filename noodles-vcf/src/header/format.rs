@@ -162,6 +162,20 @@ impl Format {
         self.idx
     }
 
+    /// Returns a mutable reference to the index of the ID in the dictionary of strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{header::Format, record::genotypes::genotype::field::Key};
+    /// let mut format = Format::from(Key::Genotype);
+    /// *format.idx_mut() = Some(1);
+    /// assert_eq!(format.idx(), Some(1));
+    /// ```
+    pub fn idx_mut(&mut self) -> &mut Option<usize> {
+        &mut self.idx
+    }
+
     /// Returns the extra fields in the record.
     ///
     /// This includes fields other than `ID`, `Number`, `Type`, `Description`, and `IDX`.
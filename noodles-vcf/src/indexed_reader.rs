@@ -0,0 +1,174 @@
+//! Indexed VCF reader.
+
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_tabix as tabix;
+
+use super::{
+    reader::{Query, Records},
+    Header, Reader,
+};
+
+/// An indexed VCF reader.
+///
+/// This pairs a bgzipped VCF reader with its associated tabix index, allowing random access to
+/// records by region.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::fs::File;
+/// use noodles_core::Region;
+/// use noodles_tabix as tabix;
+/// use noodles_vcf as vcf;
+///
+/// let index = tabix::read("sample.vcf.gz.tbi")?;
+/// let mut reader = File::open("sample.vcf.gz").map(|f| vcf::IndexedReader::new(f, index))?;
+///
+/// let header = reader.read_header()?.parse()?;
+///
+/// let region = Region::mapped("sq0", 8..=13);
+/// let query = reader.query(&header, &region)?;
+///
+/// for result in query {
+///     let record = result?;
+///     println!("{}", record);
+/// }
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct IndexedReader<R> {
+    inner: Reader<bgzf::Reader<R>>,
+    index: tabix::Index,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read,
+{
+    /// Creates an indexed VCF reader.
+    ///
+    /// The given stream is wrapped in a BGZF decoder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let data = [];
+    /// let index = tabix::Index::default();
+    /// let reader = vcf::IndexedReader::new(&data[..], index);
+    /// ```
+    pub fn new(inner: R, index: tabix::Index) -> Self {
+        Self {
+            inner: Reader::new(bgzf::Reader::new(inner)),
+            index,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let data = [];
+    /// let reader = vcf::IndexedReader::new(&data[..], tabix::Index::default());
+    /// assert!(reader.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &bgzf::Reader<R> {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let data = [];
+    /// let mut reader = vcf::IndexedReader::new(&data[..], tabix::Index::default());
+    /// assert!(reader.get_mut().get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut bgzf::Reader<R> {
+        self.inner.get_mut()
+    }
+
+    /// Unwraps and returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let data = [];
+    /// let reader = vcf::IndexedReader::new(&data[..], tabix::Index::default());
+    /// assert!(reader.into_inner().into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> bgzf::Reader<R> {
+        self.inner.into_inner()
+    }
+
+    /// Returns the associated index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let data = [];
+    /// let reader = vcf::IndexedReader::new(&data[..], tabix::Index::default());
+    /// assert!(reader.index().reference_sequence_names().is_empty());
+    /// ```
+    pub fn index(&self) -> &tabix::Index {
+        &self.index
+    }
+
+    /// Reads the raw VCF header.
+    ///
+    /// See [`Reader::read_header`].
+    pub fn read_header(&mut self) -> io::Result<String> {
+        self.inner.read_header()
+    }
+
+    /// Reads a single raw VCF record.
+    ///
+    /// See [`Reader::read_record`].
+    pub fn read_record(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.inner.read_record(buf)
+    }
+
+    /// Returns an iterator over records starting from the current stream position.
+    ///
+    /// See [`Reader::records`].
+    pub fn records<'r, 'h>(&'r mut self, header: &'h Header) -> Records<'r, 'h, bgzf::Reader<R>> {
+        self.inner.records(header)
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read + Seek,
+{
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// The reference sequence name of the region is resolved to a reference sequence ID using
+    /// the reference sequence names in the tabix index.
+    ///
+    /// See [`Reader::query`].
+    pub fn query<'r, 'h>(
+        &'r mut self,
+        header: &'h Header,
+        region: &Region,
+    ) -> io::Result<Query<'r, 'h, R>> {
+        self.inner.query(header, &self.index, region)
+    }
+}
@@ -23,12 +23,19 @@
 #[cfg(feature = "async")]
 mod r#async;
 
+mod error;
 pub mod header;
+pub mod indexed_reader;
 pub mod reader;
 pub mod record;
+#[cfg(feature = "serde")]
+mod serde;
 mod writer;
 
-pub use self::{header::Header, reader::Reader, record::Record, writer::Writer};
+pub use self::{
+    error::Error, header::Header, indexed_reader::IndexedReader, reader::Reader, record::Record,
+    writer::Writer,
+};
 
 #[cfg(feature = "async")]
 pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
@@ -1,19 +1,23 @@
 //! VCF reader and iterators.
 
+mod checkpoint;
 mod query;
 mod records;
 
-pub use self::{query::Query, records::Records};
+pub use self::{checkpoint::Checkpoint, query::Query, records::Records};
 
-use std::io::{self, BufRead, Read, Seek};
+use std::{
+    hash::Hasher,
+    io::{self, BufRead, Read, Seek},
+};
 
 use memchr::memchr;
 use noodles_bgzf as bgzf;
-use noodles_core::{region::Interval, Region};
+use noodles_core::{region::Interval, Fingerprint, Region};
 use noodles_csi::BinningIndex;
 use noodles_tabix as tabix;
 
-use super::Header;
+use super::{Error, Header};
 
 /// A VCF reader.
 ///
@@ -45,6 +49,7 @@ use super::Header;
 #[derive(Debug)]
 pub struct Reader<R> {
     inner: R,
+    header_fingerprint: Fingerprint,
 }
 
 impl<R> Reader<R>
@@ -66,7 +71,10 @@ where
     /// let reader = vcf::Reader::new(&data[..]);
     /// ```
     pub fn new(inner: R) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            header_fingerprint: Fingerprint::default(),
+        }
     }
 
     /// Returns a reference to the underlying reader.
@@ -139,7 +147,12 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn read_header(&mut self) -> io::Result<String> {
-        read_header(&mut self.inner)
+        let header = read_header(&mut self.inner)?;
+
+        self.header_fingerprint = Fingerprint::default();
+        self.header_fingerprint.write(header.as_bytes());
+
+        Ok(header)
     }
 
     /// Reads a single raw VCF record.
@@ -233,6 +246,36 @@ where
     pub fn virtual_position(&self) -> bgzf::VirtualPosition {
         self.inner.virtual_position()
     }
+
+    /// Returns a checkpoint at the current position.
+    ///
+    /// The checkpoint holds the current virtual position and a fingerprint of the header read by
+    /// this reader. It should be taken after reading the header and between records, e.g., after
+    /// [`Self::read_record`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// ")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = vcf::Reader::new(bgzf::Reader::new(&data[..]));
+    /// reader.read_header()?;
+    ///
+    /// let checkpoint = reader.checkpoint();
+    /// assert_eq!(checkpoint.virtual_position(), reader.virtual_position());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint::new(self.virtual_position(), self.header_fingerprint.finish())
+    }
 }
 
 impl<R> Reader<bgzf::Reader<R>>
@@ -261,6 +304,57 @@ where
         self.inner.seek(pos)
     }
 
+    /// Creates a VCF reader that resumes reading at the given checkpoint.
+    ///
+    /// The given reader must be a raw BGZF stream positioned at the start. The header is read and
+    /// compared to the checkpoint's header fingerprint, and the reader is then moved to the
+    /// checkpoint's virtual position. This fails with [`io::ErrorKind::InvalidData`] if the header
+    /// does not match, i.e., the checkpoint was taken from a different file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, Write};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// sq0\t1\t.\tA\t.\t.\tPASS\t.
+    /// sq0\t2\t.\tA\t.\t.\tPASS\t.
+    /// ")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = vcf::Reader::new(bgzf::Reader::new(&data[..]));
+    /// reader.read_header()?;
+    /// let mut buf = String::new();
+    /// reader.read_record(&mut buf)?;
+    /// let checkpoint = reader.checkpoint();
+    ///
+    /// let mut reader = vcf::Reader::resume_at(Cursor::new(data), &checkpoint)?;
+    /// buf.clear();
+    /// reader.read_record(&mut buf)?;
+    /// assert_eq!(buf, "sq0\t2\t.\tA\t.\t.\tPASS\t.");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn resume_at(reader: R, checkpoint: &Checkpoint) -> io::Result<Self> {
+        let mut reader = Self::new(bgzf::Reader::new(reader));
+
+        reader.read_header()?;
+
+        if reader.header_fingerprint.finish() != checkpoint.header_fingerprint() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "checkpoint header fingerprint mismatch",
+            ));
+        }
+
+        reader.seek(checkpoint.virtual_position())?;
+
+        Ok(reader)
+    }
+
     /// Returns an iterator over records that intersects the given region.
     ///
     /// # Examples
@@ -339,7 +433,7 @@ where
         reader.consume(len);
     }
 
-    String::from_utf8(header_buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    String::from_utf8(header_buf).map_err(|e| Error::InvalidHeaderText(e).into())
 }
 
 // Reads all bytes until a line feed ('\n') or EOF is reached.
@@ -465,6 +559,41 @@ sq0\t13
         Ok(())
     }
 
+    #[test]
+    fn test_resume_at() -> io::Result<()> {
+        use std::io::{Cursor, Write};
+
+        fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+            let mut writer = bgzf::Writer::new(Vec::new());
+            writer.write_all(data)?;
+            writer.finish()
+        }
+
+        let data = compress(DATA)?;
+
+        let mut reader = Reader::new(bgzf::Reader::new(&data[..]));
+        reader.read_header()?;
+
+        let mut buf = String::new();
+        reader.read_record(&mut buf)?;
+        let checkpoint = reader.checkpoint();
+
+        let mut reader = Reader::resume_at(Cursor::new(&data), &checkpoint)?;
+        buf.clear();
+        reader.read_record(&mut buf)?;
+        assert_eq!(buf, "sq0\t13");
+
+        let data =
+            compress(b"##fileformat=VCFv4.3\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n")?;
+
+        assert!(matches!(
+            Reader::resume_at(Cursor::new(&data), &checkpoint),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_record() -> io::Result<()> {
         let mut reader = Reader::new(DATA);
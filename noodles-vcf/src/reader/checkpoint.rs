@@ -0,0 +1,65 @@
+//! VCF reader checkpoint.
+
+use noodles_bgzf as bgzf;
+
+/// A VCF reader checkpoint.
+///
+/// A checkpoint is the virtual position of a reader and a fingerprint of the header it read. It
+/// can be persisted (e.g., using the `serde` feature) and later passed to
+/// [`super::Reader::resume_at`] to continue reading from the same position, e.g., after a batch
+/// job is preempted.
+///
+/// The header fingerprint is used to check that the reader is resumed on the same file. It is
+/// stable across runs but is not a cryptographic hash.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Checkpoint {
+    virtual_position: u64,
+    header_fingerprint: u64,
+}
+
+impl Checkpoint {
+    /// Creates a VCF reader checkpoint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::reader::Checkpoint;
+    /// use noodles_bgzf as bgzf;
+    /// let checkpoint = Checkpoint::new(bgzf::VirtualPosition::from(8), 13);
+    /// ```
+    pub fn new(virtual_position: bgzf::VirtualPosition, header_fingerprint: u64) -> Self {
+        Self {
+            virtual_position: u64::from(virtual_position),
+            header_fingerprint,
+        }
+    }
+
+    /// Returns the virtual position of the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::reader::Checkpoint;
+    /// use noodles_bgzf as bgzf;
+    /// let checkpoint = Checkpoint::new(bgzf::VirtualPosition::from(8), 13);
+    /// assert_eq!(checkpoint.virtual_position(), bgzf::VirtualPosition::from(8));
+    /// ```
+    pub fn virtual_position(&self) -> bgzf::VirtualPosition {
+        bgzf::VirtualPosition::from(self.virtual_position)
+    }
+
+    /// Returns the fingerprint of the header read by the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::reader::Checkpoint;
+    /// use noodles_bgzf as bgzf;
+    /// let checkpoint = Checkpoint::new(bgzf::VirtualPosition::from(8), 13);
+    /// assert_eq!(checkpoint.header_fingerprint(), 13);
+    /// ```
+    pub fn header_fingerprint(&self) -> u64 {
+        self.header_fingerprint
+    }
+}
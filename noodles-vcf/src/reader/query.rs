@@ -7,7 +7,7 @@ use noodles_bgzf as bgzf;
 use noodles_csi::index::reference_sequence::bin::Chunk;
 
 use super::Reader;
-use crate::{record::Chromosome, Header, Record};
+use crate::{record::Chromosome, Error, Header, Record};
 
 enum State {
     Seek,
@@ -47,12 +47,7 @@ where
     where
         B: RangeBounds<i32>,
     {
-        let (start, end) = match (interval.start_bound(), interval.end_bound()) {
-            (Bound::Unbounded, Bound::Unbounded) => (1, i32::MAX),
-            (Bound::Included(s), Bound::Unbounded) => (*s, i32::MAX),
-            (Bound::Included(s), Bound::Included(e)) => (*s, *e),
-            _ => todo!(),
-        };
+        let (start, end) = resolve_interval(interval);
 
         Self {
             reader,
@@ -86,8 +81,12 @@ where
         match self.reader.read_record(&mut self.line_buf) {
             Ok(0) => None,
             Ok(_) => Some(
-                Record::try_from_str(&self.line_buf, self.header)
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+                Record::try_from_str(&self.line_buf, self.header).map_err(|e| {
+                    io::Error::from(Error::InvalidRecord {
+                        record_number: None,
+                        source: e,
+                    })
+                }),
             ),
             Err(e) => Some(Err(e)),
         }
@@ -127,12 +126,7 @@ where
 
                                 let end = match record.end() {
                                     Ok(pos) => i32::from(pos),
-                                    Err(e) => {
-                                        return Some(Err(io::Error::new(
-                                            io::ErrorKind::InvalidData,
-                                            e,
-                                        )))
-                                    }
+                                    Err(e) => return Some(Err(Error::InvalidRecordEnd(e).into())),
                                 };
 
                                 if reference_sequence_name == self.reference_sequence_name
@@ -154,6 +148,60 @@ where
     }
 }
 
+fn resolve_interval<B>(interval: B) -> (i32, i32)
+where
+    B: RangeBounds<i32>,
+{
+    let start = match interval.start_bound() {
+        Bound::Included(s) => *s,
+        Bound::Excluded(s) => *s + 1,
+        Bound::Unbounded => 1,
+    };
+
+    let end = match interval.end_bound() {
+        Bound::Included(e) => *e,
+        Bound::Excluded(e) => *e - 1,
+        Bound::Unbounded => i32::MAX,
+    };
+
+    (start, end)
+}
+
 fn in_interval(a_start: i32, a_end: i32, b_start: i32, b_end: i32) -> bool {
     a_start <= b_end && b_start <= a_end
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_interval() {
+        assert_eq!(resolve_interval(8..=13), (8, 13));
+        assert_eq!(resolve_interval(8..13), (8, 12));
+        assert_eq!(resolve_interval(8..), (8, i32::MAX));
+        assert_eq!(resolve_interval(..=13), (1, 13));
+        assert_eq!(resolve_interval(..13), (1, 12));
+        assert_eq!(resolve_interval(..), (1, i32::MAX));
+        assert_eq!(
+            resolve_interval((Bound::Excluded(8), Bound::Included(13))),
+            (9, 13)
+        );
+    }
+
+    #[test]
+    fn test_in_interval() {
+        // A record that ends at the start of the query interval.
+        assert!(in_interval(5, 8, 8, 13));
+        // A record that starts at the end of the query interval.
+        assert!(in_interval(13, 21, 8, 13));
+
+        assert!(!in_interval(5, 7, 8, 13));
+        assert!(!in_interval(14, 21, 8, 13));
+
+        // An exclusive end bound does not include the record at that position.
+        let (start, end) = resolve_interval(8..13);
+        assert!(!in_interval(13, 13, start, end));
+        assert!(in_interval(12, 12, start, end));
+    }
+}
@@ -1,7 +1,7 @@
 use std::io::{self, BufRead};
 
 use super::Reader;
-use crate::{Header, Record};
+use crate::{Error, Header, Record};
 
 /// An iterator over records of a VCF reader.
 ///
@@ -10,6 +10,7 @@ pub struct Records<'r, 'h, R> {
     inner: &'r mut Reader<R>,
     header: &'h Header,
     line_buf: String,
+    record_number: u64,
 }
 
 impl<'r, 'h, R> Records<'r, 'h, R>
@@ -21,6 +22,7 @@ where
             inner,
             header,
             line_buf: String::new(),
+            record_number: 0,
         }
     }
 }
@@ -36,10 +38,19 @@ where
 
         match self.inner.read_record(&mut self.line_buf) {
             Ok(0) => None,
-            Ok(_) => Some(
-                Record::try_from_str(&self.line_buf, self.header)
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
-            ),
+            Ok(_) => {
+                let record_number = self.record_number;
+                self.record_number += 1;
+
+                Some(
+                    Record::try_from_str(&self.line_buf, self.header).map_err(|e| {
+                        io::Error::from(Error::InvalidRecord {
+                            record_number: Some(record_number),
+                            source: e,
+                        })
+                    }),
+                )
+            }
             Err(e) => Some(Err(e)),
         }
     }
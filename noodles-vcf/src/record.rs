@@ -34,6 +34,8 @@ pub use self::genotypes::Keys as Format;
 
 use std::{error, fmt, num, str::FromStr};
 
+use self::genotypes::SampleSubset;
+
 use super::Header;
 
 pub(crate) const MISSING_FIELD: &str = ".";
@@ -66,6 +68,39 @@ impl Record {
         parser::parse(s, header)
     }
 
+    /// Parses a raw VCF record, keeping only the genotypes of the given subset of samples.
+    ///
+    /// The genotype columns of samples not in the subset are skipped without being parsed. The
+    /// genotypes of the resulting record are in the order of the subset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, record::genotypes::SampleSubset};
+    ///
+    /// let raw_header = "##fileformat=VCFv4.3\n##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1\tsample2\n";
+    /// let header: vcf::Header = raw_header.parse()?;
+    ///
+    /// let sample_subset = SampleSubset::from_names(header.sample_names(), ["sample1"])?;
+    ///
+    /// let record = vcf::Record::try_from_str_with_sample_subset(
+    ///     "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0|0\t0|1\t1|1",
+    ///     &header,
+    ///     &sample_subset,
+    /// )?;
+    ///
+    /// assert_eq!(record.genotypes().len(), 1);
+    /// assert_eq!(record.genotypes().to_string(), "GT\t0|1");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_str_with_sample_subset(
+        s: &str,
+        header: &Header,
+        sample_subset: &SampleSubset,
+    ) -> Result<Self, ParseError> {
+        parser::parse_with_sample_subset(s, header, Some(sample_subset))
+    }
+
     /// Returns a builder to create a record from each of its fields.
     ///
     /// # Examples
@@ -498,6 +533,30 @@ impl Record {
     pub fn genotypes(&self) -> &Genotypes {
         &self.genotypes
     }
+
+    /// Returns a mutable reference to the genotypes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, record::{genotypes::SampleSubset, Position}};
+    ///
+    /// let mut record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse()?)
+    ///     .set_position(Position::try_from(1)?)
+    ///     .set_reference_bases("A".parse()?)
+    ///     .set_genotypes("GT\t0|0\t0|1".parse()?)
+    ///     .build()?;
+    ///
+    /// let sample_subset = SampleSubset::from_indices(2, [1])?;
+    /// *record.genotypes_mut() = sample_subset.genotypes(record.genotypes());
+    ///
+    /// assert_eq!(record.genotypes().to_string(), "GT\t0|1");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn genotypes_mut(&mut self) -> &mut Genotypes {
+        &mut self.genotypes
+    }
 }
 
 /// An error returned when the end position is invalid.
@@ -598,6 +657,19 @@ impl Record {
 
 impl fmt::Display for Record {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_fixed_fields(f)?;
+
+        if !self.genotypes().is_empty() {
+            write!(f, "\t{}", self.genotypes())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Record {
+    // Formats the fixed fields, i.e., CHROM to INFO.
+    pub(crate) fn fmt_fixed_fields(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
             "{chrom}\t{pos}\t{id}\t{ref}\t{alt}",
@@ -620,13 +692,7 @@ impl fmt::Display for Record {
             write!(f, "\t{}", MISSING_FIELD)?;
         }
 
-        write!(f, "\t{}", self.info())?;
-
-        if !self.genotypes().is_empty() {
-            write!(f, "\t{}", self.genotypes())?;
-        }
-
-        Ok(())
+        write!(f, "\t{}", self.info())
     }
 }
 
@@ -1,8 +1,9 @@
 //! VCF record alternate bases allele and symbol.
 
+pub mod breakend;
 pub mod symbol;
 
-pub use self::symbol::Symbol;
+pub use self::{breakend::Breakend, symbol::Symbol};
 
 use std::{error, fmt, str::FromStr};
 
@@ -16,7 +17,7 @@ pub enum Allele {
     /// A symbolic allele (e.g., `<DEL>`, `<CN:0>`, etc.).
     Symbol(Symbol),
     /// A breakend (e.g., `]sq0:5]A`, `G.`, etc.).
-    Breakend(String),
+    Breakend(Breakend),
     /// An overlapping deletion, i.e., a missing allele (`*`).
     OverlappingDeletion,
 }
@@ -32,7 +33,7 @@ impl fmt::Display for Allele {
                 Ok(())
             }
             Self::Symbol(symbol) => write!(f, "<{}>", symbol),
-            Self::Breakend(breakend) => f.write_str(breakend),
+            Self::Breakend(breakend) => write!(f, "{}", breakend),
             Self::OverlappingDeletion => f.write_str("*"),
         }
     }
@@ -47,6 +48,8 @@ pub enum ParseError {
     InvalidSymbol(symbol::ParseError),
     /// A base is invalid.
     InvalidBase(base::TryFromCharError),
+    /// The breakend is invalid.
+    InvalidBreakend(breakend::ParseError),
 }
 
 impl error::Error for ParseError {}
@@ -57,6 +60,7 @@ impl fmt::Display for ParseError {
             Self::Empty => f.write_str("empty input"),
             Self::InvalidSymbol(e) => write!(f, "invalid symbol: {}", e),
             Self::InvalidBase(e) => write!(f, "invalid base: {}", e),
+            Self::InvalidBreakend(e) => write!(f, "invalid breakend: {}", e),
         }
     }
 }
@@ -75,7 +79,9 @@ impl FromStr for Allele {
                         .map(Self::Symbol)
                         .map_err(ParseError::InvalidSymbol)
                 } else if is_breakend(s) {
-                    Ok(Self::Breakend(s.into()))
+                    s.parse()
+                        .map(Self::Breakend)
+                        .map_err(ParseError::InvalidBreakend)
                 } else {
                     s.chars()
                         .map(|c| c.to_ascii_uppercase())
@@ -116,22 +122,22 @@ mod tests {
         let allele = Allele::Symbol(Symbol::NonstructuralVariant(String::from("CN:0")));
         assert_eq!(allele.to_string(), "<CN:0>");
 
-        let allele = Allele::Breakend(String::from("]sq0:5]A"));
+        let allele = Allele::Breakend("]sq0:5]A".parse().unwrap());
         assert_eq!(allele.to_string(), "]sq0:5]A");
 
-        let allele = Allele::Breakend(String::from("C[sq1:13["));
+        let allele = Allele::Breakend("C[sq1:13[".parse().unwrap());
         assert_eq!(allele.to_string(), "C[sq1:13[");
 
-        let allele = Allele::Breakend(String::from("G."));
+        let allele = Allele::Breakend("G.".parse().unwrap());
         assert_eq!(allele.to_string(), "G.");
 
-        let allele = Allele::Breakend(String::from("CT."));
+        let allele = Allele::Breakend("CT.".parse().unwrap());
         assert_eq!(allele.to_string(), "CT.");
 
-        let allele = Allele::Breakend(String::from(".A"));
+        let allele = Allele::Breakend(".A".parse().unwrap());
         assert_eq!(allele.to_string(), ".A");
 
-        let allele = Allele::Breakend(String::from(".GC"));
+        let allele = Allele::Breakend(".GC".parse().unwrap());
         assert_eq!(allele.to_string(), ".GC");
     }
 
@@ -167,32 +173,32 @@ mod tests {
 
         assert_eq!(
             "]sq0:5]A".parse::<Allele>(),
-            Ok(Allele::Breakend(String::from("]sq0:5]A")))
+            Ok(Allele::Breakend("]sq0:5]A".parse().unwrap()))
         );
 
         assert_eq!(
             "C[sq1:13[".parse::<Allele>(),
-            Ok(Allele::Breakend(String::from("C[sq1:13[")))
+            Ok(Allele::Breakend("C[sq1:13[".parse().unwrap()))
         );
 
         assert_eq!(
             "G.".parse::<Allele>(),
-            Ok(Allele::Breakend(String::from("G.")))
+            Ok(Allele::Breakend("G.".parse().unwrap()))
         );
 
         assert_eq!(
             "CT.".parse::<Allele>(),
-            Ok(Allele::Breakend(String::from("CT.")))
+            Ok(Allele::Breakend("CT.".parse().unwrap()))
         );
 
         assert_eq!(
             ".A".parse::<Allele>(),
-            Ok(Allele::Breakend(String::from(".A")))
+            Ok(Allele::Breakend(".A".parse().unwrap()))
         );
 
         assert_eq!(
             ".GC".parse::<Allele>(),
-            Ok(Allele::Breakend(String::from(".GC")))
+            Ok(Allele::Breakend(".GC".parse().unwrap()))
         );
 
         assert_eq!("".parse::<Allele>(), Err(ParseError::Empty));
@@ -204,5 +210,9 @@ mod tests {
             "Z".parse::<Allele>(),
             Err(ParseError::InvalidBase(_))
         ));
+        assert!(matches!(
+            "G[sq0:5".parse::<Allele>(),
+            Err(ParseError::InvalidBreakend(_))
+        ));
     }
 }
@@ -0,0 +1,458 @@
+//! VCF record alternate bases allele breakend.
+
+use std::{error, fmt, num, str::FromStr};
+
+use crate::record::{
+    chromosome,
+    reference_bases::{base, Base},
+    Chromosome,
+};
+
+const SINGLE_BREAKEND: char = '.';
+const MATE_POSITION_DELIMITER: char = ':';
+
+/// The side of the bases a breakend adjacency is joined to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Join {
+    /// The adjacency is joined before the bases (e.g., `]sq0:5]A`, `.A`).
+    Before,
+    /// The adjacency is joined after the bases (e.g., `A[sq0:5[`, `A.`).
+    After,
+}
+
+/// The direction the joined sequence extends from the mate position.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Extension {
+    /// The joined sequence extends to the left of the mate position (`]`).
+    Left,
+    /// The joined sequence extends to the right of the mate position (`[`).
+    Right,
+}
+
+impl Extension {
+    fn bracket(self) -> char {
+        match self {
+            Self::Left => ']',
+            Self::Right => '[',
+        }
+    }
+}
+
+/// A VCF record alternate bases allele breakend mate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mate {
+    chromosome: Chromosome,
+    position: i32,
+    extension: Extension,
+}
+
+impl Mate {
+    /// Creates a breakend mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::{Extension, Mate},
+    ///     Chromosome,
+    /// };
+    ///
+    /// let mate = Mate::new(Chromosome::Name(String::from("sq0")), 5, Extension::Left);
+    /// ```
+    pub fn new(chromosome: Chromosome, position: i32, extension: Extension) -> Self {
+        Self {
+            chromosome,
+            position,
+            extension,
+        }
+    }
+
+    /// Returns the chromosome of the mate.
+    pub fn chromosome(&self) -> &Chromosome {
+        &self.chromosome
+    }
+
+    /// Returns the position of the mate.
+    pub fn position(&self) -> i32 {
+        self.position
+    }
+
+    /// Returns the direction the joined sequence extends from the mate position.
+    pub fn extension(&self) -> Extension {
+        self.extension
+    }
+}
+
+/// A VCF record alternate bases allele breakend.
+///
+/// A breakend describes a novel adjacency between the bases at the record position and either a
+/// mate position (e.g., `G]sq0:5]`) or an unknown sequence, i.e., a single breakend (e.g., `G.`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Breakend {
+    bases: Vec<Base>,
+    join: Join,
+    mate: Option<Mate>,
+}
+
+impl Breakend {
+    /// Creates a breakend.
+    ///
+    /// A breakend without a mate is a single breakend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::{Breakend, Join},
+    ///     reference_bases::Base,
+    /// };
+    ///
+    /// let breakend = Breakend::new(vec![Base::G], Join::After, None);
+    /// assert_eq!(breakend.to_string(), "G.");
+    /// ```
+    pub fn new(bases: Vec<Base>, join: Join, mate: Option<Mate>) -> Self {
+        Self { bases, join, mate }
+    }
+
+    /// Returns the bases at the breakend.
+    ///
+    /// This is the reference base at the record position, followed or preceded by any inserted
+    /// bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::Breakend,
+    ///     reference_bases::Base,
+    /// };
+    ///
+    /// let breakend: Breakend = "GT[sq1:13[".parse()?;
+    /// assert_eq!(breakend.bases(), [Base::G, Base::T]);
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn bases(&self) -> &[Base] {
+        &self.bases
+    }
+
+    /// Returns the side of the bases the adjacency is joined to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::breakend::{Breakend, Join};
+    ///
+    /// let breakend: Breakend = "]sq0:5]A".parse()?;
+    /// assert_eq!(breakend.join(), Join::Before);
+    ///
+    /// let breakend: Breakend = "A.".parse()?;
+    /// assert_eq!(breakend.join(), Join::After);
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn join(&self) -> Join {
+        self.join
+    }
+
+    /// Returns the mate of the breakend.
+    ///
+    /// This is `None` for a single breakend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::{Breakend, Extension},
+    ///     Chromosome,
+    /// };
+    ///
+    /// let breakend: Breakend = "]sq0:5]A".parse()?;
+    /// let mate = breakend.mate().expect("missing mate");
+    /// assert_eq!(mate.chromosome(), &Chromosome::Name(String::from("sq0")));
+    /// assert_eq!(mate.position(), 5);
+    /// assert_eq!(mate.extension(), Extension::Left);
+    ///
+    /// let breakend: Breakend = "A.".parse()?;
+    /// assert!(breakend.mate().is_none());
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn mate(&self) -> Option<&Mate> {
+        self.mate.as_ref()
+    }
+
+    /// Returns whether this is a single breakend, i.e., it has no mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::breakend::Breakend;
+    /// let breakend: Breakend = ".A".parse()?;
+    /// assert!(breakend.is_single());
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn is_single(&self) -> bool {
+        self.mate.is_none()
+    }
+}
+
+impl fmt::Display for Breakend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let write_bases = |f: &mut fmt::Formatter<'_>| -> fmt::Result {
+            for &base in &self.bases {
+                write!(f, "{}", char::from(base))?;
+            }
+
+            Ok(())
+        };
+
+        let write_mate = |f: &mut fmt::Formatter<'_>| -> fmt::Result {
+            match &self.mate {
+                Some(mate) => {
+                    let bracket = mate.extension.bracket();
+                    write!(
+                        f,
+                        "{}{}{}{}{}",
+                        bracket, mate.chromosome, MATE_POSITION_DELIMITER, mate.position, bracket
+                    )
+                }
+                None => write!(f, "{}", SINGLE_BREAKEND),
+            }
+        };
+
+        match self.join {
+            Join::Before => {
+                write_mate(f)?;
+                write_bases(f)
+            }
+            Join::After => {
+                write_bases(f)?;
+                write_mate(f)
+            }
+        }
+    }
+}
+
+/// An error returned when a raw VCF record alternate bases allele breakend fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid,
+    /// The bases are missing.
+    MissingBases,
+    /// A base is invalid.
+    InvalidBase(base::TryFromCharError),
+    /// The mate chromosome is invalid.
+    InvalidMateChromosome(chromosome::ParseError),
+    /// The mate position is invalid.
+    InvalidMatePosition(num::ParseIntError),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid => f.write_str("invalid input"),
+            Self::MissingBases => f.write_str("missing bases"),
+            Self::InvalidBase(e) => write!(f, "invalid base: {}", e),
+            Self::InvalidMateChromosome(e) => write!(f, "invalid mate chromosome: {}", e),
+            Self::InvalidMatePosition(e) => write!(f, "invalid mate position: {}", e),
+        }
+    }
+}
+
+impl FromStr for Breakend {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        if let Some(t) = s.strip_prefix(SINGLE_BREAKEND) {
+            return parse_bases(t).map(|bases| Self::new(bases, Join::Before, None));
+        } else if let Some(t) = s.strip_suffix(SINGLE_BREAKEND) {
+            return parse_bases(t).map(|bases| Self::new(bases, Join::After, None));
+        }
+
+        let i = s.find(['[', ']']).ok_or(ParseError::Invalid)?;
+        let j = s.rfind(['[', ']']).ok_or(ParseError::Invalid)?;
+
+        if i == j {
+            return Err(ParseError::Invalid);
+        }
+
+        let extension = match (&s[i..=i], &s[j..=j]) {
+            ("]", "]") => Extension::Left,
+            ("[", "[") => Extension::Right,
+            _ => return Err(ParseError::Invalid),
+        };
+
+        let (join, raw_bases) = if i == 0 {
+            (Join::Before, &s[j + 1..])
+        } else if j == s.len() - 1 {
+            (Join::After, &s[..i])
+        } else {
+            return Err(ParseError::Invalid);
+        };
+
+        let bases = parse_bases(raw_bases)?;
+        let mate = parse_mate(&s[i + 1..j], extension)?;
+
+        Ok(Self::new(bases, join, Some(mate)))
+    }
+}
+
+fn parse_bases(s: &str) -> Result<Vec<Base>, ParseError> {
+    if s.is_empty() {
+        return Err(ParseError::MissingBases);
+    }
+
+    s.chars()
+        .map(|c| Base::try_from(c.to_ascii_uppercase()))
+        .collect::<Result<_, _>>()
+        .map_err(ParseError::InvalidBase)
+}
+
+fn parse_mate(s: &str, extension: Extension) -> Result<Mate, ParseError> {
+    let (raw_chromosome, raw_position) = s
+        .rsplit_once(MATE_POSITION_DELIMITER)
+        .ok_or(ParseError::Invalid)?;
+
+    let chromosome = raw_chromosome
+        .parse()
+        .map_err(ParseError::InvalidMateChromosome)?;
+
+    let position = raw_position
+        .parse()
+        .map_err(ParseError::InvalidMatePosition)?;
+
+    Ok(Mate::new(chromosome, position, extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mate(chromosome: &str, position: i32, extension: Extension) -> Option<Mate> {
+        Some(Mate::new(
+            Chromosome::Name(chromosome.into()),
+            position,
+            extension,
+        ))
+    }
+
+    #[test]
+    fn test_fmt() {
+        let breakend = Breakend::new(
+            vec![Base::G],
+            Join::After,
+            mate("sq1", 13, Extension::Right),
+        );
+        assert_eq!(breakend.to_string(), "G[sq1:13[");
+
+        let breakend = Breakend::new(vec![Base::G], Join::After, mate("sq1", 13, Extension::Left));
+        assert_eq!(breakend.to_string(), "G]sq1:13]");
+
+        let breakend = Breakend::new(vec![Base::A], Join::Before, mate("sq0", 5, Extension::Left));
+        assert_eq!(breakend.to_string(), "]sq0:5]A");
+
+        let breakend = Breakend::new(
+            vec![Base::A],
+            Join::Before,
+            mate("sq0", 5, Extension::Right),
+        );
+        assert_eq!(breakend.to_string(), "[sq0:5[A");
+
+        let breakend = Breakend::new(
+            vec![Base::C],
+            Join::After,
+            Some(Mate::new(
+                Chromosome::Symbol(String::from("ctg1")),
+                8,
+                Extension::Right,
+            )),
+        );
+        assert_eq!(breakend.to_string(), "C[<ctg1>:8[");
+
+        let breakend = Breakend::new(vec![Base::C, Base::T], Join::After, None);
+        assert_eq!(breakend.to_string(), "CT.");
+
+        let breakend = Breakend::new(vec![Base::C, Base::T], Join::Before, None);
+        assert_eq!(breakend.to_string(), ".CT");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "G[sq1:13[".parse(),
+            Ok(Breakend::new(
+                vec![Base::G],
+                Join::After,
+                mate("sq1", 13, Extension::Right)
+            ))
+        );
+        assert_eq!(
+            "GT]sq1:13]".parse(),
+            Ok(Breakend::new(
+                vec![Base::G, Base::T],
+                Join::After,
+                mate("sq1", 13, Extension::Left)
+            ))
+        );
+        assert_eq!(
+            "]sq0:5]A".parse(),
+            Ok(Breakend::new(
+                vec![Base::A],
+                Join::Before,
+                mate("sq0", 5, Extension::Left)
+            ))
+        );
+        assert_eq!(
+            "[HLA-A*01:01:1[a".parse(),
+            Ok(Breakend::new(
+                vec![Base::A],
+                Join::Before,
+                mate("HLA-A*01:01", 1, Extension::Right)
+            ))
+        );
+        assert_eq!(
+            "C[<ctg1>:8[".parse(),
+            Ok(Breakend::new(
+                vec![Base::C],
+                Join::After,
+                Some(Mate::new(
+                    Chromosome::Symbol(String::from("ctg1")),
+                    8,
+                    Extension::Right
+                ))
+            ))
+        );
+        assert_eq!(
+            "G.".parse(),
+            Ok(Breakend::new(vec![Base::G], Join::After, None))
+        );
+        assert_eq!(
+            ".CT".parse(),
+            Ok(Breakend::new(vec![Base::C, Base::T], Join::Before, None))
+        );
+
+        assert_eq!("".parse::<Breakend>(), Err(ParseError::Empty));
+        assert_eq!(".".parse::<Breakend>(), Err(ParseError::MissingBases));
+        assert_eq!("[sq0:5[".parse::<Breakend>(), Err(ParseError::MissingBases));
+        assert_eq!("G[sq0:5".parse::<Breakend>(), Err(ParseError::Invalid));
+        assert_eq!("G[sq0:5]".parse::<Breakend>(), Err(ParseError::Invalid));
+        assert_eq!("G[sq0:5[T".parse::<Breakend>(), Err(ParseError::Invalid));
+        assert_eq!("G[sq0[".parse::<Breakend>(), Err(ParseError::Invalid));
+        assert!(matches!(
+            "G[sq0:five[".parse::<Breakend>(),
+            Err(ParseError::InvalidMatePosition(_))
+        ));
+        assert!(matches!(
+            "Z.".parse::<Breakend>(),
+            Err(ParseError::InvalidBase(_))
+        ));
+    }
+}
@@ -2,8 +2,9 @@
 
 pub mod genotype;
 pub mod keys;
+pub mod sample_subset;
 
-pub use self::{genotype::Genotype, keys::Keys};
+pub use self::{genotype::Genotype, keys::Keys, sample_subset::SampleSubset};
 
 use std::{
     error, fmt,
@@ -87,6 +88,8 @@ pub enum ParseError {
     InvalidKeys(keys::ParseError),
     /// A genotype is invalid.
     InvalidGenotype(genotype::ParseError),
+    /// A selected sample is missing.
+    MissingSample(usize),
 }
 
 impl error::Error for ParseError {}
@@ -98,6 +101,7 @@ impl fmt::Display for ParseError {
             Self::Invalid => f.write_str("invalid input"),
             Self::InvalidKeys(e) => write!(f, "invalid keys: {}", e),
             Self::InvalidGenotype(e) => write!(f, "invalid genotype: {}", e),
+            Self::MissingSample(i) => write!(f, "missing sample: {}", i),
         }
     }
 }
@@ -126,6 +130,41 @@ impl FromStr for Genotypes {
     }
 }
 
+impl Genotypes {
+    pub(crate) fn try_from_str_sample_subset(
+        s: &str,
+        sample_subset: &SampleSubset,
+    ) -> Result<Self, ParseError> {
+        use super::FIELD_DELIMITER;
+
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let (format, t) = s.split_once(FIELD_DELIMITER).ok_or(ParseError::Invalid)?;
+
+        let keys = format.parse().map_err(ParseError::InvalidKeys)?;
+
+        // Only the selected columns are parsed. The others are only split.
+        let raw_genotypes: Vec<_> = t.split(FIELD_DELIMITER).collect();
+
+        let genotypes = sample_subset
+            .indices()
+            .iter()
+            .map(|&i| {
+                raw_genotypes
+                    .get(i)
+                    .ok_or(ParseError::MissingSample(i))
+                    .and_then(|t| {
+                        Genotype::from_str_format(t, &keys).map_err(ParseError::InvalidGenotype)
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::new(keys, genotypes))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,6 +213,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_try_from_str_sample_subset() -> Result<(), Box<dyn std::error::Error>> {
+        let sample_subset = SampleSubset::from_indices(3, [2, 0])?;
+
+        let actual = Genotypes::try_from_str_sample_subset("GT\t0|0\t\t1|1", &sample_subset)?;
+        let expected: Genotypes = "GT\t1|1\t0|0".parse()?;
+        assert_eq!(actual, expected);
+
+        assert_eq!(
+            Genotypes::try_from_str_sample_subset("GT\t0|0", &sample_subset),
+            Err(ParseError::MissingSample(2))
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_from_str() -> Result<(), Box<dyn std::error::Error>> {
         use genotype::{
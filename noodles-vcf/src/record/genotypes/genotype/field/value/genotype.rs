@@ -14,6 +14,150 @@ use std::{
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Genotype(Vec<Allele>);
 
+impl Genotype {
+    /// Returns the ploidy of the genotype, i.e., the number of alleles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotypes::genotype::field::value::Genotype;
+    ///
+    /// let genotype: Genotype = "0/1".parse()?;
+    /// assert_eq!(genotype.ploidy(), 2);
+    ///
+    /// let genotype: Genotype = "1".parse()?;
+    /// assert_eq!(genotype.ploidy(), 1);
+    /// # Ok::<_, noodles_vcf::record::genotypes::genotype::field::value::genotype::ParseError>(())
+    /// ```
+    pub fn ploidy(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the genotype is phased.
+    ///
+    /// A genotype is phased when all of its allele separators are phased (`|`). A haploid
+    /// genotype is considered phased.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotypes::genotype::field::value::Genotype;
+    ///
+    /// let genotype: Genotype = "0|1".parse()?;
+    /// assert!(genotype.is_phased());
+    ///
+    /// let genotype: Genotype = "0|1/2".parse()?;
+    /// assert!(!genotype.is_phased());
+    /// # Ok::<_, noodles_vcf::record::genotypes::genotype::field::value::genotype::ParseError>(())
+    /// ```
+    pub fn is_phased(&self) -> bool {
+        self.0
+            .iter()
+            .skip(1)
+            .all(|allele| allele.phasing() == Some(allele::Phasing::Phased))
+    }
+
+    /// Returns whether all alleles in the genotype are missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotypes::genotype::field::value::Genotype;
+    ///
+    /// let genotype: Genotype = "./.".parse()?;
+    /// assert!(genotype.is_missing());
+    ///
+    /// let genotype: Genotype = "0/.".parse()?;
+    /// assert!(!genotype.is_missing());
+    /// # Ok::<_, noodles_vcf::record::genotypes::genotype::field::value::genotype::ParseError>(())
+    /// ```
+    pub fn is_missing(&self) -> bool {
+        self.0.iter().all(|allele| allele.position().is_none())
+    }
+
+    /// Returns whether the genotype is homozygous for the reference allele.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotypes::genotype::field::value::Genotype;
+    ///
+    /// let genotype: Genotype = "0/0".parse()?;
+    /// assert!(genotype.is_hom_ref());
+    ///
+    /// let genotype: Genotype = "0/.".parse()?;
+    /// assert!(!genotype.is_hom_ref());
+    /// # Ok::<_, noodles_vcf::record::genotypes::genotype::field::value::genotype::ParseError>(())
+    /// ```
+    pub fn is_hom_ref(&self) -> bool {
+        self.0.iter().all(|allele| allele.position() == Some(0))
+    }
+
+    /// Returns whether the genotype is homozygous for an alternate allele.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotypes::genotype::field::value::Genotype;
+    ///
+    /// let genotype: Genotype = "1|1".parse()?;
+    /// assert!(genotype.is_hom_alt());
+    ///
+    /// let genotype: Genotype = "1/2".parse()?;
+    /// assert!(!genotype.is_hom_alt());
+    /// # Ok::<_, noodles_vcf::record::genotypes::genotype::field::value::genotype::ParseError>(())
+    /// ```
+    pub fn is_hom_alt(&self) -> bool {
+        match self.0.first().and_then(|allele| allele.position()) {
+            Some(0) | None => false,
+            Some(position) => self
+                .0
+                .iter()
+                .all(|allele| allele.position() == Some(position)),
+        }
+    }
+
+    /// Returns whether the genotype is heterozygous.
+    ///
+    /// A genotype is heterozygous when all of its alleles are called and at least two differ. A
+    /// genotype with any missing allele is not considered heterozygous.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotypes::genotype::field::value::Genotype;
+    ///
+    /// let genotype: Genotype = "0/1".parse()?;
+    /// assert!(genotype.is_het());
+    ///
+    /// let genotype: Genotype = "1/1".parse()?;
+    /// assert!(!genotype.is_het());
+    ///
+    /// let genotype: Genotype = "1/.".parse()?;
+    /// assert!(!genotype.is_het());
+    /// # Ok::<_, noodles_vcf::record::genotypes::genotype::field::value::genotype::ParseError>(())
+    /// ```
+    pub fn is_het(&self) -> bool {
+        let mut positions = self.0.iter().map(|allele| allele.position());
+
+        match positions.next() {
+            Some(Some(first)) => {
+                let mut is_het = false;
+
+                for position in positions {
+                    match position {
+                        Some(p) => is_het |= p != first,
+                        None => return false,
+                    }
+                }
+
+                is_het
+            }
+            _ => false,
+        }
+    }
+}
+
 impl Deref for Genotype {
     type Target = [Allele];
 
@@ -28,6 +172,16 @@ impl DerefMut for Genotype {
     }
 }
 
+impl fmt::Display for Genotype {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for allele in &self.0 {
+            write!(f, "{}", allele)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// An error returned when a raw VCF record genotype value fails to parse.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParseError {
@@ -109,6 +263,50 @@ impl fmt::Display for TryFromAllelesError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fmt() -> Result<(), ParseError> {
+        for s in ["0", "0/1", "0|1", "./.", "0/1|2", ".|1"] {
+            let genotype: Genotype = s.parse()?;
+            assert_eq!(genotype.to_string(), s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_zygosity() -> Result<(), ParseError> {
+        let genotype: Genotype = "0/0".parse()?;
+        assert!(genotype.is_hom_ref());
+        assert!(!genotype.is_hom_alt());
+        assert!(!genotype.is_het());
+
+        let genotype: Genotype = "0|1".parse()?;
+        assert!(!genotype.is_hom_ref());
+        assert!(!genotype.is_hom_alt());
+        assert!(genotype.is_het());
+
+        let genotype: Genotype = "2/2/2".parse()?;
+        assert!(!genotype.is_hom_ref());
+        assert!(genotype.is_hom_alt());
+        assert!(!genotype.is_het());
+
+        let genotype: Genotype = "1/2".parse()?;
+        assert!(!genotype.is_hom_alt());
+        assert!(genotype.is_het());
+
+        let genotype: Genotype = "./.".parse()?;
+        assert!(genotype.is_missing());
+        assert!(!genotype.is_hom_ref());
+        assert!(!genotype.is_hom_alt());
+        assert!(!genotype.is_het());
+
+        let genotype: Genotype = "./1".parse()?;
+        assert!(!genotype.is_missing());
+        assert!(!genotype.is_het());
+
+        Ok(())
+    }
+
     #[test]
     fn test_from_str() {
         use allele::Phasing;
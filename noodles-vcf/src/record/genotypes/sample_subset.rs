@@ -0,0 +1,268 @@
+//! VCF record genotypes sample subset.
+
+use std::{collections::HashSet, error, fmt};
+
+use crate::{header::SampleNames, Header};
+
+use super::Genotypes;
+
+/// A subset of samples.
+///
+/// A sample subset is a list of sample indices into the sample names of a VCF header. It is used
+/// to restrict the genotypes of records to only the selected samples, in the order given.
+///
+/// When parsing a record with a sample subset (see [`crate::Record::try_from_str_with_sample_subset`]),
+/// the genotype columns of excluded samples are skipped without being parsed.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, record::genotypes::SampleSubset};
+///
+/// let header = vcf::Header::builder()
+///     .add_sample_name("sample0")
+///     .add_sample_name("sample1")
+///     .add_sample_name("sample2")
+///     .build();
+///
+/// let subset = SampleSubset::from_names(header.sample_names(), ["sample2", "sample0"])?;
+/// assert_eq!(subset.indices(), [2, 0]);
+/// # Ok::<_, vcf::record::genotypes::sample_subset::Error>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SampleSubset {
+    indices: Vec<usize>,
+}
+
+impl SampleSubset {
+    /// Creates a sample subset from a list of sample names.
+    ///
+    /// Each name must exist in the given sample names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     record::genotypes::{sample_subset, SampleSubset},
+    /// };
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let subset = SampleSubset::from_names(header.sample_names(), ["sample1"])?;
+    /// assert_eq!(subset.indices(), [1]);
+    ///
+    /// assert_eq!(
+    ///     SampleSubset::from_names(header.sample_names(), ["sample2"]),
+    ///     Err(sample_subset::Error::MissingSampleName(String::from("sample2")))
+    /// );
+    /// # Ok::<_, sample_subset::Error>(())
+    /// ```
+    pub fn from_names<I, N>(sample_names: &SampleNames, names: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = N>,
+        N: AsRef<str>,
+    {
+        let indices = names
+            .into_iter()
+            .map(|name| {
+                let name = name.as_ref();
+
+                sample_names
+                    .get_index_of(name)
+                    .ok_or_else(|| Error::MissingSampleName(name.into()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Self::try_from_indices(indices)
+    }
+
+    /// Creates a sample subset from a list of sample indices.
+    ///
+    /// Each index must be less than the given sample count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotypes::{sample_subset, SampleSubset};
+    ///
+    /// let subset = SampleSubset::from_indices(3, [0, 2])?;
+    /// assert_eq!(subset.indices(), [0, 2]);
+    ///
+    /// assert_eq!(
+    ///     SampleSubset::from_indices(3, [3]),
+    ///     Err(sample_subset::Error::InvalidIndex(3))
+    /// );
+    /// # Ok::<_, sample_subset::Error>(())
+    /// ```
+    pub fn from_indices<I>(sample_count: usize, indices: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let indices: Vec<_> = indices.into_iter().collect();
+
+        if let Some(&i) = indices.iter().find(|&&i| i >= sample_count) {
+            return Err(Error::InvalidIndex(i));
+        }
+
+        Self::try_from_indices(indices)
+    }
+
+    fn try_from_indices(indices: Vec<usize>) -> Result<Self, Error> {
+        let mut seen = HashSet::with_capacity(indices.len());
+
+        if let Some(&i) = indices.iter().find(|&&i| !seen.insert(i)) {
+            return Err(Error::DuplicateIndex(i));
+        }
+
+        Ok(Self { indices })
+    }
+
+    /// Returns the selected sample indices.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Returns the number of selected samples.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns whether no samples are selected.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns a copy of the given header with only the selected sample names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, record::genotypes::SampleSubset};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let subset = SampleSubset::from_indices(2, [1])?;
+    /// let subset_header = subset.header(&header);
+    ///
+    /// assert_eq!(subset_header.sample_names().len(), 1);
+    /// assert!(subset_header.sample_names().contains("sample1"));
+    /// # Ok::<_, vcf::record::genotypes::sample_subset::Error>(())
+    /// ```
+    pub fn header(&self, header: &Header) -> Header {
+        let sample_names = self
+            .indices
+            .iter()
+            .filter_map(|&i| header.sample_names().get_index(i))
+            .cloned()
+            .collect();
+
+        let mut subset_header = header.clone();
+        *subset_header.sample_names_mut() = sample_names;
+        subset_header
+    }
+
+    /// Returns a copy of the given genotypes with only the selected samples.
+    ///
+    /// Indices that are out of bounds of the given genotypes are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{genotypes::SampleSubset, Genotypes};
+    ///
+    /// let genotypes: Genotypes = "GT\t0|0\t0|1\t1|1".parse()?;
+    /// let subset = SampleSubset::from_indices(3, [2, 0])?;
+    ///
+    /// assert_eq!(subset.genotypes(&genotypes).to_string(), "GT\t1|1\t0|0");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn genotypes(&self, genotypes: &Genotypes) -> Genotypes {
+        let selected_genotypes = self
+            .indices
+            .iter()
+            .filter_map(|&i| genotypes.get(i))
+            .cloned()
+            .collect();
+
+        Genotypes::new(genotypes.keys().clone(), selected_genotypes)
+    }
+}
+
+/// An error returned when a sample subset fails to be created.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// A sample name is not in the sample names.
+    MissingSampleName(String),
+    /// A sample index is out of bounds.
+    InvalidIndex(usize),
+    /// A sample is selected more than once.
+    DuplicateIndex(usize),
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSampleName(name) => write!(f, "missing sample name: {}", name),
+            Self::InvalidIndex(i) => write!(f, "invalid index: {}", i),
+            Self::DuplicateIndex(i) => write!(f, "duplicate index: {}", i),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_names() {
+        let sample_names: SampleNames = [
+            String::from("sample0"),
+            String::from("sample1"),
+            String::from("sample2"),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            SampleSubset::from_names(&sample_names, ["sample2", "sample0"]),
+            Ok(SampleSubset {
+                indices: vec![2, 0]
+            })
+        );
+
+        assert_eq!(
+            SampleSubset::from_names(&sample_names, ["sample0", "sample0"]),
+            Err(Error::DuplicateIndex(0))
+        );
+
+        assert_eq!(
+            SampleSubset::from_names(&sample_names, ["sample3"]),
+            Err(Error::MissingSampleName(String::from("sample3")))
+        );
+    }
+
+    #[test]
+    fn test_from_indices() {
+        assert_eq!(
+            SampleSubset::from_indices(3, [1]),
+            Ok(SampleSubset { indices: vec![1] })
+        );
+        assert_eq!(
+            SampleSubset::from_indices(3, [1, 1]),
+            Err(Error::DuplicateIndex(1))
+        );
+        assert_eq!(
+            SampleSubset::from_indices(3, [5]),
+            Err(Error::InvalidIndex(5))
+        );
+    }
+}
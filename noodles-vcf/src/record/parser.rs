@@ -1,9 +1,10 @@
 use std::{error, fmt};
 
 use super::{
-    alternate_bases, chromosome, filters, genotypes, ids, info, position, quality_score,
-    reference_bases, Field, Filters, Genotypes, Info, QualityScore, Record, FIELD_DELIMITER,
-    MISSING_FIELD,
+    alternate_bases, chromosome, filters,
+    genotypes::{self, SampleSubset},
+    ids, info, position, quality_score, reference_bases, Field, Filters, Genotypes, Info,
+    QualityScore, Record, FIELD_DELIMITER, MISSING_FIELD,
 };
 use crate::Header;
 
@@ -52,6 +53,14 @@ impl fmt::Display for ParseError {
 }
 
 pub fn parse(s: &str, header: &Header) -> Result<Record, ParseError> {
+    parse_with_sample_subset(s, header, None)
+}
+
+pub fn parse_with_sample_subset(
+    s: &str,
+    header: &Header,
+    sample_subset: Option<&SampleSubset>,
+) -> Result<Record, ParseError> {
     const MAX_FIELDS: usize = 9;
 
     let mut fields = s.splitn(MAX_FIELDS, FIELD_DELIMITER);
@@ -78,7 +87,11 @@ pub fn parse(s: &str, header: &Header) -> Result<Record, ParseError> {
         .and_then(|s| Info::try_from_str(s, header.infos()).map_err(ParseError::InvalidInfo))?;
 
     let genotypes = if let Some(s) = fields.next() {
-        s.parse().map_err(ParseError::InvalidGenotypes)?
+        match sample_subset {
+            Some(sample_subset) => Genotypes::try_from_str_sample_subset(s, sample_subset),
+            None => s.parse(),
+        }
+        .map_err(ParseError::InvalidGenotypes)?
     } else {
         Genotypes::default()
     };
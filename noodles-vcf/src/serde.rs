@@ -0,0 +1,218 @@
+//! Serde implementations for the VCF header and records.
+//!
+//! A header is serialized as its raw VCF header text. A record is serialized as a struct with one
+//! field per VCF column, using the lowercase column names of the VCF specification (e.g., `chrom`,
+//! `pos`, `id`). Missing quality scores, filters, and formats are represented as `null`.
+//! Deserialization parses the fields as a VCF record, so the same validation as
+//! [`std::str::FromStr`] applies.
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{record::MISSING_FIELD, Header, Record};
+
+impl Serialize for Header {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Header {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename = "Record")]
+struct RawRecord {
+    chrom: String,
+    pos: i32,
+    id: String,
+    #[serde(rename = "ref")]
+    reference_bases: String,
+    alt: String,
+    qual: Option<f32>,
+    filter: Option<String>,
+    info: String,
+    format: Option<String>,
+    samples: Vec<String>,
+}
+
+impl From<&Record> for RawRecord {
+    fn from(record: &Record) -> Self {
+        let (format, samples) = if record.genotypes().is_empty() {
+            (None, Vec::new())
+        } else {
+            let genotypes = record.genotypes().to_string();
+            let mut fields = genotypes.split('\t').map(String::from);
+            (fields.next(), fields.collect())
+        };
+
+        Self {
+            chrom: record.chromosome().to_string(),
+            pos: i32::from(record.position()),
+            id: record.ids().to_string(),
+            reference_bases: record.reference_bases().to_string(),
+            alt: record.alternate_bases().to_string(),
+            qual: record.quality_score().map(f32::from),
+            filter: record.filters().map(|filters| filters.to_string()),
+            info: record.info().to_string(),
+            format,
+            samples,
+        }
+    }
+}
+
+impl RawRecord {
+    fn to_line(&self) -> String {
+        let mut fields = vec![
+            self.chrom.clone(),
+            self.pos.to_string(),
+            self.id.clone(),
+            self.reference_bases.clone(),
+            self.alt.clone(),
+            self.qual
+                .map(|qual| qual.to_string())
+                .unwrap_or_else(|| MISSING_FIELD.into()),
+            self.filter.clone().unwrap_or_else(|| MISSING_FIELD.into()),
+            self.info.clone(),
+        ];
+
+        if let Some(format) = &self.format {
+            fields.push(format.clone());
+            fields.extend(self.samples.iter().cloned());
+        }
+
+        fields.join("\t")
+    }
+}
+
+impl Serialize for Record {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        RawRecord::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Record {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw_record = RawRecord::deserialize(deserializer)?;
+        raw_record.to_line().parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_tokens, Token};
+
+    use super::*;
+
+    #[test]
+    fn test_header() -> Result<(), Box<dyn std::error::Error>> {
+        let header: Header =
+            "##fileformat=VCFv4.3\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n".parse()?;
+
+        assert_tokens(
+            &header,
+            &[Token::Str(
+                "##fileformat=VCFv4.3\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n",
+            )],
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record = "sq0\t1\trs1\tA\tG\t13\tPASS\tNS=1\tGT:GQ\t0|1:13".parse()?;
+
+        assert_tokens(
+            &record,
+            &[
+                Token::Struct {
+                    name: "Record",
+                    len: 10,
+                },
+                Token::Str("chrom"),
+                Token::Str("sq0"),
+                Token::Str("pos"),
+                Token::I32(1),
+                Token::Str("id"),
+                Token::Str("rs1"),
+                Token::Str("ref"),
+                Token::Str("A"),
+                Token::Str("alt"),
+                Token::Str("G"),
+                Token::Str("qual"),
+                Token::Some,
+                Token::F32(13.0),
+                Token::Str("filter"),
+                Token::Some,
+                Token::Str("PASS"),
+                Token::Str("info"),
+                Token::Str("NS=1"),
+                Token::Str("format"),
+                Token::Some,
+                Token::Str("GT:GQ"),
+                Token::Str("samples"),
+                Token::Seq { len: Some(1) },
+                Token::Str("0|1:13"),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_with_missing_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record = "sq0\t1\t.\tA\t.\t.\t.\t.".parse()?;
+
+        assert_tokens(
+            &record,
+            &[
+                Token::Struct {
+                    name: "Record",
+                    len: 10,
+                },
+                Token::Str("chrom"),
+                Token::Str("sq0"),
+                Token::Str("pos"),
+                Token::I32(1),
+                Token::Str("id"),
+                Token::Str("."),
+                Token::Str("ref"),
+                Token::Str("A"),
+                Token::Str("alt"),
+                Token::Str("."),
+                Token::Str("qual"),
+                Token::None,
+                Token::Str("filter"),
+                Token::None,
+                Token::Str("info"),
+                Token::Str("."),
+                Token::Str("format"),
+                Token::None,
+                Token::Str("samples"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+
+        Ok(())
+    }
+}
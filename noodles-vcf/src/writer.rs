@@ -1,6 +1,9 @@
-use std::io::{self, Write};
+use std::{
+    fmt,
+    io::{self, Write},
+};
 
-use super::{Header, Record};
+use super::{record::genotypes::SampleSubset, Header, Record};
 
 /// A VCF writer.
 ///
@@ -133,6 +136,77 @@ where
     pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
         writeln!(self.inner, "{}", record)
     }
+
+    /// Writes a VCF record with only the genotypes of the given subset of samples.
+    ///
+    /// The header written should be the one given by [`SampleSubset::header`]. Samples in the
+    /// subset that are missing from the record are written as missing values (`.`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, record::{genotypes::SampleSubset, Position}};
+    ///
+    /// let record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse()?)
+    ///     .set_position(Position::try_from(1)?)
+    ///     .set_reference_bases("A".parse()?)
+    ///     .set_genotypes("GT\t0|0\t0|1".parse()?)
+    ///     .build()?;
+    ///
+    /// let sample_subset = SampleSubset::from_indices(2, [1])?;
+    ///
+    /// let mut writer = vcf::Writer::new(Vec::new());
+    /// writer.write_record_with_sample_subset(&record, &sample_subset)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"sq0\t1\t.\tA\t.\t.\t.\t.\tGT\t0|1\n");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_record_with_sample_subset(
+        &mut self,
+        record: &Record,
+        sample_subset: &SampleSubset,
+    ) -> io::Result<()> {
+        writeln!(
+            self.inner,
+            "{}",
+            RecordWithSampleSubset {
+                record,
+                sample_subset
+            }
+        )
+    }
+}
+
+// A record formatted with only the genotypes of a subset of samples.
+struct RecordWithSampleSubset<'a> {
+    record: &'a Record,
+    sample_subset: &'a SampleSubset,
+}
+
+impl fmt::Display for RecordWithSampleSubset<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.record.fmt_fixed_fields(f)?;
+
+        let genotypes = self.record.genotypes();
+        let indices = self.sample_subset.indices();
+
+        if genotypes.is_empty() || indices.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, "\t{}", genotypes.keys())?;
+
+        // Samples missing from the record are written as missing values.
+        for &i in indices {
+            match genotypes.get(i) {
+                Some(genotype) => write!(f, "\t{}", genotype)?,
+                None => f.write_str("\t.")?,
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -201,4 +275,33 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_record_with_sample_subset() -> Result<(), Box<dyn std::error::Error>> {
+        let record = Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(Position::try_from(1)?)
+            .set_reference_bases("A".parse()?)
+            .set_genotypes("GT:GQ\t0|0:13\t0|1:8\t1|1:21".parse()?)
+            .build()?;
+
+        let mut writer = Writer::new(Vec::new());
+        let sample_subset = SampleSubset::from_indices(3, [2, 0])?;
+        writer.write_record_with_sample_subset(&record, &sample_subset)?;
+        let expected = b"sq0\t1\t.\tA\t.\t.\t.\t.\tGT:GQ\t1|1:21\t0|0:13\n";
+        assert_eq!(writer.get_ref(), expected);
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_record_with_sample_subset(&record, &SampleSubset::default())?;
+        let expected = b"sq0\t1\t.\tA\t.\t.\t.\t.\n";
+        assert_eq!(writer.get_ref(), expected);
+
+        let mut writer = Writer::new(Vec::new());
+        let sample_subset = SampleSubset::from_indices(4, [3, 1])?;
+        writer.write_record_with_sample_subset(&record, &sample_subset)?;
+        let expected = b"sq0\t1\t.\tA\t.\t.\t.\t.\tGT:GQ\t.\t0|1:8\n";
+        assert_eq!(writer.get_ref(), expected);
+
+        Ok(())
+    }
 }
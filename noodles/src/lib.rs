@@ -16,14 +16,26 @@ pub use noodles_bcf as bcf;
 #[doc(inline)]
 pub use noodles_bgzf as bgzf;
 
+#[cfg(feature = "bigwig")]
+#[doc(inline)]
+pub use noodles_bigwig as bigwig;
+
 #[cfg(feature = "core")]
 #[doc(inline)]
 pub use noodles_core as core;
 
+#[cfg(feature = "convert")]
+#[doc(inline)]
+pub use noodles_util::alignment::convert;
+
 #[cfg(feature = "cram")]
 #[doc(inline)]
 pub use noodles_cram as cram;
 
+#[cfg(feature = "crypt4gh")]
+#[doc(inline)]
+pub use noodles_crypt4gh as crypt4gh;
+
 #[cfg(feature = "csi")]
 #[doc(inline)]
 pub use noodles_csi as csi;
@@ -44,6 +56,18 @@ pub use noodles_gff as gff;
 #[doc(inline)]
 pub use noodles_gtf as gtf;
 
+#[cfg(feature = "htsget")]
+#[doc(inline)]
+pub use noodles_htsget as htsget;
+
+#[cfg(feature = "interval_list")]
+#[doc(inline)]
+pub use noodles_interval_list as interval_list;
+
+#[cfg(feature = "refget")]
+#[doc(inline)]
+pub use noodles_refget as refget;
+
 #[cfg(feature = "sam")]
 #[doc(inline)]
 pub use noodles_sam as sam;
@@ -52,6 +76,10 @@ pub use noodles_sam as sam;
 #[doc(inline)]
 pub use noodles_tabix as tabix;
 
+#[cfg(feature = "util")]
+#[doc(inline)]
+pub use noodles_util as util;
+
 #[cfg(feature = "vcf")]
 #[doc(inline)]
 pub use noodles_vcf as vcf;